@@ -0,0 +1,841 @@
+# Page header
+header-title = JSCalendar- und JSContact-Konvertierung
+header-subtitle = Konvertierung in beide Richtungen zwischen JSCalendar/iCalendar und JSContact/vCard.
+header-history = Verlauf
+header-history-title = Zuletzt konvertierte Eingaben
+header-tour = Rundgang starten
+header-tour-title = Erkennung, Konvertierung, Rückkonvertierung und Expansion an einem Beispiel durchgehen
+header-split-view = Geteilte Ansicht
+header-stacked-view = Gestapelte Ansicht
+header-install = App installieren
+header-install-title = Den Konverter als App installieren, die auch offline funktioniert
+theme-title = Farbschema
+theme-system = Systemschema
+theme-light = Hell
+theme-dark = Dunkel
+language-title = Sprache
+language-browser = Browsersprache
+
+# Editor
+editor-placeholder = Füge hier eine iCalendar-, JSCalendar-, vCard-, JSContact-, jCal- oder jCard-Datei ein oder lege sie hier ab, einen CSV-Export von Kontakten oder eine E-Mail mit angehängter Einladung oder vCard. Oder klicke auf die Funken, um ein Beispiel auszuprobieren.
+editor-drop = Lege eine .ics-, .vcf- oder .json-Datei ab, um sie zu laden
+editor-reading = { $name } wird gelesen… { $percent } %
+editor-undo = Rückgängig (Strg+Z)
+editor-redo = Wiederholen (Strg+Umschalt+Z)
+editor-sample = Zufälliges Beispiel laden (Strg+K)
+editor-open = Datei öffnen
+error-show = { $location } im Editor anzeigen
+error-show-title = Den Fehler im Editor markieren
+error-report-bug = Diesen Fehler melden
+error-report-bug-title = Ein GitHub-Issue mit Version, Format und Eingabe öffnen, E-Mail-Adressen und Binärdaten ersetzt
+error-report-bug-copied = Eingabe in die Zwischenablage kopiert, füge sie in das Issue ein.
+error-report-bug-copy-failed = Kopieren der Eingabe in die Zwischenablage fehlgeschlagen, hänge sie stattdessen an das Issue an.
+conversion-progress = { $done } Einträge konvertiert, { $percent } % der Quelle…
+conversion-cancel = Abbrechen
+conversion-cancelled = Konvertierung abgebrochen. Eine Änderung der Quelle startet sie erneut.
+conversion-all-failed = Keiner der { $count } Einträge konnte konvertiert werden.
+source-line = Zeile { $line }
+
+# Conversion results
+results-title = Konvertierungsergebnisse
+results-converted = So sieht dein { $source } im Format { $target } aus:
+results-roundtrip = Und so würde es zurück nach { $source } konvertiert aussehen:
+output-format = Ausgabeformat
+output-jmap-request = JMAP-Anfrage
+output-explain = Konvertierung erklären
+output-explain-title = Jedes Feld mit der Quelleigenschaft versehen, aus der es stammt
+output-download = Herunterladen
+output-jmap-failed = Die Ausgabe kann nicht als JMAP-Anfrage gesendet werden: { $error }
+footer-report-bug = Fehler melden
+footer-view-source = Quellcode ansehen
+
+# Calendar expansion
+expansion-title = Ergebnisse der Kalenderexpansion
+expansion-from-start = Ab dem ersten Vorkommen
+expansion-from-now = Ab jetzt
+expansion-between = Zwischen zwei Daten
+expansion-count-title = Höchstzahl der Vorkommen
+expansion-budget-title = Zeitbudget für die Expansion in Millisekunden
+expansion-download-ics = .ics herunterladen
+expansion-download-csv = .csv herunterladen
+expansion-range-from = Von
+expansion-range-to = bis
+expansion-summary-start = Dies sind die ersten { $count } Vorkommen des eingefügten Termins:
+expansion-summary-now = Dies sind die nächsten { $count } Vorkommen des eingefügten Termins:
+expansion-summary-range = Dies sind die { $count } Vorkommen des eingefügten Termins zwischen den gewählten Daten:
+expansion-elapsed = In { $elapsed } ms expandiert.
+expansion-elapsed-budget = In { $elapsed } ms expandiert, durch das Budget von { $budget } ms beendet.
+expansion-copy-json = Als JSON kopieren
+expansion-out-of-budget = Die Erweiterung wurde nach { $elapsed } ms mit { $count } Vorkommen angehalten, spätere fehlen möglicherweise.
+expansion-limit-reached = Nur die ersten { $count } Vorkommen wurden erweitert.
+expansion-none-start = Der Kalender enthält Termine, Aufgaben oder Journaleinträge, aber die Erweiterung hat keine Vorkommen ergeben.
+expansion-none-now = Der Kalender hat keine bevorstehenden Vorkommen.
+expansion-none-range = Der Kalender hat keine Vorkommen zwischen diesen Daten.
+occurrence-type = Art
+occurrence-from = Beginn
+occurrence-to = Ende
+occurrence-notes = Hinweise
+occurrence-due = Fällig
+occurrence-floating = Ohne Zeitzone
+occurrence-added = Durch RDATE hinzugefügt
+occurrence-modified = Geändert
+occurrence-modified-summary = Geändert: { $summary }
+occurrence-modified-title = Durch eine RECURRENCE-ID überschrieben
+occurrence-removed = Durch EXDATE entfernt
+occurrence-outside-hours = Außerhalb der Arbeitszeit
+occurrence-outside-hours-finding = Das Vorkommen liegt außerhalb der Arbeitszeit.
+occurrence-on-holiday-finding = Das Vorkommen fällt auf { $holiday }.
+date-zone-title = Zeitzone der Daten
+date-zone-display = In der Zeitzone der Erweiterung
+date-zone-event = In der Zeitzone des Termins
+date-display-title = Datumsformat
+date-display-locale = Lokalisierte Daten
+date-display-iso8601 = ISO 8601
+date-display-rfc3339 = RFC 3339
+date-display-epoch = Unix-Zeit
+entry-kind-event = Termin
+entry-kind-task = Aufgabe
+entry-kind-journal = Journaleintrag
+
+# Compatibility check
+compatibility-title = Kompatibilitätsprüfung
+compatibility-title-warnings = Kompatibilitätsprüfung, { $count ->
+    [one] 1 Warnung
+   *[other] { $count } Warnungen
+}
+compatibility-description = Sucht nach Konstrukten, die gängige Programme beim Import des iCalendar oder der vCard bekanntermaßen verwerfen oder falsch lesen, etwa Zeitzonen ohne Definition, nicht unterstützte Wiederholungsregeln und Eigenschaften von vCard 4.0.
+compatibility-no-problems = Keine bekannten Probleme
+compatibility-warnings = { $count ->
+    [one] 1 Warnung
+   *[other] { $count } Warnungen
+}
+
+# Anonymization
+anonymize-title = Zum Teilen anonymisieren
+anonymize-description = Ersetzt Namen, E-Mail-Adressen, Telefonnummern, Beschreibungen und Anhänge durch Platzhalter und behält Struktur, Wiederholungsregeln und Zeitzonen bei, damit die Eingabe in einem öffentlichen Fehlerbericht geteilt werden kann. Derselbe Wert erhält immer denselben Platzhalter.
+anonymize-run = Anonymisieren
+anonymize-nothing = Nichts zu anonymisieren.
+anonymize-replaced = { $count ->
+    [one] 1 Wert ersetzt, Rückgängig stellt ihn wieder her.
+   *[other] { $count } Werte ersetzt, Rückgängig stellt sie wieder her.
+}
+anonymize-failed = Die Eingabe konnte nicht anonymisiert werden: { $error }
+
+# Duplicate contacts
+dedup-title = Mögliche Duplikate
+dedup-summary = { $groups ->
+    [one] 1 Gruppe
+   *[other] { $groups } Gruppen
+} unter { $cards } Karten teilen eine E-Mail-Adresse, eine Telefonnummer oder einen Namen.
+dedup-merge-all = Alle Gruppen zusammenführen
+dedup-same = Gemeinsam: { $reasons }
+dedup-merged = Zusammengeführte Karte
+
+# JSON diagnostics
+json-ignored-count = { $count ->
+    [one] 1 Eigenschaft ignoriert
+   *[other] { $count } Eigenschaften ignoriert
+}
+json-ignored-description = Eigenschaften der Quelle, die die Konvertierung nicht übernommen hat oder deren Wert nicht den Typ hat, den RFC 8984 oder RFC 9553 festlegt. Eigenschaften, die nur einen Standardwert enthielten, werden nicht aufgeführt.
+json-problem-syntax = Kein gültiges JSON in Zeile { $line }, Spalte { $column }: { $message }
+json-problem-wrong-type = { $expected } erwartet, { $found } gefunden
+json-problem-unknown = Unbekannte Eigenschaft, wird wahrscheinlich ignoriert
+json-problem-ignored = Von der Konvertierung ignoriert
+
+# Keyboard shortcuts
+shortcuts-title = Tastenkürzel
+shortcuts-help = Verwende auf macOS Cmd statt Strg. Rückgängig und Wiederholen gehen im Editor erst durch den Verlauf, wenn das Getippte konvertiert wurde.
+shortcut-key-ctrl = Strg
+shortcut-key-shift = Umschalt
+shortcut-key-enter = Eingabe
+shortcut-convert = Konvertieren
+shortcut-download = Ausgabe herunterladen
+shortcut-sample = Zufälliges Beispiel laden
+shortcut-undo = Rückgängig
+shortcut-redo = Wiederholen
+
+# Settings
+settings-title = Einstellungen
+settings-cors-proxy = CORS-Proxy
+settings-cors-proxy-help = Wird für jeden Abruf aus dem Netz verwendet. Die URL wird an den Proxy angehängt oder ersetzt { $placeholder }, falls vorhanden.
+settings-bypass-cache = Immer aus dem Netz abrufen
+settings-cached = { $count ->
+    [one] 1 Ressource zwischengespeichert
+   *[other] { $count } Ressourcen zwischengespeichert
+}
+settings-cache-unavailable = Offline-Cache nicht verfügbar
+settings-clear-cache = Cache leeren
+settings-copy-header = Kontextkopf beim Kopieren
+settings-copy-header-help = Kopierte Ausgaben können mit Format, Quelltyp, Werkzeugversion, Optionen und Zeitpunkt des Exports beginnen. Beim Wiedereinfügen wird der Kopf entfernt.
+header-style-off = Kein Kopf
+header-style-comments = Kommentarzeilen
+header-style-sidecar = JSON-Begleitdatei
+settings-redact = Private und vertrauliche Termine beim Kopieren schwärzen
+settings-redact-help = Von Terminen, die als privat oder vertraulich eingestuft sind, werden nur die Zeitblöcke kopiert, so wie CalDAV-Server sie anderen Benutzern zeigen.
+settings-live = Beim Tippen konvertieren
+settings-live-delay = nach
+settings-live-delay-unit = ms ohne Tippen
+settings-live-limit = Quellen über { $size } KB werden erst konvertiert, wenn der Editor den Fokus verliert.
+settings-metrics = Leistungsmetriken unter den Ergebnissen anzeigen
+
+# Working hours
+working-hours-title = Arbeitszeiten und Feiertage
+working-hours-to = bis
+weekday-mon = Mo
+weekday-tue = Di
+weekday-wed = Mi
+weekday-thu = Do
+weekday-fri = Fr
+weekday-sat = Sa
+weekday-sun = So
+overlay-annotate = Alle Vorkommen kennzeichnen
+overlay-hide = Feiertage und Termine außerhalb der Arbeitszeit ausblenden
+overlay-only = Nur Feiertage und Termine außerhalb der Arbeitszeit anzeigen
+holidays-paste = Einen Feiertagskalender einfügen (iCalendar)
+holidays-url = Oder einen Feiertagskalender von einer URL laden
+holidays-load = Laden
+holidays-none = Keine Feiertage geladen.
+holidays-loaded = { $count ->
+    [one] 1 Feiertag geladen.
+   *[other] { $count } Feiertage geladen.
+}
+
+# Previews
+preview-contact = Kontaktvorschau
+preview-invitation = Einladungsvorschau
+preview-organizer = Organisator
+preview-join = Teilnehmen
+preview-radius = Radius { $radius } m
+
+# Session bundle
+bundle-title = Sitzungspaket
+bundle-description = Packt die Quelle, alle Optionen, die Ausgaben, die Befunde und die Werkzeugversion in eine JSON-Datei, damit eine Fehlersuche an ein Ticket angehängt und später genau so wieder geöffnet werden kann.
+bundle-export = Sitzung exportieren
+bundle-import = Sitzung importieren
+bundle-restored = Sitzung wiederhergestellt, exportiert von Version { $version }.
+bundle-restored-on = Sitzung wiederhergestellt, exportiert von Version { $version } am { $date }.
+bundle-still-converting = Die Quelle wird noch konvertiert, daher wurden ihre Ausgaben nicht verglichen.
+bundle-unchanged = Die Ausgaben sind unverändert.
+bundle-changed-conversion = Diese Version erzeugt eine andere Konvertierung als die im Paket.
+bundle-changed-roundtrip = Diese Version erzeugt eine andere Rückkonvertierung als die im Paket.
+bundle-changed-both = Diese Version erzeugt eine andere Konvertierung und Rückkonvertierung als die im Paket.
+
+# Contact cleanup
+cleanup-title = Telefonnummern und E-Mail-Adressen
+cleanup-description = Schreibt Telefonnummern im Format E.164 um, etwa +4930123456, und entfernt mailto:-Präfixe, Anzeigenamen und großgeschriebene Domains aus E-Mail-Adressen. Nationale Nummern werden im Standardland gelesen.
+cleanup-enable = Kontaktwerte normalisieren
+cleanup-country = Standardland
+cleanup-country-none = Keines, nur internationale Nummern
+cleanup-clean = Alle Werte sind bereits sauber.
+cleanup-rewrote = { $cleaned ->
+    [one] 1 Wert umgeschrieben, in den Befunden aufgeführt.
+   *[other] { $cleaned } Werte umgeschrieben, in den Befunden aufgeführt.
+}
+cleanup-rewrote-invalid = { $cleaned ->
+    [one] 1 Wert umgeschrieben
+   *[other] { $cleaned } Werte umgeschrieben
+} und { $invalid } ungültige beibehalten, in den Befunden aufgeführt.
+
+# Random samples
+samples-title = Zufallsbeispiel erzeugen
+samples-description = Erstellt ein gültiges Dokument mit zufälligen Wiederholungsregeln, Zeitzonen, Teilnehmern, Erinnerungen und selteneren Eigenschaften, sodass jedes Beispiel andere Konvertierungspfade durchläuft.
+samples-any-format = Beliebiges Format
+samples-new = Neues Beispiel
+samples-seed = Startwert
+samples-rebuild = Aus Startwert neu erzeugen
+samples-invalid-seed = Der Startwert muss eine ganze Zahl sein.
+samples-generated = { $format }-Beispiel aus Startwert { $seed }. Mit demselben Startwert und Format lässt es sich neu erzeugen.
+
+# Property usage
+usage-title = Verwendung der Eigenschaften
+usage-description = Zählt, wie oft jede Eigenschaft und jeder Parameter in allen Einträgen der geöffneten Dokumente vorkommt und wie viele Befunde sie ausgelöst haben, damit die Konvertierungspfade, auf die ein Korpus angewiesen ist, zuerst getestet werden können. Die Kommandozeile schreibt dieselbe Tabelle mit { $option }.
+usage-analyze = Geöffnete Dokumente analysieren
+usage-download = CSV herunterladen
+usage-summary = { $entries } Einträge, { $failed } nicht konvertiert, { $total } Eigenschaften und Parameter.
+usage-name = Name
+usage-format = Format
+usage-occurrences = Vorkommen
+usage-entries = Einträge
+usage-findings = Befunde
+usage-show-all = Alle { $count } anzeigen
+
+# Semantic comparison
+semantic-title = Zwei Eingaben vergleichen
+semantic-description = Füge zwei Kalender oder zwei Kontakte in einem beliebigen unterstützten Format ein. Beide werden in JSCalendar oder JSContact eingelesen und nach ihrer Bedeutung verglichen: Reihenfolge, Zeilenumbrüche, Syntax, IDs von Maps und Server-Zeitstempel werden ignoriert.
+semantic-first-input = Erste Eingabe
+semantic-second-input = Zweite Eingabe
+semantic-use-current = Aktuelle Quelle verwenden
+semantic-same = Beide Eingaben bedeuten dasselbe.
+semantic-differences = { $count ->
+    [one] 1 Unterschied
+   *[other] { $count } Unterschiede
+}
+semantic-difference = Unterschied
+semantic-first = Erste
+semantic-second = Zweite
+
+# Pager
+pager-count = { $count } { $noun }
+pager-range = { $start }–{ $end } von { $total } { $noun }
+pager-page = Seite { $page } von { $pages }
+pager-previous = Zurück
+pager-next = Weiter
+pager-rows = { $count ->
+    [one] Zeile
+   *[other] Zeilen
+}
+pager-occurrences = { $count ->
+    [one] Vorkommen
+   *[other] Vorkommen
+}
+pager-entries = { $count ->
+    [one] Eintrag
+   *[other] Einträge
+}
+pager-alarms = { $count ->
+    [one] Erinnerung
+   *[other] Erinnerungen
+}
+
+# Copy and download
+export-copy = Kopieren
+export-copied = Kopiert
+export-copied-toast = { $format } in die Zwischenablage kopiert.
+export-copy-failed = Kopieren fehlgeschlagen
+export-copy-failed-toast = Das Kopieren in die Zwischenablage ist fehlgeschlagen.
+export-download = Herunterladen
+export-download-failed = Herunterladen fehlgeschlagen
+
+# Event summary
+event-summary-title = Übersicht
+event-summary-location = Ort
+event-summary-organizer = Organisator
+event-summary-attendees = Teilnehmer
+event-summary-description = Beschreibung
+event-summary-alarms = Erinnerungen
+event-summary-accepted = Zugesagt
+event-summary-declined = Abgesagt
+event-summary-tentative = Vorläufig
+event-summary-no-reply = Keine Antwort
+
+# Colors
+colors-title = Farben und Kategorien
+colors-source = Quelle
+colors-converted = Konvertiert
+
+# Metrics
+metrics-not-measured = Nicht gemessen
+metrics-phases = Einlesen { $parse } · Konvertierung { $conversion } · Rückkonvertierung { $roundtrip }
+metrics-expansion = Expansion { $elapsed }
+metrics-sizes = Eingabe { $input } · Ausgabe { $output } · Rückkonvertierung { $roundtrip }
+metrics-run = { $count } Durchläufe ausführen
+metrics-benchmark = { $done } von { $count } Durchläufen, Mittel { $mean } · schnellster { $fastest } · langsamster { $slowest }
+
+# Organization chart
+orgchart-title = Organigramm
+orgchart-description = { $count ->
+    [one] 1 Person, gruppiert nach Organisation und Abteilung.
+   *[other] { $count } Personen, gruppiert nach Organisation und Abteilung.
+}
+orgchart-export = JSON exportieren
+
+# Alarm timeline
+alarms-title = Erinnerungen
+alarms-fires = Löst aus
+alarms-reminds-of = Erinnert an
+alarms-trigger = Auslöser
+alarms-action = Aktion
+alarms-line = VALARM in Zeile { $line }
+alarms-past = Bereits vorbei
+alarms-fixed = Fester Zeitpunkt, einmalig
+alarms-repeat = { $count }-mal wiederholen
+
+# Shift generator
+shifts-title = Schichtmuster erzeugen
+shifts-on-off = N Tage Dienst / M Tage frei
+shifts-alternating = Wechselnde Wochen
+shifts-monthly = Wochentag im Monat
+shifts-summary = Titel
+shifts-default-summary = Schicht
+shifts-days-on = Tage Dienst,
+shifts-days-off = Tage frei
+shifts-week-a = Woche A
+shifts-week-b = Woche B
+shifts-week-a-start = Woche A beginnt am Montag der Woche des ersten Tages.
+shifts-first = 1.
+shifts-second = 2.
+shifts-third = 3.
+shifts-fourth = 4.
+shifts-last = Letzter
+shifts-first-day = Erster Tag
+shifts-at = um
+shifts-for = für
+shifts-hours = Stunden,
+shifts-repetitions = Wiederholungen
+shifts-timezone = Zeitzone (z. B. Europe/Berlin)
+shifts-generate = Erzeugen
+shifts-verified = { $count ->
+    [one] Expansion geprüft: die erwartete Schicht wurde erzeugt.
+   *[other] Expansion geprüft: alle { $count } erwarteten Schichten wurden erzeugt.
+}
+shifts-mismatch = Expansion weicht ab: { $count } Schichten erwartet, { $expanded } expandiert. Fehlend: [{ $missing }]. Unerwartet: [{ $unexpected }].
+
+# Tour
+tour-step = Schritt { $step } von { $steps }
+tour-end = Tour beenden
+tour-back = Zurück
+tour-next = Weiter
+tour-finish = Fertig
+tour-sample = Tour-Beispiel
+tour-detection = Formaterkennung
+tour-detection-text = Füge hier eine Datei ein oder lege sie hier ab. Ihr Format wird an der ersten Zeile oder, bei JSON, an ihrem @type erkannt. Dieses Beispiel ist eine von Exchange gesendete iCalendar-Besprechungsanfrage (RFC 5545) und wird deshalb in JSCalendar (RFC 8984) konvertiert. vCards werden ebenso in JSContact (RFC 9553) konvertiert, und JSON wird zurückkonvertiert.
+tour-conversion = Konvertierung
+tour-conversion-text = Das ist das Beispiel als JSCalendar, dem Format, das JMAP-Server austauschen. Eigenschaften werden zu JSON-Feldern: DTSTART und seine TZID werden zu start und timeZone, DTEND zu einer duration, die RRULE zu recurrenceRules und jeder ATTENDEE zu einem Eintrag in participants.
+tour-roundtrip = Rückkonvertierung
+tour-roundtrip-text = Das JSCalendar zurück in iCalendar konvertiert. Der Vergleich mit der Quelle zeigt, was die Abbildung erhält; der Treuebericht und der Diff daneben zeigen, was unterwegs verloren ging oder umgeschrieben wurde.
+tour-expansion = Expansion der Wiederholungen
+tour-expansion-text = Die Wiederholungsregel, in Vorkommen expandiert, in der Zeitzone deiner Wahl. Das erhält ein Client, wenn er einen JMAP-Server nach den Instanzen eines wiederkehrenden Termins fragt.
+
+# Time probe
+probe-title = Findet dieser Termin statt am…
+probe-enter-date = Gib ein Datum und eine Uhrzeit ein.
+probe-unknown-timezone = Unbekannte Zeitzone: { $timezone }
+probe-nonexistent = { $at } existiert in { $timezone } nicht.
+probe-unparsable = Der Kalender konnte nicht eingelesen werden.
+probe-no-events = Der Kalender enthält keine Termine.
+probe-occurring = Ja, { $start } bis { $end }
+probe-next = Nein, nächstes Mal am { $next }
+probe-not-again = Nein, und er findet nicht mehr statt
+probe-inconclusive = Unbekannt, zu viele Vorkommen vor diesem Zeitpunkt
+probe-recurring = wiederkehrend
+
+# History
+recent-clear = Leeren
+recent-close = Schließen
+recent-empty = Eingaben erscheinen hier, sobald sie konvertiert wurden.
+recent-load = In den Editor laden
+recent-remove = Aus dem Verlauf entfernen
+recent-unknown-format = Unbekanntes Format
+recent-just-now = Gerade eben
+recent-minutes-ago = vor { $count } Min.
+recent-hours-ago = vor { $count } Std.
+
+# Status editor
+status-field-status = Status und Fortschritt
+status-field-transparency = Frei/Gebucht-Status
+status-field-classification = Vertraulichkeit
+status-component = Komponente
+status-dropped = Keine, geht beim Export verloren
+status-missing = kein { $property }
+status-unknown = unbekannt
+status-untitled = Ohne Titel
+status-select-all = Alle auswählen
+status-set-field = { $field } setzen
+status-set = { $count ->
+    [one] 1 Komponente auf { $value } gesetzt.
+   *[other] { $count } Komponenten auf { $value } gesetzt.
+}
+status-set-skipped = { $count ->
+    [one] 1 Komponente auf { $value } gesetzt, { $skipped } übersprungen, die den Wert nicht zulassen.
+   *[other] { $count } Komponenten auf { $value } gesetzt, { $skipped } übersprungen, die den Wert nicht zulassen.
+}
+
+# Scheduling
+scheduling-title = Terminplanungsnachricht
+scheduling-reply-requested = Antwort erbeten
+scheduling-reply-as = Antworten als
+scheduling-accept = Zusagen
+scheduling-maybe = Vielleicht
+scheduling-decline = Absagen
+scheduling-generate = REPLY erzeugen
+scheduling-open = Im Konverter öffnen
+
+# Gender and pronouns
+identity-title = Geschlecht und Pronomen
+identity-source = Quelle
+identity-converted = Konvertiert
+identity-roundtrip = Rückkonvertiert
+identity-gender = Geschlecht
+identity-grammatical-gender = Grammatisches Geschlecht
+identity-pronouns = Pronomen
+identity-vendor = Herstellereigenschaften
+
+# Diff
+diff-title = Diff zwischen Quelle und Rückkonvertierung
+diff-source = Quelle
+diff-roundtrip = Rückkonvertierung
+diff-identical = Identische Ausgabe.
+diff-unified = Einheitlich
+diff-side-by-side = Nebeneinander
+diff-only-changes = Nur Änderungen
+compare-run = { $current } mit { $previous } vergleichen
+compare-failed = { $version } ist fehlgeschlagen: { $error }
+compare-converted = Konvertierte Ausgabe
+compare-roundtrip = Rückkonvertierung
+
+# Description round trip
+description-title = Beschreibung nach Rückkonvertierung
+description-original = Original
+description-raw = Rohwert: { $value }
+description-html-preview = HTML-Vorschau (bereinigt)
+description-converted = Konvertiert
+description-converted-type = Konvertiert ({ $type })
+description-roundtrip = Rückkonvertiert
+description-missing = Beschreibung fehlt.
+
+# Validation
+validation-convert = Konvertieren
+validation-validate = Validieren
+validation-title = Validierungsbericht
+validation-counts = { $errors ->
+    [one] 1 Fehler
+   *[other] { $errors } Fehler
+}, { $warnings ->
+    [one] 1 Warnung
+   *[other] { $warnings } Warnungen
+}
+validation-none = Keine Konformitätsprobleme gefunden.
+validation-severity = Schweregrad
+validation-code = Code
+validation-problem = Problem
+validation-location = Position
+severity-info = Info
+severity-warning = Warnung
+severity-error = Fehler
+
+# Parsing mode
+recovery-title = Einlesemodus
+recovery-description = Nachsichtiges Einlesen überspringt oder korrigiert Zeilen, die nicht dem RFC entsprechen, und macht weiter. Striktes Einlesen lässt einen Eintrag bei der ersten solchen Zeile scheitern, mit der Zeile und dem Fehler.
+recovery-lenient = Nachsichtig
+recovery-strict = Strikt
+recovery-none = Es waren keine Korrekturen nötig.
+recovery-refused = { $count ->
+    [one] 1 Korrektur abgelehnt
+   *[other] { $count } Korrekturen abgelehnt
+}
+recovery-made = { $count ->
+    [one] 1 Korrektur vorgenommen
+   *[other] { $count } Korrekturen vorgenommen
+}
+recovery-where = Wo
+recovery-problem = Problem
+recovery-action = Nachsichtiges Einlesen
+
+# Property editor
+properties-title = Eigenschaften bearbeiten
+properties-description = Ändere einen Wert und drücke Enter oder verlasse das Feld, um diese Eigenschaft neu zu schreiben und erneut zu konvertieren.
+properties-filter = Nach Eigenschaft oder Komponente filtern
+properties-component = Komponente
+properties-property = Eigenschaft
+properties-value = Wert
+properties-text = Text, maskiert geschrieben
+properties-raw = Rohwert
+
+# Permalink
+permalink-title = Link teilen
+permalink-description = Packt die Quelle und die Optionen komprimiert in die Adresse der Seite, damit sich eine Reproduktion in einen Fehlerbericht einfügen lässt. Das Öffnen des Links konvertiert sie sofort. Nichts wird an einen Server gesendet, da der Teil nach # im Browser bleibt.
+permalink-copy = Link kopieren
+permalink-copied = Link in die Zwischenablage kopiert.
+permalink-copy-failed = Das Kopieren in die Zwischenablage ist fehlgeschlagen, der Link steht in der Adressleiste.
+permalink-too-long = Der Link ist { $length } Zeichen lang und wird beim Einfügen womöglich gekürzt. Hänge stattdessen besser ein Sitzungspaket an.
+
+# Fidelity report
+fidelity-title = Treuebericht
+fidelity-description = Vergleicht die Quelle Eigenschaft für Eigenschaft mit ihrer Rückkonvertierung über { $format }. Werte, die nur ihre Form geändert haben, werden nicht aufgeführt.
+fidelity-none = Jede Eigenschaft der Quelle kam aus { $format } zurück.
+fidelity-summary = { $count ->
+    [one] 1 Verlust bei der Rückkonvertierung: { $kinds }.
+   *[other] { $count } Verluste bei der Rückkonvertierung: { $kinds }.
+}
+fidelity-dropped = Entfernt
+fidelity-renamed = Umbenannt
+fidelity-parameter-lost = Parameter verloren
+fidelity-dropped-count = { $count } entfernt
+fidelity-renamed-count = { $count } umbenannt
+fidelity-parameter-lost-count = { $count ->
+    [one] 1 Parameter verloren
+   *[other] { $count } Parameter verloren
+}
+fidelity-property = Eigenschaft
+fidelity-lost = Verloren
+fidelity-original = Ursprünglicher Wert
+fidelity-now = Jetzt { $name }
+
+# Expected output
+expected-title = Mit erwarteter Ausgabe vergleichen
+expected-description = Füge das { $format } ein, das ein anderes Werkzeug oder ein Server für dieselbe Eingabe erzeugt hat. Reihenfolge, Zeilenumbrüche, Produkt-IDs und die IDs von JSON-Maps werden ignoriert.
+expected-placeholder = Erwartete Ausgabe
+expected-none = Keine Abweichungen von der erwarteten Ausgabe.
+expected-summary = { $count ->
+    [one] 1 Abweichung: { $kinds }.
+   *[other] { $count } Abweichungen: { $kinds }.
+}
+expected-different = Unterschiedlich
+expected-missing = Nur in der erwarteten
+expected-unexpected = Nur in unserer
+expected-different-count = { $count } unterschiedlich
+expected-missing-count = { $count } nur in der erwarteten
+expected-unexpected-count = { $count } nur in unserer
+expected-path = Pfad
+expected-ours = Unsere
+expected-expected = Erwartet
+
+# Workspace
+workspace-source = Quelle
+workspace-converted = Konvertiert
+workspace-roundtrip = Rückkonvertierung
+workspace-warnings = Warnungen
+
+# Duration statistics
+stats-window = Zeitraum
+stats-scheduled = Verplant
+stats-average = Durchschnittliche Dauer
+stats-shortest-longest = Kürzester / längster
+stats-busiest-day = Vollster Tag
+stats-busiest-day-value = { $count ->
+    [one] { $day } ({ $duration }, 1 Termin)
+   *[other] { $day } ({ $duration }, { $count } Termine)
+}
+stats-weekday = { $count ->
+    [one] { $day }: 1 Termin, { $hours } h
+   *[other] { $day }: { $count } Termine, { $hours } h
+}
+
+# Self-test
+selftest-title = Selbsttest
+selftest-description = Schickt jedes mitgelieferte Beispiel und deine Schnipsel durch Einlesen, Konvertierung und Rückkonvertierung mit dem in v{ $version } eingebauten calcard.
+selftest-placeholder = Füge einen weiteren iCalendar-, JSCalendar-, vCard- oder JSContact-Schnipsel ein
+selftest-add = Schnipsel hinzufügen ({ $count } hinzugefügt)
+selftest-run = Selbsttest ausführen
+selftest-summary = { $passed } von { $count } bestanden in { $elapsed } ms
+selftest-sample = Beispiel
+selftest-format = Format
+selftest-parse = Einlesen
+selftest-convert = Konvertieren
+selftest-roundtrip = Rückkonvertierung
+selftest-reparse = Erneut einlesen
+
+# Vendor extensions
+sanitize-title = Herstellererweiterungen
+sanitize-description = Entfernt X-Eigenschaften, -Komponenten und -Parameter sowie JSON-Eigenschaften mit Herstellerpräfix wie „example.com:foo“, um eine rein standardkonforme Ausgabe zu erzeugen.
+sanitize-enable = Herstellererweiterungen entfernen
+sanitize-keep = Behalten, z. B. X-WR-CALNAME, X-APPLE-STRUCTURED-LOCATION
+sanitize-removed = { $count ->
+    [0] Keine Herstellererweiterungen gefunden.
+    [one] 1 Element entfernt, in den Befunden aufgeführt.
+   *[other] { $count } Elemente entfernt, in den Befunden aufgeführt.
+}
+
+# Conversion report
+report-title = Konvertierungsbericht
+report-description = Eine JSON-Zusammenfassung der Eingabe, des erkannten Formats, der Befunde jedes Eintrags mit ihren Codes, des Verlaufs jeder Rückkonvertierung und ihrer Dauer. Die Kommandozeile schreibt denselben Bericht mit { $option }, sodass sich die Konvertierungsqualität über einen ganzen Korpus verfolgen lässt.
+report-download = Bericht herunterladen
+report-nothing = Konvertiere zuerst etwas.
+report-failed = Der Bericht konnte nicht heruntergeladen werden.
+
+# Patches
+patches-title = Zwei Versionen vergleichen
+patches-description = Füge zwei Versionen eines JSCalendar- oder JSContact-Objekts ein, um den Patch zu erhalten, der die erste in die zweite überführt, als Update eines JMAP-/set-Aufrufs.
+patches-before = Vorher
+patches-after = Nachher
+patches-use-current = Aktuelles Objekt verwenden
+
+# Findings
+findings-title = Befunde
+findings-all = Alle Schweregrade
+findings-warnings = Warnungen und Fehler
+findings-errors = Nur Fehler
+findings-filter = Nach Code filtern, z. B. CAL-
+findings-suppress = Unterdrücken
+findings-unsuppress = Klicken, um diesen Code nicht mehr zu unterdrücken
+findings-hidden = { $hidden } von { $count } Befunden durch Filter ausgeblendet.
+
+# Time zone inspector
+vtimezone-title = { $warnings ->
+    [0] Zeitzonen: { $count } definiert
+   *[other] Zeitzonen: { $count } definiert, { $warnings } mit Warnungen
+}
+vtimezone-mapped = Konvertiert als { $tz }.
+vtimezone-unused-resolves = Von keinem Termin verwendet, entspricht { $tz }.
+vtimezone-unused = Von keinem Termin verwendet.
+vtimezone-standard = Normalzeit
+vtimezone-daylight = Sommerzeit
+
+# Remote source
+remote-title = URL öffnen
+remote-placeholder = https://example.com/calendar.ics oder webcal://…
+remote-fetch = Abrufen
+remote-fetching = Wird abgerufen…
+remote-limits = Dateien bis { $size } MB. Server, die keine Cross-Origin-Anfragen erlauben, brauchen den CORS-Proxy aus den Einstellungen unten.
+remote-loaded = { $url } geladen.
+remote-loaded-cached = { $url } aus dem Cache geladen. Umgehe den Cache in den Einstellungen, um die Datei erneut abzurufen.
+
+# Normalization
+normalize-title = Normalisieren
+normalize-description = Schreibt die Quelle in kanonische Form um: Zeilen bei 75 Oktetten umbrochen, Komponenten und Eigenschaften sortiert, Parameter sortiert und in Großbuchstaben, Datums-/Zeitangaben in UTC, sofern sie nicht zu einer Serie gehören oder schwebend sind, und JSON-Schlüssel sortiert. Zwei Exporte derselben Daten unterscheiden sich dann nur dort, wo sich die Daten unterscheiden.
+normalize-run = Normalisieren
+normalize-changed = Normalisiert, mit Rückgängig wiederherstellen.
+normalize-unchanged = Bereits in kanonischer Form.
+
+# Merge
+merge-title = Dateien zusammenführen
+merge-description = Führt mehrere iCalendar- oder vCard-Dateien zu einer zusammen, die in einem neuen Dokument geöffnet wird. Zeitzonen, die in mehreren Dateien definiert sind, werden einmal geschrieben, und Einträge mit derselben UID werden gemeldet.
+merge-output = Kalender zusammenführen als
+merge-output-calendar = Ein einzelner VCALENDAR
+merge-output-group = Eine JSCalendar-Group
+merge-choose = Dateien zum Zusammenführen wählen
+merge-summary = { $files } Dateien zu { $entries } Einträgen und { $timezones } Zeitzonen zusammengeführt.
+
+# Contact links
+links-title = Medien, Verzeichnisse und Kalender
+links-collection = Sammlung
+links-kind = Art
+links-uri = URI
+links-media-type = Medientyp
+links-check = Prüfung
+links-ok = OK
+links-pref = Priorität { $pref }
+links-placed = in { $expected }
+links-misplaced = in { $found }, erwartet in { $expected }
+links-missing = fehlt, erwartet in { $expected }
+
+# vCard version
+vcard-version-title = vCard-Version
+vcard-version-detected = Die Quelle ist vCard { $version }. Schreiben als
+vcard-version-write = Schreiben als
+vcard-version-target = vCard-Zielversion
+vcard-version-unrepresentable = In vCard { $version } nicht darstellbar
+
+# Repair
+repair-title = Exportierten Kalender reparieren
+repair-run = Reparieren
+repair-nothing = Nichts zu reparieren für { $profile }.
+repair-applied = { $count ->
+    [one] { $count } Korrektur für { $profile } angewendet, mit Rückgängig wiederherstellen:
+   *[other] { $count } Korrekturen für { $profile } angewendet, mit Rückgängig wiederherstellen:
+}
+repair-notes = Unverändert gelassen, bitte selbst prüfen:
+
+# Free/busy
+freebusy-title = Frei/Gebucht: { $freebusy } VFREEBUSY, { $availability } VAVAILABILITY
+freebusy-kind-freebusy = Frei/Gebucht
+freebusy-kind-availability = Verfügbarkeit
+freebusy-no-span = Kein DTSTART und DTEND
+freebusy-outside = Außerhalb der verfügbaren Zeitfenster: { $status }.
+freebusy-no-periods = Keine Zeiträume.
+freebusy-free = Frei
+freebusy-busy = Gebucht
+freebusy-tentative = Vorläufig
+freebusy-unavailable = Nicht verfügbar
+
+# Documents
+documents-name = Dokumentname
+documents-rename = Zum Umbenennen doppelklicken
+documents-close = Dokument schließen
+documents-new = Neues Dokument
+
+# QR code
+qr-show = QR
+qr-hide = QR-Code ausblenden
+qr-payload = Inhalt des QR-Codes
+qr-size = { $size } von { $max } Bytes
+qr-code = QR-Code
+
+# Phonetic names
+phonetic-title = Phonetische Namen und Aussprache
+phonetic-not-playable = im Browser nicht abspielbar
+
+# Time zone picker
+timezone-title = Zeitzone, in der die Vorkommen angezeigt werden
+timezone-as-written = Wie angegeben
+
+# Web page extraction
+html-extract-title = Webseite
+html-extract-found = Gefunden: { $found }.
+html-extract-schema-org = { $count ->
+    [one] { $count } schema.org-Termin
+   *[other] { $count } schema.org-Termine
+}
+html-extract-hcalendar = { $count ->
+    [one] { $count } hCalendar-Termin
+   *[other] { $count } hCalendar-Termine
+}
+html-extract-hcards = { $count ->
+    [one] { $count } hCard
+   *[other] { $count } hCards
+}
+html-extract-events-only = Nur die Termine werden konvertiert.
+html-extract-skipped = { $count ->
+    [one] { $count } Termin ohne Startdatum wurde ausgelassen.
+   *[other] { $count } Termine ohne Startdatum wurden ausgelassen.
+}
+
+# Heatmap
+heatmap-busiest = Belebteste Stunde: { $day } { $hour } mit { $count } Vorkommen.
+heatmap-cell = { $day } { $hour }: { $count } Vorkommen
+
+# Component filter
+filter-title = Komponententypen
+filter-description = Nicht markierte Typen werden aus der Ausgabe und der Erweiterung entfernt, für Clients, die Erinnerungen doppelt anzeigen oder keine Aufgaben verarbeiten können.
+filter-events = Termine
+filter-todos = Aufgaben
+filter-journals = Journale
+filter-freebusy = Frei/Gebucht
+filter-timezones = Zeitzonen
+filter-alarms = Erinnerungen
+filter-removed = { $count ->
+    [one] { $count } Komponente ausgelassen.
+   *[other] { $count } Komponenten ausgelassen.
+}
+
+# Entries
+entries-title = { $failed ->
+    [0] { $count } Einträge
+   *[other] { $count } Einträge, { $failed } fehlgeschlagen
+}
+entries-all = Alle Einträge
+entries-untitled = Ohne Titel
+entries-findings = { $count ->
+    [one] { $count } Befund
+   *[other] { $count } Befunde
+}
+
+# Email message
+email-title = E-Mail-Nachricht
+email-no-parts = Die Nachricht hat keinen text/calendar-, text/vcard- oder text/directory-Teil.
+email-part = Teil { $path } · { $type }
+
+# CSV contacts
+csv-title = CSV-Kontakte, Layout { $layout }: { $mapped } von { $count } Spalten zugeordnet
+csv-layout-google = Google Contacts
+csv-layout-outlook = Outlook
+csv-layout-generic = Generisch
+csv-kind-home = privat
+csv-kind-work = geschäftlich
+csv-kind-mobile = mobil
+csv-kind-other = sonstige
+csv-address-pobox = Postfach
+csv-address-street = Straße
+csv-address-city = Ort
+csv-address-region = Region
+csv-address-code = Postleitzahl
+csv-address-country = Land
+csv-field-ignore = Ignorieren
+csv-field-full-name = Vollständiger Name
+csv-field-given-name = Vorname
+csv-field-family-name = Nachname
+csv-field-additional-name = Zweiter Vorname
+csv-field-prefix = Namenspräfix
+csv-field-suffix = Namenssuffix
+csv-field-nickname = Spitzname
+csv-field-organization = Organisation
+csv-field-department = Abteilung
+csv-field-title = Berufsbezeichnung
+csv-field-birthday = Geburtstag
+csv-field-url = Website
+csv-field-note = Notiz
+csv-field-categories = Kategorien
+csv-field-type = Typ der folgenden Spalten
+csv-field-email = E-Mail ({ $kind })
+csv-field-phone = Telefon ({ $kind })
+csv-field-address = Adresse ({ $kind }), { $part }
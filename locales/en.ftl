@@ -0,0 +1,844 @@
+# Page header
+header-title = JSCalendar and JSContact conversion
+header-subtitle = Bi-directional conversion from/to JSCalendar/iCalendar and JSContact/vCard.
+header-history = History
+header-history-title = Inputs converted recently
+header-tour = Take the tour
+header-tour-title = Walk through detection, conversion, round trip and expansion with a sample
+header-split-view = Split view
+header-stacked-view = Stacked view
+header-install = Install app
+header-install-title = Install the converter as an app that also works offline
+theme-title = Color theme
+theme-system = System theme
+theme-light = Light
+theme-dark = Dark
+language-title = Language
+language-browser = Browser language
+
+# Editor
+editor-placeholder = Paste or drop here an iCalendar, JSCalendar, vCard, JSContact, jCal or jCard file, a CSV export of contacts, or an email with an invite or vCard attached. Or click the sparkles to try a sample.
+editor-drop = Drop an .ics, .vcf or .json file to load it
+editor-reading = Reading { $name }… { $percent }%
+editor-undo = Undo (Ctrl+Z)
+editor-redo = Redo (Ctrl+Shift+Z)
+editor-sample = Load a random sample (Ctrl+K)
+editor-open = Open a file
+error-show = Show { $location } in the editor
+error-show-title = Select the error in the editor
+error-report-bug = Report this bug
+error-report-bug-title = Open a GitHub issue filled in with the version, the format and the input, with email addresses and binary data replaced
+error-report-bug-copied = Copied the input to the clipboard, paste it into the issue.
+error-report-bug-copy-failed = Copying the input to the clipboard failed, attach it to the issue instead.
+conversion-progress = Converted { $done } entries, { $percent }% of the source…
+conversion-cancel = Cancel
+conversion-cancelled = Conversion cancelled. Editing the source converts it again.
+conversion-all-failed = None of the { $count } entries could be converted.
+source-line = line { $line }
+
+# Conversion results
+results-title = Conversion results
+results-converted = This is how your { $source } looks like in { $target } format:
+results-roundtrip = And this is how it would look like converted back to { $source }:
+output-format = Output format
+output-jmap-request = JMAP request
+output-explain = Explain conversion
+output-explain-title = Annotate each field with the source property it came from
+output-download = Download
+output-jmap-failed = The output cannot be sent as a JMAP request: { $error }
+footer-report-bug = Report a bug
+footer-view-source = View source
+
+# Calendar expansion
+expansion-title = Calendar expansion results
+expansion-from-start = From the first occurrence
+expansion-from-now = From now
+expansion-between = Between dates
+expansion-count-title = Maximum number of occurrences
+expansion-budget-title = Time budget for the expansion, in milliseconds
+expansion-download-ics = Download .ics
+expansion-download-csv = Download .csv
+expansion-range-from = From
+expansion-range-to = to
+expansion-summary-start = These are the first { $count } occurrences of the pasted calendar event:
+expansion-summary-now = These are the next { $count } occurrences of the pasted calendar event:
+expansion-summary-range = These are the { $count } occurrences of the pasted calendar event between the selected dates:
+expansion-elapsed = Expanded in { $elapsed } ms.
+expansion-elapsed-budget = Expanded in { $elapsed } ms, stopped by the { $budget } ms budget.
+expansion-copy-json = Copy as JSON
+expansion-out-of-budget = { $count ->
+    [one] Expansion stopped after { $elapsed } ms with { $count } occurrence, later ones may be missing.
+   *[other] Expansion stopped after { $elapsed } ms with { $count } occurrences, later ones may be missing.
+}
+expansion-limit-reached = Only the first { $count } occurrences were expanded.
+expansion-none-start = The calendar contains events, tasks or journal entries but the expansion produced no occurrences.
+expansion-none-now = The calendar has no upcoming occurrences.
+expansion-none-range = The calendar has no occurrences between these dates.
+occurrence-type = Type
+occurrence-from = From date
+occurrence-to = To date
+occurrence-notes = Notes
+occurrence-due = Due
+occurrence-floating = Floating
+occurrence-added = Added by RDATE
+occurrence-modified = Modified
+occurrence-modified-summary = Modified: { $summary }
+occurrence-modified-title = Overridden by a RECURRENCE-ID
+occurrence-removed = Removed by EXDATE
+occurrence-outside-hours = Outside working hours
+occurrence-outside-hours-finding = Occurrence is outside working hours.
+occurrence-on-holiday-finding = Occurrence falls on { $holiday }.
+date-zone-title = Time zone of the dates
+date-zone-display = In the expansion time zone
+date-zone-event = In the event time zone
+date-display-title = Date format
+date-display-locale = Localized dates
+date-display-iso8601 = ISO 8601
+date-display-rfc3339 = RFC 3339
+date-display-epoch = Unix epoch
+entry-kind-event = Event
+entry-kind-task = Task
+entry-kind-journal = Journal
+
+# Compatibility check
+compatibility-title = Compatibility check
+compatibility-title-warnings = Compatibility check, { $count ->
+    [one] 1 warning
+   *[other] { $count } warnings
+}
+compatibility-description = Looks for constructs that common clients are known to drop or misread when importing the iCalendar or vCard, such as time zones without a definition, unsupported recurrence rules and vCard 4.0 properties.
+compatibility-no-problems = No known problems
+compatibility-warnings = { $count ->
+    [one] 1 warning
+   *[other] { $count } warnings
+}
+
+# Anonymization
+anonymize-title = Anonymize for sharing
+anonymize-description = Replaces names, email addresses, phone numbers, descriptions and attachments with placeholders, keeping the structure, recurrence rules and time zones, so the input can be shared in a public bug report. The same value always gets the same placeholder.
+anonymize-run = Anonymize
+anonymize-nothing = Nothing to anonymize.
+anonymize-replaced = { $count ->
+    [one] Replaced 1 value, undo to revert.
+   *[other] Replaced { $count } values, undo to revert.
+}
+anonymize-failed = Could not anonymize the input: { $error }
+
+# Duplicate contacts
+dedup-title = Possible duplicates
+dedup-summary = { $groups ->
+    [one] 1 group
+   *[other] { $groups } groups
+} among { $cards } cards share an email address, a phone number or a name.
+dedup-merge-all = Merge all groups
+dedup-same = Same { $reasons }
+dedup-merged = Merged card
+
+# JSON diagnostics
+json-ignored-count = { $count ->
+    [one] 1 property ignored
+   *[other] { $count } properties ignored
+}
+json-ignored-description = Properties of the source that the conversion did not keep, or whose value is not of the type RFC 8984 or RFC 9553 defines. Properties that only held a default value are not listed.
+json-problem-syntax = Not valid JSON at line { $line }, column { $column }: { $message }
+json-problem-wrong-type = Expected { $expected }, found { $found }
+json-problem-unknown = Unknown property, likely ignored
+json-problem-ignored = Ignored by the conversion
+
+# Keyboard shortcuts
+shortcuts-title = Keyboard shortcuts
+shortcuts-help = Use Cmd in place of Ctrl on macOS. Undo and redo in the editor only step through the history once what was typed has been converted.
+shortcut-key-ctrl = Ctrl
+shortcut-key-shift = Shift
+shortcut-key-enter = Enter
+shortcut-convert = Convert
+shortcut-download = Download the output
+shortcut-sample = Load a random sample
+shortcut-undo = Undo
+shortcut-redo = Redo
+
+# Settings
+settings-title = Settings
+settings-cors-proxy = CORS proxy
+settings-cors-proxy-help = Used for every remote fetch. The URL is appended to the proxy, or substituted for { $placeholder } when present.
+settings-bypass-cache = Always fetch from the network
+settings-cached = { $count ->
+    [one] 1 cached resource
+   *[other] { $count } cached resources
+}
+settings-cache-unavailable = Offline cache unavailable
+settings-clear-cache = Clear cache
+settings-copy-header = Context header on copy
+settings-copy-header-help = Copied output can start with the format, source type, tool version, options and time of the export. Pasting it back removes the header.
+header-style-off = No header
+header-style-comments = Comment lines
+header-style-sidecar = JSON sidecar
+settings-redact = Redact private and confidential events on copy
+settings-redact-help = Only the time blocks of events classified as private or confidential are copied, as CalDAV servers show them to other users.
+settings-live = Convert as you type
+settings-live-delay = after
+settings-live-delay-unit = ms without typing
+settings-live-limit = Sources larger than { $size } KB are only converted when the editor loses focus.
+settings-metrics = Show performance metrics below the results
+
+# Working hours
+working-hours-title = Working hours and holidays
+working-hours-to = to
+weekday-mon = Mon
+weekday-tue = Tue
+weekday-wed = Wed
+weekday-thu = Thu
+weekday-fri = Fri
+weekday-sat = Sat
+weekday-sun = Sun
+overlay-annotate = Annotate all occurrences
+overlay-hide = Hide holidays and out-of-hours
+overlay-only = Show only holidays and out-of-hours
+holidays-paste = Paste a holiday calendar (iCalendar)
+holidays-url = Or load a holiday calendar from a URL
+holidays-load = Load
+holidays-none = No holidays loaded.
+holidays-loaded = { $count ->
+    [one] 1 holiday date loaded.
+   *[other] { $count } holiday dates loaded.
+}
+
+# Previews
+preview-contact = Contact preview
+preview-invitation = Invitation preview
+preview-organizer = Organizer
+preview-join = Join
+preview-radius = { $radius } m radius
+
+# Session bundle
+bundle-title = Session bundle
+bundle-description = Packages the source, every option, the outputs, the findings and the tool version into one JSON file, so a debugging session can be attached to a ticket and reopened later exactly as it was.
+bundle-export = Export session
+bundle-import = Import session
+bundle-restored = Restored the session exported by version { $version }.
+bundle-restored-on = Restored the session exported by version { $version } on { $date }.
+bundle-still-converting = The source is still converting, so its outputs were not compared.
+bundle-unchanged = Outputs are unchanged.
+bundle-changed-conversion = This version produces a different conversion than the one in the bundle.
+bundle-changed-roundtrip = This version produces a different round trip than the one in the bundle.
+bundle-changed-both = This version produces a different conversion and round trip than the ones in the bundle.
+
+# Contact cleanup
+cleanup-title = Phone numbers and email addresses
+cleanup-description = Rewrites phone numbers as E.164, such as +4930123456, and removes mailto: prefixes, display names and uppercase domains from email addresses. National numbers are read in the default country.
+cleanup-enable = Normalize contact values
+cleanup-country = Default country
+cleanup-country-none = None, international numbers only
+cleanup-clean = All values are already clean.
+cleanup-rewrote = { $cleaned ->
+    [one] Rewrote 1 value, listed in the findings.
+   *[other] Rewrote { $cleaned } values, listed in the findings.
+}
+cleanup-rewrote-invalid = { $cleaned ->
+    [one] Rewrote 1 value
+   *[other] Rewrote { $cleaned } values
+} and kept { $invalid } that are not valid, listed in the findings.
+
+# Random samples
+samples-title = Generate a random sample
+samples-description = Builds a valid document with random recurrence rules, time zones, attendees, alarms and less common properties, so every sample exercises different conversion paths.
+samples-any-format = Any format
+samples-new = New sample
+samples-seed = Seed
+samples-rebuild = Rebuild from seed
+samples-invalid-seed = The seed must be a whole number.
+samples-generated = { $format } sample from seed { $seed }. Rebuild it from the same seed and format.
+
+# Property usage
+usage-title = Property usage
+usage-description = Counts how often each property and parameter appears across every entry of the open documents and how many findings it drew, so the conversion paths a corpus relies on can be tested first. The command line writes the same table with { $option }.
+usage-analyze = Analyze open documents
+usage-download = Download CSV
+usage-summary = { $entries } entries, { $failed } failed to convert, { $total } properties and parameters.
+usage-name = Name
+usage-format = Format
+usage-occurrences = Occurrences
+usage-entries = Entries
+usage-findings = Findings
+usage-show-all = Show all { $count }
+
+# Semantic comparison
+semantic-title = Compare two inputs
+semantic-description = Paste two calendars or two contacts, in any supported format. Both are read into JSCalendar or JSContact and compared by meaning: order, folding, syntax, ids of maps and server timestamps are ignored.
+semantic-first-input = First input
+semantic-second-input = Second input
+semantic-use-current = Use the current source
+semantic-same = Both inputs mean the same.
+semantic-differences = { $count ->
+    [one] 1 difference
+   *[other] { $count } differences
+}
+semantic-difference = Difference
+semantic-first = First
+semantic-second = Second
+
+# Pager
+pager-count = { $count } { $noun }
+pager-range = { $start }–{ $end } of { $total } { $noun }
+pager-page = Page { $page } of { $pages }
+pager-previous = Previous
+pager-next = Next
+pager-rows = { $count ->
+    [one] row
+   *[other] rows
+}
+pager-occurrences = { $count ->
+    [one] occurrence
+   *[other] occurrences
+}
+pager-entries = { $count ->
+    [one] entry
+   *[other] entries
+}
+pager-alarms = { $count ->
+    [one] alarm
+   *[other] alarms
+}
+
+# Copy and download
+export-copy = Copy
+export-copied = Copied
+export-copied-toast = Copied { $format } to the clipboard.
+export-copy-failed = Copy failed
+export-copy-failed-toast = Copying to the clipboard failed.
+export-download = Download
+export-download-failed = Download failed
+
+# Event summary
+event-summary-title = Summary
+event-summary-location = Location
+event-summary-organizer = Organizer
+event-summary-attendees = Attendees
+event-summary-description = Description
+event-summary-alarms = Alarms
+event-summary-accepted = Accepted
+event-summary-declined = Declined
+event-summary-tentative = Tentative
+event-summary-no-reply = No reply
+
+# Colors
+colors-title = Colors and categories
+colors-source = Source
+colors-converted = Converted
+
+# Metrics
+metrics-not-measured = Not measured
+metrics-phases = parse { $parse } · conversion { $conversion } · roundtrip { $roundtrip }
+metrics-expansion = expansion { $elapsed }
+metrics-sizes = input { $input } · output { $output } · roundtrip { $roundtrip }
+metrics-run = Run { $count } iterations
+metrics-benchmark = { $done } of { $count } iterations, mean { $mean } · fastest { $fastest } · slowest { $slowest }
+
+# Organization chart
+orgchart-title = Organization chart
+orgchart-description = { $count ->
+    [one] 1 person grouped by organization and department.
+   *[other] { $count } people grouped by organization and department.
+}
+orgchart-export = Export JSON
+
+# Alarm timeline
+alarms-title = Alarms
+alarms-fires = Fires
+alarms-reminds-of = Reminds of
+alarms-trigger = Trigger
+alarms-action = Action
+alarms-line = VALARM on line { $line }
+alarms-past = Already past
+alarms-fixed = Fixed time, once
+alarms-repeat = Repeat { $count }
+
+# Shift generator
+shifts-title = Generate a shift pattern
+shifts-on-off = N days on / M days off
+shifts-alternating = Alternating weeks
+shifts-monthly = Weekday of the month
+shifts-summary = Summary
+shifts-default-summary = Shift
+shifts-days-on = days on,
+shifts-days-off = days off
+shifts-week-a = Week A
+shifts-week-b = Week B
+shifts-week-a-start = Week A starts on the Monday of the first day.
+shifts-first = 1st
+shifts-second = 2nd
+shifts-third = 3rd
+shifts-fourth = 4th
+shifts-last = Last
+shifts-first-day = First day
+shifts-at = at
+shifts-for = for
+shifts-hours = hours,
+shifts-repetitions = repetitions
+shifts-timezone = Timezone (e.g. Europe/Berlin)
+shifts-generate = Generate
+shifts-verified = { $count ->
+    [one] Expansion verified: the expected shift was produced.
+   *[other] Expansion verified: all { $count } expected shifts were produced.
+}
+shifts-mismatch = Expansion mismatch: expected { $count } shifts, expanded { $expanded }. Missing: [{ $missing }]. Unexpected: [{ $unexpected }].
+
+# Tour
+tour-step = Step { $step } of { $steps }
+tour-end = End tour
+tour-back = Back
+tour-next = Next
+tour-finish = Finish
+tour-sample = Tour sample
+tour-detection = Format detection
+tour-detection-text = Paste or drop a file here. Its format is told from the first line or, for JSON, from its @type. This sample is an iCalendar (RFC 5545) meeting request sent by Exchange, so it is converted to JSCalendar (RFC 8984). vCards convert to JSContact (RFC 9553) the same way, and JSON converts back.
+tour-conversion = Conversion
+tour-conversion-text = This is the sample as JSCalendar, the format JMAP servers exchange. Properties become JSON members: DTSTART and its TZID turn into start and timeZone, DTEND into a duration, the RRULE into recurrenceRules and every ATTENDEE into an entry of participants.
+tour-roundtrip = Round trip
+tour-roundtrip-text = The JSCalendar converted back to iCalendar. Comparing it with the source shows what the mapping keeps; the fidelity report and the diff next to it point out anything lost or rewritten on the way.
+tour-expansion = Recurrence expansion
+tour-expansion-text = The recurrence rule expanded into occurrences, in the time zone of your choice. This is what a client gets when it asks a JMAP server for the instances of a recurring event.
+
+# Time probe
+probe-title = Is this event happening at…
+probe-enter-date = Enter a date and time.
+probe-unknown-timezone = Unknown time zone: { $timezone }
+probe-nonexistent = { $at } does not exist in { $timezone }.
+probe-unparsable = The calendar could not be parsed.
+probe-no-events = The calendar has no events.
+probe-occurring = Yes, { $start } to { $end }
+probe-next = No, next on { $next }
+probe-not-again = No, and it does not happen again
+probe-inconclusive = Unknown, too many occurrences before this time
+probe-recurring = recurring
+
+# History
+recent-clear = Clear
+recent-close = Close
+recent-empty = Inputs appear here once they are converted.
+recent-load = Load into the editor
+recent-remove = Remove from history
+recent-unknown-format = Unknown format
+recent-just-now = Just now
+recent-minutes-ago = { $count } min ago
+recent-hours-ago = { $count } h ago
+
+# Status editor
+status-field-status = Status and progress
+status-field-transparency = Free/busy status
+status-field-classification = Privacy
+status-component = Component
+status-dropped = None, dropped on export
+status-missing = no { $property }
+status-unknown = unknown
+status-untitled = Untitled
+status-select-all = Select all
+status-set-field = Set { $field }
+status-set = { $count ->
+    [one] Set 1 component to { $value }.
+   *[other] Set { $count } components to { $value }.
+}
+status-set-skipped = { $count ->
+    [one] Set 1 component to { $value }, skipped { $skipped } that do not accept it.
+   *[other] Set { $count } components to { $value }, skipped { $skipped } that do not accept it.
+}
+
+# Scheduling
+scheduling-title = Scheduling message
+scheduling-reply-requested = Reply requested
+scheduling-reply-as = Reply as
+scheduling-accept = Accept
+scheduling-maybe = Maybe
+scheduling-decline = Decline
+scheduling-generate = Generate REPLY
+scheduling-open = Open in converter
+
+# Gender and pronouns
+identity-title = Gender and pronouns
+identity-source = Source
+identity-converted = Converted
+identity-roundtrip = Round trip
+identity-gender = Gender
+identity-grammatical-gender = Grammatical gender
+identity-pronouns = Pronouns
+identity-vendor = Vendor properties
+
+# Diff
+diff-title = Diff source and roundtrip
+diff-source = Source
+diff-roundtrip = Roundtrip
+diff-identical = Identical output.
+diff-unified = Unified
+diff-side-by-side = Side by side
+diff-only-changes = Only changes
+compare-run = Compare { $current } with { $previous }
+compare-failed = { $version } failed: { $error }
+compare-converted = Converted output
+compare-roundtrip = Round trip
+
+# Description round trip
+description-title = Description round trip
+description-original = Original
+description-raw = Raw value: { $value }
+description-html-preview = HTML preview (sanitized)
+description-converted = Converted
+description-converted-type = Converted ({ $type })
+description-roundtrip = Round trip
+description-missing = Description missing.
+
+# Validation
+validation-convert = Convert
+validation-validate = Validate
+validation-title = Validation report
+validation-counts = { $errors ->
+    [one] 1 error
+   *[other] { $errors } errors
+}, { $warnings ->
+    [one] 1 warning
+   *[other] { $warnings } warnings
+}
+validation-none = No conformance problems found.
+validation-severity = Severity
+validation-code = Code
+validation-problem = Problem
+validation-location = Location
+severity-info = info
+severity-warning = warning
+severity-error = error
+
+# Parsing mode
+recovery-title = Parsing mode
+recovery-description = Lenient parsing skips or coerces lines that do not follow the RFC and carries on. Strict parsing fails an entry on the first one, with its line and what was wrong.
+recovery-lenient = Lenient
+recovery-strict = Strict
+recovery-none = No recoveries were needed.
+recovery-refused = { $count ->
+    [one] 1 recovery refused
+   *[other] { $count } recoveries refused
+}
+recovery-made = { $count ->
+    [one] 1 recovery made
+   *[other] { $count } recoveries made
+}
+recovery-where = Where
+recovery-problem = Problem
+recovery-action = Lenient parsing
+
+# Property editor
+properties-title = Edit properties
+properties-description = Change a value and press Enter or leave the field to rewrite that property and convert again.
+properties-filter = Filter by property or component
+properties-component = Component
+properties-property = Property
+properties-value = Value
+properties-text = Text, written escaped
+properties-raw = Raw value
+
+# Permalink
+permalink-title = Share a link
+permalink-description = Packs the source and the options into the address of the page, compressed, so a reproduction can be pasted into a bug report. Opening the link converts it straight away. Nothing is sent to a server, as the part after # stays in the browser.
+permalink-copy = Copy link
+permalink-copied = Copied the link to the clipboard.
+permalink-copy-failed = Copying to the clipboard failed, the link is in the address bar.
+permalink-too-long = The link is { $length } characters long and may be cut short where it is pasted. Consider attaching a session bundle instead.
+
+# Fidelity report
+fidelity-title = Fidelity report
+fidelity-description = Compares the source with its roundtrip through { $format }, property by property. Values that only changed form are not listed.
+fidelity-none = Every property of the source came back from { $format }.
+fidelity-summary = { $count ->
+    [one] 1 loss in the roundtrip: { $kinds }.
+   *[other] { $count } losses in the roundtrip: { $kinds }.
+}
+fidelity-dropped = Dropped
+fidelity-renamed = Renamed
+fidelity-parameter-lost = Parameter lost
+fidelity-dropped-count = { $count } dropped
+fidelity-renamed-count = { $count } renamed
+fidelity-parameter-lost-count = { $count ->
+    [one] 1 parameter lost
+   *[other] { $count } parameters lost
+}
+fidelity-property = Property
+fidelity-lost = Lost
+fidelity-original = Original value
+fidelity-now = Now { $name }
+
+# Expected output
+expected-title = Compare with expected output
+expected-description = Paste the { $format } another tool or server produced for the same input. Order, folding, product ids and the ids of JSON maps are ignored.
+expected-placeholder = Expected output
+expected-none = No disagreements with the expected output.
+expected-summary = { $count ->
+    [one] 1 disagreement: { $kinds }.
+   *[other] { $count } disagreements: { $kinds }.
+}
+expected-different = Different
+expected-missing = Only in expected
+expected-unexpected = Only in ours
+expected-different-count = { $count } different
+expected-missing-count = { $count } only in expected
+expected-unexpected-count = { $count } only in ours
+expected-path = Path
+expected-ours = Ours
+expected-expected = Expected
+
+# Workspace
+workspace-source = Source
+workspace-converted = Converted
+workspace-roundtrip = Round trip
+workspace-warnings = Warnings
+
+# Duration statistics
+stats-window = Window
+stats-scheduled = Scheduled
+stats-average = Average length
+stats-shortest-longest = Shortest / longest
+stats-busiest-day = Busiest day
+stats-busiest-day-value = { $count ->
+    [one] { $day } ({ $duration }, 1 event)
+   *[other] { $day } ({ $duration }, { $count } events)
+}
+stats-weekday = { $count ->
+    [one] { $day }: 1 event, { $hours }h
+   *[other] { $day }: { $count } events, { $hours }h
+}
+
+# Self-test
+selftest-title = Self-test
+selftest-description = Runs every bundled sample and your snippets through parse, conversion and round trip using calcard as built into v{ $version }.
+selftest-placeholder = Paste an additional iCalendar, JSCalendar, vCard or JSContact snippet
+selftest-add = Add snippet ({ $count } added)
+selftest-run = Run self-test
+selftest-summary = { $passed } of { $count } passed in { $elapsed }ms
+selftest-sample = Sample
+selftest-format = Format
+selftest-parse = Parse
+selftest-convert = Convert
+selftest-roundtrip = Round trip
+selftest-reparse = Re-parse
+
+# Vendor extensions
+sanitize-title = Vendor extensions
+sanitize-description = Strips X- properties, components and parameters, and vendor-prefixed JSON properties such as "example.com:foo", to produce standards-only output.
+sanitize-enable = Strip vendor extensions
+sanitize-keep = Keep, e.g. X-WR-CALNAME, X-APPLE-STRUCTURED-LOCATION
+sanitize-removed = { $count ->
+    [0] No vendor extensions found.
+    [one] Removed 1 item, listed in the findings.
+   *[other] Removed { $count } items, listed in the findings.
+}
+
+# Conversion report
+report-title = Conversion report
+report-description = A JSON summary of the input, the detected format, the findings of every entry with their codes, how each roundtrip went and how long it took. The command line writes the same report with { $option }, so conversion quality can be tracked across a whole corpus.
+report-download = Download report
+report-nothing = Convert something first.
+report-failed = The report could not be downloaded.
+
+# Patches
+patches-title = Diff two versions
+patches-description = Paste two versions of a JSCalendar or JSContact object to get the patch that updates the first into the second, as the update of a JMAP /set call.
+patches-before = Before
+patches-after = After
+patches-use-current = Use the current object
+
+# Findings
+findings-title = Findings
+findings-all = All severities
+findings-warnings = Warnings and errors
+findings-errors = Errors only
+findings-filter = Filter by code, e.g. CAL-
+findings-suppress = Suppress
+findings-unsuppress = Click to stop suppressing this code
+findings-hidden = { $hidden } of { $count } findings hidden by filters.
+
+# Time zone inspector
+vtimezone-title = { $warnings ->
+    [0] Time zones: { $count } defined
+   *[other] Time zones: { $count } defined, { $warnings } with warnings
+}
+vtimezone-mapped = Converted as { $tz }.
+vtimezone-unused-resolves = Not used by any event, resolves to { $tz }.
+vtimezone-unused = Not used by any event.
+vtimezone-standard = Standard
+vtimezone-daylight = Daylight
+
+# Remote source
+remote-title = Open a URL
+remote-placeholder = https://example.com/calendar.ics or webcal://…
+remote-fetch = Fetch
+remote-fetching = Fetching…
+remote-limits = Files up to { $size } MB. Servers that do not allow cross-origin requests need the CORS proxy set in the settings below.
+remote-loaded = Loaded { $url }.
+remote-loaded-cached = Loaded { $url } from the cache. Bypass the cache in the settings to fetch it again.
+
+# Normalization
+normalize-title = Normalize
+normalize-description = Rewrites the source in canonical form: lines folded at 75 octets, components and properties sorted, parameters sorted and uppercased, date-times in UTC unless they belong to a recurring series or a floating time, and JSON keys sorted. Two exports of the same data then differ only where the data does.
+normalize-run = Normalize
+normalize-changed = Normalized, undo to revert.
+normalize-unchanged = Already in canonical form.
+
+# Merge
+merge-title = Merge files
+merge-description = Combines several iCalendar or vCard files into one, opened in a new document. Time zones defined by more than one file are written once, and entries sharing a UID are reported.
+merge-output = Merged calendars as
+merge-output-calendar = A single VCALENDAR
+merge-output-group = A JSCalendar Group
+merge-choose = Choose files to merge
+merge-summary = Merged { $files } files into { $entries } entries and { $timezones } time zones.
+
+# Contact links
+links-title = Media, directories and calendars
+links-collection = Collection
+links-kind = Kind
+links-uri = URI
+links-media-type = Media type
+links-check = Check
+links-ok = OK
+links-pref = pref { $pref }
+links-placed = in { $expected }
+links-misplaced = in { $found }, expected { $expected }
+links-missing = missing, expected in { $expected }
+
+# vCard version
+vcard-version-title = vCard version
+vcard-version-detected = The source is vCard { $version }. Write it as
+vcard-version-write = Write it as
+vcard-version-target = Target vCard version
+vcard-version-unrepresentable = Not representable in vCard { $version }
+
+# Repair
+repair-title = Repair an exported calendar
+repair-run = Repair
+repair-nothing = Nothing to repair for { $profile }.
+repair-applied = { $count ->
+    [one] Applied { $count } { $profile } fix, undo to revert:
+   *[other] Applied { $count } { $profile } fixes, undo to revert:
+}
+repair-notes = Left as they were, check them yourself:
+
+# Free/busy
+freebusy-title = Free/busy: { $freebusy } VFREEBUSY, { $availability } VAVAILABILITY
+freebusy-kind-freebusy = Free/busy
+freebusy-kind-availability = Availability
+freebusy-no-span = No DTSTART and DTEND
+freebusy-outside = { $status } outside of the available slots.
+freebusy-no-periods = No periods.
+freebusy-free = Free
+freebusy-busy = Busy
+freebusy-tentative = Tentative
+freebusy-unavailable = Unavailable
+
+# Documents
+documents-name = Document name
+documents-rename = Double-click to rename
+documents-close = Close document
+documents-new = New document
+
+# QR code
+qr-show = QR
+qr-hide = Hide QR code
+qr-payload = QR code payload
+qr-size = { $size } of { $max } bytes
+qr-code = QR code
+
+# Phonetic names
+phonetic-title = Phonetic names and sounds
+phonetic-not-playable = not playable in the browser
+
+# Time zone picker
+timezone-title = Time zone the occurrences are shown in
+timezone-as-written = As written
+
+# Web page extraction
+html-extract-title = Web page
+html-extract-found = Found { $found }.
+html-extract-schema-org = { $count ->
+    [one] { $count } schema.org event
+   *[other] { $count } schema.org events
+}
+html-extract-hcalendar = { $count ->
+    [one] { $count } hCalendar event
+   *[other] { $count } hCalendar events
+}
+html-extract-hcards = { $count ->
+    [one] { $count } hCard
+   *[other] { $count } hCards
+}
+html-extract-events-only = Only the events are converted.
+html-extract-skipped = { $count ->
+    [one] { $count } event without a start date was left out.
+   *[other] { $count } events without a start date were left out.
+}
+
+# Heatmap
+heatmap-busiest = Busiest hour: { $day } { $hour } with { $count } occurrences.
+heatmap-cell = { $day } { $hour }: { $count } occurrences
+
+# Component filter
+filter-title = Component types
+filter-description = Unchecked types are dropped from the output and the expansion, for clients that duplicate alarms or cannot handle tasks.
+filter-events = Events
+filter-todos = To-dos
+filter-journals = Journals
+filter-freebusy = Free/busy
+filter-timezones = Time zones
+filter-alarms = Alarms
+filter-removed = { $count ->
+    [one] { $count } component left out.
+   *[other] { $count } components left out.
+}
+
+# Entries
+entries-title = { $failed ->
+    [0] { $count } entries
+   *[other] { $count } entries, { $failed } failed
+}
+entries-all = All entries
+entries-untitled = Untitled
+entries-findings = { $count ->
+    [one] { $count } finding
+   *[other] { $count } findings
+}
+
+# Email message
+email-title = Email message
+email-no-parts = The message has no text/calendar, text/vcard or text/directory part.
+email-part = Part { $path } · { $type }
+
+# CSV contacts
+csv-title = CSV contacts, { $layout } layout: { $mapped } of { $count } columns mapped
+csv-layout-google = Google Contacts
+csv-layout-outlook = Outlook
+csv-layout-generic = Generic
+csv-kind-home = home
+csv-kind-work = work
+csv-kind-mobile = mobile
+csv-kind-other = other
+csv-address-pobox = PO box
+csv-address-street = street
+csv-address-city = city
+csv-address-region = region
+csv-address-code = postal code
+csv-address-country = country
+csv-field-ignore = Ignore
+csv-field-full-name = Full name
+csv-field-given-name = Given name
+csv-field-family-name = Family name
+csv-field-additional-name = Middle name
+csv-field-prefix = Name prefix
+csv-field-suffix = Name suffix
+csv-field-nickname = Nickname
+csv-field-organization = Organization
+csv-field-department = Department
+csv-field-title = Job title
+csv-field-birthday = Birthday
+csv-field-url = Website
+csv-field-note = Note
+csv-field-categories = Categories
+csv-field-type = Type of the next columns
+csv-field-email = Email ({ $kind })
+csv-field-phone = Phone ({ $kind })
+csv-field-address = Address ({ $kind }), { $part }
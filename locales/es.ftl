@@ -0,0 +1,853 @@
+# Page header
+header-title = Conversión de JSCalendar y JSContact
+header-subtitle = Conversión en ambos sentidos entre JSCalendar/iCalendar y JSContact/vCard.
+header-history = Historial
+header-history-title = Entradas convertidas recientemente
+header-tour = Hacer el recorrido
+header-tour-title = Recorrer la detección, la conversión, la conversión de vuelta y la expansión con un ejemplo
+header-split-view = Vista dividida
+header-stacked-view = Vista apilada
+header-install = Instalar aplicación
+header-install-title = Instalar el conversor como aplicación que también funciona sin conexión
+theme-title = Tema de color
+theme-system = Tema del sistema
+theme-light = Claro
+theme-dark = Oscuro
+language-title = Idioma
+language-browser = Idioma del navegador
+
+# Editor
+editor-placeholder = Pega o suelta aquí un archivo iCalendar, JSCalendar, vCard, JSContact, jCal o jCard, una exportación CSV de contactos o un correo con una invitación o una vCard adjunta. O haz clic en los destellos para probar un ejemplo.
+editor-drop = Suelta un archivo .ics, .vcf o .json para cargarlo
+editor-reading = Leyendo { $name }… { $percent } %
+editor-undo = Deshacer (Ctrl+Z)
+editor-redo = Rehacer (Ctrl+Mayús+Z)
+editor-sample = Cargar un ejemplo al azar (Ctrl+K)
+editor-open = Abrir un archivo
+error-show = Mostrar { $location } en el editor
+error-show-title = Seleccionar el error en el editor
+error-report-bug = Informar de este error
+error-report-bug-title = Abrir una incidencia en GitHub con la versión, el formato y la entrada, con las direcciones de correo y los datos binarios sustituidos
+error-report-bug-copied = Se copió la entrada al portapapeles; pégala en la incidencia.
+error-report-bug-copy-failed = No se pudo copiar la entrada al portapapeles; adjúntala a la incidencia.
+conversion-progress = { $done } entradas convertidas, { $percent } % del origen…
+conversion-cancel = Cancelar
+conversion-cancelled = Conversión cancelada. Al editar el origen se vuelve a convertir.
+conversion-all-failed = No se pudo convertir ninguna de las { $count } entradas.
+source-line = línea { $line }
+
+# Conversion results
+results-title = Resultados de la conversión
+results-converted = Así se ve tu { $source } en formato { $target }:
+results-roundtrip = Y así se vería convertido de vuelta a { $source }:
+output-format = Formato de salida
+output-jmap-request = Petición JMAP
+output-explain = Explicar la conversión
+output-explain-title = Anotar cada campo con la propiedad de origen de la que procede
+output-download = Descargar
+output-jmap-failed = La salida no se puede enviar como petición JMAP: { $error }
+footer-report-bug = Informar de un error
+footer-view-source = Ver el código fuente
+
+# Calendar expansion
+expansion-title = Resultados de la expansión del calendario
+expansion-from-start = Desde la primera repetición
+expansion-from-now = Desde ahora
+expansion-between = Entre dos fechas
+expansion-count-title = Número máximo de repeticiones
+expansion-budget-title = Tiempo máximo para la expansión, en milisegundos
+expansion-download-ics = Descargar .ics
+expansion-download-csv = Descargar .csv
+expansion-range-from = Desde
+expansion-range-to = hasta
+expansion-summary-start = Estas son las primeras { $count } repeticiones del evento pegado:
+expansion-summary-now = Estas son las próximas { $count } repeticiones del evento pegado:
+expansion-summary-range = Estas son las { $count } repeticiones del evento pegado entre las fechas elegidas:
+expansion-elapsed = Expandido en { $elapsed } ms.
+expansion-elapsed-budget = Expandido en { $elapsed } ms, detenido por el límite de { $budget } ms.
+expansion-copy-json = Copiar como JSON
+expansion-out-of-budget = { $count ->
+    [one] La expansión se detuvo tras { $elapsed } ms con { $count } repetición; puede que falten las siguientes.
+   *[other] La expansión se detuvo tras { $elapsed } ms con { $count } repeticiones; puede que falten las siguientes.
+}
+expansion-limit-reached = Solo se expandieron las primeras { $count } repeticiones.
+expansion-none-start = El calendario contiene eventos, tareas o entradas de diario, pero la expansión no produjo ninguna repetición.
+expansion-none-now = El calendario no tiene repeticiones próximas.
+expansion-none-range = El calendario no tiene repeticiones entre estas fechas.
+occurrence-type = Tipo
+occurrence-from = Inicio
+occurrence-to = Fin
+occurrence-notes = Notas
+occurrence-due = Vence
+occurrence-floating = Sin zona horaria
+occurrence-added = Añadida por RDATE
+occurrence-modified = Modificada
+occurrence-modified-summary = Modificada: { $summary }
+occurrence-modified-title = Reemplazada por un RECURRENCE-ID
+occurrence-removed = Eliminada por EXDATE
+occurrence-outside-hours = Fuera del horario laboral
+occurrence-outside-hours-finding = La repetición está fuera del horario laboral.
+occurrence-on-holiday-finding = La repetición cae en { $holiday }.
+date-zone-title = Zona horaria de las fechas
+date-zone-display = En la zona horaria de la expansión
+date-zone-event = En la zona horaria del evento
+date-display-title = Formato de fecha
+date-display-locale = Fechas localizadas
+date-display-iso8601 = ISO 8601
+date-display-rfc3339 = RFC 3339
+date-display-epoch = Tiempo Unix
+entry-kind-event = Evento
+entry-kind-task = Tarea
+entry-kind-journal = Entrada de diario
+
+# Compatibility check
+compatibility-title = Comprobación de compatibilidad
+compatibility-title-warnings = Comprobación de compatibilidad, { $count ->
+    [one] 1 advertencia
+   *[other] { $count } advertencias
+}
+compatibility-description = Busca construcciones que los clientes habituales descartan o interpretan mal al importar el iCalendar o la vCard, como zonas horarias sin definición, reglas de repetición no admitidas y propiedades de vCard 4.0.
+compatibility-no-problems = Ningún problema conocido
+compatibility-warnings = { $count ->
+    [one] 1 advertencia
+   *[other] { $count } advertencias
+}
+
+# Anonymization
+anonymize-title = Anonimizar para compartir
+anonymize-description = Sustituye nombres, direcciones de correo, números de teléfono, descripciones y adjuntos por marcadores, conservando la estructura, las reglas de repetición y las zonas horarias, para que la entrada pueda compartirse en un informe de error público. El mismo valor recibe siempre el mismo marcador.
+anonymize-run = Anonimizar
+anonymize-nothing = No hay nada que anonimizar.
+anonymize-replaced = { $count ->
+    [one] Se sustituyó 1 valor, deshaz para revertirlo.
+   *[other] Se sustituyeron { $count } valores, deshaz para revertirlos.
+}
+anonymize-failed = No se pudo anonimizar la entrada: { $error }
+
+# Duplicate contacts
+dedup-title = Posibles duplicados
+dedup-summary = { $groups ->
+    [one] 1 grupo
+   *[other] { $groups } grupos
+} entre { $cards } tarjetas comparten una dirección de correo, un número de teléfono o un nombre.
+dedup-merge-all = Combinar todos los grupos
+dedup-same = En común: { $reasons }
+dedup-merged = Tarjeta combinada
+
+# JSON diagnostics
+json-ignored-count = { $count ->
+    [one] 1 propiedad ignorada
+   *[other] { $count } propiedades ignoradas
+}
+json-ignored-description = Propiedades del origen que la conversión no conservó o cuyo valor no es del tipo que define RFC 8984 o RFC 9553. No se listan las propiedades que solo tenían un valor por defecto.
+json-problem-syntax = JSON no válido en la línea { $line }, columna { $column }: { $message }
+json-problem-wrong-type = Se esperaba { $expected }, se encontró { $found }
+json-problem-unknown = Propiedad desconocida, probablemente ignorada
+json-problem-ignored = Ignorada por la conversión
+
+# Keyboard shortcuts
+shortcuts-title = Atajos de teclado
+shortcuts-help = En macOS usa Cmd en lugar de Ctrl. Deshacer y rehacer en el editor solo recorren el historial cuando lo escrito ya se ha convertido.
+shortcut-key-ctrl = Ctrl
+shortcut-key-shift = Mayús
+shortcut-key-enter = Intro
+shortcut-convert = Convertir
+shortcut-download = Descargar la salida
+shortcut-sample = Cargar un ejemplo al azar
+shortcut-undo = Deshacer
+shortcut-redo = Rehacer
+
+# Settings
+settings-title = Ajustes
+settings-cors-proxy = Proxy CORS
+settings-cors-proxy-help = Se usa en cada descarga remota. La URL se añade al proxy o sustituye a { $placeholder } si aparece.
+settings-bypass-cache = Descargar siempre de la red
+settings-cached = { $count ->
+    [one] 1 recurso en caché
+   *[other] { $count } recursos en caché
+}
+settings-cache-unavailable = Caché sin conexión no disponible
+settings-clear-cache = Vaciar la caché
+settings-copy-header = Cabecera de contexto al copiar
+settings-copy-header-help = La salida copiada puede empezar con el formato, el tipo de origen, la versión de la herramienta, las opciones y la hora de la exportación. Al pegarla de nuevo se quita la cabecera.
+header-style-off = Sin cabecera
+header-style-comments = Líneas de comentario
+header-style-sidecar = Archivo JSON adjunto
+settings-redact = Ocultar los eventos privados y confidenciales al copiar
+settings-redact-help = De los eventos clasificados como privados o confidenciales solo se copian los bloques de tiempo, tal como los servidores CalDAV los muestran a otros usuarios.
+settings-live = Convertir mientras escribes
+settings-live-delay = tras
+settings-live-delay-unit = ms sin escribir
+settings-live-limit = Los orígenes de más de { $size } KB solo se convierten cuando el editor pierde el foco.
+settings-metrics = Mostrar métricas de rendimiento bajo los resultados
+
+# Working hours
+working-hours-title = Horario laboral y festivos
+working-hours-to = a
+weekday-mon = lun
+weekday-tue = mar
+weekday-wed = mié
+weekday-thu = jue
+weekday-fri = vie
+weekday-sat = sáb
+weekday-sun = dom
+overlay-annotate = Anotar todas las repeticiones
+overlay-hide = Ocultar festivos y fuera de horario
+overlay-only = Mostrar solo festivos y fuera de horario
+holidays-paste = Pega un calendario de festivos (iCalendar)
+holidays-url = O carga un calendario de festivos desde una URL
+holidays-load = Cargar
+holidays-none = No hay festivos cargados.
+holidays-loaded = { $count ->
+    [one] 1 fecha festiva cargada.
+   *[other] { $count } fechas festivas cargadas.
+}
+
+# Previews
+preview-contact = Vista previa del contacto
+preview-invitation = Vista previa de la invitación
+preview-organizer = Organizador
+preview-join = Unirse
+preview-radius = Radio de { $radius } m
+
+# Session bundle
+bundle-title = Paquete de sesión
+bundle-description = Empaqueta el origen, todas las opciones, las salidas, los hallazgos y la versión de la herramienta en un archivo JSON, para adjuntar una sesión de depuración a una incidencia y reabrirla más tarde tal como estaba.
+bundle-export = Exportar la sesión
+bundle-import = Importar una sesión
+bundle-restored = Se restauró la sesión exportada por la versión { $version }.
+bundle-restored-on = Se restauró la sesión exportada por la versión { $version } el { $date }.
+bundle-still-converting = El origen aún se está convirtiendo, así que no se compararon sus salidas.
+bundle-unchanged = Las salidas no han cambiado.
+bundle-changed-conversion = Esta versión produce una conversión distinta de la del paquete.
+bundle-changed-roundtrip = Esta versión produce una ida y vuelta distinta de la del paquete.
+bundle-changed-both = Esta versión produce una conversión y una ida y vuelta distintas de las del paquete.
+
+# Contact cleanup
+cleanup-title = Números de teléfono y direcciones de correo
+cleanup-description = Reescribe los números de teléfono en formato E.164, como +4930123456, y quita de las direcciones de correo los prefijos mailto:, los nombres visibles y los dominios en mayúsculas. Los números nacionales se leen en el país por defecto.
+cleanup-enable = Normalizar los valores de contacto
+cleanup-country = País por defecto
+cleanup-country-none = Ninguno, solo números internacionales
+cleanup-clean = Todos los valores ya están limpios.
+cleanup-rewrote = { $cleaned ->
+    [one] Se reescribió 1 valor, listado en los hallazgos.
+   *[other] Se reescribieron { $cleaned } valores, listados en los hallazgos.
+}
+cleanup-rewrote-invalid = { $cleaned ->
+    [one] Se reescribió 1 valor
+   *[other] Se reescribieron { $cleaned } valores
+} y se conservaron { $invalid } no válidos, listados en los hallazgos.
+
+# Random samples
+samples-title = Generar un ejemplo aleatorio
+samples-description = Crea un documento válido con reglas de repetición, zonas horarias, asistentes, alarmas y propiedades poco comunes aleatorias, para que cada ejemplo recorra caminos de conversión distintos.
+samples-any-format = Cualquier formato
+samples-new = Nuevo ejemplo
+samples-seed = Semilla
+samples-rebuild = Regenerar desde la semilla
+samples-invalid-seed = La semilla debe ser un número entero.
+samples-generated = Ejemplo { $format } de la semilla { $seed }. Se regenera con la misma semilla y el mismo formato.
+
+# Property usage
+usage-title = Uso de propiedades
+usage-description = Cuenta cuántas veces aparece cada propiedad y parámetro en todas las entradas de los documentos abiertos y cuántos hallazgos produjo, para probar primero los caminos de conversión de los que depende un corpus. La línea de comandos escribe la misma tabla con { $option }.
+usage-analyze = Analizar los documentos abiertos
+usage-download = Descargar CSV
+usage-summary = { $entries } entradas, { $failed } sin convertir, { $total } propiedades y parámetros.
+usage-name = Nombre
+usage-format = Formato
+usage-occurrences = Apariciones
+usage-entries = Entradas
+usage-findings = Hallazgos
+usage-show-all = Mostrar las { $count }
+
+# Semantic comparison
+semantic-title = Comparar dos entradas
+semantic-description = Pega dos calendarios o dos contactos, en cualquier formato admitido. Ambos se leen como JSCalendar o JSContact y se comparan por su significado: se ignoran el orden, el plegado, la sintaxis, los ids de los mapas y las marcas de tiempo del servidor.
+semantic-first-input = Primera entrada
+semantic-second-input = Segunda entrada
+semantic-use-current = Usar el origen actual
+semantic-same = Ambas entradas significan lo mismo.
+semantic-differences = { $count ->
+    [one] 1 diferencia
+   *[other] { $count } diferencias
+}
+semantic-difference = Diferencia
+semantic-first = Primera
+semantic-second = Segunda
+
+# Pager
+pager-count = { $count } { $noun }
+pager-range = { $start }–{ $end } de { $total } { $noun }
+pager-page = Página { $page } de { $pages }
+pager-previous = Anterior
+pager-next = Siguiente
+pager-rows = { $count ->
+    [one] fila
+   *[other] filas
+}
+pager-occurrences = { $count ->
+    [one] ocurrencia
+   *[other] ocurrencias
+}
+pager-entries = { $count ->
+    [one] entrada
+   *[other] entradas
+}
+pager-alarms = { $count ->
+    [one] alarma
+   *[other] alarmas
+}
+
+# Copy and download
+export-copy = Copiar
+export-copied = Copiado
+export-copied-toast = { $format } copiado al portapapeles.
+export-copy-failed = Error al copiar
+export-copy-failed-toast = No se pudo copiar al portapapeles.
+export-download = Descargar
+export-download-failed = Error al descargar
+
+# Event summary
+event-summary-title = Resumen
+event-summary-location = Ubicación
+event-summary-organizer = Organizador
+event-summary-attendees = Asistentes
+event-summary-description = Descripción
+event-summary-alarms = Alarmas
+event-summary-accepted = Aceptado
+event-summary-declined = Rechazado
+event-summary-tentative = Provisional
+event-summary-no-reply = Sin respuesta
+
+# Colors
+colors-title = Colores y categorías
+colors-source = Origen
+colors-converted = Convertido
+
+# Metrics
+metrics-not-measured = Sin medir
+metrics-phases = lectura { $parse } · conversión { $conversion } · ida y vuelta { $roundtrip }
+metrics-expansion = expansión { $elapsed }
+metrics-sizes = entrada { $input } · salida { $output } · ida y vuelta { $roundtrip }
+metrics-run = Ejecutar { $count } iteraciones
+metrics-benchmark = { $done } de { $count } iteraciones, media { $mean } · más rápida { $fastest } · más lenta { $slowest }
+
+# Organization chart
+orgchart-title = Organigrama
+orgchart-description = { $count ->
+    [one] 1 persona agrupada por organización y departamento.
+   *[other] { $count } personas agrupadas por organización y departamento.
+}
+orgchart-export = Exportar JSON
+
+# Alarm timeline
+alarms-title = Alarmas
+alarms-fires = Se activa
+alarms-reminds-of = Recuerda
+alarms-trigger = Disparador
+alarms-action = Acción
+alarms-line = VALARM en la línea { $line }
+alarms-past = Ya pasó
+alarms-fixed = Hora fija, una vez
+alarms-repeat = Repetir { $count }
+
+# Shift generator
+shifts-title = Generar un patrón de turnos
+shifts-on-off = N días de turno / M días libres
+shifts-alternating = Semanas alternas
+shifts-monthly = Día de la semana del mes
+shifts-summary = Título
+shifts-default-summary = Turno
+shifts-days-on = días de turno,
+shifts-days-off = días libres
+shifts-week-a = Semana A
+shifts-week-b = Semana B
+shifts-week-a-start = La semana A empieza el lunes de la semana del primer día.
+shifts-first = 1.º
+shifts-second = 2.º
+shifts-third = 3.º
+shifts-fourth = 4.º
+shifts-last = Último
+shifts-first-day = Primer día
+shifts-at = a las
+shifts-for = durante
+shifts-hours = horas,
+shifts-repetitions = repeticiones
+shifts-timezone = Zona horaria (p. ej. Europe/Berlin)
+shifts-generate = Generar
+shifts-verified = { $count ->
+    [one] Expansión verificada: se generó el turno esperado.
+   *[other] Expansión verificada: se generaron los { $count } turnos esperados.
+}
+shifts-mismatch = La expansión no coincide: se esperaban { $count } turnos y se expandieron { $expanded }. Faltan: [{ $missing }]. Inesperados: [{ $unexpected }].
+
+# Tour
+tour-step = Paso { $step } de { $steps }
+tour-end = Terminar la visita
+tour-back = Atrás
+tour-next = Siguiente
+tour-finish = Terminar
+tour-sample = Ejemplo del recorrido
+tour-detection = Detección del formato
+tour-detection-text = Pega o suelta aquí un archivo. Su formato se deduce de la primera línea o, en JSON, de su @type. Este ejemplo es una convocatoria de reunión iCalendar (RFC 5545) enviada por Exchange, así que se convierte a JSCalendar (RFC 8984). Las vCards se convierten a JSContact (RFC 9553) del mismo modo, y el JSON se convierte de vuelta.
+tour-conversion = Conversión
+tour-conversion-text = Este es el ejemplo como JSCalendar, el formato que intercambian los servidores JMAP. Las propiedades pasan a ser miembros JSON: DTSTART y su TZID se convierten en start y timeZone, DTEND en una duration, la RRULE en recurrenceRules y cada ATTENDEE en una entrada de participants.
+tour-roundtrip = Ida y vuelta
+tour-roundtrip-text = El JSCalendar convertido de vuelta a iCalendar. Compararlo con el origen muestra lo que conserva la correspondencia; el informe de fidelidad y el diff junto a él señalan lo que se pierde o se reescribe por el camino.
+tour-expansion = Expansión de la recurrencia
+tour-expansion-text = La regla de recurrencia expandida en ocurrencias, en la zona horaria que elijas. Es lo que recibe un cliente cuando pide a un servidor JMAP las instancias de un evento recurrente.
+
+# Time probe
+probe-title = ¿Ocurre este evento el…
+probe-enter-date = Introduce una fecha y una hora.
+probe-unknown-timezone = Zona horaria desconocida: { $timezone }
+probe-nonexistent = { $at } no existe en { $timezone }.
+probe-unparsable = No se pudo leer el calendario.
+probe-no-events = El calendario no tiene eventos.
+probe-occurring = Sí, de { $start } a { $end }
+probe-next = No, el próximo es el { $next }
+probe-not-again = No, y no vuelve a ocurrir
+probe-inconclusive = Desconocido, demasiadas ocurrencias antes de esta hora
+probe-recurring = recurrente
+
+# History
+recent-clear = Vaciar
+recent-close = Cerrar
+recent-empty = Las entradas aparecen aquí una vez convertidas.
+recent-load = Cargar en el editor
+recent-remove = Quitar del historial
+recent-unknown-format = Formato desconocido
+recent-just-now = Ahora mismo
+recent-minutes-ago = hace { $count } min
+recent-hours-ago = hace { $count } h
+
+# Status editor
+status-field-status = Estado y progreso
+status-field-transparency = Estado libre/ocupado
+status-field-classification = Privacidad
+status-component = Componente
+status-dropped = Ninguno, se pierde al exportar
+status-missing = sin { $property }
+status-unknown = desconocido
+status-untitled = Sin título
+status-select-all = Seleccionar todo
+status-set-field = Establecer { $field }
+status-set = { $count ->
+    [one] 1 componente establecido a { $value }.
+   *[other] { $count } componentes establecidos a { $value }.
+}
+status-set-skipped = { $count ->
+    [one] 1 componente establecido a { $value }; se omitieron { $skipped } que no lo admiten.
+   *[other] { $count } componentes establecidos a { $value }; se omitieron { $skipped } que no lo admiten.
+}
+
+# Scheduling
+scheduling-title = Mensaje de planificación
+scheduling-reply-requested = Se pide respuesta
+scheduling-reply-as = Responder como
+scheduling-accept = Aceptar
+scheduling-maybe = Quizás
+scheduling-decline = Rechazar
+scheduling-generate = Generar REPLY
+scheduling-open = Abrir en el conversor
+
+# Gender and pronouns
+identity-title = Género y pronombres
+identity-source = Origen
+identity-converted = Convertido
+identity-roundtrip = Ida y vuelta
+identity-gender = Género
+identity-grammatical-gender = Género gramatical
+identity-pronouns = Pronombres
+identity-vendor = Propiedades del fabricante
+
+# Diff
+diff-title = Diff entre el origen y la ida y vuelta
+diff-source = Origen
+diff-roundtrip = Ida y vuelta
+diff-identical = Salida idéntica.
+diff-unified = Unificado
+diff-side-by-side = En paralelo
+diff-only-changes = Solo cambios
+compare-run = Comparar { $current } con { $previous }
+compare-failed = { $version } falló: { $error }
+compare-converted = Salida convertida
+compare-roundtrip = Ida y vuelta
+
+# Description round trip
+description-title = Descripción tras la ida y vuelta
+description-original = Original
+description-raw = Valor sin procesar: { $value }
+description-html-preview = Vista previa HTML (saneada)
+description-converted = Convertido
+description-converted-type = Convertido ({ $type })
+description-roundtrip = Ida y vuelta
+description-missing = Falta la descripción.
+
+# Validation
+validation-convert = Convertir
+validation-validate = Validar
+validation-title = Informe de validación
+validation-counts = { $errors ->
+    [one] 1 error
+   *[other] { $errors } errores
+}, { $warnings ->
+    [one] 1 aviso
+   *[other] { $warnings } avisos
+}
+validation-none = No se encontraron problemas de conformidad.
+validation-severity = Gravedad
+validation-code = Código
+validation-problem = Problema
+validation-location = Ubicación
+severity-info = info
+severity-warning = aviso
+severity-error = error
+
+# Parsing mode
+recovery-title = Modo de lectura
+recovery-description = La lectura tolerante omite o corrige las líneas que no siguen el RFC y continúa. La lectura estricta hace fallar una entrada en la primera, indicando su línea y qué estaba mal.
+recovery-lenient = Tolerante
+recovery-strict = Estricta
+recovery-none = No hizo falta ninguna corrección.
+recovery-refused = { $count ->
+    [one] 1 corrección rechazada
+   *[other] { $count } correcciones rechazadas
+}
+recovery-made = { $count ->
+    [one] 1 corrección hecha
+   *[other] { $count } correcciones hechas
+}
+recovery-where = Dónde
+recovery-problem = Problema
+recovery-action = Lectura tolerante
+
+# Property editor
+properties-title = Editar propiedades
+properties-description = Cambia un valor y pulsa Intro o sal del campo para reescribir esa propiedad y convertir de nuevo.
+properties-filter = Filtrar por propiedad o componente
+properties-component = Componente
+properties-property = Propiedad
+properties-value = Valor
+properties-text = Texto, escrito con escapes
+properties-raw = Valor sin procesar
+
+# Permalink
+permalink-title = Compartir un enlace
+permalink-description = Empaqueta el origen y las opciones, comprimidos, en la dirección de la página, para poder pegar una reproducción en un informe de error. Al abrir el enlace se convierte directamente. No se envía nada a ningún servidor, ya que la parte tras # se queda en el navegador.
+permalink-copy = Copiar enlace
+permalink-copied = Enlace copiado al portapapeles.
+permalink-copy-failed = No se pudo copiar al portapapeles; el enlace está en la barra de direcciones.
+permalink-too-long = El enlace tiene { $length } caracteres y puede cortarse donde se pegue. Considera adjuntar un paquete de sesión en su lugar.
+
+# Fidelity report
+fidelity-title = Informe de fidelidad
+fidelity-description = Compara el origen con su ida y vuelta a través de { $format }, propiedad por propiedad. No se listan los valores que solo cambiaron de forma.
+fidelity-none = Todas las propiedades del origen volvieron de { $format }.
+fidelity-summary = { $count ->
+    [one] 1 pérdida en la ida y vuelta: { $kinds }.
+   *[other] { $count } pérdidas en la ida y vuelta: { $kinds }.
+}
+fidelity-dropped = Eliminada
+fidelity-renamed = Renombrada
+fidelity-parameter-lost = Parámetro perdido
+fidelity-dropped-count = { $count ->
+    [one] 1 eliminada
+   *[other] { $count } eliminadas
+}
+fidelity-renamed-count = { $count ->
+    [one] 1 renombrada
+   *[other] { $count } renombradas
+}
+fidelity-parameter-lost-count = { $count ->
+    [one] 1 parámetro perdido
+   *[other] { $count } parámetros perdidos
+}
+fidelity-property = Propiedad
+fidelity-lost = Perdido
+fidelity-original = Valor original
+fidelity-now = Ahora { $name }
+
+# Expected output
+expected-title = Comparar con la salida esperada
+expected-description = Pega el { $format } que otra herramienta o servidor produjo para la misma entrada. Se ignoran el orden, el plegado, los ids de producto y los ids de los mapas JSON.
+expected-placeholder = Salida esperada
+expected-none = No hay discrepancias con la salida esperada.
+expected-summary = { $count ->
+    [one] 1 discrepancia: { $kinds }.
+   *[other] { $count } discrepancias: { $kinds }.
+}
+expected-different = Distinto
+expected-missing = Solo en la esperada
+expected-unexpected = Solo en la nuestra
+expected-different-count = { $count ->
+    [one] 1 distinto
+   *[other] { $count } distintos
+}
+expected-missing-count = { $count } solo en la esperada
+expected-unexpected-count = { $count } solo en la nuestra
+expected-path = Ruta
+expected-ours = Nuestra
+expected-expected = Esperada
+
+# Workspace
+workspace-source = Origen
+workspace-converted = Convertido
+workspace-roundtrip = Ida y vuelta
+workspace-warnings = Avisos
+
+# Duration statistics
+stats-window = Periodo
+stats-scheduled = Programado
+stats-average = Duración media
+stats-shortest-longest = Más corto / más largo
+stats-busiest-day = Día más ocupado
+stats-busiest-day-value = { $count ->
+    [one] { $day } ({ $duration }, 1 evento)
+   *[other] { $day } ({ $duration }, { $count } eventos)
+}
+stats-weekday = { $count ->
+    [one] { $day }: 1 evento, { $hours } h
+   *[other] { $day }: { $count } eventos, { $hours } h
+}
+
+# Self-test
+selftest-title = Autoprueba
+selftest-description = Pasa cada ejemplo incluido y tus fragmentos por la lectura, la conversión y la ida y vuelta usando el calcard integrado en la v{ $version }.
+selftest-placeholder = Pega un fragmento adicional de iCalendar, JSCalendar, vCard o JSContact
+selftest-add = Añadir fragmento ({ $count } añadidos)
+selftest-run = Ejecutar la autoprueba
+selftest-summary = { $passed } de { $count } superadas en { $elapsed } ms
+selftest-sample = Ejemplo
+selftest-format = Formato
+selftest-parse = Lectura
+selftest-convert = Conversión
+selftest-roundtrip = Ida y vuelta
+selftest-reparse = Relectura
+
+# Vendor extensions
+sanitize-title = Extensiones del fabricante
+sanitize-description = Elimina propiedades, componentes y parámetros X- y propiedades JSON con prefijo de fabricante como "example.com:foo", para producir una salida que solo siga los estándares.
+sanitize-enable = Eliminar extensiones del fabricante
+sanitize-keep = Conservar, p. ej. X-WR-CALNAME, X-APPLE-STRUCTURED-LOCATION
+sanitize-removed = { $count ->
+    [0] No se encontraron extensiones del fabricante.
+    [one] Se eliminó 1 elemento, listado en los hallazgos.
+   *[other] Se eliminaron { $count } elementos, listados en los hallazgos.
+}
+
+# Conversion report
+report-title = Informe de conversión
+report-description = Un resumen JSON de la entrada, el formato detectado, los hallazgos de cada entrada con sus códigos, cómo fue cada ida y vuelta y cuánto tardó. La línea de comandos escribe el mismo informe con { $option }, para poder seguir la calidad de la conversión en todo un corpus.
+report-download = Descargar informe
+report-nothing = Convierte algo primero.
+report-failed = No se pudo descargar el informe.
+
+# Patches
+patches-title = Comparar dos versiones
+patches-description = Pega dos versiones de un objeto JSCalendar o JSContact para obtener el parche que convierte la primera en la segunda, como la actualización de una llamada JMAP /set.
+patches-before = Antes
+patches-after = Después
+patches-use-current = Usar el objeto actual
+
+# Findings
+findings-title = Hallazgos
+findings-all = Todas las gravedades
+findings-warnings = Avisos y errores
+findings-errors = Solo errores
+findings-filter = Filtrar por código, p. ej. CAL-
+findings-suppress = Ocultar
+findings-unsuppress = Haz clic para dejar de ocultar este código
+findings-hidden = { $hidden } de { $count } hallazgos ocultos por los filtros.
+
+# Time zone inspector
+vtimezone-title = { $warnings ->
+    [0] Zonas horarias: { $count } definidas
+   *[other] Zonas horarias: { $count } definidas, { $warnings } con avisos
+}
+vtimezone-mapped = Convertida como { $tz }.
+vtimezone-unused-resolves = No la usa ningún evento; equivale a { $tz }.
+vtimezone-unused = No la usa ningún evento.
+vtimezone-standard = Estándar
+vtimezone-daylight = Horario de verano
+
+# Remote source
+remote-title = Abrir una URL
+remote-placeholder = https://example.com/calendar.ics o webcal://…
+remote-fetch = Obtener
+remote-fetching = Obteniendo…
+remote-limits = Archivos de hasta { $size } MB. Los servidores que no permiten peticiones de otros orígenes necesitan el proxy CORS de los ajustes de abajo.
+remote-loaded = { $url } cargado.
+remote-loaded-cached = { $url } cargado desde la caché. Omite la caché en los ajustes para obtenerlo de nuevo.
+
+# Normalization
+normalize-title = Normalizar
+normalize-description = Reescribe el origen en forma canónica: líneas plegadas a 75 octetos, componentes y propiedades ordenados, parámetros ordenados y en mayúsculas, fechas y horas en UTC salvo que pertenezcan a una serie recurrente o sean flotantes, y claves JSON ordenadas. Así, dos exportaciones de los mismos datos solo difieren donde difieren los datos.
+normalize-run = Normalizar
+normalize-changed = Normalizado; deshaz para revertir.
+normalize-unchanged = Ya está en forma canónica.
+
+# Merge
+merge-title = Combinar archivos
+merge-description = Combina varios archivos iCalendar o vCard en uno, que se abre en un documento nuevo. Las zonas horarias definidas en más de un archivo se escriben una sola vez y se informa de las entradas que comparten UID.
+merge-output = Combinar calendarios como
+merge-output-calendar = Un único VCALENDAR
+merge-output-group = Un Group de JSCalendar
+merge-choose = Elegir archivos para combinar
+merge-summary = Se combinaron { $files } archivos en { $entries } entradas y { $timezones } zonas horarias.
+
+# Contact links
+links-title = Multimedia, directorios y calendarios
+links-collection = Colección
+links-kind = Tipo
+links-uri = URI
+links-media-type = Tipo de medio
+links-check = Comprobación
+links-ok = OK
+links-pref = pref. { $pref }
+links-placed = en { $expected }
+links-misplaced = en { $found }, se esperaba en { $expected }
+links-missing = falta, se esperaba en { $expected }
+
+# vCard version
+vcard-version-title = Versión de vCard
+vcard-version-detected = El origen es vCard { $version }. Escribirlo como
+vcard-version-write = Escribirlo como
+vcard-version-target = Versión de vCard de destino
+vcard-version-unrepresentable = No representable en vCard { $version }
+
+# Repair
+repair-title = Reparar un calendario exportado
+repair-run = Reparar
+repair-nothing = Nada que reparar para { $profile }.
+repair-applied = { $count ->
+    [one] Se aplicó { $count } corrección para { $profile }; deshaz para revertir:
+   *[other] Se aplicaron { $count } correcciones para { $profile }; deshaz para revertir:
+}
+repair-notes = Se dejaron como estaban; revísalos tú mismo:
+
+# Free/busy
+freebusy-title = Libre/ocupado: { $freebusy } VFREEBUSY, { $availability } VAVAILABILITY
+freebusy-kind-freebusy = Libre/ocupado
+freebusy-kind-availability = Disponibilidad
+freebusy-no-span = Sin DTSTART ni DTEND
+freebusy-outside = Fuera de las franjas disponibles: { $status }.
+freebusy-no-periods = Sin periodos.
+freebusy-free = Libre
+freebusy-busy = Ocupado
+freebusy-tentative = Provisional
+freebusy-unavailable = No disponible
+
+# Documents
+documents-name = Nombre del documento
+documents-rename = Doble clic para renombrar
+documents-close = Cerrar documento
+documents-new = Documento nuevo
+
+# QR code
+qr-show = QR
+qr-hide = Ocultar código QR
+qr-payload = Contenido del código QR
+qr-size = { $size } de { $max } bytes
+qr-code = Código QR
+
+# Phonetic names
+phonetic-title = Nombres fonéticos y pronunciación
+phonetic-not-playable = no se puede reproducir en el navegador
+
+# Time zone picker
+timezone-title = Zona horaria en la que se muestran las repeticiones
+timezone-as-written = Tal como está escrito
+
+# Web page extraction
+html-extract-title = Página web
+html-extract-found = Se encontraron { $found }.
+html-extract-schema-org = { $count ->
+    [one] { $count } evento de schema.org
+   *[other] { $count } eventos de schema.org
+}
+html-extract-hcalendar = { $count ->
+    [one] { $count } evento hCalendar
+   *[other] { $count } eventos hCalendar
+}
+html-extract-hcards = { $count ->
+    [one] { $count } hCard
+   *[other] { $count } hCards
+}
+html-extract-events-only = Solo se convierten los eventos.
+html-extract-skipped = { $count ->
+    [one] Se omitió { $count } evento sin fecha de inicio.
+   *[other] Se omitieron { $count } eventos sin fecha de inicio.
+}
+
+# Heatmap
+heatmap-busiest = Hora con más actividad: { $day } { $hour }, con { $count } repeticiones.
+heatmap-cell = { $day } { $hour }: { $count } repeticiones
+
+# Component filter
+filter-title = Tipos de componente
+filter-description = Los tipos sin marcar se eliminan de la salida y de la expansión, para clientes que duplican alarmas o no admiten tareas.
+filter-events = Eventos
+filter-todos = Tareas
+filter-journals = Diarios
+filter-freebusy = Libre/ocupado
+filter-timezones = Zonas horarias
+filter-alarms = Alarmas
+filter-removed = { $count ->
+    [one] Se omitió { $count } componente.
+   *[other] Se omitieron { $count } componentes.
+}
+
+# Entries
+entries-title = { $failed ->
+    [0] { $count } entradas
+   *[other] { $count } entradas, { $failed } con errores
+}
+entries-all = Todas las entradas
+entries-untitled = Sin título
+entries-findings = { $count ->
+    [one] { $count } hallazgo
+   *[other] { $count } hallazgos
+}
+
+# Email message
+email-title = Mensaje de correo
+email-no-parts = El mensaje no tiene ninguna parte text/calendar, text/vcard ni text/directory.
+email-part = Parte { $path } · { $type }
+
+# CSV contacts
+csv-title = Contactos CSV, formato { $layout }: { $mapped } de { $count } columnas asignadas
+csv-layout-google = Google Contacts
+csv-layout-outlook = Outlook
+csv-layout-generic = Genérico
+csv-kind-home = personal
+csv-kind-work = trabajo
+csv-kind-mobile = móvil
+csv-kind-other = otro
+csv-address-pobox = apartado postal
+csv-address-street = calle
+csv-address-city = ciudad
+csv-address-region = región
+csv-address-code = código postal
+csv-address-country = país
+csv-field-ignore = Ignorar
+csv-field-full-name = Nombre completo
+csv-field-given-name = Nombre
+csv-field-family-name = Apellidos
+csv-field-additional-name = Segundo nombre
+csv-field-prefix = Prefijo del nombre
+csv-field-suffix = Sufijo del nombre
+csv-field-nickname = Apodo
+csv-field-organization = Organización
+csv-field-department = Departamento
+csv-field-title = Cargo
+csv-field-birthday = Cumpleaños
+csv-field-url = Sitio web
+csv-field-note = Nota
+csv-field-categories = Categorías
+csv-field-type = Tipo de las columnas siguientes
+csv-field-email = Correo ({ $kind })
+csv-field-phone = Teléfono ({ $kind })
+csv-field-address = Dirección ({ $kind }), { $part }
@@ -0,0 +1,853 @@
+# Page header
+header-title = Conversion JSCalendar et JSContact
+header-subtitle = Conversion dans les deux sens entre JSCalendar/iCalendar et JSContact/vCard.
+header-history = Historique
+header-history-title = Entrées converties récemment
+header-tour = Faire la visite
+header-tour-title = Parcourir la détection, la conversion, l’aller-retour et l’expansion avec un exemple
+header-split-view = Vue partagée
+header-stacked-view = Vue empilée
+header-install = Installer l’application
+header-install-title = Installer le convertisseur comme application qui fonctionne aussi hors ligne
+theme-title = Thème de couleurs
+theme-system = Thème du système
+theme-light = Clair
+theme-dark = Sombre
+language-title = Langue
+language-browser = Langue du navigateur
+
+# Editor
+editor-placeholder = Collez ou déposez ici un fichier iCalendar, JSCalendar, vCard, JSContact, jCal ou jCard, un export CSV de contacts, ou un e-mail avec une invitation ou une vCard en pièce jointe. Ou cliquez sur les étincelles pour essayer un exemple.
+editor-drop = Déposez un fichier .ics, .vcf ou .json pour le charger
+editor-reading = Lecture de { $name }… { $percent } %
+editor-undo = Annuler (Ctrl+Z)
+editor-redo = Rétablir (Ctrl+Maj+Z)
+editor-sample = Charger un exemple au hasard (Ctrl+K)
+editor-open = Ouvrir un fichier
+error-show = Afficher { $location } dans l’éditeur
+error-show-title = Sélectionner l’erreur dans l’éditeur
+error-report-bug = Signaler ce bogue
+error-report-bug-title = Ouvrir un ticket GitHub avec la version, le format et l’entrée, adresses e-mail et données binaires remplacées
+error-report-bug-copied = Entrée copiée dans le presse-papiers, collez-la dans le ticket.
+error-report-bug-copy-failed = La copie de l’entrée dans le presse-papiers a échoué, joignez-la plutôt au ticket.
+conversion-progress = { $done } entrées converties, { $percent } % de la source…
+conversion-cancel = Annuler
+conversion-cancelled = Conversion annulée. Modifier la source la relance.
+conversion-all-failed = Aucune des { $count } entrées n’a pu être convertie.
+source-line = ligne { $line }
+
+# Conversion results
+results-title = Résultats de la conversion
+results-converted = Voici votre { $source } au format { $target } :
+results-roundtrip = Et voici ce qu’il donnerait reconverti en { $source } :
+output-format = Format de sortie
+output-jmap-request = Requête JMAP
+output-explain = Expliquer la conversion
+output-explain-title = Annoter chaque champ avec la propriété source dont il provient
+output-download = Télécharger
+output-jmap-failed = La sortie ne peut pas être envoyée comme requête JMAP : { $error }
+footer-report-bug = Signaler un bogue
+footer-view-source = Voir le code source
+
+# Calendar expansion
+expansion-title = Résultats de l’expansion du calendrier
+expansion-from-start = Depuis la première occurrence
+expansion-from-now = À partir de maintenant
+expansion-between = Entre deux dates
+expansion-count-title = Nombre maximal d’occurrences
+expansion-budget-title = Temps alloué à l’expansion, en millisecondes
+expansion-download-ics = Télécharger le .ics
+expansion-download-csv = Télécharger le .csv
+expansion-range-from = Du
+expansion-range-to = au
+expansion-summary-start = Voici les { $count } premières occurrences de l’événement collé :
+expansion-summary-now = Voici les { $count } prochaines occurrences de l’événement collé :
+expansion-summary-range = Voici les { $count } occurrences de l’événement collé entre les dates choisies :
+expansion-elapsed = Expansion en { $elapsed } ms.
+expansion-elapsed-budget = Expansion en { $elapsed } ms, arrêtée par le budget de { $budget } ms.
+expansion-copy-json = Copier en JSON
+expansion-out-of-budget = { $count ->
+    [one] Expansion arrêtée après { $elapsed } ms avec { $count } occurrence, les suivantes peuvent manquer.
+   *[other] Expansion arrêtée après { $elapsed } ms avec { $count } occurrences, les suivantes peuvent manquer.
+}
+expansion-limit-reached = Seules les { $count } premières occurrences ont été développées.
+expansion-none-start = Le calendrier contient des événements, des tâches ou des entrées de journal, mais l’expansion n’a produit aucune occurrence.
+expansion-none-now = Le calendrier n’a aucune occurrence à venir.
+expansion-none-range = Le calendrier n’a aucune occurrence entre ces dates.
+occurrence-type = Type
+occurrence-from = Début
+occurrence-to = Fin
+occurrence-notes = Remarques
+occurrence-due = Échéance
+occurrence-floating = Sans fuseau horaire
+occurrence-added = Ajoutée par RDATE
+occurrence-modified = Modifiée
+occurrence-modified-summary = Modifiée : { $summary }
+occurrence-modified-title = Remplacée par un RECURRENCE-ID
+occurrence-removed = Supprimée par EXDATE
+occurrence-outside-hours = En dehors des heures de travail
+occurrence-outside-hours-finding = L’occurrence est en dehors des heures de travail.
+occurrence-on-holiday-finding = L’occurrence tombe le jour de { $holiday }.
+date-zone-title = Fuseau horaire des dates
+date-zone-display = Dans le fuseau horaire de l’expansion
+date-zone-event = Dans le fuseau horaire de l’événement
+date-display-title = Format des dates
+date-display-locale = Dates localisées
+date-display-iso8601 = ISO 8601
+date-display-rfc3339 = RFC 3339
+date-display-epoch = Temps Unix
+entry-kind-event = Événement
+entry-kind-task = Tâche
+entry-kind-journal = Entrée de journal
+
+# Compatibility check
+compatibility-title = Vérification de compatibilité
+compatibility-title-warnings = Vérification de compatibilité, { $count ->
+    [one] 1 avertissement
+   *[other] { $count } avertissements
+}
+compatibility-description = Recherche les constructions que les clients courants ignorent ou interprètent mal à l’import de l’iCalendar ou de la vCard, comme les fuseaux horaires sans définition, les règles de récurrence non prises en charge et les propriétés de vCard 4.0.
+compatibility-no-problems = Aucun problème connu
+compatibility-warnings = { $count ->
+    [one] 1 avertissement
+   *[other] { $count } avertissements
+}
+
+# Anonymization
+anonymize-title = Anonymiser pour le partage
+anonymize-description = Remplace les noms, adresses e-mail, numéros de téléphone, descriptions et pièces jointes par des substituts, en conservant la structure, les règles de récurrence et les fuseaux horaires, pour que l’entrée puisse être partagée dans un rapport de bogue public. Une même valeur reçoit toujours le même substitut.
+anonymize-run = Anonymiser
+anonymize-nothing = Rien à anonymiser.
+anonymize-replaced = { $count ->
+    [one] 1 valeur remplacée, annulez pour revenir en arrière.
+   *[other] { $count } valeurs remplacées, annulez pour revenir en arrière.
+}
+anonymize-failed = Impossible d’anonymiser l’entrée : { $error }
+
+# Duplicate contacts
+dedup-title = Doublons possibles
+dedup-summary = { $groups ->
+    [one] 1 groupe
+   *[other] { $groups } groupes
+} parmi { $cards } fiches partagent une adresse e-mail, un numéro de téléphone ou un nom.
+dedup-merge-all = Fusionner tous les groupes
+dedup-same = En commun : { $reasons }
+dedup-merged = Fiche fusionnée
+
+# JSON diagnostics
+json-ignored-count = { $count ->
+    [one] { $count } propriété ignorée
+   *[other] { $count } propriétés ignorées
+}
+json-ignored-description = Propriétés de la source que la conversion n’a pas conservées, ou dont la valeur n’est pas du type défini par la RFC 8984 ou la RFC 9553. Les propriétés qui ne contenaient qu’une valeur par défaut ne sont pas listées.
+json-problem-syntax = JSON non valide à la ligne { $line }, colonne { $column } : { $message }
+json-problem-wrong-type = { $expected } attendu, { $found } trouvé
+json-problem-unknown = Propriété inconnue, probablement ignorée
+json-problem-ignored = Ignorée par la conversion
+
+# Keyboard shortcuts
+shortcuts-title = Raccourcis clavier
+shortcuts-help = Sur macOS, utilisez Cmd à la place de Ctrl. Annuler et rétablir dans l’éditeur ne parcourent l’historique qu’une fois la saisie convertie.
+shortcut-key-ctrl = Ctrl
+shortcut-key-shift = Maj
+shortcut-key-enter = Entrée
+shortcut-convert = Convertir
+shortcut-download = Télécharger la sortie
+shortcut-sample = Charger un exemple au hasard
+shortcut-undo = Annuler
+shortcut-redo = Rétablir
+
+# Settings
+settings-title = Paramètres
+settings-cors-proxy = Proxy CORS
+settings-cors-proxy-help = Utilisé pour chaque récupération distante. L’URL est ajoutée au proxy, ou remplace { $placeholder } s’il est présent.
+settings-bypass-cache = Toujours récupérer depuis le réseau
+settings-cached = { $count ->
+    [one] { $count } ressource en cache
+   *[other] { $count } ressources en cache
+}
+settings-cache-unavailable = Cache hors ligne indisponible
+settings-clear-cache = Vider le cache
+settings-copy-header = En-tête de contexte à la copie
+settings-copy-header-help = La sortie copiée peut commencer par le format, le type de source, la version de l’outil, les options et l’heure de l’export. La recoller retire l’en-tête.
+header-style-off = Sans en-tête
+header-style-comments = Lignes de commentaire
+header-style-sidecar = Fichier JSON associé
+settings-redact = Masquer les événements privés et confidentiels à la copie
+settings-redact-help = Seules les plages horaires des événements classés privés ou confidentiels sont copiées, comme les serveurs CalDAV les montrent aux autres utilisateurs.
+settings-live = Convertir pendant la saisie
+settings-live-delay = après
+settings-live-delay-unit = ms sans saisie
+settings-live-limit = Les sources de plus de { $size } Ko ne sont converties que lorsque l’éditeur perd le focus.
+settings-metrics = Afficher les mesures de performance sous les résultats
+
+# Working hours
+working-hours-title = Heures de travail et jours fériés
+working-hours-to = à
+weekday-mon = lun.
+weekday-tue = mar.
+weekday-wed = mer.
+weekday-thu = jeu.
+weekday-fri = ven.
+weekday-sat = sam.
+weekday-sun = dim.
+overlay-annotate = Annoter toutes les occurrences
+overlay-hide = Masquer les jours fériés et hors horaires
+overlay-only = N’afficher que les jours fériés et hors horaires
+holidays-paste = Collez un calendrier des jours fériés (iCalendar)
+holidays-url = Ou chargez un calendrier des jours fériés depuis une URL
+holidays-load = Charger
+holidays-none = Aucun jour férié chargé.
+holidays-loaded = { $count ->
+    [one] { $count } jour férié chargé.
+   *[other] { $count } jours fériés chargés.
+}
+
+# Previews
+preview-contact = Aperçu du contact
+preview-invitation = Aperçu de l’invitation
+preview-organizer = Organisateur
+preview-join = Rejoindre
+preview-radius = Rayon de { $radius } m
+
+# Session bundle
+bundle-title = Paquet de session
+bundle-description = Rassemble la source, toutes les options, les sorties, les constats et la version de l’outil dans un fichier JSON, pour joindre une session de débogage à un ticket et la rouvrir plus tard telle quelle.
+bundle-export = Exporter la session
+bundle-import = Importer une session
+bundle-restored = Session restaurée, exportée par la version { $version }.
+bundle-restored-on = Session restaurée, exportée par la version { $version } le { $date }.
+bundle-still-converting = La source est encore en cours de conversion, ses sorties n’ont donc pas été comparées.
+bundle-unchanged = Les sorties sont inchangées.
+bundle-changed-conversion = Cette version produit une conversion différente de celle du paquet.
+bundle-changed-roundtrip = Cette version produit un aller-retour différent de celui du paquet.
+bundle-changed-both = Cette version produit une conversion et un aller-retour différents de ceux du paquet.
+
+# Contact cleanup
+cleanup-title = Numéros de téléphone et adresses e-mail
+cleanup-description = Réécrit les numéros de téléphone au format E.164, comme +4930123456, et retire des adresses e-mail les préfixes mailto:, les noms d’affichage et les domaines en majuscules. Les numéros nationaux sont lus dans le pays par défaut.
+cleanup-enable = Normaliser les coordonnées
+cleanup-country = Pays par défaut
+cleanup-country-none = Aucun, numéros internationaux uniquement
+cleanup-clean = Toutes les valeurs sont déjà propres.
+cleanup-rewrote = { $cleaned ->
+    [one] { $cleaned } valeur réécrite, listée dans les constats.
+   *[other] { $cleaned } valeurs réécrites, listées dans les constats.
+}
+cleanup-rewrote-invalid = { $cleaned ->
+    [one] { $cleaned } valeur réécrite
+   *[other] { $cleaned } valeurs réécrites
+} et { $invalid } non valides conservées, listées dans les constats.
+
+# Random samples
+samples-title = Générer un exemple aléatoire
+samples-description = Construit un document valide avec des règles de récurrence, fuseaux horaires, participants, alarmes et propriétés peu courantes aléatoires, pour que chaque exemple emprunte des chemins de conversion différents.
+samples-any-format = Tout format
+samples-new = Nouvel exemple
+samples-seed = Graine
+samples-rebuild = Reconstruire depuis la graine
+samples-invalid-seed = La graine doit être un nombre entier.
+samples-generated = Exemple { $format } issu de la graine { $seed }. La même graine et le même format le reconstruisent.
+
+# Property usage
+usage-title = Utilisation des propriétés
+usage-description = Compte combien de fois chaque propriété et chaque paramètre apparaît dans toutes les entrées des documents ouverts et combien de constats il a suscités, pour tester d’abord les chemins de conversion dont dépend un corpus. La ligne de commande écrit le même tableau avec { $option }.
+usage-analyze = Analyser les documents ouverts
+usage-download = Télécharger le CSV
+usage-summary = { $entries } entrées, { $failed } non converties, { $total } propriétés et paramètres.
+usage-name = Nom
+usage-format = Format
+usage-occurrences = Occurrences
+usage-entries = Entrées
+usage-findings = Constats
+usage-show-all = Tout afficher ({ $count })
+
+# Semantic comparison
+semantic-title = Comparer deux entrées
+semantic-description = Collez deux calendriers ou deux contacts, dans n’importe quel format pris en charge. Les deux sont lus en JSCalendar ou JSContact et comparés selon leur sens : l’ordre, le repli des lignes, la syntaxe, les identifiants des tables et les horodatages du serveur sont ignorés.
+semantic-first-input = Première entrée
+semantic-second-input = Seconde entrée
+semantic-use-current = Utiliser la source actuelle
+semantic-same = Les deux entrées ont le même sens.
+semantic-differences = { $count ->
+    [one] { $count } différence
+   *[other] { $count } différences
+}
+semantic-difference = Différence
+semantic-first = Première
+semantic-second = Seconde
+
+# Pager
+pager-count = { $count } { $noun }
+pager-range = { $start }–{ $end } sur { $total } { $noun }
+pager-page = Page { $page } sur { $pages }
+pager-previous = Précédent
+pager-next = Suivant
+pager-rows = { $count ->
+    [one] ligne
+   *[other] lignes
+}
+pager-occurrences = { $count ->
+    [one] occurrence
+   *[other] occurrences
+}
+pager-entries = { $count ->
+    [one] entrée
+   *[other] entrées
+}
+pager-alarms = { $count ->
+    [one] alarme
+   *[other] alarmes
+}
+
+# Copy and download
+export-copy = Copier
+export-copied = Copié
+export-copied-toast = { $format } copié dans le presse-papiers.
+export-copy-failed = Échec de la copie
+export-copy-failed-toast = La copie dans le presse-papiers a échoué.
+export-download = Télécharger
+export-download-failed = Échec du téléchargement
+
+# Event summary
+event-summary-title = Résumé
+event-summary-location = Lieu
+event-summary-organizer = Organisateur
+event-summary-attendees = Participants
+event-summary-description = Description
+event-summary-alarms = Alarmes
+event-summary-accepted = Accepté
+event-summary-declined = Refusé
+event-summary-tentative = Provisoire
+event-summary-no-reply = Sans réponse
+
+# Colors
+colors-title = Couleurs et catégories
+colors-source = Source
+colors-converted = Converti
+
+# Metrics
+metrics-not-measured = Non mesuré
+metrics-phases = lecture { $parse } · conversion { $conversion } · aller-retour { $roundtrip }
+metrics-expansion = expansion { $elapsed }
+metrics-sizes = entrée { $input } · sortie { $output } · aller-retour { $roundtrip }
+metrics-run = Lancer { $count } itérations
+metrics-benchmark = { $done } itérations sur { $count }, moyenne { $mean } · plus rapide { $fastest } · plus lente { $slowest }
+
+# Organization chart
+orgchart-title = Organigramme
+orgchart-description = { $count ->
+    [one] { $count } personne regroupée par organisation et service.
+   *[other] { $count } personnes regroupées par organisation et service.
+}
+orgchart-export = Exporter en JSON
+
+# Alarm timeline
+alarms-title = Alarmes
+alarms-fires = Se déclenche
+alarms-reminds-of = Rappelle
+alarms-trigger = Déclencheur
+alarms-action = Action
+alarms-line = VALARM à la ligne { $line }
+alarms-past = Déjà passée
+alarms-fixed = Heure fixe, une fois
+alarms-repeat = Répéter { $count } fois
+
+# Shift generator
+shifts-title = Générer un planning de service
+shifts-on-off = N jours de service / M jours de repos
+shifts-alternating = Semaines alternées
+shifts-monthly = Jour de la semaine du mois
+shifts-summary = Titre
+shifts-default-summary = Service
+shifts-days-on = jours de service,
+shifts-days-off = jours de repos
+shifts-week-a = Semaine A
+shifts-week-b = Semaine B
+shifts-week-a-start = La semaine A commence le lundi de la semaine du premier jour.
+shifts-first = 1er
+shifts-second = 2e
+shifts-third = 3e
+shifts-fourth = 4e
+shifts-last = Dernier
+shifts-first-day = Premier jour
+shifts-at = à
+shifts-for = pendant
+shifts-hours = heures,
+shifts-repetitions = répétitions
+shifts-timezone = Fuseau horaire (p. ex. Europe/Berlin)
+shifts-generate = Générer
+shifts-verified = { $count ->
+    [one] Expansion vérifiée : le service attendu a été produit.
+   *[other] Expansion vérifiée : les { $count } services attendus ont été produits.
+}
+shifts-mismatch = Expansion incohérente : { $count } services attendus, { $expanded } obtenus. Manquants : [{ $missing }]. Inattendus : [{ $unexpected }].
+
+# Tour
+tour-step = Étape { $step } sur { $steps }
+tour-end = Terminer la visite
+tour-back = Retour
+tour-next = Suivant
+tour-finish = Terminer
+tour-sample = Exemple de la visite
+tour-detection = Détection du format
+tour-detection-text = Collez ou déposez un fichier ici. Son format est déduit de la première ligne ou, pour le JSON, de son @type. Cet exemple est une invitation iCalendar (RFC 5545) envoyée par Exchange ; il est donc converti en JSCalendar (RFC 8984). Les vCards sont converties en JSContact (RFC 9553) de la même façon, et le JSON est reconverti.
+tour-conversion = Conversion
+tour-conversion-text = Voici l’exemple en JSCalendar, le format qu’échangent les serveurs JMAP. Les propriétés deviennent des membres JSON : DTSTART et son TZID deviennent start et timeZone, DTEND une duration, la RRULE recurrenceRules et chaque ATTENDEE une entrée de participants.
+tour-roundtrip = Aller-retour
+tour-roundtrip-text = Le JSCalendar reconverti en iCalendar. La comparaison avec la source montre ce que la correspondance conserve ; le rapport de fidélité et le diff à côté signalent ce qui est perdu ou réécrit en chemin.
+tour-expansion = Expansion de la récurrence
+tour-expansion-text = La règle de récurrence développée en occurrences, dans le fuseau horaire de votre choix. C’est ce que reçoit un client lorsqu’il demande à un serveur JMAP les instances d’un événement récurrent.
+
+# Time probe
+probe-title = Cet événement a-t-il lieu le…
+probe-enter-date = Saisissez une date et une heure.
+probe-unknown-timezone = Fuseau horaire inconnu : { $timezone }
+probe-nonexistent = { $at } n’existe pas dans { $timezone }.
+probe-unparsable = Le calendrier n’a pas pu être lu.
+probe-no-events = Le calendrier ne contient aucun événement.
+probe-occurring = Oui, de { $start } à { $end }
+probe-next = Non, prochaine fois le { $next }
+probe-not-again = Non, et il n’a plus lieu
+probe-inconclusive = Inconnu, trop d’occurrences avant cette heure
+probe-recurring = récurrent
+
+# History
+recent-clear = Effacer
+recent-close = Fermer
+recent-empty = Les entrées apparaissent ici une fois converties.
+recent-load = Charger dans l’éditeur
+recent-remove = Retirer de l’historique
+recent-unknown-format = Format inconnu
+recent-just-now = À l’instant
+recent-minutes-ago = il y a { $count } min
+recent-hours-ago = il y a { $count } h
+
+# Status editor
+status-field-status = Statut et avancement
+status-field-transparency = Statut libre/occupé
+status-field-classification = Confidentialité
+status-component = Composant
+status-dropped = Aucun, perdu à l’export
+status-missing = pas de { $property }
+status-unknown = inconnu
+status-untitled = Sans titre
+status-select-all = Tout sélectionner
+status-set-field = Définir { $field }
+status-set = { $count ->
+    [one] { $count } composant défini sur { $value }.
+   *[other] { $count } composants définis sur { $value }.
+}
+status-set-skipped = { $count ->
+    [one] { $count } composant défini sur { $value }, { $skipped } ignorés car ils ne l’acceptent pas.
+   *[other] { $count } composants définis sur { $value }, { $skipped } ignorés car ils ne l’acceptent pas.
+}
+
+# Scheduling
+scheduling-title = Message de planification
+scheduling-reply-requested = Réponse demandée
+scheduling-reply-as = Répondre en tant que
+scheduling-accept = Accepter
+scheduling-maybe = Peut-être
+scheduling-decline = Refuser
+scheduling-generate = Générer la REPLY
+scheduling-open = Ouvrir dans le convertisseur
+
+# Gender and pronouns
+identity-title = Genre et pronoms
+identity-source = Source
+identity-converted = Converti
+identity-roundtrip = Aller-retour
+identity-gender = Genre
+identity-grammatical-gender = Genre grammatical
+identity-pronouns = Pronoms
+identity-vendor = Propriétés propres au fournisseur
+
+# Diff
+diff-title = Diff entre la source et l’aller-retour
+diff-source = Source
+diff-roundtrip = Aller-retour
+diff-identical = Sortie identique.
+diff-unified = Unifié
+diff-side-by-side = Côte à côte
+diff-only-changes = Modifications seulement
+compare-run = Comparer { $current } avec { $previous }
+compare-failed = Échec de { $version } : { $error }
+compare-converted = Sortie convertie
+compare-roundtrip = Aller-retour
+
+# Description round trip
+description-title = Description après aller-retour
+description-original = Original
+description-raw = Valeur brute : { $value }
+description-html-preview = Aperçu HTML (nettoyé)
+description-converted = Converti
+description-converted-type = Converti ({ $type })
+description-roundtrip = Aller-retour
+description-missing = Description manquante.
+
+# Validation
+validation-convert = Convertir
+validation-validate = Valider
+validation-title = Rapport de validation
+validation-counts = { $errors ->
+    [one] { $errors } erreur
+   *[other] { $errors } erreurs
+}, { $warnings ->
+    [one] { $warnings } avertissement
+   *[other] { $warnings } avertissements
+}
+validation-none = Aucun problème de conformité trouvé.
+validation-severity = Gravité
+validation-code = Code
+validation-problem = Problème
+validation-location = Emplacement
+severity-info = info
+severity-warning = avertissement
+severity-error = erreur
+
+# Parsing mode
+recovery-title = Mode de lecture
+recovery-description = La lecture tolérante ignore ou corrige les lignes qui ne suivent pas la RFC et continue. La lecture stricte fait échouer une entrée dès la première, avec sa ligne et le problème rencontré.
+recovery-lenient = Tolérante
+recovery-strict = Stricte
+recovery-none = Aucune correction n’a été nécessaire.
+recovery-refused = { $count ->
+    [one] { $count } correction refusée
+   *[other] { $count } corrections refusées
+}
+recovery-made = { $count ->
+    [one] { $count } correction effectuée
+   *[other] { $count } corrections effectuées
+}
+recovery-where = Où
+recovery-problem = Problème
+recovery-action = Lecture tolérante
+
+# Property editor
+properties-title = Modifier les propriétés
+properties-description = Modifiez une valeur et appuyez sur Entrée ou quittez le champ pour réécrire cette propriété et convertir à nouveau.
+properties-filter = Filtrer par propriété ou composant
+properties-component = Composant
+properties-property = Propriété
+properties-value = Valeur
+properties-text = Texte, écrit échappé
+properties-raw = Valeur brute
+
+# Permalink
+permalink-title = Partager un lien
+permalink-description = Place la source et les options, compressées, dans l’adresse de la page, pour qu’une reproduction puisse être collée dans un rapport de bug. Ouvrir le lien la convertit immédiatement. Rien n’est envoyé à un serveur, car la partie après # reste dans le navigateur.
+permalink-copy = Copier le lien
+permalink-copied = Lien copié dans le presse-papiers.
+permalink-copy-failed = La copie dans le presse-papiers a échoué ; le lien se trouve dans la barre d’adresse.
+permalink-too-long = Le lien fait { $length } caractères et risque d’être tronqué là où il sera collé. Joignez plutôt un paquet de session.
+
+# Fidelity report
+fidelity-title = Rapport de fidélité
+fidelity-description = Compare la source avec son aller-retour via { $format }, propriété par propriété. Les valeurs qui n’ont changé que de forme ne sont pas listées.
+fidelity-none = Toutes les propriétés de la source sont revenues de { $format }.
+fidelity-summary = { $count ->
+    [one] { $count } perte lors de l’aller-retour : { $kinds }.
+   *[other] { $count } pertes lors de l’aller-retour : { $kinds }.
+}
+fidelity-dropped = Supprimée
+fidelity-renamed = Renommée
+fidelity-parameter-lost = Paramètre perdu
+fidelity-dropped-count = { $count ->
+    [one] { $count } supprimée
+   *[other] { $count } supprimées
+}
+fidelity-renamed-count = { $count ->
+    [one] { $count } renommée
+   *[other] { $count } renommées
+}
+fidelity-parameter-lost-count = { $count ->
+    [one] { $count } paramètre perdu
+   *[other] { $count } paramètres perdus
+}
+fidelity-property = Propriété
+fidelity-lost = Perdu
+fidelity-original = Valeur d’origine
+fidelity-now = Désormais { $name }
+
+# Expected output
+expected-title = Comparer avec la sortie attendue
+expected-description = Collez le { $format } qu’un autre outil ou serveur a produit pour la même entrée. L’ordre, le repli des lignes, les identifiants de produit et les identifiants des tables JSON sont ignorés.
+expected-placeholder = Sortie attendue
+expected-none = Aucun écart avec la sortie attendue.
+expected-summary = { $count ->
+    [one] { $count } écart : { $kinds }.
+   *[other] { $count } écarts : { $kinds }.
+}
+expected-different = Différent
+expected-missing = Seulement dans l’attendue
+expected-unexpected = Seulement dans la nôtre
+expected-different-count = { $count ->
+    [one] { $count } différent
+   *[other] { $count } différents
+}
+expected-missing-count = { $count } seulement dans l’attendue
+expected-unexpected-count = { $count } seulement dans la nôtre
+expected-path = Chemin
+expected-ours = La nôtre
+expected-expected = Attendue
+
+# Workspace
+workspace-source = Source
+workspace-converted = Converti
+workspace-roundtrip = Aller-retour
+workspace-warnings = Avertissements
+
+# Duration statistics
+stats-window = Période
+stats-scheduled = Planifié
+stats-average = Durée moyenne
+stats-shortest-longest = Plus court / plus long
+stats-busiest-day = Jour le plus chargé
+stats-busiest-day-value = { $count ->
+    [one] { $day } ({ $duration }, { $count } événement)
+   *[other] { $day } ({ $duration }, { $count } événements)
+}
+stats-weekday = { $count ->
+    [one] { $day } : { $count } événement, { $hours } h
+   *[other] { $day } : { $count } événements, { $hours } h
+}
+
+# Self-test
+selftest-title = Autotest
+selftest-description = Fait passer chaque exemple fourni et vos extraits par la lecture, la conversion et l’aller-retour avec le calcard intégré à la v{ $version }.
+selftest-placeholder = Collez un extrait iCalendar, JSCalendar, vCard ou JSContact supplémentaire
+selftest-add = Ajouter l’extrait ({ $count } ajoutés)
+selftest-run = Lancer l’autotest
+selftest-summary = { $passed } sur { $count } réussis en { $elapsed } ms
+selftest-sample = Exemple
+selftest-format = Format
+selftest-parse = Lecture
+selftest-convert = Conversion
+selftest-roundtrip = Aller-retour
+selftest-reparse = Relecture
+
+# Vendor extensions
+sanitize-title = Extensions propriétaires
+sanitize-description = Supprime les propriétés, composants et paramètres X- ainsi que les propriétés JSON préfixées par un fournisseur comme « example.com:foo », pour produire une sortie strictement standard.
+sanitize-enable = Supprimer les extensions propriétaires
+sanitize-keep = Conserver, p. ex. X-WR-CALNAME, X-APPLE-STRUCTURED-LOCATION
+sanitize-removed = { $count ->
+    [0] Aucune extension propriétaire trouvée.
+    [one] { $count } élément supprimé, listé dans les constats.
+   *[other] { $count } éléments supprimés, listés dans les constats.
+}
+
+# Conversion report
+report-title = Rapport de conversion
+report-description = Un résumé JSON de l’entrée, du format détecté, des constats de chaque entrée avec leurs codes, du déroulement de chaque aller-retour et de sa durée. La ligne de commande écrit le même rapport avec { $option }, pour suivre la qualité de conversion sur tout un corpus.
+report-download = Télécharger le rapport
+report-nothing = Convertissez d’abord quelque chose.
+report-failed = Le rapport n’a pas pu être téléchargé.
+
+# Patches
+patches-title = Comparer deux versions
+patches-description = Collez deux versions d’un objet JSCalendar ou JSContact pour obtenir le patch qui transforme la première en la seconde, comme la mise à jour d’un appel JMAP /set.
+patches-before = Avant
+patches-after = Après
+patches-use-current = Utiliser l’objet actuel
+
+# Findings
+findings-title = Constats
+findings-all = Toutes les gravités
+findings-warnings = Avertissements et erreurs
+findings-errors = Erreurs seulement
+findings-filter = Filtrer par code, p. ex. CAL-
+findings-suppress = Masquer
+findings-unsuppress = Cliquez pour ne plus masquer ce code
+findings-hidden = { $hidden } constats sur { $count } masqués par les filtres.
+
+# Time zone inspector
+vtimezone-title = { $warnings ->
+    [0] Fuseaux horaires : { $count } définis
+   *[other] Fuseaux horaires : { $count } définis, { $warnings } avec avertissements
+}
+vtimezone-mapped = Converti en { $tz }.
+vtimezone-unused-resolves = Utilisé par aucun événement, correspond à { $tz }.
+vtimezone-unused = Utilisé par aucun événement.
+vtimezone-standard = Heure normale
+vtimezone-daylight = Heure d’été
+
+# Remote source
+remote-title = Ouvrir une URL
+remote-placeholder = https://example.com/calendar.ics ou webcal://…
+remote-fetch = Récupérer
+remote-fetching = Récupération…
+remote-limits = Fichiers jusqu’à { $size } Mo. Les serveurs qui n’autorisent pas les requêtes cross-origin nécessitent le proxy CORS défini dans les réglages ci-dessous.
+remote-loaded = { $url } chargé.
+remote-loaded-cached = { $url } chargé depuis le cache. Contournez le cache dans les réglages pour le récupérer à nouveau.
+
+# Normalization
+normalize-title = Normaliser
+normalize-description = Réécrit la source sous forme canonique : lignes repliées à 75 octets, composants et propriétés triés, paramètres triés et en majuscules, dates-heures en UTC sauf si elles appartiennent à une série récurrente ou sont flottantes, et clés JSON triées. Deux exports des mêmes données ne diffèrent alors que là où les données diffèrent.
+normalize-run = Normaliser
+normalize-changed = Normalisé, annulez pour revenir en arrière.
+normalize-unchanged = Déjà sous forme canonique.
+
+# Merge
+merge-title = Fusionner des fichiers
+merge-description = Combine plusieurs fichiers iCalendar ou vCard en un seul, ouvert dans un nouveau document. Les fuseaux horaires définis par plusieurs fichiers sont écrits une seule fois et les entrées partageant un UID sont signalées.
+merge-output = Fusionner les calendriers en
+merge-output-calendar = Un seul VCALENDAR
+merge-output-group = Un Group JSCalendar
+merge-choose = Choisir les fichiers à fusionner
+merge-summary = { $files } fichiers fusionnés en { $entries } entrées et { $timezones } fuseaux horaires.
+
+# Contact links
+links-title = Médias, annuaires et calendriers
+links-collection = Collection
+links-kind = Type
+links-uri = URI
+links-media-type = Type de média
+links-check = Vérification
+links-ok = OK
+links-pref = préf. { $pref }
+links-placed = dans { $expected }
+links-misplaced = dans { $found }, attendu dans { $expected }
+links-missing = absent, attendu dans { $expected }
+
+# vCard version
+vcard-version-title = Version vCard
+vcard-version-detected = La source est en vCard { $version }. L’écrire en
+vcard-version-write = L’écrire en
+vcard-version-target = Version vCard cible
+vcard-version-unrepresentable = Non représentable en vCard { $version }
+
+# Repair
+repair-title = Réparer un calendrier exporté
+repair-run = Réparer
+repair-nothing = Rien à réparer pour { $profile }.
+repair-applied = { $count ->
+    [one] { $count } correction { $profile } appliquée, annulez pour revenir en arrière :
+   *[other] { $count } corrections { $profile } appliquées, annulez pour revenir en arrière :
+}
+repair-notes = Laissés tels quels, à vérifier vous-même :
+
+# Free/busy
+freebusy-title = Disponibilités : { $freebusy } VFREEBUSY, { $availability } VAVAILABILITY
+freebusy-kind-freebusy = Libre/occupé
+freebusy-kind-availability = Disponibilité
+freebusy-no-span = Ni DTSTART ni DTEND
+freebusy-outside = En dehors des créneaux disponibles : { $status }.
+freebusy-no-periods = Aucune période.
+freebusy-free = Libre
+freebusy-busy = Occupé
+freebusy-tentative = Provisoire
+freebusy-unavailable = Indisponible
+
+# Documents
+documents-name = Nom du document
+documents-rename = Double-cliquez pour renommer
+documents-close = Fermer le document
+documents-new = Nouveau document
+
+# QR code
+qr-show = QR
+qr-hide = Masquer le code QR
+qr-payload = Contenu du code QR
+qr-size = { $size } sur { $max } octets
+qr-code = Code QR
+
+# Phonetic names
+phonetic-title = Noms phonétiques et prononciation
+phonetic-not-playable = non lisible dans le navigateur
+
+# Time zone picker
+timezone-title = Fuseau horaire dans lequel les occurrences sont affichées
+timezone-as-written = Tel qu’écrit
+
+# Web page extraction
+html-extract-title = Page web
+html-extract-found = Trouvé : { $found }.
+html-extract-schema-org = { $count ->
+    [one] { $count } événement schema.org
+   *[other] { $count } événements schema.org
+}
+html-extract-hcalendar = { $count ->
+    [one] { $count } événement hCalendar
+   *[other] { $count } événements hCalendar
+}
+html-extract-hcards = { $count ->
+    [one] { $count } hCard
+   *[other] { $count } hCards
+}
+html-extract-events-only = Seuls les événements sont convertis.
+html-extract-skipped = { $count ->
+    [one] { $count } événement sans date de début a été ignoré.
+   *[other] { $count } événements sans date de début ont été ignorés.
+}
+
+# Heatmap
+heatmap-busiest = Heure la plus chargée : { $day } { $hour } avec { $count } occurrences.
+heatmap-cell = { $day } { $hour } : { $count } occurrences
+
+# Component filter
+filter-title = Types de composants
+filter-description = Les types décochés sont retirés de la sortie et de l’expansion, pour les clients qui dupliquent les alarmes ou ne gèrent pas les tâches.
+filter-events = Événements
+filter-todos = Tâches
+filter-journals = Journaux
+filter-freebusy = Libre/occupé
+filter-timezones = Fuseaux horaires
+filter-alarms = Alarmes
+filter-removed = { $count ->
+    [one] { $count } composant ignoré.
+   *[other] { $count } composants ignorés.
+}
+
+# Entries
+entries-title = { $failed ->
+    [0] { $count } entrées
+   *[other] { $count } entrées, { $failed } en échec
+}
+entries-all = Toutes les entrées
+entries-untitled = Sans titre
+entries-findings = { $count ->
+    [one] { $count } constat
+   *[other] { $count } constats
+}
+
+# Email message
+email-title = Message e-mail
+email-no-parts = Le message n’a aucune partie text/calendar, text/vcard ou text/directory.
+email-part = Partie { $path } · { $type }
+
+# CSV contacts
+csv-title = Contacts CSV, format { $layout } : { $mapped } colonnes sur { $count } associées
+csv-layout-google = Google Contacts
+csv-layout-outlook = Outlook
+csv-layout-generic = Générique
+csv-kind-home = domicile
+csv-kind-work = travail
+csv-kind-mobile = mobile
+csv-kind-other = autre
+csv-address-pobox = boîte postale
+csv-address-street = rue
+csv-address-city = ville
+csv-address-region = région
+csv-address-code = code postal
+csv-address-country = pays
+csv-field-ignore = Ignorer
+csv-field-full-name = Nom complet
+csv-field-given-name = Prénom
+csv-field-family-name = Nom de famille
+csv-field-additional-name = Deuxième prénom
+csv-field-prefix = Préfixe du nom
+csv-field-suffix = Suffixe du nom
+csv-field-nickname = Surnom
+csv-field-organization = Organisation
+csv-field-department = Service
+csv-field-title = Fonction
+csv-field-birthday = Anniversaire
+csv-field-url = Site web
+csv-field-note = Note
+csv-field-categories = Catégories
+csv-field-type = Type des colonnes suivantes
+csv-field-email = E-mail ({ $kind })
+csv-field-phone = Téléphone ({ $kind })
+csv-field-address = Adresse ({ $kind }), { $part }
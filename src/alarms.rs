@@ -0,0 +1,194 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// When the alarms of expanded occurrences go off. Relative triggers fire once
+// per occurrence, absolute ones once in all, and both again for every REPEAT.
+
+use std::collections::HashSet;
+
+use calcard::{common::timezone::Tz, icalendar::ICalendar};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use serde_json::json;
+
+use crate::{
+    contentline::{self, Component, ContentLine},
+    occurrences::{self, Occurrence},
+    preview::{duration_text, parse_duration},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Firing {
+    pub at: DateTime<Tz>,
+    // Start of the occurrence reminded of, None for absolute triggers.
+    pub occurrence: Option<DateTime<Tz>>,
+    pub title: Option<String>,
+    pub action: String,
+    // "15 minutes before start".
+    pub trigger: String,
+    // The trigger as a JSCalendar alert writes it.
+    pub jscalendar: String,
+    // 0 for the first firing, then one per REPEAT.
+    pub repeat: u32,
+    // Line of the VALARM in the source.
+    pub line: usize,
+}
+
+enum Trigger {
+    Offset { offset: Duration, end: bool },
+    Absolute(NaiveDateTime),
+}
+
+// `source` must be the text `icalendar` was parsed from. Firings are in the
+// zone of the occurrence they belong to, in time order.
+pub fn timeline(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> Vec<Firing> {
+    let components = contentline::components(source);
+    let mut absolute_fired = HashSet::new();
+    let mut firings = Vec::new();
+    for occurrence in occurrences {
+        let Some(instance) = occurrences::instance(&components, icalendar, occurrence) else {
+            continue;
+        };
+        let title = property(instance, "SUMMARY")
+            .map(ContentLine::text)
+            .filter(|title| !title.is_empty());
+        let prefix = format!("{}/", instance.path);
+        for alarm in components
+            .iter()
+            .filter(|component| component.name() == "VALARM")
+            .filter(|component| {
+                component
+                    .path
+                    .strip_prefix(&prefix)
+                    .is_some_and(|rest| !rest.contains('/'))
+            })
+        {
+            let Some((trigger, line)) = property(alarm, "TRIGGER")
+                .and_then(|line| parse_trigger(line).map(|trigger| (trigger, line)))
+            else {
+                continue;
+            };
+            let (first, reminded) = match &trigger {
+                Trigger::Offset { offset, end } => {
+                    let anchor = if *end {
+                        occurrence.end.with_timezone(&occurrence.start.timezone())
+                    } else {
+                        occurrence.start
+                    };
+                    (anchor + *offset, Some(occurrence.start))
+                }
+                Trigger::Absolute(when) => {
+                    if !absolute_fired.insert(alarm.path.clone()) {
+                        continue;
+                    }
+                    (
+                        Utc.from_utc_datetime(when)
+                            .with_timezone(&occurrence.start.timezone()),
+                        None,
+                    )
+                }
+            };
+            let repeat = property(alarm, "REPEAT")
+                .and_then(|line| line.value.trim().parse::<u32>().ok())
+                .unwrap_or(0);
+            let interval = property(alarm, "DURATION")
+                .and_then(|line| parse_duration(line.value.trim().trim_start_matches('+')))
+                .filter(|interval| !interval.is_zero());
+            let action = property(alarm, "ACTION")
+                .map(|line| action_name(&line.value))
+                .unwrap_or_else(|| "Display".to_string());
+            let (text, jscalendar) = (trigger_text(&trigger), trigger_json(&trigger, line));
+            for index in 0..=interval.map_or(0, |_| repeat) {
+                firings.push(Firing {
+                    at: first + interval.unwrap_or_default() * index as i32,
+                    occurrence: reminded,
+                    title: title.clone(),
+                    action: action.clone(),
+                    trigger: text.clone(),
+                    jscalendar: jscalendar.clone(),
+                    repeat: index,
+                    line: alarm.line,
+                });
+            }
+        }
+    }
+    firings.sort_by_key(|firing| firing.at.naive_utc());
+    firings
+}
+
+fn parse_trigger(line: &ContentLine) -> Option<Trigger> {
+    let value = line.value.trim();
+    if line
+        .param("VALUE")
+        .is_some_and(|value| value.eq_ignore_ascii_case("DATE-TIME"))
+    {
+        return contentline::parse_datetime(value)
+            .filter(|_| value.ends_with('Z'))
+            .map(Trigger::Absolute);
+    }
+    let offset = match value.strip_prefix('-') {
+        Some(value) => -parse_duration(value)?,
+        None => parse_duration(value.trim_start_matches('+'))?,
+    };
+    Some(Trigger::Offset {
+        offset,
+        end: line
+            .param("RELATED")
+            .is_some_and(|related| related.eq_ignore_ascii_case("END")),
+    })
+}
+
+fn trigger_text(trigger: &Trigger) -> String {
+    match trigger {
+        Trigger::Offset { offset, end } => {
+            let anchor = if *end { "end" } else { "start" };
+            if offset.is_zero() {
+                format!("At {}", anchor)
+            } else if *offset < Duration::zero() {
+                format!("{} before {}", duration_text(-*offset), anchor)
+            } else {
+                format!("{} after {}", duration_text(*offset), anchor)
+            }
+        }
+        Trigger::Absolute(when) => format!("On {}", when.format("%b %-d, %Y %-I:%M%P UTC")),
+    }
+}
+
+fn trigger_json(trigger: &Trigger, line: &ContentLine) -> String {
+    match trigger {
+        Trigger::Offset { end, .. } => {
+            let mut trigger = json!({
+                "@type": "OffsetTrigger",
+                "offset": line.value.trim().trim_start_matches('+'),
+            });
+            if *end {
+                trigger["relativeTo"] = "end".into();
+            }
+            trigger.to_string()
+        }
+        Trigger::Absolute(when) => json!({
+            "@type": "AbsoluteTrigger",
+            "when": when.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        })
+        .to_string(),
+    }
+}
+
+fn action_name(value: &str) -> String {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "DISPLAY" => "Display".to_string(),
+        "EMAIL" => "Email".to_string(),
+        "AUDIO" => "Audio".to_string(),
+        "PROCEDURE" => "Procedure".to_string(),
+        _ => value.trim().to_string(),
+    }
+}
+
+fn property<'x>(component: &'x Component, name: &str) -> Option<&'x ContentLine> {
+    component
+        .properties
+        .iter()
+        .find(|property| property.name == name)
+}
@@ -0,0 +1,366 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Replaces the personal data of a calendar or contact with placeholders so
+// it can be shared in a bug report. Names, email addresses, phone numbers,
+// descriptions and attachments are replaced; everything else, including
+// recurrence rules, time zones and UIDs, is kept as written. The same value
+// always gets the same placeholder, so an organizer still matches the
+// attendee they are.
+
+use serde_json::{Map, Value};
+
+use crate::contentline::{self, escape, fold_line, split_unescaped, ContentLine};
+
+const NAMES: &[(&str, &str)] = &[
+    ("Alex", "Morgan"),
+    ("Sam", "Carter"),
+    ("Jordan", "Lee"),
+    ("Taylor", "Reed"),
+    ("Casey", "Brooks"),
+    ("Riley", "Hayes"),
+    ("Jamie", "Fox"),
+    ("Morgan", "Blake"),
+];
+const DESCRIPTION: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+const BINARY: &str = "AAAA";
+const NAME_PROPERTIES: &[&str] = &["FN", "NICKNAME"];
+const DESCRIPTION_PROPERTIES: &[&str] = &["DESCRIPTION", "NOTE", "COMMENT"];
+const ATTACHMENT_PROPERTIES: &[&str] = &["ATTACH", "PHOTO", "LOGO", "SOUND", "KEY"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Anonymized {
+    pub text: String,
+    // Values replaced with a placeholder.
+    pub replaced: usize,
+}
+
+pub fn anonymize(source: &str) -> Result<Anonymized, String> {
+    let mut placeholders = Placeholders::default();
+    let text = if source.trim_start().starts_with("BEGIN:") {
+        anonymize_text(source, &mut placeholders)
+    } else {
+        let mut root: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+        anonymize_json(&mut root, &mut Vec::new(), &mut placeholders);
+        serde_json::to_string_pretty(&root).map_err(|err| err.to_string())?
+    };
+    Ok(Anonymized {
+        text,
+        replaced: placeholders.replaced,
+    })
+}
+
+#[derive(Default)]
+struct Placeholders {
+    names: Vec<String>,
+    emails: Vec<String>,
+    phones: Vec<String>,
+    links: Vec<String>,
+    replaced: usize,
+}
+
+impl Placeholders {
+    // First and last name of the placeholder for a full name.
+    fn name_parts(&mut self, original: &str) -> (String, String) {
+        self.replaced += 1;
+        let index = position(&mut self.names, original);
+        let (first, last) = NAMES[index % NAMES.len()];
+        match index / NAMES.len() {
+            0 => (first.to_string(), last.to_string()),
+            round => (first.to_string(), format!("{} {}", last, round + 1)),
+        }
+    }
+
+    fn name(&mut self, original: &str) -> String {
+        let (first, last) = self.name_parts(original);
+        format!("{} {}", first, last)
+    }
+
+    fn email(&mut self, original: &str) -> String {
+        self.replaced += 1;
+        let index = position(&mut self.emails, original);
+        let (first, last) = NAMES[index % NAMES.len()];
+        let round = match index / NAMES.len() {
+            0 => String::new(),
+            round => (round + 1).to_string(),
+        };
+        format!(
+            "{}.{}{}@example.com",
+            first.to_ascii_lowercase(),
+            last.to_ascii_lowercase(),
+            round
+        )
+    }
+
+    // Numbers 555-0100 to 555-0199 are reserved for fiction.
+    fn phone(&mut self, original: &str) -> String {
+        self.replaced += 1;
+        let index = position(&mut self.phones, original);
+        format!("+1-202-555-01{:02}", index % 100)
+    }
+
+    fn link(&mut self, original: &str) -> String {
+        self.replaced += 1;
+        let index = position(&mut self.links, original);
+        format!("https://example.com/attachment-{}", index + 1)
+    }
+
+    fn description(&mut self) -> String {
+        self.replaced += 1;
+        DESCRIPTION.to_string()
+    }
+
+    // Inline data keeps its media type.
+    fn data_uri(&mut self, uri: &str) -> String {
+        self.replaced += 1;
+        match uri.split_once(',') {
+            Some((prefix, _)) => format!("{},{}", prefix, BINARY),
+            None => format!("data:,{}", BINARY),
+        }
+    }
+
+    // "mailto:" and "tel:" URIs keep their scheme.
+    fn uri(&mut self, uri: &str) -> Option<String> {
+        if let Some(address) = strip_scheme(uri, "mailto:") {
+            Some(format!("{}{}", &uri[..7], self.email(address)))
+        } else if let Some(number) = strip_scheme(uri, "tel:") {
+            Some(format!("{}{}", &uri[..4], self.phone(number)))
+        } else if uri.starts_with("data:") {
+            Some(self.data_uri(uri))
+        } else {
+            None
+        }
+    }
+}
+
+fn position(seen: &mut Vec<String>, original: &str) -> usize {
+    let key = original.trim().to_lowercase();
+    match seen.iter().position(|seen| *seen == key) {
+        Some(index) => index,
+        None => {
+            seen.push(key);
+            seen.len() - 1
+        }
+    }
+}
+
+fn strip_scheme<'x>(uri: &'x str, scheme: &str) -> Option<&'x str> {
+    uri.get(..scheme.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        .map(|_| &uri[scheme.len()..])
+}
+
+// Lines left alone are written back as they were.
+fn anonymize_text(source: &str, placeholders: &mut Placeholders) -> String {
+    let mut text = String::with_capacity(source.len());
+    for (number, raw) in contentline::unfold(source) {
+        match contentline::parse_line(number, &raw) {
+            Some(mut line) => {
+                if anonymize_line(&mut line, placeholders) {
+                    fold_line(&mut text, &line.to_line());
+                } else {
+                    fold_line(&mut text, &raw);
+                }
+            }
+            None => fold_line(&mut text, &raw),
+        }
+    }
+    text
+}
+
+// Whether anything in the line was replaced.
+fn anonymize_line(line: &mut ContentLine, placeholders: &mut Placeholders) -> bool {
+    let replaced = placeholders.replaced;
+    for (param, value) in &mut line.params {
+        match param.as_str() {
+            "CN" => *value = placeholders.name(value),
+            "EMAIL" => *value = placeholders.email(value),
+            // Lists of quoted calendar addresses.
+            "SENT-BY" | "DELEGATED-TO" | "DELEGATED-FROM" | "MEMBER" => {
+                *value = value
+                    .split("\",\"")
+                    .map(|uri| placeholders.uri(uri).unwrap_or_else(|| uri.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\",\"");
+            }
+            _ => {}
+        }
+    }
+
+    let name = line.name.as_str();
+    if NAME_PROPERTIES.contains(&name) {
+        line.value = split_unescaped(&line.value, ',')
+            .iter()
+            .map(|name| escape(&placeholders.name(&contentline::unescape(name))))
+            .collect::<Vec<_>>()
+            .join(",");
+    } else if name == "N" {
+        line.value = structured_name(&line.value, placeholders);
+    } else if name == "EMAIL" {
+        line.value = placeholders.email(&line.value);
+    } else if name == "TEL" {
+        line.value = placeholders
+            .uri(&line.value)
+            .unwrap_or_else(|| placeholders.phone(&line.value));
+    } else if DESCRIPTION_PROPERTIES.contains(&name) {
+        line.value = escape(&placeholders.description());
+    } else if name == "X-ALT-DESC" {
+        line.value = escape(&format!(
+            "<html><body><p>{}</p></body></html>",
+            placeholders.description()
+        ));
+    } else if ATTACHMENT_PROPERTIES.contains(&name) {
+        let binary = line.param("ENCODING").is_some_and(|encoding| {
+            matches!(encoding.to_ascii_uppercase().as_str(), "B" | "BASE64")
+        }) || line
+            .param("VALUE")
+            .is_some_and(|value| value.eq_ignore_ascii_case("BINARY"));
+        line.value = if binary {
+            placeholders.replaced += 1;
+            BINARY.to_string()
+        } else {
+            placeholders
+                .uri(&line.value)
+                .unwrap_or_else(|| placeholders.link(&line.value))
+        };
+    } else if !matches!(name, "UID" | "URL" | "SOURCE" | "TZURL") {
+        // ORGANIZER, ATTENDEE and other calendar addresses.
+        if let Some(uri) = placeholders.uri(&line.value) {
+            line.value = uri;
+        }
+    }
+    placeholders.replaced != replaced
+}
+
+// Family and given names are replaced as a whole so they match the FN.
+fn structured_name(value: &str, placeholders: &mut Placeholders) -> String {
+    let mut components = split_unescaped(value, ';');
+    let given = components.get(1).cloned().unwrap_or_default();
+    let family = components.first().cloned().unwrap_or_default();
+    let full = format!(
+        "{} {}",
+        contentline::unescape(&given),
+        contentline::unescape(&family)
+    );
+    let (first, last) = placeholders.name_parts(&full);
+    if let Some(family) = components.first_mut() {
+        *family = escape(&last);
+    }
+    if let Some(given) = components.get_mut(1) {
+        *given = escape(&first);
+    }
+    // Additional names.
+    if let Some(additional) = components.get_mut(2) {
+        additional.clear();
+    }
+    components.join(";")
+}
+
+// `path` holds the keys leading to `value`.
+fn anonymize_json(value: &mut Value, path: &mut Vec<String>, placeholders: &mut Placeholders) {
+    match value {
+        Value::Object(object) => {
+            if path.last().map(String::as_str) == Some("name") {
+                anonymize_name_components(object, placeholders);
+            }
+            for (key, child) in object.iter_mut() {
+                if let Value::String(text) = child {
+                    if let Some(replacement) = json_string(key, text, path, placeholders) {
+                        *text = replacement;
+                    }
+                    continue;
+                }
+                path.push(key.clone());
+                anonymize_json(child, path, placeholders);
+                path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                anonymize_json(item, path, placeholders);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_string(
+    key: &str,
+    text: &str,
+    path: &[String],
+    placeholders: &mut Placeholders,
+) -> Option<String> {
+    let within = |parent: &str| path.iter().any(|key| key == parent);
+    match key {
+        "uid" | "@type" | "prodId" | "timeZone" | "recurrenceIdTimeZone" => None,
+        "description" | "note" => Some(placeholders.description()),
+        "name" | "full"
+            if ![
+                "addresses",
+                "locations",
+                "virtualLocations",
+                "organizations",
+                "timeZones",
+            ]
+            .iter()
+            .any(|parent| within(parent)) =>
+        {
+            Some(placeholders.name(text))
+        }
+        "email" | "address" if text.contains('@') => Some(placeholders.email(text)),
+        "number" if within("phones") => Some(
+            placeholders
+                .uri(text)
+                .unwrap_or_else(|| placeholders.phone(text)),
+        ),
+        "href" | "uri" if within("links") || within("media") || within("cryptoKeys") => Some(
+            placeholders
+                .uri(text)
+                .unwrap_or_else(|| placeholders.link(text)),
+        ),
+        // Addresses participants are sent to, and data: URIs elsewhere.
+        _ => placeholders.uri(text),
+    }
+}
+
+// JSContact names also list their components, which are replaced to match
+// the full name.
+fn anonymize_name_components(object: &mut Map<String, Value>, placeholders: &mut Placeholders) {
+    let Some(Value::Array(components)) = object.get_mut("components") else {
+        return;
+    };
+    let part = |components: &[Value], kind: &str| {
+        components
+            .iter()
+            .filter(|component| component.get("kind").and_then(Value::as_str) == Some(kind))
+            .filter_map(|component| component.get("value").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let full = format!(
+        "{} {}",
+        part(components, "given"),
+        part(components, "surname")
+    );
+    let (first, last) = placeholders.name_parts(&full);
+    // Second given names and surnames are dropped.
+    components.retain(|component| {
+        !matches!(
+            component.get("kind").and_then(Value::as_str),
+            Some("given2" | "surname2")
+        )
+    });
+    for component in components.iter_mut() {
+        let replacement = match component.get("kind").and_then(Value::as_str) {
+            Some("given") => first.clone(),
+            Some("surname") => last.clone(),
+            _ => continue,
+        };
+        if let Some(value) = component.get_mut("value") {
+            *value = Value::String(replacement);
+        }
+    }
+}
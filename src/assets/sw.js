@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Keeps the page, its WebAssembly bundle and icons cached so the converter
+// works offline. The build replaces @VERSION@ with the package version and
+// build time, so every release starts from a fresh cache.
+const CACHE = 'jmap-convert-@VERSION@';
+const ICONS = ['/android-chrome-192x192.png', '/android-chrome-512x512.png'];
+
+self.addEventListener('install', (event) => {
+  event.waitUntil(
+    (async () => {
+      const cache = await caches.open(CACHE);
+      const page = await fetch('/', { cache: 'no-cache' });
+      // The hashed names of the bundle are only known from the built page.
+      const html = await page.clone().text();
+      const assets = [...html.matchAll(/(?:href|src)="(\/[^"]*)"|'(\/[^']+\.(?:js|wasm))'/g)]
+        .map((match) => match[1] || match[2]);
+      await cache.put('/', page);
+      await cache.addAll([...new Set([...assets, ...ICONS])]);
+      await self.skipWaiting();
+    })()
+  );
+});
+
+self.addEventListener('activate', (event) => {
+  event.waitUntil(
+    (async () => {
+      for (const name of await caches.keys()) {
+        if (name.startsWith('jmap-convert-') && name !== CACHE) {
+          await caches.delete(name);
+        }
+      }
+      await self.clients.claim();
+    })()
+  );
+});
+
+self.addEventListener('fetch', (event) => {
+  const request = event.request;
+  if (request.method !== 'GET' || new URL(request.url).origin !== self.location.origin) {
+    return;
+  }
+  if (request.mode === 'navigate') {
+    // The page comes from the network when online, so a new release is
+    // picked up on the next visit.
+    event.respondWith(
+      fetch(request)
+        .then(async (response) => {
+          if (response.ok) {
+            const cache = await caches.open(CACHE);
+            await cache.put('/', response.clone());
+          }
+          return response;
+        })
+        .catch(async () => (await caches.match('/')) || Response.error())
+    );
+    return;
+  }
+  // Everything else has a hashed or fixed name and never changes.
+  event.respondWith(
+    caches.match(request).then(
+      (cached) =>
+        cached ||
+        fetch(request).then(async (response) => {
+          if (response.ok) {
+            const cache = await caches.open(CACHE);
+            await cache.put(request, response.clone());
+          }
+          return response;
+        })
+    )
+  );
+});
@@ -0,0 +1,420 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use jmap_convert::{
+    contact_cleanup,
+    findings::{Finding, Severity},
+    legacy_encoding, recovery,
+    report::{ConversionReport, Timings},
+    sanitize,
+    usage::UsageReport,
+    Converter, Format,
+};
+use serde_json::Value;
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+const USAGE: &str = "Usage: jmap-convert-cli [OPTIONS] [FILE...]
+
+Converts every FILE, or standard input when none is given, to its counterpart
+format. Asking for the source format writes the round-tripped document.
+
+Options:
+  --to FORMAT     icalendar, jscalendar, vcard, jscontact, jcal or jcard
+  --pretty        Indent JSON output
+  --strip-vendor  Remove X- properties, components and parameters and
+                  vendor-prefixed JSON properties before converting
+  --keep NAMES    Comma separated vendor extensions --strip-vendor keeps
+  --normalize     Rewrite contact phone numbers as E.164 and check email
+                  addresses before converting
+  --country CC    ISO country code --normalize reads national phone
+                  numbers in
+  --strict        Fail an entry on the first malformed line instead of
+                  skipping or coercing it
+  --out-dir DIR   Write each FILE to DIR with the extension of its output
+                  format instead of writing to standard output
+  --report FILE   Append a JSON conversion report for each input to FILE,
+                  one per line
+  --usage FILE    Write to FILE, as CSV, how often each property and
+                  parameter occurs across all inputs and how many findings
+                  it drew
+  --watch DIR     Keep converting the .ics and .vcf files that appear or
+                  change in DIR, writing them to --out-dir or else to a
+                  sibling directory named DIR-converted
+  -h, --help      Show this help";
+
+#[derive(Clone, Default)]
+struct Options {
+    to: Option<Format>,
+    pretty: bool,
+    strip_vendor: bool,
+    keep: Vec<String>,
+    normalize: bool,
+    country: Option<String>,
+    strict: bool,
+    out_dir: Option<PathBuf>,
+    report: Option<PathBuf>,
+    usage: Option<PathBuf>,
+    watch: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(dir) = &options.watch {
+        return watch(&options, dir);
+    }
+
+    let mut failed = false;
+    let mut usage = UsageReport::default();
+    if options.paths.is_empty() {
+        failed |= !run(&options, None, &mut usage);
+    } else {
+        for path in &options.paths {
+            failed |= !run(&options, Some(path), &mut usage);
+        }
+    }
+    if let Some(usage_path) = &options.usage {
+        if let Err(err) = fs::write(usage_path, usage.to_string()) {
+            eprintln!("{}: error: {}", usage_path.display(), err);
+            failed = true;
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// Polls `dir` until it goes away, converting each calendar or contact file
+// when it is first seen and again whenever its modification time changes.
+fn watch(options: &Options, dir: &Path) -> ExitCode {
+    let out_dir = match &options.out_dir {
+        Some(out_dir) => out_dir.clone(),
+        None => {
+            // "." and ".." are named after the directory they stand for.
+            let dir = match fs::canonicalize(dir) {
+                Ok(dir) => dir,
+                Err(err) => {
+                    eprintln!("{}: error: {}", dir.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let Some(name) = dir.file_name() else {
+                eprintln!(
+                    "{}: error: the directory has no name to derive the output directory from, use --out-dir",
+                    dir.display()
+                );
+                return ExitCode::FAILURE;
+            };
+            let mut name = name.to_os_string();
+            name.push("-converted");
+            dir.with_file_name(name)
+        }
+    };
+    if let Err(err) = fs::create_dir_all(&out_dir) {
+        eprintln!("{}: error: {}", out_dir.display(), err);
+        return ExitCode::FAILURE;
+    }
+    let options = Options {
+        out_dir: Some(out_dir.clone()),
+        ..options.clone()
+    };
+    eprintln!(
+        "Watching {} and writing to {}",
+        dir.display(),
+        out_dir.display()
+    );
+
+    let mut seen: HashMap<PathBuf, SystemTime> = HashMap::new();
+    loop {
+        let files = match fs::read_dir(dir) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("{}: error: {}", dir.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+        let mut present = Vec::new();
+        for file in files.flatten() {
+            let path = file.path();
+            let watched = path.extension().is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("ics") || extension.eq_ignore_ascii_case("vcf")
+            });
+            let Some(modified) = file
+                .metadata()
+                .ok()
+                .filter(|metadata| watched && metadata.is_file())
+                .and_then(|metadata| metadata.modified().ok())
+            else {
+                continue;
+            };
+            present.push(path.clone());
+            if seen.get(&path) != Some(&modified) {
+                seen.insert(path.clone(), modified);
+                if run(&options, Some(&path), &mut UsageReport::default()) {
+                    eprintln!("{}: converted", path.display());
+                }
+            }
+        }
+        seen.retain(|path, _| present.contains(path));
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+// Reports problems on standard error, returning whether the input converted.
+fn run(options: &Options, path: Option<&Path>, usage: &mut UsageReport) -> bool {
+    let name = path.map_or_else(|| "<stdin>".to_string(), |path| path.display().to_string());
+    match convert(options, path, &name, usage) {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("{}: error: {}", name, err);
+            false
+        }
+    }
+}
+
+fn convert(
+    options: &Options,
+    path: Option<&Path>,
+    name: &str,
+    usage: &mut UsageReport,
+) -> Result<(), String> {
+    let source = match path {
+        Some(path) => fs::read_to_string(path).map_err(|err| err.to_string())?,
+        None => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|err| err.to_string())?;
+            source
+        }
+    };
+
+    // Values in a legacy encoding are decoded first, then vendor extensions
+    // stripped and contact values normalized when asked to.
+    let (mut source, mut stripped) = match legacy_encoding::transcode(&source) {
+        Some(transcoded) => (transcoded.text, transcoded.findings),
+        None => (source, vec![]),
+    };
+    if options.strip_vendor {
+        let removed;
+        (source, removed) = sanitize::strip_vendor(&source, &options.keep);
+        stripped.extend(removed);
+    }
+    if options.normalize {
+        let cleaned;
+        (source, cleaned) =
+            contact_cleanup::normalize(&source, options.country.as_deref().unwrap_or_default());
+        stripped.extend(cleaned);
+    }
+    for finding in &stripped {
+        eprintln!(
+            "{}: {}: {}",
+            name,
+            finding.severity().as_str(),
+            describe(finding)
+        );
+    }
+
+    let started = Instant::now();
+    let entries = Converter.convert_all(&source);
+    let entries = if options.strict {
+        recovery::strict(entries)
+    } else {
+        entries
+    };
+    for entry in &entries {
+        usage.add(entry);
+    }
+    if let Some(report_path) = &options.report {
+        let report = ConversionReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            generated: chrono::Utc::now().to_rfc3339(),
+            findings: stripped,
+            timings: Timings {
+                conversion_ms: started.elapsed().as_secs_f64() * 1000.0,
+                expansion_ms: None,
+            },
+            ..ConversionReport::new(name, &source, &entries)
+        };
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(report_path)
+            .and_then(|mut file| writeln!(file, "{}", report.to_json()))
+            .map_err(|err| format!("{}: {}", report_path.display(), err))?;
+    }
+    let mut failed = 0;
+    let mut converted = Vec::new();
+    for entry in &entries {
+        let result = match &entry.result {
+            Ok(result) => result,
+            Err(finding) => {
+                eprintln!("{}: error: {}", name, describe(finding));
+                failed += 1;
+                continue;
+            }
+        };
+        for finding in &result.findings {
+            if finding.severity() > Severity::Info {
+                eprintln!(
+                    "{}: {}: {}",
+                    name,
+                    finding.severity().as_str(),
+                    describe(finding)
+                );
+            }
+        }
+        converted.push(result.output(options.to)?);
+    }
+    let Some(format) = converted.first().map(|(format, _)| *format) else {
+        return Err(format!("None of the {} entries converted", entries.len()));
+    };
+    if converted.iter().any(|(other, _)| *other != format) {
+        return Err("Entries convert to different formats".to_string());
+    }
+    let output = format.join(converted.iter().map(|(_, output)| output.as_ref()));
+
+    let mut output = if format.is_json() && !options.pretty {
+        serde_json::from_str::<Value>(&output)
+            .and_then(|value| serde_json::to_string(&value))
+            .map_err(|err| err.to_string())?
+    } else {
+        output
+    };
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+
+    match (&options.out_dir, path) {
+        (Some(out_dir), Some(path)) => {
+            let target = out_dir
+                .join(path.file_stem().unwrap_or(path.as_os_str()))
+                .with_extension(format.extension());
+            if target == path {
+                return Err(format!("Refusing to overwrite {}", target.display()));
+            }
+            fs::write(&target, output).map_err(|err| err.to_string())?;
+        }
+        _ => io::stdout()
+            .lock()
+            .write_all(output.as_bytes())
+            .map_err(|err| err.to_string())?,
+    }
+
+    if failed > 0 {
+        Err(format!(
+            "{} of {} entries failed to convert",
+            failed,
+            entries.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn describe(finding: &Finding) -> String {
+    match &finding.location {
+        Some(location) => format!(
+            "{} {} ({})",
+            finding.code.as_str(),
+            finding.message,
+            location
+        ),
+        None => format!("{} {}", finding.code.as_str(), finding.message),
+    }
+}
+
+impl Options {
+    // Returns None when help was asked for.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--pretty" => options.pretty = true,
+                "--strip-vendor" => options.strip_vendor = true,
+                "--strict" => options.strict = true,
+                "--normalize" => options.normalize = true,
+                "--country" => {
+                    let value = args.next().ok_or("Missing value for --country")?;
+                    if contact_cleanup::find_country(&value).is_none() {
+                        return Err(format!("Unknown country \"{}\"", value));
+                    }
+                    options.country = Some(value);
+                }
+                "--keep" => {
+                    options.keep =
+                        sanitize::parse_keep_list(&args.next().ok_or("Missing value for --keep")?);
+                }
+                "--to" => {
+                    let value = args.next().ok_or("Missing value for --to")?;
+                    options.to = Some(
+                        Format::parse(&value)
+                            .ok_or_else(|| format!("Unknown format \"{}\"", value))?,
+                    );
+                }
+                "--out-dir" => {
+                    options.out_dir =
+                        Some(args.next().ok_or("Missing value for --out-dir")?.into());
+                }
+                "--report" => {
+                    options.report = Some(args.next().ok_or("Missing value for --report")?.into());
+                }
+                "--usage" => {
+                    options.usage = Some(args.next().ok_or("Missing value for --usage")?.into());
+                }
+                "--watch" => {
+                    options.watch = Some(args.next().ok_or("Missing value for --watch")?.into());
+                }
+                "-" => {
+                    return Err("Standard input is read when no FILE is given".to_string());
+                }
+                _ if arg.starts_with('-') => return Err(format!("Unknown option \"{}\"", arg)),
+                _ => options.paths.push(arg.into()),
+            }
+        }
+        if !options.keep.is_empty() && !options.strip_vendor {
+            return Err("--keep needs --strip-vendor".to_string());
+        }
+        if options.country.is_some() && !options.normalize {
+            return Err("--country needs --normalize".to_string());
+        }
+        if options.watch.is_some() && !options.paths.is_empty() {
+            return Err("--watch does not take FILE arguments".to_string());
+        }
+        if options.watch.is_some() && options.usage.is_some() {
+            return Err("--usage cannot be combined with --watch".to_string());
+        }
+        if options.out_dir.is_some() && options.paths.is_empty() && options.watch.is_none() {
+            return Err("--out-dir needs at least one FILE".to_string());
+        }
+        Ok(Some(options))
+    }
+}
@@ -0,0 +1,310 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{borrow::Cow, collections::HashMap, net::SocketAddr, process::ExitCode, time::Instant};
+
+use axum::{
+    extract::{DefaultBodyLimit, Query},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use calcard::common::timezone::Tz;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use jmap_convert::{
+    contact_cleanup,
+    findings::{Finding, Severity},
+    jcal,
+    occurrences::{self, ExpansionWindow},
+    sanitize, validation, Converter, Format,
+};
+use serde_json::{json, Value};
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+const DEFAULT_COUNT: usize = 100;
+const MAX_COUNT: usize = 5_000;
+const EXPANSION_BUDGET_MS: f64 = 2_000.0;
+const BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+const USAGE: &str = "Usage: jmap-convert-server [--listen ADDR]
+
+Serves the conversion over HTTP. Every endpoint takes the source as the
+request body, in any format the web page accepts, and answers with JSON.
+Bodies larger than 16 MB are refused.
+
+  POST /convert   ?to=FORMAT&strip-vendor&keep=NAMES&normalize&country=CC
+  POST /validate
+  POST /expand    ?from=DATE&until=DATE&count=N&timeZone=NAME
+
+Options:
+  --listen ADDR   Address and port to listen on, 127.0.0.1:8080 by default
+  -h, --help      Show this help";
+
+type Response = (StatusCode, Json<Value>);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut listen = DEFAULT_LISTEN.to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "--listen" => match args.next() {
+                Some(value) => listen = value,
+                None => {
+                    eprintln!("Missing value for --listen\n\n{}", USAGE);
+                    return ExitCode::from(2);
+                }
+            },
+            _ => {
+                eprintln!("Unknown option \"{}\"\n\n{}", arg, USAGE);
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let Ok(address) = listen.parse::<SocketAddr>() else {
+        eprintln!("Invalid address \"{}\"", listen);
+        return ExitCode::from(2);
+    };
+
+    let app = Router::new()
+        .route("/convert", post(convert))
+        .route("/validate", post(validate))
+        .route("/expand", post(expand))
+        .layer(DefaultBodyLimit::max(BODY_LIMIT));
+    let listener = match tokio::net::TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("{}: error: {}", address, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("Listening on {}", address);
+    match axum::serve(listener, app).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn convert(Query(params): Query<HashMap<String, String>>, source: String) -> Response {
+    blocking(move || run_convert(&params, source)).await
+}
+
+async fn validate(source: String) -> Response {
+    blocking(move || run_validate(&source)).await
+}
+
+async fn expand(Query(params): Query<HashMap<String, String>>, source: String) -> Response {
+    blocking(move || run_expand(&params, &source)).await
+}
+
+// Parsing and expansion can take seconds on large sources, so they run on
+// the blocking pool instead of holding up the async workers.
+async fn blocking(task: impl FnOnce() -> Response + Send + 'static) -> Response {
+    tokio::task::spawn_blocking(task)
+        .await
+        .unwrap_or_else(|err| error(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+// Converts every entry, answering with the joined output and each entry's
+// findings. Fails when no entry converts.
+fn run_convert(params: &HashMap<String, String>, source: String) -> Response {
+    let to = match params.get("to").map(|to| (to, Format::parse(to))) {
+        Some((to, None)) => {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown format \"{}\"", to),
+            )
+        }
+        Some((_, to)) => to,
+        None => None,
+    };
+    let (source, stripped) = if flag(params, "strip-vendor") {
+        let keep = sanitize::parse_keep_list(params.get("keep").map_or("", String::as_str));
+        sanitize::strip_vendor(&source, &keep)
+    } else if params.contains_key("keep") {
+        return error(StatusCode::BAD_REQUEST, "keep needs strip-vendor");
+    } else {
+        (source, vec![])
+    };
+    let (source, stripped) = if flag(params, "normalize") {
+        let country = params.get("country").map_or("", String::as_str);
+        if !country.is_empty() && contact_cleanup::find_country(country).is_none() {
+            return error(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown country \"{}\"", country),
+            );
+        }
+        let (source, cleaned) = contact_cleanup::normalize(&source, country);
+        (source, stripped.into_iter().chain(cleaned).collect())
+    } else if params.contains_key("country") {
+        return error(StatusCode::BAD_REQUEST, "country needs normalize");
+    } else {
+        (source, stripped)
+    };
+
+    let entries = Converter.convert_all(&source);
+    let mut converted = Vec::new();
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        match &entry.result {
+            Ok(result) => {
+                let (format, output) = match result.output(to) {
+                    Ok(output) => output,
+                    Err(err) => return error(StatusCode::UNPROCESSABLE_ENTITY, err),
+                };
+                results.push(json!({
+                    "format": result.format.as_str(),
+                    "findings": findings(&result.findings),
+                }));
+                converted.push((format, output));
+            }
+            Err(finding) => results.push(json!({ "error": finding.to_json() })),
+        }
+    }
+    let Some(format) = converted.first().map(|(format, _)| *format) else {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": format!("None of the {} entries converted", entries.len()),
+                "entries": results,
+            })),
+        );
+    };
+    if converted.iter().any(|(other, _)| *other != format) {
+        return error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Entries convert to different formats",
+        );
+    }
+    let output = format.join(converted.iter().map(|(_, output)| output.as_ref()));
+    (
+        StatusCode::OK,
+        Json(json!({
+            "format": format.as_str(),
+            // JSON formats are embedded as JSON, text formats as a string.
+            "output": if format.is_json() {
+                serde_json::from_str::<Value>(&output).unwrap_or(Value::String(output))
+            } else {
+                Value::String(output)
+            },
+            "entries": results,
+            "findings": findings(&stripped),
+        })),
+    )
+}
+
+fn run_validate(source: &str) -> Response {
+    let found = validation::validate(source);
+    (
+        StatusCode::OK,
+        Json(json!({
+            "valid": !found.iter().any(|finding| finding.severity() == Severity::Error),
+            "findings": findings(&found),
+        })),
+    )
+}
+
+// Occurrences of the first calendar in the source as JSCalendar Events with
+// utcStart and utcEnd. Floating times are read in `timeZone`, UTC otherwise.
+fn run_expand(params: &HashMap<String, String>, source: &str) -> Response {
+    let timezone = match params.get("timeZone") {
+        Some(name) => match name.parse::<chrono_tz::Tz>() {
+            Ok(tz) => Tz::Tz(tz),
+            Err(_) => {
+                return error(
+                    StatusCode::BAD_REQUEST,
+                    format!("Unknown time zone \"{}\"", name),
+                )
+            }
+        },
+        None => Tz::Tz(chrono_tz::UTC),
+    };
+    let bound = |key: &str| match params.get(key) {
+        Some(value) => parse_bound(value).map(Some).ok_or_else(|| {
+            format!(
+                "Invalid {} \"{}\", expected a date or an RFC 3339 time",
+                key, value
+            )
+        }),
+        None => Ok(None),
+    };
+    let window = match (bound("from"), bound("until")) {
+        (Ok(from), Ok(until)) => ExpansionWindow {
+            from,
+            until,
+            count: match params.get("count").map(|count| count.parse::<usize>()) {
+                Some(Ok(count)) => count.clamp(1, MAX_COUNT),
+                Some(Err(_)) => return error(StatusCode::BAD_REQUEST, "Invalid count"),
+                None => DEFAULT_COUNT,
+            },
+        },
+        (Err(err), _) | (_, Err(err)) => return error(StatusCode::BAD_REQUEST, err),
+    };
+
+    let entries = Converter.convert_all(source);
+    let Some((entry, result, icalendar)) = entries.iter().find_map(|entry| {
+        let result = entry.result.as_ref().ok()?;
+        Some((entry, result, result.icalendar.as_ref()?))
+    }) else {
+        return error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "The source holds no calendar that converts",
+        );
+    };
+    // The text the calendar was parsed from.
+    let text = match result.format {
+        Format::ICalendar => Cow::Borrowed(entry.source.as_str()),
+        Format::JCal => Cow::Owned(jcal::to_text(&entry.source).unwrap_or_default()),
+        _ => Cow::Borrowed(result.converted.as_str()),
+    };
+    let started = Instant::now();
+    let expanded = occurrences::expand(icalendar, timezone, &window, EXPANSION_BUDGET_MS, || {
+        started.elapsed().as_secs_f64() * 1000.0
+    });
+    let events = occurrences::to_jscalendar(&text, icalendar, &expanded.occurrences);
+    (
+        StatusCode::OK,
+        Json(json!({
+            "occurrences": serde_json::from_str::<Value>(&events).unwrap_or_default(),
+            "elapsedMs": expanded.elapsed_ms,
+            "outOfBudget": expanded.out_of_budget,
+        })),
+    )
+}
+
+// Dates stand for midnight UTC.
+fn parse_bound(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date_time| date_time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        })
+}
+
+// Query flags count as set when present without a value.
+fn flag(params: &HashMap<String, String>, key: &str) -> bool {
+    params
+        .get(key)
+        .is_some_and(|value| !matches!(value.as_str(), "false" | "0"))
+}
+
+fn findings(findings: &[Finding]) -> Vec<Value> {
+    findings.iter().map(Finding::to_json).collect()
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() })))
+}
@@ -0,0 +1,172 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Issues for conversions that fail their round trip, filled in with what is
+// needed to reproduce them.
+
+use crate::{findings::Finding, preview::url_encode, Format};
+
+const ISSUES_URL: &str = "https://github.com/stalwartlabs/calcard/issues/new";
+// Inputs longer than this are left out of the link, which browsers and
+// GitHub cut off at around 8 KB once encoded.
+const MAX_LINKED_INPUT: usize = 4_000;
+const BINARY_PLACEHOLDER: &str = "AAAA";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BugReport {
+    pub title: String,
+    pub body: String,
+    // The sanitized input when it is too long for the link, to be copied to
+    // the clipboard and pasted into the issue.
+    pub clipboard: Option<String>,
+}
+
+impl BugReport {
+    // `source` is the entry that failed, not the whole file it came from.
+    pub fn new(format: Format, error: &Finding, source: &str) -> Self {
+        let input = sanitize(source.trim()).trim_end().to_string();
+        let mut body = format!(
+            "**Version:** jmap-convert {}\n**Detected format:** {} to {}\n**Error:** {}\n\n",
+            env!("CARGO_PKG_VERSION"),
+            format.as_str(),
+            format.counterpart().as_str(),
+            error.message,
+        );
+        let clipboard = if input.len() > MAX_LINKED_INPUT {
+            body.push_str(
+                "The input was too long for the link and was copied to the clipboard, please paste it below.\n\n```\n\n```\n",
+            );
+            Some(input)
+        } else {
+            body.push_str(&format!("```\n{}\n```\n", input));
+            None
+        };
+        BugReport {
+            title: format!("{} round trip fails", format.as_str()),
+            body,
+            clipboard,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!(
+            "{}?title={}&body={}",
+            ISSUES_URL,
+            url_encode(&self.title),
+            url_encode(&self.body)
+        )
+    }
+}
+
+// Replaces email addresses and inline binary data, which are seldom needed
+// to reproduce a conversion bug. The same address always gets the same
+// placeholder so organizers and attendees still match.
+pub fn sanitize(source: &str) -> String {
+    let text = if source.trim_start().starts_with("BEGIN:") {
+        strip_binary_lines(source)
+    } else {
+        strip_data_uris(source)
+    };
+    mask_emails(&text)
+}
+
+// Content lines with base64 values keep their name and parameters.
+fn strip_binary_lines(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut skipping = false;
+    for line in source.lines() {
+        if skipping && line.starts_with([' ', '\t']) {
+            continue;
+        }
+        skipping = false;
+        if let Some((head, value)) = line.split_once(':') {
+            let head_upper = head.to_ascii_uppercase();
+            if head_upper.contains("ENCODING=B")
+                || head_upper.contains("VALUE=BINARY")
+                || value.starts_with("data:")
+            {
+                let prefix = if value.starts_with("data:") {
+                    value.split_once(',').map_or("", |(prefix, _)| prefix)
+                } else {
+                    ""
+                };
+                let separator = if prefix.is_empty() { "" } else { "," };
+                out.push_str(&format!(
+                    "{}:{}{}{}\n",
+                    head, prefix, separator, BINARY_PLACEHOLDER
+                ));
+                skipping = true;
+                continue;
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+// JSON strings holding data: URIs keep their media type.
+fn strip_data_uris(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("\"data:") {
+        out.push_str(&rest[..start + 1]);
+        rest = &rest[start + 1..];
+        let end = rest.find('"').unwrap_or(rest.len());
+        let uri = &rest[..end];
+        match uri.split_once(',') {
+            Some((prefix, _)) => {
+                out.push_str(prefix);
+                out.push(',');
+                out.push_str(BINARY_PLACEHOLDER);
+            }
+            None => out.push_str(uri),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn mask_emails(text: &str) -> String {
+    let is_local = |ch: char| ch.is_ascii_alphanumeric() || "._%+-".contains(ch);
+    let is_domain = |ch: char| ch.is_ascii_alphanumeric() || ".-".contains(ch);
+    let mut seen: Vec<String> = Vec::new();
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        let local_start = rest[..at]
+            .char_indices()
+            .rev()
+            .take_while(|(_, ch)| is_local(*ch))
+            .last()
+            .map_or(at, |(index, _)| index);
+        let domain_end = rest[at + 1..]
+            .char_indices()
+            .find(|(_, ch)| !is_domain(*ch))
+            .map_or(rest.len(), |(index, _)| at + 1 + index);
+        let domain = rest[at + 1..domain_end].trim_end_matches('.');
+        let domain_end = at + 1 + domain.len();
+        if local_start == at || !domain.contains('.') {
+            out.push_str(&rest[..=at]);
+            rest = &rest[at + 1..];
+            continue;
+        }
+        let address = rest[local_start..domain_end].to_ascii_lowercase();
+        let number = match seen.iter().position(|seen| *seen == address) {
+            Some(index) => index + 1,
+            None => {
+                seen.push(address);
+                seen.len()
+            }
+        };
+        out.push_str(&rest[..local_start]);
+        out.push_str(&format!("user{}@example.com", number));
+        rest = &rest[domain_end..];
+    }
+    out.push_str(rest);
+    out
+}
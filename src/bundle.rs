@@ -0,0 +1,291 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use chrono::{NaiveDate, NaiveTime};
+use serde_json::{json, Value};
+
+use crate::{
+    filter::ComponentKind,
+    findings::{Code, Finding},
+    working_hours::{OverlayMode, WorkingHours},
+    Format,
+};
+
+const BUNDLE_TYPE: &str = "jmap-convert-session";
+// Layout of the bundle, raised when a field changes meaning.
+const BUNDLE_VERSION: u64 = 1;
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+// A debugging session in one file: the source, every option that affects
+// its conversion, and what the tool version that exported it produced.
+#[derive(Clone, Debug, Default)]
+pub struct SessionBundle {
+    pub version: String,
+    // RFC 3339 time of the export.
+    pub exported: String,
+    pub name: String,
+    pub format: Option<Format>,
+    pub source: String,
+    pub options: BundleOptions,
+    pub converted: String,
+    pub roundtrip: String,
+    // Expanded occurrences as JSCalendar.
+    pub occurrences: String,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BundleOptions {
+    pub excluded_components: Vec<ComponentKind>,
+    pub strip_vendor: bool,
+    pub vendor_keep: String,
+    pub normalize_contacts: bool,
+    // ISO code national phone numbers are read in, empty for none.
+    pub default_country: String,
+    pub strict_parsing: bool,
+    pub selected_entry: Option<usize>,
+    // Where expansion starts, as named by the page: start, now or range.
+    pub expansion_anchor: String,
+    // IANA name, empty for floating time.
+    pub timezone: String,
+    pub expansion_from: Option<NaiveDate>,
+    pub expansion_until: Option<NaiveDate>,
+    pub expansion_count: usize,
+    pub expansion_budget_ms: u32,
+    pub working_hours: WorkingHours,
+    pub overlay: OverlayMode,
+    // Holiday calendar as it was loaded.
+    pub holidays: String,
+}
+
+impl BundleOptions {
+    pub fn to_json(&self) -> Value {
+        let date = |date: Option<NaiveDate>| date.map(|date| date.format("%Y-%m-%d").to_string());
+        let time = |time: NaiveTime| time.format("%H:%M").to_string();
+        json!({
+            "excludedComponents": self
+                .excluded_components
+                .iter()
+                .map(ComponentKind::icalendar_name)
+                .collect::<Vec<_>>(),
+            "stripVendor": self.strip_vendor,
+            "vendorKeep": self.vendor_keep,
+            "normalizeContacts": self.normalize_contacts,
+            "defaultCountry": self.default_country,
+            "strictParsing": self.strict_parsing,
+            "selectedEntry": self.selected_entry,
+            "expansion": {
+                "anchor": self.expansion_anchor,
+                "timeZone": self.timezone,
+                "from": date(self.expansion_from),
+                "until": date(self.expansion_until),
+                "count": self.expansion_count,
+                "budgetMs": self.expansion_budget_ms,
+            },
+            "workingHours": {
+                "days": WEEKDAYS
+                    .iter()
+                    .zip(self.working_hours.days)
+                    .filter(|(_, enabled)| *enabled)
+                    .map(|(day, _)| *day)
+                    .collect::<Vec<_>>(),
+                "start": time(self.working_hours.start),
+                "end": time(self.working_hours.end),
+            },
+            "overlay": self.overlay.as_str(),
+            "holidays": self.holidays,
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Self {
+        let date = |value: &Value, key: &str| {
+            NaiveDate::parse_from_str(value.get(key)?.as_str()?, "%Y-%m-%d").ok()
+        };
+        let time = |value: &Value, key: &str| {
+            NaiveTime::parse_from_str(value.get(key)?.as_str()?, "%H:%M").ok()
+        };
+        let expansion = value.get("expansion").unwrap_or(&Value::Null);
+        let hours = value.get("workingHours").unwrap_or(&Value::Null);
+        let defaults = WorkingHours::default();
+        let working_hours = WorkingHours {
+            days: match hours.get("days").and_then(Value::as_array) {
+                Some(days) => WEEKDAYS.map(|weekday| {
+                    days.iter().any(|day| {
+                        day.as_str()
+                            .is_some_and(|day| day.eq_ignore_ascii_case(weekday))
+                    })
+                }),
+                None => defaults.days,
+            },
+            start: time(hours, "start").unwrap_or(defaults.start),
+            end: time(hours, "end").unwrap_or(defaults.end),
+        };
+        BundleOptions {
+            excluded_components: value
+                .get("excludedComponents")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|name| {
+                    let name = name.as_str()?;
+                    ComponentKind::ALL
+                        .into_iter()
+                        .find(|kind| kind.icalendar_name().eq_ignore_ascii_case(name))
+                })
+                .collect(),
+            strip_vendor: value
+                .get("stripVendor")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            vendor_keep: text(value, "vendorKeep"),
+            normalize_contacts: value
+                .get("normalizeContacts")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            default_country: text(value, "defaultCountry"),
+            strict_parsing: value
+                .get("strictParsing")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            selected_entry: value
+                .get("selectedEntry")
+                .and_then(Value::as_u64)
+                .map(|entry| entry as usize),
+            expansion_anchor: text(expansion, "anchor"),
+            timezone: text(expansion, "timeZone"),
+            expansion_from: date(expansion, "from"),
+            expansion_until: date(expansion, "until"),
+            expansion_count: expansion
+                .get("count")
+                .and_then(Value::as_u64)
+                .map_or(0, |count| count as usize),
+            expansion_budget_ms: expansion
+                .get("budgetMs")
+                .and_then(Value::as_u64)
+                .map_or(0, |budget| budget.min(u64::from(u32::MAX)) as u32),
+            working_hours,
+            overlay: OverlayMode::parse(&text(value, "overlay")),
+            holidays: text(value, "holidays"),
+        }
+    }
+}
+
+impl SessionBundle {
+    // Outputs that differ from the ones in the bundle, for telling whether
+    // the current version still converts the source the same way.
+    pub fn changed_outputs(&self, converted: &str, roundtrip: &str) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.converted.trim() != converted.trim() {
+            changed.push("conversion");
+        }
+        if self.roundtrip.trim() != roundtrip.trim() {
+            changed.push("roundtrip");
+        }
+        changed
+    }
+
+    pub fn file_name(&self) -> String {
+        let name = self
+            .name
+            .trim()
+            .trim_end_matches(".json")
+            .trim_end_matches(".ics")
+            .trim_end_matches(".vcf");
+        format!(
+            "{}.session.json",
+            if name.is_empty() {
+                "jmap-convert"
+            } else {
+                name
+            }
+        )
+    }
+}
+
+impl fmt::Display for SessionBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = json!({
+            "@type": BUNDLE_TYPE,
+            "bundleVersion": BUNDLE_VERSION,
+            "version": self.version,
+            "exported": self.exported,
+            "name": self.name,
+            "format": self.format.map(|format| format.as_str()),
+            "source": self.source,
+            "options": self.options.to_json(),
+            "outputs": {
+                "converted": self.converted,
+                "roundtrip": self.roundtrip,
+                "occurrences": self.occurrences,
+            },
+            "findings": self.findings.iter().map(Finding::to_json).collect::<Vec<_>>(),
+        });
+        write!(
+            f,
+            "{}",
+            serde_json::to_string_pretty(&value).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+impl FromStr for SessionBundle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = serde_json::from_str::<Value>(s.trim())
+            .map_err(|err| format!("Failed to parse the session bundle: {}", err))?;
+        if value.get("@type").and_then(Value::as_str) != Some(BUNDLE_TYPE) {
+            return Err("This is not a session bundle exported by this tool.".to_string());
+        }
+        if value
+            .get("bundleVersion")
+            .and_then(Value::as_u64)
+            .is_some_and(|version| version > BUNDLE_VERSION)
+        {
+            return Err("This session bundle was exported by a newer version.".to_string());
+        }
+
+        let outputs = value.get("outputs").unwrap_or(&Value::Null);
+
+        Ok(SessionBundle {
+            version: text(&value, "version"),
+            exported: text(&value, "exported"),
+            name: text(&value, "name"),
+            format: Format::parse(&text(&value, "format")),
+            source: text(&value, "source"),
+            options: BundleOptions::from_json(value.get("options").unwrap_or(&Value::Null)),
+            converted: text(outputs, "converted"),
+            roundtrip: text(outputs, "roundtrip"),
+            occurrences: text(outputs, "occurrences"),
+            findings: value
+                .get("findings")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|finding| {
+                    Some(Finding {
+                        code: Code::parse(finding.get("code")?.as_str()?)?,
+                        message: text(finding, "message"),
+                        location: finding
+                            .get("location")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+fn text(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,114 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    converter::{normalize_json, Format},
+    diff::{diff_lines, DiffLine},
+};
+
+pub const CURRENT_LABEL: &str = "calcard (git main)";
+pub const PREVIOUS_LABEL: &str = "calcard 0.1 (crates.io)";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionOutput {
+    pub converted: String,
+    pub roundtrip: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionComparison {
+    pub current: Result<VersionOutput, String>,
+    pub previous: Result<VersionOutput, String>,
+}
+
+// Both calcard versions expose the same entry points, so the pipeline is
+// expanded once per crate.
+macro_rules! run_pipeline {
+    ($calcard:ident, $source:expr) => {{
+        use $calcard::{jscalendar::JSCalendar, jscontact::JSContact, Entry, Parser};
+
+        let source: &str = $source.trim();
+        let json = normalize_json(source);
+        if source.starts_with("BEGIN:") {
+            match Parser::new(source).entry() {
+                Entry::ICalendar(icalendar) => {
+                    let jscalendar = icalendar.into_jscalendar();
+                    let converted = jscalendar.to_string_pretty();
+                    jscalendar
+                        .into_icalendar()
+                        .map(|roundtrip| VersionOutput {
+                            converted,
+                            roundtrip: roundtrip.to_string(),
+                        })
+                        .ok_or_else(|| "Conversion back to iCalendar failed".to_string())
+                }
+                Entry::VCard(vcard) => {
+                    let jscontact = vcard.into_jscontact();
+                    let converted = jscontact.to_string_pretty();
+                    jscontact
+                        .into_vcard()
+                        .map(|roundtrip| VersionOutput {
+                            converted,
+                            roundtrip: roundtrip.to_string(),
+                        })
+                        .ok_or_else(|| "Conversion back to vCard failed".to_string())
+                }
+                other => Err(format!("Parser returned {:?}", other)),
+            }
+        } else if let Some((Format::JSCalendar, source)) = &json {
+            match JSCalendar::parse(source) {
+                Ok(jscalendar) => jscalendar
+                    .into_icalendar()
+                    .map(|icalendar| VersionOutput {
+                        converted: icalendar.to_string(),
+                        roundtrip: icalendar.into_jscalendar().to_string_pretty(),
+                    })
+                    .ok_or_else(|| "Conversion to iCalendar failed".to_string()),
+                Err(err) => Err(format!("Failed to parse JSCalendar: {}", err)),
+            }
+        } else if let Some((Format::JSContact, source)) = &json {
+            match JSContact::parse(source) {
+                Ok(jscontact) => jscontact
+                    .into_vcard()
+                    .map(|vcard| VersionOutput {
+                        converted: vcard.to_string(),
+                        roundtrip: vcard.into_jscontact().to_string_pretty(),
+                    })
+                    .ok_or_else(|| "Conversion to vCard failed".to_string()),
+                Err(err) => Err(format!("Failed to parse JSContact: {}", err)),
+            }
+        } else {
+            Err("Unrecognized format".to_string())
+        }
+    }};
+}
+
+pub fn compare_versions(source: &str) -> VersionComparison {
+    VersionComparison {
+        current: run_pipeline!(calcard, source),
+        previous: run_pipeline!(calcard_previous, source),
+    }
+}
+
+impl VersionComparison {
+    pub fn converted_diff(&self) -> Option<Vec<DiffLine>> {
+        match (&self.previous, &self.current) {
+            (Ok(previous), Ok(current)) => {
+                Some(diff_lines(&previous.converted, &current.converted))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn roundtrip_diff(&self) -> Option<Vec<DiffLine>> {
+        match (&self.previous, &self.current) {
+            (Ok(previous), Ok(current)) => {
+                Some(diff_lines(&previous.roundtrip, &current.roundtrip))
+            }
+            _ => None,
+        }
+    }
+}
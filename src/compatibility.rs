@@ -0,0 +1,461 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono_tz::Tz;
+
+use crate::{
+    contentline::{self, Component},
+    findings::{Code, Finding},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Client {
+    Google,
+    Outlook,
+    Apple,
+}
+
+pub const CLIENTS: [Client; 3] = [Client::Google, Client::Outlook, Client::Apple];
+
+// What a rule looks for in the iCalendar or vCard.
+enum Check {
+    // A TZID with no VTIMEZONE defining it. Some clients know the IANA
+    // names on their own.
+    UndefinedTimezone {
+        iana_names: bool,
+    },
+    // An RRULE part, or only some of its values when any are listed.
+    RecurrencePart {
+        part: &'static str,
+        values: &'static [&'static str],
+    },
+    Component(&'static str),
+    Property {
+        component: &'static str,
+        name: &'static str,
+    },
+    PropertyValue {
+        component: &'static str,
+        name: &'static str,
+        value: &'static str,
+    },
+    RepeatedProperty {
+        component: &'static str,
+        name: &'static str,
+    },
+    // More than one child of this type in the same parent.
+    RepeatedComponent {
+        parent: &'static str,
+        name: &'static str,
+    },
+    VCardVersion(&'static str),
+    // Any of VCARD4_PROPERTIES.
+    VCard4Property,
+}
+
+// One known problem of a client. "{}" in the message is replaced with what
+// was matched: the TZID, the RRULE part or the property.
+struct Rule {
+    check: Check,
+    message: &'static str,
+}
+
+const FREQUENT: &[&str] = &["SECONDLY", "MINUTELY", "HOURLY"];
+// Properties RFC 6350 added in vCard 4.0.
+const VCARD4_PROPERTIES: &[&str] = &[
+    "KIND",
+    "GENDER",
+    "ANNIVERSARY",
+    "MEMBER",
+    "RELATED",
+    "LANG",
+    "CLIENTPIDMAP",
+    "XML",
+];
+
+const GOOGLE_RULES: &[Rule] = &[
+    Rule {
+        check: Check::UndefinedTimezone { iana_names: true },
+        message: "TZID {} is not an IANA time zone and has no VTIMEZONE, so Google Calendar shows these times in the calendar's own zone.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "FREQ",
+            values: FREQUENT,
+        },
+        message: "Google Calendar cannot repeat events {}, the event is imported once.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "RSCALE",
+            values: &[],
+        },
+        message: "Google Calendar ignores {}, the rule is expanded on the Gregorian calendar.",
+    },
+    Rule {
+        check: Check::Component("VTODO"),
+        message: "Google Calendar does not import tasks, this {} is skipped.",
+    },
+    Rule {
+        check: Check::Component("VJOURNAL"),
+        message: "Google Calendar does not import journal entries, this {} is skipped.",
+    },
+    Rule {
+        check: Check::Component("VALARM"),
+        message: "Google Calendar drops the alarms of imported events and applies the calendar's default reminders instead of this {}.",
+    },
+    Rule {
+        check: Check::Property {
+            component: "VEVENT",
+            name: "ATTACH",
+        },
+        message: "Google Calendar drops {} from imported events.",
+    },
+    Rule {
+        check: Check::VCard4Property,
+        message: "Google Contacts drops {}, which only vCard 4.0 defines.",
+    },
+];
+
+const OUTLOOK_RULES: &[Rule] = &[
+    Rule {
+        check: Check::UndefinedTimezone { iana_names: false },
+        message: "Outlook needs a VTIMEZONE for TZID {}, without it the times are read as UTC.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "FREQ",
+            values: FREQUENT,
+        },
+        message: "Outlook cannot repeat events {}, the recurrence is dropped.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "BYWEEKNO",
+            values: &[],
+        },
+        message: "Outlook does not support {} in recurrence rules.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "BYYEARDAY",
+            values: &[],
+        },
+        message: "Outlook does not support {} in recurrence rules.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "BYHOUR",
+            values: &[],
+        },
+        message: "Outlook does not support {}, each occurrence starts at the time of DTSTART.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "BYMINUTE",
+            values: &[],
+        },
+        message: "Outlook does not support {}, each occurrence starts at the time of DTSTART.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "RSCALE",
+            values: &[],
+        },
+        message: "Outlook ignores {}, the rule is expanded on the Gregorian calendar.",
+    },
+    Rule {
+        check: Check::RepeatedProperty {
+            component: "VEVENT",
+            name: "RRULE",
+        },
+        message: "Outlook only reads the first {} of an event.",
+    },
+    Rule {
+        check: Check::RepeatedComponent {
+            parent: "VEVENT",
+            name: "VALARM",
+        },
+        message: "Outlook keeps one reminder per event, this {} is dropped.",
+    },
+    Rule {
+        check: Check::PropertyValue {
+            component: "VALARM",
+            name: "ACTION",
+            value: "EMAIL",
+        },
+        message: "Outlook shows {} alarms as a pop-up reminder, no email is sent.",
+    },
+    Rule {
+        check: Check::Component("VTODO"),
+        message: "Outlook does not import tasks from calendar files, this {} is skipped.",
+    },
+    Rule {
+        check: Check::Component("VJOURNAL"),
+        message: "Outlook does not import journal entries, this {} is skipped.",
+    },
+    Rule {
+        check: Check::Property {
+            component: "VEVENT",
+            name: "COLOR",
+        },
+        message: "Outlook ignores {}, events take the color of their calendar or category.",
+    },
+    Rule {
+        check: Check::Property {
+            component: "VEVENT",
+            name: "CONFERENCE",
+        },
+        message: "Outlook ignores {}, put the meeting link in LOCATION or DESCRIPTION.",
+    },
+    Rule {
+        check: Check::VCardVersion("4.0"),
+        message: "Outlook imports vCard 2.1 and 3.0, not vCard {}.",
+    },
+];
+
+const APPLE_RULES: &[Rule] = &[
+    Rule {
+        check: Check::UndefinedTimezone { iana_names: true },
+        message: "TZID {} is not an IANA time zone and has no VTIMEZONE, so Apple Calendar shows these times as floating.",
+    },
+    Rule {
+        check: Check::RecurrencePart {
+            part: "FREQ",
+            values: FREQUENT,
+        },
+        message: "Apple Calendar cannot repeat events {}, the event is imported once.",
+    },
+    Rule {
+        check: Check::Component("VJOURNAL"),
+        message: "Apple Calendar does not import journal entries, this {} is skipped.",
+    },
+    Rule {
+        check: Check::PropertyValue {
+            component: "VALARM",
+            name: "ACTION",
+            value: "EMAIL",
+        },
+        message: "Apple Calendar shows {} alarms as a notification, no email is sent.",
+    },
+    Rule {
+        check: Check::PropertyValue {
+            component: "VCARD",
+            name: "KIND",
+            value: "group",
+        },
+        message: "Apple Contacts imports {} as a contact, groups need X-ADDRESSBOOKSERVER-KIND:group.",
+    },
+    Rule {
+        check: Check::Property {
+            component: "VCARD",
+            name: "RELATED",
+        },
+        message: "Apple Contacts ignores {}, related names are read from X-ABRELATEDNAMES.",
+    },
+    Rule {
+        check: Check::Property {
+            component: "VCARD",
+            name: "ANNIVERSARY",
+        },
+        message: "Apple Contacts ignores {}, other dates are read from X-ABDATE.",
+    },
+];
+
+impl Client {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Client::Google => "google",
+            Client::Outlook => "outlook",
+            Client::Apple => "apple",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Client::Google => "Google Calendar and Contacts",
+            Client::Outlook => "Outlook",
+            Client::Apple => "Apple Calendar and Contacts",
+        }
+    }
+
+    fn rules(&self) -> &'static [Rule] {
+        match self {
+            Client::Google => GOOGLE_RULES,
+            Client::Outlook => OUTLOOK_RULES,
+            Client::Apple => APPLE_RULES,
+        }
+    }
+}
+
+// Constructs of an iCalendar or vCard known to break or be dropped by each
+// client, in the order of CLIENTS. JSON has nothing to check.
+pub fn check(source: &str) -> Vec<(Client, Vec<Finding>)> {
+    let components = if source.trim_start().starts_with("BEGIN:") {
+        contentline::components(source)
+    } else {
+        vec![]
+    };
+    CLIENTS
+        .iter()
+        .map(|client| {
+            let mut findings = client
+                .rules()
+                .iter()
+                .flat_map(|rule| apply(rule, &components))
+                .collect::<Vec<_>>();
+            findings.sort_by_key(|finding| {
+                finding
+                    .location
+                    .as_deref()
+                    .and_then(|location| location.strip_prefix("line "))
+                    .and_then(|line| line.parse::<usize>().ok())
+            });
+            (*client, findings)
+        })
+        .collect()
+}
+
+fn apply(rule: &Rule, components: &[Component]) -> Vec<Finding> {
+    let mut out = Vec::new();
+    let mut report = |code: Code, matched: &str, line: usize| {
+        out.push(
+            Finding::new(code, rule.message.replace("{}", matched))
+                .with_location(format!("line {}", line)),
+        );
+    };
+    let named = |name: &'static str| {
+        components
+            .iter()
+            .filter(move |component| component.name() == name)
+    };
+    match rule.check {
+        Check::UndefinedTimezone { iana_names } => {
+            let defined = named("VTIMEZONE")
+                .flat_map(|component| &component.properties)
+                .filter(|property| property.name == "TZID")
+                .map(|property| property.value.trim())
+                .collect::<Vec<_>>();
+            let mut reported: Vec<&str> = Vec::new();
+            for property in components
+                .iter()
+                .flat_map(|component| &component.properties)
+            {
+                let Some(tzid) = property.param("TZID") else {
+                    continue;
+                };
+                if defined.contains(&tzid)
+                    || reported.contains(&tzid)
+                    || (iana_names && tzid.trim_start_matches('/').parse::<Tz>().is_ok())
+                {
+                    continue;
+                }
+                reported.push(tzid);
+                report(Code::UndefinedTimezone, tzid, property.line);
+            }
+        }
+        Check::RecurrencePart { part, values } => {
+            for property in components
+                .iter()
+                .flat_map(|component| &component.properties)
+                .filter(|property| property.name == "RRULE")
+            {
+                let found = property.value.split(';').find_map(|item| {
+                    let (name, value) = item.split_once('=')?;
+                    name.trim().eq_ignore_ascii_case(part).then(|| value.trim())
+                });
+                match found {
+                    Some(value)
+                        if values.is_empty()
+                            || values.iter().any(|item| item.eq_ignore_ascii_case(value)) =>
+                    {
+                        let matched = if values.is_empty() {
+                            part.to_string()
+                        } else {
+                            format!("{}={}", part, value.to_ascii_uppercase())
+                        };
+                        report(Code::UnsupportedRecurrence, &matched, property.line);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Check::Component(name) => {
+            for component in named(name) {
+                report(Code::UnsupportedComponent, name, component.line);
+            }
+        }
+        Check::Property { component, name } => {
+            for property in named(component)
+                .flat_map(|component| &component.properties)
+                .filter(|property| property.name == name)
+            {
+                report(Code::UnsupportedProperty, name, property.line);
+            }
+        }
+        Check::PropertyValue {
+            component,
+            name,
+            value,
+        } => {
+            for property in named(component)
+                .flat_map(|component| &component.properties)
+                .filter(|property| {
+                    property.name == name && property.value.trim().eq_ignore_ascii_case(value)
+                })
+            {
+                report(
+                    Code::UnsupportedProperty,
+                    &format!("{}:{}", name, property.value.trim()),
+                    property.line,
+                );
+            }
+        }
+        Check::RepeatedProperty { component, name } => {
+            for property in named(component).flat_map(|component| {
+                component
+                    .properties
+                    .iter()
+                    .filter(|property| property.name == name)
+                    .skip(1)
+            }) {
+                report(Code::UnsupportedProperty, name, property.line);
+            }
+        }
+        Check::RepeatedComponent { parent, name } => {
+            for parent in named(parent) {
+                let prefix = format!("{}/{}[", parent.path, name);
+                for child in components
+                    .iter()
+                    .filter(|component| component.path.starts_with(&prefix))
+                    .skip(1)
+                {
+                    report(Code::UnsupportedComponent, name, child.line);
+                }
+            }
+        }
+        Check::VCardVersion(version) => {
+            for component in named("VCARD") {
+                if let Some(property) = component
+                    .properties
+                    .iter()
+                    .find(|property| property.name == "VERSION" && property.value.trim() == version)
+                {
+                    report(Code::UnsupportedVCardVersion, version, property.line);
+                }
+            }
+        }
+        Check::VCard4Property => {
+            for property in named("VCARD")
+                .flat_map(|component| &component.properties)
+                .filter(|property| VCARD4_PROPERTIES.contains(&property.name.as_str()))
+            {
+                report(Code::UnsupportedProperty, &property.name, property.line);
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,455 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Phone numbers rewritten to E.164 and email addresses checked in vCard,
+// jCard and JSContact, before conversion. National numbers are read in the
+// default country; values that cannot be cleaned are left as they are and
+// reported.
+
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, fold_line},
+    findings::{Code, Finding},
+};
+
+pub struct Country {
+    // ISO 3166-1 alpha-2.
+    pub code: &'static str,
+    pub name: &'static str,
+    pub calling_code: &'static str,
+    // Dialed before national numbers within the country, empty where the
+    // leading digit is part of the number.
+    pub trunk_prefix: &'static str,
+    // Dialed before numbers abroad.
+    pub international_prefix: &'static str,
+}
+
+pub const COUNTRIES: &[Country] = &[
+    country("AR", "Argentina", "54", "0", "00"),
+    country("AU", "Australia", "61", "0", "0011"),
+    country("AT", "Austria", "43", "0", "00"),
+    country("BE", "Belgium", "32", "0", "00"),
+    country("BR", "Brazil", "55", "0", "00"),
+    country("CA", "Canada", "1", "1", "011"),
+    country("CN", "China", "86", "0", "00"),
+    country("CZ", "Czechia", "420", "", "00"),
+    country("DK", "Denmark", "45", "", "00"),
+    country("FI", "Finland", "358", "0", "00"),
+    country("FR", "France", "33", "0", "00"),
+    country("DE", "Germany", "49", "0", "00"),
+    country("GR", "Greece", "30", "", "00"),
+    country("HU", "Hungary", "36", "06", "00"),
+    country("IN", "India", "91", "0", "00"),
+    country("IE", "Ireland", "353", "0", "00"),
+    country("IL", "Israel", "972", "0", "00"),
+    country("IT", "Italy", "39", "", "00"),
+    country("JP", "Japan", "81", "0", "010"),
+    country("LU", "Luxembourg", "352", "", "00"),
+    country("MX", "Mexico", "52", "", "00"),
+    country("NL", "Netherlands", "31", "0", "00"),
+    country("NZ", "New Zealand", "64", "0", "00"),
+    country("NO", "Norway", "47", "", "00"),
+    country("PL", "Poland", "48", "", "00"),
+    country("PT", "Portugal", "351", "", "00"),
+    country("RO", "Romania", "40", "0", "00"),
+    country("RU", "Russia", "7", "8", "810"),
+    country("SG", "Singapore", "65", "", "000"),
+    country("ZA", "South Africa", "27", "0", "00"),
+    country("KR", "South Korea", "82", "0", "001"),
+    country("ES", "Spain", "34", "", "00"),
+    country("SE", "Sweden", "46", "0", "00"),
+    country("CH", "Switzerland", "41", "0", "00"),
+    country("TR", "Turkey", "90", "0", "00"),
+    country("UA", "Ukraine", "380", "0", "00"),
+    country("AE", "United Arab Emirates", "971", "0", "00"),
+    country("GB", "United Kingdom", "44", "0", "00"),
+    country("US", "United States", "1", "1", "011"),
+];
+
+const fn country(
+    code: &'static str,
+    name: &'static str,
+    calling_code: &'static str,
+    trunk_prefix: &'static str,
+    international_prefix: &'static str,
+) -> Country {
+    Country {
+        code,
+        name,
+        calling_code,
+        trunk_prefix,
+        international_prefix,
+    }
+}
+
+pub fn find_country(code: &str) -> Option<&'static Country> {
+    let code = code.trim();
+    COUNTRIES
+        .iter()
+        .find(|country| country.code.eq_ignore_ascii_case(code))
+}
+
+// Rewrites phone numbers to E.164 and cleans email addresses, reporting every
+// changed value and every one that is not valid. `country` is the ISO code
+// national numbers are read in, or empty to only accept international ones.
+pub fn normalize(source: &str, country: &str) -> (String, Vec<Finding>) {
+    let country = find_country(country);
+    let trimmed = source.trim_start();
+    if trimmed.starts_with("BEGIN:") {
+        normalize_text(source, country)
+    } else if trimmed.starts_with(['{', '[']) {
+        match serde_json::from_str::<Value>(trimmed) {
+            Ok(mut root) => {
+                let mut findings = Vec::new();
+                if normalize_value("", &mut root, country, &mut findings) {
+                    (
+                        serde_json::to_string_pretty(&root).unwrap_or_else(|_| source.to_string()),
+                        findings,
+                    )
+                } else {
+                    (source.to_string(), findings)
+                }
+            }
+            Err(_) => (source.to_string(), vec![]),
+        }
+    } else {
+        (source.to_string(), vec![])
+    }
+}
+
+// The number in E.164 form, keeping a tel: URI a URI, and any extension.
+pub fn normalize_phone(value: &str, country: Option<&Country>) -> Result<String, String> {
+    let value = value.trim();
+    let (uri, number, mut extension, context) = match value
+        .get(..4)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("tel:"))
+    {
+        Some(_) => {
+            let mut parts = value[4..].split(';');
+            let number = parts.next().unwrap_or_default();
+            let mut extension = None;
+            let mut context = None;
+            for param in parts {
+                match param.split_once('=') {
+                    Some((name, value)) if name.eq_ignore_ascii_case("ext") => {
+                        extension = Some(value.to_string())
+                    }
+                    Some((name, value)) if name.eq_ignore_ascii_case("phone-context") => {
+                        context = Some(value)
+                    }
+                    _ => {}
+                }
+            }
+            (true, number, extension, context)
+        }
+        None => (false, value, None, None),
+    };
+    let number = match split_extension(number) {
+        Some((number, found)) => {
+            extension.get_or_insert(found);
+            number
+        }
+        None => number,
+    };
+    // "+49 (0)30 ..." shows the trunk prefix dialed within the country.
+    let number = if number.starts_with('+') {
+        number.replacen("(0)", "", 1)
+    } else {
+        number.to_string()
+    };
+
+    let mut digits = String::new();
+    for (index, ch) in number.chars().enumerate() {
+        match ch {
+            '0'..='9' => digits.push(ch),
+            '+' if index == 0 => {}
+            ' ' | '\u{a0}' | '-' | '.' | '(' | ')' | '/' => {}
+            ch if ch.is_alphabetic() => return Err("it contains letters".to_string()),
+            ch => return Err(format!("it contains \"{}\"", ch)),
+        }
+    }
+    if digits.is_empty() {
+        return Err("it has no digits".to_string());
+    }
+
+    let international = if number.starts_with('+') {
+        digits
+    } else if let Some(context) = context.filter(|context| context.starts_with('+')) {
+        let prefix = context
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>();
+        format!("{}{}", prefix, digits)
+    } else if let Some(country) = country {
+        if let Some(rest) = digits.strip_prefix(country.international_prefix) {
+            rest.to_string()
+        } else {
+            let national = digits
+                .strip_prefix(country.trunk_prefix)
+                .filter(|_| !country.trunk_prefix.is_empty())
+                .unwrap_or(&digits);
+            format!("{}{}", country.calling_code, national)
+        }
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.to_string()
+    } else {
+        return Err("it is a national number and no default country is set".to_string());
+    };
+
+    if international.starts_with('0') {
+        return Err("no country calling code starts with 0".to_string());
+    }
+    if international.len() < 7 {
+        return Err("it is too short for an international number".to_string());
+    }
+    if international.len() > 15 {
+        return Err("E.164 numbers have at most 15 digits".to_string());
+    }
+
+    Ok(match (uri, extension) {
+        (true, Some(extension)) => format!("tel:+{};ext={}", international, extension),
+        (true, None) => format!("tel:+{}", international),
+        (false, Some(extension)) => format!("+{} ext. {}", international, extension),
+        (false, None) => format!("+{}", international),
+    })
+}
+
+// Splits a trailing extension such as "x12", "ext. 12" or "#12" off a number.
+fn split_extension(number: &str) -> Option<(&str, String)> {
+    let lower = number.to_ascii_lowercase();
+    ["ext.", "ext", "x", "#"].iter().find_map(|marker| {
+        let at = lower.rfind(marker)?;
+        let extension = number[at + marker.len()..].trim();
+        (!extension.is_empty() && extension.chars().all(|ch| ch.is_ascii_digit()))
+            .then(|| (number[..at].trim_end(), extension.to_string()))
+    })
+}
+
+// The address without a mailto: scheme, display name or surrounding spaces,
+// and with its domain in lowercase.
+pub fn normalize_email(value: &str) -> Result<String, String> {
+    let mut address = value.trim();
+    if let Some(rest) = address
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+        .map(|_| &address[7..])
+    {
+        address = rest.trim();
+    }
+    if let (Some(start), true) = (address.rfind('<'), address.ends_with('>')) {
+        address = address[start + 1..address.len() - 1].trim();
+    }
+
+    let Some((local, domain)) = address.rsplit_once('@') else {
+        return Err("it has no @".to_string());
+    };
+    if local.is_empty() {
+        return Err("the part before the @ is empty".to_string());
+    }
+    if domain.is_empty() {
+        return Err("the part after the @ is empty".to_string());
+    }
+    if local.len() > 64 {
+        return Err("the part before the @ is longer than 64 characters".to_string());
+    }
+    let quoted = local.len() > 1 && local.starts_with('"') && local.ends_with('"');
+    if !quoted {
+        if let Some(ch) = local
+            .chars()
+            .find(|ch| !(ch.is_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(*ch)))
+        {
+            return Err(format!("\"{}\" is not allowed before the @", ch));
+        }
+        if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+            return Err("the part before the @ has a misplaced dot".to_string());
+        }
+    }
+
+    if domain.starts_with('[') && domain.ends_with(']') {
+        return Ok(format!("{}@{}", local, domain));
+    }
+    let domain = domain.to_lowercase();
+    let labels = domain.split('.').collect::<Vec<_>>();
+    if domain.len() > 253 || labels.len() < 2 {
+        return Err(format!("\"{}\" is not a domain name", domain));
+    }
+    for label in &labels {
+        if label.is_empty()
+            || label.len() > 63
+            || label.starts_with('-')
+            || label.ends_with('-')
+            || !label.chars().all(|ch| ch.is_alphanumeric() || ch == '-')
+        {
+            return Err(format!("\"{}\" is not a domain name", domain));
+        }
+    }
+    if labels
+        .last()
+        .is_some_and(|tld| tld.chars().all(|ch| ch.is_ascii_digit()))
+    {
+        return Err(format!("\"{}\" is not a domain name", domain));
+    }
+    Ok(format!("{}@{}", local, domain))
+}
+
+fn normalize_text(source: &str, country: Option<&Country>) -> (String, Vec<Finding>) {
+    let mut text = String::with_capacity(source.len());
+    let mut findings = Vec::new();
+    for (number, raw) in contentline::unfold(source) {
+        let Some(mut line) = contentline::parse_line(number, &raw) else {
+            fold_line(&mut text, &raw);
+            continue;
+        };
+        let location = format!("line {}", number);
+        let cleaned = match line.name.as_str() {
+            "TEL" => check_phone(&line.value, country, location, &mut findings),
+            "EMAIL" => check_email(&line.value, location, &mut findings),
+            _ => None,
+        };
+        match cleaned {
+            Some(value) => {
+                line.value = value;
+                fold_line(&mut text, &line.to_line());
+            }
+            None => fold_line(&mut text, &raw),
+        }
+    }
+    (text, findings)
+}
+
+// Cleans the phones and emails of JSContact cards and the tel and email
+// properties of jCards. Returns whether any value changed.
+fn normalize_value(
+    path: &str,
+    value: &mut Value,
+    country: Option<&Country>,
+    findings: &mut Vec<Finding>,
+) -> bool {
+    let mut changed = false;
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                let field = match key.as_str() {
+                    "phones" => Some("number"),
+                    "emails" => Some("address"),
+                    _ => None,
+                };
+                match (field, value) {
+                    (Some(field), Value::Object(entries)) => {
+                        for (id, entry) in entries.iter_mut() {
+                            let location = format!("{}/{}/{}", path, id, field);
+                            let Some(Value::String(text)) = entry.get_mut(field) else {
+                                continue;
+                            };
+                            let cleaned = match field {
+                                "number" => check_phone(text, country, location, findings),
+                                _ => check_email(text, location, findings),
+                            };
+                            if let Some(cleaned) = cleaned {
+                                *text = cleaned;
+                                changed = true;
+                            }
+                        }
+                    }
+                    (_, value) => changed |= normalize_value(&path, value, country, findings),
+                }
+            }
+        }
+        Value::Array(items) => {
+            // A jCard property is [name, parameters, type, value].
+            let name = items
+                .first()
+                .and_then(Value::as_str)
+                .map(str::to_ascii_lowercase);
+            let location = format!("{}/3", path);
+            let cleaned = match (name.as_deref(), items.get(3)) {
+                (Some("tel"), Some(Value::String(text))) if items[1].is_object() => {
+                    check_phone(text, country, location, findings)
+                }
+                (Some("email"), Some(Value::String(text))) if items[1].is_object() => {
+                    check_email(text, location, findings)
+                }
+                _ => {
+                    for (index, item) in items.iter_mut().enumerate() {
+                        changed |= normalize_value(
+                            &format!("{}/{}", path, index),
+                            item,
+                            country,
+                            findings,
+                        );
+                    }
+                    None
+                }
+            };
+            if let Some(cleaned) = cleaned {
+                items[3] = Value::String(cleaned);
+                changed = true;
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+// The cleaned value when it differs from `value`.
+fn check_phone(
+    value: &str,
+    country: Option<&Country>,
+    location: String,
+    findings: &mut Vec<Finding>,
+) -> Option<String> {
+    match normalize_phone(value, country) {
+        Ok(cleaned) if cleaned != value => {
+            findings.push(
+                Finding::new(
+                    Code::PhoneNumberNormalized,
+                    format!("Rewrote phone number \"{}\" as \"{}\".", value, cleaned),
+                )
+                .with_location(location),
+            );
+            Some(cleaned)
+        }
+        Ok(_) => None,
+        Err(reason) => {
+            findings.push(
+                Finding::new(
+                    Code::InvalidPhoneNumber,
+                    format!("Kept phone number \"{}\" as is: {}.", value, reason),
+                )
+                .with_location(location),
+            );
+            None
+        }
+    }
+}
+
+fn check_email(value: &str, location: String, findings: &mut Vec<Finding>) -> Option<String> {
+    match normalize_email(value) {
+        Ok(cleaned) if cleaned != value => {
+            findings.push(
+                Finding::new(
+                    Code::EmailAddressNormalized,
+                    format!("Rewrote email address \"{}\" as \"{}\".", value, cleaned),
+                )
+                .with_location(location),
+            );
+            Some(cleaned)
+        }
+        Ok(_) => None,
+        Err(reason) => {
+            findings.push(
+                Finding::new(
+                    Code::InvalidEmailAddress,
+                    format!("Email address \"{}\" is not valid: {}.", value, reason),
+                )
+                .with_location(location),
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,412 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+// Minimal reader for the raw content lines of a vCard or iCalendar file, used
+// to inspect properties and parameters the way they were written, before any
+// conversion normalizes them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentLine {
+    pub line: usize,
+    pub group: Option<String>,
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl ContentLine {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Parameter values that may be repeated or comma separated, such as TYPE.
+    pub fn param_values(&self, name: &str) -> Vec<String> {
+        self.params
+            .iter()
+            .filter(|(param, _)| param.eq_ignore_ascii_case(name))
+            .flat_map(|(_, value)| value.split(','))
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    pub fn has_type(&self, value: &str) -> bool {
+        self.param_values("TYPE")
+            .iter()
+            .any(|item| item.eq_ignore_ascii_case(value))
+    }
+
+    pub fn text(&self) -> String {
+        unescape(&self.value)
+    }
+
+    pub fn set_param(&mut self, name: &str, value: &str) {
+        match self
+            .params
+            .iter_mut()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+        {
+            Some((_, current)) => *current = value.to_string(),
+            None => self
+                .params
+                .push((name.to_ascii_uppercase(), value.to_string())),
+        }
+    }
+
+    pub fn remove_param(&mut self, name: &str) {
+        self.params
+            .retain(|(param, _)| !param.eq_ignore_ascii_case(name));
+    }
+
+    // Writes the line back out, unfolded, quoting parameter values as needed.
+    pub fn to_line(&self) -> String {
+        let mut line = String::with_capacity(self.name.len() + self.value.len() + 16);
+        if let Some(group) = &self.group {
+            line.push_str(group);
+            line.push('.');
+        }
+        line.push_str(&self.name);
+        for (name, value) in &self.params {
+            line.push(';');
+            line.push_str(name);
+            line.push('=');
+            if value.contains([':', ';', ',']) {
+                line.push('"');
+                line.push_str(value);
+                line.push('"');
+            } else {
+                line.push_str(value);
+            }
+        }
+        line.push(':');
+        line.push_str(&self.value);
+        line
+    }
+}
+
+pub fn parse(source: &str) -> Vec<ContentLine> {
+    unfold(source)
+        .into_iter()
+        .filter_map(|(line, text)| parse_line(line, &text))
+        .collect()
+}
+
+// Joins folded lines, returning each logical line with its first line number.
+pub fn unfold(source: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (idx, raw) in source.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        match (
+            raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')),
+            lines.last_mut(),
+        ) {
+            (Some(continuation), Some((_, line))) => line.push_str(continuation),
+            _ if raw.is_empty() => {}
+            _ => lines.push((idx + 1, raw.to_string())),
+        }
+    }
+    lines
+}
+
+pub fn parse_line(line: usize, text: &str) -> Option<ContentLine> {
+    let mut in_quotes = false;
+    let mut value_start = None;
+    let mut segments = Vec::new();
+    let mut segment_start = 0;
+    for (idx, ch) in text.char_indices() {
+        match ch {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                segments.push(&text[segment_start..idx]);
+                segment_start = idx + 1;
+            }
+            ':' if !in_quotes => {
+                segments.push(&text[segment_start..idx]);
+                value_start = Some(idx + 1);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let value = text[value_start?..].to_string();
+
+    let mut segments = segments.into_iter();
+    let name = segments.next()?;
+    let (group, name) = match name.split_once('.') {
+        Some((group, name)) => (Some(group.to_string()), name),
+        None => (None, name),
+    };
+    let params = segments
+        .map(|param| match param.split_once('=') {
+            Some((name, value)) => (
+                name.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ),
+            // vCard 2.1 style bare parameters are types.
+            None => ("TYPE".to_string(), param.to_string()),
+        })
+        .collect();
+
+    Some(ContentLine {
+        line,
+        group,
+        name: name.to_ascii_uppercase(),
+        params,
+        value,
+    })
+}
+
+pub fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('n' | 'N') => result.push('\n'),
+                Some(ch) => result.push(ch),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Appends a content line folded at 75 octets, CRLF terminated.
+pub fn fold_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        width += ch.len_utf8();
+        out.push(ch);
+    }
+    out.push_str("\r\n");
+}
+
+// Splits a structured value on unescaped separators.
+pub fn split_unescaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            current.push(ch);
+            if let Some(next) = chars.next() {
+                current.push(next);
+            }
+        } else if ch == separator {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(ch);
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+// Splits a BYDAY entry such as -1SU into its ordinal and its two letter
+// weekday, None when the entry is not one.
+pub fn parse_byday(value: &str) -> Option<(Option<i32>, &str)> {
+    let value = value.trim();
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, weekday) = (value.get(..split)?, value.get(split..)?);
+    if !["MO", "TU", "WE", "TH", "FR", "SA", "SU"]
+        .iter()
+        .any(|day| day.eq_ignore_ascii_case(weekday))
+    {
+        return None;
+    }
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(ordinal.parse().ok()?),
+    };
+    Some((ordinal, weekday))
+}
+
+// Reads a DATE such as 19970714.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y%m%d").ok()
+}
+
+// Reads a DATE-TIME such as 19970714T133000, dropping the Z of a UTC one.
+pub fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    NaiveDateTime::parse_from_str(value.strip_suffix('Z').unwrap_or(value), "%Y%m%dT%H%M%S").ok()
+}
+
+// Reads a DATE-TIME, or a DATE as its midnight.
+pub fn parse_date_or_datetime(value: &str) -> Option<NaiveDateTime> {
+    parse_datetime(value).or_else(|| parse_date(value)?.and_hms_opt(0, 0, 0))
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+// Writes a local DATE-TIME, a UTC one once a Z is added.
+pub fn format_datetime(date_time: NaiveDateTime) -> String {
+    date_time.format("%Y%m%dT%H%M%S").to_string()
+}
+
+// Decodes =XX escapes, as used by vCard 2.1 and MIME bodies.
+pub fn decode_quoted_printable(value: &str) -> Vec<u8> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'=' {
+            let hex = bytes
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = hex {
+                out.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        out.push(bytes[index]);
+        index += 1;
+    }
+    out
+}
+
+// A component with its own properties, identified by a path that stays the
+// same across two versions of a file: UID and RECURRENCE-ID when present,
+// otherwise the position among siblings of the same type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Component {
+    pub path: String,
+    pub line: usize,
+    pub properties: Vec<ContentLine>,
+}
+
+impl Component {
+    // The component type, the last path segment without its key.
+    pub fn name(&self) -> &str {
+        let segment = self.path.rsplit('/').next().unwrap_or_default();
+        segment.split('[').next().unwrap_or_default()
+    }
+}
+
+pub fn components(source: &str) -> Vec<Component> {
+    // Parents always come before their children.
+    let mut parsed: Vec<(Option<usize>, String, Component)> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    for line in parse(source) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                parsed.push((
+                    stack.last().copied(),
+                    line.value.trim().to_ascii_uppercase(),
+                    Component {
+                        line: line.line,
+                        ..Default::default()
+                    },
+                ));
+                stack.push(parsed.len() - 1);
+            }
+            "END" => {
+                stack.pop();
+            }
+            _ => {
+                if let Some(index) = stack.last() {
+                    parsed[*index].2.properties.push(line);
+                }
+            }
+        }
+    }
+
+    for index in 0..parsed.len() {
+        let (parent, name, component) = &parsed[index];
+        let value = |name: &str| {
+            component
+                .properties
+                .iter()
+                .find(|property| property.name == name)
+                .map(|property| property.value.trim())
+        };
+        let key = match (value("UID"), value("RECURRENCE-ID")) {
+            (Some(uid), Some(recurrence_id)) => format!("{} {}", uid, recurrence_id),
+            (Some(uid), None) => uid.to_string(),
+            _ => {
+                let position = parsed[..index]
+                    .iter()
+                    .filter(|(other_parent, other_name, _)| {
+                        other_parent == parent && other_name == name
+                    })
+                    .count();
+                (position + 1).to_string()
+            }
+        };
+        let parent = parent
+            .map(|parent| parsed[parent].2.path.as_str())
+            .unwrap_or_default();
+        let path = format!("{}/{}[{}]", parent, name, key);
+        parsed[index].2.path = path;
+    }
+    parsed
+        .into_iter()
+        .map(|(_, _, component)| component)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_byday() {
+        assert_eq!(parse_byday("MO"), Some((None, "MO")));
+        assert_eq!(parse_byday(" -1su"), Some((Some(-1), "su")));
+        assert_eq!(parse_byday("+2TH"), Some((Some(2), "TH")));
+        assert_eq!(parse_byday("XX"), None);
+        assert_eq!(parse_byday("1XMO"), None);
+        assert_eq!(parse_byday("M"), None);
+    }
+
+    #[test]
+    fn parses_dates() {
+        let date = NaiveDate::from_ymd_opt(1997, 7, 14).unwrap();
+        let date_time = date.and_hms_opt(13, 30, 0).unwrap();
+        assert_eq!(parse_date(" 19970714"), Some(date));
+        assert_eq!(parse_datetime("19970714T133000"), Some(date_time));
+        assert_eq!(parse_datetime("19970714T133000Z"), Some(date_time));
+        assert_eq!(parse_datetime("19970714T133000ZZ"), None);
+        assert_eq!(parse_datetime("19970714T13€000"), None);
+        assert_eq!(parse_date_or_datetime("19970714T133000Z"), Some(date_time));
+        assert_eq!(
+            parse_date_or_datetime("19970714"),
+            date.and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(parse_date_or_datetime("1997-07-14"), None);
+        assert_eq!(format_date(date), "19970714");
+        assert_eq!(format_datetime(date_time), "19970714T133000");
+    }
+
+    #[test]
+    fn rejects_non_ascii_byday() {
+        assert_eq!(parse_byday("ÖA"), None);
+        assert_eq!(parse_byday("1€"), None);
+        assert_eq!(parse_byday("2S€U"), None);
+        assert_eq!(parse_byday("€MO"), None);
+    }
+}
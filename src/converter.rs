@@ -0,0 +1,1138 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{borrow::Cow, collections::HashSet, ops::Range};
+
+use calcard::{icalendar::ICalendar, jscalendar::JSCalendar, jscontact::JSContact, Entry, Parser};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    colors, contentline, description,
+    fidelity::{self, Loss},
+    findings::{Code, Finding},
+    identity, jcal, location, month_end, patches, pathological, phonetic, status,
+};
+
+const ROUNDTRIP_FAILED: &str = "Looks like you've found a bug in the conversion. Please report it.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    ICalendar,
+    JSCalendar,
+    VCard,
+    JSContact,
+    JCal,
+    JCard,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConversionResult {
+    pub format: Format,
+    // Source converted to the counterpart format.
+    pub converted: String,
+    // Converted output taken back to the source format.
+    pub roundtrip: String,
+    pub findings: Vec<Finding>,
+    // What did not survive the roundtrip.
+    pub fidelity: Vec<Loss>,
+    // Parsed calendar, for expanding occurrences.
+    pub icalendar: Option<ICalendar>,
+}
+
+// One document of a multi-entry file and its conversion.
+#[derive(Clone, Debug)]
+pub struct EntryResult {
+    pub source: String,
+    pub result: Result<ConversionResult, Finding>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Converter;
+
+impl Format {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::ICalendar => "iCalendar",
+            Format::JSCalendar => "JSCalendar",
+            Format::VCard => "vCard",
+            Format::JSContact => "JSContact",
+            Format::JCal => "jCal",
+            Format::JCard => "jCard",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "icalendar" => Some(Format::ICalendar),
+            "jscalendar" => Some(Format::JSCalendar),
+            "vcard" => Some(Format::VCard),
+            "jscontact" => Some(Format::JSContact),
+            "jcal" => Some(Format::JCal),
+            "jcard" => Some(Format::JCard),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::ICalendar => "ics",
+            Format::VCard => "vcf",
+            Format::JSCalendar | Format::JSContact | Format::JCal | Format::JCard => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Format::ICalendar => "text/calendar",
+            Format::VCard => "text/vcard",
+            Format::JSCalendar | Format::JSContact => "application/json",
+            Format::JCal => "application/calendar+json",
+            Format::JCard => "application/vcard+json",
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        matches!(
+            self,
+            Format::JSCalendar | Format::JSContact | Format::JCal | Format::JCard
+        )
+    }
+
+    pub fn is_calendar(&self) -> bool {
+        matches!(self, Format::ICalendar | Format::JSCalendar | Format::JCal)
+    }
+
+    // Joins documents of this format into one file: JSON documents become an
+    // array and text documents are concatenated.
+    pub fn join<'x>(&self, documents: impl IntoIterator<Item = &'x str>) -> String {
+        let documents = documents.into_iter().collect::<Vec<_>>();
+        if documents.len() == 1 {
+            return documents[0].to_string();
+        }
+        if self.is_json() {
+            let values = documents
+                .iter()
+                .map(|document| serde_json::from_str(document).unwrap_or(Value::Null))
+                .collect();
+            serde_json::to_string_pretty(&Value::Array(values)).unwrap_or_default()
+        } else {
+            let mut text = String::new();
+            for document in documents {
+                text.push_str(document);
+                if !document.ends_with('\n') {
+                    text.push_str("\r\n");
+                }
+            }
+            text
+        }
+    }
+
+    pub fn counterpart(&self) -> Format {
+        match self {
+            Format::ICalendar => Format::JSCalendar,
+            Format::JSCalendar => Format::ICalendar,
+            Format::VCard => Format::JSContact,
+            Format::JSContact => Format::VCard,
+            Format::JCal => Format::JSCalendar,
+            Format::JCard => Format::JSContact,
+        }
+    }
+
+    // jCal or jCard for iCalendar and vCard, the format itself otherwise.
+    pub fn json_form(&self) -> Format {
+        match self {
+            Format::ICalendar => Format::JCal,
+            Format::VCard => Format::JCard,
+            format => *format,
+        }
+    }
+}
+
+impl ConversionResult {
+    // The result written as `to`, or as the counterpart format when None.
+    // Asking for the source format gives the round-tripped document.
+    pub fn output(&self, to: Option<Format>) -> Result<(Format, Cow<'_, str>), String> {
+        let counterpart = self.format.counterpart();
+        match to {
+            None => Ok((counterpart, Cow::Borrowed(self.converted.as_str()))),
+            Some(to) if to == counterpart => Ok((to, Cow::Borrowed(self.converted.as_str()))),
+            Some(to) if to == self.format => Ok((to, Cow::Borrowed(self.roundtrip.as_str()))),
+            // iCalendar and vCard output can be written as jCal and jCard too.
+            Some(to) if to == counterpart.json_form() => {
+                Ok((to, Cow::Owned(jcal::from_text(&self.converted))))
+            }
+            Some(to) if to == self.format.json_form() => {
+                Ok((to, Cow::Owned(jcal::from_text(&self.roundtrip))))
+            }
+            Some(to) => Err(format!(
+                "Cannot convert {} to {}",
+                self.format.as_str(),
+                to.as_str()
+            )),
+        }
+    }
+}
+
+impl Converter {
+    // Guesses the format from the first line or, for JSON, from the @type of
+    // the top-level object.
+    pub fn detect_format(&self, source: &str) -> Result<Format, Finding> {
+        let source = source.trim_start();
+        if source.starts_with("BEGIN:") {
+            let first = source.lines().next().unwrap_or_default();
+            Ok(if first.trim().eq_ignore_ascii_case("BEGIN:VCARD") {
+                Format::VCard
+            } else {
+                Format::ICalendar
+            })
+        } else if source.starts_with('{') {
+            normalize_json(source)
+                .map(|(format, _)| format)
+                .ok_or_else(|| {
+                    Finding::new(
+                        Code::UnknownJsonType,
+                        "This does not look like a valid JSCalendar or JSContact. The top-level @type must be Group, Event, Task, Card or CardGroup.",
+                    )
+                })
+        } else if source.starts_with('[') {
+            jcal::detect(source).ok_or_else(|| {
+                Finding::new(
+                    Code::UnknownJsonType,
+                    "This does not look like a valid jCal or jCard. The array must start with \"vcalendar\" or \"vcard\".",
+                )
+            })
+        } else {
+            Err(Finding::new(Code::UnrecognizedFormat, "Unrecognized format. Please provide a valid iCalendar, JSCalendar, vCard, JSContact, jCal or jCard file."))
+        }
+    }
+
+    // Converts the source to its counterpart format and back, collecting the
+    // findings of every check that applies to it.
+    pub fn convert(&self, source: &str) -> Result<ConversionResult, Finding> {
+        let original = source;
+        let source = source.trim_start();
+        // Parse errors are located in the source as it was given.
+        let skipped = original.len() - source.len();
+        let format = self.detect_format(source).map_err(|finding| {
+            if finding.code == Code::UnknownJsonType {
+                json_error_location(original, finding)
+            } else {
+                finding
+            }
+        })?;
+        match format {
+            Format::ICalendar | Format::VCard => match Parser::new(source).entry() {
+                Entry::VCard(vcard) => {
+                    let jscontact = vcard.into_jscontact();
+                    let converted = jscontact.to_string_pretty();
+                    let roundtrip = jscontact
+                        .into_vcard()
+                        .ok_or_else(roundtrip_failed)?
+                        .to_string();
+                    let findings = check_contact(source, &converted, &roundtrip);
+                    Ok(ConversionResult {
+                        format: Format::VCard,
+                        converted,
+                        fidelity: fidelity::report(source, &roundtrip),
+                        roundtrip,
+                        findings,
+                        icalendar: None,
+                    })
+                }
+                Entry::ICalendar(icalendar) => {
+                    let mut findings = audit_recurrences(source, &icalendar);
+                    findings.extend(status::audit(&status::entries(source)));
+                    findings.extend(location::audit(&location::structured_locations(source)));
+                    let jscalendar = icalendar.clone().into_jscalendar();
+                    let converted = jscalendar.to_string_pretty();
+                    let converted = description::apply_html_to_jscalendar(source, &converted)
+                        .unwrap_or(converted);
+                    let converted =
+                        location::apply_to_jscalendar(source, &converted).unwrap_or(converted);
+                    let roundtrip = jscalendar
+                        .into_icalendar()
+                        .ok_or_else(roundtrip_failed)?
+                        .to_string();
+                    let roundtrip = description::apply_html_to_icalendar(&converted, &roundtrip)
+                        .unwrap_or(roundtrip);
+                    findings.extend(colors::check_conversion(source, &converted, &roundtrip));
+                    Ok(ConversionResult {
+                        format: Format::ICalendar,
+                        converted,
+                        fidelity: fidelity::report(source, &roundtrip),
+                        roundtrip,
+                        findings,
+                        icalendar: Some(icalendar),
+                    })
+                }
+                Entry::InvalidLine(text) => {
+                    let finding =
+                        Finding::new(Code::InvalidLine, format!("Invalid line found: {}", text));
+                    Err(match source.find(text.trim()) {
+                        Some(offset) => finding.with_location(position(original, skipped + offset)),
+                        None => finding,
+                    })
+                }
+                Entry::UnexpectedComponentEnd { expected, found } => {
+                    let end = format!("END:{}", found.as_str());
+                    let finding = Finding::new(
+                        Code::UnexpectedComponentEnd,
+                        format!(
+                            "Unexpected component end: found {} while {} is still open",
+                            end,
+                            expected.as_str()
+                        ),
+                    );
+                    Err(match find_line(original, &end, false) {
+                        Some(location) => finding.with_location(location),
+                        None => finding,
+                    })
+                }
+                Entry::UnterminatedComponent(name) => {
+                    let finding = Finding::new(
+                        Code::UnterminatedComponent,
+                        format!(
+                            "Unterminated component: {} has no END line before the file ends",
+                            name
+                        ),
+                    );
+                    // The innermost one is the last left open.
+                    Err(
+                        match find_line(original, &format!("BEGIN:{}", name), true) {
+                            Some(location) => finding.with_location(location),
+                            None => finding,
+                        },
+                    )
+                }
+                Entry::TooManyComponents => Err(Finding::new(
+                    Code::TooManyComponents,
+                    "Too many components: the entry has more than the parser accepts",
+                )),
+                Entry::Eof => Err(Finding::new(
+                    Code::UnexpectedEof,
+                    "Unexpected end of file: no complete iCalendar or vCard was found",
+                )),
+            },
+            Format::JSCalendar => {
+                let normalized = normalize_json(source.trim_end())
+                    .map_or(Cow::Borrowed(source), |(_, normalized)| normalized);
+                let source = normalized.as_ref();
+                let jscalendar = JSCalendar::parse(source.trim_end()).map_err(|err| {
+                    json_error_location(
+                        original,
+                        Finding::new(
+                            Code::InvalidJSCalendar,
+                            format!("Failed to parse JSCalendar: {}", err),
+                        ),
+                    )
+                })?;
+                let icalendar = jscalendar.into_icalendar().ok_or_else(roundtrip_failed)?;
+                let mut findings = patches::validate_patches(source);
+                let converted = icalendar.to_string();
+                let converted =
+                    description::apply_html_to_icalendar(source, &converted).unwrap_or(converted);
+                findings.extend(audit_recurrences(&converted, &icalendar));
+                findings.extend(status::audit(&status::entries(&converted)));
+                let roundtrip = icalendar.clone().into_jscalendar().to_string_pretty();
+                let roundtrip = description::apply_html_to_jscalendar(&converted, &roundtrip)
+                    .unwrap_or(roundtrip);
+                findings.extend(colors::check_conversion(source, &converted, &roundtrip));
+                Ok(ConversionResult {
+                    format: Format::JSCalendar,
+                    converted,
+                    fidelity: fidelity::report(source, &roundtrip),
+                    roundtrip,
+                    findings,
+                    icalendar: Some(icalendar),
+                })
+            }
+            Format::JSContact => {
+                let normalized = normalize_json(source.trim_end())
+                    .map_or(Cow::Borrowed(source), |(_, normalized)| normalized);
+                let source = normalized.as_ref();
+                let jscontact = JSContact::parse(source).map_err(|err| {
+                    json_error_location(
+                        original,
+                        Finding::new(
+                            Code::InvalidJSContact,
+                            format!("Failed to parse JSContact: {}", err),
+                        ),
+                    )
+                })?;
+                let vcard = jscontact.into_vcard().ok_or_else(roundtrip_failed)?;
+                let converted = vcard.to_string();
+                let roundtrip = vcard.into_jscontact().to_string_pretty();
+                let findings = check_contact(source, &converted, &roundtrip);
+                Ok(ConversionResult {
+                    format: Format::JSContact,
+                    converted,
+                    fidelity: fidelity::report(source, &roundtrip),
+                    roundtrip,
+                    findings,
+                    icalendar: None,
+                })
+            }
+            // Converted through the text format, so findings and losses are
+            // located in the iCalendar or vCard it maps to.
+            Format::JCal | Format::JCard => {
+                let text = jcal::to_text(source).map_err(|err| {
+                    json_error_location(
+                        original,
+                        Finding::new(
+                            Code::InvalidJCal,
+                            format!("Failed to parse {}: {}", format.as_str(), err),
+                        ),
+                    )
+                })?;
+                let mut result = self.convert(&text)?;
+                result.format = format;
+                result.roundtrip = jcal::from_text(&result.roundtrip);
+                Ok(result)
+            }
+        }
+    }
+
+    // Converts every entry of a file holding several vCards or calendars, or
+    // a JSON array of them. Findings are located by entry number when there
+    // is more than one.
+    pub fn convert_all(&self, source: &str) -> Vec<EntryResult> {
+        let entries = split_entries(source);
+        let count = entries.len();
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, source)| self.convert_entry(index, count, source))
+            .collect()
+    }
+
+    // Converts entry `index` of the `count` that `split_entries` returned, so
+    // callers can convert a large file one entry at a time.
+    pub fn convert_entry(&self, index: usize, count: usize, source: String) -> EntryResult {
+        let result = self.convert(&source);
+        entry_result(index, count, source, result)
+    }
+
+    // Converts one of the `calendar_batches` of a calendar, with findings
+    // located in the whole calendar.
+    pub fn convert_batch(&self, batch: &CalendarBatch) -> Result<ConversionResult, Finding> {
+        let mut result = self.convert(&batch.source);
+        match &mut result {
+            Ok(result) => {
+                for finding in &mut result.findings {
+                    batch.locate(&mut finding.location);
+                }
+                for loss in &mut result.fidelity {
+                    batch.locate(&mut loss.location);
+                }
+            }
+            Err(finding) => batch.locate(&mut finding.location),
+        }
+        result
+    }
+
+    // Joins the `convert_batch` results of entry `index` into the result
+    // `convert_entry` gives for the whole calendar.
+    pub fn join_batches(
+        &self,
+        index: usize,
+        count: usize,
+        source: String,
+        results: Vec<Result<ConversionResult, Finding>>,
+    ) -> EntryResult {
+        let result = join_batches(&source, results);
+        entry_result(index, count, source, result)
+    }
+
+    // Source taken to its counterpart format and back to its own.
+    pub fn roundtrip(&self, source: &str) -> Result<String, Finding> {
+        self.convert(source).map(|result| result.roundtrip)
+    }
+}
+
+// Findings of an entry located by its number when there is more than one.
+fn entry_result(
+    index: usize,
+    count: usize,
+    source: String,
+    mut result: Result<ConversionResult, Finding>,
+) -> EntryResult {
+    if count > 1 {
+        let locate = |location: &mut Option<String>| {
+            *location = Some(match location.take() {
+                Some(location) => format!("entry {}, {}", index + 1, location),
+                None => format!("entry {}", index + 1),
+            });
+        };
+        match &mut result {
+            Ok(result) => {
+                for finding in &mut result.findings {
+                    locate(&mut finding.location);
+                }
+                for loss in &mut result.fidelity {
+                    locate(&mut loss.location);
+                }
+            }
+            Err(finding) => locate(&mut finding.location),
+        }
+    }
+    EntryResult { source, result }
+}
+
+// Splits concatenated top-level components, or the elements of a JSON array,
+// into separate documents. Anything else is returned as a single document.
+pub fn split_entries(source: &str) -> Vec<String> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with('[') {
+        return match serde_json::from_str::<Value>(trimmed) {
+            // A single jCal or jCard is an array too.
+            Ok(Value::Array(values)) if values.first().is_some_and(Value::is_string) => {
+                vec![source.to_string()]
+            }
+            Ok(Value::Array(values)) if !values.is_empty() => values
+                .iter()
+                .map(|value| serde_json::to_string_pretty(value).unwrap_or_default())
+                .collect(),
+            _ => vec![source.to_string()],
+        };
+    }
+    if !trimmed.starts_with("BEGIN:") {
+        return vec![source.to_string()];
+    }
+
+    let mut splitter = EntrySplitter::default();
+    let mut entries = splitter.push(trimmed);
+    entries.extend(splitter.finish());
+    if entries.is_empty() {
+        entries.push(source.to_string());
+    }
+    entries
+}
+
+// Splits concatenated top-level components given a piece at a time, so the
+// entries of a large file can be converted while the rest is still read.
+#[derive(Clone, Debug, Default)]
+pub struct EntrySplitter {
+    // The last line, until its line break arrives.
+    partial: String,
+    entry: String,
+    depth: usize,
+}
+
+// Part of a calendar too large to convert at once, written as a calendar of
+// its own.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CalendarBatch {
+    pub source: String,
+    // Line of the whole calendar each line of `source` was taken from.
+    lines: Vec<usize>,
+}
+
+impl EntrySplitter {
+    // Adds the next piece of the text, returning the entries it completed.
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        let mut entries = Vec::new();
+        for line in text.split_inclusive('\n') {
+            if !line.ends_with('\n') {
+                self.partial.push_str(line);
+                break;
+            }
+            if self.partial.is_empty() {
+                self.line(line, &mut entries);
+            } else {
+                let mut joined = std::mem::take(&mut self.partial);
+                joined.push_str(line);
+                self.line(&joined, &mut entries);
+            }
+        }
+        entries
+    }
+
+    // Entries left at the end of the text. An unterminated entry is kept so
+    // its conversion reports the error.
+    pub fn finish(mut self) -> Vec<String> {
+        let mut entries = Vec::new();
+        let partial = std::mem::take(&mut self.partial);
+        if !partial.is_empty() {
+            self.line(&partial, &mut entries);
+        }
+        if !self.entry.trim().is_empty() {
+            entries.push(self.entry);
+        }
+        entries
+    }
+
+    fn line(&mut self, line: &str, entries: &mut Vec<String>) {
+        let name = line.trim_end().to_ascii_uppercase();
+        if self.depth == 0 && name.is_empty() {
+            return;
+        }
+        self.entry.push_str(line);
+        if name.starts_with("BEGIN:") {
+            self.depth += 1;
+        } else if name.starts_with("END:") {
+            self.depth = self.depth.saturating_sub(1);
+            if self.depth == 0 {
+                entries.push(std::mem::take(&mut self.entry));
+            }
+        }
+    }
+}
+
+// Splits a calendar between its components into calendars of about
+// `batch_size` bytes of components, so a large one converts a batch at a
+// time. The calendar properties and time zones are collected from the whole
+// calendar first and repeated in every batch, wherever they were written.
+// Components sharing a UID stay together when they follow each other, as
+// overrides usually follow their event. Anything else, or a calendar that
+// fits in one batch, is returned as a single batch.
+pub fn calendar_batches(source: &str, batch_size: usize) -> Vec<CalendarBatch> {
+    let whole = || {
+        vec![CalendarBatch {
+            source: source.to_string(),
+            lines: (1..=source.lines().count()).collect(),
+        }]
+    };
+    let mut header = CalendarBatch::default();
+    let mut timezones = CalendarBatch::default();
+    // Components other than time zones, with their UID.
+    let mut components = Vec::new();
+    let mut component = CalendarBatch::default();
+    let mut uid = None;
+    let mut end = None;
+    let mut depth = 0;
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let number = index + 1;
+        let name = line.trim_end().to_ascii_uppercase();
+        match depth {
+            0 if name.is_empty() => {}
+            0 if header.source.is_empty() && name == "BEGIN:VCALENDAR" => {
+                depth = 1;
+                header.push(line, number);
+            }
+            0 => return whole(),
+            1 if name.starts_with("BEGIN:") => {
+                depth = 2;
+                component.push(line, number);
+            }
+            1 if name.starts_with("END:") => {
+                depth = 0;
+                end = Some((line, number));
+            }
+            1 => header.push(line, number),
+            _ => {
+                component.push(line, number);
+                if name.starts_with("BEGIN:") {
+                    depth += 1;
+                } else if name.starts_with("END:") {
+                    depth -= 1;
+                    if depth == 1 {
+                        let component = std::mem::take(&mut component);
+                        if name == "END:VTIMEZONE" {
+                            timezones.extend(&component);
+                        } else {
+                            components.push((component, uid.take()));
+                        }
+                    }
+                } else if depth == 2 && (name.starts_with("UID:") || name.starts_with("UID;")) {
+                    uid = line
+                        .split_once(':')
+                        .map(|(_, uid)| uid.trim_end().to_string());
+                }
+            }
+        }
+    }
+    // An unterminated calendar converts whole, so the error is reported.
+    let Some((end, end_line)) = end else {
+        return whole();
+    };
+
+    let mut batches = vec![CalendarBatch::default()];
+    let mut last_uid = None;
+    for (component, uid) in components {
+        let batch = batches
+            .last_mut()
+            .filter(|batch| batch.source.len() < batch_size || uid.is_some() && uid == last_uid);
+        match batch {
+            Some(batch) => batch.extend(&component),
+            None => batches.push(component),
+        }
+        last_uid = uid;
+    }
+    if batches.len() < 2 {
+        return whole();
+    }
+    batches
+        .into_iter()
+        .map(|components| {
+            let mut batch = header.clone();
+            batch.extend(&timezones);
+            batch.extend(&components);
+            batch.push(end, end_line);
+            batch
+        })
+        .collect()
+}
+
+impl CalendarBatch {
+    fn push(&mut self, line: &str, number: usize) {
+        self.source.push_str(line);
+        self.lines.push(number);
+    }
+
+    fn extend(&mut self, other: &CalendarBatch) {
+        self.source.push_str(&other.source);
+        self.lines.extend(&other.lines);
+    }
+
+    // "line N" of the batch as the line of the whole calendar.
+    fn locate(&self, location: &mut Option<String>) {
+        let Some(rest) = location
+            .as_deref()
+            .and_then(|location| location.strip_prefix("line "))
+        else {
+            return;
+        };
+        let digits = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let line = rest[..digits]
+            .parse::<usize>()
+            .ok()
+            .and_then(|line| self.lines.get(line.checked_sub(1)?));
+        if let Some(line) = line {
+            *location = Some(format!("line {}{}", line, &rest[digits..]));
+        }
+    }
+}
+
+// The conversions of the batches of a calendar joined into the conversion of
+// the whole calendar. The first error is the whole calendar's.
+fn join_batches(
+    source: &str,
+    results: Vec<Result<ConversionResult, Finding>>,
+) -> Result<ConversionResult, Finding> {
+    let mut results = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+    if results.len() < 2 {
+        return results.pop().map_or_else(|| Converter.convert(source), Ok);
+    }
+    let source = source.trim_start();
+    let converted = join_jscalendar(results.iter().map(|result| result.converted.as_str()))
+        .ok_or_else(roundtrip_failed)?;
+    let roundtrip = join_icalendar(results.iter().map(|result| result.roundtrip.as_str()));
+    // Findings of the calendar properties and time zones repeat in every
+    // batch.
+    let mut seen = HashSet::new();
+    let findings = results
+        .iter_mut()
+        .flat_map(|result| std::mem::take(&mut result.findings))
+        .filter(|finding| seen.insert(finding.clone()))
+        .collect();
+    Ok(ConversionResult {
+        format: results[0].format,
+        fidelity: fidelity::report(source, &roundtrip),
+        icalendar: match Parser::new(source).entry() {
+            Entry::ICalendar(icalendar) => Some(icalendar),
+            _ => None,
+        },
+        converted,
+        roundtrip,
+        findings,
+    })
+}
+
+// JSCalendar Groups as one, with the entries of all of them in order and the
+// time zones of each. A batch of a single entry may not be a Group.
+fn join_jscalendar<'x>(documents: impl IntoIterator<Item = &'x str>) -> Option<String> {
+    let mut group: Option<Map<String, Value>> = None;
+    let mut entries = Vec::new();
+    for document in documents {
+        match serde_json::from_str::<Value>(document).ok()? {
+            Value::Object(mut object)
+                if object.get("@type").and_then(Value::as_str) == Some("Group") =>
+            {
+                if let Some(Value::Array(found)) = object.get_mut("entries").map(Value::take) {
+                    entries.extend(found);
+                }
+                let Some(group) = &mut group else {
+                    group = Some(object);
+                    continue;
+                };
+                for (key, value) in object {
+                    match (group.get_mut(&key), value) {
+                        (Some(Value::Object(ours)), Value::Object(theirs)) => {
+                            for (key, value) in theirs {
+                                ours.entry(key).or_insert(value);
+                            }
+                        }
+                        (None, value) => {
+                            group.insert(key, value);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            entry => entries.push(entry),
+        }
+    }
+    let mut group = group.unwrap_or_else(|| Map::from_iter([("@type".into(), "Group".into())]));
+    group.insert("entries".into(), Value::Array(entries));
+    serde_json::to_string_pretty(&group).ok()
+}
+
+// iCalendar calendars as one: the first whole, and the components of the
+// others but their time zones, which every batch repeats.
+fn join_icalendar<'x>(documents: impl IntoIterator<Item = &'x str>) -> String {
+    let mut documents = documents.into_iter();
+    let first = documents.next().unwrap_or_default();
+    // The END:VCALENDAR line of the first.
+    let end = first.trim_end().rfind('\n').map_or(0, |index| index + 1);
+    let mut text = first[..end].to_string();
+    for document in documents {
+        let mut depth = 0usize;
+        let mut keep = false;
+        for line in document.split_inclusive('\n') {
+            let name = line.trim_end().to_ascii_uppercase();
+            if name.starts_with("BEGIN:") {
+                depth += 1;
+                if depth == 2 {
+                    keep = name != "BEGIN:VTIMEZONE";
+                }
+            }
+            if depth >= 2 && keep {
+                text.push_str(line);
+            }
+            if name.starts_with("END:") {
+                depth = depth.saturating_sub(1);
+            }
+        }
+    }
+    text.push_str(&first[end..]);
+    text
+}
+
+// Summary of an event or name of a contact, to tell entries apart.
+pub fn entry_title(source: &str) -> Option<String> {
+    let source = source.trim_start();
+    if source.starts_with('{') {
+        let value = serde_json::from_str::<Value>(source).ok()?;
+        let value = value
+            .get("entries")
+            .and_then(|entries| entries.get(0))
+            .unwrap_or(&value);
+        value
+            .get("title")
+            .or_else(|| value.get("name").and_then(|name| name.get("full")))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    } else {
+        contentline::parse(source)
+            .into_iter()
+            .find(|line| matches!(line.name.as_str(), "SUMMARY" | "FN"))
+            .map(|line| line.text())
+    }
+    .filter(|title| !title.trim().is_empty())
+}
+
+// Byte range of the parse error a "line N, column M" location points at in
+// `source`, from the column to the end of the line. Locations of entries in a
+// multi-entry file count from the start of the entry.
+pub fn error_span(source: &str, location: &str) -> Option<Range<usize>> {
+    let location = &location[location.find("line ")? + 5..];
+    let (line, column) = match location.split_once(", column ") {
+        Some((line, column)) => (line.parse::<usize>().ok()?, column.parse::<usize>().ok()?),
+        None => (location.parse::<usize>().ok()?, 1),
+    };
+    let start = source
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum::<usize>();
+    let text = source.get(start..)?;
+    let end = start + text.find(['\r', '\n']).unwrap_or(text.len());
+    let start = start
+        + text[..end - start]
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(end - start, |(offset, _)| offset);
+    Some(start..end)
+}
+
+// "line N, column M" of a byte offset, both counted from 1, columns in characters.
+fn position(source: &str, offset: usize) -> String {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &source[..offset];
+    format!(
+        "line {}, column {}",
+        before.matches('\n').count() + 1,
+        before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1
+    )
+}
+
+// Location of the first or last line that reads `text`, ignoring case.
+fn find_line(source: &str, text: &str, last: bool) -> Option<String> {
+    let mut offset = 0;
+    let mut found = None;
+    for line in source.split_inclusive('\n') {
+        if line.trim().eq_ignore_ascii_case(text) {
+            found = Some(offset);
+            if !last {
+                break;
+            }
+        }
+        offset += line.len();
+    }
+    found.map(|offset| position(source, offset))
+}
+
+// Locates a JSON parse error when the source is not even valid JSON.
+fn json_error_location(source: &str, finding: Finding) -> Finding {
+    match serde_json::from_str::<Value>(source) {
+        Err(err) if err.line() > 0 => {
+            let start = source
+                .split_inclusive('\n')
+                .take(err.line() - 1)
+                .map(str::len)
+                .sum::<usize>();
+            finding.with_location(position(source, start + err.column().saturating_sub(1)))
+        }
+        _ => finding,
+    }
+}
+
+// Tells JSCalendar from JSContact by the @type of the top-level object and
+// rewrites the types the parser does not take on their own: an Event or Task
+// is wrapped in a Group, and a CardGroup becomes a Card of kind group. JSON
+// that does not parse is told apart by the types it mentions, so that the
+// parser gets to report what is wrong with it.
+pub fn normalize_json(source: &str) -> Option<(Format, Cow<'_, str>)> {
+    let Ok(Value::Object(mut object)) = serde_json::from_str::<Value>(source) else {
+        return if ["\"Group\"", "\"Event\"", "\"Task\""]
+            .iter()
+            .any(|kind| source.contains(kind))
+        {
+            Some((Format::JSCalendar, Cow::Borrowed(source)))
+        } else if ["\"Card\"", "\"CardGroup\""]
+            .iter()
+            .any(|kind| source.contains(kind))
+        {
+            Some((Format::JSContact, Cow::Borrowed(source)))
+        } else {
+            None
+        };
+    };
+    match object.get("@type").and_then(Value::as_str)? {
+        "Group" => Some((Format::JSCalendar, Cow::Borrowed(source))),
+        // Written around the source so that parse errors keep their line.
+        "Event" | "Task" => Some((
+            Format::JSCalendar,
+            Cow::Owned(format!(
+                "{{\"@type\":\"Group\",\"entries\":[{}]}}",
+                source.trim_end()
+            )),
+        )),
+        "Card" => Some((Format::JSContact, Cow::Borrowed(source))),
+        // CardGroup is from the JSContact drafts; RFC 9553 section 2.1.4
+        // replaced it with the kind and members of a Card.
+        "CardGroup" => {
+            let mut card = match object.remove("card") {
+                Some(Value::Object(card)) => card,
+                _ => Map::new(),
+            };
+            card.insert("@type".into(), "Card".into());
+            card.insert("kind".into(), "group".into());
+            card.entry("version").or_insert_with(|| "1.0".into());
+            for key in ["uid", "members"] {
+                if let Some(value) = object.remove(key) {
+                    card.entry(key).or_insert(value);
+                }
+            }
+            if let Some(Value::String(name)) = object.remove("name") {
+                card.entry("name")
+                    .or_insert_with(|| json!({ "@type": "Name", "full": name }));
+            }
+            Some((
+                Format::JSContact,
+                Cow::Owned(serde_json::to_string_pretty(&card).unwrap_or_default()),
+            ))
+        }
+        _ => None,
+    }
+}
+
+// Contact data that conversions have been known to drop silently.
+fn check_contact(source: &str, converted: &str, roundtrip: &str) -> Vec<Finding> {
+    let mut findings = phonetic::check_conversion(source, converted, roundtrip);
+    findings.extend(identity::check_conversion(source, converted, roundtrip));
+    findings
+}
+
+// The month-end audit expands the calendar, which pathological rules would stall.
+fn audit_recurrences(source: &str, icalendar: &ICalendar) -> Vec<Finding> {
+    let findings = pathological::audit(source);
+    if findings.is_empty() {
+        month_end::audit(source, icalendar)
+    } else {
+        findings
+    }
+}
+
+fn roundtrip_failed() -> Finding {
+    Finding::new(Code::RoundtripFailed, ROUNDTRIP_FAILED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Events with an override, a time zone between them and a calendar
+    // property after them, as large calendars sometimes are written.
+    fn calendar(events: usize) -> String {
+        let mut text = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example//EN\r\n".to_string();
+        for index in 0..events {
+            if index == events / 2 {
+                text.push_str("BEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\nBEGIN:STANDARD\r\nDTSTART:19701025T030000\r\nTZOFFSETFROM:+0200\r\nTZOFFSETTO:+0100\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\n");
+            }
+            text.push_str(&format!(
+                "BEGIN:VEVENT\r\nUID:event-{index}\r\nDTSTART;TZID=Europe/Berlin:20240101T090000\r\nRRULE:FREQ=DAILY;COUNT=3\r\nSUMMARY:Event {index}\r\nEND:VEVENT\r\n"
+            ));
+            text.push_str(&format!(
+                "BEGIN:VEVENT\r\nUID:event-{index}\r\nRECURRENCE-ID;TZID=Europe/Berlin:20240102T090000\r\nDTSTART;TZID=Europe/Berlin:20240102T100000\r\nSUMMARY:Moved {index}\r\nEND:VEVENT\r\n"
+            ));
+        }
+        text.push_str("X-WR-CALNAME:Team\r\nEND:VCALENDAR\r\n");
+        text
+    }
+
+    #[test]
+    fn batches_repeat_calendar_properties_and_timezones() {
+        let source = calendar(20);
+        let batches = calendar_batches(&source, 1024);
+        assert!(batches.len() > 2);
+        let lines = source.lines().collect::<Vec<_>>();
+        let mut events = 0;
+        for batch in &batches {
+            assert!(batch
+                .source
+                .starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+            assert!(batch.source.contains("X-WR-CALNAME:Team\r\n"));
+            assert!(batch.source.contains("TZID:Europe/Berlin\r\n"));
+            assert!(batch.source.ends_with("END:VCALENDAR\r\n"));
+            // Every line of a batch is the line of the calendar it maps to.
+            for (line, number) in batch.source.lines().zip(&batch.lines) {
+                assert_eq!(line, lines[number - 1]);
+            }
+            // Overrides stay with their event.
+            let uids = batch.source.matches("UID:").count();
+            assert_eq!(uids % 2, 0);
+            events += uids;
+        }
+        assert_eq!(events, 40);
+    }
+
+    #[test]
+    fn keeps_small_calendars_whole() {
+        let source = calendar(2);
+        assert_eq!(
+            calendar_batches(&source, 64 * 1024),
+            vec![CalendarBatch {
+                source: source.clone(),
+                lines: (1..=source.lines().count()).collect(),
+            }]
+        );
+        let unterminated = source.trim_end().trim_end_matches("END:VCALENDAR");
+        assert_eq!(calendar_batches(unterminated, 16).len(), 1);
+        let card = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane\r\nEND:VCARD\r\n";
+        assert_eq!(calendar_batches(card, 16)[0].source, card);
+    }
+
+    #[test]
+    fn locates_batch_lines_in_the_calendar() {
+        let source = calendar(20);
+        let batch = calendar_batches(&source, 1024).pop().unwrap();
+        let component = batch.source.find("BEGIN:VEVENT").unwrap();
+        let line = batch.source[..component].lines().count() + 1;
+        let mut location = Some(format!("line {}, column 3", line));
+        batch.locate(&mut location);
+        let expected = source
+            .lines()
+            .position(|text| text == batch.source[component..].lines().nth(1).unwrap_or_default())
+            .unwrap();
+        assert_eq!(location, Some(format!("line {}, column 3", expected)));
+        let mut location = Some("/entries/0".to_string());
+        batch.locate(&mut location);
+        assert_eq!(location.as_deref(), Some("/entries/0"));
+    }
+
+    #[test]
+    fn joins_batch_outputs() {
+        let icalendar = join_icalendar([
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTIMEZONE\r\nTZID:X\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT\r\nUID:a\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTIMEZONE\r\nTZID:X\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT\r\nUID:b\r\nSUMMARY:Long\r\n  line\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        ]);
+        assert_eq!(
+            icalendar,
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTIMEZONE\r\nTZID:X\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT\r\nUID:a\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nUID:b\r\nSUMMARY:Long\r\n  line\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+
+        let jscalendar = join_jscalendar([
+            r#"{"@type":"Group","entries":[{"@type":"Event","uid":"a"}],"timeZones":{"/X":{"@type":"TimeZone"}}}"#,
+            r#"{"@type":"Group","entries":[{"@type":"Event","uid":"b"}],"timeZones":{"/Y":{"@type":"TimeZone"}}}"#,
+            r#"{"@type":"Event","uid":"c"}"#,
+        ])
+        .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&jscalendar).unwrap(),
+            json!({
+                "@type": "Group",
+                "entries": [
+                    {"@type": "Event", "uid": "a"},
+                    {"@type": "Event", "uid": "b"},
+                    {"@type": "Event", "uid": "c"}
+                ],
+                "timeZones": {"/X": {"@type": "TimeZone"}, "/Y": {"@type": "TimeZone"}}
+            })
+        );
+    }
+
+    // The same calendar converted whole, as below the background conversion
+    // threshold, and a batch at a time, as above it.
+    #[test]
+    fn batched_conversion_matches_whole_conversion() {
+        let source = calendar(400);
+        assert!(source.len() > 64 * 1024);
+        let output = |entry: EntryResult| {
+            entry.result.map(|result| {
+                (
+                    result.format,
+                    result.converted,
+                    result.roundtrip,
+                    result.findings,
+                    result.fidelity,
+                    result.icalendar,
+                )
+            })
+        };
+        let whole = Converter.convert_entry(0, 1, source.clone());
+        let batches = calendar_batches(&source, 16 * 1024);
+        assert!(batches.len() > 1);
+        let results = batches
+            .iter()
+            .map(|batch| Converter.convert_batch(batch))
+            .collect();
+        let batched = Converter.join_batches(0, 1, source, results);
+        assert_eq!(output(batched), output(whole));
+    }
+}
@@ -0,0 +1,604 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Contacts exported as CSV by Google Contacts, Outlook and others, turned into
+// one vCard 4.0 per row through a mapping of columns to vCard fields.
+
+use chrono::NaiveDate;
+
+use crate::{contentline, email};
+
+// Google Contacts puts several values in one cell with this separator.
+const MULTI_VALUE_SEPARATOR: &str = " ::: ";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    Google,
+    Outlook,
+    Generic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Home,
+    Work,
+    Mobile,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressPart {
+    PoBox,
+    Street,
+    City,
+    Region,
+    PostalCode,
+    Country,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Ignore,
+    FullName,
+    GivenName,
+    FamilyName,
+    AdditionalName,
+    Prefix,
+    Suffix,
+    Nickname,
+    Organization,
+    Department,
+    Title,
+    Birthday,
+    Url,
+    Note,
+    Categories,
+    Email(Kind),
+    Phone(Kind),
+    Address(Kind, AddressPart),
+    // The type of the email, phone, address or website in the columns after
+    // it, as Google Contacts writes it.
+    Type,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvTable {
+    pub layout: Layout,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Layout {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Layout::Google => "csv-layout-google",
+            Layout::Outlook => "csv-layout-outlook",
+            Layout::Generic => "csv-layout-generic",
+        }
+    }
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Home => "home",
+            Kind::Work => "work",
+            Kind::Mobile => "mobile",
+            Kind::Other => "other",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Kind::Home => "csv-kind-home",
+            Kind::Work => "csv-kind-work",
+            Kind::Mobile => "csv-kind-mobile",
+            Kind::Other => "csv-kind-other",
+        }
+    }
+
+    // The vCard 4.0 TYPE parameter value.
+    fn vcard_type(&self, phone: bool) -> Option<&'static str> {
+        match self {
+            Kind::Home => Some("home"),
+            Kind::Work => Some("work"),
+            Kind::Mobile if phone => Some("cell"),
+            Kind::Mobile | Kind::Other => None,
+        }
+    }
+
+    fn from_label(label: &str) -> Self {
+        let label = label.to_ascii_lowercase();
+        if label.contains("mobile") || label.contains("cell") {
+            Kind::Mobile
+        } else if label.contains("home") {
+            Kind::Home
+        } else if label.contains("work") || label.contains("business") {
+            Kind::Work
+        } else {
+            Kind::Other
+        }
+    }
+}
+
+impl AddressPart {
+    const ALL: [AddressPart; 6] = [
+        AddressPart::PoBox,
+        AddressPart::Street,
+        AddressPart::City,
+        AddressPart::Region,
+        AddressPart::PostalCode,
+        AddressPart::Country,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AddressPart::PoBox => "pobox",
+            AddressPart::Street => "street",
+            AddressPart::City => "city",
+            AddressPart::Region => "region",
+            AddressPart::PostalCode => "code",
+            AddressPart::Country => "country",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            AddressPart::PoBox => "csv-address-pobox",
+            AddressPart::Street => "csv-address-street",
+            AddressPart::City => "csv-address-city",
+            AddressPart::Region => "csv-address-region",
+            AddressPart::PostalCode => "csv-address-code",
+            AddressPart::Country => "csv-address-country",
+        }
+    }
+
+    // Position in the ADR value, which starts with the PO box and the
+    // extended address.
+    fn position(&self) -> usize {
+        match self {
+            AddressPart::PoBox => 0,
+            AddressPart::Street => 2,
+            AddressPart::City => 3,
+            AddressPart::Region => 4,
+            AddressPart::PostalCode => 5,
+            AddressPart::Country => 6,
+        }
+    }
+}
+
+impl Field {
+    // Every field a column can be mapped to, in the order they are offered.
+    pub fn all() -> Vec<Field> {
+        let mut fields = vec![
+            Field::Ignore,
+            Field::FullName,
+            Field::GivenName,
+            Field::FamilyName,
+            Field::AdditionalName,
+            Field::Prefix,
+            Field::Suffix,
+            Field::Nickname,
+            Field::Organization,
+            Field::Department,
+            Field::Title,
+            Field::Birthday,
+            Field::Url,
+            Field::Note,
+            Field::Categories,
+            Field::Type,
+        ];
+        for kind in [Kind::Home, Kind::Work, Kind::Other] {
+            fields.push(Field::Email(kind));
+        }
+        for kind in [Kind::Mobile, Kind::Home, Kind::Work, Kind::Other] {
+            fields.push(Field::Phone(kind));
+        }
+        for kind in [Kind::Home, Kind::Work, Kind::Other] {
+            for part in AddressPart::ALL {
+                fields.push(Field::Address(kind, part));
+            }
+        }
+        fields
+    }
+
+    pub fn as_str(&self) -> String {
+        match self {
+            Field::Ignore => "ignore".to_string(),
+            Field::FullName => "full-name".to_string(),
+            Field::GivenName => "given-name".to_string(),
+            Field::FamilyName => "family-name".to_string(),
+            Field::AdditionalName => "additional-name".to_string(),
+            Field::Prefix => "prefix".to_string(),
+            Field::Suffix => "suffix".to_string(),
+            Field::Nickname => "nickname".to_string(),
+            Field::Organization => "organization".to_string(),
+            Field::Department => "department".to_string(),
+            Field::Title => "title".to_string(),
+            Field::Birthday => "birthday".to_string(),
+            Field::Url => "url".to_string(),
+            Field::Note => "note".to_string(),
+            Field::Categories => "categories".to_string(),
+            Field::Type => "type".to_string(),
+            Field::Email(kind) => format!("email-{}", kind.as_str()),
+            Field::Phone(kind) => format!("phone-{}", kind.as_str()),
+            Field::Address(kind, part) => format!("address-{}-{}", kind.as_str(), part.as_str()),
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Field::all()
+            .into_iter()
+            .find(|field| field.as_str() == value)
+    }
+
+    // Email, phone and address fields take the kind, and the address part,
+    // as the kind and part arguments.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Field::Ignore => "csv-field-ignore",
+            Field::FullName => "csv-field-full-name",
+            Field::GivenName => "csv-field-given-name",
+            Field::FamilyName => "csv-field-family-name",
+            Field::AdditionalName => "csv-field-additional-name",
+            Field::Prefix => "csv-field-prefix",
+            Field::Suffix => "csv-field-suffix",
+            Field::Nickname => "csv-field-nickname",
+            Field::Organization => "csv-field-organization",
+            Field::Department => "csv-field-department",
+            Field::Title => "csv-field-title",
+            Field::Birthday => "csv-field-birthday",
+            Field::Url => "csv-field-url",
+            Field::Note => "csv-field-note",
+            Field::Categories => "csv-field-categories",
+            Field::Type => "csv-field-type",
+            Field::Email(_) => "csv-field-email",
+            Field::Phone(_) => "csv-field-phone",
+            Field::Address(..) => "csv-field-address",
+        }
+    }
+
+    // The field a column header of the given layout stands for.
+    pub fn for_header(header: &str, layout: Layout) -> Self {
+        let header = header.trim().to_ascii_lowercase();
+        // Google Contacts numbers repeated values: "Phone 1 - Value".
+        if let Some((group, part)) = header.split_once(" - ") {
+            let group = group.trim_end_matches(|ch: char| ch.is_ascii_digit() || ch == ' ');
+            return match (group, part) {
+                (_, "type" | "label") => Field::Type,
+                ("e-mail" | "email", "value") => Field::Email(Kind::Other),
+                ("phone", "value") => Field::Phone(Kind::Other),
+                ("website", "value") => Field::Url,
+                ("organization", "name") => Field::Organization,
+                ("organization", "title") => Field::Title,
+                ("organization", "department") => Field::Department,
+                ("address", part) => address_part(part)
+                    .map_or(Field::Ignore, |part| Field::Address(Kind::Other, part)),
+                _ => Field::Ignore,
+            };
+        }
+        let (kind, rest) = match header.split_once(' ') {
+            Some(("home", rest)) => (Some(Kind::Home), rest),
+            Some(("business" | "work", rest)) => (Some(Kind::Work), rest),
+            Some(("other", rest)) => (Some(Kind::Other), rest),
+            _ => (None, header.as_str()),
+        };
+        if let Some(part) = address_part(rest) {
+            return Field::Address(kind.unwrap_or(Kind::Other), part);
+        }
+        match rest {
+            "phone" | "phone 2" | "telephone" => {
+                return Field::Phone(kind.unwrap_or(Kind::Other));
+            }
+            "email" | "e-mail" | "email address" | "e-mail address" => {
+                return Field::Email(kind.unwrap_or(Kind::Other));
+            }
+            _ => {}
+        }
+        match header.as_str() {
+            "name" | "full name" | "display name" | "formatted name" | "fn" => Field::FullName,
+            "first name" | "given name" | "firstname" => Field::GivenName,
+            "last name" | "family name" | "surname" | "lastname" => Field::FamilyName,
+            "middle name" | "additional name" => Field::AdditionalName,
+            "name prefix" | "prefix" => Field::Prefix,
+            // Outlook calls the name prefix a title.
+            "title" if layout == Layout::Outlook => Field::Prefix,
+            "name suffix" | "suffix" => Field::Suffix,
+            "nickname" => Field::Nickname,
+            "company" | "organization" | "organization name" => Field::Organization,
+            "department" | "organization department" => Field::Department,
+            "job title" | "title" | "organization title" => Field::Title,
+            "birthday" | "bday" | "date of birth" => Field::Birthday,
+            "web page" | "website" | "url" | "homepage" => Field::Url,
+            "notes" | "note" => Field::Note,
+            "categories" | "labels" | "group membership" => Field::Categories,
+            "mobile phone" | "mobile" | "cell phone" | "cell" => Field::Phone(Kind::Mobile),
+            "primary phone" | "company main phone" => Field::Phone(Kind::Work),
+            header if header.starts_with("e-mail") && header.ends_with("address") => {
+                Field::Email(Kind::Other)
+            }
+            _ => Field::Ignore,
+        }
+    }
+}
+
+fn address_part(name: &str) -> Option<AddressPart> {
+    match name {
+        "po box" | "pobox" => Some(AddressPart::PoBox),
+        "street" | "address" => Some(AddressPart::Street),
+        "city" | "locality" => Some(AddressPart::City),
+        "state" | "region" | "province" => Some(AddressPart::Region),
+        "postal code" | "zip" | "zip code" | "postcode" => Some(AddressPart::PostalCode),
+        "country" | "country/region" => Some(AddressPart::Country),
+        _ => None,
+    }
+}
+
+impl CsvTable {
+    // Reads a CSV export with a header row. Sources that are another format,
+    // or whose headers name no contact field, are not taken for CSV.
+    pub fn parse(source: &str) -> Option<Self> {
+        let source = source.trim_start_matches('\u{feff}').trim();
+        if source.starts_with(['{', '[', '<'])
+            || source
+                .get(..6)
+                .is_some_and(|start| start.eq_ignore_ascii_case("BEGIN:"))
+            || email::is_message(source)
+        {
+            return None;
+        }
+        let first = source.lines().next()?;
+        let delimiter = [',', ';', '\t']
+            .into_iter()
+            .max_by_key(|delimiter| first.matches(*delimiter).count())?;
+        let mut records = parse_records(source, delimiter).into_iter();
+        let headers = records.next()?;
+        if headers.len() < 2 {
+            return None;
+        }
+        let lowercase = headers
+            .iter()
+            .map(|header| header.trim().to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let has = |name: &str| lowercase.iter().any(|header| header == name);
+        let layout = if has("e-mail address") || has("business phone") || has("mobile phone") {
+            Layout::Outlook
+        } else if lowercase
+            .iter()
+            .any(|header| header.ends_with(" - value") || header == "group membership")
+        {
+            Layout::Google
+        } else {
+            Layout::Generic
+        };
+        if headers.iter().all(|header| {
+            matches!(
+                Field::for_header(header, layout),
+                Field::Ignore | Field::Type
+            )
+        }) {
+            return None;
+        }
+        let rows = records
+            .filter(|row| row.iter().any(|value| !value.trim().is_empty()))
+            .collect::<Vec<_>>();
+        (!rows.is_empty()).then_some(CsvTable {
+            layout,
+            headers,
+            rows,
+        })
+    }
+
+    pub fn default_mapping(&self) -> Vec<Field> {
+        self.headers
+            .iter()
+            .map(|header| Field::for_header(header, self.layout))
+            .collect()
+    }
+
+    // One vCard per row, skipping rows where no mapped column has a value.
+    pub fn to_vcards(&self, mapping: &[Field]) -> String {
+        let mut out = String::new();
+        for row in &self.rows {
+            if let Some(card) = row_to_vcard(row, mapping) {
+                out.push_str(&card);
+            }
+        }
+        out
+    }
+}
+
+fn row_to_vcard(row: &[String], mapping: &[Field]) -> Option<String> {
+    // Family, given and middle names, prefix and suffix, as in N.
+    let mut name: [String; 5] = Default::default();
+    let mut full_name = String::new();
+    let mut organization: [String; 2] = Default::default();
+    let mut addresses: Vec<(Kind, [String; 7])> = Vec::new();
+    let mut lines = Vec::new();
+    let mut categories = Vec::new();
+    // Set by a type column for the values after it.
+    let mut kind_label: Option<Kind> = None;
+
+    for (value, field) in row.iter().zip(mapping) {
+        let value = value.trim();
+        if *field == Field::Type {
+            kind_label = (!value.is_empty()).then(|| Kind::from_label(value));
+            continue;
+        }
+        if value.is_empty() {
+            continue;
+        }
+        let values = value
+            .split(MULTI_VALUE_SEPARATOR)
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        match *field {
+            Field::Ignore | Field::Type => {}
+            Field::FullName => full_name = value.to_string(),
+            Field::FamilyName => name[0] = value.to_string(),
+            Field::GivenName => name[1] = value.to_string(),
+            Field::AdditionalName => name[2] = value.to_string(),
+            Field::Prefix => name[3] = value.to_string(),
+            Field::Suffix => name[4] = value.to_string(),
+            Field::Organization => organization[0] = value.to_string(),
+            Field::Department => organization[1] = value.to_string(),
+            Field::Nickname => lines.push(format!("NICKNAME:{}", contentline::escape(value))),
+            Field::Title => lines.push(format!("TITLE:{}", contentline::escape(value))),
+            Field::Note => lines.push(format!("NOTE:{}", contentline::escape(value))),
+            Field::Url => {
+                for value in values {
+                    lines.push(format!("URL:{}", value));
+                }
+            }
+            Field::Birthday => {
+                if let Some(date) = birthday(value) {
+                    lines.push(format!("BDAY:{}", date));
+                }
+            }
+            Field::Categories => categories.extend(
+                values
+                    .map(|value| value.trim_start_matches("* ").to_string())
+                    .filter(|value| value != "myContacts"),
+            ),
+            Field::Email(kind) => {
+                let kind = kind_label.unwrap_or(kind);
+                for value in values {
+                    lines.push(format!("EMAIL{}:{}", type_param(kind, false), value));
+                }
+            }
+            Field::Phone(kind) => {
+                let kind = kind_label.unwrap_or(kind);
+                for value in values {
+                    lines.push(format!("TEL{}:{}", type_param(kind, true), value));
+                }
+            }
+            Field::Address(kind, part) => {
+                let kind = kind_label.unwrap_or(kind);
+                let index = match addresses.iter().position(|(other, _)| *other == kind) {
+                    Some(index) => index,
+                    None => {
+                        addresses.push((kind, Default::default()));
+                        addresses.len() - 1
+                    }
+                };
+                addresses[index].1[part.position()] = contentline::escape(value);
+            }
+        }
+    }
+
+    for (kind, parts) in addresses {
+        lines.push(format!(
+            "ADR{}:{}",
+            type_param(kind, false),
+            parts.join(";")
+        ));
+    }
+    if !categories.is_empty() {
+        lines.push(format!(
+            "CATEGORIES:{}",
+            categories
+                .iter()
+                .map(|category| contentline::escape(category))
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+    }
+    let has_name = name.iter().any(|part| !part.is_empty());
+    if has_name {
+        lines.insert(
+            0,
+            format!(
+                "N:{}",
+                name.iter()
+                    .map(|part| contentline::escape(part))
+                    .collect::<Vec<_>>()
+                    .join(";")
+            ),
+        );
+    }
+    if !organization[0].is_empty() || !organization[1].is_empty() {
+        lines.push(format!(
+            "ORG:{};{}",
+            contentline::escape(&organization[0]),
+            contentline::escape(&organization[1])
+        ));
+    }
+    if full_name.is_empty() {
+        full_name = [&name[3], &name[1], &name[2], &name[0], &name[4]]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    if full_name.is_empty() {
+        full_name = organization[0].clone();
+    }
+    if full_name.is_empty() && lines.is_empty() {
+        return None;
+    }
+
+    let mut out = String::new();
+    contentline::fold_line(&mut out, "BEGIN:VCARD");
+    contentline::fold_line(&mut out, "VERSION:4.0");
+    contentline::fold_line(&mut out, &format!("FN:{}", contentline::escape(&full_name)));
+    for line in lines {
+        contentline::fold_line(&mut out, &line);
+    }
+    contentline::fold_line(&mut out, "END:VCARD");
+    Some(out)
+}
+
+fn type_param(kind: Kind, phone: bool) -> String {
+    kind.vcard_type(phone)
+        .map(|kind| format!(";TYPE={}", kind))
+        .unwrap_or_default()
+}
+
+// Birthdays as vCard 4.0 dates. Google writes --MM-DD when the year is not
+// known and Outlook writes 0/0/00 when there is no birthday.
+fn birthday(value: &str) -> Option<String> {
+    if let Some(date) = value.strip_prefix("--") {
+        return Some(format!("--{}", date.replace('-', "")));
+    }
+    ["%Y-%m-%d", "%m/%d/%Y", "%d.%m.%Y", "%Y%m%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .map(|date| date.format("%Y%m%d").to_string())
+}
+
+// RFC 4180 records: quoted fields may hold delimiters, doubled quotes and
+// line breaks.
+fn parse_records(source: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = source.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ch if quoted => field.push(ch),
+            ch if ch == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            ch => field.push(ch),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// How the dates of expanded occurrences are shown: in which format, and in
+// the zone of their event or the one they are displayed in.
+
+use std::{fmt, str::FromStr};
+
+use calcard::common::timezone::Tz;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
+
+use crate::i18n::Locale;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DateDisplay {
+    // "2025-03-03T09:00:00 (Europe/Berlin)", the wall-clock time in its zone.
+    Iso8601,
+    // "2025-03-03T09:00:00+01:00".
+    Rfc3339,
+    // Seconds since 1970-01-01T00:00:00Z.
+    UnixEpoch,
+    // As written in the language of the UI.
+    #[default]
+    Locale,
+}
+
+impl DateDisplay {
+    pub const ALL: [DateDisplay; 4] = [
+        DateDisplay::Locale,
+        DateDisplay::Iso8601,
+        DateDisplay::Rfc3339,
+        DateDisplay::UnixEpoch,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateDisplay::Iso8601 => "iso8601",
+            DateDisplay::Rfc3339 => "rfc3339",
+            DateDisplay::UnixEpoch => "epoch",
+            DateDisplay::Locale => "locale",
+        }
+    }
+
+    // The id of its name in the UI translations.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            DateDisplay::Iso8601 => "date-display-iso8601",
+            DateDisplay::Rfc3339 => "date-display-rfc3339",
+            DateDisplay::UnixEpoch => "date-display-epoch",
+            DateDisplay::Locale => "date-display-locale",
+        }
+    }
+}
+
+impl fmt::Display for DateDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DateDisplay {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateDisplay::ALL
+            .into_iter()
+            .find(|display| display.as_str() == s)
+            .ok_or_else(|| format!("Unknown date format: {}", s))
+    }
+}
+
+// A date of an occurrence as found in its event.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OccurrenceTime {
+    // Wall-clock time in `zone`.
+    pub local: NaiveDateTime,
+    // None for floating times.
+    pub zone: Option<String>,
+    pub offset: Option<FixedOffset>,
+}
+
+impl OccurrenceTime {
+    pub fn of(date_time: &DateTime<Tz>) -> Self {
+        let zone = date_time.timezone().name().map(|name| name.into_owned());
+        OccurrenceTime {
+            local: date_time.naive_local(),
+            offset: zone.is_some().then(|| date_time.offset().fix()),
+            zone,
+        }
+    }
+
+    // A time in the zone named by a TZID, floating if it has none. Zones
+    // that cannot be resolved keep their name but have no offset.
+    pub fn in_named_zone(local: NaiveDateTime, zone: Option<&str>) -> Self {
+        let offset = zone
+            .and_then(|zone| zone.parse::<chrono_tz::Tz>().ok())
+            .and_then(|tz| tz.from_local_datetime(&local).earliest())
+            .map(|date_time| date_time.offset().fix());
+        OccurrenceTime {
+            local,
+            zone: zone.map(str::to_string),
+            offset,
+        }
+    }
+
+    pub fn utc(&self) -> Option<DateTime<Utc>> {
+        self.offset.and_then(|offset| {
+            offset
+                .from_local_datetime(&self.local)
+                .single()
+                .map(|date_time| date_time.with_timezone(&Utc))
+        })
+    }
+
+    // The same instant in `tz`. Floating times stay as they are.
+    pub fn in_zone(&self, tz: Tz) -> Self {
+        match self.utc() {
+            Some(utc) if !matches!(tz, Tz::Floating) => OccurrenceTime::of(&utc.with_timezone(&tz)),
+            _ => self.clone(),
+        }
+    }
+
+    // Floating times and those in unknown zones have no offset, so RFC 3339
+    // and the Unix epoch read them as UTC and follow them with their zone, or
+    // `floating`, the word for it in the UI.
+    pub fn format(&self, display: DateDisplay, locale: Locale, floating: &str) -> String {
+        let zone = self.zone.as_deref().unwrap_or(floating);
+        match (display, self.offset) {
+            (DateDisplay::Iso8601, _) => {
+                format!("{} ({})", self.local.format("%Y-%m-%dT%H:%M:%S"), zone)
+            }
+            (DateDisplay::Locale, _) => {
+                format!("{} ({})", locale.format_date_time(self.local), zone)
+            }
+            (DateDisplay::Rfc3339, Some(offset)) => offset
+                .from_local_datetime(&self.local)
+                .single()
+                .map_or_else(String::new, |date_time| date_time.to_rfc3339()),
+            (DateDisplay::Rfc3339, None) => {
+                format!("{}Z ({})", self.local.format("%Y-%m-%dT%H:%M:%S"), zone)
+            }
+            (DateDisplay::UnixEpoch, Some(_)) => self
+                .utc()
+                .map_or_else(String::new, |utc| utc.timestamp().to_string()),
+            (DateDisplay::UnixEpoch, None) => {
+                format!("{} ({})", self.local.and_utc().timestamp(), zone)
+            }
+        }
+    }
+}
+
+impl fmt::Display for OccurrenceTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(DateDisplay::Iso8601, Locale::En, "Floating"))
+    }
+}
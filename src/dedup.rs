@@ -0,0 +1,281 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Finds cards of a contact list that are likely the same person, because they
+// share an email address, a phone number or a full name, and merges each
+// cluster into a single card. Cards are compared as JSContact; vCards are
+// converted on the way in and the merged cards are written back as vCard.
+
+use serde_json::{Map, Value};
+
+use crate::{converter::split_entries, orgchart::card_name, Converter, Format};
+
+// Shorter numbers are extensions or typos rather than something to match on.
+const MIN_PHONE_DIGITS: usize = 7;
+// Trailing digits compared, so a number matches with or without its country
+// code.
+const PHONE_DIGITS: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cluster {
+    // Positions of the cards in the list, first one first.
+    pub cards: Vec<usize>,
+    pub names: Vec<String>,
+    // What the cards have in common, e.g. "email jane@example.com".
+    pub reasons: Vec<String>,
+    // The merged card, in the format of the list.
+    pub merged: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deduplicated {
+    pub format: Format,
+    pub cards: usize,
+    pub clusters: Vec<Cluster>,
+    // The list with each cluster replaced by its merged card.
+    pub text: String,
+}
+
+pub fn deduplicate(source: &str) -> Result<Deduplicated, String> {
+    let documents = split_entries(source);
+    let format = match documents
+        .first()
+        .map(|document| Converter.detect_format(document))
+    {
+        Some(Ok(format @ (Format::VCard | Format::JSContact))) => format,
+        _ => return Err("Only vCard and JSContact lists can be deduplicated.".to_string()),
+    };
+    let cards = documents
+        .iter()
+        .enumerate()
+        .map(|(index, document)| {
+            let json = if format == Format::VCard {
+                Converter
+                    .convert(document)
+                    .map(|result| result.converted)
+                    .map_err(|finding| format!("Card {}: {}", index + 1, finding.message))?
+            } else {
+                document.clone()
+            };
+            serde_json::from_str::<Value>(&json)
+                .map_err(|err| format!("Card {}: {}", index + 1, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut parents = (0..cards.len()).collect::<Vec<_>>();
+    let mut links: Vec<(usize, String)> = Vec::new();
+    let mut seen: Vec<(String, usize)> = Vec::new();
+    for (index, card) in cards.iter().enumerate() {
+        if card.get("@type").and_then(Value::as_str) != Some("Card") {
+            continue;
+        }
+        for key in match_keys(card) {
+            match seen.iter().find(|(seen, _)| *seen == key) {
+                Some((_, first)) => {
+                    let root = find(&mut parents, *first);
+                    let other = find(&mut parents, index);
+                    if root != other {
+                        parents[other.max(root)] = other.min(root);
+                    }
+                    links.push((index, key));
+                }
+                None => seen.push((key, index)),
+            }
+        }
+    }
+
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); cards.len()];
+    for index in 0..cards.len() {
+        let root = find(&mut parents, index);
+        members[root].push(index);
+    }
+    let mut clusters = Vec::new();
+    for group in members.into_iter().filter(|group| group.len() > 1) {
+        let mut reasons = Vec::new();
+        for (_, key) in links.iter().filter(|(index, _)| group.contains(index)) {
+            if !reasons.contains(key) {
+                reasons.push(key.clone());
+            }
+        }
+        let merged = merge_cards(group.iter().map(|index| &cards[*index]));
+        let merged = serde_json::to_string_pretty(&merged).map_err(|err| err.to_string())?;
+        let merged = if format == Format::VCard {
+            Converter
+                .convert(&merged)
+                .map(|result| result.converted)
+                .map_err(|finding| finding.message)?
+        } else {
+            merged
+        };
+        clusters.push(Cluster {
+            names: group
+                .iter()
+                .map(|index| card_name(&cards[*index]).unwrap_or_else(|| "(No name)".to_string()))
+                .collect(),
+            cards: group,
+            reasons,
+            merged,
+        });
+    }
+
+    let text = format.join(
+        documents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, document)| {
+                match clusters
+                    .iter()
+                    .find(|cluster| cluster.cards.contains(&index))
+                {
+                    Some(cluster) if cluster.cards[0] == index => Some(cluster.merged.as_str()),
+                    Some(_) => None,
+                    None => Some(document.as_str()),
+                }
+            }),
+    );
+    Ok(Deduplicated {
+        format,
+        cards: cards.len(),
+        clusters,
+        text,
+    })
+}
+
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+    root
+}
+
+// Email addresses, phone numbers and the full name, each written as the
+// reason two cards matched.
+fn match_keys(card: &Value) -> Vec<String> {
+    let mut keys = Vec::new();
+    for email in map_entries(card, "emails") {
+        if let Some(address) = email.get("address").and_then(Value::as_str) {
+            let address = address.trim().to_lowercase();
+            if address.contains('@') {
+                keys.push(format!("email {}", address));
+            }
+        }
+    }
+    for phone in map_entries(card, "phones") {
+        if let Some(number) = phone
+            .get("number")
+            .and_then(Value::as_str)
+            .and_then(phone_key)
+        {
+            keys.push(format!("phone ending in {}", number));
+        }
+    }
+    if let Some(name) = card_name(card).and_then(|name| name_key(&name)) {
+        keys.push(format!("name {}", name));
+    }
+    keys
+}
+
+fn map_entries<'x>(card: &'x Value, property: &str) -> impl Iterator<Item = &'x Value> {
+    card.get(property)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|map| map.values())
+}
+
+fn phone_key(number: &str) -> Option<String> {
+    let digits = number
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<Vec<_>>();
+    (digits.len() >= MIN_PHONE_DIGITS).then(|| {
+        digits[digits.len().saturating_sub(PHONE_DIGITS)..]
+            .iter()
+            .collect()
+    })
+}
+
+// Lowercased words in alphabetical order, so "Doe, Jane" matches "Jane Doe".
+// A single word is too common to match on.
+fn name_key(name: &str) -> Option<String> {
+    let mut words = name
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    words.sort();
+    words.dedup();
+    (words.len() > 1).then(|| words.join(" "))
+}
+
+// The first card wins on single values. Maps such as emails and phones, and
+// arrays, get the entries of the other cards that they do not already have.
+fn merge_cards<'x>(cards: impl IntoIterator<Item = &'x Value>) -> Value {
+    let mut cards = cards.into_iter();
+    let mut merged = cards.next().cloned().unwrap_or_default();
+    let Some(target) = merged.as_object_mut() else {
+        return merged;
+    };
+    for card in cards.filter_map(Value::as_object) {
+        for (property, value) in card {
+            match (target.get_mut(property), value) {
+                (None, value) => {
+                    target.insert(property.clone(), value.clone());
+                }
+                (Some(Value::Object(map)), Value::Object(other))
+                    if is_id_map(map) && is_id_map(other) =>
+                {
+                    merge_map(property, map, other);
+                }
+                (Some(Value::Array(items)), Value::Array(other)) => {
+                    for item in other {
+                        if !items.contains(item) {
+                            items.push(item.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    merged
+}
+
+// Maps of objects keyed by id, such as emails or addresses.
+fn is_id_map(map: &Map<String, Value>) -> bool {
+    !map.is_empty() && map.values().all(Value::is_object)
+}
+
+fn merge_map(property: &str, map: &mut Map<String, Value>, other: &Map<String, Value>) {
+    for (id, entry) in other {
+        let duplicate = map.values().any(|existing| {
+            existing == entry
+                || match property {
+                    "emails" => same_field(existing, entry, "address", |address| {
+                        Some(address.trim().to_lowercase())
+                    }),
+                    "phones" => same_field(existing, entry, "number", phone_key),
+                    _ => false,
+                }
+        });
+        if duplicate {
+            continue;
+        }
+        let mut new_id = id.clone();
+        let mut suffix = 2;
+        while map.contains_key(&new_id) {
+            new_id = format!("{}-{}", id, suffix);
+            suffix += 1;
+        }
+        map.insert(new_id, entry.clone());
+    }
+}
+
+fn same_field(a: &Value, b: &Value, field: &str, key: impl Fn(&str) -> Option<String>) -> bool {
+    let key = |value: &Value| value.get(field).and_then(Value::as_str).and_then(&key);
+    key(a).is_some() && key(a) == key(b)
+}
@@ -0,0 +1,299 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::{
+    contentline::{self, fold_line},
+    html,
+};
+
+const DESCRIBED_COMPONENTS: &[&str] = &["VEVENT", "VTODO", "VJOURNAL"];
+
+const HTML_TYPE: &str = "text/html";
+
+// Description of the first event, task or journal entry in a calendar.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Description {
+    pub text: String,
+    pub content_type: Option<String>,
+    // Raw property value, escapes included, when read from iCalendar.
+    pub raw: Option<String>,
+    // X-ALT-DESC in iCalendar, or the description itself when it is HTML.
+    pub html: Option<String>,
+}
+
+impl Description {
+    pub fn extract(text: &str) -> Option<Self> {
+        let text = text.trim_start();
+        if text.starts_with('{') {
+            Self::from_jscalendar(&serde_json::from_str::<Value>(text).ok()?)
+        } else {
+            Self::from_icalendar(text)
+        }
+    }
+
+    fn from_icalendar(text: &str) -> Option<Self> {
+        let mut stack: Vec<String> = Vec::new();
+        let mut description: Option<Description> = None;
+        let mut html = None;
+        for line in contentline::parse(text) {
+            match line.name.as_str() {
+                "BEGIN" => stack.push(line.value.to_ascii_uppercase()),
+                "END" => {
+                    let closed = stack.pop().is_some_and(|component| {
+                        DESCRIBED_COMPONENTS.contains(&component.as_str())
+                    });
+                    if closed && (description.is_some() || html.is_some()) {
+                        break;
+                    }
+                }
+                _ if !stack.last().is_some_and(|component| {
+                    DESCRIBED_COMPONENTS.contains(&component.as_str())
+                }) => {}
+                "DESCRIPTION" => {
+                    description = Some(Description {
+                        text: line.text(),
+                        content_type: None,
+                        raw: Some(line.value),
+                        html: None,
+                    });
+                }
+                "X-ALT-DESC" if is_html(&line) => html = Some(line.text()),
+                _ => {}
+            }
+        }
+
+        match (description, html) {
+            (Some(description), html) => Some(Description {
+                html,
+                ..description
+            }),
+            (None, Some(html)) => Some(Description {
+                text: String::new(),
+                content_type: None,
+                raw: None,
+                html: Some(html),
+            }),
+            (None, None) => None,
+        }
+    }
+
+    fn from_jscalendar(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+        match object.get("@type").and_then(Value::as_str)? {
+            "Event" | "Task" => {
+                let text = object.get("description")?.as_str()?.to_string();
+                let content_type = object
+                    .get("descriptionContentType")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                Some(Description {
+                    html: content_type
+                        .as_deref()
+                        .is_some_and(is_html_type)
+                        .then(|| text.clone()),
+                    text,
+                    content_type,
+                    raw: None,
+                })
+            }
+            "Group" => object
+                .get("entries")?
+                .as_array()?
+                .iter()
+                .find_map(Self::from_jscalendar),
+            _ => None,
+        }
+    }
+}
+
+// Moves X-ALT-DESC HTML descriptions into the JSCalendar description of the
+// matching event, returning None when nothing needed changing.
+pub fn apply_html_to_jscalendar(icalendar: &str, jscalendar: &str) -> Option<String> {
+    let descriptions = icalendar_html_descriptions(icalendar);
+    if descriptions.is_empty() {
+        return None;
+    }
+
+    let mut value = serde_json::from_str::<Value>(jscalendar).ok()?;
+    let mut changed = false;
+    for_each_entry(&mut value, &mut |entry| {
+        let Some((_, html)) = descriptions
+            .iter()
+            .find(|(uid, _)| entry.get("uid").and_then(Value::as_str) == Some(uid.as_str()))
+        else {
+            return;
+        };
+        if entry
+            .get("descriptionContentType")
+            .and_then(Value::as_str)
+            .is_some_and(is_html_type)
+        {
+            return;
+        }
+        entry.insert("description".into(), html.clone().into());
+        entry.insert("descriptionContentType".into(), HTML_TYPE.into());
+        changed = true;
+    });
+    changed
+        .then(|| serde_json::to_string_pretty(&value).ok())
+        .flatten()
+}
+
+// Regenerates a plain text DESCRIPTION and an X-ALT-DESC for every JSCalendar
+// entry with an HTML description, returning None when nothing needed changing.
+pub fn apply_html_to_icalendar(jscalendar: &str, icalendar: &str) -> Option<String> {
+    let mut value = serde_json::from_str::<Value>(jscalendar).ok()?;
+    let mut descriptions = Vec::new();
+    for_each_entry(&mut value, &mut |entry| {
+        let html_description = entry
+            .get("descriptionContentType")
+            .and_then(Value::as_str)
+            .is_some_and(is_html_type);
+        if let (true, Some(uid), Some(description)) = (
+            html_description,
+            entry.get("uid").and_then(Value::as_str),
+            entry.get("description").and_then(Value::as_str),
+        ) {
+            descriptions.push((uid.to_string(), description.to_string()));
+        }
+    });
+    if descriptions.is_empty() {
+        return None;
+    }
+
+    let mut out = String::with_capacity(icalendar.len());
+    let mut component: Option<Vec<String>> = None;
+    let mut changed = false;
+    for (_, line) in contentline::unfold(icalendar) {
+        let parsed = contentline::parse_line(0, &line);
+        let name = parsed
+            .as_ref()
+            .map(|line| line.name.as_str())
+            .unwrap_or_default();
+        let value = parsed
+            .as_ref()
+            .map(|line| line.value.to_ascii_uppercase())
+            .unwrap_or_default();
+
+        if name == "BEGIN" && DESCRIBED_COMPONENTS.contains(&value.as_str()) {
+            component = Some(vec![line]);
+        } else if let Some(mut lines) = component.take() {
+            if name == "END" && DESCRIBED_COMPONENTS.contains(&value.as_str()) {
+                lines.push(line);
+                changed |= rewrite_component(&mut lines, &descriptions);
+                for line in lines {
+                    fold_line(&mut out, &line);
+                }
+            } else {
+                lines.push(line);
+                component = Some(lines);
+            }
+        } else {
+            fold_line(&mut out, &line);
+        }
+    }
+    changed.then_some(out)
+}
+
+fn rewrite_component(lines: &mut Vec<String>, descriptions: &[(String, String)]) -> bool {
+    let parsed = lines
+        .iter()
+        .map(|line| contentline::parse_line(0, line))
+        .collect::<Vec<_>>();
+    let Some(html) = parsed
+        .iter()
+        .flatten()
+        .find(|line| line.name == "UID")
+        .and_then(|uid| {
+            descriptions
+                .iter()
+                .find(|(item, _)| *item == uid.text())
+                .map(|(_, html)| html)
+        })
+    else {
+        return false;
+    };
+    if parsed
+        .iter()
+        .flatten()
+        .any(|line| line.name == "X-ALT-DESC" && is_html(line))
+    {
+        return false;
+    }
+
+    let description = format!(
+        "DESCRIPTION:{}",
+        contentline::escape(&html::to_plain_text(html))
+    );
+    match parsed
+        .iter()
+        .position(|line| line.as_ref().is_some_and(|line| line.name == "DESCRIPTION"))
+    {
+        Some(idx) => lines[idx] = description,
+        None => lines.insert(lines.len() - 1, description),
+    }
+    lines.insert(
+        lines.len() - 1,
+        format!(
+            "X-ALT-DESC;FMTTYPE={}:{}",
+            HTML_TYPE,
+            contentline::escape(html)
+        ),
+    );
+    true
+}
+
+fn icalendar_html_descriptions(icalendar: &str) -> Vec<(String, String)> {
+    let mut descriptions = Vec::new();
+    let (mut uid, mut html) = (None, None);
+    for line in contentline::parse(icalendar) {
+        match line.name.as_str() {
+            "BEGIN" => (uid, html) = (None, None),
+            "UID" => uid = Some(line.text()),
+            "X-ALT-DESC" if is_html(&line) => html = Some(line.text()),
+            "END" => {
+                if let (Some(uid), Some(html)) = (uid.take(), html.take()) {
+                    descriptions.push((uid, html));
+                }
+            }
+            _ => {}
+        }
+    }
+    descriptions
+}
+
+pub fn for_each_entry(value: &mut Value, f: &mut impl FnMut(&mut Map<String, Value>)) {
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    match object.get("@type").and_then(Value::as_str) {
+        Some("Event" | "Task") => f(object),
+        Some("Group") => {
+            for entry in object
+                .get_mut("entries")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten()
+            {
+                for_each_entry(entry, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_html(line: &contentline::ContentLine) -> bool {
+    line.param("FMTTYPE").is_some_and(is_html_type)
+}
+
+fn is_html_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|content_type| content_type.trim().eq_ignore_ascii_case(HTML_TYPE))
+}
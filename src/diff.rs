@@ -0,0 +1,252 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Above this many cells the LCS table is skipped and the differing middle
+// section is reported as a single replacement.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+}
+
+// A row of a side-by-side view.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffRow {
+    pub old: Option<DiffLine>,
+    pub new: Option<DiffLine>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiffSpan {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+impl DiffRow {
+    pub fn is_changed(&self) -> bool {
+        !matches!(&self.old, Some(line) if line.op == DiffOp::Equal)
+    }
+}
+
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let ops = diff_ops(&old, &new);
+
+    let (mut old_pos, mut new_pos) = (0, 0);
+    ops.into_iter()
+        .map(|op| match op {
+            DiffOp::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+                DiffLine {
+                    op,
+                    text: old[old_pos - 1].to_string(),
+                    old_line: Some(old_pos),
+                    new_line: Some(new_pos),
+                }
+            }
+            DiffOp::Delete => {
+                old_pos += 1;
+                DiffLine {
+                    op,
+                    text: old[old_pos - 1].to_string(),
+                    old_line: Some(old_pos),
+                    new_line: None,
+                }
+            }
+            DiffOp::Insert => {
+                new_pos += 1;
+                DiffLine {
+                    op,
+                    text: new[new_pos - 1].to_string(),
+                    old_line: None,
+                    new_line: Some(new_pos),
+                }
+            }
+        })
+        .collect()
+}
+
+// Character level diff, with consecutive characters sharing an operation
+// merged into a single span.
+pub fn diff_chars(old: &str, new: &str) -> Vec<DiffSpan> {
+    let old = old.chars().collect::<Vec<_>>();
+    let new = new.chars().collect::<Vec<_>>();
+
+    let (mut old_pos, mut new_pos) = (0, 0);
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    for op in diff_ops(&old, &new) {
+        let ch = match op {
+            DiffOp::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+                old[old_pos - 1]
+            }
+            DiffOp::Delete => {
+                old_pos += 1;
+                old[old_pos - 1]
+            }
+            DiffOp::Insert => {
+                new_pos += 1;
+                new[new_pos - 1]
+            }
+        };
+        match spans.last_mut() {
+            Some(span) if span.op == op => span.text.push(ch),
+            _ => spans.push(DiffSpan {
+                op,
+                text: ch.to_string(),
+            }),
+        }
+    }
+    spans
+}
+
+pub fn has_changes(lines: &[DiffLine]) -> bool {
+    lines.iter().any(|line| line.op != DiffOp::Equal)
+}
+
+// Pairs lines for a side-by-side view: removed lines sit next to the lines
+// inserted in their place, and the rest next to an empty cell.
+pub fn side_by_side(lines: &[DiffLine]) -> Vec<DiffRow> {
+    let mut rows = Vec::with_capacity(lines.len());
+    let mut deleted: Vec<DiffLine> = Vec::new();
+    let mut inserted: Vec<DiffLine> = Vec::new();
+    let flush =
+        |rows: &mut Vec<DiffRow>, deleted: &mut Vec<DiffLine>, inserted: &mut Vec<DiffLine>| {
+            let count = deleted.len().max(inserted.len());
+            let mut deleted = deleted.drain(..);
+            let mut inserted = inserted.drain(..);
+            for _ in 0..count {
+                rows.push(DiffRow {
+                    old: deleted.next(),
+                    new: inserted.next(),
+                });
+            }
+        };
+    for line in lines {
+        match line.op {
+            DiffOp::Delete => deleted.push(line.clone()),
+            DiffOp::Insert => inserted.push(line.clone()),
+            DiffOp::Equal => {
+                flush(&mut rows, &mut deleted, &mut inserted);
+                rows.push(DiffRow {
+                    old: Some(line.clone()),
+                    new: Some(line.clone()),
+                });
+            }
+        }
+    }
+    flush(&mut rows, &mut deleted, &mut inserted);
+    rows
+}
+
+// Keeps the changed items and `context` items around each of them. A None
+// stands for a run of unchanged items left out.
+pub fn with_context<T>(
+    items: Vec<T>,
+    changed: impl Fn(&T) -> bool,
+    context: usize,
+) -> Vec<Option<T>> {
+    let keep = items.iter().map(&changed).collect::<Vec<_>>();
+    let near_change = |index: usize| {
+        let from = index.saturating_sub(context);
+        let to = index
+            .saturating_add(context)
+            .saturating_add(1)
+            .min(keep.len());
+        keep[from..to].iter().any(|changed| *changed)
+    };
+    let mut result = Vec::new();
+    let mut skipped = false;
+    for (index, item) in items.into_iter().enumerate() {
+        if near_change(index) {
+            if skipped {
+                result.push(None);
+                skipped = false;
+            }
+            result.push(Some(item));
+        } else {
+            skipped = true;
+        }
+    }
+    if skipped {
+        result.push(None);
+    }
+    result
+}
+
+fn diff_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    ops.extend(std::iter::repeat_n(DiffOp::Equal, prefix));
+    if old_mid.len() * new_mid.len() > MAX_LCS_CELLS {
+        ops.extend(std::iter::repeat_n(DiffOp::Delete, old_mid.len()));
+        ops.extend(std::iter::repeat_n(DiffOp::Insert, new_mid.len()));
+    } else {
+        ops.extend(lcs_ops(old_mid, new_mid));
+    }
+    ops.extend(std::iter::repeat_n(DiffOp::Equal, suffix));
+    ops
+}
+
+fn lcs_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let width = new.len() + 1;
+    let mut table = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i * width + j] = if old[i] == new[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Equal);
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push(DiffOp::Delete);
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(DiffOp::Delete, old.len() - i));
+    ops.extend(std::iter::repeat_n(DiffOp::Insert, new.len() - j));
+    ops
+}
@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use serde_json::{json, Value};
+
+use crate::history::History;
+
+const HISTORY_LIMIT: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub name: String,
+    pub source: String,
+    // Edit history is kept in memory only.
+    pub history: History<String>,
+}
+
+// Open documents and the selected one, persisted across reloads.
+#[derive(Clone, Debug)]
+pub struct Session {
+    documents: Vec<Document>,
+    active: usize,
+}
+
+impl Document {
+    fn new(name: String, source: String) -> Self {
+        Document {
+            name,
+            source,
+            history: History::new(HISTORY_LIMIT),
+        }
+    }
+}
+
+impl Session {
+    pub fn documents(&self) -> &[Document] {
+        &self.documents
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> &Document {
+        &self.documents[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Document {
+        &mut self.documents[self.active]
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.documents.len() {
+            self.active = index;
+        }
+    }
+
+    // Opens an empty document and selects it.
+    pub fn open(&mut self) {
+        let name = (1..)
+            .map(|n| format!("Document {}", n))
+            .find(|name| !self.documents.iter().any(|document| document.name == *name))
+            .unwrap_or_default();
+        self.documents.push(Document::new(name, String::new()));
+        self.active = self.documents.len() - 1;
+    }
+
+    // Closes a document, always leaving at least one open.
+    pub fn close(&mut self, index: usize) {
+        if index >= self.documents.len() {
+            return;
+        }
+        self.documents.remove(index);
+        if self.documents.is_empty() {
+            self.open();
+        } else if self.active > index || self.active == self.documents.len() {
+            self.active -= 1;
+        }
+    }
+
+    pub fn rename(&mut self, index: usize, name: &str) {
+        let name = name.trim();
+        if let (Some(document), false) = (self.documents.get_mut(index), name.is_empty()) {
+            document.name = name.to_string();
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            documents: vec![Document::new("Document 1".to_string(), String::new())],
+            active: 0,
+        }
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = json!({
+            "active": self.active,
+            "documents": self
+                .documents
+                .iter()
+                .map(|document| json!({ "name": document.name, "source": document.source }))
+                .collect::<Vec<_>>(),
+        });
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for Session {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = serde_json::from_str::<Value>(s).map_err(|err| err.to_string())?;
+        let documents = value
+            .get("documents")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "Missing documents".to_string())?
+            .iter()
+            .filter_map(|document| {
+                Some(Document::new(
+                    document.get("name")?.as_str()?.to_string(),
+                    document.get("source")?.as_str()?.to_string(),
+                ))
+            })
+            .collect::<Vec<_>>();
+        if documents.is_empty() {
+            return Err("No documents".to_string());
+        }
+        let active = value
+            .get("active")
+            .and_then(Value::as_u64)
+            .map_or(0, |active| active as usize)
+            .min(documents.len() - 1);
+        Ok(Session { documents, active })
+    }
+}
@@ -0,0 +1,298 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Raw RFC 5322 messages: the MIME structure is walked to find the calendar
+// invites and vCards sent in them.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+const CALENDAR_TYPES: &[&str] = &["text/calendar", "application/ics"];
+const CONTACT_TYPES: &[&str] = &["text/vcard", "text/x-vcard", "text/directory"];
+
+// A calendar or vCard body part, decoded to text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Part {
+    // Position in the MIME tree, such as 2.1 for the first part of the second.
+    pub path: String,
+    pub content_type: String,
+    pub name: Option<String>,
+    // The iTIP method of a text/calendar part.
+    pub method: Option<String>,
+    pub text: String,
+}
+
+// Whether the source starts with a message header block rather than a
+// calendar, contact or JSON document.
+pub fn is_message(source: &str) -> bool {
+    let source = source.trim_start();
+    if source.starts_with(['{', '[', '<'])
+        || source
+            .get(..6)
+            .is_some_and(|start| start.eq_ignore_ascii_case("BEGIN:"))
+    {
+        return false;
+    }
+    let (headers, _) = split_entity(source);
+    let headers = parse_headers(headers);
+    headers.iter().any(|(name, _)| {
+        matches!(
+            name.to_ascii_lowercase().as_str(),
+            "mime-version" | "content-type" | "from" | "subject" | "received" | "message-id"
+        )
+    })
+}
+
+// The calendar and vCard parts of a message, in MIME order.
+pub fn extract(source: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    walk(source.trim_start(), "", &mut parts);
+    parts
+}
+
+fn walk(entity: &str, path: &str, parts: &mut Vec<Part>) {
+    let (headers, body) = split_entity(entity);
+    let headers = parse_headers(headers);
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let (content_type, params) = parse_content_type(header("Content-Type").unwrap_or("text/plain"));
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    if content_type.starts_with("multipart/") {
+        let Some(boundary) = param("boundary") else {
+            return;
+        };
+        for (index, child) in split_multipart(body, &boundary).into_iter().enumerate() {
+            let child_path = if path.is_empty() {
+                (index + 1).to_string()
+            } else {
+                format!("{}.{}", path, index + 1)
+            };
+            walk(child, &child_path, parts);
+        }
+        return;
+    }
+    if content_type == "message/rfc822" {
+        walk(body, path, parts);
+        return;
+    }
+
+    let disposition = header("Content-Disposition").map(parse_content_type);
+    let name = disposition
+        .as_ref()
+        .and_then(|(_, params)| {
+            params
+                .iter()
+                .find(|(param, _)| param == "filename")
+                .map(|(_, value)| value.clone())
+        })
+        .or_else(|| param("name"));
+    let extension = name
+        .as_deref()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    let wanted = CALENDAR_TYPES.contains(&content_type.as_str())
+        || CONTACT_TYPES.contains(&content_type.as_str())
+        || (content_type == "application/octet-stream"
+            && matches!(extension.as_deref(), Some("ics" | "ifb" | "vcf" | "vcard")));
+    if !wanted {
+        return;
+    }
+
+    let bytes = match header("Content-Transfer-Encoding")
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("base64") => {
+            let encoded = body
+                .chars()
+                .filter(|ch| !ch.is_ascii_whitespace())
+                .collect::<String>();
+            STANDARD.decode(encoded).unwrap_or_default()
+        }
+        Some("quoted-printable") => crate::contentline::decode_quoted_printable(
+            &body.replace("=\r\n", "").replace("=\n", ""),
+        ),
+        _ => body.as_bytes().to_vec(),
+    };
+    let text = match param("charset")
+        .map(|charset| charset.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("iso-8859-1" | "latin1" | "windows-1252" | "us-ascii") => {
+            bytes.iter().map(|byte| char::from(*byte)).collect()
+        }
+        _ => String::from_utf8_lossy(&bytes).into_owned(),
+    };
+    parts.push(Part {
+        path: if path.is_empty() {
+            "1".to_string()
+        } else {
+            path.to_string()
+        },
+        content_type,
+        name,
+        method: param("method").map(|method| method.to_ascii_uppercase()),
+        text: text.trim_start_matches('\u{feff}').to_string(),
+    });
+}
+
+// Splits an entity at the first empty line into its headers and body.
+fn split_entity(entity: &str) -> (&str, &str) {
+    if let Some(body) = entity
+        .strip_prefix("\r\n")
+        .or_else(|| entity.strip_prefix('\n'))
+    {
+        return ("", body);
+    }
+    let crlf = entity.find("\r\n\r\n").map(|index| (index, index + 4));
+    let lf = entity.find("\n\n").map(|index| (index, index + 2));
+    let split = match (crlf, lf) {
+        (Some(crlf), Some(lf)) => Some(if crlf.0 < lf.0 { crlf } else { lf }),
+        (crlf, lf) => crlf.or(lf),
+    };
+    match split {
+        Some((end, start)) => (&entity[..end], &entity[start..]),
+        None => (entity, ""),
+    }
+}
+
+// Unfolded header fields, stopping at the first line that is not one.
+fn parse_headers(headers: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in headers.lines() {
+        if line.starts_with([' ', '\t']) {
+            match fields.last_mut() {
+                Some((_, value)) => {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                None => break,
+            }
+            continue;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            break;
+        };
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+        {
+            break;
+        }
+        fields.push((name.to_string(), value.trim().to_string()));
+    }
+    fields
+}
+
+// The lower-cased value of a Content-Type or Content-Disposition header and
+// its parameters, with names lower-cased and quotes removed. RFC 2231
+// parameters such as filename*=UTF-8''invite.ics are read as their plain
+// form.
+fn parse_content_type(value: &str) -> (String, Vec<(String, String)>) {
+    let mut items = split_params(value).into_iter();
+    let kind = items.next().unwrap_or_default().trim().to_ascii_lowercase();
+    let params = items
+        .filter_map(|item| {
+            let (name, value) = item.split_once('=')?;
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim().trim_matches('"');
+            match name.strip_suffix('*') {
+                Some(name) => Some((
+                    name.to_string(),
+                    percent_decode(value.rsplit_once('\'').map_or(value, |(_, value)| value)),
+                )),
+                None => Some((name, value.to_string())),
+            }
+        })
+        .collect();
+    (kind, params)
+}
+
+// Splits on semicolons outside of quoted strings.
+fn split_params(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in value.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                current.push(ch);
+            }
+            ';' if !quoted => items.push(std::mem::take(&mut current)),
+            ch => current.push(ch),
+        }
+    }
+    items.push(current);
+    items
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                out.push(byte);
+                index += 3;
+            }
+            None => {
+                out.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// The body parts between boundary delimiter lines, without the preamble and
+// epilogue.
+fn split_multipart<'x>(body: &'x str, boundary: &str) -> Vec<&'x str> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if let Some(rest) = trimmed.strip_prefix(&delimiter) {
+            if let Some(start) = start {
+                parts.push(trim_line_end(&body[start..offset]));
+            }
+            if rest == "--" {
+                return parts;
+            }
+            start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+// The line break before a delimiter belongs to the delimiter.
+fn trim_line_end(text: &str) -> &str {
+    text.strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .unwrap_or(text)
+}
@@ -0,0 +1,314 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::{contentline, Format};
+
+// Set by every tool to its own value, so never a disagreement.
+pub const VOLATILE_PROPERTIES: &[&str] = &["PRODID", "DTSTAMP", "REV"];
+pub const VOLATILE_KEYS: &[&str] = &["prodId"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisagreementKind {
+    // In the expected output only.
+    Missing,
+    // In this tool's output only.
+    Unexpected,
+    Different,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub kind: DisagreementKind,
+    // JSON pointer, or component path and property name.
+    pub path: String,
+    pub ours: Option<String>,
+    pub expected: Option<String>,
+}
+
+// A component of a vCard or iCalendar with its properties written in a
+// canonical form that ignores parameter order, case and groups.
+struct Component {
+    path: String,
+    properties: Vec<(String, String)>,
+}
+
+impl DisagreementKind {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            DisagreementKind::Missing => "expected-missing",
+            DisagreementKind::Unexpected => "expected-unexpected",
+            DisagreementKind::Different => "expected-different",
+        }
+    }
+}
+
+// Compares this tool's conversion against what another tool produced for the
+// same input, in terms of the data model rather than the text: property and
+// key order, line folding and the ids of JSON maps do not matter.
+pub fn compare(format: Format, ours: &str, expected: &str) -> Result<Vec<Disagreement>, String> {
+    let mut disagreements = Vec::new();
+    if format.is_json() {
+        let ours = serde_json::from_str::<Value>(ours)
+            .map_err(|err| format!("Failed to parse the conversion: {}", err))?;
+        let expected = serde_json::from_str::<Value>(expected.trim())
+            .map_err(|err| format!("Failed to parse the expected output: {}", err))?;
+        compare_values("", &ours, &expected, &mut disagreements);
+    } else {
+        if !expected.trim_start().starts_with("BEGIN:") {
+            return Err(format!(
+                "The expected output does not look like {}.",
+                format.as_str()
+            ));
+        }
+        compare_components(&components(ours), &components(expected), &mut disagreements);
+    }
+    Ok(disagreements)
+}
+
+// Compares two JSCalendar or JSContact values the same way.
+pub fn compare_json(ours: &Value, expected: &Value) -> Vec<Disagreement> {
+    let mut disagreements = Vec::new();
+    compare_values("", ours, expected, &mut disagreements);
+    disagreements
+}
+
+fn compare_values(path: &str, ours: &Value, expected: &Value, out: &mut Vec<Disagreement>) {
+    match (ours, expected) {
+        (Value::Object(ours), Value::Object(expected))
+            if is_id_map(ours) && is_id_map(expected) =>
+        {
+            compare_id_maps(path, ours, expected, out);
+        }
+        (Value::Object(ours), Value::Object(expected)) => {
+            for (key, value) in ours {
+                let path = format!("{}/{}", path, escape_pointer(key));
+                match expected.get(key) {
+                    Some(other) => compare_values(&path, value, other, out),
+                    None if !VOLATILE_KEYS.contains(&key.as_str()) => {
+                        out.push(Disagreement::unexpected(path, value.to_string()));
+                    }
+                    None => {}
+                }
+            }
+            for (key, value) in expected {
+                if !ours.contains_key(key) && !VOLATILE_KEYS.contains(&key.as_str()) {
+                    out.push(Disagreement::missing(
+                        format!("{}/{}", path, escape_pointer(key)),
+                        value.to_string(),
+                    ));
+                }
+            }
+        }
+        (Value::Array(ours), Value::Array(expected)) => {
+            for (index, value) in ours.iter().enumerate() {
+                let path = format!("{}/{}", path, index);
+                match expected.get(index) {
+                    Some(other) => compare_values(&path, value, other, out),
+                    None => out.push(Disagreement::unexpected(path, value.to_string())),
+                }
+            }
+            for (index, value) in expected.iter().enumerate().skip(ours.len()) {
+                out.push(Disagreement::missing(
+                    format!("{}/{}", path, index),
+                    value.to_string(),
+                ));
+            }
+        }
+        (Value::Number(ours), Value::Number(expected)) if ours.as_f64() == expected.as_f64() => {}
+        (ours, expected) if ours == expected => {}
+        (ours, expected) => out.push(Disagreement {
+            kind: DisagreementKind::Different,
+            path: if path.is_empty() {
+                "/".to_string()
+            } else {
+                path.to_string()
+            },
+            ours: Some(ours.to_string()),
+            expected: Some(expected.to_string()),
+        }),
+    }
+}
+
+// Maps such as alerts or participants are keyed by ids each tool picks on
+// its own, so entries are matched by id first and then by content.
+fn compare_id_maps(
+    path: &str,
+    ours: &Map<String, Value>,
+    expected: &Map<String, Value>,
+    out: &mut Vec<Disagreement>,
+) {
+    let mut unmatched = expected.keys().collect::<Vec<_>>();
+    let mut pending = Vec::new();
+    for (key, value) in ours {
+        if let Some(index) = unmatched
+            .iter()
+            .position(|other| *other == key)
+            .or_else(|| {
+                unmatched
+                    .iter()
+                    .position(|other| expected[*other] == *value)
+            })
+        {
+            let other = unmatched.remove(index);
+            compare_values(
+                &format!("{}/{}", path, escape_pointer(key)),
+                value,
+                &expected[other],
+                out,
+            );
+        } else {
+            pending.push((key, value));
+        }
+    }
+    // Whatever is left is paired in order, the rest has no counterpart.
+    let paired = pending.len();
+    for (index, (key, value)) in pending.into_iter().enumerate() {
+        let path = format!("{}/{}", path, escape_pointer(key));
+        match unmatched.get(index) {
+            Some(other) => compare_values(&path, value, &expected[*other], out),
+            None => out.push(Disagreement::unexpected(path, value.to_string())),
+        }
+    }
+    for other in unmatched.into_iter().skip(paired) {
+        out.push(Disagreement::missing(
+            format!("{}/{}", path, escape_pointer(other)),
+            expected[other].to_string(),
+        ));
+    }
+}
+
+pub fn is_id_map(map: &Map<String, Value>) -> bool {
+    !map.is_empty()
+        && map
+            .values()
+            .all(|value| value.get("@type").is_some_and(Value::is_string))
+}
+
+pub fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn components(source: &str) -> Vec<Component> {
+    contentline::components(source)
+        .into_iter()
+        .map(|component| Component {
+            path: component.path,
+            properties: component
+                .properties
+                .iter()
+                .filter(|property| !VOLATILE_PROPERTIES.contains(&property.name.as_str()))
+                .map(|property| {
+                    let mut params = property
+                        .params
+                        .iter()
+                        .map(|(param, value)| format!("{}={}", param, value))
+                        .collect::<Vec<_>>();
+                    params.sort_unstable();
+                    let value = if params.is_empty() {
+                        property.value.clone()
+                    } else {
+                        format!("{}:{}", params.join(";"), property.value)
+                    };
+                    (property.name.clone(), value)
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn compare_components(ours: &[Component], expected: &[Component], out: &mut Vec<Disagreement>) {
+    for component in ours {
+        match expected.iter().find(|other| other.path == component.path) {
+            Some(other) => compare_properties(component, other, out),
+            None => out.push(Disagreement::unexpected(
+                component.path.clone(),
+                format!("{} properties", component.properties.len()),
+            )),
+        }
+    }
+    for component in expected {
+        if !ours.iter().any(|other| other.path == component.path) {
+            out.push(Disagreement::missing(
+                component.path.clone(),
+                format!("{} properties", component.properties.len()),
+            ));
+        }
+    }
+}
+
+fn compare_properties(ours: &Component, expected: &Component, out: &mut Vec<Disagreement>) {
+    let mut names = ours
+        .properties
+        .iter()
+        .chain(&expected.properties)
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        let values = |component: &Component| {
+            component
+                .properties
+                .iter()
+                .filter(|(property, _)| property == name)
+                .map(|(_, value)| value.clone())
+                .collect::<Vec<_>>()
+        };
+        let (mut left, mut right) = (values(ours), values(expected));
+        left.retain(
+            |value| match right.iter().position(|other| other == value) {
+                Some(index) => {
+                    right.remove(index);
+                    false
+                }
+                None => true,
+            },
+        );
+        let path = format!("{}/{}", ours.path, name);
+        match (left.as_slice(), right.as_slice()) {
+            ([value], [other]) => out.push(Disagreement {
+                kind: DisagreementKind::Different,
+                path,
+                ours: Some(value.clone()),
+                expected: Some(other.clone()),
+            }),
+            _ => {
+                out.extend(
+                    left.into_iter()
+                        .map(|value| Disagreement::unexpected(path.clone(), value)),
+                );
+                out.extend(
+                    right
+                        .into_iter()
+                        .map(|value| Disagreement::missing(path.clone(), value)),
+                );
+            }
+        }
+    }
+}
+
+impl Disagreement {
+    fn missing(path: String, expected: String) -> Self {
+        Disagreement {
+            kind: DisagreementKind::Missing,
+            path,
+            ours: None,
+            expected: Some(expected),
+        }
+    }
+
+    fn unexpected(path: String, ours: String) -> Self {
+        Disagreement {
+            kind: DisagreementKind::Unexpected,
+            path,
+            ours: Some(ours),
+            expected: None,
+        }
+    }
+}
@@ -0,0 +1,583 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Converted output annotated with where each of its fields came from, such as
+// "DTSTART;TZID=Europe/Berlin → start + timeZone", following the mapping of
+// RFC 9553 and RFC 9555 as the converter applies it.
+
+use std::{borrow::Cow, collections::HashSet};
+
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, Component, ContentLine},
+    jcal, Format,
+};
+
+// A JSON property and the iCalendar or vCard properties it is read from.
+// Sources are written "NAME", "NAME;PARAM" when a parameter decides it, or
+// "BEGIN:NAME" for components. Properties of map entries such as
+// participants are listed under the name of their map.
+struct Rule {
+    parent: &'static str,
+    key: &'static str,
+    sources: &'static [&'static str],
+}
+
+const fn rule(parent: &'static str, key: &'static str, sources: &'static [&'static str]) -> Rule {
+    Rule {
+        parent,
+        key,
+        sources,
+    }
+}
+
+const RULES: &[Rule] = &[
+    // RFC 9555, iCalendar.
+    rule("", "uid", &["UID"]),
+    rule("", "title", &["SUMMARY"]),
+    rule("", "description", &["DESCRIPTION"]),
+    rule(
+        "",
+        "descriptionContentType",
+        &["DESCRIPTION;FMTTYPE", "X-ALT-DESC"],
+    ),
+    rule("", "start", &["DTSTART"]),
+    rule("", "timeZone", &["DTSTART;TZID"]),
+    rule("", "showWithoutTime", &["DTSTART;VALUE"]),
+    rule("", "duration", &["DTEND", "DURATION"]),
+    rule("", "due", &["DUE"]),
+    rule("", "estimatedDuration", &["ESTIMATED-DURATION"]),
+    rule("", "recurrenceRules", &["RRULE"]),
+    rule("", "excludedRecurrenceRules", &["EXRULE"]),
+    rule("", "recurrenceOverrides", &["RDATE", "EXDATE"]),
+    rule("", "recurrenceId", &["RECURRENCE-ID"]),
+    rule("", "recurrenceIdTimeZone", &["RECURRENCE-ID;TZID"]),
+    rule("", "sequence", &["SEQUENCE"]),
+    rule("", "priority", &["PRIORITY"]),
+    rule("", "privacy", &["CLASS"]),
+    rule("", "freeBusyStatus", &["TRANSP"]),
+    rule("", "status", &["STATUS"]),
+    rule("", "progress", &["STATUS"]),
+    rule("", "percentComplete", &["PERCENT-COMPLETE"]),
+    rule("", "color", &["COLOR"]),
+    rule("", "locations", &["LOCATION", "GEO"]),
+    rule("", "virtualLocations", &["CONFERENCE"]),
+    rule("", "participants", &["ORGANIZER", "ATTENDEE"]),
+    rule("", "replyTo", &["ORGANIZER"]),
+    rule("", "alerts", &["BEGIN:VALARM"]),
+    rule("", "relatedTo", &["RELATED-TO"]),
+    rule("", "method", &["METHOD"]),
+    rule(
+        "",
+        "entries",
+        &["BEGIN:VEVENT", "BEGIN:VTODO", "BEGIN:VJOURNAL"],
+    ),
+    rule("participants", "name", &["ATTENDEE;CN", "ORGANIZER;CN"]),
+    rule("participants", "email", &["ATTENDEE", "ORGANIZER"]),
+    rule("participants", "sendTo", &["ATTENDEE", "ORGANIZER"]),
+    rule("participants", "roles", &["ATTENDEE;ROLE", "ORGANIZER"]),
+    rule(
+        "participants",
+        "participationStatus",
+        &["ATTENDEE;PARTSTAT"],
+    ),
+    rule("participants", "expectReply", &["ATTENDEE;RSVP"]),
+    rule("participants", "kind", &["ATTENDEE;CUTYPE"]),
+    rule("participants", "delegatedTo", &["ATTENDEE;DELEGATED-TO"]),
+    rule(
+        "participants",
+        "delegatedFrom",
+        &["ATTENDEE;DELEGATED-FROM"],
+    ),
+    rule("participants", "memberOf", &["ATTENDEE;MEMBER"]),
+    rule(
+        "participants",
+        "sentBy",
+        &["ATTENDEE;SENT-BY", "ORGANIZER;SENT-BY"],
+    ),
+    rule(
+        "participants",
+        "scheduleAgent",
+        &["ATTENDEE;SCHEDULE-AGENT"],
+    ),
+    rule("alerts", "trigger", &["TRIGGER"]),
+    rule("alerts", "action", &["ACTION"]),
+    rule("alerts", "acknowledged", &["ACKNOWLEDGED"]),
+    rule("locations", "name", &["LOCATION"]),
+    rule("locations", "coordinates", &["GEO"]),
+    rule("virtualLocations", "uri", &["CONFERENCE"]),
+    rule("links", "contentType", &["ATTACH;FMTTYPE"]),
+    // RFC 9553, vCard.
+    rule("", "kind", &["KIND"]),
+    rule("", "version", &["VERSION"]),
+    rule("", "name", &["N", "FN", "NAME", "X-WR-CALNAME"]),
+    rule("", "nicknames", &["NICKNAME"]),
+    rule("", "organizations", &["ORG"]),
+    rule("", "titles", &["TITLE", "ROLE"]),
+    rule("", "emails", &["EMAIL"]),
+    rule("", "phones", &["TEL"]),
+    rule("", "addresses", &["ADR"]),
+    rule("", "onlineServices", &["IMPP", "X-SOCIALPROFILE"]),
+    rule("", "notes", &["NOTE"]),
+    rule("", "anniversaries", &["BDAY", "ANNIVERSARY", "DEATHDATE"]),
+    rule("", "media", &["PHOTO", "LOGO", "SOUND"]),
+    rule("", "preferredLanguages", &["LANG"]),
+    rule("", "calendars", &["CALURI", "FBURL"]),
+    rule("", "schedulingAddresses", &["CALADRURI"]),
+    rule("", "cryptoKeys", &["KEY"]),
+    rule("", "directories", &["SOURCE", "ORG-DIRECTORY"]),
+    rule("", "members", &["MEMBER"]),
+    rule("", "speakToAs", &["GENDER", "GRAMGENDER", "PRONOUNS"]),
+    rule("", "personalInfo", &["EXPERTISE", "HOBBY", "INTEREST"]),
+    rule("emails", "address", &["EMAIL"]),
+    rule("emails", "contexts", &["EMAIL;TYPE"]),
+    rule("emails", "pref", &["EMAIL;PREF"]),
+    rule("phones", "number", &["TEL"]),
+    rule("phones", "features", &["TEL;TYPE"]),
+    rule("phones", "contexts", &["TEL;TYPE"]),
+    rule("phones", "pref", &["TEL;PREF"]),
+    rule("addresses", "components", &["ADR"]),
+    rule("addresses", "contexts", &["ADR;TYPE"]),
+    rule("addresses", "full", &["ADR;LABEL"]),
+    // Both.
+    rule("", "keywords", &["CATEGORIES"]),
+    rule("", "links", &["ATTACH", "URL", "IMAGE"]),
+    rule("", "prodId", &["PRODID"]),
+    rule("", "created", &["CREATED"]),
+    rule("", "updated", &["DTSTAMP", "LAST-MODIFIED", "REV"]),
+    rule("links", "href", &["ATTACH", "URL", "IMAGE"]),
+];
+
+// Parameter values longer than this are shortened in notes.
+const MAX_PARAM_LEN: usize = 24;
+
+// A line of the converted output and where it came from, when known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExplainedLine {
+    pub text: String,
+    pub note: Option<String>,
+}
+
+// Annotates `output`, the conversion of `source` from `format`, line by line.
+pub fn explain(source: &str, format: Format, output: &str) -> Vec<ExplainedLine> {
+    match format {
+        Format::ICalendar | Format::VCard => explain_json(source, output),
+        Format::JCal | Format::JCard => {
+            explain_json(&jcal::to_text(source).unwrap_or_default(), output)
+        }
+        Format::JSCalendar | Format::JSContact => explain_text(source, output),
+    }
+}
+
+// The iCalendar or vCard properties an object was read from: those of its
+// own component, or of its descendants for the entries of maps.
+struct Scope<'x> {
+    components: Vec<&'x Component>,
+    // Components within, for "BEGIN:" sources.
+    children: Vec<&'x Component>,
+}
+
+impl Scope<'_> {
+    fn properties<'y>(&'y self, spec: &'y str) -> impl Iterator<Item = &'y ContentLine> + 'y {
+        let (name, param) = split_spec(spec);
+        self.components
+            .iter()
+            .flat_map(|component| &component.properties)
+            .filter(move |property| {
+                property.name == name && param.is_none_or(|param| property.param(param).is_some())
+            })
+    }
+
+    fn count(&self, spec: &str) -> usize {
+        match spec.strip_prefix("BEGIN:") {
+            Some(name) => self
+                .children
+                .iter()
+                .filter(|child| child.name() == name)
+                .count(),
+            None => self.properties(spec).count(),
+        }
+    }
+
+    // The source as written, with its parameters, when it is the only one.
+    fn describe(&self, spec: &str, nested: bool) -> String {
+        let count = self.count(spec);
+        let mut text = match self.properties(spec).next() {
+            Some(property) if count == 1 && !nested => {
+                let (_, param) = split_spec(spec);
+                let mut text = property.name.clone();
+                for (name, value) in &property.params {
+                    if param.is_some_and(|param| param != name) {
+                        continue;
+                    }
+                    text.push(';');
+                    text.push_str(name);
+                    text.push('=');
+                    if value.chars().count() > MAX_PARAM_LEN {
+                        text.extend(value.chars().take(MAX_PARAM_LEN - 1));
+                        text.push('…');
+                    } else {
+                        text.push_str(value);
+                    }
+                }
+                text
+            }
+            _ => spec.to_string(),
+        };
+        if count > 1 && !nested {
+            text.push_str(&format!(" ×{}", count));
+        }
+        text
+    }
+}
+
+// iCalendar or vCard converted to JSON. Keys are explained in the objects of
+// entries and of the maps within them.
+fn explain_json(source: &str, output: &str) -> Vec<ExplainedLine> {
+    let Ok(value) = serde_json::from_str::<Value>(output) else {
+        return plain(output);
+    };
+    let components = contentline::components(source);
+    let depth = |component: &Component| component.path.matches('/').count();
+    let roots = components
+        .iter()
+        .filter(|component| depth(component) == 1)
+        .collect::<Vec<_>>();
+    let children = |parent: &Component| {
+        let prefix = format!("{}/", parent.path);
+        components
+            .iter()
+            .filter(|component| {
+                component.path.starts_with(&prefix) && depth(component) == depth(parent) + 1
+            })
+            .collect::<Vec<_>>()
+    };
+    let descendants = |parent: &Component| {
+        let prefix = format!("{}/", parent.path);
+        components
+            .iter()
+            .filter(|component| component.path.starts_with(&prefix))
+            .collect::<Vec<_>>()
+    };
+    // The component an entry was read from, by UID and RECURRENCE-ID, or else
+    // by its position.
+    let entry_component = |root: &Component, entry: &Value, index: usize| {
+        let entries = children(root)
+            .into_iter()
+            .filter(|child| matches!(child.name(), "VEVENT" | "VTODO" | "VJOURNAL"))
+            .collect::<Vec<_>>();
+        let text = |component: &Component, name: &str| {
+            component
+                .properties
+                .iter()
+                .find(|property| property.name == name)
+                .map(|property| property.value.trim().to_string())
+        };
+        let uid = entry.get("uid").and_then(Value::as_str);
+        let recurrence_id = entry.get("recurrenceId").is_some();
+        entries
+            .iter()
+            .find(|child| {
+                uid.is_some()
+                    && text(child, "UID").as_deref() == uid
+                    && text(child, "RECURRENCE-ID").is_some() == recurrence_id
+            })
+            .or_else(|| entries.get(index))
+            .copied()
+    };
+
+    let multiple = value.is_array();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut lines = Vec::new();
+    for text in output.lines() {
+        let line = text.trim_start();
+        let (key, rest) = match split_key(line) {
+            Some((key, rest)) => (Some(key), rest),
+            None => (None, line.trim_end_matches(',')),
+        };
+        let mut note = None;
+        if rest.starts_with(['}', ']']) {
+            frames.pop();
+        } else if let Some(parent) = frames.last_mut() {
+            let segment = match &key {
+                Some(key) => key.clone(),
+                None => {
+                    parent.next += 1;
+                    (parent.next - 1).to_string()
+                }
+            };
+            if let Some(key) = &key {
+                let path = parent.path.clone();
+                note = locate(&path, multiple).and_then(|object| {
+                    let json = pointer(&value, &path)?;
+                    let root = *roots.get(object.document)?;
+                    let document = pointer(&value, &path[..multiple as usize])?;
+                    let group = document.get("@type").and_then(Value::as_str) == Some("Group");
+                    let entry = match (object.entry, group) {
+                        (Some(index), true) => {
+                            let entry = pointer(&value, &path[..multiple as usize + 2])?;
+                            Some(entry_component(root, entry, index)?)
+                        }
+                        (Some(_), false) => return None,
+                        // A calendar with a single entry converts to it alone.
+                        (None, false) if root.name() == "VCALENDAR" => {
+                            Some(entry_component(root, document, 0)?)
+                        }
+                        (None, _) => None,
+                    };
+                    let own = entry.unwrap_or(root);
+                    let scope = match object.map {
+                        Some("alerts") => Scope {
+                            components: descendants(own)
+                                .into_iter()
+                                .filter(|component| component.name() == "VALARM")
+                                .collect(),
+                            children: vec![],
+                        },
+                        Some(_) => Scope {
+                            components: vec![own],
+                            children: vec![],
+                        },
+                        None => Scope {
+                            components: match entry {
+                                Some(entry) => vec![entry, root],
+                                None => vec![root],
+                            },
+                            children: children(own),
+                        },
+                    };
+                    if key == "@type" {
+                        return object
+                            .map
+                            .is_none()
+                            .then(|| format!("BEGIN:{} → @type", own.name()));
+                    }
+                    annotate(object.map.unwrap_or_default(), key, json, &scope)
+                });
+            }
+            if rest.ends_with(['{', '[']) {
+                let mut path = parent.path.clone();
+                path.push(segment);
+                frames.push(Frame { path, next: 0 });
+            }
+        } else if rest.ends_with(['{', '[']) {
+            frames.push(Frame {
+                path: vec![],
+                next: 0,
+            });
+        }
+        lines.push(ExplainedLine {
+            text: text.to_string(),
+            note,
+        });
+    }
+    lines
+}
+
+// An open object or array of the output, by its path from the root.
+struct Frame {
+    path: Vec<String>,
+    // Index of the next element of an array.
+    next: usize,
+}
+
+// Where an object of the output sits: the document, the entry of a group and
+// the map, such as participants, it is an entry of.
+struct Location<'x> {
+    document: usize,
+    entry: Option<usize>,
+    map: Option<&'x str>,
+}
+
+fn locate(path: &[String], multiple: bool) -> Option<Location<'_>> {
+    let (document, rest) = if multiple {
+        let (index, rest) = path.split_first()?;
+        (index.parse().ok()?, rest)
+    } else {
+        (0, path)
+    };
+    let (entry, rest) = match rest {
+        [entries, index, rest @ ..] if entries == "entries" => (Some(index.parse().ok()?), rest),
+        rest => (None, rest),
+    };
+    match rest {
+        [] => Some(Location {
+            document,
+            entry,
+            map: None,
+        }),
+        [map, _] if RULES.iter().any(|rule| rule.parent == map) => Some(Location {
+            document,
+            entry,
+            map: Some(map.as_str()),
+        }),
+        _ => None,
+    }
+}
+
+fn pointer<'x>(value: &'x Value, path: &[String]) -> Option<&'x Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Array(values) => values.get(segment.parse::<usize>().ok()?),
+        value => value.get(segment),
+    })
+}
+
+// "DTSTART;TZID=Europe/Berlin → start + timeZone" for `key` of `object`.
+fn annotate(parent: &str, key: &str, object: &Value, scope: &Scope) -> Option<String> {
+    let mut seen = HashSet::new();
+    let notes = RULES
+        .iter()
+        .filter(|rule| rule.parent == parent && rule.key == key)
+        .flat_map(|rule| rule.sources)
+        .filter(|spec| scope.count(spec) > 0)
+        .filter(|spec| seen.insert(split_spec(spec).0))
+        .map(|spec| {
+            let name = split_spec(spec).0;
+            let mut targets = RULES
+                .iter()
+                .filter(|rule| rule.parent == parent && object.get(rule.key).is_some())
+                // Entries of maps are told apart by their parameters.
+                .filter(|rule| {
+                    rule.sources.iter().any(|source| {
+                        let same = if parent.is_empty() {
+                            split_spec(source).0 == name
+                        } else {
+                            source == spec
+                        };
+                        same && scope.count(source) > 0
+                    })
+                })
+                .map(|rule| rule.key)
+                .collect::<Vec<_>>();
+            targets.dedup();
+            format!(
+                "{} → {}",
+                scope.describe(spec, !parent.is_empty()),
+                targets.join(" + ")
+            )
+        })
+        .collect::<Vec<_>>();
+    (!notes.is_empty()).then(|| notes.join("; "))
+}
+
+// JSON converted to iCalendar or vCard. Each property is explained by the
+// JSON properties of the source it can be written from.
+fn explain_text(source: &str, output: &str) -> Vec<ExplainedLine> {
+    let Ok(value) = serde_json::from_str::<Value>(source) else {
+        return plain(output);
+    };
+    let mut keys = HashSet::new();
+    collect_keys(&value, &mut keys);
+    output
+        .lines()
+        .map(|text| {
+            let note = contentline::parse_line(0, text.trim_end_matches('\r'))
+                .filter(|_| !text.starts_with([' ', '\t']))
+                .and_then(|line| {
+                    let spec = if line.name == "BEGIN" {
+                        Cow::Owned(format!("BEGIN:{}", line.value.trim().to_ascii_uppercase()))
+                    } else {
+                        Cow::Borrowed(line.name.as_str())
+                    };
+                    written_from(&spec, &keys)
+                });
+            ExplainedLine {
+                text: text.trim_end_matches('\r').to_string(),
+                note,
+            }
+        })
+        .collect()
+}
+
+// "← participants (roles, participationStatus)".
+fn written_from(name: &str, keys: &HashSet<&str>) -> Option<String> {
+    let mut own: Vec<&str> = Vec::new();
+    let mut nested: Vec<(&str, Vec<&str>)> = Vec::new();
+    for rule in RULES {
+        if !keys.contains(rule.key)
+            || !rule
+                .sources
+                .iter()
+                .any(|source| split_spec(source).0 == name)
+        {
+            continue;
+        }
+        if rule.parent.is_empty() {
+            if !own.contains(&rule.key) {
+                own.push(rule.key);
+            }
+        } else if !keys.contains(rule.parent) {
+            continue;
+        } else {
+            match nested.iter_mut().find(|(parent, _)| *parent == rule.parent) {
+                Some((_, keys)) => keys.push(rule.key),
+                None => nested.push((rule.parent, vec![rule.key])),
+            }
+        }
+    }
+    let mut text = own.join(" + ");
+    for (parent, keys) in nested {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(&format!("({}: {})", parent, keys.join(", ")));
+    }
+    (!text.is_empty()).then(|| format!("← {}", text))
+}
+
+fn collect_keys<'x>(value: &'x Value, keys: &mut HashSet<&'x str>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                keys.insert(key.as_str());
+                collect_keys(value, keys);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_keys(value, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn plain(output: &str) -> Vec<ExplainedLine> {
+    output
+        .lines()
+        .map(|text| ExplainedLine {
+            text: text.to_string(),
+            note: None,
+        })
+        .collect()
+}
+
+// "DTSTART;TZID" into its property and parameter names. Components have no
+// parameter.
+fn split_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once(';') {
+        Some((name, param)) => (name, Some(param)),
+        None => (spec, None),
+    }
+}
+
+// The key of a line of pretty-printed JSON and what follows it.
+fn split_key(line: &str) -> Option<(String, &str)> {
+    let rest = line.strip_prefix('"')?;
+    let mut escaped = false;
+    let end = rest.char_indices().find_map(|(index, ch)| {
+        match ch {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(index),
+            _ => escaped = false,
+        }
+        None
+    })?;
+    let after = rest[end + 1..].strip_prefix(": ")?;
+    let key = serde_json::from_str::<String>(&line[..end + 2]).ok()?;
+    Some((key, after.trim_end_matches(',')))
+}
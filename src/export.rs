@@ -0,0 +1,210 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use serde_json::{json, Map, Value};
+
+use crate::{contentline, entry_title, Format};
+
+const COMMENT_PREFIX: &str = "# ";
+const MAX_FILE_NAME_LEN: usize = 64;
+
+// How the conversion context is attached to copied output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeaderStyle {
+    #[default]
+    Off,
+    // "# key: value" lines ahead of iCalendar and vCard payloads.
+    Comments,
+    // A standalone JSON object followed by a blank line and the payload,
+    // so JSON payloads stay free of non-standard comments.
+    Sidecar,
+}
+
+impl HeaderStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HeaderStyle::Off => "off",
+            HeaderStyle::Comments => "comments",
+            HeaderStyle::Sidecar => "sidecar",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            HeaderStyle::Off => "header-style-off",
+            HeaderStyle::Comments => "header-style-comments",
+            HeaderStyle::Sidecar => "header-style-sidecar",
+        }
+    }
+
+    // JSON has no comment syntax, so only the sidecar is offered for it.
+    pub fn available(json: bool) -> &'static [HeaderStyle] {
+        if json {
+            &[HeaderStyle::Off, HeaderStyle::Sidecar]
+        } else {
+            &[
+                HeaderStyle::Off,
+                HeaderStyle::Comments,
+                HeaderStyle::Sidecar,
+            ]
+        }
+    }
+}
+
+impl fmt::Display for HeaderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for HeaderStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(HeaderStyle::Off),
+            "comments" => Ok(HeaderStyle::Comments),
+            "sidecar" => Ok(HeaderStyle::Sidecar),
+            _ => Err(format!("Unknown header style: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportContext {
+    pub format: String,
+    pub source_type: String,
+    pub version: String,
+    pub options: Vec<(String, String)>,
+    pub timestamp: String,
+}
+
+impl ExportContext {
+    fn fields(&self) -> Vec<(&str, String)> {
+        let mut fields = vec![
+            ("Format", self.format.clone()),
+            ("Source type", self.source_type.clone()),
+            ("Tool", format!("jmap-convert v{}", self.version)),
+        ];
+        if !self.options.is_empty() {
+            fields.push((
+                "Options",
+                self.options
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+        fields.push(("Exported", self.timestamp.clone()));
+        fields
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "format": self.format,
+            "sourceType": self.source_type,
+            "tool": "jmap-convert",
+            "version": self.version,
+            "options": self
+                .options
+                .iter()
+                .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+                .collect::<Map<_, _>>(),
+            "exported": self.timestamp,
+        })
+    }
+}
+
+pub fn with_context(payload: &str, context: &ExportContext, style: HeaderStyle) -> String {
+    match style {
+        HeaderStyle::Off => payload.to_string(),
+        HeaderStyle::Comments => {
+            let mut out = String::with_capacity(payload.len() + 256);
+            for (name, value) in context.fields() {
+                out.push_str(COMMENT_PREFIX);
+                out.push_str(name);
+                out.push_str(": ");
+                out.push_str(&value);
+                out.push_str("\r\n");
+            }
+            out.push_str(payload);
+            out
+        }
+        HeaderStyle::Sidecar => format!(
+            "{}\n\n{}",
+            serde_json::to_string_pretty(&json!({ "jmapConvertContext": context.to_json() }))
+                .unwrap_or_default(),
+            payload
+        ),
+    }
+}
+
+// Removes a header added by `with_context`, so copied output can be pasted
+// straight back into the converter.
+pub fn strip_context(text: &str) -> &str {
+    let mut rest = text.trim_start();
+    if rest.starts_with('{') && rest.contains("\"jmapConvertContext\"") {
+        let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+        if let Some(Ok(value)) = stream.next() {
+            if value.get("jmapConvertContext").is_some() {
+                return rest[stream.byte_offset()..].trim_start();
+            }
+        }
+        return text;
+    }
+    while rest.starts_with(COMMENT_PREFIX.trim_end()) {
+        rest = rest
+            .split_once('\n')
+            .map_or("", |(_, rest)| rest)
+            .trim_start();
+    }
+    rest
+}
+
+// Name for saving a payload of this format, taken from the summary or name
+// of its first entry, or else its UID.
+pub fn file_name(format: Format, payload: &str) -> String {
+    let stem = entry_title(payload)
+        .or_else(|| entry_uid(payload))
+        .map(|name| {
+            let mut stem = String::with_capacity(name.len());
+            for ch in name.trim().chars() {
+                if ch.is_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                    stem.push(ch);
+                } else if !stem.ends_with('-') {
+                    stem.push('-');
+                }
+            }
+            stem.trim_matches(['-', '.'])
+                .chars()
+                .take(MAX_FILE_NAME_LEN)
+                .collect::<String>()
+        })
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "jmap-convert".to_string());
+    format!("{}.{}", stem, format.extension())
+}
+
+fn entry_uid(payload: &str) -> Option<String> {
+    let payload = payload.trim_start();
+    if payload.starts_with(['{', '[']) {
+        let value = serde_json::from_str::<Value>(payload).ok()?;
+        let value = value
+            .get("entries")
+            .and_then(|entries| entries.get(0))
+            .or_else(|| value.get(0))
+            .unwrap_or(&value);
+        value.get("uid")?.as_str().map(str::to_string)
+    } else {
+        contentline::parse(payload)
+            .into_iter()
+            .find(|line| line.name == "UID")
+            .map(|line| line.text())
+    }
+}
@@ -0,0 +1,251 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use js_sys::{Object, Promise, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode, Response};
+
+const DB_NAME: &str = "jmap-convert";
+const DB_VERSION: u32 = 1;
+const CACHE_STORE: &str = "fetch-cache";
+// Larger bodies are refused rather than loaded into the editor.
+pub const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FetchOptions {
+    // Either a prefix the encoded URL is appended to, or a template containing `{url}`.
+    pub cors_proxy: String,
+    pub bypass_cache: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FetchedResource {
+    pub url: String,
+    pub body: String,
+    pub from_cache: bool,
+    // Milliseconds since the epoch.
+    pub fetched_at: f64,
+}
+
+pub async fn fetch_text(url: &str, options: &FetchOptions) -> Result<FetchedResource, String> {
+    let url = normalize_url(url);
+    let url = url.as_str();
+    if url.is_empty() {
+        return Err("Please enter a URL.".to_string());
+    }
+
+    if !options.bypass_cache {
+        match cache_get(url).await {
+            Ok(Some(resource)) => return Ok(resource),
+            Ok(None) => (),
+            Err(err) => log::warn!("Fetch cache unavailable: {}", err),
+        }
+    }
+
+    let window = web_sys::window().ok_or("No window available")?;
+    let response = JsFuture::from(window.fetch_with_str(&proxied_url(url, &options.cors_proxy)))
+        .await
+        .map_err(|err| {
+            format!(
+                "Failed to fetch {}: {}. If the server does not allow cross-origin requests, configure a CORS proxy in the settings.",
+                url,
+                js_error(&err)
+            )
+        })?
+        .dyn_into::<Response>()
+        .map_err(|_| "Invalid fetch response".to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch {}: HTTP {} {}",
+            url,
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let too_large = || {
+        format!(
+            "{} is larger than {} MB.",
+            url,
+            MAX_BODY_BYTES / (1024 * 1024)
+        )
+    };
+    if response
+        .headers()
+        .get("content-length")
+        .ok()
+        .flatten()
+        .and_then(|length| length.parse::<usize>().ok())
+        .is_some_and(|length| length > MAX_BODY_BYTES)
+    {
+        return Err(too_large());
+    }
+    let body = JsFuture::from(response.text().map_err(|err| js_error(&err))?)
+        .await
+        .map_err(|err| js_error(&err))?
+        .as_string()
+        .unwrap_or_default();
+    // Servers may leave the length out or compress the body.
+    if body.len() > MAX_BODY_BYTES {
+        return Err(too_large());
+    }
+
+    let resource = FetchedResource {
+        url: url.to_string(),
+        body,
+        from_cache: false,
+        fetched_at: js_sys::Date::now(),
+    };
+    if let Err(err) = cache_put(&resource).await {
+        log::warn!("Failed to cache {}: {}", url, err);
+    }
+
+    Ok(resource)
+}
+
+// Subscription links use the webcal scheme for what is served over HTTPS.
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    match url.split_once("://") {
+        Some((scheme, rest))
+            if scheme.eq_ignore_ascii_case("webcal") || scheme.eq_ignore_ascii_case("webcals") =>
+        {
+            format!("https://{}", rest)
+        }
+        _ => url.to_string(),
+    }
+}
+
+pub fn proxied_url(url: &str, cors_proxy: &str) -> String {
+    let cors_proxy = cors_proxy.trim();
+    if cors_proxy.is_empty() {
+        url.to_string()
+    } else {
+        let encoded = String::from(js_sys::encode_uri_component(url));
+        if cors_proxy.contains("{url}") {
+            cors_proxy.replace("{url}", &encoded)
+        } else {
+            format!("{}{}", cors_proxy, encoded)
+        }
+    }
+}
+
+pub async fn cache_len() -> Result<u32, String> {
+    let store = open_store(IdbTransactionMode::Readonly).await?;
+    let count = request_result(&store.count().map_err(|err| js_error(&err))?).await?;
+    Ok(count.as_f64().unwrap_or_default() as u32)
+}
+
+pub async fn cache_clear() -> Result<(), String> {
+    let store = open_store(IdbTransactionMode::Readwrite).await?;
+    request_result(&store.clear().map_err(|err| js_error(&err))?)
+        .await
+        .map(|_| ())
+}
+
+async fn cache_get(url: &str) -> Result<Option<FetchedResource>, String> {
+    let store = open_store(IdbTransactionMode::Readonly).await?;
+    let record = request_result(
+        &store
+            .get(&JsValue::from_str(url))
+            .map_err(|err| js_error(&err))?,
+    )
+    .await?;
+    if record.is_undefined() || record.is_null() {
+        return Ok(None);
+    }
+    let field = |name: &str| Reflect::get(&record, &JsValue::from_str(name)).ok();
+
+    Ok(field("body")
+        .and_then(|body| body.as_string())
+        .map(|body| FetchedResource {
+            url: url.to_string(),
+            body,
+            from_cache: true,
+            fetched_at: field("fetchedAt")
+                .and_then(|value| value.as_f64())
+                .unwrap_or_default(),
+        }))
+}
+
+async fn cache_put(resource: &FetchedResource) -> Result<(), String> {
+    let record = Object::new();
+    Reflect::set(&record, &"body".into(), &resource.body.as_str().into())
+        .and_then(|_| Reflect::set(&record, &"fetchedAt".into(), &resource.fetched_at.into()))
+        .map_err(|err| js_error(&err))?;
+    let store = open_store(IdbTransactionMode::Readwrite).await?;
+    let request = store
+        .put_with_key(&record, &JsValue::from_str(&resource.url))
+        .map_err(|err| js_error(&err))?;
+    request_result(&request).await.map(|_| ())
+}
+
+async fn open_store(mode: IdbTransactionMode) -> Result<IdbObjectStore, String> {
+    let factory = web_sys::window()
+        .ok_or("No window available")?
+        .indexed_db()
+        .map_err(|err| js_error(&err))?
+        .ok_or("IndexedDB is not available")?;
+    let request = factory
+        .open_with_u32(DB_NAME, DB_VERSION)
+        .map_err(|err| js_error(&err))?;
+    let upgrade_request = request.clone();
+    let on_upgrade = Closure::once_into_js(move |_: web_sys::Event| {
+        if let Ok(db) = upgrade_request
+            .result()
+            .and_then(|db| db.dyn_into::<IdbDatabase>())
+        {
+            if !db.object_store_names().contains(CACHE_STORE) {
+                let _ = db.create_object_store(CACHE_STORE);
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    request_result(&request)
+        .await?
+        .dyn_into::<IdbDatabase>()
+        .map_err(|_| "Failed to open IndexedDB".to_string())?
+        .transaction_with_str_and_mode(CACHE_STORE, mode)
+        .and_then(|transaction| transaction.object_store(CACHE_STORE))
+        .map_err(|err| js_error(&err))
+}
+
+async fn request_result(request: &IdbRequest) -> Result<JsValue, String> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = resolve.call1(
+                &JsValue::NULL,
+                &success_request.result().unwrap_or(JsValue::UNDEFINED),
+            );
+        });
+        let error_request = request.clone();
+        let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+            let error = error_request
+                .error()
+                .ok()
+                .flatten()
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+
+    JsFuture::from(promise).await.map_err(|err| js_error(&err))
+}
+
+pub fn js_error(err: &JsValue) -> String {
+    err.as_string()
+        .or_else(|| {
+            Reflect::get(err, &JsValue::from_str("message"))
+                .ok()
+                .and_then(|message| message.as_string())
+        })
+        .unwrap_or_else(|| format!("{:?}", err))
+}
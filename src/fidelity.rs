@@ -0,0 +1,326 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::{
+    contentline::{self, Component, ContentLine},
+    expected::{escape_pointer, is_id_map, VOLATILE_KEYS, VOLATILE_PROPERTIES},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LossKind {
+    Dropped,
+    // Came back under another name with the same value.
+    Renamed,
+    // Came back without some of its parameters, or for JSON, members.
+    ParameterLost,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Loss {
+    pub kind: LossKind,
+    // Component path and property name, or JSON pointer.
+    pub path: String,
+    // New name of a renamed property, or the lost parameter.
+    pub detail: Option<String>,
+    // Value as written in the original.
+    pub value: String,
+    pub location: Option<String>,
+}
+
+impl LossKind {
+    pub const ALL: [LossKind; 3] = [
+        LossKind::Dropped,
+        LossKind::Renamed,
+        LossKind::ParameterLost,
+    ];
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            LossKind::Dropped => "fidelity-dropped",
+            LossKind::Renamed => "fidelity-renamed",
+            LossKind::ParameterLost => "fidelity-parameter-lost",
+        }
+    }
+}
+
+// Compares an entry against its roundtrip, property by property, and lists
+// what did not survive the trip to the counterpart format and back. Values
+// that changed form are not reported, only data that went missing.
+pub fn report(original: &str, roundtrip: &str) -> Vec<Loss> {
+    let mut losses = Vec::new();
+    let original = original.trim_start();
+    if original.starts_with("BEGIN:") {
+        let roundtrip = contentline::components(roundtrip);
+        let mut dropped_components: Vec<String> = Vec::new();
+        for component in contentline::components(original) {
+            // Children of a dropped component went with it.
+            if dropped_components
+                .iter()
+                .any(|path| component.path.starts_with(&format!("{}/", path)))
+            {
+                continue;
+            }
+            match roundtrip.iter().find(|other| other.path == component.path) {
+                Some(other) => compare_properties(&component, other, &mut losses),
+                None => {
+                    dropped_components.push(component.path.clone());
+                    losses.push(Loss {
+                        kind: LossKind::Dropped,
+                        path: component.path,
+                        detail: None,
+                        value: format!("{} properties", component.properties.len()),
+                        location: Some(format!("line {}", component.line)),
+                    });
+                }
+            }
+        }
+    } else if let (Ok(original), Ok(roundtrip)) = (
+        serde_json::from_str::<Value>(original),
+        serde_json::from_str::<Value>(roundtrip.trim_start()),
+    ) {
+        compare_entry("", &original, &roundtrip, &mut losses);
+    }
+    losses
+}
+
+fn compare_properties(original: &Component, roundtrip: &Component, out: &mut Vec<Loss>) {
+    let original_properties = properties(original);
+    let roundtrip_properties = properties(roundtrip);
+    // Properties under a name the original does not use are rename candidates.
+    let mut renamed = roundtrip_properties
+        .iter()
+        .filter(|property| {
+            !original_properties
+                .iter()
+                .any(|other| other.name == property.name)
+        })
+        .collect::<Vec<_>>();
+
+    let mut names = original_properties
+        .iter()
+        .map(|property| property.name.as_str())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        let path = format!("{}/{}", original.path, name);
+        let pairs = pair(
+            original_properties
+                .iter()
+                .filter(|property| property.name == name),
+            roundtrip_properties
+                .iter()
+                .filter(|property| property.name == name)
+                .collect(),
+        );
+        for (property, other) in pairs {
+            let location = Some(format!("line {}", property.line));
+            match other {
+                Some(other) => {
+                    for (param, value) in &property.params {
+                        if other.param(param).is_none() {
+                            out.push(Loss {
+                                kind: LossKind::ParameterLost,
+                                path: path.clone(),
+                                detail: Some(format!("{}={}", param, value)),
+                                value: property.value.clone(),
+                                location: location.clone(),
+                            });
+                        }
+                    }
+                }
+                None => {
+                    let detail = renamed
+                        .iter()
+                        .position(|other| normalize(&other.value) == normalize(&property.value))
+                        .map(|index| renamed.remove(index).name.clone());
+                    out.push(Loss {
+                        kind: if detail.is_some() {
+                            LossKind::Renamed
+                        } else {
+                            LossKind::Dropped
+                        },
+                        path: path.clone(),
+                        detail,
+                        value: property.value.clone(),
+                        location,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn properties(component: &Component) -> Vec<&ContentLine> {
+    component
+        .properties
+        .iter()
+        .filter(|property| !VOLATILE_PROPERTIES.contains(&property.name.as_str()))
+        .collect()
+}
+
+// Pairs repeated properties such as ATTENDEE or TEL by value first and then
+// in order. Originals left without a counterpart are paired with None.
+fn pair<'x>(
+    original: impl Iterator<Item = &'x &'x ContentLine>,
+    mut roundtrip: Vec<&'x &'x ContentLine>,
+) -> Vec<(&'x ContentLine, Option<&'x ContentLine>)> {
+    let mut pairs = Vec::new();
+    let mut pending = Vec::new();
+    for property in original {
+        match roundtrip
+            .iter()
+            .position(|other| normalize(&other.value) == normalize(&property.value))
+        {
+            Some(index) => pairs.push((*property, Some(*roundtrip.remove(index)))),
+            None => pending.push(*property),
+        }
+    }
+    for property in pending {
+        let other = (!roundtrip.is_empty()).then(|| *roundtrip.remove(0));
+        pairs.push((property, other));
+    }
+    pairs
+}
+
+fn normalize(value: &str) -> String {
+    contentline::unescape(value.trim()).to_lowercase()
+}
+
+// Members of an entry are its properties. Groups hold their entries in an
+// array, each compared the same way.
+fn compare_entry(path: &str, original: &Value, roundtrip: &Value, out: &mut Vec<Loss>) {
+    let (Value::Object(original), Value::Object(roundtrip)) = (original, roundtrip) else {
+        return;
+    };
+    let mut renamed = roundtrip
+        .iter()
+        .filter(|(key, _)| !original.contains_key(*key))
+        .collect::<Vec<_>>();
+    for (key, value) in original {
+        let path = format!("{}/{}", path, escape_pointer(key));
+        match roundtrip.get(key) {
+            Some(Value::Array(entries)) if key == "entries" => {
+                if let Value::Array(original_entries) = value {
+                    for (index, entry) in original_entries.iter().enumerate() {
+                        match entries.get(index) {
+                            Some(other) => {
+                                compare_entry(&format!("{}/{}", path, index), entry, other, out)
+                            }
+                            None => out.push(dropped(format!("{}/{}", path, index), entry)),
+                        }
+                    }
+                }
+            }
+            Some(other) => compare_members(&path, value, other, out),
+            None if VOLATILE_KEYS.contains(&key.as_str()) => {}
+            None => match renamed.iter().position(|(_, other)| *other == value) {
+                Some(index) => out.push(Loss {
+                    kind: LossKind::Renamed,
+                    path,
+                    detail: Some(renamed.remove(index).0.clone()),
+                    value: value.to_string(),
+                    location: None,
+                }),
+                None => out.push(dropped(path, value)),
+            },
+        }
+    }
+}
+
+// Members missing below a property are reported as lost parameters of the
+// object holding them.
+fn compare_members(path: &str, original: &Value, roundtrip: &Value, out: &mut Vec<Loss>) {
+    match (original, roundtrip) {
+        (Value::Object(original), Value::Object(roundtrip))
+            if is_id_map(original) && is_id_map(roundtrip) =>
+        {
+            compare_id_maps(path, original, roundtrip, out);
+        }
+        (Value::Object(original), Value::Object(roundtrip)) => {
+            for (key, value) in original {
+                match roundtrip.get(key) {
+                    Some(other) => compare_members(
+                        &format!("{}/{}", path, escape_pointer(key)),
+                        value,
+                        other,
+                        out,
+                    ),
+                    None => out.push(Loss {
+                        kind: LossKind::ParameterLost,
+                        path: path.to_string(),
+                        detail: Some(key.clone()),
+                        value: value.to_string(),
+                        location: None,
+                    }),
+                }
+            }
+        }
+        (Value::Array(original), Value::Array(roundtrip)) => {
+            for (index, value) in original.iter().enumerate() {
+                let path = format!("{}/{}", path, index);
+                match roundtrip.get(index) {
+                    Some(other) => compare_members(&path, value, other, out),
+                    None => out.push(dropped(path, value)),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// Ids of maps such as participants or alerts may be picked anew on the way
+// back, so entries are matched by id first and then by content.
+fn compare_id_maps(
+    path: &str,
+    original: &Map<String, Value>,
+    roundtrip: &Map<String, Value>,
+    out: &mut Vec<Loss>,
+) {
+    let mut unmatched = roundtrip.keys().collect::<Vec<_>>();
+    let mut pending = Vec::new();
+    for (key, value) in original {
+        match unmatched
+            .iter()
+            .position(|other| *other == key)
+            .or_else(|| {
+                unmatched
+                    .iter()
+                    .position(|other| roundtrip[*other] == *value)
+            }) {
+            Some(index) => {
+                let other = unmatched.remove(index);
+                compare_members(
+                    &format!("{}/{}", path, escape_pointer(key)),
+                    value,
+                    &roundtrip[other],
+                    out,
+                );
+            }
+            None => pending.push((key, value)),
+        }
+    }
+    for (index, (key, value)) in pending.into_iter().enumerate() {
+        let path = format!("{}/{}", path, escape_pointer(key));
+        match unmatched.get(index) {
+            Some(other) => compare_members(&path, value, &roundtrip[*other], out),
+            None => out.push(dropped(path, value)),
+        }
+    }
+}
+
+fn dropped(path: String, value: &Value) -> Loss {
+    Loss {
+        kind: LossKind::Dropped,
+        path,
+        detail: None,
+        value: value.to_string(),
+        location: None,
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::contentline::{self, fold_line};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentKind {
+    Event,
+    Todo,
+    Journal,
+    FreeBusy,
+    TimeZone,
+    Alarm,
+}
+
+impl ComponentKind {
+    pub const ALL: [ComponentKind; 6] = [
+        ComponentKind::Event,
+        ComponentKind::Todo,
+        ComponentKind::Journal,
+        ComponentKind::FreeBusy,
+        ComponentKind::TimeZone,
+        ComponentKind::Alarm,
+    ];
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ComponentKind::Event => "filter-events",
+            ComponentKind::Todo => "filter-todos",
+            ComponentKind::Journal => "filter-journals",
+            ComponentKind::FreeBusy => "filter-freebusy",
+            ComponentKind::TimeZone => "filter-timezones",
+            ComponentKind::Alarm => "filter-alarms",
+        }
+    }
+
+    pub fn icalendar_name(&self) -> &'static str {
+        match self {
+            ComponentKind::Event => "VEVENT",
+            ComponentKind::Todo => "VTODO",
+            ComponentKind::Journal => "VJOURNAL",
+            ComponentKind::FreeBusy => "VFREEBUSY",
+            ComponentKind::TimeZone => "VTIMEZONE",
+            ComponentKind::Alarm => "VALARM",
+        }
+    }
+}
+
+// Drops the excluded component types from an iCalendar or JSCalendar source
+// before it is converted, returning it with the number of components
+// dropped. Anything else is returned unchanged.
+pub fn filter_source(source: &str, excluded: &[ComponentKind]) -> (String, usize) {
+    let trimmed = source.trim_start();
+    if excluded.is_empty() {
+        (source.to_string(), 0)
+    } else if trimmed.starts_with("BEGIN:") {
+        filter_icalendar(source, excluded)
+    } else if trimmed.starts_with(['{', '[']) {
+        filter_jscalendar(source, excluded).unwrap_or_else(|_| (source.to_string(), 0))
+    } else {
+        (source.to_string(), 0)
+    }
+}
+
+pub fn filter_icalendar(source: &str, excluded: &[ComponentKind]) -> (String, usize) {
+    let mut text = String::with_capacity(source.len());
+    let mut removed = 0;
+    let mut depth = 0;
+    // Depth of the excluded component being skipped, with everything inside.
+    let mut skipping: Option<usize> = None;
+    for (number, raw) in contentline::unfold(source) {
+        let line = contentline::parse_line(number, &raw);
+        match line.as_ref().map(|line| line.name.as_str()) {
+            Some("BEGIN") => {
+                depth += 1;
+                let name = line
+                    .as_ref()
+                    .map(|line| line.value.trim().to_ascii_uppercase())
+                    .unwrap_or_default();
+                if skipping.is_none() && excluded.iter().any(|kind| kind.icalendar_name() == name) {
+                    skipping = Some(depth);
+                    removed += 1;
+                }
+            }
+            Some("END") => {
+                if skipping == Some(depth) {
+                    skipping = None;
+                    depth -= 1;
+                    continue;
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+        if skipping.is_none() {
+            fold_line(&mut text, &raw);
+        }
+    }
+    (text, removed)
+}
+
+// JSCalendar has no journals or free/busy objects, so only events, tasks,
+// alerts and time zones are dropped. Arrays of groups are filtered one by one.
+pub fn filter_jscalendar(
+    source: &str,
+    excluded: &[ComponentKind],
+) -> Result<(String, usize), String> {
+    let mut root: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+    let mut removed = 0;
+    match &mut root {
+        Value::Array(groups) => {
+            for group in groups {
+                removed += filter_object(group, excluded);
+            }
+        }
+        object => removed += filter_object(object, excluded),
+    }
+    serde_json::to_string_pretty(&root)
+        .map(|text| (text, removed))
+        .map_err(|err| err.to_string())
+}
+
+fn filter_object(value: &mut Value, excluded: &[ComponentKind]) -> usize {
+    let Value::Object(object) = value else {
+        return 0;
+    };
+    let mut removed = remove_map(object, "timeZones", excluded, ComponentKind::TimeZone);
+    match object.get("@type").and_then(Value::as_str) {
+        Some("Group") => {
+            if let Some(Value::Array(entries)) = object.get_mut("entries") {
+                let count = entries.len();
+                entries.retain(|entry| {
+                    let kind = match entry.get("@type").and_then(Value::as_str) {
+                        Some("Event") => ComponentKind::Event,
+                        Some("Task") => ComponentKind::Todo,
+                        _ => return true,
+                    };
+                    !excluded.contains(&kind)
+                });
+                removed += count - entries.len();
+                for entry in entries {
+                    removed += filter_object(entry, excluded);
+                }
+            }
+        }
+        Some("Event" | "Task") => {
+            removed += remove_map(object, "alerts", excluded, ComponentKind::Alarm);
+            // Overrides may patch alerts in or out.
+            if excluded.contains(&ComponentKind::Alarm) {
+                if let Some(Value::Object(overrides)) = object.get_mut("recurrenceOverrides") {
+                    for patch in overrides.values_mut() {
+                        if let Value::Object(patch) = patch {
+                            patch.retain(|key, _| key.split('/').next() != Some("alerts"));
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    removed
+}
+
+fn remove_map(
+    object: &mut Map<String, Value>,
+    key: &str,
+    excluded: &[ComponentKind],
+    kind: ComponentKind,
+) -> usize {
+    if !excluded.contains(&kind) {
+        return 0;
+    }
+    match object.remove(key) {
+        Some(Value::Object(map)) => map.len(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_unmatched_end() {
+        let (text, removed) = filter_icalendar(
+            "END:VCALENDAR\r\nBEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            &[ComponentKind::Alarm],
+        );
+        assert_eq!(removed, 1);
+        assert_eq!(
+            text,
+            "END:VCALENDAR\r\nBEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+    }
+}
@@ -0,0 +1,393 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::fmt::Display;
+
+use serde_json::{json, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+// Codes are stable identifiers: never renumber or reuse them, only append.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Code {
+    InvalidLine,
+    UnexpectedComponentEnd,
+    UnterminatedComponent,
+    TooManyComponents,
+    UnexpectedEof,
+    UnrecognizedFormat,
+    InvalidJSCalendar,
+    InvalidJSContact,
+    UnknownJsonType,
+    RoundtripFailed,
+    OccurrenceOnHoliday,
+    OccurrenceOutsideWorkingHours,
+    NoOccurrences,
+    ExpansionLimitReached,
+    InvalidPatchPointer,
+    ImmutablePatch,
+    ArrayPatch,
+    OverlappingPatch,
+    MissingPatchParent,
+    PhoneticDataDropped,
+    IdentityDataDropped,
+    VendorIdentityUnmapped,
+    ColorNotPreserved,
+    MonthEndRecurrence,
+    UnknownStatus,
+    VendorLocationExtension,
+    VendorExtensionStripped,
+    PathologicalRecurrence,
+    ExpansionBudgetExceeded,
+    MissingRequiredProperty,
+    PropertyCardinality,
+    InvalidValue,
+    UnknownParameter,
+    InvalidRecurrenceRule,
+    InvalidJCal,
+    StrictParsingViolation,
+    MergeUidCollision,
+    MergeTimezoneConflict,
+    LegacyEncodingDecoded,
+    PhoneNumberNormalized,
+    InvalidPhoneNumber,
+    EmailAddressNormalized,
+    InvalidEmailAddress,
+    UndefinedTimezone,
+    UnsupportedRecurrence,
+    UnsupportedComponent,
+    UnsupportedProperty,
+    UnsupportedVCardVersion,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Finding {
+    pub code: Code,
+    pub message: String,
+    // Line number, JSON pointer or occurrence the finding refers to.
+    pub location: Option<String>,
+}
+
+pub const CODES: &[Code] = &[
+    Code::InvalidLine,
+    Code::UnexpectedComponentEnd,
+    Code::UnterminatedComponent,
+    Code::TooManyComponents,
+    Code::UnexpectedEof,
+    Code::UnrecognizedFormat,
+    Code::InvalidJSCalendar,
+    Code::InvalidJSContact,
+    Code::UnknownJsonType,
+    Code::RoundtripFailed,
+    Code::OccurrenceOnHoliday,
+    Code::OccurrenceOutsideWorkingHours,
+    Code::NoOccurrences,
+    Code::ExpansionLimitReached,
+    Code::InvalidPatchPointer,
+    Code::ImmutablePatch,
+    Code::ArrayPatch,
+    Code::OverlappingPatch,
+    Code::MissingPatchParent,
+    Code::PhoneticDataDropped,
+    Code::IdentityDataDropped,
+    Code::VendorIdentityUnmapped,
+    Code::ColorNotPreserved,
+    Code::MonthEndRecurrence,
+    Code::UnknownStatus,
+    Code::VendorLocationExtension,
+    Code::VendorExtensionStripped,
+    Code::PathologicalRecurrence,
+    Code::ExpansionBudgetExceeded,
+    Code::MissingRequiredProperty,
+    Code::PropertyCardinality,
+    Code::InvalidValue,
+    Code::UnknownParameter,
+    Code::InvalidRecurrenceRule,
+    Code::InvalidJCal,
+    Code::StrictParsingViolation,
+    Code::MergeUidCollision,
+    Code::MergeTimezoneConflict,
+    Code::LegacyEncodingDecoded,
+    Code::PhoneNumberNormalized,
+    Code::InvalidPhoneNumber,
+    Code::EmailAddressNormalized,
+    Code::InvalidEmailAddress,
+    Code::UndefinedTimezone,
+    Code::UnsupportedRecurrence,
+    Code::UnsupportedComponent,
+    Code::UnsupportedProperty,
+    Code::UnsupportedVCardVersion,
+];
+
+impl Code {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Code::InvalidLine => "PAR-001",
+            Code::UnexpectedComponentEnd => "PAR-002",
+            Code::UnterminatedComponent => "PAR-003",
+            Code::TooManyComponents => "PAR-004",
+            Code::UnexpectedEof => "PAR-005",
+            Code::UnrecognizedFormat => "PAR-006",
+            Code::InvalidJSCalendar => "PAR-007",
+            Code::InvalidJSContact => "PAR-008",
+            Code::UnknownJsonType => "PAR-009",
+            Code::RoundtripFailed => "CNV-001",
+            Code::OccurrenceOnHoliday => "CAL-001",
+            Code::OccurrenceOutsideWorkingHours => "CAL-002",
+            Code::NoOccurrences => "CAL-003",
+            Code::ExpansionLimitReached => "CAL-004",
+            Code::InvalidPatchPointer => "PAT-001",
+            Code::ImmutablePatch => "PAT-002",
+            Code::ArrayPatch => "PAT-003",
+            Code::OverlappingPatch => "PAT-004",
+            Code::MissingPatchParent => "PAT-005",
+            Code::PhoneticDataDropped => "CNV-002",
+            Code::IdentityDataDropped => "CNV-003",
+            Code::VendorIdentityUnmapped => "CNV-004",
+            Code::ColorNotPreserved => "CNV-005",
+            Code::MonthEndRecurrence => "CAL-005",
+            Code::UnknownStatus => "CAL-006",
+            Code::VendorLocationExtension => "CNV-006",
+            Code::VendorExtensionStripped => "CNV-007",
+            Code::PathologicalRecurrence => "CAL-007",
+            Code::ExpansionBudgetExceeded => "CAL-008",
+            Code::MissingRequiredProperty => "VAL-001",
+            Code::PropertyCardinality => "VAL-002",
+            Code::InvalidValue => "VAL-003",
+            Code::UnknownParameter => "VAL-004",
+            Code::InvalidRecurrenceRule => "VAL-005",
+            Code::InvalidJCal => "PAR-011",
+            Code::StrictParsingViolation => "PAR-012",
+            Code::MergeUidCollision => "MRG-001",
+            Code::MergeTimezoneConflict => "MRG-002",
+            Code::LegacyEncodingDecoded => "PAR-013",
+            Code::PhoneNumberNormalized => "CNT-001",
+            Code::InvalidPhoneNumber => "CNT-002",
+            Code::EmailAddressNormalized => "CNT-003",
+            Code::InvalidEmailAddress => "CNT-004",
+            Code::UndefinedTimezone => "CMP-001",
+            Code::UnsupportedRecurrence => "CMP-002",
+            Code::UnsupportedComponent => "CMP-003",
+            Code::UnsupportedProperty => "CMP-004",
+            Code::UnsupportedVCardVersion => "CMP-005",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim();
+        CODES
+            .iter()
+            .copied()
+            .find(|item| item.as_str().eq_ignore_ascii_case(code))
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Code::InvalidLine
+            | Code::UnexpectedComponentEnd
+            | Code::UnterminatedComponent
+            | Code::TooManyComponents
+            | Code::UnexpectedEof
+            | Code::UnrecognizedFormat
+            | Code::InvalidJSCalendar
+            | Code::InvalidJSContact
+            | Code::UnknownJsonType
+            | Code::RoundtripFailed
+            | Code::MissingRequiredProperty
+            | Code::InvalidValue
+            | Code::InvalidRecurrenceRule
+            | Code::InvalidJCal
+            | Code::StrictParsingViolation => Severity::Error,
+            Code::NoOccurrences
+            | Code::InvalidPatchPointer
+            | Code::ImmutablePatch
+            | Code::ArrayPatch
+            | Code::OverlappingPatch
+            | Code::MissingPatchParent
+            | Code::PhoneticDataDropped
+            | Code::IdentityDataDropped
+            | Code::VendorIdentityUnmapped
+            | Code::ColorNotPreserved
+            | Code::UnknownStatus
+            | Code::VendorLocationExtension
+            | Code::PathologicalRecurrence
+            | Code::ExpansionBudgetExceeded
+            | Code::PropertyCardinality
+            | Code::UnknownParameter
+            | Code::MergeUidCollision
+            | Code::MergeTimezoneConflict
+            | Code::InvalidPhoneNumber
+            | Code::InvalidEmailAddress
+            | Code::UndefinedTimezone
+            | Code::UnsupportedRecurrence
+            | Code::UnsupportedComponent
+            | Code::UnsupportedProperty
+            | Code::UnsupportedVCardVersion => Severity::Warning,
+            Code::OccurrenceOnHoliday
+            | Code::OccurrenceOutsideWorkingHours
+            | Code::ExpansionLimitReached
+            | Code::MonthEndRecurrence
+            | Code::VendorExtensionStripped
+            | Code::LegacyEncodingDecoded
+            | Code::PhoneNumberNormalized
+            | Code::EmailAddressNormalized => Severity::Info,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Code::InvalidLine => "Invalid line",
+            Code::UnexpectedComponentEnd => "Unexpected component end",
+            Code::UnterminatedComponent => "Unterminated component",
+            Code::TooManyComponents => "Too many components",
+            Code::UnexpectedEof => "Unexpected end of file",
+            Code::UnrecognizedFormat => "Unrecognized format",
+            Code::InvalidJSCalendar => "Invalid JSCalendar",
+            Code::InvalidJSContact => "Invalid JSContact",
+            Code::UnknownJsonType => "Unknown JSON object type",
+            Code::RoundtripFailed => "Round trip failed",
+            Code::OccurrenceOnHoliday => "Occurrence on a holiday",
+            Code::OccurrenceOutsideWorkingHours => "Occurrence outside working hours",
+            Code::NoOccurrences => "Recurring data produced no occurrences",
+            Code::ExpansionLimitReached => "Expansion limit reached",
+            Code::InvalidPatchPointer => "Invalid patch pointer",
+            Code::ImmutablePatch => "Patch to an immutable property",
+            Code::ArrayPatch => "Patch inside an array",
+            Code::OverlappingPatch => "Overlapping patch pointers",
+            Code::MissingPatchParent => "Patch parent does not exist",
+            Code::PhoneticDataDropped => "Phonetic data dropped",
+            Code::IdentityDataDropped => "Gender or pronoun data dropped",
+            Code::VendorIdentityUnmapped => "Vendor pronoun property not representable",
+            Code::ColorNotPreserved => "Color not preserved",
+            Code::MonthEndRecurrence => "Recurrence anchored near the end of the month",
+            Code::UnknownStatus => "Unknown status value",
+            Code::VendorLocationExtension => "Location data kept as a vendor extension",
+            Code::VendorExtensionStripped => "Vendor extension stripped",
+            Code::PathologicalRecurrence => "Recurrence rule likely to be slow to expand",
+            Code::ExpansionBudgetExceeded => "Expansion stopped by its time budget",
+            Code::MissingRequiredProperty => "Required property missing",
+            Code::PropertyCardinality => "Property occurs more often than allowed",
+            Code::InvalidValue => "Value does not match its type",
+            Code::UnknownParameter => "Unknown parameter",
+            Code::InvalidRecurrenceRule => "Invalid recurrence rule",
+            Code::InvalidJCal => "Invalid jCal or jCard",
+            Code::StrictParsingViolation => "Malformed line refused by strict parsing",
+            Code::MergeUidCollision => "UID used by more than one merged entry",
+            Code::MergeTimezoneConflict => "Merged files define a time zone differently",
+            Code::LegacyEncodingDecoded => "Legacy encoding decoded",
+            Code::PhoneNumberNormalized => "Phone number rewritten as E.164",
+            Code::InvalidPhoneNumber => "Phone number not normalized",
+            Code::EmailAddressNormalized => "Email address cleaned",
+            Code::InvalidEmailAddress => "Invalid email address",
+            Code::UndefinedTimezone => "Time zone the client cannot resolve",
+            Code::UnsupportedRecurrence => "Recurrence the client does not support",
+            Code::UnsupportedComponent => "Component the client ignores",
+            Code::UnsupportedProperty => "Property the client ignores",
+            Code::UnsupportedVCardVersion => "vCard version the client cannot import",
+        }
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Severity::Info => "severity-info",
+            Severity::Warning => "severity-warning",
+            Severity::Error => "severity-error",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "info" => Some(Severity::Info),
+            "warning" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+impl Finding {
+    pub fn new(code: Code, message: impl Into<String>) -> Self {
+        Finding {
+            code,
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.code.severity()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "code": self.code.as_str(),
+            "severity": self.severity().as_str(),
+            "message": self.message,
+            "location": self.location,
+        })
+    }
+}
+
+// Filter applied to the findings list, `suppressed` holds user-silenced codes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FindingFilter {
+    pub min_severity: Option<Severity>,
+    pub code_query: String,
+    pub suppressed: Vec<Code>,
+}
+
+impl FindingFilter {
+    pub fn matches(&self, finding: &Finding) -> bool {
+        let query = self.code_query.trim();
+        self.min_severity
+            .is_none_or(|severity| finding.severity() >= severity)
+            && (query.is_empty()
+                || finding
+                    .code
+                    .as_str()
+                    .to_ascii_lowercase()
+                    .starts_with(&query.to_ascii_lowercase()))
+            && (finding.severity() == Severity::Error || !self.suppressed.contains(&finding.code))
+    }
+}
+
+pub fn parse_code_list(value: &str) -> Vec<Code> {
+    value.split(',').filter_map(Code::parse).collect()
+}
+
+pub fn format_code_list(codes: &[Code]) -> String {
+    codes
+        .iter()
+        .map(|code| code.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
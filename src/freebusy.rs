@@ -0,0 +1,453 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// VFREEBUSY and VAVAILABILITY components, which have no JSCalendar entry of
+// their own. Free/busy periods are written as the BusyPeriod objects of JMAP
+// Calendars availability, and availability as a JSCalendar style object with
+// its AVAILABLE slots.
+
+use chrono::NaiveDateTime;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    contentline::{self, Component, ContentLine},
+    preview::parse_duration,
+    rrule_text,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockKind {
+    FreeBusy,
+    Availability,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BusyStatus {
+    Free,
+    Busy,
+    Tentative,
+    Unavailable,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interval {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub status: BusyStatus,
+    pub title: Option<String>,
+    // "UTC", a TZID, or none for floating time.
+    pub time_zone: Option<String>,
+    // The RRULE of an AVAILABLE slot in words.
+    pub recurrence: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub line: usize,
+    pub uid: Option<String>,
+    // SUMMARY, or the ORGANIZER of free/busy.
+    pub title: Option<String>,
+    pub start: Option<NaiveDateTime>,
+    pub end: Option<NaiveDateTime>,
+    pub time_zone: Option<String>,
+    // BUSYTYPE of availability, the status outside of its slots.
+    pub status: BusyStatus,
+    pub intervals: Vec<Interval>,
+    pub jscalendar: Value,
+}
+
+impl BlockKind {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            BlockKind::FreeBusy => "freebusy-kind-freebusy",
+            BlockKind::Availability => "freebusy-kind-availability",
+        }
+    }
+}
+
+impl BusyStatus {
+    // FBTYPE and BUSYTYPE values, BUSY when missing.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "FREE" => BusyStatus::Free,
+            "BUSY-TENTATIVE" => BusyStatus::Tentative,
+            "BUSY-UNAVAILABLE" => BusyStatus::Unavailable,
+            _ => BusyStatus::Busy,
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            BusyStatus::Free => "freebusy-free",
+            BusyStatus::Busy => "freebusy-busy",
+            BusyStatus::Tentative => "freebusy-tentative",
+            BusyStatus::Unavailable => "freebusy-unavailable",
+        }
+    }
+
+    // The busyStatus of a JMAP BusyPeriod.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BusyStatus::Free => "free",
+            BusyStatus::Busy => "confirmed",
+            BusyStatus::Tentative => "tentative",
+            BusyStatus::Unavailable => "unavailable",
+        }
+    }
+}
+
+impl Block {
+    // The span the timeline covers: the component's own range, widened to
+    // its intervals.
+    pub fn range(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let starts = self
+            .start
+            .into_iter()
+            .chain(self.intervals.iter().map(|i| i.start));
+        let ends = self
+            .end
+            .into_iter()
+            .chain(self.intervals.iter().map(|i| i.end));
+        Some((starts.min()?, ends.max()?)).filter(|(start, end)| start < end)
+    }
+}
+
+pub fn blocks(icalendar: &str) -> Vec<Block> {
+    let components = contentline::components(icalendar);
+    components
+        .iter()
+        .filter_map(|component| match component.name() {
+            "VFREEBUSY" => Some(free_busy(component)),
+            "VAVAILABILITY" => Some(availability(component, &components)),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn to_jscalendar(blocks: &[Block]) -> String {
+    serde_json::to_string_pretty(&Value::Array(
+        blocks
+            .iter()
+            .map(|block| block.jscalendar.clone())
+            .collect(),
+    ))
+    .unwrap_or_default()
+}
+
+fn free_busy(component: &Component) -> Block {
+    let (start, time_zone) = date_time(property(component, "DTSTART"));
+    let (end, _) = date_time(property(component, "DTEND"));
+    let mut intervals = Vec::new();
+    for line in component
+        .properties
+        .iter()
+        .filter(|line| line.name == "FREEBUSY")
+    {
+        let status = BusyStatus::parse(line.param("FBTYPE").unwrap_or("BUSY"));
+        for period in line.value.split(',') {
+            if let Some((start, end)) = parse_period(period) {
+                intervals.push(Interval {
+                    start,
+                    end,
+                    status,
+                    title: None,
+                    time_zone: Some("UTC".to_string()),
+                    recurrence: None,
+                });
+            }
+        }
+    }
+    intervals.sort_by_key(|interval| interval.start);
+
+    let busy = intervals
+        .iter()
+        .filter(|interval| interval.status != BusyStatus::Free)
+        .map(|interval| {
+            json!({
+                "@type": "BusyPeriod",
+                "utcStart": utc_date_time(interval.start),
+                "utcEnd": utc_date_time(interval.end),
+                "busyStatus": interval.status.as_str(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut object = Map::new();
+    object.insert("@type".into(), "FreeBusy".into());
+    insert_text(&mut object, "uid", text(component, "UID"));
+    insert_text(&mut object, "organizer", text(component, "ORGANIZER"));
+    insert_text(&mut object, "utcStart", start.map(utc_date_time));
+    insert_text(&mut object, "utcEnd", end.map(utc_date_time));
+    object.insert("busyPeriods".into(), Value::Array(busy));
+
+    Block {
+        kind: BlockKind::FreeBusy,
+        line: component.line,
+        uid: text(component, "UID"),
+        title: text(component, "ORGANIZER"),
+        start,
+        end,
+        time_zone,
+        status: BusyStatus::Busy,
+        intervals,
+        jscalendar: Value::Object(object),
+    }
+}
+
+fn availability(component: &Component, components: &[Component]) -> Block {
+    let (start, time_zone) = date_time(property(component, "DTSTART"));
+    let end = end_of(component, start);
+    let status = BusyStatus::parse(
+        text(component, "BUSYTYPE")
+            .as_deref()
+            .unwrap_or("BUSY-UNAVAILABLE"),
+    );
+
+    let mut intervals = Vec::new();
+    let mut available = Vec::new();
+    for slot in components.iter().filter(|other| {
+        other
+            .path
+            .strip_prefix(component.path.as_str())
+            .is_some_and(|rest| rest.starts_with("/AVAILABLE["))
+    }) {
+        let (slot_start, slot_zone) = date_time(property(slot, "DTSTART"));
+        let Some(slot_start) = slot_start else {
+            continue;
+        };
+        let slot_end = end_of(slot, Some(slot_start)).unwrap_or(slot_start);
+        let rrule = text(slot, "RRULE");
+        intervals.push(Interval {
+            start: slot_start,
+            end: slot_end,
+            status: BusyStatus::Free,
+            title: text(slot, "SUMMARY"),
+            time_zone: slot_zone.clone(),
+            recurrence: rrule
+                .as_deref()
+                .map(|rrule| rrule_text::explain_rrule(rrule, Some(slot_start))),
+        });
+
+        let mut object = Map::new();
+        object.insert("@type".into(), "AvailableTime".into());
+        insert_text(&mut object, "uid", text(slot, "UID"));
+        insert_text(&mut object, "title", text(slot, "SUMMARY"));
+        object.insert("start".into(), local_date_time(slot_start).into());
+        insert_text(&mut object, "timeZone", slot_zone.map(json_time_zone));
+        object.insert("duration".into(), duration(slot_end - slot_start).into());
+        if let Some(rrule) = rrule {
+            object.insert("recurrenceRules".into(), json!([recurrence_rule(&rrule)]));
+        }
+        available.push(Value::Object(object));
+    }
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut object = Map::new();
+    object.insert("@type".into(), "Availability".into());
+    insert_text(&mut object, "uid", text(component, "UID"));
+    insert_text(&mut object, "title", text(component, "SUMMARY"));
+    insert_text(&mut object, "start", start.map(local_date_time));
+    insert_text(
+        &mut object,
+        "timeZone",
+        time_zone.clone().map(json_time_zone),
+    );
+    if let (Some(start), Some(end)) = (start, end) {
+        object.insert("duration".into(), duration(end - start).into());
+    }
+    object.insert("busyStatus".into(), status.as_str().into());
+    if let Some(priority) = text(component, "PRIORITY").and_then(|value| value.parse::<u64>().ok())
+    {
+        object.insert("priority".into(), priority.into());
+    }
+    object.insert("available".into(), Value::Array(available));
+
+    Block {
+        kind: BlockKind::Availability,
+        line: component.line,
+        uid: text(component, "UID"),
+        title: text(component, "SUMMARY"),
+        start,
+        end,
+        time_zone,
+        status,
+        intervals,
+        jscalendar: Value::Object(object),
+    }
+}
+
+// DTEND, or DTSTART plus DURATION.
+fn end_of(component: &Component, start: Option<NaiveDateTime>) -> Option<NaiveDateTime> {
+    date_time(property(component, "DTEND")).0.or_else(|| {
+        Some(start? + parse_duration(text(component, "DURATION")?.trim_start_matches('+'))?)
+    })
+}
+
+// "19970308T160000Z/19970308T170000Z" or "19970308T160000Z/PT8H30M".
+fn parse_period(value: &str) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let (start, end) = value.trim().split_once('/')?;
+    let start = contentline::parse_date_or_datetime(start)?;
+    let end = match end.strip_prefix('+').unwrap_or(end) {
+        duration if duration.starts_with('P') => start + parse_duration(duration)?,
+        end => contentline::parse_date_or_datetime(end)?,
+    };
+    Some((start, end))
+}
+
+// The value of a date-time property with its time zone.
+fn date_time(line: Option<&ContentLine>) -> (Option<NaiveDateTime>, Option<String>) {
+    let Some(line) = line else {
+        return (None, None);
+    };
+    let time_zone = if line.value.trim().ends_with('Z') {
+        Some("UTC".to_string())
+    } else {
+        line.param("TZID").map(str::to_string)
+    };
+    (contentline::parse_date_or_datetime(&line.value), time_zone)
+}
+
+// RRULE parts as a JSCalendar RecurrenceRule.
+fn recurrence_rule(rrule: &str) -> Value {
+    let mut rule = Map::new();
+    rule.insert("@type".into(), "RecurrenceRule".into());
+    let numbers = |value: &str| {
+        value
+            .split(',')
+            .filter_map(|number| number.trim().trim_start_matches('+').parse::<i64>().ok())
+            .collect::<Vec<_>>()
+    };
+    for part in rrule.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let (key, value) = match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => ("frequency", value.to_ascii_lowercase().into()),
+            "INTERVAL" => ("interval", value.parse::<u64>().unwrap_or(1).into()),
+            "COUNT" => ("count", value.parse::<u64>().unwrap_or_default().into()),
+            "UNTIL" => match contentline::parse_date_or_datetime(value) {
+                Some(until) => ("until", local_date_time(until).into()),
+                None => continue,
+            },
+            "WKST" => ("firstDayOfWeek", value.to_ascii_lowercase().into()),
+            "BYDAY" => (
+                "byDay",
+                value
+                    .split(',')
+                    .filter_map(|day| {
+                        let (nth, name) = contentline::parse_byday(day)?;
+                        let mut object = Map::new();
+                        object.insert("@type".into(), "NDay".into());
+                        object.insert("day".into(), name.to_ascii_lowercase().into());
+                        if let Some(nth) = nth {
+                            object.insert("nthOfPeriod".into(), nth.into());
+                        }
+                        Some(Value::Object(object))
+                    })
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            "BYMONTH" => (
+                "byMonth",
+                value
+                    .split(',')
+                    .map(|month| month.trim().to_string())
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            "BYMONTHDAY" => ("byMonthDay", numbers(value).into()),
+            "BYYEARDAY" => ("byYearDay", numbers(value).into()),
+            "BYWEEKNO" => ("byWeekNo", numbers(value).into()),
+            "BYHOUR" => ("byHour", numbers(value).into()),
+            "BYMINUTE" => ("byMinute", numbers(value).into()),
+            "BYSECOND" => ("bySecond", numbers(value).into()),
+            "BYSETPOS" => ("bySetPosition", numbers(value).into()),
+            _ => continue,
+        };
+        rule.insert(key.into(), value);
+    }
+    Value::Object(rule)
+}
+
+fn duration(duration: chrono::Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+    let mut text = String::from("P");
+    if days > 0 {
+        text.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 {
+        text.push('T');
+        for (count, unit) in [(hours, 'H'), (minutes, 'M'), (seconds, 'S')] {
+            if count > 0 {
+                text.push_str(&format!("{}{}", count, unit));
+            }
+        }
+    }
+    if text.len() == 1 {
+        text.push_str("T0S");
+    }
+    text
+}
+
+fn utc_date_time(date_time: NaiveDateTime) -> String {
+    date_time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn local_date_time(date_time: NaiveDateTime) -> String {
+    date_time.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+fn json_time_zone(zone: String) -> String {
+    if zone == "UTC" {
+        "Etc/UTC".to_string()
+    } else {
+        zone
+    }
+}
+
+fn insert_text(object: &mut Map<String, Value>, key: &str, value: Option<String>) {
+    if let Some(value) = value {
+        object.insert(key.to_string(), value.into());
+    }
+}
+
+fn property<'x>(component: &'x Component, name: &str) -> Option<&'x ContentLine> {
+    component.properties.iter().find(|line| line.name == name)
+}
+
+fn text(component: &Component, name: &str) -> Option<String> {
+    property(component, name)
+        .map(ContentLine::text)
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_non_ascii_byday() {
+        let rule = recurrence_rule("FREQ=MONTHLY;BYDAY=2S€U,ÖA,-1MO,FR");
+        assert_eq!(
+            rule["byDay"],
+            json!([
+                {"@type": "NDay", "day": "mo", "nthOfPeriod": -1},
+                {"@type": "NDay", "day": "fr"}
+            ])
+        );
+        let blocks = blocks(
+            "BEGIN:VCALENDAR\r\nBEGIN:VAVAILABILITY\r\nDTSTART:20240101T000000Z\r\nBEGIN:AVAILABLE\r\nDTSTART:20240101T090000Z\r\nDTEND:20240101T170000Z\r\nRRULE:FREQ=WEEKLY;BYDAY=2S€U\r\nEND:AVAILABLE\r\nEND:VAVAILABILITY\r\nEND:VCALENDAR\r\n",
+        );
+        assert_eq!(blocks.len(), 1);
+    }
+}
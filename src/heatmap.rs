@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{Datelike, Duration, DurationRound, NaiveDateTime, Timelike};
+
+const HOURS_PER_WEEK: usize = 7 * 24;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeeklyHeatmap {
+    // Occurrences overlapping each hour, indexed by
+    // `Weekday::num_days_from_monday` and then by hour of the day.
+    pub cells: [[usize; 24]; 7],
+    pub max: usize,
+}
+
+impl WeeklyHeatmap {
+    // Spans are (start, end) pairs in the local time of each occurrence. An
+    // occurrence counts once in every hour it overlaps, and instants in the
+    // hour they fall in.
+    pub fn compute(spans: &[(NaiveDateTime, NaiveDateTime)]) -> Option<Self> {
+        if spans.is_empty() {
+            return None;
+        }
+        let mut heatmap = WeeklyHeatmap {
+            cells: [[0; 24]; 7],
+            max: 0,
+        };
+        for (start, end) in spans {
+            let Ok(mut hour) = start.duration_trunc(Duration::hours(1)) else {
+                continue;
+            };
+            // A week of hours already covers every cell once.
+            for _ in 0..HOURS_PER_WEEK {
+                let cell = &mut heatmap.cells[hour.weekday().num_days_from_monday() as usize]
+                    [hour.hour() as usize];
+                *cell += 1;
+                heatmap.max = heatmap.max.max(*cell);
+                hour += Duration::hours(1);
+                if hour >= *end {
+                    break;
+                }
+            }
+        }
+        Some(heatmap)
+    }
+
+    // Weekday and hour with the most occurrences, the earliest on ties.
+    pub fn busiest(&self) -> (usize, usize) {
+        (0..HOURS_PER_WEEK)
+            .map(|index| (index / 24, index % 24))
+            .max_by(|a, b| {
+                self.cells[a.0][a.1]
+                    .cmp(&self.cells[b.0][b.1])
+                    .then(b.cmp(a))
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,195 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Splits iCalendar, vCard and JSON text into tokens for syntax highlighting.
+// The tokens cover the whole text, in order, so joining them gives it back.
+
+// Larger documents are shown plain, as thousands of spans slow the page down.
+pub const MAX_LENGTH: usize = 128 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    // Separators such as ":", ";", "=" and JSON brackets.
+    Punctuation,
+    // Content lines.
+    Property,
+    Component,
+    Parameter,
+    ParameterValue,
+    // JSON.
+    Key,
+    String,
+    Number,
+    Literal,
+}
+
+// The tokens of `text`, as plain text when it is neither JSON nor content
+// lines or is longer than MAX_LENGTH.
+pub fn highlight(text: &str) -> Vec<(TokenKind, &str)> {
+    let trimmed = text.trim_start();
+    let mut tokens = Tokens {
+        text,
+        ranges: Vec::new(),
+    };
+    if text.len() > MAX_LENGTH {
+        tokens.push(TokenKind::Plain, 0, text.len());
+    } else if trimmed.starts_with(['{', '[']) {
+        json(&mut tokens);
+    } else if trimmed
+        .get(..6)
+        .is_some_and(|begin| begin.eq_ignore_ascii_case("BEGIN:"))
+    {
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            content_line(&mut tokens, start, start + line.len());
+            start += line.len();
+        }
+    } else {
+        tokens.push(TokenKind::Plain, 0, text.len());
+    }
+    tokens.into_tokens()
+}
+
+// Byte ranges of the text, with runs of the same kind joined to keep the
+// number of spans down.
+struct Tokens<'x> {
+    text: &'x str,
+    ranges: Vec<(TokenKind, usize, usize)>,
+}
+
+impl<'x> Tokens<'x> {
+    fn push(&mut self, kind: TokenKind, start: usize, end: usize) {
+        if start == end {
+            return;
+        }
+        match self.ranges.last_mut() {
+            Some((last_kind, _, last_end)) if *last_kind == kind && *last_end == start => {
+                *last_end = end;
+            }
+            _ => self.ranges.push((kind, start, end)),
+        }
+    }
+
+    fn into_tokens(self) -> Vec<(TokenKind, &'x str)> {
+        self.ranges
+            .into_iter()
+            .map(|(kind, start, end)| (kind, &self.text[start..end]))
+            .collect()
+    }
+}
+
+fn content_line(tokens: &mut Tokens, start: usize, end: usize) {
+    let text = tokens.text;
+    let bytes = text.as_bytes();
+    // Folded continuations belong to the value of the line before.
+    if matches!(bytes[start], b' ' | b'\t') {
+        tokens.push(TokenKind::Plain, start, end);
+        return;
+    }
+    let name_end = text[start..end]
+        .find([';', ':'])
+        .map_or(end, |pos| start + pos);
+    tokens.push(TokenKind::Property, start, name_end);
+
+    let mut pos = name_end;
+    let mut quoted = false;
+    // Parameters up to the first unquoted ":".
+    while pos < end && bytes[pos] == b';' {
+        tokens.push(TokenKind::Punctuation, pos, pos + 1);
+        pos += 1;
+        let param_end = text[pos..end]
+            .find(['=', ';', ':'])
+            .map_or(end, |found| pos + found);
+        tokens.push(TokenKind::Parameter, pos, param_end);
+        pos = param_end;
+        if pos < end && bytes[pos] == b'=' {
+            tokens.push(TokenKind::Punctuation, pos, pos + 1);
+            pos += 1;
+            let value_start = pos;
+            while pos < end {
+                match bytes[pos] {
+                    b'"' => quoted = !quoted,
+                    b';' | b':' if !quoted => break,
+                    _ => {}
+                }
+                pos += 1;
+            }
+            tokens.push(TokenKind::ParameterValue, value_start, pos);
+        }
+    }
+    if pos < end && bytes[pos] == b':' {
+        tokens.push(TokenKind::Punctuation, pos, pos + 1);
+        pos += 1;
+    }
+    let name = &text[start..name_end];
+    let kind = if name.eq_ignore_ascii_case("BEGIN") || name.eq_ignore_ascii_case("END") {
+        TokenKind::Component
+    } else {
+        TokenKind::Plain
+    };
+    let value_end = pos + text[pos..end].trim_end_matches(['\r', '\n']).len();
+    tokens.push(kind, pos, value_end);
+    tokens.push(TokenKind::Plain, value_end, end);
+}
+
+fn json(tokens: &mut Tokens) {
+    let text = tokens.text;
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let kind = match bytes[pos] {
+            b'"' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != b'"' {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+                pos = (pos + 1).min(bytes.len());
+                // A string followed by ":" is a key.
+                if text[pos..].trim_start().starts_with(':') {
+                    TokenKind::Key
+                } else {
+                    TokenKind::String
+                }
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => {
+                pos += 1;
+                TokenKind::Punctuation
+            }
+            b'-' | b'0'..=b'9' => {
+                while pos < bytes.len()
+                    && matches!(bytes[pos], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                {
+                    pos += 1;
+                }
+                TokenKind::Number
+            }
+            b't' | b'f' | b'n' => {
+                while pos < bytes.len() && bytes[pos].is_ascii_alphabetic() {
+                    pos += 1;
+                }
+                TokenKind::Literal
+            }
+            _ => {
+                // Whitespace and anything malformed, up to the next token.
+                pos += 1;
+                while pos < bytes.len() && !is_json_token_start(bytes[pos]) {
+                    pos += 1;
+                }
+                TokenKind::Plain
+            }
+        };
+        tokens.push(kind, start, pos);
+    }
+}
+
+fn is_json_token_start(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'"' | b'{' | b'}' | b'[' | b']' | b':' | b',' | b'-' | b'0'..=b'9' | b't' | b'f' | b'n'
+    )
+}
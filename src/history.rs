@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Bounded undo/redo stacks for values replaced as a whole, such as the
+// source editor contents.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    limit: usize,
+}
+
+impl<T: PartialEq> History<T> {
+    pub fn new(limit: usize) -> Self {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    // Records the value being replaced by `current`, discarding the redo stack.
+    pub fn record(&mut self, previous: T, current: &T) {
+        if previous == *current {
+            return;
+        }
+        if self.undo.len() >= self.limit {
+            self.undo.remove(0);
+        }
+        self.undo.push(previous);
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
@@ -0,0 +1,311 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "div",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+// Elements whose content is dropped along with the tags.
+const DROPPED_CONTENT: &[&str] = &["head", "iframe", "object", "script", "style", "title"];
+
+const BLOCK_TAGS: &[&str] = &[
+    "blockquote",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "p",
+    "pre",
+    "table",
+    "tr",
+];
+
+pub enum Token<'x> {
+    Text(&'x str),
+    Tag {
+        name: String,
+        closing: bool,
+        attributes: Vec<(String, String)>,
+    },
+}
+
+// Keeps a small set of formatting tags and safe links, dropping scripts,
+// styles, event handlers and everything else.
+pub fn sanitize(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut dropping: Option<String> = None;
+    for token in tokenize(html) {
+        match token {
+            Token::Tag { name, closing, .. } if dropping.as_ref() == Some(&name) => {
+                if closing {
+                    dropping = None;
+                }
+            }
+            _ if dropping.is_some() => {}
+            Token::Text(text) => out.push_str(&text.replace('<', "&lt;").replace('>', "&gt;")),
+            Token::Tag { name, closing, .. } if DROPPED_CONTENT.contains(&name.as_str()) => {
+                if !closing {
+                    dropping = Some(name);
+                }
+            }
+            Token::Tag {
+                name,
+                closing: true,
+                ..
+            } if ALLOWED_TAGS.contains(&name.as_str()) => {
+                if !matches!(name.as_str(), "br" | "hr") {
+                    out.push_str(&format!("</{}>", name));
+                }
+            }
+            Token::Tag {
+                name, attributes, ..
+            } if ALLOWED_TAGS.contains(&name.as_str()) => {
+                out.push('<');
+                out.push_str(&name);
+                if name == "a" {
+                    if let Some((_, href)) = attributes.iter().find(|(attr, _)| attr == "href") {
+                        let lower = href.trim().to_ascii_lowercase();
+                        if ["http:", "https:", "mailto:"]
+                            .iter()
+                            .any(|scheme| lower.starts_with(scheme))
+                        {
+                            out.push_str(&format!(
+                                " href=\"{}\" target=\"_blank\" rel=\"noopener noreferrer\"",
+                                escape(href)
+                            ));
+                        }
+                    }
+                }
+                out.push('>');
+            }
+            Token::Tag { .. } => {}
+        }
+    }
+    out
+}
+
+// Plain text rendering used to regenerate DESCRIPTION from an HTML description.
+pub fn to_plain_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut dropping: Option<String> = None;
+    for token in tokenize(html) {
+        match token {
+            Token::Tag { name, closing, .. } if dropping.as_ref() == Some(&name) => {
+                if closing {
+                    dropping = None;
+                }
+            }
+            _ if dropping.is_some() => {}
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                let mut last_space = out.ends_with([' ', '\n']) || out.is_empty();
+                for ch in text.chars() {
+                    if ch.is_whitespace() && ch != '\u{a0}' {
+                        if !last_space {
+                            out.push(' ');
+                            last_space = true;
+                        }
+                    } else {
+                        out.push(if ch == '\u{a0}' { ' ' } else { ch });
+                        last_space = false;
+                    }
+                }
+            }
+            Token::Tag { name, closing, .. } if DROPPED_CONTENT.contains(&name.as_str()) => {
+                if !closing {
+                    dropping = Some(name);
+                }
+            }
+            Token::Tag { name, closing, .. } => {
+                if name == "br" || (closing && BLOCK_TAGS.contains(&name.as_str())) {
+                    while out.ends_with(' ') {
+                        out.pop();
+                    }
+                    out.push('\n');
+                } else if name == "li" && !closing {
+                    out.push_str("- ");
+                }
+            }
+        }
+    }
+
+    // Collapse runs of blank lines left over by nested blocks.
+    let mut text = String::with_capacity(out.len());
+    for line in out.lines().map(str::trim_end) {
+        if !(line.is_empty() && (text.is_empty() || text.ends_with("\n\n"))) {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    text.trim_end().to_string()
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let raw = &rest[..end + 1];
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag.trim_end_matches('/')),
+        };
+        let name_end = tag.find(|ch: char| ch.is_whitespace()).unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        if name.is_empty() || !name.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            // Doctypes and processing instructions are skipped, stray
+            // brackets are kept as text.
+            if !tag.starts_with(['!', '?']) {
+                tokens.push(Token::Text(raw));
+            }
+            continue;
+        }
+        tokens.push(Token::Tag {
+            name,
+            closing,
+            attributes: parse_attributes(&tag[name_end..]),
+        });
+    }
+    tokens
+}
+
+fn parse_attributes(text: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|ch: char| ch == '=' || ch.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (value, remaining) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    let end = value.find(quote).unwrap_or(value.len());
+                    (&value[..end], value.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = value
+                        .find(|ch: char| ch.is_whitespace())
+                        .unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+    }
+    attributes
+}
+
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#')?.parse::<u32>().ok())
+                    .and_then(char::from_u32),
+            }?;
+            Some((ch, end))
+        });
+        match decoded {
+            Some((ch, end)) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
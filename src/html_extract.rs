@@ -0,0 +1,714 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Events and contacts marked up in a web page: hCalendar and hCard
+// microformats, in their classic and h-* forms, and schema.org Events in
+// JSON-LD. Events are written as an iCalendar, or contacts as vCards when the
+// page has no events.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, escape, fold_line},
+    html::{self, Token},
+    Format,
+};
+
+const PRODID: &str = "-//Stalwart Labs LLC//jmap-convert HTML extraction//EN";
+const EVENT_ROOTS: &[&str] = &["vevent", "h-event"];
+const CARD_ROOTS: &[&str] = &["vcard", "h-card"];
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Extracted {
+    pub format: Format,
+    pub text: String,
+    pub json_ld_events: usize,
+    pub hcalendar_events: usize,
+    pub hcards: usize,
+    // Events left out because they have no start date.
+    pub skipped: usize,
+}
+
+// Whether the source is markup rather than a calendar, contact or JSON
+// document.
+pub fn is_html(source: &str) -> bool {
+    source.trim_start().starts_with('<')
+}
+
+// `now` is written as the DTSTAMP of the events.
+pub fn extract(source: &str, now: DateTime<Utc>) -> Result<Extracted, String> {
+    let root = parse(source);
+    let mut events = Vec::new();
+    let mut cards = Vec::new();
+    let mut extracted = Extracted {
+        format: Format::ICalendar,
+        text: String::new(),
+        json_ld_events: 0,
+        hcalendar_events: 0,
+        hcards: 0,
+        skipped: 0,
+    };
+
+    for script in json_ld_scripts(source) {
+        let Ok(value) = serde_json::from_str::<Value>(&script) else {
+            continue;
+        };
+        for event in json_ld_events(&value) {
+            match json_ld_event(event) {
+                Some(event) => {
+                    events.push(event);
+                    extracted.json_ld_events += 1;
+                }
+                None => extracted.skipped += 1,
+            }
+        }
+    }
+
+    let mut event_roots = Vec::new();
+    let mut card_roots = Vec::new();
+    find_roots(&root, &mut event_roots, &mut card_roots);
+    for element in event_roots {
+        match hcalendar_event(element) {
+            Some(event) => {
+                events.push(event);
+                extracted.hcalendar_events += 1;
+            }
+            None => extracted.skipped += 1,
+        }
+    }
+    for element in card_roots {
+        if let Some(card) = hcard(element) {
+            cards.push(card);
+            extracted.hcards += 1;
+        }
+    }
+
+    let mut text = String::new();
+    if !events.is_empty() {
+        fold_line(&mut text, "BEGIN:VCALENDAR");
+        fold_line(&mut text, "VERSION:2.0");
+        fold_line(&mut text, &format!("PRODID:{}", PRODID));
+        let mut uids: Vec<String> = Vec::new();
+        for (index, event) in events.into_iter().enumerate() {
+            let mut uid = event
+                .uid
+                .clone()
+                .unwrap_or_else(|| format!("html-{}-{}", now.timestamp(), index + 1));
+            if uids.contains(&uid) {
+                uid = format!("{}-{}", uid, index + 1);
+            }
+            fold_line(&mut text, "BEGIN:VEVENT");
+            fold_line(&mut text, &format!("UID:{}", escape(&uid)));
+            fold_line(
+                &mut text,
+                &format!("DTSTAMP:{}Z", contentline::format_datetime(now.naive_utc())),
+            );
+            for line in &event.lines {
+                fold_line(&mut text, line);
+            }
+            fold_line(&mut text, "END:VEVENT");
+            uids.push(uid);
+        }
+        fold_line(&mut text, "END:VCALENDAR");
+    } else if !cards.is_empty() {
+        extracted.format = Format::VCard;
+        for card in cards {
+            fold_line(&mut text, "BEGIN:VCARD");
+            fold_line(&mut text, "VERSION:4.0");
+            for line in &card {
+                fold_line(&mut text, line);
+            }
+            fold_line(&mut text, "END:VCARD");
+        }
+    } else {
+        return Err(
+            "The page has no hCalendar, hCard or schema.org Event markup to extract.".to_string(),
+        );
+    }
+    extracted.text = text;
+    Ok(extracted)
+}
+
+struct Event {
+    uid: Option<String>,
+    // Content lines other than UID and DTSTAMP.
+    lines: Vec<String>,
+}
+
+#[derive(Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+enum Node {
+    Text(String),
+    Element(Element),
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn has_class(&self, classes: &[&str]) -> bool {
+        self.attribute("class").is_some_and(|class| {
+            class
+                .split_ascii_whitespace()
+                .any(|class| classes.contains(&class))
+        })
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    // Text content with whitespace collapsed.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn collect_text(&self, out: &mut String) {
+        for child in &self.children {
+            match child {
+                Node::Text(text) => out.push_str(text),
+                Node::Element(element) => {
+                    if element.name == "br" {
+                        out.push(' ');
+                    }
+                    element.collect_text(out);
+                }
+            }
+        }
+    }
+
+    // Value of a plain text property, taken from the attribute that holds it
+    // on elements whose content is not the value.
+    fn text_value(&self) -> String {
+        match self.name.as_str() {
+            "abbr" => self.attribute("title"),
+            "data" | "input" => self.attribute("value"),
+            "img" | "area" => self.attribute("alt"),
+            "meta" => self.attribute("content"),
+            _ => None,
+        }
+        .map(|value| value.trim().to_string())
+        .unwrap_or_else(|| self.text())
+    }
+
+    fn date_value(&self) -> String {
+        self.attribute("datetime")
+            .map(|value| value.trim().to_string())
+            .unwrap_or_else(|| self.text_value())
+    }
+
+    fn url_value(&self) -> String {
+        match self.name.as_str() {
+            "a" | "area" | "link" => self.attribute("href"),
+            "img" | "audio" | "video" | "source" => self.attribute("src"),
+            "object" => self.attribute("data"),
+            _ => None,
+        }
+        .map(|value| value.trim().to_string())
+        .unwrap_or_else(|| self.text_value())
+    }
+
+    // Property elements under this root, not looking into nested roots,
+    // which may themselves be a property such as an event's location.
+    fn properties<'x>(&'x self, classes: &[&str], found: &mut Vec<&'x Element>) {
+        for element in self.elements() {
+            if element.has_class(classes) {
+                found.push(element);
+            }
+            if !element.has_class(EVENT_ROOTS) && !element.has_class(CARD_ROOTS) {
+                element.properties(classes, found);
+            }
+        }
+    }
+
+    fn property(&self, classes: &[&str]) -> Option<&Element> {
+        let mut found = Vec::new();
+        self.properties(classes, &mut found);
+        found.into_iter().next()
+    }
+
+    fn property_text(&self, classes: &[&str]) -> Option<String> {
+        self.property(classes)
+            .map(Element::text_value)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+// A lenient tree: unclosed elements end with their parent and stray end tags
+// are ignored.
+fn parse(source: &str) -> Element {
+    let mut stack = vec![Element::default()];
+    for token in html::tokenize(source) {
+        match token {
+            Token::Text(text) => {
+                let parent = stack.last_mut().expect("root element");
+                // Scripts and styles hold no text content.
+                if !matches!(parent.name.as_str(), "script" | "style") {
+                    parent
+                        .children
+                        .push(Node::Text(html::decode_entities(text)));
+                }
+            }
+            Token::Tag {
+                name,
+                closing: false,
+                attributes,
+            } => {
+                let element = Element {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                };
+                if VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    stack
+                        .last_mut()
+                        .expect("root element")
+                        .children
+                        .push(Node::Element(element));
+                } else {
+                    stack.push(element);
+                }
+            }
+            Token::Tag { name, .. } => {
+                if let Some(position) = stack.iter().skip(1).rposition(|open| open.name == name) {
+                    while stack.len() > position + 1 {
+                        let element = stack.pop().expect("open element");
+                        stack
+                            .last_mut()
+                            .expect("root element")
+                            .children
+                            .push(Node::Element(element));
+                    }
+                }
+            }
+        }
+    }
+    while stack.len() > 1 {
+        let element = stack.pop().expect("open element");
+        stack
+            .last_mut()
+            .expect("root element")
+            .children
+            .push(Node::Element(element));
+    }
+    stack.pop().unwrap_or_default()
+}
+
+// Top-level events and cards. Cards inside an event are its organizer or
+// location, not contacts of their own.
+fn find_roots<'x>(
+    element: &'x Element,
+    events: &mut Vec<&'x Element>,
+    cards: &mut Vec<&'x Element>,
+) {
+    for child in element.elements() {
+        if child.has_class(EVENT_ROOTS) {
+            events.push(child);
+        } else if child.has_class(CARD_ROOTS) {
+            cards.push(child);
+        } else {
+            find_roots(child, events, cards);
+        }
+    }
+}
+
+fn hcalendar_event(element: &Element) -> Option<Event> {
+    let (start, end) = date_lines(
+        &element.property(&["dtstart", "dt-start"])?.date_value(),
+        element
+            .property(&["dtend", "dt-end"])
+            .map(Element::date_value)
+            .as_deref(),
+    )?;
+    let mut lines = Vec::new();
+    if let Some(summary) = element.property_text(&["summary", "p-name", "p-summary"]) {
+        lines.push(format!("SUMMARY:{}", escape(&summary)));
+    }
+    lines.push(start);
+    if let Some(end) = end {
+        lines.push(end);
+    } else if let Some(duration) = element.property_text(&["duration", "dt-duration"]) {
+        lines.push(format!("DURATION:{}", duration));
+    }
+    if let Some(location) = element.property_text(&["location", "p-location"]) {
+        lines.push(format!("LOCATION:{}", escape(&location)));
+    }
+    if let Some(description) = element.property_text(&["description", "p-description", "e-content"])
+    {
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+    }
+    if let Some(url) = element
+        .property(&["url", "u-url"])
+        .map(Element::url_value)
+        .filter(|url| !url.is_empty())
+    {
+        lines.push(format!("URL:{}", url));
+    }
+    let mut categories = Vec::new();
+    element.properties(&["category", "p-category"], &mut categories);
+    let categories = categories
+        .into_iter()
+        .map(|category| escape(&category.text_value()))
+        .filter(|category| !category.is_empty())
+        .collect::<Vec<_>>();
+    if !categories.is_empty() {
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+    Some(Event {
+        uid: element
+            .property(&["uid", "u-uid"])
+            .map(Element::url_value)
+            .filter(|uid| !uid.is_empty()),
+        lines,
+    })
+}
+
+fn hcard(element: &Element) -> Option<Vec<String>> {
+    // Family, given and additional names, prefix and suffix, as in N.
+    let name = [
+        ["family-name", "p-family-name"],
+        ["given-name", "p-given-name"],
+        ["additional-name", "p-additional-name"],
+        ["honorific-prefix", "p-honorific-prefix"],
+        ["honorific-suffix", "p-honorific-suffix"],
+    ]
+    .map(|classes| element.property_text(&classes).unwrap_or_default());
+    let full_name = element
+        .property_text(&["fn", "p-name"])
+        .or_else(|| {
+            let parts = [&name[3], &name[1], &name[2], &name[0], &name[4]]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            (!parts.is_empty()).then(|| parts.join(" "))
+        })
+        // The root itself, as in <span class="h-card">Jane Doe</span>.
+        .or_else(|| Some(element.text()).filter(|text| !text.is_empty()))?;
+
+    let mut lines = vec![format!("FN:{}", escape(&full_name))];
+    if name.iter().any(|part| !part.is_empty()) {
+        lines.push(format!(
+            "N:{}",
+            name.iter()
+                .map(|part| escape(part))
+                .collect::<Vec<_>>()
+                .join(";")
+        ));
+    }
+    let mut found = Vec::new();
+    element.properties(&["email", "u-email"], &mut found);
+    for email in found.drain(..) {
+        let address = email.url_value();
+        let address = address.strip_prefix("mailto:").unwrap_or(&address);
+        if !address.is_empty() {
+            lines.push(format!("EMAIL:{}", address));
+        }
+    }
+    element.properties(&["tel", "p-tel"], &mut found);
+    for tel in found.drain(..) {
+        let number = tel.text_value();
+        if !number.is_empty() {
+            lines.push(format!("TEL:{}", escape(&number)));
+        }
+    }
+    if let Some(org) = element.property_text(&["org", "p-org"]) {
+        lines.push(format!("ORG:{}", escape(&org)));
+    }
+    if let Some(title) = element.property_text(&["title", "p-job-title"]) {
+        lines.push(format!("TITLE:{}", escape(&title)));
+    }
+    element.properties(&["adr", "p-adr", "h-adr"], &mut found);
+    for adr in found.drain(..) {
+        // Post office box, extended and street address, locality, region,
+        // postal code and country, as in ADR.
+        let parts = [
+            ["post-office-box", "p-post-office-box"],
+            ["extended-address", "p-extended-address"],
+            ["street-address", "p-street-address"],
+            ["locality", "p-locality"],
+            ["region", "p-region"],
+            ["postal-code", "p-postal-code"],
+            ["country-name", "p-country-name"],
+        ]
+        .map(|classes| escape(&adr.property_text(&classes).unwrap_or_default()));
+        if parts.iter().any(|part| !part.is_empty()) {
+            lines.push(format!("ADR:{}", parts.join(";")));
+        }
+    }
+    for (classes, property) in [
+        (["url", "u-url"], "URL"),
+        (["photo", "u-photo"], "PHOTO"),
+        (["uid", "u-uid"], "UID"),
+    ] {
+        if let Some(value) = element
+            .property(&classes)
+            .map(Element::url_value)
+            .filter(|value| !value.is_empty())
+        {
+            lines.push(format!("{}:{}", property, value));
+        }
+    }
+    if let Some(note) = element.property_text(&["note", "p-note"]) {
+        lines.push(format!("NOTE:{}", escape(&note)));
+    }
+    Some(lines)
+}
+
+// Contents of the <script type="application/ld+json"> elements.
+fn json_ld_scripts(source: &str) -> Vec<String> {
+    let lower = source.to_ascii_lowercase();
+    let mut scripts = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = lower[offset..].find("<script") {
+        let start = offset + start;
+        let Some(tag_end) = lower[start..].find('>').map(|end| start + end) else {
+            break;
+        };
+        let content_end = lower[tag_end..]
+            .find("</script")
+            .map_or(source.len(), |end| tag_end + end);
+        if lower[start..tag_end].contains("application/ld+json") {
+            scripts.push(source[tag_end + 1..content_end].to_string());
+        }
+        offset = content_end;
+    }
+    scripts
+}
+
+// Events anywhere in a JSON-LD document, including @graph lists.
+fn json_ld_events(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(json_ld_events).collect(),
+        Value::Object(object) => {
+            let is_event = match object.get("@type") {
+                Some(Value::String(kind)) => is_event_type(kind),
+                Some(Value::Array(kinds)) => {
+                    kinds.iter().filter_map(Value::as_str).any(is_event_type)
+                }
+                _ => false,
+            };
+            if is_event {
+                vec![value]
+            } else {
+                object.get("@graph").map(json_ld_events).unwrap_or_default()
+            }
+        }
+        _ => vec![],
+    }
+}
+
+// Event and its subtypes, such as MusicEvent or SportsEvent.
+fn is_event_type(kind: &str) -> bool {
+    kind.rsplit(['/', ':'])
+        .next()
+        .is_some_and(|kind| kind.ends_with("Event"))
+}
+
+fn json_ld_event(event: &Value) -> Option<Event> {
+    let string = |key: &str| {
+        event
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let (start, end) = date_lines(string("startDate")?, string("endDate"))?;
+    let mut lines = Vec::new();
+    if let Some(name) = string("name") {
+        lines.push(format!("SUMMARY:{}", escape(name)));
+    }
+    lines.push(start);
+    if let Some(end) = end {
+        lines.push(end);
+    } else if let Some(duration) = string("duration") {
+        lines.push(format!("DURATION:{}", duration));
+    }
+    let location = match event.get("location") {
+        Some(Value::Array(locations)) => locations.first(),
+        location => location,
+    };
+    match location {
+        Some(Value::String(location)) => lines.push(format!("LOCATION:{}", escape(location))),
+        Some(location @ Value::Object(_)) => {
+            let text = [
+                location
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                postal_address(location.get("address")),
+                location
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+            if !text.is_empty() {
+                lines.push(format!("LOCATION:{}", escape(&text.join(", "))));
+            }
+            let coordinate = |key: &str| {
+                location.get("geo").and_then(|geo| match geo.get(key)? {
+                    Value::Number(number) => number.as_f64(),
+                    Value::String(number) => number.trim().parse().ok(),
+                    _ => None,
+                })
+            };
+            if let (Some(latitude), Some(longitude)) =
+                (coordinate("latitude"), coordinate("longitude"))
+            {
+                lines.push(format!("GEO:{};{}", latitude, longitude));
+            }
+        }
+        _ => {}
+    }
+    if let Some(description) = string("description") {
+        lines.push(format!("DESCRIPTION:{}", escape(description)));
+    }
+    if let Some(url) = string("url") {
+        lines.push(format!("URL:{}", url));
+    }
+    match string("eventStatus").and_then(|status| status.rsplit('/').next()) {
+        Some("EventCancelled") => lines.push("STATUS:CANCELLED".to_string()),
+        Some("EventScheduled" | "EventRescheduled") => lines.push("STATUS:CONFIRMED".to_string()),
+        Some("EventPostponed") => lines.push("STATUS:TENTATIVE".to_string()),
+        _ => {}
+    }
+    let organizer = match event.get("organizer") {
+        Some(Value::Array(organizers)) => organizers.first(),
+        organizer => organizer,
+    };
+    if let Some(email) = organizer
+        .and_then(|organizer| organizer.get("email"))
+        .and_then(Value::as_str)
+    {
+        let email = email.trim().trim_start_matches("mailto:");
+        match organizer
+            .and_then(|organizer| organizer.get("name"))
+            .and_then(Value::as_str)
+        {
+            Some(name) => lines.push(format!(
+                "ORGANIZER;CN=\"{}\":mailto:{}",
+                name.replace('"', "'"),
+                email
+            )),
+            None => lines.push(format!("ORGANIZER:mailto:{}", email)),
+        }
+    }
+    Some(Event {
+        uid: string("@id").or_else(|| string("url")).map(str::to_string),
+        lines,
+    })
+}
+
+// A PostalAddress, or an address given as text, on one line.
+fn postal_address(address: Option<&Value>) -> Option<String> {
+    match address? {
+        Value::String(address) => Some(address.trim().to_string()),
+        Value::Object(address) => {
+            let parts = [
+                "streetAddress",
+                "addressLocality",
+                "addressRegion",
+                "postalCode",
+                "addressCountry",
+            ]
+            .into_iter()
+            .filter_map(|key| match address.get(key)? {
+                Value::String(part) => Some(part.trim()),
+                // A Country, with its name.
+                country => country.get("name")?.as_str().map(str::trim),
+            })
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+            Some(parts.join(", "))
+        }
+        _ => None,
+    }
+}
+
+enum HtmlDate {
+    Date(NaiveDate),
+    Floating(NaiveDateTime),
+    Offset(DateTime<FixedOffset>),
+}
+
+// ISO 8601 dates as used in HTML, with a space or a T before the time.
+fn parse_date(value: &str) -> Option<HtmlDate> {
+    let value = value.trim().replacen(' ', "T", 1);
+    let value = match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => format!("{}+00:00", utc),
+        None => value,
+    };
+    [
+        "%Y-%m-%dT%H:%M:%S%.f%:z",
+        "%Y-%m-%dT%H:%M%:z",
+        "%Y-%m-%dT%H:%M:%S%.f%z",
+        "%Y-%m-%dT%H:%M%z",
+    ]
+    .into_iter()
+    .find_map(|format| DateTime::parse_from_str(&value, format).ok())
+    .map(HtmlDate::Offset)
+    .or_else(|| {
+        ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+            .into_iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(&value, format).ok())
+            .map(HtmlDate::Floating)
+    })
+    .or_else(|| {
+        NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+            .ok()
+            .map(HtmlDate::Date)
+    })
+}
+
+// DTSTART and DTEND lines. A date is an all-day value, a date-time with an
+// offset is written in UTC and one without stays floating, unless it is the
+// end of a start that has an offset, which it then shares.
+fn date_lines(start: &str, end: Option<&str>) -> Option<(String, Option<String>)> {
+    let start = parse_date(start)?;
+    let end = end.and_then(parse_date).map(|end| match (&start, end) {
+        (HtmlDate::Offset(start), HtmlDate::Floating(end)) => start
+            .offset()
+            .from_local_datetime(&end)
+            .single()
+            .map_or(HtmlDate::Floating(end), HtmlDate::Offset),
+        (_, end) => end,
+    });
+    let line = |name: &str, date: &HtmlDate| match date {
+        HtmlDate::Date(date) => format!("{};VALUE=DATE:{}", name, contentline::format_date(*date)),
+        HtmlDate::Floating(date_time) => {
+            format!("{}:{}", name, contentline::format_datetime(*date_time))
+        }
+        HtmlDate::Offset(date_time) => format!(
+            "{}:{}Z",
+            name,
+            contentline::format_datetime(date_time.naive_utc())
+        ),
+    };
+    Some((line("DTSTART", &start), end.map(|end| line("DTEND", &end))))
+}
@@ -0,0 +1,173 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Translations of the UI, as Fluent messages in locales/, and the date
+// formats of each language.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use fluent_bundle::{FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::En, Locale::De, Locale::Es, Locale::Fr];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+        }
+    }
+
+    // In the language itself, as offered in the language list.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+            Locale::Es => "Español",
+            Locale::Fr => "Français",
+        }
+    }
+
+    // The locale of a BCP 47 tag such as "de-AT", by its language.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.as_str().eq_ignore_ascii_case(language))
+    }
+
+    // The first of the preferred languages there is a translation for.
+    pub fn negotiate<'x>(languages: impl IntoIterator<Item = &'x str>) -> Self {
+        languages
+            .into_iter()
+            .find_map(Locale::parse)
+            .unwrap_or_default()
+    }
+
+    // "Mon Mar 3, 2025 9:00am", "Mo., 3. März 2025, 09:00".
+    pub fn format_date_time(&self, date_time: NaiveDateTime) -> String {
+        let pattern = match self {
+            Locale::En => "%a %b %-d, %Y %-I:%M%P",
+            Locale::De => "%a, %-d. %b %Y, %H:%M",
+            Locale::Es => "%a, %-d %b %Y, %H:%M",
+            Locale::Fr => "%a %-d %b %Y, %H:%M",
+        };
+        date_time
+            .and_utc()
+            .format_localized(pattern, self.chrono_locale())
+            .to_string()
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        let pattern = match self {
+            Locale::En => "%a %b %-d, %Y",
+            Locale::De => "%a, %-d. %b %Y",
+            Locale::Es => "%a, %-d %b %Y",
+            Locale::Fr => "%a %-d %b %Y",
+        };
+        date.format_localized(pattern, self.chrono_locale())
+            .to_string()
+    }
+
+    fn chrono_locale(&self) -> chrono::Locale {
+        match self {
+            Locale::En => chrono::Locale::en_US,
+            Locale::De => chrono::Locale::de_DE,
+            Locale::Es => chrono::Locale::es_ES,
+            Locale::Fr => chrono::Locale::fr_FR,
+        }
+    }
+
+    fn messages(&self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::De => include_str!("../locales/de.ftl"),
+            Locale::Es => include_str!("../locales/es.ftl"),
+            Locale::Fr => include_str!("../locales/fr.ftl"),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::parse(s).ok_or_else(|| format!("Unknown locale: {}", s))
+    }
+}
+
+// The messages of every locale. Those missing from a translation fall back
+// to English, and unknown ones to their id.
+pub struct Catalog {
+    bundles: Vec<(Locale, FluentBundle<FluentResource>)>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        let bundles = Locale::ALL
+            .into_iter()
+            .map(|locale| {
+                let language = locale
+                    .as_str()
+                    .parse::<LanguageIdentifier>()
+                    .unwrap_or_default();
+                let mut bundle = FluentBundle::new(vec![language]);
+                // The isolation marks around arguments only matter for
+                // right-to-left languages.
+                bundle.set_use_isolating(false);
+                let resource = FluentResource::try_new(locale.messages().to_string())
+                    .unwrap_or_else(|(resource, _)| resource);
+                // Messages defined twice keep their first definition.
+                let _ = bundle.add_resource(resource);
+                (locale, bundle)
+            })
+            .collect();
+        Catalog { bundles }
+    }
+
+    pub fn message(&self, locale: Locale, id: &str, args: Option<&FluentArgs>) -> String {
+        [locale, Locale::En]
+            .into_iter()
+            .find_map(|locale| {
+                let (_, bundle) = self.bundles.iter().find(|(other, _)| *other == locale)?;
+                let pattern = bundle.get_message(id)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, args, &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::new()
+    }
+}
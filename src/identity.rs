@@ -0,0 +1,211 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::Value;
+
+use crate::{
+    contentline,
+    findings::{Code, Finding},
+};
+
+// Non-standard properties some address books use for pronouns and gender.
+const VENDOR_PROPERTIES: &[&str] = &["X-PRONOUNS", "X-PRONOUN", "X-GENDER", "X-SEX"];
+
+// Gender and pronoun data as found in a vCard or JSContact card.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IdentityData {
+    pub gender: Option<String>,
+    pub grammatical_gender: Option<String>,
+    pub pronouns: Vec<String>,
+    pub vendor: Vec<(String, String)>,
+}
+
+impl IdentityData {
+    pub fn extract(text: &str) -> Self {
+        let text = text.trim_start();
+        if text.starts_with('{') {
+            serde_json::from_str::<Value>(text)
+                .map(|card| Self::from_jscontact(&card))
+                .unwrap_or_default()
+        } else {
+            Self::from_vcard(text)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gender.is_none()
+            && self.grammatical_gender.is_none()
+            && self.pronouns.is_empty()
+            && self.vendor.is_empty()
+    }
+
+    fn from_vcard(text: &str) -> Self {
+        let mut data = IdentityData::default();
+        let mut pronouns = Vec::new();
+        for line in contentline::parse(text) {
+            match line.name.as_str() {
+                "GENDER" => {
+                    data.gender = Some(format_gender(
+                        &contentline::split_unescaped(&line.value, ';')
+                            .iter()
+                            .map(|part| contentline::unescape(part))
+                            .collect::<Vec<_>>(),
+                    ))
+                }
+                "GRAMGENDER" => data.grammatical_gender = Some(line.text().to_ascii_lowercase()),
+                "PRONOUNS" => pronouns.push((
+                    line.param("PREF")
+                        .and_then(|pref| pref.parse::<u32>().ok())
+                        .unwrap_or(u32::MAX),
+                    line.text(),
+                )),
+                name if VENDOR_PROPERTIES.contains(&name) => {
+                    data.vendor.push((line.name.clone(), line.text()))
+                }
+                _ => {}
+            }
+        }
+        pronouns.sort_by_key(|(pref, _)| *pref);
+        data.pronouns = pronouns.into_iter().map(|(_, pronouns)| pronouns).collect();
+        data
+    }
+
+    fn from_jscontact(card: &Value) -> Self {
+        let mut data = IdentityData::default();
+        if let Some(speak_to_as) = card.get("speakToAs") {
+            data.grammatical_gender = speak_to_as
+                .get("grammaticalGender")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let mut pronouns = speak_to_as
+                .get("pronouns")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|pronouns| pronouns.values())
+                .filter_map(|pronouns| {
+                    Some((
+                        pronouns
+                            .get("pref")
+                            .and_then(Value::as_u64)
+                            .unwrap_or(u64::MAX),
+                        pronouns.get("pronouns")?.as_str()?.to_string(),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            pronouns.sort_by_key(|(pref, _)| *pref);
+            data.pronouns = pronouns.into_iter().map(|(_, pronouns)| pronouns).collect();
+        }
+
+        // vCard properties without a JSContact equivalent, as
+        // [name, parameters, value type, value].
+        for prop in card
+            .get("vCardProps")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_array)
+        {
+            let (Some(name), Some(value)) = (prop.first().and_then(Value::as_str), prop.get(3))
+            else {
+                continue;
+            };
+            let name = name.to_ascii_uppercase();
+            let parts = match value {
+                Value::String(value) => vec![value.clone()],
+                Value::Array(values) => values
+                    .iter()
+                    .map(|value| value.as_str().unwrap_or_default().to_string())
+                    .collect(),
+                _ => continue,
+            };
+            if name == "GENDER" {
+                data.gender = Some(format_gender(&parts));
+            } else if VENDOR_PROPERTIES.contains(&name.as_str()) {
+                data.vendor.push((name, parts.join(";")));
+            }
+        }
+        data
+    }
+}
+
+fn format_gender(parts: &[String]) -> String {
+    let sex = match parts
+        .first()
+        .map(|sex| sex.trim().to_ascii_uppercase())
+        .as_deref()
+    {
+        Some("M") => "M (male)",
+        Some("F") => "F (female)",
+        Some("O") => "O (other)",
+        Some("N") => "N (none or not applicable)",
+        Some("U") => "U (unknown)",
+        _ => "",
+    }
+    .to_string();
+    match parts.get(1).filter(|identity| !identity.is_empty()) {
+        Some(identity) if sex.is_empty() => identity.clone(),
+        Some(identity) => format!("{}, {}", sex, identity),
+        None => sex,
+    }
+}
+
+// Reports every gender or pronoun value present in the source that the
+// conversion or round trip lost or changed.
+pub fn check_conversion(source: &str, converted: &str, roundtrip: &str) -> Vec<Finding> {
+    let source = IdentityData::extract(source);
+    if source.is_empty() {
+        return vec![];
+    }
+
+    let mut findings = Vec::new();
+    for (stage, text) in [("conversion", converted), ("round trip", roundtrip)] {
+        let output = IdentityData::extract(text);
+        let mut check = |label: &str, before: Option<&String>, after: Option<&String>| {
+            if let Some(before) = before {
+                if after != Some(before) {
+                    findings.push(Finding::new(
+                        Code::IdentityDataDropped,
+                        match after {
+                            Some(after) => format!(
+                                "The {} changed {} from {:?} to {:?}.",
+                                stage, label, before, after
+                            ),
+                            None => format!("The {} dropped {} {:?}.", stage, label, before),
+                        },
+                    ));
+                }
+            }
+        };
+        check("gender", source.gender.as_ref(), output.gender.as_ref());
+        check(
+            "grammatical gender",
+            source.grammatical_gender.as_ref(),
+            output.grammatical_gender.as_ref(),
+        );
+        for pronouns in &source.pronouns {
+            check(
+                "pronouns",
+                Some(pronouns),
+                output.pronouns.iter().find(|item| *item == pronouns),
+            );
+        }
+
+        for (property, value) in &source.vendor {
+            let kept = output.vendor.iter().any(|(_, output)| output == value)
+                || output.pronouns.contains(value);
+            if !kept {
+                findings.push(Finding::new(
+                    Code::VendorIdentityUnmapped,
+                    format!(
+                        "The {} could not represent {} {:?}.",
+                        stage, property, value
+                    ),
+                ));
+            }
+        }
+    }
+    findings
+}
@@ -0,0 +1,126 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use js_sys::{ArrayBuffer, Promise, Uint8Array};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, File, FileReader};
+
+use crate::fetch::js_error;
+
+// Larger files are most likely not calendars or contacts and would stall the page.
+const MAX_FILE_SIZE: f64 = 10.0 * 1024.0 * 1024.0;
+
+// Files are read a slice at a time so a progress bar can follow larger ones.
+const CHUNK_SIZE: f64 = 1024.0 * 1024.0;
+
+pub async fn read_file(file: &File) -> Result<String, String> {
+    read_file_with_progress(file, |_, _| {}).await
+}
+
+// Reads the file, calling `on_progress` with the bytes read so far and the
+// size of the file after every slice.
+pub async fn read_file_with_progress(
+    file: &File,
+    on_progress: impl Fn(f64, f64),
+) -> Result<String, String> {
+    let size = file.size();
+    if size > MAX_FILE_SIZE {
+        return Err(format!(
+            "{} is too large, files up to {} MB can be loaded.",
+            file.name(),
+            MAX_FILE_SIZE / 1024.0 / 1024.0
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(size as usize);
+    let mut offset = 0.0;
+    while offset < size {
+        let end = (offset + CHUNK_SIZE).min(size);
+        let slice = file
+            .slice_with_f64_and_f64(offset, end)
+            .map_err(|err| js_error(&err))?;
+        let buffer = read_blob(&slice)
+            .await
+            .map_err(|err| format!("Failed to read {}: {}", file.name(), err))?;
+        bytes.extend(Uint8Array::new(&buffer).to_vec());
+        offset = end;
+        on_progress(offset, size);
+    }
+    Ok(decode(&bytes))
+}
+
+async fn read_blob(blob: &Blob) -> Result<ArrayBuffer, String> {
+    let reader = FileReader::new().map_err(|err| js_error(&err))?;
+    let promise = Promise::new(&mut |resolve, reject| {
+        let load_reader = reader.clone();
+        let on_load = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = resolve.call1(
+                &JsValue::NULL,
+                &load_reader.result().unwrap_or(JsValue::UNDEFINED),
+            );
+        });
+        let error_reader = reader.clone();
+        let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+            let error = error_reader
+                .error()
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+        reader.set_onload(Some(on_load.unchecked_ref()));
+        reader.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    reader
+        .read_as_array_buffer(blob)
+        .map_err(|err| js_error(&err))?;
+
+    JsFuture::from(promise)
+        .await
+        .map_err(|err| js_error(&err))?
+        .dyn_into::<ArrayBuffer>()
+        .map_err(|_| "the file could not be read as bytes".to_string())
+}
+
+// Files exported by older clients are not always UTF-8: Outlook writes
+// UTF-16 and some address books Latin-1. A byte order mark decides when
+// present, otherwise UTF-16 is recognized by its zero bytes and anything
+// that is not valid UTF-8 is read as Latin-1.
+pub fn decode(bytes: &[u8]) -> String {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => {
+            // Markup and JSON are ASCII at the start, so every other byte is zero.
+            let head = &bytes[..bytes.len().min(64) & !1];
+            let zeros = |offset: usize| {
+                head.iter()
+                    .skip(offset)
+                    .step_by(2)
+                    .filter(|b| **b == 0)
+                    .count()
+            };
+            let half = head.len() / 2;
+            if half > 0 && zeros(1) == half && zeros(0) == 0 {
+                decode_utf16(bytes, u16::from_le_bytes)
+            } else if half > 0 && zeros(0) == half && zeros(1) == 0 {
+                decode_utf16(bytes, u16::from_be_bytes)
+            } else {
+                match std::str::from_utf8(bytes) {
+                    Ok(text) => text.to_string(),
+                    Err(_) => bytes.iter().map(|byte| char::from(*byte)).collect(),
+                }
+            }
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    char::decode_utf16(bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])))
+        .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
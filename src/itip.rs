@@ -0,0 +1,341 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// iTIP (RFC 5546) scheduling messages, as sent by iMIP (RFC 6047) in
+// invitation emails: what the METHOD of a calendar means for the people on it,
+// and the REPLY an attendee would send back.
+
+use chrono::{DateTime, Utc};
+
+use crate::contentline::{self, Component, ContentLine};
+
+const PRODID: &str = "-//Stalwart Labs LLC//jmap-convert//EN";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Participant {
+    pub name: String,
+    // Calendar address without the mailto: scheme.
+    pub address: String,
+    pub partstat: String,
+    pub role: Option<String>,
+    pub rsvp: bool,
+    pub delegated_to: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchedulingMessage {
+    pub method: String,
+    pub summary: String,
+    pub sequence: i64,
+    // Set when the message is about one occurrence of a recurring event.
+    pub recurrence_id: Option<String>,
+    pub organizer: Option<Participant>,
+    pub attendees: Vec<Participant>,
+    pub explanation: String,
+    // Whether a REPLY can be generated for it.
+    pub can_reply: bool,
+}
+
+impl Participant {
+    fn from_line(line: &ContentLine) -> Self {
+        let address = line.value.trim();
+        let address = address
+            .strip_prefix("mailto:")
+            .or_else(|| address.strip_prefix("MAILTO:"))
+            .unwrap_or(address)
+            .to_string();
+        Participant {
+            name: line
+                .param("CN")
+                .filter(|name| !name.trim().is_empty())
+                .map_or_else(|| address.clone(), str::to_string),
+            address,
+            partstat: line
+                .param("PARTSTAT")
+                .unwrap_or("NEEDS-ACTION")
+                .to_ascii_uppercase(),
+            role: line.param("ROLE").map(str::to_ascii_uppercase),
+            rsvp: line
+                .param("RSVP")
+                .is_some_and(|rsvp| rsvp.eq_ignore_ascii_case("TRUE")),
+            delegated_to: line.param("DELEGATED-TO").map(|delegate| {
+                delegate
+                    .trim_start_matches("mailto:")
+                    .trim_start_matches("MAILTO:")
+                    .to_string()
+            }),
+        }
+    }
+}
+
+impl SchedulingMessage {
+    // Reads the METHOD of an iCalendar source and the component it applies
+    // to. Calendars without a METHOD are not scheduling messages.
+    pub fn interpret(source: &str) -> Option<Self> {
+        let components = contentline::components(source);
+        let method = components
+            .first()?
+            .properties
+            .iter()
+            .find(|line| line.name == "METHOD")?
+            .value
+            .trim()
+            .to_ascii_uppercase();
+        let component = main_component(&components)?;
+        let value = |name: &str| {
+            component
+                .properties
+                .iter()
+                .find(|line| line.name == name)
+                .map(|line| line.text())
+        };
+        let summary = value("SUMMARY").unwrap_or_else(|| "an untitled event".to_string());
+        let sequence = value("SEQUENCE")
+            .and_then(|sequence| sequence.trim().parse().ok())
+            .unwrap_or(0);
+        let organizer = component
+            .properties
+            .iter()
+            .find(|line| line.name == "ORGANIZER")
+            .map(Participant::from_line);
+        let attendees = component
+            .properties
+            .iter()
+            .filter(|line| line.name == "ATTENDEE")
+            .map(Participant::from_line)
+            .collect::<Vec<_>>();
+        let recurrence_id = value("RECURRENCE-ID");
+        let status = value("STATUS").map(|status| status.to_ascii_uppercase());
+
+        let mut message = SchedulingMessage {
+            can_reply: matches!(method.as_str(), "REQUEST" | "ADD")
+                && organizer.is_some()
+                && !attendees.is_empty(),
+            explanation: String::new(),
+            method,
+            summary,
+            sequence,
+            recurrence_id,
+            organizer,
+            attendees,
+        };
+        message.explanation = message.explain(status.as_deref(), dtstart(component).as_deref());
+        Some(message)
+    }
+
+    fn explain(&self, status: Option<&str>, start: Option<&str>) -> String {
+        let organizer = self
+            .organizer
+            .as_ref()
+            .map_or("The organizer", |organizer| organizer.name.as_str());
+        let title = match &self.recurrence_id {
+            Some(recurrence_id) => format!(
+                "the occurrence on {} of “{}”",
+                readable_date(recurrence_id),
+                self.summary
+            ),
+            None => format!("“{}”", self.summary),
+        };
+        let invited = self
+            .attendees
+            .iter()
+            .filter(|attendee| {
+                self.organizer
+                    .as_ref()
+                    .is_none_or(|organizer| organizer.address != attendee.address)
+            })
+            .map(|attendee| attendee.name.as_str())
+            .collect::<Vec<_>>();
+        let mut text = match self.method.as_str() {
+            "PUBLISH" => format!(
+                "{} publishes {}. It is for information only and expects no reply.",
+                organizer, title
+            ),
+            "REQUEST" if self.sequence > 0 => format!(
+                "{} sends an update (sequence {}) of {} to {}.",
+                organizer,
+                self.sequence,
+                title,
+                list(&invited)
+            ),
+            "REQUEST" => format!("{} invites {} to {}.", organizer, list(&invited), title),
+            "ADD" => format!(
+                "{} adds occurrences to {} for {}.",
+                organizer,
+                title,
+                list(&invited)
+            ),
+            "REPLY" => self
+                .attendees
+                .iter()
+                .map(|attendee| {
+                    let answer = match attendee.partstat.as_str() {
+                        "ACCEPTED" => "accepted",
+                        "DECLINED" => "declined",
+                        "TENTATIVE" => "tentatively accepted",
+                        "DELEGATED" => {
+                            return format!(
+                                "{} delegated {} to {}.",
+                                attendee.name,
+                                title,
+                                attendee.delegated_to.as_deref().unwrap_or("someone else")
+                            )
+                        }
+                        _ => "has not decided on",
+                    };
+                    format!("{} {} {}.", attendee.name, answer, title)
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            "CANCEL" if status == Some("CANCELLED") || invited.is_empty() => {
+                format!("{} cancelled {}.", organizer, title)
+            }
+            "CANCEL" => format!("{} removed {} from {}.", organizer, list(&invited), title),
+            "REFRESH" => format!(
+                "{} asks the organizer to send the latest version of {}.",
+                list(&invited),
+                title
+            ),
+            "COUNTER" => format!(
+                "{} proposes a change to {}{}.",
+                list(&invited),
+                title,
+                start
+                    .map(|start| format!(", starting {}", readable_date(start)))
+                    .unwrap_or_default()
+            ),
+            "DECLINECOUNTER" => format!(
+                "{} declines the change {} proposed to {}.",
+                organizer,
+                list(&invited),
+                title
+            ),
+            method => format!("The calendar is a {} message about {}.", method, title),
+        };
+        let awaited = self
+            .attendees
+            .iter()
+            .filter(|attendee| attendee.rsvp && attendee.partstat == "NEEDS-ACTION")
+            .map(|attendee| attendee.name.as_str())
+            .collect::<Vec<_>>();
+        if matches!(self.method.as_str(), "REQUEST" | "ADD") && !awaited.is_empty() {
+            text.push_str(&format!(" A reply is requested from {}.", list(&awaited)));
+        }
+        text
+    }
+}
+
+// The REPLY `address` would send to a REQUEST with its answer, stamped with
+// `now`.
+pub fn reply(source: &str, address: &str, partstat: &str, now: DateTime<Utc>) -> Option<String> {
+    let components = contentline::components(source);
+    let component = main_component(&components)?;
+    let attendee = component.properties.iter().find(|line| {
+        line.name == "ATTENDEE"
+            && Participant::from_line(line)
+                .address
+                .eq_ignore_ascii_case(address)
+    })?;
+    let organizer = component
+        .properties
+        .iter()
+        .find(|line| line.name == "ORGANIZER")?;
+    let name = component_name(component);
+
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        &format!("PRODID:{}", PRODID),
+        "VERSION:2.0",
+        "METHOD:REPLY",
+        &format!("BEGIN:{}", name),
+    ] {
+        contentline::fold_line(&mut out, line);
+    }
+    for property in &component.properties {
+        if matches!(
+            property.name.as_str(),
+            "UID" | "SEQUENCE" | "RECURRENCE-ID" | "DTSTART" | "DTEND" | "DUE" | "SUMMARY"
+        ) {
+            contentline::fold_line(&mut out, &property.to_line());
+        }
+    }
+    contentline::fold_line(
+        &mut out,
+        &format!("DTSTAMP:{}Z", contentline::format_datetime(now.naive_utc())),
+    );
+    contentline::fold_line(&mut out, &organizer.to_line());
+    let mut attendee = attendee.clone();
+    attendee.set_param("PARTSTAT", &partstat.to_ascii_uppercase());
+    attendee.remove_param("RSVP");
+    contentline::fold_line(&mut out, &attendee.to_line());
+    contentline::fold_line(&mut out, &format!("END:{}", name));
+    contentline::fold_line(&mut out, "END:VCALENDAR");
+    Some(out)
+}
+
+// The master event, to-do or journal entry, or the first override when the
+// message is only about occurrences.
+fn main_component(components: &[Component]) -> Option<&Component> {
+    let schedulable = components
+        .iter()
+        .filter(|component| matches!(component_name(component), "VEVENT" | "VTODO" | "VJOURNAL"))
+        .collect::<Vec<_>>();
+    schedulable
+        .iter()
+        .find(|component| {
+            !component
+                .properties
+                .iter()
+                .any(|line| line.name == "RECURRENCE-ID")
+        })
+        .or_else(|| schedulable.first())
+        .copied()
+}
+
+fn component_name(component: &Component) -> &str {
+    component
+        .path
+        .rsplit('/')
+        .next()
+        .and_then(|segment| segment.split('[').next())
+        .unwrap_or_default()
+}
+
+fn dtstart(component: &Component) -> Option<String> {
+    component
+        .properties
+        .iter()
+        .find(|line| line.name == "DTSTART")
+        .map(|line| line.value.trim().to_string())
+}
+
+// 20260315T093000Z becomes 2026-03-15 09:30 UTC.
+fn readable_date(value: &str) -> String {
+    let value = value.trim();
+    let date = value
+        .get(..8)
+        .filter(|date| date.bytes().all(|byte| byte.is_ascii_digit()));
+    let Some(date) = date else {
+        return value.to_string();
+    };
+    let mut text = format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..8]);
+    if let Some(time) = value.get(9..13) {
+        text.push_str(&format!(" {}:{}", &time[..2], &time[2..]));
+        if value.ends_with('Z') {
+            text.push_str(" UTC");
+        }
+    }
+    text
+}
+
+fn list(names: &[&str]) -> String {
+    match names {
+        [] => "nobody".to_string(),
+        [name] => name.to_string(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
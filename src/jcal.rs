@@ -0,0 +1,532 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// jCal (RFC 7265) and jCard (RFC 7095), the JSON forms of iCalendar and vCard.
+// A component is written as `[name, properties, components]` and a property as
+// `[name, parameters, type, value...]`, so both map line by line to the text
+// formats and are converted through them.
+
+use serde_json::{Map, Number, Value};
+
+use crate::{contentline, converter::Format};
+
+// Properties holding a list of values separated by commas.
+const MULTI_VALUED: &[&str] = &[
+    "categories",
+    "resources",
+    "exdate",
+    "rdate",
+    "freebusy",
+    "nickname",
+];
+
+// Properties whose value has components separated by semicolons.
+const STRUCTURED: &[&str] = &[
+    "n",
+    "adr",
+    "org",
+    "gender",
+    "request-status",
+    "clientpidmap",
+];
+
+// Tells jCal from jCard by the name of the top-level component.
+pub fn detect(source: &str) -> Option<Format> {
+    let Ok(Value::Array(items)) = serde_json::from_str::<Value>(source.trim()) else {
+        return None;
+    };
+    match items.first()?.as_str()? {
+        name if name.eq_ignore_ascii_case("vcalendar") => Some(Format::JCal),
+        name if name.eq_ignore_ascii_case("vcard") => Some(Format::JCard),
+        _ => None,
+    }
+}
+
+// Writes a jCal or jCard document, or an array of them, as iCalendar or vCard.
+pub fn to_text(source: &str) -> Result<String, String> {
+    let value = serde_json::from_str::<Value>(source.trim()).map_err(|err| err.to_string())?;
+    let Value::Array(items) = &value else {
+        return Err("expected an array".to_string());
+    };
+    let mut out = String::new();
+    if items.first().is_some_and(Value::is_array) {
+        for item in items {
+            write_component(&mut out, item)?;
+        }
+    } else {
+        write_component(&mut out, &value)?;
+    }
+    Ok(out)
+}
+
+// Reads iCalendar or vCard text into jCal or jCard. Several top-level
+// components become an array of them.
+pub fn from_text(source: &str) -> String {
+    let mut roots = components(source);
+    let value = if roots.len() == 1 {
+        roots.remove(0)
+    } else {
+        Value::Array(roots)
+    };
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+// Top-level components of iCalendar or vCard text, in their jCal or jCard
+// form.
+pub fn components(source: &str) -> Vec<Value> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<(String, Vec<Value>, Vec<Value>)> = Vec::new();
+    for (line, text) in contentline::unfold(source) {
+        let Some(line) = contentline::parse_line(line, &text) else {
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" => stack.push((line.value.trim().to_ascii_lowercase(), vec![], vec![])),
+            "END" => {
+                let Some((name, properties, components)) = stack.pop() else {
+                    continue;
+                };
+                let component = if name == "vcard" {
+                    Value::Array(vec![Value::String(name), Value::Array(properties)])
+                } else {
+                    Value::Array(vec![
+                        Value::String(name),
+                        Value::Array(properties),
+                        Value::Array(components),
+                    ])
+                };
+                match stack.last_mut() {
+                    Some((_, _, components)) => components.push(component),
+                    None => roots.push(component),
+                }
+            }
+            _ => {
+                if let Some((component, properties, _)) = stack.last_mut() {
+                    properties.push(read_property(component == "vcard", &line));
+                }
+            }
+        }
+    }
+    roots
+}
+
+fn read_property(vcard: bool, line: &contentline::ContentLine) -> Value {
+    let name = line.name.to_ascii_lowercase();
+    let mut parameters = Map::new();
+    if let Some(group) = &line.group {
+        parameters.insert(
+            "group".to_string(),
+            Value::String(group.to_ascii_lowercase()),
+        );
+    }
+    for (param, value) in &line.params {
+        if param == "VALUE" {
+            continue;
+        }
+        let values = value.split("\",\"").map(str::to_string).collect::<Vec<_>>();
+        parameters.insert(
+            param.to_ascii_lowercase(),
+            if values.len() > 1 {
+                Value::Array(values.into_iter().map(Value::String).collect())
+            } else {
+                Value::String(value.clone())
+            },
+        );
+    }
+    let kind = line
+        .param("VALUE")
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| default_type(vcard, &name, &line.value).to_string());
+
+    let mut property = vec![
+        Value::String(name.clone()),
+        Value::Object(parameters),
+        Value::String(kind.clone()),
+    ];
+    if kind == "recur" {
+        property.push(read_recur(&line.value));
+    } else if name == "geo" && !vcard {
+        property.push(Value::Array(
+            line.value
+                .split(';')
+                .map(|part| read_scalar("float", part))
+                .collect(),
+        ));
+    } else if STRUCTURED.contains(&name.as_str()) {
+        property.push(Value::Array(
+            contentline::split_unescaped(&line.value, ';')
+                .iter()
+                .map(|part| {
+                    let values = contentline::split_unescaped(part, ',');
+                    if values.len() > 1 {
+                        Value::Array(
+                            values
+                                .iter()
+                                .map(|value| Value::String(contentline::unescape(value)))
+                                .collect(),
+                        )
+                    } else {
+                        Value::String(contentline::unescape(part))
+                    }
+                })
+                .collect(),
+        ));
+    } else if MULTI_VALUED.contains(&name.as_str()) {
+        property.extend(
+            contentline::split_unescaped(&line.value, ',')
+                .iter()
+                .map(|value| read_scalar(&kind, value)),
+        );
+    } else {
+        property.push(read_scalar(&kind, &line.value));
+    }
+    Value::Array(property)
+}
+
+fn read_scalar(kind: &str, value: &str) -> Value {
+    match kind {
+        "text" | "unknown" => Value::String(contentline::unescape(value)),
+        "integer" => value
+            .trim()
+            .parse::<i64>()
+            .map_or_else(|_| Value::String(value.to_string()), Value::from),
+        "float" => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| Value::String(value.to_string()), Value::Number),
+        "boolean" => Value::Bool(value.trim().eq_ignore_ascii_case("TRUE")),
+        "date" | "date-time" | "timestamp" | "date-and-or-time" => {
+            Value::String(extended_date(value.trim()))
+        }
+        "utc-offset" => Value::String(extended_offset(value.trim())),
+        "period" => Value::String(
+            value
+                .split('/')
+                .map(extended_date)
+                .collect::<Vec<_>>()
+                .join("/"),
+        ),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+fn read_recur(value: &str) -> Value {
+    let mut rule = Map::new();
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let values = value
+            .split(',')
+            .map(|value| match key.as_str() {
+                "until" => Value::String(extended_date(value)),
+                "freq" | "wkst" | "byday" => Value::String(value.to_string()),
+                _ => value
+                    .parse::<i64>()
+                    .map_or_else(|_| Value::String(value.to_string()), Value::from),
+            })
+            .collect::<Vec<_>>();
+        rule.insert(
+            key,
+            if values.len() > 1 {
+                Value::Array(values)
+            } else {
+                values.into_iter().next().unwrap_or(Value::Null)
+            },
+        );
+    }
+    Value::Object(rule)
+}
+
+// Value type of a property written without a VALUE parameter.
+fn default_type(vcard: bool, name: &str, value: &str) -> &'static str {
+    let date = || {
+        if value.contains('T') {
+            "date-time"
+        } else {
+            "date"
+        }
+    };
+    if vcard {
+        return match name {
+            "bday" | "anniversary" | "deathdate" => "date-and-or-time",
+            "rev" => "timestamp",
+            "url" | "photo" | "logo" | "sound" | "key" | "source" | "member" | "impp"
+            | "caladruri" | "caluri" | "fburl" | "related" | "geo" => "uri",
+            "tel" if value.starts_with("tel:") => "uri",
+            _ => "text",
+        };
+    }
+    match name {
+        "dtstart" | "dtend" | "due" | "recurrence-id" | "exdate" | "rdate" => date(),
+        "dtstamp" | "created" | "last-modified" | "completed" | "acknowledged" => "date-time",
+        "rrule" | "exrule" => "recur",
+        "duration" => "duration",
+        "trigger" if value.trim_start_matches(['-', '+']).starts_with('P') => "duration",
+        "trigger" => "date-time",
+        "geo" => "float",
+        "priority" | "sequence" | "percent-complete" | "repeat" => "integer",
+        "organizer" | "attendee" => "cal-address",
+        "url" | "attach" | "tzurl" | "source" => "uri",
+        "tzoffsetfrom" | "tzoffsetto" => "utc-offset",
+        "freebusy" => "period",
+        _ => "text",
+    }
+}
+
+fn write_component(out: &mut String, component: &Value) -> Result<(), String> {
+    let Some([name, properties, rest @ ..]) = component.as_array().map(Vec::as_slice) else {
+        return Err("expected a component as [name, properties, components]".to_string());
+    };
+    let name = name
+        .as_str()
+        .ok_or("component names must be strings")?
+        .to_ascii_uppercase();
+    let properties = properties
+        .as_array()
+        .ok_or_else(|| format!("the properties of {} must be an array", name))?;
+    let vcard = name == "VCARD";
+    contentline::fold_line(out, &format!("BEGIN:{}", name));
+    for property in properties {
+        contentline::fold_line(out, &write_property(vcard, property)?.to_line());
+    }
+    if let Some(components) = rest.first() {
+        for component in components
+            .as_array()
+            .ok_or_else(|| format!("the components of {} must be an array", name))?
+        {
+            write_component(out, component)?;
+        }
+    }
+    contentline::fold_line(out, &format!("END:{}", name));
+    Ok(())
+}
+
+fn write_property(vcard: bool, property: &Value) -> Result<contentline::ContentLine, String> {
+    let Some([name, parameters, kind, values @ ..]) = property.as_array().map(Vec::as_slice) else {
+        return Err("expected a property as [name, parameters, type, value]".to_string());
+    };
+    let name = name
+        .as_str()
+        .ok_or("property names must be strings")?
+        .to_ascii_uppercase();
+    let kind = kind
+        .as_str()
+        .ok_or_else(|| format!("the type of {} must be a string", name))?
+        .to_ascii_lowercase();
+    let mut line = contentline::ContentLine {
+        name: name.clone(),
+        ..Default::default()
+    };
+    for (param, value) in parameters
+        .as_object()
+        .ok_or_else(|| format!("the parameters of {} must be an object", name))?
+    {
+        let value = match value {
+            Value::Array(values) => values
+                .iter()
+                .map(scalar_text)
+                .collect::<Vec<_>>()
+                .join("\",\""),
+            value => scalar_text(value),
+        };
+        if param.eq_ignore_ascii_case("group") {
+            line.group = Some(value);
+        } else {
+            line.params.push((param.to_ascii_uppercase(), value));
+        }
+    }
+    line.value = values
+        .iter()
+        .map(|value| match value {
+            Value::Object(rule) => write_recur(rule),
+            Value::Array(parts) => parts
+                .iter()
+                .map(|part| match part {
+                    Value::Array(values) => values
+                        .iter()
+                        .map(|value| write_scalar(&kind, value))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                    part => write_scalar(&kind, part),
+                })
+                .collect::<Vec<_>>()
+                .join(";"),
+            value => write_scalar(&kind, value),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    // The type is only written when it is not the one the property has by
+    // default.
+    if kind != "unknown" && kind != default_type(vcard, &name.to_ascii_lowercase(), &line.value) {
+        line.params
+            .push(("VALUE".to_string(), kind.to_ascii_uppercase()));
+    }
+    Ok(line)
+}
+
+fn write_scalar(kind: &str, value: &Value) -> String {
+    let text = scalar_text(value);
+    match kind {
+        "text" | "unknown" => contentline::escape(&text),
+        "date" | "date-time" | "timestamp" | "date-and-or-time" | "period" | "utc-offset" => {
+            basic_date(&text)
+        }
+        _ => text,
+    }
+}
+
+// FREQ is written first, as older readers expect.
+fn write_recur(rule: &Map<String, Value>) -> String {
+    let (freq, rest): (Vec<_>, Vec<_>) = rule
+        .iter()
+        .partition(|(key, _)| key.eq_ignore_ascii_case("freq"));
+    freq.into_iter()
+        .chain(rest)
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Array(values) => {
+                    values.iter().map(scalar_text).collect::<Vec<_>>().join(",")
+                }
+                value => scalar_text(value),
+            };
+            let value = if key.eq_ignore_ascii_case("until") {
+                basic_date(&value)
+            } else {
+                value
+            };
+            format!("{}={}", key.to_ascii_uppercase(), value)
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Bool(true) => "TRUE".to_string(),
+        Value::Bool(false) => "FALSE".to_string(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+// 20260315T093000Z becomes 2026-03-15T09:30:00Z. Partial vCard dates such as
+// --0315 are left as written.
+fn extended_date(value: &str) -> String {
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let mut out = if date.len() == 8 && date.bytes().all(|byte| byte.is_ascii_digit()) {
+        format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..])
+    } else {
+        date.to_string()
+    };
+    if let Some(time) = time {
+        out.push('T');
+        let digits = time.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 6 {
+            out.push_str(&format!(
+                "{}:{}:{}{}",
+                &time[..2],
+                &time[2..4],
+                &time[4..6],
+                &time[6..]
+            ));
+        } else {
+            out.push_str(time);
+        }
+    }
+    out
+}
+
+fn extended_offset(value: &str) -> String {
+    // +0100 becomes +01:00 and +013000 becomes +01:30:00, anything else is
+    // left as written.
+    let digits = value
+        .strip_prefix(['+', '-'])
+        .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_digit()));
+    match digits.map(str::len) {
+        Some(4) => format!("{}:{}", &value[..3], &value[3..]),
+        Some(6) => format!("{}:{}:{}", &value[..3], &value[3..5], &value[5..]),
+        _ => value.to_string(),
+    }
+}
+
+// The reverse of `extended_date`, applied to each part of a period. A UTC
+// offset after the time keeps its sign.
+pub fn basic_date(value: &str) -> String {
+    if value.contains('/') {
+        value
+            .split('/')
+            .map(basic_date)
+            .collect::<Vec<_>>()
+            .join("/")
+    } else if value.len() >= 10 && value.as_bytes()[4] == b'-' && value.as_bytes()[7] == b'-' {
+        let (date, time) = match value.split_once('T') {
+            Some((date, time)) => (date, Some(time)),
+            None => (value, None),
+        };
+        let mut out = date.replace('-', "");
+        if let Some(time) = time {
+            let (time, offset) = time.split_at(time.find(['+', '-']).unwrap_or(time.len()));
+            out.push('T');
+            out.push_str(&time.replace(':', ""));
+            out.push_str(&offset.replace(':', ""));
+        }
+        out
+    } else if value.len() >= 6 && value.starts_with(['+', '-']) && value.as_bytes()[3] == b':' {
+        value.replace(':', "")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_negative_offsets() {
+        assert_eq!(basic_date("2009-08-08T1430-0500"), "20090808T1430-0500");
+        assert_eq!(
+            basic_date("1987-09-27T08:30:00-06:00"),
+            "19870927T083000-0600"
+        );
+        assert_eq!(
+            basic_date("1997-01-01T18:00:00-05:00/PT1H"),
+            "19970101T180000-0500/PT1H"
+        );
+        assert_eq!(basic_date("-05:00"), "-0500");
+        assert_eq!(basic_date("2009-08-08"), "20090808");
+    }
+
+    #[test]
+    fn writes_negative_offsets() {
+        let text = to_text(
+            r#"["vcard",[["version",{},"text","4.0"],["anniversary",{},"date-and-or-time","2009-08-08T14:30-05:00"],["bday",{},"date-and-or-time","1987-09-27T08:30:00-06:00"]],[]]"#,
+        )
+        .unwrap();
+        assert!(text.contains("ANNIVERSARY:20090808T1430-0500\r\n"));
+        assert!(text.contains("BDAY:19870927T083000-0600\r\n"));
+    }
+
+    #[test]
+    fn passes_non_ascii_offsets_through() {
+        assert_eq!(extended_offset("-0700"), "-07:00");
+        assert_eq!(extended_offset("+013000"), "+01:30:00");
+        assert_eq!(extended_offset("-070é0"), "-070é0");
+        let json = from_text(
+            "BEGIN:VCALENDAR\r\nBEGIN:VTIMEZONE\r\nTZID:X\r\nBEGIN:STANDARD\r\nTZOFFSETFROM:-070é0\r\nTZOFFSETTO:-0800\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\nEND:VCALENDAR\r\n",
+        );
+        assert!(json.contains("-070é0"));
+        assert!(json.contains("-08:00"));
+    }
+}
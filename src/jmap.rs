@@ -0,0 +1,128 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// JMAP (RFC 8620) request bodies that create converted objects on a server:
+// CalendarEvent/set and Task/set for JSCalendar, ContactCard/set (RFC 9610)
+// for JSContact.
+
+use serde_json::{json, Map, Value};
+
+const CORE: &str = "urn:ietf:params:jmap:core";
+
+// Stand-ins for the ids only the server knows.
+pub const ACCOUNT_ID: &str = "ACCOUNT_ID";
+const CALENDAR_ID: &str = "CALENDAR_ID";
+const TASK_LIST_ID: &str = "TASK_LIST_ID";
+const ADDRESS_BOOK_ID: &str = "ADDRESS_BOOK_ID";
+
+struct SetCall {
+    method: &'static str,
+    capability: &'static str,
+    create: Map<String, Value>,
+}
+
+// Wraps a JSCalendar or JSContact document in a request with one /set call
+// per object type, each creating the objects it holds.
+pub fn set_request(source: &str) -> Result<String, String> {
+    let value = serde_json::from_str::<Value>(source).map_err(|err| err.to_string())?;
+    let mut objects = Vec::new();
+    collect(value, &mut objects);
+
+    let mut calls: Vec<SetCall> = Vec::new();
+    // Creation ids are shared by the whole request, so they run on across
+    // calls.
+    for (number, mut object) in objects.into_iter().enumerate() {
+        let (method, capability, container) = match object
+            .get("@type")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+        {
+            "Event" => (
+                "CalendarEvent/set",
+                "urn:ietf:params:jmap:calendars",
+                ("calendarIds", json!({ CALENDAR_ID: true })),
+            ),
+            "Task" => (
+                "Task/set",
+                "urn:ietf:params:jmap:tasks",
+                ("taskListId", json!(TASK_LIST_ID)),
+            ),
+            "Card" => (
+                "ContactCard/set",
+                "urn:ietf:params:jmap:contacts",
+                ("addressBookIds", json!({ ADDRESS_BOOK_ID: true })),
+            ),
+            kind => {
+                return Err(format!(
+                    "Objects of type {:?} cannot be created over JMAP.",
+                    kind
+                ))
+            }
+        };
+        object.entry(container.0).or_insert(container.1);
+        let index = match calls.iter().position(|call| call.method == method) {
+            Some(index) => index,
+            None => {
+                calls.push(SetCall {
+                    method,
+                    capability,
+                    create: Map::new(),
+                });
+                calls.len() - 1
+            }
+        };
+        calls[index]
+            .create
+            .insert(format!("c{}", number + 1), Value::Object(object));
+    }
+    if calls.is_empty() {
+        return Err("There are no objects to create.".to_string());
+    }
+
+    let mut using = vec![CORE];
+    for call in &calls {
+        if !using.contains(&call.capability) {
+            using.push(call.capability);
+        }
+    }
+    let method_calls = calls
+        .into_iter()
+        .enumerate()
+        .map(|(index, call)| {
+            json!([
+                call.method,
+                { "accountId": ACCOUNT_ID, "create": call.create },
+                index.to_string()
+            ])
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&json!({
+        "using": using,
+        "methodCalls": method_calls,
+    }))
+    .map_err(|err| err.to_string())
+}
+
+// Objects of a document, with groups and arrays flattened.
+fn collect(value: Value, objects: &mut Vec<Map<String, Value>>) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                collect(value, objects);
+            }
+        }
+        Value::Object(mut object) => {
+            if object.get("@type").and_then(Value::as_str) == Some("Group") {
+                if let Some(entries) = object.remove("entries") {
+                    collect(entries, objects);
+                }
+            } else {
+                objects.push(object);
+            }
+        }
+        _ => {}
+    }
+}
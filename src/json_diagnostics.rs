@@ -0,0 +1,720 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::expected::escape_pointer;
+
+use JsonType::{Array, Boolean, Object, String as Text, StringOrNull, UnsignedInt};
+use Shape::{ArrayOf, MapOf, MapOfBool, Value as Is};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonType {
+    Null,
+    Boolean,
+    UnsignedInt,
+    Int,
+    Number,
+    String,
+    StringOrNull,
+    Array,
+    Object,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    // The text is not JSON, with the line and column of the first error.
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    WrongType {
+        expected: JsonType,
+        found: JsonType,
+    },
+    // Not defined by RFC 8984 or RFC 9553 and not a vendor property.
+    Unknown,
+    // In the source but missing from its roundtrip.
+    Ignored,
+}
+
+// A problem with the value at `path`, the unescaped JSON pointer segments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: Vec<String>,
+    pub problem: Problem,
+}
+
+// Diagnostics arranged by the object they are in, for showing as a tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Node {
+    pub key: String,
+    pub pointer: String,
+    pub problems: Vec<Problem>,
+    pub children: Vec<Node>,
+}
+
+// How the value of a property is checked.
+#[derive(Clone, Copy)]
+enum Shape {
+    Value(JsonType),
+    // An object of the named type, a map of them or an array of them.
+    Object(&'static str),
+    MapOf(&'static str),
+    ArrayOf(&'static str),
+    // A set, a map whose values are all true.
+    MapOfBool,
+}
+
+// RFC 8984 section 4, properties common to Group, Event and Task.
+const CALENDAR_COMMON: &[(&str, Shape)] = &[
+    ("@type", Is(Text)),
+    ("uid", Is(Text)),
+    ("relatedTo", Is(Object)),
+    ("prodId", Is(Text)),
+    ("created", Is(Text)),
+    ("updated", Is(Text)),
+    ("sequence", Is(UnsignedInt)),
+    ("method", Is(Text)),
+    ("title", Is(Text)),
+    ("description", Is(Text)),
+    ("descriptionContentType", Is(Text)),
+    ("showWithoutTime", Is(Boolean)),
+    ("locations", MapOf("Location")),
+    ("virtualLocations", MapOf("VirtualLocation")),
+    ("links", MapOf("Link")),
+    ("locale", Is(Text)),
+    ("keywords", MapOfBool),
+    ("categories", MapOfBool),
+    ("color", Is(Text)),
+    ("recurrenceId", Is(Text)),
+    ("recurrenceIdTimeZone", Is(StringOrNull)),
+    ("recurrenceRules", ArrayOf("RecurrenceRule")),
+    ("excludedRecurrenceRules", ArrayOf("RecurrenceRule")),
+    ("recurrenceOverrides", Is(Object)),
+    ("excluded", Is(Boolean)),
+    ("priority", Is(JsonType::Int)),
+    ("freeBusyStatus", Is(Text)),
+    ("privacy", Is(Text)),
+    ("replyTo", Is(Object)),
+    ("sentBy", Is(Text)),
+    ("participants", MapOf("Participant")),
+    ("requestStatus", Is(Text)),
+    ("useDefaultAlerts", Is(Boolean)),
+    ("alerts", MapOf("Alert")),
+    ("localizations", Is(Object)),
+    ("timeZone", Is(StringOrNull)),
+    ("timeZones", Is(Object)),
+];
+
+const OBJECT_TYPES: &[(&str, &[(&str, Shape)])] = &[
+    ("Group", &[("entries", Is(Array)), ("source", Is(Text))]),
+    (
+        "Event",
+        &[
+            ("start", Is(Text)),
+            ("duration", Is(Text)),
+            ("status", Is(Text)),
+        ],
+    ),
+    (
+        "Task",
+        &[
+            ("start", Is(Text)),
+            ("due", Is(Text)),
+            ("estimatedDuration", Is(Text)),
+            ("percentComplete", Is(UnsignedInt)),
+            ("progress", Is(Text)),
+            ("progressUpdated", Is(Text)),
+        ],
+    ),
+    (
+        "Participant",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("email", Is(Text)),
+            ("description", Is(Text)),
+            ("sendTo", Is(Object)),
+            ("calendarAddress", Is(Text)),
+            ("kind", Is(Text)),
+            ("roles", MapOfBool),
+            ("locationId", Is(Text)),
+            ("language", Is(Text)),
+            ("participationStatus", Is(Text)),
+            ("participationComment", Is(Text)),
+            ("expectReply", Is(Boolean)),
+            ("scheduleAgent", Is(Text)),
+            ("scheduleForceSend", Is(Boolean)),
+            ("scheduleSequence", Is(UnsignedInt)),
+            ("scheduleStatus", Is(Array)),
+            ("scheduleUpdated", Is(Text)),
+            ("sentBy", Is(Text)),
+            ("invitedBy", Is(Text)),
+            ("delegatedTo", MapOfBool),
+            ("delegatedFrom", MapOfBool),
+            ("memberOf", MapOfBool),
+            ("links", MapOf("Link")),
+            ("progress", Is(Text)),
+            ("progressUpdated", Is(Text)),
+            ("percentComplete", Is(UnsignedInt)),
+        ],
+    ),
+    (
+        "Location",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("description", Is(Text)),
+            ("locationTypes", MapOfBool),
+            ("relativeTo", Is(Text)),
+            ("timeZone", Is(Text)),
+            ("coordinates", Is(Text)),
+            ("links", MapOf("Link")),
+        ],
+    ),
+    (
+        "VirtualLocation",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("description", Is(Text)),
+            ("uri", Is(Text)),
+            ("features", MapOfBool),
+        ],
+    ),
+    (
+        "Link",
+        &[
+            ("@type", Is(Text)),
+            ("href", Is(Text)),
+            ("uri", Is(Text)),
+            ("cid", Is(Text)),
+            ("contentType", Is(Text)),
+            ("mediaType", Is(Text)),
+            ("size", Is(UnsignedInt)),
+            ("rel", Is(Text)),
+            ("kind", Is(Text)),
+            ("display", Is(Text)),
+            ("title", Is(Text)),
+            ("contexts", MapOfBool),
+            ("pref", Is(UnsignedInt)),
+            ("label", Is(Text)),
+        ],
+    ),
+    (
+        "Alert",
+        &[
+            ("@type", Is(Text)),
+            ("trigger", Is(Object)),
+            ("acknowledged", Is(Text)),
+            ("relatedTo", Is(Object)),
+            ("action", Is(Text)),
+        ],
+    ),
+    (
+        "RecurrenceRule",
+        &[
+            ("@type", Is(Text)),
+            ("frequency", Is(Text)),
+            ("interval", Is(UnsignedInt)),
+            ("rscale", Is(Text)),
+            ("skip", Is(Text)),
+            ("firstDayOfWeek", Is(Text)),
+            ("byDay", Is(Array)),
+            ("byMonthDay", Is(Array)),
+            ("byMonth", Is(Array)),
+            ("byYearDay", Is(Array)),
+            ("byWeekNo", Is(Array)),
+            ("byHour", Is(Array)),
+            ("byMinute", Is(Array)),
+            ("bySecond", Is(Array)),
+            ("bySetPosition", Is(Array)),
+            ("count", Is(UnsignedInt)),
+            ("until", Is(Text)),
+        ],
+    ),
+    // RFC 9553 section 2.
+    (
+        "Card",
+        &[
+            ("@type", Is(Text)),
+            ("version", Is(Text)),
+            ("created", Is(Text)),
+            ("kind", Is(Text)),
+            ("language", Is(Text)),
+            ("members", MapOfBool),
+            ("prodId", Is(Text)),
+            ("relatedTo", Is(Object)),
+            ("uid", Is(Text)),
+            ("updated", Is(Text)),
+            ("name", Shape::Object("Name")),
+            ("nicknames", MapOf("Nickname")),
+            ("organizations", MapOf("Organization")),
+            ("speakToAs", Is(Object)),
+            ("titles", MapOf("Title")),
+            ("emails", MapOf("EmailAddress")),
+            ("onlineServices", MapOf("OnlineService")),
+            ("phones", MapOf("Phone")),
+            ("preferredLanguages", Is(Object)),
+            ("calendars", MapOf("Link")),
+            ("schedulingAddresses", Is(Object)),
+            ("addresses", MapOf("Address")),
+            ("cryptoKeys", MapOf("Link")),
+            ("directories", MapOf("Link")),
+            ("links", MapOf("Link")),
+            ("media", MapOf("Link")),
+            ("localizations", Is(Object)),
+            ("anniversaries", MapOf("Anniversary")),
+            ("keywords", MapOfBool),
+            ("notes", MapOf("Note")),
+            ("personalInfo", Is(Object)),
+        ],
+    ),
+    (
+        "Name",
+        &[
+            ("@type", Is(Text)),
+            ("components", Is(Array)),
+            ("isOrdered", Is(Boolean)),
+            ("defaultSeparator", Is(Text)),
+            ("full", Is(Text)),
+            ("sortAs", Is(Object)),
+            ("phoneticScript", Is(Text)),
+            ("phoneticSystem", Is(Text)),
+        ],
+    ),
+    (
+        "Nickname",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("contexts", MapOfBool),
+            ("pref", Is(UnsignedInt)),
+        ],
+    ),
+    (
+        "Organization",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("units", Is(Array)),
+            ("sortAs", Is(Text)),
+            ("contexts", MapOfBool),
+        ],
+    ),
+    (
+        "Title",
+        &[
+            ("@type", Is(Text)),
+            ("name", Is(Text)),
+            ("kind", Is(Text)),
+            ("organizationId", Is(Text)),
+        ],
+    ),
+    (
+        "EmailAddress",
+        &[
+            ("@type", Is(Text)),
+            ("address", Is(Text)),
+            ("contexts", MapOfBool),
+            ("pref", Is(UnsignedInt)),
+            ("label", Is(Text)),
+        ],
+    ),
+    (
+        "OnlineService",
+        &[
+            ("@type", Is(Text)),
+            ("service", Is(Text)),
+            ("uri", Is(Text)),
+            ("user", Is(Text)),
+            ("contexts", MapOfBool),
+            ("pref", Is(UnsignedInt)),
+            ("label", Is(Text)),
+        ],
+    ),
+    (
+        "Phone",
+        &[
+            ("@type", Is(Text)),
+            ("number", Is(Text)),
+            ("features", MapOfBool),
+            ("contexts", MapOfBool),
+            ("pref", Is(UnsignedInt)),
+            ("label", Is(Text)),
+        ],
+    ),
+    (
+        "Address",
+        &[
+            ("@type", Is(Text)),
+            ("components", Is(Array)),
+            ("isOrdered", Is(Boolean)),
+            ("countryCode", Is(Text)),
+            ("coordinates", Is(Text)),
+            ("timeZone", Is(Text)),
+            ("contexts", MapOfBool),
+            ("full", Is(Text)),
+            ("defaultSeparator", Is(Text)),
+            ("pref", Is(UnsignedInt)),
+            ("phoneticScript", Is(Text)),
+            ("phoneticSystem", Is(Text)),
+        ],
+    ),
+    (
+        "Anniversary",
+        &[
+            ("@type", Is(Text)),
+            ("kind", Is(Text)),
+            ("date", Is(Object)),
+            ("place", Shape::Object("Address")),
+        ],
+    ),
+    (
+        "Note",
+        &[
+            ("@type", Is(Text)),
+            ("note", Is(Text)),
+            ("created", Is(Text)),
+            ("author", Is(Object)),
+        ],
+    ),
+];
+
+impl JsonType {
+    pub fn of(value: &Value) -> JsonType {
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Boolean,
+            Value::Number(number) if number.is_u64() => JsonType::UnsignedInt,
+            Value::Number(number) if number.is_i64() => JsonType::Int,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        match self {
+            JsonType::Int => value.is_i64() || value.is_u64(),
+            JsonType::Number => value.is_number(),
+            JsonType::StringOrNull => value.is_string() || value.is_null(),
+            expected => JsonType::of(value) == *expected,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Boolean => "Boolean",
+            JsonType::UnsignedInt => "UnsignedInt",
+            JsonType::Int => "Int",
+            JsonType::Number => "Number",
+            JsonType::String => "String",
+            JsonType::StringOrNull => "String or null",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+}
+
+impl Problem {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Problem::Syntax { .. } => "json-problem-syntax",
+            Problem::WrongType { .. } => "json-problem-wrong-type",
+            Problem::Unknown => "json-problem-unknown",
+            Problem::Ignored => "json-problem-ignored",
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Problem::Syntax { .. } | Problem::WrongType { .. })
+    }
+}
+
+impl Diagnostic {
+    pub fn pointer(&self) -> String {
+        self.path
+            .iter()
+            .map(|key| format!("/{}", escape_pointer(key)))
+            .collect()
+    }
+}
+
+// Problems of a JSCalendar or JSContact document: syntax errors, values of
+// the wrong type and, given the roundtrip of a conversion that succeeded,
+// the properties it did not keep. Without a roundtrip, properties no RFC
+// defines are listed instead.
+pub fn diagnose(source: &str, roundtrip: Option<&str>) -> Vec<Diagnostic> {
+    let value = match serde_json::from_str::<Value>(source.trim()) {
+        Ok(value) => value,
+        Err(err) => {
+            let message = err.to_string();
+            // serde_json appends the position, which is reported on its own.
+            let message = message
+                .split(" at line ")
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            return vec![Diagnostic {
+                path: vec![],
+                problem: Problem::Syntax {
+                    message,
+                    line: err.line(),
+                    column: err.column(),
+                },
+            }];
+        }
+    };
+    let roundtrip = roundtrip.and_then(|roundtrip| serde_json::from_str::<Value>(roundtrip).ok());
+    let mut out = Vec::new();
+    match (&value, &roundtrip) {
+        // Several entries, joined into an array on both sides.
+        (Value::Array(entries), roundtrip) => {
+            for (index, entry) in entries.iter().enumerate() {
+                let mut path = vec![index.to_string()];
+                let roundtrip = roundtrip.as_ref().map(|roundtrip| roundtrip.get(index));
+                diagnose_entry(entry, roundtrip, &mut path, &mut out);
+            }
+        }
+        (value, roundtrip) => {
+            diagnose_entry(value, roundtrip.as_ref().map(Some), &mut vec![], &mut out)
+        }
+    }
+    out
+}
+
+// `roundtrip` is None when the conversion failed and Some(None) when its
+// output has no counterpart for the entry.
+fn diagnose_entry(
+    value: &Value,
+    roundtrip: Option<Option<&Value>>,
+    path: &mut Vec<String>,
+    out: &mut Vec<Diagnostic>,
+) {
+    let mut errors = Vec::new();
+    check_object(value, None, roundtrip.is_none(), path, &mut errors);
+    if let Some(Some(roundtrip)) = roundtrip {
+        // An Event or Task comes back inside a Group.
+        let roundtrip = match (value.get("@type"), roundtrip.get("@type")) {
+            (Some(kind), Some(wrapper)) if kind != wrapper => roundtrip
+                .get("entries")
+                .and_then(|entries| entries.get(0))
+                .unwrap_or(roundtrip),
+            _ => roundtrip,
+        };
+        let mut ignored = Vec::new();
+        compare(value, roundtrip, path, &mut ignored);
+        // A value of the wrong type is already reported.
+        ignored.retain(|diagnostic| !errors.iter().any(|error| error.path == diagnostic.path));
+        errors.extend(ignored);
+    }
+    out.extend(errors);
+}
+
+// Checks an object against the schema of its @type, or of `kind` for
+// nested objects that may leave it out.
+fn check_object(
+    value: &Value,
+    kind: Option<&str>,
+    unknown: bool,
+    path: &mut Vec<String>,
+    out: &mut Vec<Diagnostic>,
+) {
+    let Value::Object(object) = value else {
+        out.push(Diagnostic {
+            path: path.clone(),
+            problem: Problem::WrongType {
+                expected: JsonType::Object,
+                found: JsonType::of(value),
+            },
+        });
+        return;
+    };
+    let kind = object.get("@type").and_then(Value::as_str).or(kind);
+    let Some((kind, properties)) = kind.and_then(|kind| {
+        OBJECT_TYPES
+            .iter()
+            .find(|(name, _)| *name == kind)
+            .map(|(name, properties)| (*name, *properties))
+    }) else {
+        return;
+    };
+    let common = match kind {
+        "Group" | "Event" | "Task" => CALENDAR_COMMON,
+        _ => &[],
+    };
+    for (key, value) in object {
+        let shape = properties
+            .iter()
+            .chain(common)
+            .find(|(name, _)| name == key)
+            .map(|(_, shape)| *shape);
+        path.push(key.clone());
+        match shape {
+            Some(shape) => check_shape(value, shape, unknown, path, out),
+            // Vendor properties are prefixed with a domain name.
+            None if unknown && !key.contains(':') => out.push(Diagnostic {
+                path: path.clone(),
+                problem: Problem::Unknown,
+            }),
+            None => {}
+        }
+        path.pop();
+    }
+    if kind == "Group" {
+        if let Some(Value::Array(entries)) = object.get("entries") {
+            for (index, entry) in entries.iter().enumerate() {
+                path.extend(["entries".to_string(), index.to_string()]);
+                check_object(entry, None, unknown, path, out);
+                path.truncate(path.len() - 2);
+            }
+        }
+    }
+}
+
+fn check_shape(
+    value: &Value,
+    shape: Shape,
+    unknown: bool,
+    path: &mut Vec<String>,
+    out: &mut Vec<Diagnostic>,
+) {
+    let mut wrong = |path: &[String], expected: JsonType, value: &Value| {
+        out.push(Diagnostic {
+            path: path.to_vec(),
+            problem: Problem::WrongType {
+                expected,
+                found: JsonType::of(value),
+            },
+        })
+    };
+    match (shape, value) {
+        (Shape::Value(expected), value) => {
+            if !expected.accepts(value) {
+                wrong(path, expected, value);
+            }
+        }
+        (Shape::Object(kind), value) => check_object(value, Some(kind), unknown, path, out),
+        (Shape::MapOf(kind), Value::Object(map)) => {
+            for (key, value) in map {
+                path.push(key.clone());
+                check_object(value, Some(kind), unknown, path, out);
+                path.pop();
+            }
+        }
+        (Shape::ArrayOf(kind), Value::Array(items)) => {
+            for (index, value) in items.iter().enumerate() {
+                path.push(index.to_string());
+                check_object(value, Some(kind), unknown, path, out);
+                path.pop();
+            }
+        }
+        (Shape::MapOfBool, Value::Object(map)) => {
+            for (key, value) in map {
+                if value != &Value::Bool(true) {
+                    path.push(key.clone());
+                    wrong(path, JsonType::Boolean, value);
+                    path.pop();
+                }
+            }
+        }
+        (Shape::ArrayOf(_), value) => wrong(path, JsonType::Array, value),
+        (_, value) => wrong(path, JsonType::Object, value),
+    }
+}
+
+// Properties of the source that are missing from the roundtrip. Map entries
+// whose ids changed are paired in order, and properties that only held a
+// default or empty value are not reported.
+fn compare(source: &Value, roundtrip: &Value, path: &mut Vec<String>, out: &mut Vec<Diagnostic>) {
+    match (source, roundtrip) {
+        (Value::Object(source), Value::Object(roundtrip)) => {
+            let map = is_map(source);
+            let mut unmatched = roundtrip
+                .iter()
+                .filter(|(key, _)| !source.contains_key(*key))
+                .map(|(_, value)| value);
+            for (key, value) in source {
+                path.push(key.clone());
+                match roundtrip.get(key) {
+                    Some(other) => compare(value, other, path, out),
+                    None if map => match unmatched.next() {
+                        Some(other) => compare(value, other, path, out),
+                        None => ignored(value, path, out),
+                    },
+                    None if key != "@type" => ignored(value, path, out),
+                    None => {}
+                }
+                path.pop();
+            }
+        }
+        (Value::Array(source), Value::Array(roundtrip)) => {
+            for (index, value) in source.iter().enumerate() {
+                path.push(index.to_string());
+                match roundtrip.get(index) {
+                    Some(other) => compare(value, other, path, out),
+                    None => ignored(value, path, out),
+                }
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn ignored(value: &Value, path: &[String], out: &mut Vec<Diagnostic>) {
+    let empty = match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Number(number) => number.as_f64() == Some(0.0),
+        Value::String(text) => text.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        Value::Bool(true) => false,
+    };
+    if !empty {
+        out.push(Diagnostic {
+            path: path.to_vec(),
+            problem: Problem::Ignored,
+        });
+    }
+}
+
+// Maps of ids to objects, such as participants, have no @type of their own.
+fn is_map(object: &Map<String, Value>) -> bool {
+    !object.contains_key("@type") && object.values().all(Value::is_object)
+}
+
+// The diagnostics as a tree of the objects they are in.
+pub fn tree(diagnostics: &[Diagnostic]) -> Node {
+    let mut root = Node::default();
+    for diagnostic in diagnostics {
+        let mut node = &mut root;
+        let mut pointer = String::new();
+        for key in &diagnostic.path {
+            pointer.push('/');
+            pointer.push_str(&escape_pointer(key));
+            let position = match node.children.iter().position(|child| child.key == *key) {
+                Some(position) => position,
+                None => {
+                    node.children.push(Node {
+                        key: key.clone(),
+                        pointer: pointer.clone(),
+                        ..Default::default()
+                    });
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[position];
+        }
+        node.problems.push(diagnostic.problem.clone());
+    }
+    root
+}
@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Keyboard shortcuts of the page. Every binding uses Ctrl, or Cmd on macOS.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Convert,
+    Download,
+    Sample,
+    Undo,
+    Redo,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Binding {
+    pub action: Action,
+    // KeyboardEvent.key, lowercase for letters.
+    pub key: &'static str,
+    pub shift: bool,
+}
+
+pub const BINDINGS: &[Binding] = &[
+    Binding {
+        action: Action::Convert,
+        key: "enter",
+        shift: false,
+    },
+    Binding {
+        action: Action::Download,
+        key: "s",
+        shift: false,
+    },
+    Binding {
+        action: Action::Sample,
+        key: "k",
+        shift: false,
+    },
+    Binding {
+        action: Action::Undo,
+        key: "z",
+        shift: false,
+    },
+    Binding {
+        action: Action::Redo,
+        key: "z",
+        shift: true,
+    },
+    Binding {
+        action: Action::Redo,
+        key: "y",
+        shift: false,
+    },
+];
+
+impl Action {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Action::Convert => "shortcut-convert",
+            Action::Download => "shortcut-download",
+            Action::Sample => "shortcut-sample",
+            Action::Undo => "shortcut-undo",
+            Action::Redo => "shortcut-redo",
+        }
+    }
+
+    // Text fields keep their own undo, which the page history must not
+    // take over while there is uncommitted typing.
+    pub fn is_history(&self) -> bool {
+        matches!(self, Action::Undo | Action::Redo)
+    }
+}
+
+impl Binding {
+    // As written in menus, such as "Ctrl+Shift+Z", given the names of Ctrl,
+    // Shift and Enter in the page language. Cmd takes the place of Ctrl on
+    // macOS.
+    pub fn label(&self, ctrl: &str, shift: &str, enter: &str) -> String {
+        let key = match self.key {
+            "enter" => enter.to_string(),
+            key => key.to_uppercase(),
+        };
+        if self.shift {
+            format!("{}+{}+{}", ctrl, shift, key)
+        } else {
+            format!("{}+{}", ctrl, key)
+        }
+    }
+}
+
+// The action bound to a key press, given KeyboardEvent.key and whether Ctrl
+// or Cmd, Shift and Alt were held.
+pub fn action(key: &str, command: bool, shift: bool, alt: bool) -> Option<Action> {
+    if !command || alt {
+        return None;
+    }
+    let key = key.to_ascii_lowercase();
+    BINDINGS
+        .iter()
+        .find(|binding| binding.key == key && binding.shift == shift)
+        .map(|binding| binding.action)
+}
@@ -0,0 +1,192 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// vCard 2.1 and vCalendar 1.0 values written as quoted-printable, or in a
+// charset other than UTF-8, as Outlook and older phones export them. They are
+// decoded to plain UTF-8 text before parsing, which otherwise shows them as
+// =XX escapes or mojibake.
+
+use crate::{
+    contentline::{self, fold_line},
+    findings::{Code, Finding},
+};
+
+// Windows-1252 characters for bytes 0x80 to 0x9F, which ISO-8859-1 leaves to
+// control codes. Unassigned bytes keep the control code.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+// ISO-8859-15 characters that differ from ISO-8859-1.
+const ISO_8859_15: [(u8, char); 8] = [
+    (0xA4, '€'),
+    (0xA6, 'Š'),
+    (0xA8, 'š'),
+    (0xB4, 'Ž'),
+    (0xB8, 'ž'),
+    (0xBC, 'Œ'),
+    (0xBD, 'œ'),
+    (0xBE, 'Ÿ'),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transcoded {
+    pub text: String,
+    // One per decoded value, located by line.
+    pub findings: Vec<Finding>,
+}
+
+// None when no value has a legacy encoding.
+pub fn transcode(source: &str) -> Option<Transcoded> {
+    if !source.trim_start().starts_with("BEGIN:") {
+        return None;
+    }
+    let mut text = String::with_capacity(source.len());
+    let mut findings = Vec::new();
+    for (number, raw) in unfold(source) {
+        match contentline::parse_line(number, &raw).and_then(|line| decode(line, &mut findings)) {
+            Some(line) => fold_line(&mut text, &line),
+            None => fold_line(&mut text, &raw),
+        }
+    }
+    (!findings.is_empty()).then_some(Transcoded { text, findings })
+}
+
+// Unfolds lines like contentline::unfold, and also joins quoted-printable
+// values broken with a soft line break, a trailing "=" followed by an
+// unindented line.
+fn unfold(source: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut soft_break = false;
+    for (index, raw) in source.lines().enumerate() {
+        let raw = raw.trim_end_matches('\r');
+        match lines.last_mut() {
+            Some((_, line)) if soft_break => {
+                line.pop();
+                line.push_str(raw.trim_start());
+            }
+            Some((_, line)) if raw.starts_with([' ', '\t']) => line.push_str(&raw[1..]),
+            _ if raw.is_empty() => {}
+            _ => lines.push((index + 1, raw.to_string())),
+        }
+        soft_break = lines
+            .last()
+            .is_some_and(|(_, line)| line.ends_with('=') && is_quoted_printable(line));
+    }
+    lines
+}
+
+fn is_quoted_printable(line: &str) -> bool {
+    let head = line.split(':').next().unwrap_or_default();
+    head.to_ascii_uppercase().contains("QUOTED-PRINTABLE")
+}
+
+// The line with its value decoded and the encoding parameters removed, or
+// None when it has none.
+fn decode(mut line: contentline::ContentLine, out: &mut Vec<Finding>) -> Option<String> {
+    let quoted_printable = line.params.iter().any(|(name, value)| {
+        matches!(name.as_str(), "ENCODING" | "TYPE")
+            && value.eq_ignore_ascii_case("QUOTED-PRINTABLE")
+    });
+    let charset = line.param("CHARSET").map(str::to_string);
+    if !quoted_printable && charset.is_none() {
+        return None;
+    }
+
+    let label = charset.as_deref().map(str::to_ascii_lowercase);
+    let mut known_charset = true;
+    if quoted_printable {
+        let bytes = contentline::decode_quoted_printable(&line.value);
+        line.value = match decode_charset(&bytes, label.as_deref().unwrap_or("utf-8")) {
+            Some(value) => value,
+            None => {
+                known_charset = false;
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+        };
+        // Decoded line breaks are written as the escape that every version
+        // understands.
+        line.value = line.value.replace("\r\n", "\n").replace('\n', "\\n");
+    } else if let Some(label) = &label {
+        // Without quoted-printable the value was already read as text; a
+        // Windows-1252 file that failed UTF-8 was read as Latin-1 and only
+        // needs its 0x80 to 0x9F range remapped.
+        known_charset = decode_charset(&[], label).is_some();
+        if is_windows_1252(label) {
+            line.value = line
+                .value
+                .chars()
+                .map(|ch| match ch as u32 {
+                    code @ 0x80..=0x9F => WINDOWS_1252[code as usize - 0x80],
+                    _ => ch,
+                })
+                .collect();
+        }
+    }
+
+    line.params.retain(|(name, value)| {
+        !(name == "ENCODING" && value.eq_ignore_ascii_case("QUOTED-PRINTABLE")
+            || name == "TYPE" && value.eq_ignore_ascii_case("QUOTED-PRINTABLE")
+            || name == "CHARSET")
+    });
+    let mut message = match (&charset, quoted_printable) {
+        (Some(charset), true) => format!(
+            "Decoded the quoted-printable {} value of {}.",
+            charset, line.name
+        ),
+        (Some(charset), false) => format!(
+            "Removed CHARSET={} from {}, whose value was already decoded.",
+            charset, line.name
+        ),
+        (None, _) => format!("Decoded the quoted-printable value of {}.", line.name),
+    };
+    if !known_charset {
+        message.push_str(" The charset is not supported and was read as UTF-8.");
+    }
+    out.push(
+        Finding::new(Code::LegacyEncodingDecoded, message)
+            .with_location(format!("line {}", line.line)),
+    );
+    Some(line.to_line())
+}
+
+fn is_windows_1252(label: &str) -> bool {
+    matches!(label, "windows-1252" | "cp1252" | "x-cp1252")
+}
+
+// None for charsets other than UTF-8, US-ASCII and the Western European
+// single-byte ones.
+fn decode_charset(bytes: &[u8], label: &str) -> Option<String> {
+    match label {
+        "utf-8" | "utf8" | "us-ascii" | "ascii" => {
+            Some(String::from_utf8_lossy(bytes).into_owned())
+        }
+        "iso-8859-1" | "latin1" | "latin-1" | "iso8859-1" | "iso_8859-1" => {
+            Some(bytes.iter().map(|byte| char::from(*byte)).collect())
+        }
+        "iso-8859-15" | "latin-9" | "latin9" | "iso8859-15" | "iso_8859-15" => Some(
+            bytes
+                .iter()
+                .map(|byte| {
+                    ISO_8859_15
+                        .iter()
+                        .find(|(code, _)| code == byte)
+                        .map_or(char::from(*byte), |(_, ch)| *ch)
+                })
+                .collect(),
+        ),
+        label if is_windows_1252(label) => Some(
+            bytes
+                .iter()
+                .map(|byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252[*byte as usize - 0x80],
+                    _ => char::from(*byte),
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
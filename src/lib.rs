@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+pub use converter::{
+    calendar_batches, entry_title, error_span, normalize_json, split_entries, CalendarBatch,
+    ConversionResult, Converter, EntryResult, EntrySplitter, Format,
+};
+
+pub mod alarms;
+pub mod anonymize;
+pub mod bug_report;
+pub mod bundle;
+pub mod colors;
+#[cfg(feature = "compare-previous")]
+pub mod compare;
+pub mod compatibility;
+pub mod contact_cleanup;
+pub mod contentline;
+mod converter;
+pub mod csv_import;
+pub mod date_display;
+pub mod dedup;
+pub mod description;
+pub mod diff;
+pub mod documents;
+pub mod email;
+pub mod expected;
+pub mod explain;
+pub mod export;
+pub mod fidelity;
+pub mod filter;
+pub mod findings;
+pub mod freebusy;
+pub mod heatmap;
+pub mod highlight;
+pub mod history;
+pub mod html;
+pub mod html_extract;
+pub mod i18n;
+pub mod identity;
+pub mod itip;
+pub mod jcal;
+pub mod jmap;
+pub mod json_diagnostics;
+pub mod keybindings;
+pub mod legacy_encoding;
+pub mod links;
+pub mod location;
+pub mod merge;
+pub mod metrics;
+pub mod month_end;
+pub mod normalize;
+pub mod occurrences;
+pub mod orgchart;
+pub mod patches;
+pub mod pathological;
+pub mod permalink;
+pub mod phonetic;
+pub mod preview;
+pub mod probe;
+pub mod properties;
+pub mod qr;
+pub mod query;
+pub mod recent;
+pub mod recovery;
+pub mod redact;
+pub mod repair;
+pub mod report;
+pub mod rrule_text;
+pub mod samples;
+pub mod sanitize;
+pub mod selftest;
+pub mod semantic;
+pub mod shifts;
+pub mod stats;
+pub mod status;
+pub mod usage;
+pub mod validation;
+pub mod vcard_version;
+pub mod vtimezone;
+pub mod working_hours;
+pub mod xcal;
@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::Value;
+
+use crate::contentline;
+
+// JSContact collections holding URIs, in display order. Generic links are
+// included to catch URIs that did not land in a more specific collection.
+pub const COLLECTIONS: &[&str] = &[
+    "media",
+    "directories",
+    "calendars",
+    "schedulingAddresses",
+    "links",
+];
+
+// URI-valued vCard properties and where RFC 9555 puts them, as
+// (property, collection, kind).
+const VCARD_PROPERTIES: &[(&str, &str, Option<&str>)] = &[
+    ("PHOTO", "media", Some("photo")),
+    ("LOGO", "media", Some("logo")),
+    ("SOUND", "media", Some("sound")),
+    ("ORG-DIRECTORY", "directories", Some("directory")),
+    ("SOURCE", "directories", Some("entry")),
+    ("CALURI", "calendars", Some("calendar")),
+    ("FBURL", "calendars", Some("freeBusy")),
+    ("CALADRURI", "schedulingAddresses", None),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContactLink {
+    pub collection: &'static str,
+    pub id: String,
+    pub kind: Option<String>,
+    pub uri: String,
+    pub media_type: Option<String>,
+    pub pref: Option<u64>,
+    pub problem: Option<String>,
+}
+
+// Where a URI-valued vCard property ended up in the JSContact card.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub line: usize,
+    pub property: String,
+    pub expected: &'static str,
+    pub found: Option<&'static str>,
+}
+
+impl Placement {
+    pub fn is_correct(&self) -> bool {
+        self.found == Some(self.expected)
+    }
+}
+
+pub fn links(jscontact: &str) -> Vec<ContactLink> {
+    let Ok(card) = serde_json::from_str::<Value>(jscontact) else {
+        return Vec::new();
+    };
+    let mut links = Vec::new();
+    for collection in COLLECTIONS.iter().copied() {
+        for (id, entry) in card
+            .get(collection)
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let text = |key: &str| entry.get(key).and_then(Value::as_str).map(str::to_string);
+            let uri = text("uri").unwrap_or_default();
+            links.push(ContactLink {
+                collection,
+                id: id.clone(),
+                kind: text("kind"),
+                media_type: text("mediaType"),
+                pref: entry.get("pref").and_then(Value::as_u64),
+                problem: check_uri(&uri),
+                uri,
+            });
+        }
+    }
+    links
+}
+
+// Matches every URI-valued property of a vCard against the JSContact links.
+// Inline vCard 3 data has no URI, so it matches any data: URI of the
+// expected kind.
+pub fn placements(vcard: &str, links: &[ContactLink]) -> Vec<Placement> {
+    let mut placements = Vec::new();
+    for line in contentline::parse(vcard) {
+        let Some((_, expected, kind)) = VCARD_PROPERTIES
+            .iter()
+            .find(|(property, _, _)| *property == line.name)
+        else {
+            continue;
+        };
+        let inline = line.param("ENCODING").is_some_and(|encoding| {
+            matches!(encoding.to_ascii_uppercase().as_str(), "B" | "BASE64")
+        });
+        let uri = line.value.trim();
+        let found = links
+            .iter()
+            .filter(|link| {
+                if inline {
+                    link.uri.starts_with("data:") && link.kind.as_deref() == *kind
+                } else {
+                    link.uri == uri
+                }
+            })
+            .map(|link| link.collection)
+            .min_by_key(|collection| *collection != *expected);
+        placements.push(Placement {
+            line: line.line,
+            property: line.name,
+            expected,
+            found,
+        });
+    }
+    placements
+}
+
+// Syntax check of a link, returning what is wrong with it.
+pub fn check_uri(uri: &str) -> Option<String> {
+    let uri = uri.trim();
+    if uri.is_empty() {
+        return Some("Empty URI".to_string());
+    }
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return Some("Missing URI scheme".to_string());
+    };
+    if scheme.is_empty()
+        || !scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        || !scheme
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'))
+    {
+        return Some(format!("Invalid URI scheme \"{}\"", scheme));
+    }
+    if uri.contains(char::is_whitespace) {
+        return Some("Contains whitespace".to_string());
+    }
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "https" => {
+            let host = rest
+                .strip_prefix("//")
+                .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+            match host {
+                Some(host) if !host.is_empty() => None,
+                _ => Some("Missing host".to_string()),
+            }
+        }
+        "data" => (!rest.contains(',')).then(|| "Malformed data URI".to_string()),
+        "mailto" => (!rest.contains('@')).then(|| "Missing email address".to_string()),
+        _ => None,
+    }
+}
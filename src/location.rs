@@ -0,0 +1,287 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::{
+    contentline::{self, ContentLine},
+    description::for_each_entry,
+    findings::{Code, Finding},
+};
+
+// Vendor properties used for data JSCalendar has no place for.
+pub const RADIUS_PROPERTY: &str = "apple.com:radius";
+pub const PROXIMITY_PROPERTY: &str = "apple.com:proximity";
+pub const LOCATION_ID_PROPERTY: &str = "apple.com:locationId";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Proximity {
+    Arrive,
+    Depart,
+}
+
+// An X-APPLE-STRUCTURED-LOCATION, either on an event or on one of its
+// proximity alarms.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuredLocation {
+    pub line: usize,
+    pub uid: Option<String>,
+    pub title: Option<String>,
+    pub address: Option<String>,
+    // geo: URI.
+    pub coordinates: Option<String>,
+    // Geofence radius in meters.
+    pub radius: Option<f64>,
+    pub alarm: Option<ProximityAlarm>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProximityAlarm {
+    pub proximity: Proximity,
+    // Absolute trigger as a UTCDateTime, which Apple sets to a fixed date.
+    pub trigger: Option<String>,
+}
+
+impl Proximity {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "ARRIVE" => Some(Proximity::Arrive),
+            "DEPART" => Some(Proximity::Depart),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Proximity::Arrive => "arrive",
+            Proximity::Depart => "depart",
+        }
+    }
+}
+
+impl StructuredLocation {
+    fn from_line(line: &ContentLine) -> Self {
+        let param = |name: &str| {
+            line.param(name)
+                .map(contentline::unescape)
+                .filter(|value| !value.trim().is_empty())
+        };
+        StructuredLocation {
+            line: line.line,
+            uid: None,
+            title: param("X-TITLE"),
+            address: param("X-ADDRESS"),
+            coordinates: line
+                .value
+                .trim()
+                .to_ascii_lowercase()
+                .starts_with("geo:")
+                .then(|| line.value.trim().to_string()),
+            radius: line
+                .param("X-APPLE-RADIUS")
+                .and_then(|radius| radius.trim().parse().ok()),
+            alarm: None,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.title.as_deref().or(self.address.as_deref())
+    }
+}
+
+pub fn structured_locations(icalendar: &str) -> Vec<StructuredLocation> {
+    let mut locations = Vec::new();
+    let mut depth = 0;
+    let mut uid = None;
+    // Event locations wait for the UID, which may come later.
+    let mut pending = Vec::new();
+    let mut alarm: (
+        Option<Proximity>,
+        Option<String>,
+        Option<StructuredLocation>,
+    ) = (None, None, None);
+    for line in contentline::parse(icalendar) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                if depth == 3 {
+                    alarm = (None, None, None);
+                }
+            }
+            "END" => {
+                if depth == 3 {
+                    if let (Some(proximity), trigger, Some(mut location)) =
+                        std::mem::take(&mut alarm)
+                    {
+                        location.alarm = Some(ProximityAlarm { proximity, trigger });
+                        pending.push(location);
+                    }
+                } else if depth == 2 {
+                    locations.extend(pending.drain(..).map(|location| StructuredLocation {
+                        uid: uid.clone(),
+                        ..location
+                    }));
+                    uid = None;
+                }
+                depth -= 1;
+            }
+            "UID" if depth == 2 => uid = Some(line.text()),
+            "X-APPLE-STRUCTURED-LOCATION" if depth == 2 => {
+                pending.push(StructuredLocation::from_line(&line));
+            }
+            "X-APPLE-STRUCTURED-LOCATION" if depth == 3 => {
+                alarm.2 = Some(StructuredLocation::from_line(&line));
+            }
+            "X-APPLE-PROXIMITY" | "PROXIMITY" if depth == 3 => {
+                alarm.0 = Proximity::parse(&line.value);
+            }
+            "TRIGGER" if depth == 3 => {
+                alarm.1 = contentline::parse_datetime(&line.value)
+                    .map(|trigger| trigger.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+            }
+            _ => {}
+        }
+    }
+    locations
+}
+
+// Reports data that only survives as a vendor extension in JSCalendar.
+pub fn audit(locations: &[StructuredLocation]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for location in locations {
+        let name = location.name().unwrap_or("unnamed location");
+        if let Some(alarm) = &location.alarm {
+            findings.push(
+                Finding::new(
+                    Code::VendorLocationExtension,
+                    format!(
+                        "Alarm on {} \"{}\" has no JSCalendar trigger, kept as {} on the alert.",
+                        if alarm.proximity == Proximity::Arrive {
+                            "arriving at"
+                        } else {
+                            "leaving"
+                        },
+                        name,
+                        PROXIMITY_PROPERTY
+                    ),
+                )
+                .with_location(format!("line {}", location.line)),
+            );
+        } else if let Some(radius) = location.radius {
+            findings.push(
+                Finding::new(
+                    Code::VendorLocationExtension,
+                    format!(
+                        "Radius of {:.0} m around \"{}\" has no JSCalendar property, kept as {}.",
+                        radius, name, RADIUS_PROPERTY
+                    ),
+                )
+                .with_location(format!("line {}", location.line)),
+            );
+        }
+    }
+    findings
+}
+
+// Adds structured locations to the JSCalendar entries with the same UID:
+// coordinates and address go on the matching location, or a new one, and
+// proximity alarms mark the alert with the same absolute trigger. Returns
+// None when nothing needed changing.
+pub fn apply_to_jscalendar(icalendar: &str, jscalendar: &str) -> Option<String> {
+    let locations = structured_locations(icalendar);
+    if locations.is_empty() {
+        return None;
+    }
+
+    let mut value = serde_json::from_str::<Value>(jscalendar).ok()?;
+    let mut changed = false;
+    for_each_entry(&mut value, &mut |entry| {
+        let uid = entry.get("uid").and_then(Value::as_str).map(str::to_string);
+        for location in locations
+            .iter()
+            .filter(|location| location.uid.is_some() && location.uid == uid)
+        {
+            let id = merge_location(entry, location);
+            changed = true;
+            let Some(alarm) = &location.alarm else {
+                continue;
+            };
+            let alert = entry
+                .get_mut("alerts")
+                .and_then(Value::as_object_mut)
+                .into_iter()
+                .flat_map(|alerts| alerts.values_mut())
+                .filter_map(Value::as_object_mut)
+                .find(|alert| {
+                    let when = alert
+                        .get("trigger")
+                        .and_then(|trigger| trigger.get("when"))
+                        .and_then(Value::as_str);
+                    alarm.trigger.is_some() && when == alarm.trigger.as_deref()
+                });
+            if let Some(alert) = alert {
+                alert.insert(PROXIMITY_PROPERTY.into(), alarm.proximity.as_str().into());
+                alert.insert(LOCATION_ID_PROPERTY.into(), id.into());
+            }
+        }
+    });
+    changed
+        .then(|| serde_json::to_string_pretty(&value).ok())
+        .flatten()
+}
+
+// Returns the id of the location the structured data was merged into.
+fn merge_location(entry: &mut Map<String, Value>, location: &StructuredLocation) -> String {
+    let locations = entry
+        .entry("locations")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !locations.is_object() {
+        *locations = Value::Object(Map::new());
+    }
+    let Some(locations) = locations.as_object_mut() else {
+        return String::new();
+    };
+
+    // Apple also writes LOCATION as the title followed by the address.
+    let existing = location.name().and_then(|name| {
+        locations.iter().find_map(|(id, existing)| {
+            existing
+                .get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|existing| existing.trim().starts_with(name.trim()))
+                .then(|| id.clone())
+        })
+    });
+    let id = existing.unwrap_or_else(|| {
+        (1..)
+            .map(|n| format!("apple-{}", n))
+            .find(|id| !locations.contains_key(id))
+            .unwrap_or_default()
+    });
+    let target = locations
+        .entry(id.clone())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if let Some(target) = target.as_object_mut() {
+        target.entry("@type").or_insert_with(|| "Location".into());
+        if let Some(name) = location.name() {
+            target.entry("name").or_insert_with(|| name.into());
+        }
+        if let Some(address) = &location.address {
+            target
+                .entry("description")
+                .or_insert_with(|| address.clone().into());
+        }
+        if let Some(coordinates) = &location.coordinates {
+            target
+                .entry("coordinates")
+                .or_insert_with(|| coordinates.clone().into());
+        }
+        if let Some(radius) = location.radius {
+            target.insert(RADIUS_PROPERTY.into(), radius.into());
+        }
+    }
+    id
+}
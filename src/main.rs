@@ -5,13 +5,154 @@
  */
 
 use calcard::{
-    common::timezone::Tz, icalendar::dates::CalendarExpand, jscalendar::JSCalendar,
-    jscontact::JSContact, Entry, Parser,
+    common::timezone::Tz,
+    icalendar::{ICalendar, ICalendarComponentType},
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use codee::string::FromToStringCodec;
+use jmap_convert::{
+    alarms::{self, Firing},
+    bug_report::BugReport,
+    bundle::{BundleOptions, SessionBundle},
+    calendar_batches, contact_cleanup,
+    csv_import::{self, CsvTable},
+    date_display::{DateDisplay, OccurrenceTime},
+    documents::Session,
+    email, error_span, explain,
+    export::{self, ExportContext},
+    fidelity::Loss,
+    filter::{self, ComponentKind},
+    findings::{Code, Finding},
+    heatmap::WeeklyHeatmap,
+    highlight,
+    html_extract::{self, Extracted},
+    i18n::FluentArgs,
+    jcal, jmap,
+    json_diagnostics::{self, Diagnostic},
+    keybindings::{self, Action},
+    legacy_encoding,
+    occurrences::{self, EntryKind, Expansion, ExpansionWindow, Origin},
+    permalink,
+    query::{OutputFormat, QueryOptions},
+    recent::{self, RecentInput, RecentInputs},
+    recovery::{self, Recovery},
+    report::{ConversionReport, Timings},
+    rrule_text, sanitize, split_entries,
+    stats::DurationStats,
+    status,
+    usage::UsageReport,
+    working_hours::{Holidays, OverlayMode, WorkingHours},
+    xcal, EntryResult, EntrySplitter, Format,
 };
 use leptos::*;
 use leptos_meta::*;
+use leptos_router::*;
+use leptos_use::storage::use_local_storage;
 use rand::seq::SliceRandom;
-use std::borrow::Cow;
+use std::{ops::Range, time::Duration};
+use ui::{
+    alarms::AlarmTimelinePanel,
+    anonymize::AnonymizePanel,
+    bug_report::BugReportButton,
+    bundle::SessionBundlePanel,
+    colors::ColorPalettePanel,
+    compare::VersionComparePanel,
+    compatibility::CompatibilityPanel,
+    contact_cleanup::ContactCleanupPanel,
+    csv_import::CsvMappingPanel,
+    dedup::DedupPanel,
+    description::DescriptionDiffPanel,
+    diff::RoundtripDiffPanel,
+    documents::DocumentTabs,
+    email::EmailParts,
+    entries::{EntryList, EntrySummary},
+    event_preview::EventSummaryCards,
+    expected::ExpectedOutputPanel,
+    export::{download, save_file, CopyButton, DownloadButton},
+    fidelity::FidelityReportPanel,
+    filter::ComponentFilterPanel,
+    findings::FindingsPanel,
+    freebusy::FreeBusyPanel,
+    heatmap::HeatmapPanel,
+    highlight::HighlightedText,
+    html_extract::HtmlExtractNote,
+    i18n::{I18n, LanguageSelect},
+    identity::IdentityMappingPanel,
+    install::InstallButton,
+    json_diagnostics::{IgnoredPropertiesPanel, JsonDiagnosticsTree},
+    keybindings::KeyboardShortcutsPanel,
+    links::ContactLinksPanel,
+    merge::MergePanel,
+    metrics::MetricsFooter,
+    normalize::NormalizePanel,
+    orgchart::OrgChartPanel,
+    pager::Pager,
+    patches::PatchDiffPanel,
+    permalink::PermalinkPanel,
+    phonetic::PhoneticPanel,
+    preview::{ContactPreviewCard, InvitationPreview},
+    probe::TimeProbePanel,
+    properties::PropertyTablePanel,
+    qr::QrCodePanel,
+    query::use_query_options,
+    recent::RecentInputsSidebar,
+    recovery::ParserRecoveryPanel,
+    remote::RemoteSourcePanel,
+    repair::RepairPanel,
+    report::ConversionReportPanel,
+    samples::SampleGenerator,
+    sanitize::VendorStripPanel,
+    scheduling::SchedulingPanel,
+    selftest::SelfTest,
+    semantic::SemanticComparePanel,
+    settings::{
+        EditorSettings, ExportSettings, FetchSettings, FetchSettingsPanel, LIVE_CONVERSION_LIMIT,
+    },
+    shifts::ShiftGenerator,
+    stats::DurationStatsPanel,
+    status::StatusPanel,
+    theme::{ThemeSelect, ThemeSettings},
+    timezone::TimezoneSelect,
+    toast::{ToastStack, Toasts},
+    tour::{
+        ConversionTour, TourStep, CONVERSION_TARGET, EXPANSION_TARGET, ROUNDTRIP_TARGET,
+        SOURCE_TARGET,
+    },
+    usage::PropertyUsagePanel,
+    validation::{ResultsTabs, ValidationPanel},
+    vcard_version::VCardVersionPanel,
+    vtimezone::TimeZoneInspectorPanel,
+    working_hours::WorkingHoursPanel,
+    workspace::{Workspace, WorkspaceLayout},
+};
+use wasm_bindgen::{JsCast, JsValue};
+
+mod fetch;
+mod input;
+mod ui;
+
+const EXPANSION_LIMIT: usize = 25;
+// Rows of the occurrence table rendered at once.
+const OCCURRENCE_PAGE_SIZE: usize = 100;
+// Larger sources convert in the background, entry by entry.
+const BACKGROUND_CONVERSION_THRESHOLD: usize = 1024 * 1024;
+// Bytes of a large source split into entries at a time.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+// Bytes of components a large calendar converts at a time.
+const CALENDAR_BATCH_SIZE: usize = 64 * 1024;
+// How long a background conversion runs before letting the page render.
+const FRAME_MS: f64 = 16.0;
+const MAX_EXPANSION_COUNT: usize = 5_000;
+const EXPANSION_BUDGET_MS: u32 = 500;
+const EXPANSION_INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+// Findings produced by the expansion, replaced whenever it runs again.
+const EXPANSION_CODES: &[Code] = &[
+    Code::OccurrenceOnHoliday,
+    Code::OccurrenceOutsideWorkingHours,
+    Code::NoOccurrences,
+    Code::ExpansionLimitReached,
+    Code::ExpansionBudgetExceeded,
+];
 
 fn main() {
     _ = console_log::init_with_level(log::Level::Debug);
@@ -19,257 +160,1880 @@ fn main() {
     leptos::mount_to_body(|| view! { <App/> })
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct Occurrence {
-    from: String,
-    to: String,
+    kind: EntryKind,
+    title: Option<String>,
+    // In the zone of its event.
+    from: Option<OccurrenceTime>,
+    // Due time of tasks.
+    to: Option<OccurrenceTime>,
+    // Status and completion of tasks.
+    progress: Option<String>,
+    holiday: Option<String>,
+    outside_hours: bool,
+    origin: Origin,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ExpansionAnchor {
+    #[default]
+    Start,
+    Now,
+    Range,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum SourceType {
-    ICalendar,
-    JSCalendar,
-    VCard,
-    JSContact,
+struct ExpansionParams {
+    anchor: ExpansionAnchor,
+    // Occurrences are shown in this zone, floating times are read in it too.
+    timezone: Tz,
+    // Inclusive dates in `timezone`, used by `ExpansionAnchor::Range`.
+    from: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    count: usize,
+    budget_ms: u32,
+}
+
+impl Default for ExpansionParams {
+    fn default() -> Self {
+        ExpansionParams {
+            anchor: ExpansionAnchor::default(),
+            timezone: ui::preview::viewer_timezone().map_or(Tz::Floating, Tz::Tz),
+            from: None,
+            until: None,
+            count: EXPANSION_LIMIT,
+            budget_ms: EXPANSION_BUDGET_MS,
+        }
+    }
+}
+
+impl ExpansionAnchor {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExpansionAnchor::Start => "start",
+            ExpansionAnchor::Now => "now",
+            ExpansionAnchor::Range => "range",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "now" => ExpansionAnchor::Now,
+            "range" => ExpansionAnchor::Range,
+            _ => ExpansionAnchor::Start,
+        }
+    }
+}
+
+// iCalendar and vCard output can also be shown as jCal and jCard or as xCal
+// and xCard, JSCalendar and JSContact output as a JMAP request creating it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum OutputForm {
+    #[default]
+    Text,
+    Json,
+    Xml,
+    Jmap,
+}
+
+impl OutputForm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OutputForm::Text => "text",
+            OutputForm::Json => "json",
+            OutputForm::Xml => "xml",
+            OutputForm::Jmap => "jmap",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "json" => OutputForm::Json,
+            "xml" => OutputForm::Xml,
+            "jmap" => OutputForm::Jmap,
+            _ => OutputForm::Text,
+        }
+    }
+
+    fn label(&self, format: Format, i18n: I18n) -> String {
+        match (self, format.is_calendar()) {
+            (OutputForm::Text, _) => format.as_str().to_string(),
+            (OutputForm::Json, _) => format.json_form().as_str().to_string(),
+            (OutputForm::Xml, true) => "xCal".to_string(),
+            (OutputForm::Xml, false) => "xCard".to_string(),
+            (OutputForm::Jmap, _) => i18n.t("output-jmap-request"),
+        }
+    }
+
+    // The forms output in `format` can be shown in.
+    fn offered(format: Format) -> &'static [OutputForm] {
+        if format.is_json() {
+            &[OutputForm::Text, OutputForm::Jmap]
+        } else {
+            &[OutputForm::Text, OutputForm::Json, OutputForm::Xml]
+        }
+    }
+
+    fn from_query(output: OutputFormat) -> Self {
+        match output {
+            OutputFormat::Text => OutputForm::Text,
+            OutputFormat::JCal | OutputFormat::JCard => OutputForm::Json,
+            OutputFormat::XCal | OutputFormat::XCard => OutputForm::Xml,
+            OutputFormat::Jmap => OutputForm::Jmap,
+        }
+    }
+
+    // As named in links, for output in `format`.
+    fn to_query(self, format: Format) -> OutputFormat {
+        match (self, format.is_calendar()) {
+            (OutputForm::Text, _) => OutputFormat::Text,
+            (OutputForm::Json, true) => OutputFormat::JCal,
+            (OutputForm::Json, false) => OutputFormat::JCard,
+            (OutputForm::Xml, true) => OutputFormat::XCal,
+            (OutputForm::Xml, false) => OutputFormat::XCard,
+            (OutputForm::Jmap, _) => OutputFormat::Jmap,
+        }
+    }
+}
+
+impl ExpansionParams {
+    // Missing or out of range values in a bundle fall back to the defaults.
+    fn from_options(options: &BundleOptions) -> Self {
+        let defaults = ExpansionParams::default();
+        ExpansionParams {
+            anchor: ExpansionAnchor::parse(&options.expansion_anchor),
+            timezone: options
+                .timezone
+                .parse::<chrono_tz::Tz>()
+                .map_or(Tz::Floating, Tz::Tz),
+            from: options.expansion_from,
+            until: options.expansion_until,
+            count: match options.expansion_count {
+                0 => defaults.count,
+                count => count.min(MAX_EXPANSION_COUNT),
+            },
+            budget_ms: match options.expansion_budget_ms {
+                0 => defaults.budget_ms,
+                budget_ms => budget_ms,
+            },
+        }
+    }
+
+    fn to_options(self, options: &mut BundleOptions) {
+        options.expansion_anchor = self.anchor.as_str().to_string();
+        options.timezone = match self.timezone {
+            Tz::Tz(tz) => tz.name().to_string(),
+            _ => String::new(),
+        };
+        options.expansion_from = self.from;
+        options.expansion_until = self.until;
+        options.expansion_count = self.count;
+        options.expansion_budget_ms = self.budget_ms;
+    }
+
+    fn window(&self) -> ExpansionWindow {
+        let midnight = |date: NaiveDate| {
+            self.timezone
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .earliest()
+                .map(|date| date.with_timezone(&Utc))
+        };
+        let (from, until) = match self.anchor {
+            ExpansionAnchor::Start => (None, None),
+            ExpansionAnchor::Now => (
+                DateTime::from_timestamp_millis(js_sys::Date::now() as i64),
+                None,
+            ),
+            ExpansionAnchor::Range => (
+                self.from.and_then(midnight),
+                self.until
+                    .and_then(|date| date.succ_opt())
+                    .and_then(midnight),
+            ),
+        };
+        ExpansionWindow {
+            from,
+            until,
+            count: self.count,
+        }
+    }
+}
+
+// Values of date inputs, which are empty when cleared.
+fn date_value(date: Option<NaiveDate>) -> String {
+    date.map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn parse_date_value(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+}
+
+// Resolves on the next turn of the event loop, after the page had a chance
+// to render and handle input.
+async fn next_tick() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        _ = window().set_timeout_with_callback(&resolve);
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+// Millisecond clock for timing conversions and expansions.
+fn now_ms() -> f64 {
+    window()
+        .performance()
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+// Where a parse error is in the editor text: its location as reported and its
+// byte range. The entry it was reported for is looked up in the text, falling
+// back to its line when filtering changed the entry before conversion.
+fn locate_error(text: &str, entry: &str, finding: &Finding) -> Option<(String, Range<usize>)> {
+    let location = finding.location.as_deref()?;
+    let span = error_span(entry, location)?;
+    let start = match text.find(entry) {
+        Some(offset) => offset + span.start,
+        None => text.find(entry.get(span.clone()).filter(|line| !line.is_empty())?)?,
+    };
+    let location = &location[location.find("line ")?..];
+    Some((location.to_string(), start..start + span.len()))
 }
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
+    provide_context(FetchSettings::load());
+    provide_context(ExportSettings::load());
+    provide_context(EditorSettings::load());
+    let theme = ThemeSettings::load();
+    provide_context(theme);
+    let i18n = I18n::load();
+    provide_context(i18n);
+    provide_context(Toasts::new());
 
-    let source = create_rw_signal(String::new());
-    let source_type = create_rw_signal(SourceType::ICalendar);
+    view! {
+        <Body class=move || theme.body_class()/>
+        <ToastStack/>
+
+        <Router>
+            <Routes>
+                <Route path="/" view=Converter/>
+                <Route path="/selftest" view=SelfTest/>
+            </Routes>
+        </Router>
+    }
+}
+
+#[component]
+pub fn Converter() -> impl IntoView {
+    let (session, set_session, _) =
+        use_local_storage::<Session, FromToStringCodec>("jmap-convert.session");
+    let (recent_inputs, set_recent_inputs, _) =
+        use_local_storage::<RecentInputs, FromToStringCodec>("jmap-convert.recent");
+    let history_open = create_rw_signal(false);
+    let source =
+        create_rw_signal(session.with_untracked(|session| session.active().source.clone()));
+    let source_type = create_rw_signal(Format::ICalendar);
     let conversion = create_rw_signal(String::new());
     let roundtrip_conversion = create_rw_signal(String::new());
+    // Form iCalendar and vCard output is shown in.
+    let output_form = create_rw_signal(OutputForm::default());
+    // Whether the output is annotated with the source of each field, and
+    // the entries it was converted from.
+    let explaining = create_rw_signal(false);
+    let shown_source = create_rw_signal(String::new());
+    // Calendar and vCard parts of a pasted email, and the one converted.
+    let email_parts: RwSignal<Option<Vec<email::Part>>> = create_rw_signal(None);
+    let email_part = create_rw_signal(0usize);
+    // Columns of a pasted CSV export, and the vCard fields they are mapped to.
+    let csv_columns: RwSignal<Option<(csv_import::Layout, Vec<String>)>> = create_rw_signal(None);
+    let csv_mapping: RwSignal<Vec<csv_import::Field>> = create_rw_signal(vec![]);
+    // Events or contacts marked up in a pasted web page.
+    let html_extracted: RwSignal<Option<Result<Extracted, String>>> = create_rw_signal(None);
+    // What is converted in place of the source: the selected part of an email,
+    // values decoded from a legacy encoding, the markup extracted from a web
+    // page or the vCards made from CSV rows.
+    let imported: RwSignal<Option<String>> = create_rw_signal(None);
+    let document = create_memo(move |_| imported.get().unwrap_or_else(|| source.get()));
+    let fidelity: RwSignal<Vec<Loss>> = create_rw_signal(vec![]);
     let error_message = create_rw_signal(String::new());
+    let error_location: RwSignal<Option<(String, Range<usize>)>> = create_rw_signal(None);
+    // Offered with the error when the conversion failed its round trip.
+    let bug_report: RwSignal<Option<BugReport>> = create_rw_signal(None);
+    // Where a JSCalendar or JSContact source that failed to convert is wrong.
+    let json_errors: RwSignal<Vec<Diagnostic>> = create_rw_signal(vec![]);
+    let editor_ref = create_node_ref::<html::Textarea>();
+    // The highlighted copy of the editor text drawn behind the textarea, which
+    // follows every keystroke rather than only committed changes.
+    let highlight_ref = create_node_ref::<html::Pre>();
+    let typed = create_rw_signal(String::new());
+    create_effect(move |_| typed.set(source.get()));
+    let highlighting = move || typed.with(|typed| typed.len() <= highlight::MAX_LENGTH);
     let occurrences: RwSignal<Vec<Occurrence>> = create_rw_signal(vec![]);
-
-    let set_error = move |msg: String| {
-        error_message.set(msg);
-        conversion.set(String::new());
-        roundtrip_conversion.set(String::new());
-        occurrences.set(vec![]);
+    let occurrence_page = create_rw_signal(0usize);
+    // Occurrences as JSCalendar Events with utcStart and utcEnd.
+    let occurrences_export = create_rw_signal(String::new());
+    // The same occurrences as CSV rows and as single events without rules.
+    let occurrences_csv = create_rw_signal(String::new());
+    let occurrences_ics = create_rw_signal(String::new());
+    let working_hours = create_rw_signal(WorkingHours::default());
+    let holidays = create_rw_signal(Holidays::default());
+    let overlay_mode = create_rw_signal(OverlayMode::default());
+    // Occurrences the overlay keeps, as listed in the table.
+    let shown_occurrences = create_memo(move |_| {
+        let mode = overlay_mode.get();
+        occurrences.with(|occurrences| {
+            occurrences
+                .iter()
+                .filter(|occurrence| mode.includes(occurrence.is_flagged()))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    });
+    // Rows for removed dates are not occurrences.
+    let occurrence_count = move || {
+        occurrences.with(|occurrences| {
+            occurrences
+                .iter()
+                .filter(|occurrence| occurrence.origin != Origin::Removed)
+                .count()
+        })
+    };
+    // A new expansion or overlay starts over from the first page.
+    create_effect(move |_| {
+        shown_occurrences.track();
+        occurrence_page.set(0);
+    });
+    let expansion = create_rw_signal(ExpansionParams::default());
+    // How the occurrence table shows dates, and whether in the zone of their
+    // event rather than the expansion zone.
+    let date_display = create_rw_signal(DateDisplay::default());
+    let event_zone = create_rw_signal(false);
+    let show_time = move |time: &OccurrenceTime| {
+        let time = if event_zone.get() {
+            time.clone()
+        } else {
+            time.in_zone(expansion.with(|params| params.timezone))
+        };
+        time.format(
+            date_display.get(),
+            i18n.locale(),
+            &i18n.t("occurrence-floating"),
+        )
     };
+    // How long the last expansion took and whether it ran out of budget.
+    let expansion_elapsed: RwSignal<Option<f64>> = create_rw_signal(None);
+    let expansion_out_of_budget = create_rw_signal(false);
+    let conversion_elapsed: StoredValue<f64> = store_value(0.0);
+    // Entries converted, bytes of the source split and its length while a
+    // large source converts in the background.
+    let conversion_progress: RwSignal<Option<(usize, usize, usize)>> = create_rw_signal(None);
+    // Name of the file being read, bytes read and its size.
+    let file_progress: RwSignal<Option<(String, f64, f64)>> = create_rw_signal(None);
+    // Bumped whenever a conversion starts or is cancelled, so a background
+    // conversion that was superseded stops at its next entry.
+    let conversion_generation = store_value(0u64);
+    // Bumped whenever an expansion starts, the source converts again or the
+    // conversion is cancelled, so an expansion still running stops at its
+    // next pass.
+    let expansion_generation = store_value(0u64);
+    let duration_stats: RwSignal<Option<DurationStats>> = create_rw_signal(None);
+    let heatmap: RwSignal<Option<WeeklyHeatmap>> = create_rw_signal(None);
+    // When the alarms of the occurrences go off, in the expansion zone.
+    let alarm_firings: RwSignal<Vec<Firing>> = create_rw_signal(vec![]);
+    let findings: RwSignal<Vec<Finding>> = create_rw_signal(vec![]);
+    let entry_results: StoredValue<Vec<EntryResult>> = store_value(vec![]);
+    let entries: RwSignal<Vec<EntrySummary>> = create_rw_signal(vec![]);
+    // Entry of a multi-entry file being shown, all of them when None.
+    let selected_entry: RwSignal<Option<usize>> = create_rw_signal(None);
+    let excluded_components: RwSignal<Vec<ComponentKind>> = create_rw_signal(vec![]);
+    let removed_components = create_rw_signal(0);
+    let strip_vendor = create_rw_signal(false);
+    let vendor_keep = create_rw_signal(String::new());
+    // What stripping vendor extensions removed, reported with the findings.
+    let stripped: StoredValue<Vec<Finding>> = store_value(vec![]);
+    let normalize_contacts = create_rw_signal(false);
+    let default_country = create_rw_signal(String::new());
+    // Phone numbers and email addresses rewritten or found invalid.
+    let normalized: StoredValue<Vec<Finding>> = store_value(vec![]);
+    // Values decoded from quoted-printable or a legacy charset before parsing.
+    let transcoded: StoredValue<Vec<Finding>> = store_value(vec![]);
+    // Strict parsing fails entries lenient parsing would recover, and the
+    // recoveries either would make.
+    let strict_parsing = create_rw_signal(false);
+    let recoveries: RwSignal<Vec<Recovery>> = create_rw_signal(vec![]);
+    let layout = WorkspaceLayout::load();
+    // Whether the results show the RFC validation report instead of the conversion.
+    let validating = create_rw_signal(false);
+    let tour: RwSignal<Option<TourStep>> = create_rw_signal(None);
+    let editor_settings = expect_context::<EditorSettings>();
+    let export_settings = expect_context::<ExportSettings>();
+    // Bumped on every keystroke and commit so only the latest pending live
+    // conversion runs.
+    let live_generation = store_value(0u64);
+
+    let add_finding = move |finding: Finding| findings.update(|findings| findings.push(finding));
 
-    let set_occurrences = move |expanded: CalendarExpand| {
-        let mut events = expanded
-            .events
-            .into_iter()
-            .filter_map(|event| event.try_into_date_time())
-            .collect::<Vec<_>>();
-        events.sort_unstable_by(|a, b| a.start.cmp(&b.start));
-        occurrences.set(
-            events
+    // `text` is the iCalendar the calendar was parsed from.
+    let set_occurrences =
+        move |icalendar: &ICalendar, text: &str, params: ExpansionParams, expanded: Expansion| {
+            let timezone = params.timezone;
+            let window = params.window();
+            expansion_elapsed.set(Some(expanded.elapsed_ms));
+            expansion_out_of_budget.set(expanded.out_of_budget);
+            if expanded.out_of_budget {
+                add_finding(Finding::new(Code::ExpansionBudgetExceeded, {
+                    let mut args = FluentArgs::new();
+                    args.set("elapsed", format!("{:.0}", expanded.elapsed_ms));
+                    args.set("count", expanded.occurrences.len());
+                    i18n.t_with("expansion-out-of-budget", args)
+                }));
+            }
+            let mut events = expanded.occurrences;
+            let times = events
+                .iter()
+                .map(|event| {
+                    (
+                        OccurrenceTime::of(&event.start),
+                        OccurrenceTime::of(&event.end),
+                    )
+                })
+                .collect::<Vec<_>>();
+            occurrences_export.set(occurrences::to_jscalendar(text, icalendar, &events));
+            occurrences_csv.set(occurrences::to_csv(text, icalendar, &events));
+            occurrences_ics.set(occurrences::to_icalendar(text, icalendar, &events));
+            // Both need the times as written, before they move to `timezone`.
+            let origins = occurrences::origins(text, icalendar, &events);
+            let details = occurrences::details(text, icalendar, &events);
+            let mut firings = alarms::timeline(text, icalendar, &events);
+            // Tasks with only a DUE have no start to expand from and are listed
+            // once, when they fall inside the window.
+            let due_tasks = occurrences::due_only_tasks(text)
                 .into_iter()
-                .map(|event| Occurrence {
-                    from: format!(
-                        "{} ({})",
-                        event.start.format("%a %b %-d, %Y %-I:%M%P"),
-                        event
-                            .start
-                            .timezone()
-                            .name()
-                            .unwrap_or(Cow::Borrowed("Floating"))
-                    ),
-                    to: format!(
-                        "{} ({})",
-                        event.end.format("%a %b %-d, %Y %-I:%M%P"),
-                        event
-                            .end
-                            .timezone()
-                            .name()
-                            .unwrap_or(Cow::Borrowed("Floating"))
+                .filter(|task| {
+                    window.from.is_none_or(|from| task.due >= from.naive_utc())
+                        && window
+                            .until
+                            .is_none_or(|until| task.due < until.naive_utc())
+                })
+                .collect::<Vec<_>>();
+            // Dates an EXDATE removed are listed among the occurrences around them.
+            let removed = match (events.first(), events.last()) {
+                (Some(first), Some(last)) => {
+                    let (first, last) = (first.start.naive_local(), last.start.naive_local());
+                    occurrences::removed_dates(text)
+                        .into_iter()
+                        .filter(|date| date.start >= first && date.start <= last)
+                        .collect()
+                }
+                _ => vec![],
+            };
+            if !matches!(timezone, Tz::Floating) {
+                for event in &mut events {
+                    event.start = event.start.with_timezone(&timezone);
+                    event.end = event.end.with_timezone(&timezone);
+                }
+                for firing in &mut firings {
+                    firing.at = firing.at.with_timezone(&timezone);
+                    firing.occurrence = firing
+                        .occurrence
+                        .map(|start| start.with_timezone(&timezone));
+                }
+            }
+            alarm_firings.set(firings);
+            if events.is_empty()
+                && due_tasks.is_empty()
+                && icalendar.components.iter().any(|component| {
+                    matches!(
+                        component.component_type,
+                        ICalendarComponentType::VEvent
+                            | ICalendarComponentType::VTodo
+                            | ICalendarComponentType::VJournal
+                    )
+                })
+            {
+                add_finding(Finding::new(
+                    Code::NoOccurrences,
+                    i18n.t(match params.anchor {
+                        ExpansionAnchor::Start => "expansion-none-start",
+                        ExpansionAnchor::Now => "expansion-none-now",
+                        ExpansionAnchor::Range => "expansion-none-range",
+                    }),
+                ));
+            } else if events.len() >= params.count {
+                add_finding(Finding::new(
+                    Code::ExpansionLimitReached,
+                    i18n.t_with(
+                        "expansion-limit-reached",
+                        FluentArgs::from_iter([("count", params.count)]),
                     ),
+                ));
+            }
+            let spans = events
+                .iter()
+                .map(|event| (event.start.naive_local(), event.end.naive_local()))
+                .collect::<Vec<_>>();
+            duration_stats.set(DurationStats::compute(&spans));
+            heatmap.set(WeeklyHeatmap::compute(&spans));
+            let working_hours = working_hours.get();
+            let holidays = holidays.get();
+            let mut rows = events
+                .into_iter()
+                .zip(origins)
+                .zip(details)
+                .zip(times)
+                .map(|(((event, origin), details), (from, to))| {
+                    let occurrence = Occurrence {
+                        kind: details.kind,
+                        title: details.title,
+                        progress: details.progress,
+                        holiday: holidays
+                            .find(event.start.naive_local(), event.end.naive_local())
+                            .map(|name| name.to_string()),
+                        outside_hours: !working_hours
+                            .contains(event.start.naive_local(), event.end.naive_local()),
+                        from: Some(from),
+                        to: Some(to),
+                        origin,
+                    };
+                    (event.start.naive_local(), occurrence)
                 })
+                .collect::<Vec<_>>();
+            rows.extend(due_tasks.into_iter().map(|task| {
+                let occurrence = Occurrence {
+                    kind: EntryKind::Task,
+                    title: task.title,
+                    from: None,
+                    to: Some(OccurrenceTime::in_named_zone(task.due, task.tz.as_deref())),
+                    progress: task.progress,
+                    holiday: None,
+                    outside_hours: false,
+                    origin: Origin::Regular,
+                };
+                (task.due, occurrence)
+            }));
+            rows.extend(removed.into_iter().map(|date| {
+                let occurrence = Occurrence {
+                    kind: date.kind,
+                    title: None,
+                    from: Some(OccurrenceTime::in_named_zone(
+                        date.start,
+                        date.tz.as_deref(),
+                    )),
+                    to: None,
+                    progress: None,
+                    holiday: None,
+                    outside_hours: false,
+                    origin: Origin::Removed,
+                };
+                (date.start, occurrence)
+            }));
+            rows.sort_by_key(|(start, _)| *start);
+            let occurrence_list = rows
+                .into_iter()
+                .map(|(_, occurrence)| occurrence)
+                .collect::<Vec<_>>();
+            for occurrence in &occurrence_list {
+                let location = occurrence
+                    .from
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                if let Some(holiday) = &occurrence.holiday {
+                    add_finding(
+                        Finding::new(
+                            Code::OccurrenceOnHoliday,
+                            i18n.t_with(
+                                "occurrence-on-holiday-finding",
+                                FluentArgs::from_iter([("holiday", holiday.clone())]),
+                            ),
+                        )
+                        .with_location(location.clone()),
+                    );
+                }
+                if occurrence.outside_hours {
+                    add_finding(
+                        Finding::new(
+                            Code::OccurrenceOutsideWorkingHours,
+                            i18n.t("occurrence-outside-hours-finding"),
+                        )
+                        .with_location(location),
+                    );
+                }
+            }
+            occurrences.set(occurrence_list);
+        };
+
+    // Expansion is limited to the first calendar shown. It runs a pass at a
+    // time, letting the page render in between so a large calendar does not
+    // freeze it, and stops once superseded.
+    let expand_shown = move |results: &[EntryResult], selected: Option<usize>| {
+        expansion_generation.update_value(|generation| *generation += 1);
+        let generation = expansion_generation.get_value();
+        expansion_elapsed.set(None);
+        expansion_out_of_budget.set(false);
+        occurrences.set(vec![]);
+        occurrences_export.set(String::new());
+        occurrences_csv.set(String::new());
+        occurrences_ics.set(String::new());
+        duration_stats.set(None);
+        heatmap.set(None);
+        alarm_firings.set(vec![]);
+        if let Some((entry, result, icalendar)) = results
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| selected.is_none_or(|selected| selected == *index))
+            .find_map(|(_, entry)| {
+                let result = entry.result.as_ref().ok()?;
+                Some((entry, result, result.icalendar.as_ref()?))
+            })
+        {
+            let text = match result.format {
+                Format::ICalendar => entry.source.clone(),
+                Format::JCal => jcal::to_text(&entry.source).unwrap_or_default(),
+                _ => result.converted.clone(),
+            };
+            let icalendar = icalendar.clone();
+            let params = expansion.get_untracked();
+            spawn_local(async move {
+                let mut expander =
+                    occurrences::Expander::new(&icalendar, params.timezone, &params.window());
+                let mut elapsed = 0.0;
+                loop {
+                    if expansion_generation.get_value() != generation {
+                        return;
+                    }
+                    let started = now_ms();
+                    let done = expander.step();
+                    elapsed += now_ms() - started;
+                    if done || elapsed > f64::from(params.budget_ms) {
+                        break;
+                    }
+                    next_tick().await;
+                }
+                let expanded = expander.finish(elapsed);
+                set_occurrences(&icalendar, &text, params, expanded);
+            });
+        }
+    };
+
+    // Runs the expansion again with new parameters, reusing the parsed calendar.
+    let reexpand = move || {
+        findings
+            .update(|findings| findings.retain(|finding| !EXPANSION_CODES.contains(&finding.code)));
+        entry_results.with_value(|results| expand_shown(results, selected_entry.get_untracked()));
+    };
+
+    // Findings cover every entry, the output and occurrences only the shown ones.
+    let show_entries = move |selected: Option<usize>| {
+        selected_entry.set(selected);
+        occurrences.set(vec![]);
+        occurrences_export.set(String::new());
+        occurrences_csv.set(String::new());
+        occurrences_ics.set(String::new());
+        duration_stats.set(None);
+        heatmap.set(None);
+        alarm_firings.set(vec![]);
+        error_message.set(String::new());
+        error_location.set(None);
+        bug_report.set(None);
+        json_errors.set(vec![]);
+        entry_results.with_value(|results| {
+            findings.set(
+                transcoded
+                    .get_value()
+                    .into_iter()
+                    .chain(stripped.get_value())
+                    .chain(normalized.get_value())
+                    .chain(results.iter().flat_map(|entry| match &entry.result {
+                        Ok(result) => result.findings.clone(),
+                        Err(finding) => vec![finding.clone()],
+                    }))
+                    .collect(),
+            );
+            let in_selection = |index: &usize| selected.is_none_or(|selected| selected == *index);
+            let shown = results
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| in_selection(index))
+                .filter_map(|(_, entry)| entry.result.as_ref().ok())
+                .collect::<Vec<_>>();
+
+            let Some(format) = shown.first().map(|result| result.format) else {
+                let errors = results
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| in_selection(index))
+                    .filter_map(|(_, entry)| {
+                        Some((entry.source.as_str(), entry.result.as_ref().err()?))
+                    })
+                    .collect::<Vec<_>>();
+                error_message.set(match errors.as_slice() {
+                    [(entry, finding)] => {
+                        error_location
+                            .set(source.with_untracked(|text| locate_error(text, entry, finding)));
+                        if entry.trim_start().starts_with('{') {
+                            json_errors.set(json_diagnostics::diagnose(entry, None));
+                        }
+                        if finding.code == Code::RoundtripFailed {
+                            bug_report.set(
+                                jmap_convert::Converter
+                                    .detect_format(entry)
+                                    .ok()
+                                    .map(|format| BugReport::new(format, finding, entry)),
+                            );
+                        }
+                        finding.message.clone()
+                    }
+                    _ => i18n.t_with(
+                        "conversion-all-failed",
+                        FluentArgs::from_iter([("count", errors.len())]),
+                    ),
+                });
+                conversion.set(String::new());
+                roundtrip_conversion.set(String::new());
+                fidelity.set(vec![]);
+                return;
+            };
+            source_type.set(format);
+            shown_source.set(
+                format.join(
+                    results
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, entry)| in_selection(index) && entry.result.is_ok())
+                        .map(|(_, entry)| entry.source.as_str()),
+                ),
+            );
+            expand_shown(results, selected);
+            conversion.set(
+                format
+                    .counterpart()
+                    .join(shown.iter().map(|result| result.converted.as_str())),
+            );
+            roundtrip_conversion
+                .set(format.join(shown.iter().map(|result| result.roundtrip.as_str())));
+            fidelity.set(
+                shown
+                    .iter()
+                    .flat_map(|result| result.fidelity.clone())
+                    .collect(),
+            );
+        });
+    };
+
+    // Every programmatic or committed edit goes through here so it can be undone.
+    let set_source = move |text: String| {
+        let previous = source.get_untracked();
+        set_session.update(|session| {
+            let document = session.active_mut();
+            document.history.record(previous, &text);
+            document.source = text.clone();
+        });
+        source.set(text);
+    };
+
+    // Every option that affects a conversion, as saved with bundles and the history.
+    let current_options = move || {
+        let mut options = BundleOptions {
+            excluded_components: excluded_components.get_untracked(),
+            strip_vendor: strip_vendor.get_untracked(),
+            vendor_keep: vendor_keep.get_untracked(),
+            normalize_contacts: normalize_contacts.get_untracked(),
+            default_country: default_country.get_untracked(),
+            strict_parsing: strict_parsing.get_untracked(),
+            selected_entry: selected_entry.get_untracked(),
+            working_hours: working_hours.get_untracked(),
+            overlay: overlay_mode.get_untracked(),
+            holidays: holidays.with_untracked(|holidays| holidays.source().to_string()),
+            ..Default::default()
+        };
+        expansion.get_untracked().to_options(&mut options);
+        options
+    };
+    let apply_options = move |options: &BundleOptions| {
+        excluded_components.set(options.excluded_components.clone());
+        strip_vendor.set(options.strip_vendor);
+        vendor_keep.set(options.vendor_keep.clone());
+        normalize_contacts.set(options.normalize_contacts);
+        default_country.set(options.default_country.clone());
+        strict_parsing.set(options.strict_parsing);
+        selected_entry.set(options.selected_entry);
+        expansion.set(ExpansionParams::from_options(options));
+        working_hours.set(options.working_hours.clone());
+        overlay_mode.set(options.overlay);
+        holidays.set(Holidays::parse(&options.holidays).unwrap_or_default());
+    };
+
+    let finish_conversion = move |results: Vec<EntryResult>, elapsed: f64| {
+        conversion_elapsed.set_value(elapsed);
+        recoveries.set(recovery::scan_all(&results));
+        let results = if strict_parsing.get_untracked() {
+            recovery::strict(results)
+        } else {
+            results
+        };
+        let count = results.len();
+        entries.set(
+            results
+                .iter()
+                .map(|result| EntrySummary::new(result, i18n))
                 .collect(),
         );
+        entry_results.set_value(results);
+        show_entries(
+            selected_entry
+                .get_untracked()
+                .filter(|index| *index < count),
+        );
+        // Only inputs that convert are worth coming back to.
+        let titles = entries.with_untracked(|entries| {
+            entries
+                .iter()
+                .filter(|entry| entry.error.is_none())
+                .map(|entry| entry.title.clone())
+                .collect::<Vec<_>>()
+        });
+        if !titles.is_empty() {
+            let input = RecentInput {
+                document: session.with_untracked(|session| session.active().name.clone()),
+                source: source.get_untracked(),
+                format: Some(source_type.get_untracked()),
+                converted: DateTime::from_timestamp_millis(js_sys::Date::now() as i64)
+                    .unwrap_or_default(),
+                summary: recent::summarize(&titles),
+                options: current_options(),
+            };
+            set_recent_inputs.update(|recent| recent.record(input));
+        }
     };
 
     let convert = move || {
         let source = source.get();
         let source = source.trim_start();
-        occurrences.set(vec![]);
-        error_message.set(String::new());
+        conversion_generation.update_value(|generation| *generation += 1);
+        expansion_generation.update_value(|generation| *generation += 1);
+        conversion_progress.set(None);
 
         if source.is_empty() {
+            email_parts.set(None);
+            html_extracted.set(None);
+            csv_columns.set(None);
+            imported.set(None);
+            entry_results.set_value(vec![]);
+            entries.set(vec![]);
+            selected_entry.set(None);
+            occurrences.set(vec![]);
+            occurrences_export.set(String::new());
+            occurrences_csv.set(String::new());
+            occurrences_ics.set(String::new());
+            duration_stats.set(None);
+            heatmap.set(None);
+            alarm_firings.set(vec![]);
+            findings.set(vec![]);
+            error_message.set(String::new());
+            error_location.set(None);
+            bug_report.set(None);
+            json_errors.set(vec![]);
+            conversion.set(String::new());
+            roundtrip_conversion.set(String::new());
+            fidelity.set(vec![]);
+            removed_components.set(0);
+            stripped.set_value(vec![]);
+            normalized.set_value(vec![]);
+            transcoded.set_value(vec![]);
+            recoveries.set(vec![]);
             return;
         }
 
-        if source.starts_with("BEGIN:") {
-            match Parser::new(source).entry() {
-                Entry::VCard(vcard) => {
-                    source_type.set(SourceType::VCard);
-                    let jscontact = vcard.into_jscontact();
-                    conversion.set(jscontact.to_string_pretty());
-                    match jscontact.into_vcard() {
-                        Some(vcard_roundtrip) => {
-                            roundtrip_conversion.set(vcard_roundtrip.to_string());
-                        }
-                        None => {
-                            set_error("Looks like you've found a bug in the conversion. Please report it.".to_string());
-                        }
-                    }
-                }
-                Entry::ICalendar(icalendar) => {
-                    source_type.set(SourceType::ICalendar);
-                    set_occurrences(icalendar.expand_dates(Tz::Floating, 25));
-                    let jscalendar = icalendar.into_jscalendar();
-                    conversion.set(jscalendar.to_string_pretty());
-                    match jscalendar.into_icalendar() {
-                        Some(icalendar_roundtrip) => {
-                            roundtrip_conversion.set(icalendar_roundtrip.to_string());
-                        }
-                        None => {
-                            set_error("Looks like you've found a bug in the conversion. Please report it.".to_string());
-                        }
-                    }
-                }
-                Entry::InvalidLine(text) => {
-                    set_error(format!("Invalid line found: {}", text));
-                }
-                Entry::UnexpectedComponentEnd { expected, found } => {
-                    set_error(format!(
-                        "Unexpected component end: expected {}, found {}",
-                        expected.as_str(),
-                        found.as_str()
-                    ));
-                }
-                Entry::UnterminatedComponent(cow) => {
-                    set_error(format!("Unterminated component: {}", cow));
-                }
-                Entry::TooManyComponents => {
-                    set_error("Too many components".to_string());
-                }
-                Entry::Eof => {
-                    set_error("Unexpected end of file".to_string());
-                }
-                _ => todo!(),
+        // An email converts the calendar or vCard part picked from it.
+        let parts = email::is_message(source).then(|| email::extract(source));
+        if let Some(parts) = &parts {
+            email_part.set(
+                email_part
+                    .get_untracked()
+                    .min(parts.len().saturating_sub(1)),
+            );
+        }
+        let part = parts
+            .as_ref()
+            .and_then(|parts| parts.get(email_part.get_untracked()))
+            .map(|part| part.text.clone());
+        email_parts.set(parts);
+        let source = part.as_deref().unwrap_or(source);
+
+        // vCard 2.1 and vCalendar 1.0 values written as quoted-printable or in
+        // a legacy charset are decoded before parsing.
+        let decoded = legacy_encoding::transcode(source).map(|decoded| {
+            transcoded.set_value(decoded.findings);
+            decoded.text
+        });
+        if decoded.is_none() {
+            transcoded.set_value(vec![]);
+        }
+        let source = decoded.as_deref().unwrap_or(source);
+
+        // A web page converts the events, or else the contacts, marked up in
+        // it.
+        let extracted = html_extract::is_html(source).then(|| {
+            let now =
+                DateTime::from_timestamp_millis(js_sys::Date::now() as i64).unwrap_or_default();
+            html_extract::extract(source, now)
+        });
+        let markup = extracted
+            .as_ref()
+            .and_then(|extracted| extracted.as_ref().ok())
+            .map(|extracted| extracted.text.clone());
+        html_extracted.set(extracted);
+        let source = markup.as_deref().unwrap_or(source);
+
+        // A CSV export of contacts converts as one vCard per row. The mapping
+        // is reset whenever the columns change.
+        let table = CsvTable::parse(source);
+        if let Some(table) = &table {
+            let columns = Some((table.layout, table.headers.clone()));
+            if csv_columns.get_untracked() != columns {
+                csv_mapping.set(table.default_mapping());
+                csv_columns.set(columns);
             }
-        } else if source.starts_with('{') {
-            if source.contains("\"Group\"") {
-                match JSCalendar::parse(source.trim_end()) {
-                    Ok(jscalendar) => match jscalendar.into_icalendar() {
-                        Some(icalendar) => {
-                            source_type.set(SourceType::JSCalendar);
-                            conversion.set(icalendar.to_string());
-                            set_occurrences(icalendar.expand_dates(Tz::Floating, 25));
-                            roundtrip_conversion
-                                .set(icalendar.into_jscalendar().to_string_pretty());
-                        }
-                        None => {
-                            set_error("Looks like you've found a bug in the conversion. Please report it.".to_string());
-                        }
-                    },
-                    Err(err) => {
-                        set_error(format!("Failed to parse JSCalendar: {}", err));
+        } else {
+            csv_columns.set(None);
+        }
+        let vcards = table.map(|table| table.to_vcards(&csv_mapping.get_untracked()));
+        let source = vcards.as_deref().unwrap_or(source);
+        imported.set(
+            (part.is_some() || decoded.is_some() || markup.is_some() || vcards.is_some())
+                .then(|| source.to_string()),
+        );
+
+        let (source, removed) = filter::filter_source(source, &excluded_components.get_untracked());
+        removed_components.set(removed);
+        let source = if strip_vendor.get_untracked() {
+            let keep = sanitize::parse_keep_list(&vendor_keep.get_untracked());
+            let (source, removed) = sanitize::strip_vendor(&source, &keep);
+            stripped.set_value(removed);
+            source
+        } else {
+            stripped.set_value(vec![]);
+            source
+        };
+        let source = if normalize_contacts.get_untracked() {
+            let (source, cleaned) =
+                contact_cleanup::normalize(&source, &default_country.get_untracked());
+            normalized.set_value(cleaned);
+            source
+        } else {
+            normalized.set_value(vec![]);
+            source
+        };
+        let started = now_ms();
+        if source.len() <= BACKGROUND_CONVERSION_THRESHOLD {
+            finish_conversion(
+                jmap_convert::Converter.convert_all(&source),
+                now_ms() - started,
+            );
+            return;
+        }
+
+        // Large sources are split a slice at a time and each entry converts
+        // as soon as it is complete. A calendar converts a batch of its
+        // components at a time, joined back into one result once all of them
+        // are done, so the output is the same as for a small source. Control
+        // goes back to the page every frame, so the entry list grows, the
+        // progress shows and the conversion can be cancelled.
+        let generation = conversion_generation.get_value();
+        let total = source.len();
+        let mut offset = total - source.trim_start().len();
+        // JSON arrays can only be split once parsed as a whole.
+        let streamed = source[offset..].starts_with("BEGIN:");
+        entries.set(vec![]);
+        entry_results.set_value(vec![]);
+        conversion_progress.set(Some((0, offset, total)));
+        spawn_local(async move {
+            let mut splitter = EntrySplitter::default();
+            let mut index = 0;
+            let mut summaries = Vec::new();
+            let mut yielded = now_ms();
+            while offset < total {
+                let end = if streamed {
+                    let mut end = (offset + STREAM_CHUNK_SIZE).min(total);
+                    while !source.is_char_boundary(end) {
+                        end += 1;
+                    }
+                    end
+                } else {
+                    total
+                };
+                let mut parts = if streamed {
+                    splitter.push(&source[offset..end])
+                } else {
+                    split_entries(&source)
+                };
+                offset = end;
+                if streamed && offset == total {
+                    parts.extend(std::mem::take(&mut splitter).finish());
+                    if index == 0 && parts.is_empty() {
+                        parts.push(source.clone());
                     }
                 }
-            } else if source.contains("\"Card\"") {
-                match JSContact::parse(source) {
-                    Ok(jscontact) => match jscontact.into_vcard() {
-                        Some(vcard) => {
-                            source_type.set(SourceType::JSContact);
-                            conversion.set(vcard.to_string());
-                            roundtrip_conversion.set(vcard.into_jscontact().to_string_pretty());
+                let last = parts.len();
+                for (position, part) in parts.into_iter().enumerate() {
+                    let batches = calendar_batches(&part, CALENDAR_BATCH_SIZE);
+                    let mut converted = Vec::with_capacity(batches.len());
+                    for batch in &batches {
+                        if now_ms() - yielded > FRAME_MS {
+                            entries.update(|entries| entries.append(&mut summaries));
+                            conversion_progress.set(Some((index, offset, total)));
+                            next_tick().await;
+                            if conversion_generation.get_value() != generation {
+                                return;
+                            }
+                            yielded = now_ms();
                         }
-                        None => {
-                            set_error("Looks like you've found a bug in the conversion. Please report it.".to_string());
-                        }
-                    },
-                    Err(err) => {
-                        set_error(format!("Failed to parse JSContact: {}", err));
+                        converted.push(jmap_convert::Converter.convert_batch(batch));
                     }
+                    // The entries found so far, which is all of them once the
+                    // source has been split to its end.
+                    let count = index + 1 + usize::from(offset < total || position + 1 < last);
+                    let result =
+                        jmap_convert::Converter.join_batches(index, count, part, converted);
+                    summaries.push(EntrySummary::new(&result, i18n));
+                    entry_results.update_value(|results| results.push(result));
+                    index += 1;
                 }
-            } else {
-                set_error("This does not look like a valid JSCalendar or JSContact.".to_string());
             }
+            next_tick().await;
+            if conversion_generation.get_value() == generation {
+                conversion_progress.set(None);
+                let mut results = vec![];
+                entry_results.update_value(|converted| results = std::mem::take(converted));
+                finish_conversion(results, now_ms() - started);
+            }
+        });
+    };
+    let cancel_conversion = move || {
+        conversion_generation.update_value(|generation| *generation += 1);
+        expansion_generation.update_value(|generation| *generation += 1);
+        conversion_progress.set(None);
+        error_location.set(None);
+        bug_report.set(None);
+        json_errors.set(vec![]);
+        error_message.set(i18n.t("conversion-cancelled"));
+    };
+
+    if !source.get_untracked().trim().is_empty() {
+        convert();
+    }
+
+    let step_history = move |redo: bool| {
+        let current = source.get_untracked();
+        if let Some(text) = set_session
+            .try_update(|session| {
+                let document = session.active_mut();
+                let text = if redo {
+                    document.history.redo(current)
+                } else {
+                    document.history.undo(current)
+                }?;
+                document.source = text.clone();
+                Some(text)
+            })
+            .flatten()
+        {
+            source.set(text);
+            convert();
+        }
+    };
+    let undo = move || step_history(false);
+    let redo = move || step_history(true);
+    // A form picked for the other kind of output falls back to the text.
+    let shown_form = Signal::derive(move || {
+        let form = output_form.get();
+        if OutputForm::offered(source_type.get().counterpart()).contains(&form) {
+            form
+        } else {
+            OutputForm::Text
+        }
+    });
+    let output_format = Signal::derive(move || {
+        let format = source_type.get().counterpart();
+        match shown_form.get() {
+            OutputForm::Json => format.json_form(),
+            OutputForm::Text | OutputForm::Xml | OutputForm::Jmap => format,
+        }
+    });
+    let shown_conversion = Signal::derive(move || match shown_form.get() {
+        OutputForm::Text => conversion.get(),
+        OutputForm::Json => conversion.with(|conversion| jcal::from_text(conversion)),
+        OutputForm::Xml => conversion.with(|conversion| xcal::from_text(conversion)),
+        OutputForm::Jmap => conversion.with(|conversion| {
+            jmap::set_request(conversion).unwrap_or_else(|err| {
+                i18n.t_with(
+                    "output-jmap-failed",
+                    FluentArgs::from_iter([("error", err)]),
+                )
+            })
+        }),
+    });
+    let download_xml = move || {
+        let format = source_type.get_untracked().counterpart();
+        let name = export::file_name(format, &conversion.get_untracked());
+        let name = format!(
+            "{}.xml",
+            name.strip_suffix(&format!(".{}", format.extension()))
+                .unwrap_or(&name)
+        );
+        let mime_type = if format.is_calendar() {
+            "application/calendar+xml"
+        } else {
+            "application/vcard+xml"
+        };
+        if let Err(err) = save_file(&shown_conversion.get_untracked(), mime_type, &name) {
+            log::warn!("Failed to download the output: {:?}", err);
+        }
+    };
+    let download_output = move || {
+        if conversion.with_untracked(String::is_empty) {
+            return;
+        }
+        if shown_form.get_untracked() == OutputForm::Xml {
+            download_xml();
+        } else if let Err(err) = download(
+            shown_conversion.get_untracked(),
+            output_format.get_untracked(),
+            export_settings.redact_private.get_untracked(),
+        ) {
+            log::warn!("Failed to download the output: {:?}", err);
+        }
+    };
+    let load_sample = move || {
+        set_source(
+            SAMPLES
+                .choose(&mut rand::thread_rng())
+                .map(|(_, sample)| *sample)
+                .unwrap_or_default()
+                .to_string(),
+        );
+        convert();
+    };
+    // Converts what is in the editor, including typing not yet committed.
+    let convert_editor = move || {
+        if let Some(editor) = editor_ref.get_untracked() {
+            live_generation.update_value(|generation| *generation += 1);
+            let text = export::strip_context(&editor.value()).to_string();
+            if text != source.get_untracked() {
+                set_source(text);
+            }
+        }
+        convert();
+    };
+    let shortcut_action = move |ev: &ev::KeyboardEvent| {
+        keybindings::action(
+            &ev.key(),
+            ev.ctrl_key() || ev.meta_key(),
+            ev.shift_key(),
+            ev.alt_key(),
+        )
+    };
+    let run_shortcut = move |ev: &ev::KeyboardEvent, action: Action| {
+        ev.prevent_default();
+        match action {
+            Action::Convert => convert_editor(),
+            Action::Download => download_output(),
+            Action::Sample => load_sample(),
+            Action::Undo => undo(),
+            Action::Redo => redo(),
+        }
+    };
+    // Form fields keep their native undo, the textarea is handled below.
+    let shortcut_keys = window_event_listener(ev::keydown, move |ev| {
+        let Some(action) = shortcut_action(&ev) else {
+            return;
+        };
+        let in_field = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            .is_some_and(|element| {
+                matches!(element.tag_name().as_str(), "INPUT" | "SELECT" | "TEXTAREA")
+            });
+        if !(in_field && action.is_history()) {
+            run_shortcut(&ev, action);
+        }
+    });
+    on_cleanup(move || shortcut_keys.remove());
+
+    let load_active_document = move |update: &dyn Fn(&mut Session)| {
+        set_session.update(|session| update(session));
+        source.set(session.with_untracked(|session| session.active().source.clone()));
+        convert();
+    };
+
+    // The tour explains a recurring meeting request, opened in a document of
+    // its own so whatever is being worked on stays as it was.
+    let start_tour = move || {
+        load_active_document(&|session| {
+            session.open();
+            session.rename(session.active_index(), &i18n.t("tour-sample"));
+            session.active_mut().source = TOUR_SAMPLE.to_string();
+        });
+        validating.set(false);
+        tour.set(Some(TourStep::Detection));
+    };
+
+    let session_bundle = move || SessionBundle {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        exported: String::from(js_sys::Date::new_0().to_iso_string()),
+        name: session.with_untracked(|session| session.active().name.clone()),
+        format: (!conversion.with_untracked(String::is_empty)).then(|| source_type.get_untracked()),
+        source: source.get_untracked(),
+        options: current_options(),
+        converted: conversion.get_untracked(),
+        roundtrip: roundtrip_conversion.get_untracked(),
+        occurrences: occurrences_export.get_untracked(),
+        findings: findings.get_untracked(),
+    };
+    let conversion_report = move || {
+        entry_results.with_value(|results| {
+            if results.is_empty() {
+                return None;
+            }
+            Some(ConversionReport {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                generated: String::from(js_sys::Date::new_0().to_iso_string()),
+                findings: transcoded
+                    .get_value()
+                    .into_iter()
+                    .chain(stripped.get_value())
+                    .chain(normalized.get_value())
+                    .collect(),
+                timings: Timings {
+                    conversion_ms: conversion_elapsed.get_value(),
+                    expansion_ms: expansion_elapsed.get_untracked(),
+                },
+                ..ConversionReport::new(
+                    session.with_untracked(|session| session.active().name.clone()),
+                    &source.get_untracked(),
+                    results,
+                )
+            })
+        })
+    };
+    // Opens the bundle in a new document with its options and converts it.
+    let open_bundle = move |bundle: &SessionBundle| {
+        apply_options(&bundle.options);
+        load_active_document(&|session| {
+            session.open();
+            session.rename(session.active_index(), &bundle.name);
+            session.active_mut().source = bundle.source.clone();
+        });
+    };
+    // Loads an input from the history into the active document, where loading
+    // it can be undone.
+    let restore_recent = move |index: usize| {
+        let Some(input) = recent_inputs.with_untracked(|recent| recent.get(index).cloned()) else {
+            return;
+        };
+        apply_options(&input.options);
+        set_source(input.source);
+        convert();
+    };
+    // Restores an imported bundle, telling whether this version still
+    // produces the same outputs.
+    let restore_bundle = move |bundle: SessionBundle| {
+        open_bundle(&bundle);
+
+        let exported_by = if bundle.exported.is_empty() {
+            i18n.t_with(
+                "bundle-restored",
+                FluentArgs::from_iter([("version", bundle.version.clone())]),
+            )
         } else {
-            set_error("Unrecognized format. Please provide a valid iCalendar, JSCalendar, vCard or JSContact file.".to_string());
+            i18n.t_with(
+                "bundle-restored-on",
+                FluentArgs::from_iter([
+                    ("version", bundle.version.clone()),
+                    ("date", bundle.exported.clone()),
+                ]),
+            )
+        };
+        if conversion_progress.get_untracked().is_some() {
+            return format!("{} {}", exported_by, i18n.t("bundle-still-converting"));
         }
+        let outcome = match bundle
+            .changed_outputs(
+                &conversion.get_untracked(),
+                &roundtrip_conversion.get_untracked(),
+            )
+            .as_slice()
+        {
+            [] => "bundle-unchanged",
+            ["conversion"] => "bundle-changed-conversion",
+            ["roundtrip"] => "bundle-changed-roundtrip",
+            _ => "bundle-changed-both",
+        };
+        format!("{} {}", exported_by, i18n.t(outcome))
     };
+    // A shared link opens its session in a new document. The fragment is
+    // dropped afterwards so reloading the page does not open it again.
+    if let Some(bundle) = permalink::decode(&window().location().hash().unwrap_or_default()) {
+        match bundle {
+            Ok(bundle) => open_bundle(&bundle),
+            Err(err) => error_message.set(err),
+        }
+        let location = window().location();
+        if let Ok(history) = window().history() {
+            _ = history.replace_state_with_url(
+                &JsValue::NULL,
+                "",
+                Some(&format!(
+                    "{}{}",
+                    location.pathname().unwrap_or_default(),
+                    location.search().unwrap_or_default()
+                )),
+            );
+        }
+    }
 
-    view! {
-        <Body class="dark:bg-slate-900 bg-gray-100 "/>
-
-        <div class="max-w-4xl px-4 py-10 sm:px-6 lg:px-8 mx-auto">
-            <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
-                <div class="mb-8">
-                    <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
-                        JSCalendar and JSContact conversion
-                    </h2>
-                    <p class="text-sm text-gray-600 dark:text-neutral-400">
-                        "Bi-directional conversion from/to JSCalendar/iCalendar and JSContact/vCard."
-                    </p>
-                </div>
+    // Query parameters preset the sample, the expansion and the output form,
+    // on load and whenever the address changes.
+    let (query_options, set_query_options) = use_query_options(
+        SAMPLES.iter().map(|(name, _)| *name).collect(),
+        MAX_EXPANSION_COUNT,
+    );
+    let apply_query = move |options: QueryOptions| {
+        let current = expansion.get_untracked();
+        let mut params = current;
+        if let Some(timezone) = options.timezone {
+            params.timezone = Tz::Tz(timezone);
+        }
+        if let Some(count) = options.expand {
+            params.count = count;
+        }
+        let reexpanding = params.timezone != current.timezone || params.count != current.count;
+        if reexpanding {
+            expansion.set(params);
+        }
+        if let Some(form) = options.output.map(OutputForm::from_query) {
+            if output_form.get_untracked() != form {
+                output_form.set(form);
+            }
+        }
+        match options
+            .sample
+            .and_then(|name| SAMPLES.iter().find(|(sample, _)| *sample == name))
+        {
+            Some((name, sample)) if source.with_untracked(|source| source.as_str() != *sample) => {
+                load_active_document(&|session| {
+                    session.open();
+                    session.rename(session.active_index(), name);
+                    session.active_mut().source = sample.to_string();
+                });
+            }
+            _ if reexpanding => reexpand(),
+            _ => {}
+        }
+    };
+    create_effect(move |_| {
+        let options = query_options.get();
+        untrack(|| apply_query(options));
+    });
+    // The address follows the options, so it can be shared to open the page
+    // as it is. Only options away from their defaults are written, and the
+    // sample only while the source is left as it was.
+    create_effect(move |_| {
+        let defaults = ExpansionParams::default();
+        let params = expansion.get();
+        let options = QueryOptions {
+            sample: source.with(|source| {
+                SAMPLES
+                    .iter()
+                    .find(|(_, sample)| *sample == source.as_str())
+                    .map(|(name, _)| name.to_string())
+            }),
+            timezone: match params.timezone {
+                Tz::Tz(timezone) if params.timezone != defaults.timezone => Some(timezone),
+                _ => None,
+            },
+            expand: (params.count != defaults.count).then_some(params.count),
+            output: match output_form.get() {
+                OutputForm::Text => None,
+                form => Some(form.to_query(source_type.get().counterpart())),
+            },
+        };
+        untrack(|| set_query_options(&options));
+    });
 
-                <Show when=move || !error_message.get().is_empty()>
-                    <div class="mb-6">
-                        <div class="bg-red-50 border border-red-200 text-sm text-red-800 rounded-lg p-4 dark:bg-red-800/10 dark:border-red-900 dark:text-red-500">
-                            <div class="flex">
-                                <div class="shrink-0">
-                                    <svg
-                                        class="shrink-0 size-4 mt-0.5"
-                                        xmlns="http://www.w3.org/2000/svg"
-                                        width="24"
-                                        height="24"
-                                        viewBox="0 0 24 24"
-                                        fill="none"
-                                        stroke="currentColor"
-                                        stroke-width="2"
-                                        stroke-linecap="round"
-                                        stroke-linejoin="round"
-                                    >
-                                        <circle cx="12" cy="12" r="10"></circle>
-                                        <path d="m15 9-6 6"></path>
-                                        <path d="m9 9 6 6"></path>
-                                    </svg>
-                                </div>
-                                <div class="ms-4">
-                                    <h3 id="hs-with-list-label" class="text-sm font-semibold">
-                                        {move || error_message.get()}
-                                    </h3>
-                                </div>
+    let jscontact = Signal::derive(move || match source_type.get() {
+        Format::VCard | Format::JCard => conversion.get(),
+        Format::JSContact => document.get(),
+        Format::ICalendar | Format::JSCalendar | Format::JCal => String::new(),
+    });
+    let vcard = Signal::derive(move || match source_type.get() {
+        Format::VCard => document.get(),
+        Format::JSContact => conversion.get(),
+        Format::JCard => document.with(|source| jcal::to_text(source).unwrap_or_default()),
+        Format::ICalendar | Format::JSCalendar | Format::JCal => String::new(),
+    });
+    let jscalendar = Signal::derive(move || match source_type.get() {
+        Format::ICalendar | Format::JCal => conversion.get(),
+        Format::JSCalendar => document.get(),
+        Format::VCard | Format::JSContact | Format::JCard => String::new(),
+    });
+    let icalendar = Signal::derive(move || match source_type.get() {
+        Format::ICalendar => document.get(),
+        Format::JSCalendar => conversion.get(),
+        Format::JCal => document.with(|source| jcal::to_text(source).unwrap_or_default()),
+        Format::VCard | Format::JSContact | Format::JCard => String::new(),
+    });
+    // Explained from the source as it was written.
+    let recurrence_explanations = create_memo(move |_| match source_type.get() {
+        Format::ICalendar => document.with(|source| rrule_text::from_icalendar(source)),
+        Format::JSCalendar => document.with(|source| rrule_text::from_jscalendar(source)),
+        Format::JCal => icalendar.with(|source| rrule_text::from_icalendar(source)),
+        Format::VCard | Format::JSContact | Format::JCard => vec![],
+    });
+    // Properties of the shown JSCalendar or JSContact entries that their
+    // round trip did not keep.
+    let ignored_properties = create_memo(move |_| match source_type.get() {
+        Format::JSCalendar | Format::JSContact => shown_source.with(|source| {
+            roundtrip_conversion
+                .with(|roundtrip| json_diagnostics::diagnose(source, Some(roundtrip)))
+        }),
+        _ => vec![],
+    });
+
+    let export_context = move |format: Format| ExportContext {
+        format: format.as_str().to_string(),
+        source_type: source_type.get().as_str().to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        options: match source_type.get() {
+            Format::ICalendar | Format::JSCalendar | Format::JCal => vec![
+                (
+                    "expansion-limit".to_string(),
+                    expansion.get().count.to_string(),
+                ),
+                (
+                    "overlay".to_string(),
+                    overlay_mode.get().as_str().to_string(),
+                ),
+            ],
+            Format::VCard | Format::JSContact | Format::JCard => vec![],
+        },
+        timestamp: String::new(),
+    };
+
+    // Selects the parse error in the editor and scrolls it into view.
+    let show_error = move || {
+        let (Some(textarea), Some((_, range))) =
+            (editor_ref.get_untracked(), error_location.get_untracked())
+        else {
+            return;
+        };
+        source.with_untracked(|text| {
+            // The textarea counts UTF-16 units and has its line breaks normalized.
+            let position = |offset: usize| {
+                text.get(..offset)
+                    .map_or(0, |text| text.replace("\r\n", "\n").encode_utf16().count())
+                    as u32
+            };
+            _ = textarea.focus();
+            _ = textarea.set_selection_range(position(range.start), position(range.end));
+            let line = text
+                .get(..range.start)
+                .unwrap_or_default()
+                .matches('\n')
+                .count() as i32;
+            let lines = text.matches('\n').count() as i32 + 1;
+            textarea.set_scroll_top(
+                textarea.scroll_height() * line / lines - textarea.client_height() / 3,
+            );
+        });
+    };
+    let error_banner = move || {
+        view! {
+            <Show when=move || !error_message.get().is_empty()>
+                <div class="mb-6">
+                    <div class="bg-red-50 border border-red-200 text-sm text-red-800 rounded-lg p-4 dark:bg-red-800/10 dark:border-red-900 dark:text-red-500">
+                        <div class="flex">
+                            <div class="shrink-0">
+                                <svg
+                                    class="shrink-0 size-4 mt-0.5"
+                                    xmlns="http://www.w3.org/2000/svg"
+                                    width="24"
+                                    height="24"
+                                    viewBox="0 0 24 24"
+                                    fill="none"
+                                    stroke="currentColor"
+                                    stroke-width="2"
+                                    stroke-linecap="round"
+                                    stroke-linejoin="round"
+                                >
+                                    <circle cx="12" cy="12" r="10"></circle>
+                                    <path d="m15 9-6 6"></path>
+                                    <path d="m9 9 6 6"></path>
+                                </svg>
+                            </div>
+                            <div class="ms-4">
+                                <h3 id="hs-with-list-label" class="text-sm font-semibold">
+                                    {move || error_message.get()}
+                                </h3>
+                                <BugReportButton report=bug_report/>
+                                <Show when=move || json_errors.with(|errors| !errors.is_empty())>
+                                    <JsonDiagnosticsTree diagnostics=json_errors/>
+                                </Show>
+                                {move || {
+                                    error_location
+                                        .get()
+                                        .map(|(location, _)| {
+                                            view! {
+                                                <button
+                                                    type="button"
+                                                    class="mt-1 text-xs underline hover:no-underline"
+                                                    title=move || i18n.t("error-show-title")
+                                                    on:click=move |_| show_error()
+                                                >
+                                                    {move || {
+                                                        i18n.t_with(
+                                                            "error-show",
+                                                            FluentArgs::from_iter([("location", location.clone())]),
+                                                        )
+                                                    }}
+                                                </button>
+                                            }
+                                        })
+                                }}
                             </div>
                         </div>
                     </div>
-                </Show>
+                </div>
+            </Show>
+        }
+    };
+    let conversion_status = move || {
+        view! {
+            {move || {
+                file_progress
+                    .get()
+                    .map(|(name, read, size)| {
+                        let percent = (read * 100.0 / size.max(1.0)).round() as usize;
+                        view! {
+                            <div class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                                <p>
+                                    {move || {
+                                        i18n.t_with(
+                                            "editor-reading",
+                                            FluentArgs::from_iter([
+                                                ("name", name.clone()),
+                                                ("percent", percent.to_string()),
+                                            ]),
+                                        )
+                                    }}
+                                </p>
+                                <div class="mt-1 h-1.5 rounded-full bg-gray-200 overflow-hidden dark:bg-neutral-700">
+                                    <div class="h-full bg-blue-600" style=format!("width: {}%", percent)></div>
+                                </div>
+                            </div>
+                        }
+                    })
+            }}
+            {move || {
+                conversion_progress
+                    .get()
+                    .map(|(done, offset, total)| {
+                        let percent = offset * 100 / total.max(1);
+                        view! {
+                            <div class="mb-6 flex items-center gap-3 text-sm text-gray-600 dark:text-neutral-400">
+                                <div class="grow">
+                                    <p>
+                                        {move || {
+                                            i18n.t_with(
+                                                "conversion-progress",
+                                                FluentArgs::from_iter([("done", done), ("percent", percent)]),
+                                            )
+                                        }}
+                                    </p>
+                                    <div class="mt-1 h-1.5 rounded-full bg-gray-200 overflow-hidden dark:bg-neutral-700">
+                                        <div class="h-full bg-blue-600" style=format!("width: {}%", percent)></div>
+                                    </div>
+                                </div>
+                                <button
+                                    type="button"
+                                    class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                                    on:click=move |_| cancel_conversion()
+                                >
+                                    {move || i18n.t("conversion-cancel")}
+                                </button>
+                            </div>
+                        }
+                    })
+            }}
+        }
+    };
+    let entry_list = move || {
+        view! {
+            <EmailParts
+                parts=email_parts
+                selected=email_part
+                on_select=move |index: usize| {
+                    email_part.set(index);
+                    convert();
+                }
+            />
+            <HtmlExtractNote extracted=html_extracted/>
+            <CsvMappingPanel
+                columns=csv_columns
+                mapping=csv_mapping
+                on_change=move |_| convert()
+            />
+            <EntryList
+                entries=entries
+                selected=selected_entry
+                on_select=move |selected: Option<usize>| show_entries(selected)
+            />
+        }
+    };
+    let dragging = create_rw_signal(false);
+    let load_file = move |file: web_sys::File| {
+        spawn_local(async move {
+            let name = file.name();
+            let read = input::read_file_with_progress(&file, |read, size| {
+                file_progress.set(Some((name.clone(), read, size)))
+            })
+            .await;
+            file_progress.set(None);
+            match read {
+                Ok(text) => {
+                    set_session
+                        .update(|session| session.rename(session.active_index(), &file.name()));
+                    set_source(export::strip_context(&text).to_string());
+                    convert();
+                }
+                Err(err) => {
+                    error_location.set(None);
+                    bug_report.set(None);
+                    json_errors.set(vec![]);
+                    error_message.set(err);
+                }
+            }
+        });
+    };
 
-                <div class="relative">
+    let editor = move |fill: bool| {
+        view! {
+            <div id=SOURCE_TARGET class=if fill { "flex flex-col h-full" } else { "" }>
+                <DocumentTabs
+                    session=session
+                    on_select=move |index: usize| load_active_document(&move |session| session.select(index))
+                    on_open=move |_| load_active_document(&Session::open)
+                    on_close=move |index: usize| load_active_document(&move |session| session.close(index))
+                    on_rename=move |(index, name): (usize, String)| {
+                        set_session.update(|session| session.rename(index, &name))
+                    }
+                />
+                <div
+                    class=if fill { "relative grow min-h-0" } else { "relative" }
+                    on:dragover=move |ev: ev::DragEvent| {
+                        ev.prevent_default();
+                        dragging.set(true);
+                    }
+                    on:dragleave=move |ev: ev::DragEvent| {
+                        // Moving over the textarea or the toolbar also leaves this element.
+                        let inside = ev
+                            .related_target()
+                            .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+                            .zip(ev.current_target().and_then(|target| target.dyn_into::<web_sys::Node>().ok()))
+                            .is_some_and(|(target, zone)| zone.contains(Some(&target)));
+                        if !inside {
+                            dragging.set(false);
+                        }
+                    }
+                    on:drop=move |ev: ev::DragEvent| {
+                        ev.prevent_default();
+                        dragging.set(false);
+                        if let Some(file) = ev.data_transfer().and_then(|data| data.files()).and_then(|files| files.get(0)) {
+                            load_file(file);
+                        }
+                    }
+                >
+                    <Show when=highlighting>
+                        <pre
+                            node_ref=highlight_ref
+                            aria-hidden="true"
+                            class="absolute inset-0 m-0 overflow-hidden p-3 sm:p-4 pb-12 sm:pb-12 border border-transparent rounded-lg bg-gray-100 font-mono text-gray-800 sm:text-sm whitespace-pre pointer-events-none dark:bg-neutral-800 dark:text-neutral-400"
+                        >
+                            <HighlightedText text=typed/>
+                            // Keeps a trailing empty line as tall as in the textarea.
+                            "\n"
+                        </pre>
+                    </Show>
                     <textarea
-                        class="p-3 sm:p-4 pb-12 sm:pb-12 block w-full bg-gray-100 border-gray-200 rounded-lg sm:text-sm focus:border-blue-500 focus:ring-blue-500 dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-400 dark:placeholder-neutral-500 dark:focus:ring-neutral-600"
+                        class=move || {
+                            format!(
+                                "relative p-3 sm:p-4 pb-12 sm:pb-12 block w-full border-gray-200 rounded-lg font-mono sm:text-sm focus:border-blue-500 focus:ring-blue-500 dark:border-neutral-700 dark:placeholder-neutral-500 dark:focus:ring-neutral-600 {} {}",
+                                if highlighting() {
+                                    "bg-transparent text-transparent caret-gray-800 dark:caret-neutral-200"
+                                } else {
+                                    "bg-gray-100 dark:bg-neutral-800 dark:text-neutral-400"
+                                },
+                                if fill { "h-full resize-none" } else { "" },
+                            )
+                        }
+                        node_ref=editor_ref
+                        wrap="off"
+                        spellcheck="false"
                         autocapitalize="off"
                         rows="10"
-                        placeholder="Paste here an iCalendar, JSCalendar, vCard or JSContact file. Or click the sparkles to try a sample."
+                        placeholder=move || i18n.t("editor-placeholder")
                         prop:value=move || source.get()
+                        on:input=move |ev| {
+                            live_generation.update_value(|generation| *generation += 1);
+                            let text = event_target_value(&ev);
+                            typed.set(text.clone());
+                            if !editor_settings.live.get_untracked() || text.len() > LIVE_CONVERSION_LIMIT {
+                                return;
+                            }
+                            let generation = live_generation.get_value();
+                            // Dropped when the source changed some other way in the meantime.
+                            let base = source.get_untracked();
+                            set_timeout(
+                                move || {
+                                    if live_generation.get_value() != generation
+                                        || source.with_untracked(|source| *source != base)
+                                    {
+                                        return;
+                                    }
+                                    set_source(export::strip_context(&text).to_string());
+                                    convert();
+                                },
+                                Duration::from_millis(u64::from(editor_settings.delay_ms.get_untracked())),
+                            );
+                        }
                         on:change=move |ev| {
-                            source
-                                .update(|data| {
-                                    *data = event_target_value(&ev);
-                                });
-                            convert();
+                            live_generation.update_value(|generation| *generation += 1);
+                            let text = export::strip_context(&event_target_value(&ev)).to_string();
+                            // Already converted while it was typed.
+                            if text != source.get_untracked() {
+                                set_source(text);
+                                convert();
+                            }
+                        }
+                        on:keydown=move |ev| {
+                            // Native undo covers uncommitted typing, history covers the rest.
+                            if let Some(action) = shortcut_action(&ev).filter(Action::is_history) {
+                                if event_target_value(&ev) == source.get_untracked() {
+                                    run_shortcut(&ev, action);
+                                }
+                            }
+                        }
+                        on:scroll=move |ev| {
+                            let (Some(editor), Some(highlighted)) = (
+                                ev.target().and_then(|target| target.dyn_into::<web_sys::Element>().ok()),
+                                highlight_ref.get_untracked(),
+                            ) else {
+                                return;
+                            };
+                            highlighted.set_scroll_top(editor.scroll_top());
+                            highlighted.set_scroll_left(editor.scroll_left());
                         }
                     >
                     </textarea>
+                    <Show when=move || dragging.get()>
+                        <div class="absolute inset-0 flex items-center justify-center rounded-lg border-2 border-dashed border-blue-500 bg-blue-50/80 text-sm text-blue-700 pointer-events-none dark:bg-neutral-900/80 dark:text-blue-400">
+                            {move || i18n.t("editor-drop")}
+                        </div>
+                    </Show>
 
                     <div class="absolute bottom-px inset-x-px p-2 rounded-b-lg bg-gray-100 dark:bg-neutral-800">
                         <div class="flex flex-wrap justify-between items-center gap-2">
-                            <div class="flex items-center">
-                                <p class="text-xs text-gray-500 dark:text-neutral-500"></p>
+                            <div class="flex items-center gap-x-1">
+                                <button
+                                    type="button"
+                                    title=move || i18n.t("editor-undo")
+                                    class="inline-flex shrink-0 justify-center items-center size-8 rounded-lg text-gray-500 hover:bg-white disabled:opacity-40 disabled:pointer-events-none focus:z-10 focus:outline-hidden focus:bg-white dark:text-neutral-500 dark:hover:bg-neutral-700 dark:focus:bg-neutral-700"
+                                    disabled=move || !session.with(|session| session.active().history.can_undo())
+                                    on:click=move |_| undo()
+                                >
+                                    <svg
+                                        class="shrink-0 size-4"
+                                        xmlns="http://www.w3.org/2000/svg"
+                                        width="24"
+                                        height="24"
+                                        viewBox="0 0 24 24"
+                                        fill="none"
+                                        stroke="currentColor"
+                                        stroke-width="2"
+                                        stroke-linecap="round"
+                                        stroke-linejoin="round"
+                                    >
+                                        <path d="M9 14 4 9l5-5"></path>
+                                        <path d="M4 9h10.5a5.5 5.5 0 0 1 0 11H11"></path>
+                                    </svg>
+                                </button>
+                                <button
+                                    type="button"
+                                    title=move || i18n.t("editor-redo")
+                                    class="inline-flex shrink-0 justify-center items-center size-8 rounded-lg text-gray-500 hover:bg-white disabled:opacity-40 disabled:pointer-events-none focus:z-10 focus:outline-hidden focus:bg-white dark:text-neutral-500 dark:hover:bg-neutral-700 dark:focus:bg-neutral-700"
+                                    disabled=move || !session.with(|session| session.active().history.can_redo())
+                                    on:click=move |_| redo()
+                                >
+                                    <svg
+                                        class="shrink-0 size-4"
+                                        xmlns="http://www.w3.org/2000/svg"
+                                        width="24"
+                                        height="24"
+                                        viewBox="0 0 24 24"
+                                        fill="none"
+                                        stroke="currentColor"
+                                        stroke-width="2"
+                                        stroke-linecap="round"
+                                        stroke-linejoin="round"
+                                    >
+                                        <path d="m15 14 5-5-5-5"></path>
+                                        <path d="M20 9H9.5a5.5 5.5 0 0 0 0 11H13"></path>
+                                    </svg>
+                                </button>
+                                <label
+                                    title=move || i18n.t("editor-open")
+                                    class="inline-flex shrink-0 justify-center items-center size-8 rounded-lg text-gray-500 cursor-pointer hover:bg-white focus-within:z-10 focus-within:bg-white dark:text-neutral-500 dark:hover:bg-neutral-700 dark:focus-within:bg-neutral-700"
+                                >
+                                    <input
+                                        type="file"
+                                        class="sr-only"
+                                        accept=".ics,.ifb,.vcf,.vcard,.json,.eml,.csv,.html,.htm,text/calendar,text/vcard,text/csv,text/html,application/json,application/calendar+json,application/vcard+json,message/rfc822"
+                                        on:change=move |ev| {
+                                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                                            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                                                load_file(file);
+                                            }
+                                            // Picking the same file again should reload it.
+                                            input.set_value("");
+                                        }
+                                    />
+                                    <svg
+                                        class="shrink-0 size-4"
+                                        xmlns="http://www.w3.org/2000/svg"
+                                        width="24"
+                                        height="24"
+                                        viewBox="0 0 24 24"
+                                        fill="none"
+                                        stroke="currentColor"
+                                        stroke-width="2"
+                                        stroke-linecap="round"
+                                        stroke-linejoin="round"
+                                    >
+                                        <path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4"></path>
+                                        <path d="m17 8-5-5-5 5"></path>
+                                        <path d="M12 3v12"></path>
+                                    </svg>
+                                </label>
                             </div>
                             <div class="flex items-center gap-x-1">
                                 <button
                                     type="button"
                                     class="inline-flex shrink-0 justify-center items-center size-8 rounded-lg text-gray-500 hover:bg-white focus:z-10 focus:outline-hidden focus:bg-white dark:text-neutral-500 dark:hover:bg-neutral-700 dark:focus:bg-neutral-700"
-                                    on:click=move |_| {
-                                        source
-                                            .set(
-                                                SAMPLES
-                                                    .choose(&mut rand::thread_rng())
-                                                    .unwrap_or(&"")
-                                                    .to_string(),
-                                            );
-                                        convert();
-                                    }
+                                    title=move || i18n.t("editor-sample")
+                                    on:click=move |_| load_sample()
                                 >
 
                                     <svg
@@ -311,44 +2075,499 @@ pub fn App() -> impl IntoView {
                         </div>
                     </div>
                 </div>
+            </div>
+        }
+    };
+    let explanation = create_memo(move |_| {
+        if !explaining.get() || shown_form.get() != OutputForm::Text {
+            return vec![];
+        }
+        conversion.with(|conversion| {
+            shown_source.with(|source| explain::explain(source, source_type.get(), conversion))
+        })
+    });
+    let converted_output = move || {
+        view! {
+            <div id=CONVERSION_TARGET class="relative bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                <div class="absolute top-2 end-2 flex gap-1">
+                    <select
+                        class="py-1 px-2 rounded-lg text-xs text-gray-800 bg-white border border-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:border-neutral-600"
+                        aria-label=move || i18n.t("output-format")
+                        on:change=move |ev| output_form.set(OutputForm::parse(&event_target_value(&ev)))
+                    >
+                        {move || {
+                            let format = source_type.get().counterpart();
+                            OutputForm::offered(format)
+                                .iter()
+                                .map(|form| {
+                                    let form = *form;
+                                    view! {
+                                        <option value=form.as_str() selected=move || shown_form.get() == form>
+                                            {form.label(format, i18n)}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </select>
+                    <Show when=move || shown_form.get() == OutputForm::Text>
+                        <button
+                            type="button"
+                            class=move || {
+                                if explaining.get() {
+                                    "py-1 px-3 rounded-lg text-xs text-white bg-blue-600 hover:bg-blue-500"
+                                } else {
+                                    "py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+                                }
+                            }
+                            title=move || i18n.t("output-explain-title")
+                            aria-pressed=move || explaining.get().to_string()
+                            on:click=move |_| explaining.update(|explaining| *explaining = !*explaining)
+                        >
+                            {move || i18n.t("output-explain")}
+                        </button>
+                    </Show>
+                    <CopyButton
+                        payload=shown_conversion
+                        context=Signal::derive(move || export_context(output_format.get()))
+                        json=Signal::derive(move || output_format.get().is_json())
+                    />
+                    <Show
+                        when=move || shown_form.get() == OutputForm::Xml
+                        fallback=move || view! { <DownloadButton payload=shown_conversion format=output_format/> }
+                    >
+                        <button
+                            type="button"
+                            class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+                            on:click=move |_| download_xml()
+                        >
+                            {move || i18n.t("output-download")}
+                        </button>
+                    </Show>
+                </div>
+                <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                    {move || {
+                        if explaining.get() && shown_form.get() == OutputForm::Text {
+                            explanation
+                                .get()
+                                .into_iter()
+                                .map(|line| {
+                                    view! {
+                                        <span class="block" title=line.note.clone()>
+                                            {line.text}
+                                            {line
+                                                .note
+                                                .map(|note| {
+                                                    view! {
+                                                        <span class="ms-6 text-xs text-blue-700 dark:text-blue-400 select-none">
+                                                            {note}
+                                                        </span>
+                                                    }
+                                                })}
+                                        </span>
+                                    }
+                                })
+                                .collect_view()
+                        } else {
+                            view! { <HighlightedText text=shown_conversion/> }.into_view()
+                        }
+                    }}
+                </pre>
+            </div>
+        }
+    };
+    let roundtrip_output = move || {
+        view! {
+            <div id=ROUNDTRIP_TARGET class="relative bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                <div class="absolute top-2 end-2 flex gap-1">
+                    <CopyButton
+                        payload=roundtrip_conversion
+                        context=Signal::derive(move || export_context(source_type.get()))
+                        json=Signal::derive(move || source_type.get().is_json())
+                    />
+                    <DownloadButton payload=roundtrip_conversion format=source_type/>
+                </div>
+                <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                    <HighlightedText text=roundtrip_conversion/>
+                </pre>
+            </div>
+        }
+    };
+    let tools = move || {
+        view! {
+            <RemoteSourcePanel on_load=move |body: String| {
+                set_source(body);
+                convert();
+            }/>
+
+            <RepairPanel source=source on_repair=move |repaired: String| {
+                set_source(repaired);
+                convert();
+            }/>
+
+            <AnonymizePanel source=source on_anonymize=move |anonymized: String| {
+                set_source(anonymized);
+                convert();
+            }/>
+
+            <NormalizePanel source=source on_normalize=move |normalized: String| {
+                set_source(normalized);
+                convert();
+            }/>
+
+            <MergePanel on_merge=move |(name, merged): (String, String)| {
+                load_active_document(&|session| {
+                    session.open();
+                    session.rename(session.active_index(), &name);
+                    session.active_mut().source = merged.clone();
+                });
+            }/>
+
+            <ParserRecoveryPanel
+                strict=strict_parsing
+                recoveries=recoveries
+                on_change=move |strict: bool| {
+                    strict_parsing.set(strict);
+                    convert();
+                }
+            />
+
+            <ShiftGenerator on_generate=move |icalendar: String| {
+                set_source(icalendar);
+                convert();
+            }/>
+
+            <SampleGenerator on_generate=move |sample: String| {
+                set_source(sample);
+                convert();
+            }/>
+
+            <PatchDiffPanel current=move || {
+                let jscalendar = jscalendar.get();
+                if jscalendar.is_empty() { jscontact.get() } else { jscalendar }
+            }/>
+
+            <SemanticComparePanel current=source/>
+
+            <ConversionReportPanel report=move |_| conversion_report()/>
+
+            <PermalinkPanel fragment=move |_| permalink::encode(&session_bundle())/>
+
+            <PropertyUsagePanel analyze=move |_| {
+                let mut usage = UsageReport::default();
+                session.with_untracked(|session| {
+                    for document in session.documents() {
+                        if document.source.trim().is_empty() {
+                            continue;
+                        }
+                        for entry in jmap_convert::Converter.convert_all(&document.source) {
+                            usage.add(&entry);
+                        }
+                    }
+                });
+                usage
+            }/>
+
+            <SessionBundlePanel export=move |_| session_bundle() on_import=restore_bundle/>
+
+            <FetchSettingsPanel/>
+
+            <KeyboardShortcutsPanel/>
+        }
+    };
+    let header_controls = move || {
+        view! {
+            <div class="shrink-0 flex items-center gap-2">
+                <ThemeSelect/>
+                <LanguageSelect/>
+                <InstallButton/>
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                    title=move || i18n.t("header-history-title")
+                    on:click=move |_| history_open.update(|open| *open = !*open)
+                >
+                    {move || i18n.t("header-history")}
+                </button>
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                    title=move || i18n.t("header-tour-title")
+                    on:click=move |_| start_tour()
+                >
+                    {move || i18n.t("header-tour")}
+                </button>
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                    on:click=move |_| layout.set_enabled.set(!layout.enabled.get_untracked())
+                >
+                    {move || {
+                        i18n.t(if layout.enabled.get() { "header-stacked-view" } else { "header-split-view" })
+                    }}
+                </button>
+            </div>
+        }
+    };
+
+    view! {
+        <Show when=move || !layout.enabled.get()>
+            <div class="max-w-4xl px-4 py-10 sm:px-6 lg:px-8 mx-auto">
+                <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                    <div class="mb-8 flex items-start justify-between gap-4">
+                        <div>
+                            <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                                {move || i18n.t("header-title")}
+                            </h2>
+                            <p class="text-sm text-gray-600 dark:text-neutral-400">
+                                {move || i18n.t("header-subtitle")}
+                            </p>
+                        </div>
+                        {header_controls()}
+                    </div>
+
+                    {error_banner()}
+
+                    {conversion_status()}
+
+                    {entry_list()}
+
+                    {editor(false)}
+
+                    {tools()}
+
+                </div>
+            </div>
 
+            <FindingsPanel findings=findings/>
+        </Show>
+
+        <Show when=move || layout.enabled.get()>
+            <div class="px-4 pt-10 pb-4 sm:px-6 lg:px-8 flex items-center justify-between gap-4">
+                <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                    {move || i18n.t("header-title")}
+                </h2>
+                {header_controls()}
             </div>
-        </div>
+            <Workspace
+                layout=layout
+                source=move || {
+                    view! {
+                        <div class="flex flex-col h-full">
+                            {error_banner()}
+                            {conversion_status()}
+                            {entry_list()}
+                            <div class="grow min-h-0">{editor(true)}</div>
+                        </div>
+                    }
+                }
+                output=converted_output
+                roundtrip=roundtrip_output
+                warnings=move || view! { <FindingsPanel findings=findings embedded=true/> }
+            />
+            <div class="max-w-4xl px-4 pb-10 sm:px-6 lg:px-8 mx-auto">
+                <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                    {tools()}
+                </div>
+            </div>
+        </Show>
+
+        <Show when=move || !source.with(|source| source.trim().is_empty())>
+            <ResultsTabs validating=validating/>
+        </Show>
+
+        <Show when=move || validating.get() && !source.with(|source| source.trim().is_empty())>
+            <ValidationPanel source=source/>
+        </Show>
 
-        <Show when=move || !conversion.get().is_empty()>
+        <Show when=move || !validating.get() && !conversion.get().is_empty()>
             <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-10">
                 <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
                     <div class="mb-4">
                         <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
-                            Conversion results
+                            {move || i18n.t("results-title")}
                         </h2>
 
                     </div>
-                    <p class="text-sm text-gray-600 dark:text-neutral-400 mb-4">
-                        {format!(
-                            "This is how your {} looks like in {} format:",
-                            source_type.get().as_str(),
-                            source_type.get().counterpart().as_str(),
-                        )}
+                    <ContactPreviewCard jscontact=jscontact/>
+                    <QrCodePanel vcard=vcard/>
+                    <InvitationPreview jscalendar=jscalendar/>
+                    <EventSummaryCards jscalendar=jscalendar icalendar=icalendar/>
+                    <SchedulingPanel
+                        icalendar=icalendar
+                        on_open=move |reply: String| {
+                            set_source(reply);
+                            convert();
+                        }
+                    />
+                    <VendorStripPanel
+                        enabled=strip_vendor
+                        keep=vendor_keep
+                        removed=Signal::derive(move || {
+                            findings
+                                .with(|findings| {
+                                    findings
+                                        .iter()
+                                        .filter(|finding| finding.code == Code::VendorExtensionStripped)
+                                        .count()
+                                })
+                        })
+                        on_change=move |(enabled, keep): (bool, String)| {
+                            strip_vendor.set(enabled);
+                            vendor_keep.set(keep);
+                            convert();
+                        }
+                    />
+                    <Show when=move || !source_type.get().is_calendar()>
+                        <ContactCleanupPanel
+                            enabled=normalize_contacts
+                            country=default_country
+                            counts=Signal::derive(move || {
+                                findings
+                                    .with(|findings| {
+                                        let count = |codes: [Code; 2]| {
+                                            findings
+                                                .iter()
+                                                .filter(|finding| codes.contains(&finding.code))
+                                                .count()
+                                        };
+                                        (
+                                            count([Code::PhoneNumberNormalized, Code::EmailAddressNormalized]),
+                                            count([Code::InvalidPhoneNumber, Code::InvalidEmailAddress]),
+                                        )
+                                    })
+                            })
+                            on_change=move |(enabled, country): (bool, String)| {
+                                normalize_contacts.set(enabled);
+                                default_country.set(country);
+                                convert();
+                            }
+                        />
+                    </Show>
+                    <Show when=move || !source_type.get().is_json()>
+                        <PropertyTablePanel
+                            source=source
+                            on_change=move |updated: String| {
+                                set_source(updated);
+                                convert();
+                            }
+                        />
+                    </Show>
+                    <Show when=move || {
+                        source_type.get().is_calendar()
+                    }>
+                        <ComponentFilterPanel
+                            excluded=excluded_components
+                            removed=removed_components
+                            on_change=move |excluded: Vec<ComponentKind>| {
+                                excluded_components.set(excluded);
+                                convert();
+                            }
+                        />
+                        <TimeProbePanel icalendar=icalendar/>
+                        <TimeZoneInspectorPanel icalendar=icalendar jscalendar=jscalendar/>
+                        <FreeBusyPanel icalendar=icalendar/>
+                        <StatusPanel
+                            field=status::Field::Status
+                            source=source
+                            icalendar=icalendar
+                            json=Signal::derive(move || source_type.get().is_json())
+                            on_change=move |updated: String| {
+                                set_source(updated);
+                                convert();
+                            }
+                        />
+                        <StatusPanel
+                            field=status::Field::Transparency
+                            source=source
+                            icalendar=icalendar
+                            json=Signal::derive(move || source_type.get().is_json())
+                            on_change=move |updated: String| {
+                                set_source(updated);
+                                convert();
+                            }
+                        />
+                        <StatusPanel
+                            field=status::Field::Classification
+                            source=source
+                            icalendar=icalendar
+                            json=Signal::derive(move || source_type.get().is_json())
+                            on_change=move |updated: String| {
+                                set_source(updated);
+                                convert();
+                            }
+                        />
+                        <ColorPalettePanel source=source converted=conversion/>
+                        <DescriptionDiffPanel
+                            source=source
+                            converted=conversion
+                            roundtrip=roundtrip_conversion
+                        />
+                    </Show>
+                    <Show when=move || {
+                        !source_type.get().is_calendar()
+                    }>
+                        <PhoneticPanel source=source converted=conversion/>
+                        <IdentityMappingPanel
+                            source=source
+                            converted=conversion
+                            roundtrip=roundtrip_conversion
+                        />
+                        <ContactLinksPanel vcard=vcard jscontact=jscontact/>
+                        <VCardVersionPanel vcard=vcard/>
+                    </Show>
+                    <Show when=move || !layout.enabled.get()>
+                        <p class="text-sm text-gray-600 dark:text-neutral-400 mb-4">
+                            {move || {
+                                i18n.t_with(
+                                    "results-converted",
+                                    FluentArgs::from_iter([
+                                        ("source", source_type.get().as_str()),
+                                        ("target", source_type.get().counterpart().as_str()),
+                                    ]),
+                                )
+                            }}
 
-                    </p>
-                    <div class="bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
-                        <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
-                            {move || conversion.get()}
-                        </pre>
-                    </div>
-                    <p class="text-sm text-gray-600 dark:text-neutral-400 mt-4 mb-4">
-                        {format!(
-                            "And this is how it would look like converted back to {}:",
-                            source_type.get().as_str(),
-                        )}
+                        </p>
+                        {converted_output()}
+                        <p class="text-sm text-gray-600 dark:text-neutral-400 mt-4 mb-4">
+                            {move || {
+                                i18n.t_with(
+                                    "results-roundtrip",
+                                    FluentArgs::from_iter([("source", source_type.get().as_str())]),
+                                )
+                            }}
 
-                    </p>
-                    <div class="bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
-                        <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
-                            {move || roundtrip_conversion.get()}
-                        </pre>
-                    </div>
+                        </p>
+                        {roundtrip_output()}
+                    </Show>
+                    <Show when=move || !roundtrip_conversion.with(String::is_empty)>
+                        <FidelityReportPanel losses=fidelity format=source_type/>
+                        <IgnoredPropertiesPanel diagnostics=ignored_properties/>
+                        <RoundtripDiffPanel source=source roundtrip=roundtrip_conversion/>
+                        // What clients import: the iCalendar or vCard side
+                        // of the conversion.
+                        <CompatibilityPanel text=Signal::derive(move || match source_type.get() {
+                            Format::JSCalendar | Format::JSContact => conversion.get(),
+                            Format::JCal | Format::JCard => {
+                                document.with(|document| jcal::to_text(document).unwrap_or_default())
+                            }
+                            Format::ICalendar | Format::VCard => document.get(),
+                        })/>
+                    </Show>
+                    <ExpectedOutputPanel
+                        converted=conversion
+                        format=Signal::derive(move || source_type.get().counterpart())
+                    />
+                    <VersionComparePanel source=source/>
+                    <Show when=move || editor_settings.show_metrics.get()>
+                        <MetricsFooter
+                            source=document
+                            converted=conversion
+                            roundtrip=roundtrip_conversion
+                            expansion_ms=expansion_elapsed
+                        />
+                    </Show>
                     <div class="flex justify-end gap-4 mt-3">
                         <p class="text-xs text-gray-600">
                             {format!("v{}", env!("CARGO_PKG_VERSION"))}
@@ -358,7 +2577,7 @@ pub fn App() -> impl IntoView {
                             class="text-xs text-blue-600 hover:text-blue-700 dark:text-blue-400 dark:hover:text-blue-300 hover:underline"
                             target="_blank"
                         >
-                            Report a bug
+                            {move || i18n.t("footer-report-bug")}
                         </a>
                         <a
                             href="#"
@@ -366,7 +2585,7 @@ pub fn App() -> impl IntoView {
                             href="https://github.com/stalwartlabs/jmap-convert/tree/main"
                             target="_blank"
                         >
-                            View source
+                            {move || i18n.t("footer-view-source")}
                         </a>
                     </div>
 
@@ -374,22 +2593,263 @@ pub fn App() -> impl IntoView {
             </div>
         </Show>
 
-        <Show when=move || !occurrences.get().is_empty()>
+        <OrgChartPanel source=source/>
+
+        <DedupPanel source=source on_dedupe=move |deduplicated: String| {
+            set_source(deduplicated);
+            convert();
+        }/>
+
+        <Show when=move || {
+            !occurrences.get().is_empty()
+                || (expansion.get().anchor != ExpansionAnchor::Start
+                    && !conversion.get().is_empty()
+                    && source_type.get().is_calendar())
+        }>
             <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto">
-                <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
-                    <div class="mb-4">
+                <div id=EXPANSION_TARGET class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                    <div class="mb-4 flex flex-wrap items-center justify-between gap-2">
                         <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
-                            Calendar expansion results
+                            {move || i18n.t("expansion-title")}
                         </h2>
-
+                        <div class="flex items-center gap-2">
+                            <TimezoneSelect
+                                selected=Signal::derive(move || expansion.get().timezone)
+                                on_change=move |timezone: Tz| {
+                                    expansion.update(|params| params.timezone = timezone);
+                                    reexpand();
+                                }
+                            />
+                            <select
+                                class=EXPANSION_INPUT_CLASS
+                                title=move || i18n.t("date-zone-title")
+                                on:change=move |ev| event_zone.set(event_target_value(&ev) == "event")
+                            >
+                                <option value="display" selected=move || !event_zone.get()>
+                                    {move || i18n.t("date-zone-display")}
+                                </option>
+                                <option value="event" selected=move || event_zone.get()>
+                                    {move || i18n.t("date-zone-event")}
+                                </option>
+                            </select>
+                            <select
+                                class=EXPANSION_INPUT_CLASS
+                                title=move || i18n.t("date-display-title")
+                                on:change=move |ev| {
+                                    if let Ok(display) = event_target_value(&ev).parse() {
+                                        date_display.set(display);
+                                    }
+                                }
+                            >
+                                {DateDisplay::ALL
+                                    .iter()
+                                    .map(|display| {
+                                        let display = *display;
+                                        view! {
+                                            <option value=display.as_str() selected=move || date_display.get() == display>
+                                                {move || i18n.t(display.message_id())}
+                                            </option>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </select>
+                            <select
+                                class=EXPANSION_INPUT_CLASS
+                                on:change=move |ev| {
+                                    let anchor = ExpansionAnchor::parse(&event_target_value(&ev));
+                                    expansion.update(|params| params.anchor = anchor);
+                                    reexpand();
+                                }
+                            >
+                                <option value="start" selected=move || expansion.get().anchor == ExpansionAnchor::Start>
+                                    {move || i18n.t("expansion-from-start")}
+                                </option>
+                                <option value="now" selected=move || expansion.get().anchor == ExpansionAnchor::Now>
+                                    {move || i18n.t("expansion-from-now")}
+                                </option>
+                                <option value="range" selected=move || expansion.get().anchor == ExpansionAnchor::Range>
+                                    {move || i18n.t("expansion-between")}
+                                </option>
+                            </select>
+                            <input
+                                type="number"
+                                class=format!("w-20 {}", EXPANSION_INPUT_CLASS)
+                                title=move || i18n.t("expansion-count-title")
+                                min="1"
+                                max=MAX_EXPANSION_COUNT
+                                prop:value=move || expansion.get().count.to_string()
+                                on:change=move |ev| {
+                                    if let Ok(count) = event_target_value(&ev).trim().parse::<usize>() {
+                                        expansion
+                                            .update(|params| {
+                                                params.count = count.clamp(1, MAX_EXPANSION_COUNT);
+                                            });
+                                        reexpand();
+                                    }
+                                }
+                            />
+                            <input
+                                type="number"
+                                class=format!("w-20 {}", EXPANSION_INPUT_CLASS)
+                                title=move || i18n.t("expansion-budget-title")
+                                min="10"
+                                step="10"
+                                prop:value=move || expansion.get().budget_ms.to_string()
+                                on:change=move |ev| {
+                                    if let Ok(budget_ms) = event_target_value(&ev).trim().parse::<u32>() {
+                                        expansion.update(|params| params.budget_ms = budget_ms.max(10));
+                                        reexpand();
+                                    }
+                                }
+                            />
+                            <CopyButton
+                                payload=occurrences_export
+                                context=Signal::derive(move || ExportContext {
+                                    format: "JSCalendar occurrences".to_string(),
+                                    ..export_context(Format::JSCalendar)
+                                })
+                                json=true
+                            />
+                            <DownloadButton payload=occurrences_export format=Format::JSCalendar/>
+                            <DownloadButton
+                                payload=occurrences_ics
+                                format=Format::ICalendar
+                                label="expansion-download-ics"
+                            />
+                            <button
+                                type="button"
+                                class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+                                on:click=move |_| {
+                                    if let Err(err) = save_file(
+                                        &occurrences_csv.get_untracked(),
+                                        "text/csv",
+                                        "occurrences.csv",
+                                    ) {
+                                        log::warn!("Failed to download the occurrences: {:?}", err);
+                                    }
+                                }
+                            >
+                                {move || i18n.t("expansion-download-csv")}
+                            </button>
+                            <CopyButton
+                                payload=Signal::derive(move || {
+                                    let mode = overlay_mode.get();
+                                    occurrences.with(|occurrences| {
+                                        serde_json::to_string_pretty(
+                                            &occurrences
+                                                .iter()
+                                                .filter(|occurrence| mode.includes(occurrence.is_flagged()))
+                                                .map(Occurrence::to_json)
+                                                .collect::<Vec<_>>(),
+                                        )
+                                        .unwrap_or_default()
+                                    })
+                                })
+                                context=Signal::derive(move || ExportContext {
+                                    format: "JSON occurrence table".to_string(),
+                                    ..export_context(Format::JSCalendar)
+                                })
+                                json=true
+                                label="expansion-copy-json"
+                            />
+                        </div>
                     </div>
+                    <Show when=move || expansion.get().anchor == ExpansionAnchor::Range>
+                        <div class="mb-4 flex flex-wrap items-center gap-2 text-sm text-gray-600 dark:text-neutral-400">
+                            <label class="inline-flex items-center gap-2">
+                                {move || i18n.t("expansion-range-from")}
+                                <input
+                                    type="date"
+                                    class=EXPANSION_INPUT_CLASS
+                                    prop:value=move || date_value(expansion.get().from)
+                                    on:change=move |ev| {
+                                        let from = parse_date_value(&event_target_value(&ev));
+                                        expansion.update(|params| params.from = from);
+                                        reexpand();
+                                    }
+                                />
+                            </label>
+                            <label class="inline-flex items-center gap-2">
+                                {move || i18n.t("expansion-range-to")}
+                                <input
+                                    type="date"
+                                    class=EXPANSION_INPUT_CLASS
+                                    prop:value=move || date_value(expansion.get().until)
+                                    on:change=move |ev| {
+                                        let until = parse_date_value(&event_target_value(&ev));
+                                        expansion.update(|params| params.until = until);
+                                        reexpand();
+                                    }
+                                />
+                            </label>
+                        </div>
+                    </Show>
+                    <Show when=move || recurrence_explanations.with(|explanations| !explanations.is_empty())>
+                        <ul class="mb-4 space-y-1 text-sm text-gray-800 dark:text-neutral-200">
+                            {move || {
+                                recurrence_explanations
+                                    .get()
+                                    .into_iter()
+                                    .map(|explanation| {
+                                        view! {
+                                            <li>
+                                                <span class="font-medium">{explanation.title}</span>
+                                                ": "
+                                                {explanation.text}
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </ul>
+                    </Show>
                     <p class="text-sm text-gray-600 dark:text-neutral-400 mb-4">
-                        {format!(
-                            "These are the first {} occurrences of the pasted calendar event:",
-                            occurrences.get().len(),
-                        )}
+                        {move || {
+                            let id = match expansion.get().anchor {
+                                ExpansionAnchor::Start => "expansion-summary-start",
+                                ExpansionAnchor::Now => "expansion-summary-now",
+                                ExpansionAnchor::Range => "expansion-summary-range",
+                            };
+                            i18n.t_with(id, FluentArgs::from_iter([("count", occurrence_count())]))
+                        }}
 
                     </p>
+                    <p class="-mt-3 mb-4 text-xs text-gray-500 dark:text-neutral-500">
+                        {move || {
+                            expansion_elapsed
+                                .get()
+                                .map(|elapsed| {
+                                    let mut args = FluentArgs::new();
+                                    args.set("elapsed", format!("{:.0}", elapsed));
+                                    if expansion_out_of_budget.get() {
+                                        args.set("budget", expansion.get().budget_ms);
+                                        i18n.t_with("expansion-elapsed-budget", args)
+                                    } else {
+                                        i18n.t_with("expansion-elapsed", args)
+                                    }
+                                })
+                        }}
+                    </p>
+
+                    <WorkingHoursPanel
+                        working_hours=working_hours
+                        holidays=holidays
+                        overlay_mode=overlay_mode
+                        on_change=move |_| reexpand()
+                    />
+
+                    <DurationStatsPanel stats=duration_stats/>
+
+                    <HeatmapPanel heatmap=heatmap/>
+
+                    <AlarmTimelinePanel firings=alarm_firings/>
+
+                    <Pager
+                        page=occurrence_page
+                        total=Signal::derive(move || shown_occurrences.with(Vec::len))
+                        page_size=OCCURRENCE_PAGE_SIZE
+                        noun="pager-occurrences"
+                    />
 
                     <div class="flex flex-col">
                         <div class="-m-1.5 overflow-x-auto">
@@ -402,28 +2862,113 @@ pub fn App() -> impl IntoView {
                                                     scope="col"
                                                     class="px-6 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500"
                                                 >
-                                                    From date
+                                                    {move || i18n.t("occurrence-type")}
+                                                </th>
+                                                <th
+                                                    scope="col"
+                                                    class="px-6 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500"
+                                                >
+                                                    {move || i18n.t("occurrence-from")}
                                                 </th>
                                                 <th
                                                     scope="col"
                                                     class="px-6 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500"
                                                 >
-                                                    To date
+                                                    {move || i18n.t("occurrence-to")}
+                                                </th>
+                                                <th
+                                                    scope="col"
+                                                    class="px-6 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500"
+                                                >
+                                                    {move || i18n.t("occurrence-notes")}
                                                 </th>
                                             </tr>
                                         </thead>
                                         <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
                                             <For
-                                                each=move || occurrences.get()
-                                                key=move |occurrence| occurrence.from.clone()
+                                                each=move || {
+                                                    let shown = shown_occurrences.get();
+                                                    let last = shown.len().saturating_sub(1) / OCCURRENCE_PAGE_SIZE;
+                                                    shown
+                                                        .into_iter()
+                                                        .skip(occurrence_page.get().min(last) * OCCURRENCE_PAGE_SIZE)
+                                                        .take(OCCURRENCE_PAGE_SIZE)
+                                                        .collect::<Vec<_>>()
+                                                }
+                                                key=move |occurrence| {
+                                                    (
+                                                        occurrence.kind,
+                                                        occurrence.from.clone(),
+                                                        occurrence.to.clone(),
+                                                        occurrence.origin.clone(),
+                                                    )
+                                                }
                                                 children=move |occurrence| {
+                                                    let removed = occurrence.origin == Origin::Removed;
+                                                    let due = occurrence.kind == EntryKind::Task && occurrence.to.is_some();
+                                                    let (from, to) = (occurrence.from, occurrence.to);
                                                     view! {
-                                                        <tr>
+                                                        <tr class:line-through=removed class:opacity-60=removed>
+                                                            <td class="px-6 py-4 whitespace-nowrap text-sm text-gray-800 dark:text-neutral-200">
+                                                                {move || i18n.t(occurrence.kind.message_id())}
+                                                                {occurrence.title.map(|title| view! {
+                                                                    <span class="block text-xs text-gray-500 dark:text-neutral-500">
+                                                                        {title}
+                                                                    </span>
+                                                                })}
+                                                            </td>
                                                             <td class="px-6 py-4 whitespace-nowrap text-sm font-medium text-gray-800 dark:text-neutral-200">
-                                                                {occurrence.from}
+                                                                {move || from.as_ref().map(show_time)}
+                                                            </td>
+                                                            <td class="px-6 py-4 whitespace-nowrap text-sm text-gray-800 dark:text-neutral-200">
+                                                                {due.then(|| format!("{} ", i18n.t("occurrence-due")))}
+                                                                {move || to.as_ref().map(show_time)}
                                                             </td>
                                                             <td class="px-6 py-4 whitespace-nowrap text-sm text-gray-800 dark:text-neutral-200">
-                                                                {occurrence.to}
+                                                                {match occurrence.origin {
+                                                                    Origin::Regular => None,
+                                                                    Origin::Added => Some(view! {
+                                                                        <span class="inline-flex items-center gap-x-1.5 py-0.5 px-2 me-1 rounded-full text-xs font-medium bg-blue-100 text-blue-800 dark:bg-blue-800/30 dark:text-blue-500">
+                                                                            {move || i18n.t("occurrence-added")}
+                                                                        </span>
+                                                                    }),
+                                                                    Origin::Modified(summary) => Some(view! {
+                                                                        <span
+                                                                            class="inline-flex items-center gap-x-1.5 py-0.5 px-2 me-1 rounded-full text-xs font-medium bg-purple-100 text-purple-800 dark:bg-purple-800/30 dark:text-purple-500"
+                                                                            title=move || i18n.t("occurrence-modified-title")
+                                                                        >
+                                                                            {move || match &summary {
+                                                                                Some(summary) => {
+                                                                                    i18n.t_with(
+                                                                                        "occurrence-modified-summary",
+                                                                                        FluentArgs::from_iter([("summary", summary.clone())]),
+                                                                                    )
+                                                                                }
+                                                                                None => i18n.t("occurrence-modified"),
+                                                                            }}
+                                                                        </span>
+                                                                    }),
+                                                                    Origin::Removed => Some(view! {
+                                                                        <span class="inline-flex items-center gap-x-1.5 py-0.5 px-2 me-1 rounded-full text-xs font-medium bg-gray-100 text-gray-800 dark:bg-neutral-700 dark:text-neutral-200">
+                                                                            {move || i18n.t("occurrence-removed")}
+                                                                        </span>
+                                                                    }),
+                                                                }}
+                                                                {occurrence.progress.map(|progress| view! {
+                                                                    <span class="inline-flex items-center gap-x-1.5 py-0.5 px-2 me-1 rounded-full text-xs font-medium bg-teal-100 text-teal-800 dark:bg-teal-800/30 dark:text-teal-500">
+                                                                        {progress}
+                                                                    </span>
+                                                                })}
+                                                                {occurrence.holiday.map(|name| view! {
+                                                                    <span class="inline-flex items-center gap-x-1.5 py-0.5 px-2 me-1 rounded-full text-xs font-medium bg-red-100 text-red-800 dark:bg-red-800/30 dark:text-red-500">
+                                                                        {name}
+                                                                    </span>
+                                                                })}
+                                                                {occurrence.outside_hours.then(|| view! {
+                                                                    <span class="inline-flex items-center gap-x-1.5 py-0.5 px-2 rounded-full text-xs font-medium bg-yellow-100 text-yellow-800 dark:bg-yellow-800/30 dark:text-yellow-500">
+                                                                        {move || i18n.t("occurrence-outside-hours")}
+                                                                    </span>
+                                                                })}
                                                             </td>
                                                         </tr>
                                                     }
@@ -440,39 +2985,66 @@ pub fn App() -> impl IntoView {
                 </div>
             </div>
         </Show>
+
+        <ConversionTour step=tour/>
+
+        <RecentInputsSidebar
+            recent=recent_inputs
+            open=history_open
+            on_restore=move |index: usize| restore_recent(index)
+            on_remove=move |index: usize| set_recent_inputs.update(|recent| recent.remove(index))
+            on_clear=move |_| set_recent_inputs.update(RecentInputs::clear)
+        />
     }
 }
 
-const SAMPLES: &[&str] = &[
-    include_str!("../resources/ical_001.ics"),
-    include_str!("../resources/ical_002.ics"),
-    include_str!("../resources/ical_003.ics"),
-    include_str!("../resources/vcard_001.vcf"),
-    include_str!("../resources/vcard_002.vcf"),
-    include_str!("../resources/vcard_003.vcf"),
-    include_str!("../resources/jscal_001.json"),
-    include_str!("../resources/jscal_002.json"),
-    include_str!("../resources/jscal_003.json"),
-    include_str!("../resources/jscontact_001.json"),
-    include_str!("../resources/jscontact_002.json"),
+// Exchange meeting request repeating every other day, covering every step of the tour.
+const TOUR_SAMPLE: &str = include_str!("../resources/ical_001.ics");
+const SAMPLES: &[(&str, &str)] = &[
+    ("ical_001", include_str!("../resources/ical_001.ics")),
+    ("ical_002", include_str!("../resources/ical_002.ics")),
+    ("ical_003", include_str!("../resources/ical_003.ics")),
+    ("vcard_001", include_str!("../resources/vcard_001.vcf")),
+    ("vcard_002", include_str!("../resources/vcard_002.vcf")),
+    ("vcard_003", include_str!("../resources/vcard_003.vcf")),
+    ("jscal_001", include_str!("../resources/jscal_001.json")),
+    ("jscal_002", include_str!("../resources/jscal_002.json")),
+    ("jscal_003", include_str!("../resources/jscal_003.json")),
+    (
+        "jscontact_001",
+        include_str!("../resources/jscontact_001.json"),
+    ),
+    (
+        "jscontact_002",
+        include_str!("../resources/jscontact_002.json"),
+    ),
 ];
 
-impl SourceType {
-    fn as_str(&self) -> &str {
-        match self {
-            SourceType::ICalendar => "iCalendar",
-            SourceType::JSCalendar => "JSCalendar",
-            SourceType::VCard => "vCard",
-            SourceType::JSContact => "JSContact",
-        }
+impl Occurrence {
+    fn is_flagged(&self) -> bool {
+        self.holiday.is_some() || self.outside_hours
     }
 
-    fn counterpart(&self) -> SourceType {
-        match self {
-            SourceType::ICalendar => SourceType::JSCalendar,
-            SourceType::JSCalendar => SourceType::ICalendar,
-            SourceType::VCard => SourceType::JSContact,
-            SourceType::JSContact => SourceType::VCard,
-        }
+    // Row of the occurrence table as copied.
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": self.kind.label().to_ascii_lowercase(),
+            "title": self.title,
+            "from": self.from.as_ref().map(ToString::to_string),
+            "to": self.to.as_ref().map(ToString::to_string),
+            "progress": self.progress,
+            "holiday": self.holiday,
+            "outsideWorkingHours": self.outside_hours,
+            "origin": match self.origin {
+                Origin::Regular => "rule",
+                Origin::Added => "rdate",
+                Origin::Modified(_) => "override",
+                Origin::Removed => "exdate",
+            },
+            "overrideSummary": match &self.origin {
+                Origin::Modified(summary) => summary.clone(),
+                _ => None,
+            },
+        })
     }
 }
@@ -0,0 +1,248 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Combines several iCalendar or vCard files into one. Calendars share a
+// single VCALENDAR, or a JSCalendar Group, with each VTIMEZONE written once;
+// contacts are listed one after the other. Entries whose UID is already
+// taken are reported, and dropped when they are identical.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    contentline::{self, fold_line},
+    findings::{Code, Finding},
+    Converter, Format,
+};
+
+const PRODID: &str = "-//Stalwart Labs LLC//jmap-convert merge//EN";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeOutput {
+    #[default]
+    Calendar,
+    Group,
+}
+
+impl MergeOutput {
+    pub const ALL: [MergeOutput; 2] = [MergeOutput::Calendar, MergeOutput::Group];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeOutput::Calendar => "calendar",
+            MergeOutput::Group => "group",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            MergeOutput::Calendar => "merge-output-calendar",
+            MergeOutput::Group => "merge-output-group",
+        }
+    }
+}
+
+impl fmt::Display for MergeOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MergeOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MergeOutput::ALL
+            .into_iter()
+            .find(|output| output.as_str() == s)
+            .ok_or_else(|| format!("Unknown merge output: {}", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Merged {
+    pub format: Format,
+    pub text: String,
+    pub entries: usize,
+    pub timezones: usize,
+    pub findings: Vec<Finding>,
+}
+
+// A component as written, unfolded, from BEGIN to END.
+struct Block {
+    kind: String,
+    lines: Vec<String>,
+    uid: Option<String>,
+    recurrence_id: Option<String>,
+    tzid: Option<String>,
+}
+
+// `files` are named so findings can tell where each entry came from.
+pub fn merge(files: &[(String, String)], output: MergeOutput) -> Result<Merged, String> {
+    let mut format = None;
+    let mut blocks = Vec::new();
+    for (name, source) in files {
+        let file_format = match Converter.detect_format(source) {
+            Ok(format @ (Format::ICalendar | Format::VCard)) => format,
+            _ => return Err(format!("{} is not an iCalendar or vCard file.", name)),
+        };
+        if format.is_some_and(|format| format != file_format) {
+            return Err("Calendars and contacts cannot be merged together.".to_string());
+        }
+        format = Some(file_format);
+        let depth = if file_format == Format::ICalendar {
+            1
+        } else {
+            0
+        };
+        blocks.extend(
+            blocks_at(source, depth)
+                .into_iter()
+                .map(|block| (name.as_str(), block)),
+        );
+    }
+    let format = format.ok_or_else(|| "There are no files to merge.".to_string())?;
+
+    let mut findings = Vec::new();
+    let mut timezones: Vec<(&str, Block)> = Vec::new();
+    let mut entries: Vec<(&str, Block)> = Vec::new();
+    for (file, block) in blocks {
+        if block.kind == "VTIMEZONE" {
+            match timezones
+                .iter()
+                .find(|(_, other)| other.tzid.is_some() && other.tzid == block.tzid)
+            {
+                Some((first, other)) if other.lines != block.lines => findings.push(
+                    Finding::new(
+                        Code::MergeTimezoneConflict,
+                        format!(
+                            "The VTIMEZONE {} of {} differs from the one in {}, which is kept.",
+                            block.tzid.as_deref().unwrap_or_default(),
+                            file,
+                            first
+                        ),
+                    )
+                    .with_location(file.to_string()),
+                ),
+                Some(_) => {}
+                None => timezones.push((file, block)),
+            }
+            continue;
+        }
+        if let Some(uid) = &block.uid {
+            let taken = entries.iter().find(|(_, other)| {
+                other.kind == block.kind
+                    && other.uid.as_ref() == Some(uid)
+                    && other.recurrence_id == block.recurrence_id
+            });
+            if let Some((first, other)) = taken {
+                let identical = other.lines == block.lines;
+                findings.push(
+                    Finding::new(
+                        Code::MergeUidCollision,
+                        if identical {
+                            format!(
+                                "{} in {} has the UID {}, like an identical entry in {}. It is kept once.",
+                                block.kind, file, uid, first
+                            )
+                        } else {
+                            format!(
+                                "{} in {} has the UID {}, like a different entry in {}. Both are kept.",
+                                block.kind, file, uid, first
+                            )
+                        },
+                    )
+                    .with_location(file.to_string()),
+                );
+                if identical {
+                    continue;
+                }
+            }
+        }
+        entries.push((file, block));
+    }
+
+    let mut text = String::new();
+    if format == Format::ICalendar {
+        fold_line(&mut text, "BEGIN:VCALENDAR");
+        fold_line(&mut text, "VERSION:2.0");
+        fold_line(&mut text, &format!("PRODID:{}", PRODID));
+    }
+    for (_, block) in timezones.iter().chain(&entries) {
+        for line in &block.lines {
+            fold_line(&mut text, line);
+        }
+    }
+    if format == Format::ICalendar {
+        fold_line(&mut text, "END:VCALENDAR");
+    }
+
+    let (format, text) = match (format, output) {
+        (Format::ICalendar, MergeOutput::Group) => (
+            Format::JSCalendar,
+            Converter
+                .convert(&text)
+                .map(|result| result.converted)
+                .map_err(|finding| finding.message)?,
+        ),
+        _ => (format, text),
+    };
+    Ok(Merged {
+        format,
+        text,
+        entries: entries.len(),
+        timezones: timezones.len(),
+        findings,
+    })
+}
+
+// Components that start at `depth`, 1 for those inside a VCALENDAR.
+fn blocks_at(source: &str, depth: usize) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+    let mut level = 0;
+    for (number, raw) in contentline::unfold(source) {
+        let line = contentline::parse_line(number, &raw);
+        let name = line.as_ref().map(|line| line.name.as_str());
+        if name == Some("BEGIN") {
+            if level == depth {
+                current = Some(Block {
+                    kind: line
+                        .as_ref()
+                        .map(|line| line.value.trim().to_ascii_uppercase())
+                        .unwrap_or_default(),
+                    lines: Vec::new(),
+                    uid: None,
+                    recurrence_id: None,
+                    tzid: None,
+                });
+            }
+            level += 1;
+        }
+        if let Some(block) = &mut current {
+            // Properties of nested components such as alarms are not the
+            // entry's own.
+            if level == depth + 1 {
+                if let Some(line) = &line {
+                    let value = Some(line.value.trim().to_string());
+                    match line.name.as_str() {
+                        "UID" => block.uid = value,
+                        "RECURRENCE-ID" => block.recurrence_id = value,
+                        "TZID" => block.tzid = value,
+                        _ => {}
+                    }
+                }
+            }
+            block.lines.push(raw);
+        }
+        if name == Some("END") {
+            level = level.saturating_sub(1);
+            if level == depth {
+                blocks.extend(current.take());
+            }
+        }
+    }
+    blocks
+}
@@ -0,0 +1,186 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{borrow::Cow, hint::black_box};
+
+use calcard::{jscalendar::JSCalendar, jscontact::JSContact, Entry, Parser};
+
+use crate::{jcal, normalize_json, split_entries, Converter, Format};
+
+pub const BENCHMARK_ITERATIONS: usize = 100;
+// Larger sources are not measured again after every conversion.
+pub const MAX_SOURCE_LEN: usize = 1024 * 1024;
+
+// Time spent in each step of converting a document. Only the work done by
+// the parser and the conversions is counted, not the checks the converter
+// runs on their output.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhaseTimings {
+    pub parse_ms: f64,
+    pub conversion_ms: f64,
+    pub roundtrip_ms: f64,
+}
+
+// Timings of repeated runs over the same document.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Benchmark {
+    pub iterations: usize,
+    pub total: PhaseTimings,
+    pub fastest: Option<PhaseTimings>,
+    pub slowest: Option<PhaseTimings>,
+}
+
+impl PhaseTimings {
+    pub fn total_ms(&self) -> f64 {
+        self.parse_ms + self.conversion_ms + self.roundtrip_ms
+    }
+
+    fn add(&mut self, started: f64, parsed: f64, converted: f64, finished: f64) {
+        self.parse_ms += parsed - started;
+        self.conversion_ms += converted - parsed;
+        self.roundtrip_ms += finished - converted;
+    }
+}
+
+impl Benchmark {
+    pub fn add(&mut self, timings: PhaseTimings) {
+        self.iterations += 1;
+        self.total.parse_ms += timings.parse_ms;
+        self.total.conversion_ms += timings.conversion_ms;
+        self.total.roundtrip_ms += timings.roundtrip_ms;
+        if self
+            .fastest
+            .is_none_or(|fastest| timings.total_ms() < fastest.total_ms())
+        {
+            self.fastest = Some(timings);
+        }
+        if self
+            .slowest
+            .is_none_or(|slowest| timings.total_ms() > slowest.total_ms())
+        {
+            self.slowest = Some(timings);
+        }
+    }
+
+    pub fn mean(&self) -> PhaseTimings {
+        let count = self.iterations.max(1) as f64;
+        PhaseTimings {
+            parse_ms: self.total.parse_ms / count,
+            conversion_ms: self.total.conversion_ms / count,
+            roundtrip_ms: self.total.roundtrip_ms / count,
+        }
+    }
+}
+
+// Parses, converts and converts back every entry of `source`, timing each
+// step with `now`, a millisecond clock.
+pub fn measure(source: &str, now: impl Fn() -> f64) -> Result<PhaseTimings, String> {
+    let mut timings = PhaseTimings::default();
+    for entry in split_entries(source) {
+        measure_entry(entry.trim_start(), &now, &mut timings)?;
+    }
+    Ok(timings)
+}
+
+fn measure_entry(
+    source: &str,
+    now: &impl Fn() -> f64,
+    timings: &mut PhaseTimings,
+) -> Result<(), String> {
+    let format = Converter
+        .detect_format(source)
+        .map_err(|finding| finding.message)?;
+    let started = now();
+    match format {
+        Format::ICalendar | Format::VCard | Format::JCal | Format::JCard => {
+            let text = match format {
+                Format::JCal | Format::JCard => Cow::Owned(jcal::to_text(source)?),
+                _ => Cow::Borrowed(source),
+            };
+            let json = matches!(format, Format::JCal | Format::JCard);
+            match Parser::new(&text).entry() {
+                Entry::ICalendar(icalendar) => {
+                    let parsed = now();
+                    let jscalendar = icalendar.into_jscalendar();
+                    let converted = jscalendar.to_string_pretty();
+                    let converted_at = now();
+                    let roundtrip = jscalendar
+                        .into_icalendar()
+                        .ok_or_else(roundtrip_failed)?
+                        .to_string();
+                    if json {
+                        black_box(jcal::from_text(&roundtrip));
+                    }
+                    black_box((converted, roundtrip));
+                    timings.add(started, parsed, converted_at, now());
+                }
+                Entry::VCard(vcard) => {
+                    let parsed = now();
+                    let jscontact = vcard.into_jscontact();
+                    let converted = jscontact.to_string_pretty();
+                    let converted_at = now();
+                    let roundtrip = jscontact
+                        .into_vcard()
+                        .ok_or_else(roundtrip_failed)?
+                        .to_string();
+                    if json {
+                        black_box(jcal::from_text(&roundtrip));
+                    }
+                    black_box((converted, roundtrip));
+                    timings.add(started, parsed, converted_at, now());
+                }
+                _ => {
+                    return Err(format!(
+                        "Failed to parse {}, the conversion shows why",
+                        format.as_str()
+                    ))
+                }
+            }
+        }
+        Format::JSCalendar => {
+            let normalized = normalize_json(source.trim_end())
+                .map_or(Cow::Borrowed(source), |(_, normalized)| normalized);
+            let jscalendar = JSCalendar::parse(normalized.trim_end())
+                .map_err(|err| format!("Failed to parse JSCalendar: {}", err))?;
+            let parsed = now();
+            let icalendar = jscalendar.into_icalendar().ok_or_else(roundtrip_failed)?;
+            let converted = icalendar.to_string();
+            let converted_at = now();
+            let roundtrip = icalendar.into_jscalendar().to_string_pretty();
+            black_box((converted, roundtrip));
+            timings.add(started, parsed, converted_at, now());
+        }
+        Format::JSContact => {
+            let normalized = normalize_json(source.trim_end())
+                .map_or(Cow::Borrowed(source), |(_, normalized)| normalized);
+            let jscontact = JSContact::parse(&normalized)
+                .map_err(|err| format!("Failed to parse JSContact: {}", err))?;
+            let parsed = now();
+            let vcard = jscontact.into_vcard().ok_or_else(roundtrip_failed)?;
+            let converted = vcard.to_string();
+            let converted_at = now();
+            let roundtrip = vcard.into_jscontact().to_string_pretty();
+            black_box((converted, roundtrip));
+            timings.add(started, parsed, converted_at, now());
+        }
+    }
+    Ok(())
+}
+
+fn roundtrip_failed() -> String {
+    "The conversion could not be taken back to the source format".to_string()
+}
+
+// Size for display, in bytes, KB or MB.
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{ICalendar, ICalendarProperty, ICalendarValue},
+};
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    contentline,
+    findings::{Code, Finding},
+};
+
+const AUDIT_LIMIT: usize = 200;
+const MAX_EXAMPLES: usize = 3;
+const DATE_FORMAT: &str = "%b %-d, %Y";
+
+struct Rule {
+    uid: String,
+    summary: String,
+    rrule: String,
+    line: usize,
+    // Day of month the rule is anchored on, and the month for yearly rules.
+    day: u32,
+    month: Option<u32>,
+    yearly: bool,
+    interval: i32,
+}
+
+// Finds rules anchored on days 29 to 31 or on February 29 and reports, with
+// example dates from the expansion, whether short months are skipped or
+// clamped to their last day.
+pub fn audit(source: &str, icalendar: &ICalendar) -> Vec<Finding> {
+    let rules = collect_rules(source);
+    if rules.is_empty() {
+        return Vec::new();
+    }
+
+    let mut occurrences: Vec<(String, NaiveDate)> = Vec::new();
+    for event in icalendar.expand_dates(Tz::Floating, AUDIT_LIMIT).events {
+        let uid = icalendar
+            .components
+            .get(event.comp_id as usize)
+            .and_then(|component| {
+                component
+                    .entries
+                    .iter()
+                    .find(|entry| entry.name == ICalendarProperty::Uid)
+            })
+            .and_then(|entry| match entry.values.first() {
+                Some(ICalendarValue::Text(uid)) => Some(uid.clone()),
+                _ => None,
+            });
+        if let (Some(uid), Some(event)) = (uid, event.try_into_date_time()) {
+            occurrences.push((uid, event.start.naive_local().date()));
+        }
+    }
+
+    let mut findings = Vec::new();
+    for rule in rules {
+        let mut dates = occurrences
+            .iter()
+            .filter(|(uid, _)| *uid == rule.uid)
+            .map(|(_, date)| *date)
+            .collect::<Vec<_>>();
+        dates.sort_unstable();
+        dates.dedup();
+
+        let clamped = dates
+            .iter()
+            .filter(|date| date.day() < rule.day && Some(date.day()) == days_in_month(date))
+            .take(MAX_EXAMPLES)
+            .map(|date| date.format(DATE_FORMAT).to_string())
+            .collect::<Vec<_>>();
+        let skipped = skipped_periods(&rule, &dates);
+
+        let anchor = match rule.month {
+            Some(2) if rule.day == 29 => "February 29".to_string(),
+            _ => format!("day {}", rule.day),
+        };
+        let behavior = match (clamped.is_empty(), skipped.is_empty()) {
+            (false, true) => format!(
+                "clamps short months to their last day, e.g. {}",
+                clamped.join(", ")
+            ),
+            (true, false) => format!(
+                "skips periods without that day, e.g. {}",
+                skipped.join(", ")
+            ),
+            (false, false) => format!(
+                "clamps some short months ({}) and skips others ({})",
+                clamped.join(", "),
+                skipped.join(", ")
+            ),
+            (true, true) => continue,
+        };
+        findings.push(
+            Finding::new(
+                Code::MonthEndRecurrence,
+                format!(
+                    "\"{}\" recurs on {} ({}); calcard {}.",
+                    rule.summary, anchor, rule.rrule, behavior
+                ),
+            )
+            .with_location(format!("line {}", rule.line)),
+        );
+    }
+    findings
+}
+
+fn collect_rules(source: &str) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    let mut depth = 0;
+    let (mut uid, mut summary, mut dtstart, mut rrule) = (None, None, None, None);
+    for line in contentline::parse(source) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                if matches!(line.value.to_ascii_uppercase().as_str(), "VEVENT" | "VTODO") {
+                    (uid, summary, dtstart, rrule) = (None, None, None, None);
+                }
+            }
+            "END" => {
+                depth -= 1;
+                if !matches!(line.value.to_ascii_uppercase().as_str(), "VEVENT" | "VTODO") {
+                    continue;
+                }
+                if let (Some(uid), Some(dtstart), Some((line, value))) =
+                    (uid.take(), dtstart.take(), rrule.take())
+                {
+                    rules.extend(anchored_rule(
+                        uid,
+                        summary
+                            .take()
+                            .unwrap_or_else(|| "Untitled event".to_string()),
+                        line,
+                        value,
+                        dtstart,
+                    ));
+                }
+            }
+            "UID" if depth == 2 => uid = Some(line.text()),
+            "SUMMARY" if depth == 2 => summary = Some(line.text()),
+            "DTSTART" if depth == 2 => {
+                dtstart = line.value.get(..8).and_then(contentline::parse_date);
+            }
+            "RRULE" if depth == 2 => rrule = Some((line.line, line.value)),
+            _ => {}
+        }
+    }
+    rules
+}
+
+fn anchored_rule(
+    uid: String,
+    summary: String,
+    line: usize,
+    rrule: String,
+    dtstart: NaiveDate,
+) -> Option<Rule> {
+    let part = |name: &str| {
+        rrule.split(';').find_map(|part| {
+            part.split_once('=')
+                .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.to_string())
+        })
+    };
+    let freq = part("FREQ")?.to_ascii_uppercase();
+    let yearly = match freq.as_str() {
+        "MONTHLY" => false,
+        "YEARLY" => true,
+        _ => return None,
+    };
+    // Weekday based rules never land on a missing date.
+    if part("BYDAY").is_some() || part("BYSETPOS").is_some() {
+        return None;
+    }
+    let day = match part("BYMONTHDAY") {
+        Some(days) if days.contains(',') => return None,
+        Some(day) => day.parse::<u32>().ok()?,
+        None => dtstart.day(),
+    };
+    let month = match part("BYMONTH") {
+        Some(months) if months.contains(',') => None,
+        Some(month) => month.parse().ok(),
+        None if yearly => Some(dtstart.month()),
+        None => None,
+    };
+    let anchored = match month {
+        Some(2) => day >= 29,
+        Some(4 | 6 | 9 | 11) => day == 31,
+        Some(_) => false,
+        None => day >= 29,
+    };
+    let interval = part("INTERVAL")
+        .and_then(|interval| interval.parse().ok())
+        .filter(|interval| *interval > 0)
+        .unwrap_or(1);
+    anchored.then_some(Rule {
+        uid,
+        summary,
+        line,
+        day,
+        month,
+        yearly,
+        interval,
+        rrule,
+    })
+}
+
+// Months, or years for yearly rules, between the first and last occurrence
+// that the rule should have hit but did not.
+fn skipped_periods(rule: &Rule, dates: &[NaiveDate]) -> Vec<String> {
+    let (Some(first), Some(last)) = (dates.first(), dates.last()) else {
+        return Vec::new();
+    };
+    let index = |date: &NaiveDate| date.year() * 12 + date.month0() as i32;
+    let step = if rule.yearly {
+        12 * rule.interval
+    } else {
+        rule.interval
+    };
+    let hit = dates.iter().map(index).collect::<Vec<_>>();
+    (index(first)..=index(last))
+        .step_by(step as usize)
+        .filter(|period| !hit.contains(period))
+        .take(MAX_EXAMPLES)
+        .map(|period| {
+            let (year, month) = (period.div_euclid(12), period.rem_euclid(12) as u32 + 1);
+            if rule.yearly {
+                year.to_string()
+            } else {
+                NaiveDate::from_ymd_opt(year, month, 1)
+                    .map(|date| date.format("%b %Y").to_string())
+                    .unwrap_or_default()
+            }
+        })
+        .collect()
+}
+
+fn days_in_month(date: &NaiveDate) -> Option<u32> {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?
+        .pred_opt()
+        .map(|last| last.day())
+}
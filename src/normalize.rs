@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Canonical form of a source, so that two exports of the same data compare
+// equal line by line: properties and components in a fixed order, parameters
+// sorted and uppercased, date-times in UTC where that keeps their meaning, and
+// JSON keys sorted.
+
+use chrono::{DateTime, TimeZone};
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, fold_line, ContentLine},
+    repair,
+};
+
+// Parameters whose values are case-insensitive tokens.
+const ENUMERATED_PARAMS: &[&str] = &[
+    "CALSCALE",
+    "CUTYPE",
+    "DISPLAY",
+    "ENCODING",
+    "FBTYPE",
+    "FEATURE",
+    "PARTSTAT",
+    "RANGE",
+    "RELATED",
+    "RELTYPE",
+    "ROLE",
+    "RSVP",
+    "SCHEDULE-AGENT",
+    "SCHEDULE-FORCE-SEND",
+    "TYPE",
+    "VALUE",
+];
+
+const DATE_TIME_PROPERTIES: &[&str] = &[
+    "COMPLETED",
+    "CREATED",
+    "DTEND",
+    "DTSTAMP",
+    "DTSTART",
+    "DUE",
+    "EXDATE",
+    "LAST-MODIFIED",
+    "RDATE",
+    "RECURRENCE-ID",
+    "REV",
+];
+
+struct Node {
+    name: String,
+    properties: Vec<ContentLine>,
+    children: Vec<Node>,
+}
+
+pub fn normalize(source: &str) -> Result<String, String> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with(['{', '[']) {
+        // Maps are ordered by key.
+        serde_json::from_str::<Value>(trimmed)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .map_err(|err| format!("The JSON cannot be read: {}", err))
+    } else if trimmed.starts_with("BEGIN:") {
+        let mut roots = parse(source)?;
+        for root in &mut roots {
+            canonicalize(root);
+        }
+        sort_components(&mut roots);
+        let mut text = String::with_capacity(source.len());
+        for root in &roots {
+            write(&mut text, root);
+        }
+        Ok(text)
+    } else {
+        Err("Only iCalendar, vCard, JSCalendar and JSContact can be normalized.".to_string())
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Node>, String> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<Node> = Vec::new();
+    for (number, raw) in contentline::unfold(source) {
+        let line = contentline::parse_line(number, &raw)
+            .ok_or_else(|| format!("Line {} is not a content line.", number))?;
+        match line.name.as_str() {
+            "BEGIN" => stack.push(Node {
+                name: line.value.trim().to_ascii_uppercase(),
+                properties: vec![],
+                children: vec![],
+            }),
+            "END" => {
+                let name = line.value.trim().to_ascii_uppercase();
+                let node = stack
+                    .pop()
+                    .filter(|node| node.name == name)
+                    .ok_or_else(|| format!("END:{} on line {} closes nothing.", name, number))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => roots.push(node),
+                }
+            }
+            _ => stack
+                .last_mut()
+                .ok_or_else(|| format!("Line {} is outside of any component.", number))?
+                .properties
+                .push(line),
+        }
+    }
+    match stack.pop() {
+        Some(node) => Err(format!("BEGIN:{} is never closed.", node.name)),
+        None => Ok(roots),
+    }
+}
+
+fn canonicalize(node: &mut Node) {
+    // The local times of a recurring series decide its occurrences, and
+    // observances are defined in local time.
+    let keep_local = matches!(node.name.as_str(), "STANDARD" | "DAYLIGHT")
+        || node
+            .properties
+            .iter()
+            .any(|line| matches!(line.name.as_str(), "RRULE" | "RDATE"));
+    for line in &mut node.properties {
+        normalize_params(line);
+        if DATE_TIME_PROPERTIES.contains(&line.name.as_str()) {
+            to_utc(line, keep_local);
+        }
+    }
+    node.properties.sort_by_cached_key(|line| {
+        (
+            line.name != "VERSION",
+            line.name.clone(),
+            line.group.clone(),
+            line.to_line(),
+        )
+    });
+    for child in &mut node.children {
+        canonicalize(child);
+    }
+    sort_components(&mut node.children);
+}
+
+// Types are written one per parameter, as a list would be quoted.
+fn normalize_params(line: &mut ContentLine) {
+    let mut params: Vec<(String, String)> = Vec::with_capacity(line.params.len());
+    for (name, value) in line.params.drain(..) {
+        if name == "TYPE" {
+            params.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (name.clone(), value.to_ascii_uppercase())),
+            );
+        } else if ENUMERATED_PARAMS.contains(&name.as_str()) {
+            params.push((name, value.to_ascii_uppercase()));
+        } else {
+            params.push((name, value));
+        }
+    }
+    params.sort();
+    params.dedup();
+    line.params = params;
+}
+
+// Rewrites date-times with a known time zone or a UTC offset in UTC. Values
+// that do not map to a single instant are left alone.
+fn to_utc(line: &mut ContentLine, keep_local: bool) {
+    if line
+        .param("VALUE")
+        .is_some_and(|value| value != "DATE-TIME")
+    {
+        return;
+    }
+    let zone = match line.param("TZID") {
+        Some(_) if keep_local => return,
+        Some(tzid) => {
+            let tzid = tzid.trim_start_matches('/');
+            match tzid
+                .parse::<chrono_tz::Tz>()
+                .ok()
+                .or_else(|| repair::windows_to_iana(tzid)?.parse().ok())
+            {
+                Some(zone) => Some(zone),
+                None => return,
+            }
+        }
+        None => None,
+    };
+
+    let mut values = Vec::new();
+    for value in line.value.split(',') {
+        let value = value.trim();
+        let utc = match zone {
+            Some(_) if value.ends_with('Z') => None,
+            Some(zone) => contentline::parse_datetime(value)
+                .and_then(|local| zone.from_local_datetime(&local).single())
+                .map(|date_time| date_time.naive_utc()),
+            None if value.ends_with('Z') => contentline::parse_datetime(value),
+            None => [
+                "%Y%m%dT%H%M%S%z",
+                "%Y-%m-%dT%H:%M:%S%:z",
+                "%Y-%m-%dT%H:%M:%S%z",
+            ]
+            .iter()
+            .find_map(|format| DateTime::parse_from_str(value, format).ok())
+            .map(|date_time| date_time.naive_utc()),
+        };
+        match utc {
+            Some(utc) => values.push(format!("{}Z", contentline::format_datetime(utc))),
+            // Floating times, dates and periods.
+            None => return,
+        }
+    }
+    line.value = values.join(",");
+    line.remove_param("TZID");
+}
+
+// Time zones first, so that they are defined before they are used, then by
+// type and identity. Components without an identity keep their order.
+fn sort_components(nodes: &mut [Node]) {
+    nodes.sort_by_cached_key(|node| {
+        let value = |name: &str| {
+            node.properties
+                .iter()
+                .find(|line| line.name == name)
+                .map(|line| line.value.clone())
+                .unwrap_or_default()
+        };
+        (
+            node.name != "VTIMEZONE",
+            node.name.clone(),
+            value("TZID"),
+            value("UID"),
+            value("RECURRENCE-ID"),
+            value("DTSTART"),
+        )
+    });
+}
+
+fn write(text: &mut String, node: &Node) {
+    fold_line(text, &format!("BEGIN:{}", node.name));
+    for line in &node.properties {
+        fold_line(text, &line.to_line());
+    }
+    for child in &node.children {
+        write(text, child);
+    }
+    fold_line(text, &format!("END:{}", node.name));
+}
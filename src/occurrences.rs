@@ -0,0 +1,646 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{
+        dates::CalendarEvent, ICalendar, ICalendarComponentType, ICalendarProperty, ICalendarValue,
+    },
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::contentline::{self, fold_line};
+
+// Occurrences generated while looking for the ones inside a window, raised
+// pass by pass up to the last one while the time budget allows.
+const SEARCH_LIMITS: [usize; 3] = [100, 1_000, 10_000];
+
+pub type Occurrence = CalendarEvent<DateTime<Tz>, DateTime<Tz>>;
+
+// Occurrences starting at or after `from` and before `until`, at most `count`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExpansionWindow {
+    pub from: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Expansion {
+    pub occurrences: Vec<Occurrence>,
+    pub elapsed_ms: f64,
+    // Whether the budget ran out before the window was searched.
+    pub out_of_budget: bool,
+}
+
+// Returns the occurrences inside `window` in start order. Floating times are
+// read in `default_tz`. A single `expand_dates` call cannot be interrupted,
+// so windows are searched in passes of growing size and no further pass
+// starts once `budget_ms` is spent. `now` is a millisecond clock.
+pub fn expand(
+    icalendar: &ICalendar,
+    default_tz: Tz,
+    window: &ExpansionWindow,
+    budget_ms: f64,
+    now: impl Fn() -> f64,
+) -> Expansion {
+    let started = now();
+    let mut expander = Expander::new(icalendar, default_tz, window);
+    while !expander.step() && now() - started <= budget_ms {}
+    expander.finish(now() - started)
+}
+
+// An expansion run one pass at a time, so the page can render and give up
+// between passes.
+pub struct Expander<'x> {
+    icalendar: &'x ICalendar,
+    default_tz: Tz,
+    window: ExpansionWindow,
+    limits: Vec<usize>,
+    pass: usize,
+    events: Vec<Occurrence>,
+    done: bool,
+}
+
+impl<'x> Expander<'x> {
+    pub fn new(icalendar: &'x ICalendar, default_tz: Tz, window: &ExpansionWindow) -> Self {
+        let limits = if window.from.is_some() || window.until.is_some() {
+            SEARCH_LIMITS.to_vec()
+        } else {
+            vec![window.count]
+        };
+        Expander {
+            icalendar,
+            default_tz,
+            window: *window,
+            limits,
+            pass: 0,
+            events: Vec::new(),
+            done: false,
+        }
+    }
+
+    // Runs the next pass, returning whether the window has been searched.
+    pub fn step(&mut self) -> bool {
+        let Some(limit) = self.limits.get(self.pass).copied() else {
+            self.done = true;
+            return true;
+        };
+        self.pass += 1;
+        let from = self.window.from.map(|from| from.naive_utc());
+        let until = self.window.until.map(|until| until.naive_utc());
+        let expanded = self.icalendar.expand_dates(self.default_tz, limit).events;
+        let exhausted = expanded.len() < limit;
+        self.events = expanded
+            .into_iter()
+            .filter_map(|event| event.try_into_date_time())
+            .filter(|event| {
+                let start = event.start.naive_utc();
+                from.is_none_or(|from| start >= from) && until.is_none_or(|until| start < until)
+            })
+            .collect::<Vec<_>>();
+        self.done =
+            exhausted || self.events.len() >= self.window.count || self.pass == self.limits.len();
+        self.done
+    }
+
+    // The occurrences found so far. Stopping before the window has been
+    // searched counts as running out of budget.
+    pub fn finish(mut self, elapsed_ms: f64) -> Expansion {
+        self.events
+            .sort_unstable_by_key(|event| event.start.naive_utc());
+        self.events.truncate(self.window.count);
+        Expansion {
+            occurrences: self.events,
+            elapsed_ms,
+            out_of_budget: !self.done,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    #[default]
+    Event,
+    Task,
+    Journal,
+}
+
+impl EntryKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Event => "Event",
+            EntryKind::Task => "Task",
+            EntryKind::Journal => "Journal",
+        }
+    }
+
+    // The id of its name in the UI translations.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            EntryKind::Event => "entry-kind-event",
+            EntryKind::Task => "entry-kind-task",
+            EntryKind::Journal => "entry-kind-journal",
+        }
+    }
+
+    pub fn icalendar_name(&self) -> &'static str {
+        match self {
+            EntryKind::Event => "VEVENT",
+            EntryKind::Task => "VTODO",
+            EntryKind::Journal => "VJOURNAL",
+        }
+    }
+
+    pub fn of(icalendar: &ICalendar, occurrence: &Occurrence) -> Self {
+        match icalendar
+            .components
+            .get(occurrence.comp_id as usize)
+            .map(|component| &component.component_type)
+        {
+            Some(ICalendarComponentType::VTodo) => EntryKind::Task,
+            Some(ICalendarComponentType::VJournal) => EntryKind::Journal,
+            _ => EntryKind::Event,
+        }
+    }
+}
+
+// What an occurrence is, read from the override or series it comes from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Details {
+    pub kind: EntryKind,
+    pub title: Option<String>,
+    // Status and completion of tasks.
+    pub progress: Option<String>,
+}
+
+// A task with a DUE but no DTSTART, so nothing to expand from. It is listed
+// once, at its due time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DueTask {
+    pub due: NaiveDateTime,
+    pub tz: Option<String>,
+    pub title: Option<String>,
+    pub progress: Option<String>,
+}
+
+// Where an occurrence of the table comes from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Origin {
+    // Generated by the recurrence rule, or the only instance of an event.
+    #[default]
+    Regular,
+    // Listed in an RDATE.
+    Added,
+    // Replaced by an override with a RECURRENCE-ID, and its SUMMARY.
+    Modified(Option<String>),
+    // Listed in an EXDATE, so not an occurrence at all.
+    Removed,
+}
+
+// A date an EXDATE takes out of a series, in the time zone it was written in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemovedDate {
+    pub kind: EntryKind,
+    pub start: NaiveDateTime,
+    pub tz: Option<String>,
+}
+
+// Recurrence data of a UID, read from the iCalendar text. Date-times are
+// local, as written.
+#[derive(Default)]
+struct Series {
+    kind: EntryKind,
+    recurring: bool,
+    overrides: Vec<Override>,
+    added: Vec<String>,
+    removed: Vec<RemovedDate>,
+}
+
+struct Override {
+    start: String,
+    recurrence_id: String,
+    summary: Option<String>,
+}
+
+// Tells the occurrences generated by the rules from the ones added by RDATE
+// and the ones overridden. `source` must be the text `icalendar` was parsed
+// from.
+pub fn origins(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> Vec<Origin> {
+    let series = series(source);
+    occurrences
+        .iter()
+        .map(|occurrence| {
+            let Some(series) = uid(icalendar, occurrence)
+                .and_then(|uid| series.iter().find(|(id, _)| *id == uid))
+                .map(|(_, series)| series)
+            else {
+                return Origin::Regular;
+            };
+            let local = occurrence
+                .start
+                .naive_local()
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string();
+            if let Some(entry) = series.overrides.iter().find(|entry| entry.start == local) {
+                Origin::Modified(entry.summary.clone())
+            } else if series.added.contains(&local) {
+                Origin::Added
+            } else {
+                Origin::Regular
+            }
+        })
+        .collect()
+}
+
+// `source` must be the text `icalendar` was parsed from.
+pub fn details(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> Vec<Details> {
+    let components = contentline::components(source);
+    occurrences
+        .iter()
+        .map(|occurrence| {
+            let kind = EntryKind::of(icalendar, occurrence);
+            let component = instance(&components, icalendar, occurrence);
+            Details {
+                kind,
+                title: component
+                    .and_then(|component| property(component, "SUMMARY"))
+                    .map(|summary| summary.text())
+                    .filter(|title| !title.is_empty()),
+                progress: component
+                    .filter(|_| kind == EntryKind::Task)
+                    .map(task_progress),
+            }
+        })
+        .collect()
+}
+
+pub fn due_only_tasks(source: &str) -> Vec<DueTask> {
+    let mut tasks = contentline::components(source)
+        .iter()
+        .filter(|component| component.name() == "VTODO")
+        .filter(|component| property(component, "DTSTART").is_none())
+        .filter_map(|component| {
+            let due = property(component, "DUE")?;
+            Some(DueTask {
+                due: contentline::parse_date_or_datetime(&due.value)?,
+                tz: if due.value.trim().ends_with('Z') {
+                    Some("UTC".to_string())
+                } else {
+                    due.param("TZID").map(str::to_string)
+                },
+                title: property(component, "SUMMARY")
+                    .map(|summary| summary.text())
+                    .filter(|title| !title.is_empty()),
+                progress: Some(task_progress(component)),
+            })
+        })
+        .collect::<Vec<_>>();
+    tasks.sort_by_key(|task| task.due);
+    tasks
+}
+
+// "Completed Mar 3, 2025", "In progress, 40%" or "Needs action".
+fn task_progress(component: &contentline::Component) -> String {
+    let percent = property(component, "PERCENT-COMPLETE")
+        .and_then(|line| line.value.trim().parse::<u8>().ok())
+        .filter(|percent| *percent > 0);
+    let completed = property(component, "COMPLETED")
+        .and_then(|line| contentline::parse_date_or_datetime(&line.value))
+        .map(|date| format!("Completed {}", date.format("%b %-d, %Y")));
+    let status = property(component, "STATUS")
+        .map(|line| line.value.trim().to_ascii_uppercase())
+        .unwrap_or_default();
+    match status.as_str() {
+        "COMPLETED" => completed.unwrap_or_else(|| "Completed".to_string()),
+        "CANCELLED" => "Cancelled".to_string(),
+        "IN-PROCESS" => match percent {
+            Some(percent) => format!("In progress, {}%", percent),
+            None => "In progress".to_string(),
+        },
+        _ => match (completed, percent) {
+            (Some(completed), _) => completed,
+            (None, Some(percent)) => format!("{}% done", percent),
+            (None, None) => "Needs action".to_string(),
+        },
+    }
+}
+
+// Every EXDATE of the recurring series in the text, in date order.
+pub fn removed_dates(source: &str) -> Vec<RemovedDate> {
+    let mut dates = series(source)
+        .into_iter()
+        .filter(|(_, series)| series.recurring)
+        .flat_map(|(_, series)| series.removed)
+        .collect::<Vec<_>>();
+    dates.sort_by_key(|date| date.start);
+    dates
+}
+
+// Writes occurrences as the minimal Events a JMAP server returns for an
+// expanding CalendarEvent/query, so its expansion can be compared with this
+// one entry by entry. The recurrence id of overrides is their original start,
+// which is only in the text, so `source` must be the text `icalendar` was
+// parsed from.
+pub fn to_jscalendar(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> String {
+    let series = series(source);
+    let events = occurrences
+        .iter()
+        .map(|occurrence| {
+            let uid = uid(icalendar, occurrence);
+            let start = occurrence.start.naive_local();
+            let local = start.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+            let mut event = Map::new();
+            event.insert(
+                "@type".into(),
+                match EntryKind::of(icalendar, occurrence) {
+                    EntryKind::Task => "Task",
+                    EntryKind::Event | EntryKind::Journal => "Event",
+                }
+                .into(),
+            );
+            if let Some((uid, series)) = uid.as_ref().and_then(|uid| {
+                series
+                    .iter()
+                    .find(|(id, _)| id == uid)
+                    .map(|(_, series)| (uid, series))
+            }) {
+                event.insert("uid".into(), uid.clone().into());
+                if series.recurring {
+                    let recurrence_id = series
+                        .overrides
+                        .iter()
+                        .find(|entry| entry.start == local)
+                        .map_or(local, |entry| entry.recurrence_id.clone());
+                    event.insert("recurrenceId".into(), recurrence_id.into());
+                    if let Some(tz) = occurrence.start.timezone().name() {
+                        event.insert("recurrenceIdTimeZone".into(), tz.into_owned().into());
+                    }
+                }
+            }
+            event.insert("utcStart".into(), utc(&occurrence.start).into());
+            event.insert("utcEnd".into(), utc(&occurrence.end).into());
+            Value::Object(event)
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&Value::Array(events)).unwrap_or_default()
+}
+
+fn uid(icalendar: &ICalendar, occurrence: &Occurrence) -> Option<String> {
+    icalendar
+        .components
+        .get(occurrence.comp_id as usize)
+        .and_then(|component| {
+            component
+                .entries
+                .iter()
+                .find(|entry| entry.name == ICalendarProperty::Uid)
+        })
+        .and_then(|entry| match entry.values.first() {
+            Some(ICalendarValue::Text(uid)) => Some(uid.clone()),
+            _ => None,
+        })
+}
+
+// Occurrences as CSV with their local start and end, time zone and summary.
+// `source` must be the text `icalendar` was parsed from.
+pub fn to_csv(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> String {
+    let components = contentline::components(source);
+    let mut csv = String::from("start,end,timezone,summary,type\n");
+    for occurrence in occurrences {
+        let summary = instance(&components, icalendar, occurrence)
+            .and_then(|component| property(component, "SUMMARY"))
+            .map(|summary| summary.text())
+            .unwrap_or_default();
+        let summary = if summary.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", summary.replace('"', "\"\""))
+        } else {
+            summary
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            occurrence.start.format("%Y-%m-%d %H:%M:%S"),
+            occurrence.end.format("%Y-%m-%d %H:%M:%S"),
+            occurrence
+                .start
+                .timezone()
+                .name()
+                .as_deref()
+                .unwrap_or("Floating"),
+            summary,
+            EntryKind::of(icalendar, occurrence).label()
+        ));
+    }
+    csv
+}
+
+// Occurrences as single, non-recurring events, tasks and journal entries in
+// UTC, keeping the other properties of the override or series they come from. Each gets a UID of its
+// own so calendars that cannot expand rules import every instance.
+pub fn to_icalendar(source: &str, icalendar: &ICalendar, occurrences: &[Occurrence]) -> String {
+    let components = contentline::components(source);
+    let mut ical = String::new();
+    fold_line(&mut ical, "BEGIN:VCALENDAR");
+    fold_line(&mut ical, "VERSION:2.0");
+    fold_line(
+        &mut ical,
+        "PRODID:-//Stalwart Labs LLC//jmap-convert occurrences//EN",
+    );
+    for occurrence in occurrences {
+        let component = instance(&components, icalendar, occurrence);
+        let date_time = |date_time: &DateTime<Tz>| {
+            if matches!(date_time.timezone(), Tz::Floating) {
+                contentline::format_datetime(date_time.naive_local())
+            } else {
+                format!("{}Z", contentline::format_datetime(date_time.naive_utc()))
+            }
+        };
+        let start = date_time(&occurrence.start);
+        let uid = uid(icalendar, occurrence).unwrap_or_else(|| "occurrence".to_string());
+        let kind = EntryKind::of(icalendar, occurrence);
+        fold_line(&mut ical, &format!("BEGIN:{}", kind.icalendar_name()));
+        fold_line(&mut ical, &format!("UID:{}-{}", uid, start));
+        if component
+            .and_then(|component| property(component, "DTSTAMP"))
+            .is_none()
+        {
+            fold_line(
+                &mut ical,
+                &format!(
+                    "DTSTAMP:{}Z",
+                    contentline::format_datetime(occurrence.start.naive_utc())
+                ),
+            );
+        }
+        fold_line(&mut ical, &format!("DTSTART:{}", start));
+        match kind {
+            EntryKind::Event => {
+                fold_line(&mut ical, &format!("DTEND:{}", date_time(&occurrence.end)))
+            }
+            EntryKind::Task => fold_line(&mut ical, &format!("DUE:{}", date_time(&occurrence.end))),
+            // Journal entries have no duration.
+            EntryKind::Journal => {}
+        }
+        for line in component
+            .into_iter()
+            .flat_map(|component| &component.properties)
+        {
+            if !matches!(
+                line.name.as_str(),
+                "UID"
+                    | "DTSTART"
+                    | "DTEND"
+                    | "DURATION"
+                    | "DUE"
+                    | "RRULE"
+                    | "RDATE"
+                    | "EXRULE"
+                    | "EXDATE"
+                    | "RECURRENCE-ID"
+            ) {
+                fold_line(&mut ical, &line.to_line());
+            }
+        }
+        fold_line(&mut ical, &format!("END:{}", kind.icalendar_name()));
+    }
+    fold_line(&mut ical, "END:VCALENDAR");
+    ical
+}
+
+// The text of the override an occurrence comes from, or else of its series.
+// `components` must be read from the text `icalendar` was parsed from.
+pub fn instance<'x>(
+    components: &'x [contentline::Component],
+    icalendar: &ICalendar,
+    occurrence: &Occurrence,
+) -> Option<&'x contentline::Component> {
+    let uid = uid(icalendar, occurrence)?;
+    let local = occurrence
+        .start
+        .naive_local()
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string();
+    let series = components
+        .iter()
+        .filter(|component| property(component, "UID").is_some_and(|line| line.value.trim() == uid))
+        .collect::<Vec<_>>();
+    series
+        .iter()
+        .find(|component| {
+            property(component, "RECURRENCE-ID").is_some()
+                && property(component, "DTSTART")
+                    .and_then(|line| local_date_time(&line.value))
+                    .is_some_and(|start| start == local)
+        })
+        .or_else(|| {
+            series
+                .iter()
+                .find(|component| property(component, "RECURRENCE-ID").is_none())
+        })
+        .copied()
+}
+
+fn property<'x>(
+    component: &'x contentline::Component,
+    name: &str,
+) -> Option<&'x contentline::ContentLine> {
+    component
+        .properties
+        .iter()
+        .find(|property| property.name == name)
+}
+
+fn series(source: &str) -> Vec<(String, Series)> {
+    let mut series: Vec<(String, Series)> = Vec::new();
+    let mut depth = 0;
+    let (mut uid, mut recurring, mut dtstart, mut recurrence_id) = (None, false, None, None);
+    let mut kind = EntryKind::Event;
+    let (mut summary, mut added, mut removed) = (None, Vec::new(), Vec::new());
+    for line in contentline::parse(source) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                if depth == 2 {
+                    kind = match line.value.trim().to_ascii_uppercase().as_str() {
+                        "VTODO" => EntryKind::Task,
+                        "VJOURNAL" => EntryKind::Journal,
+                        _ => EntryKind::Event,
+                    };
+                    (uid, recurring, dtstart, recurrence_id) = (None, false, None, None);
+                    (summary, added, removed) = (None, Vec::new(), Vec::new());
+                }
+            }
+            "END" => {
+                if depth == 2 {
+                    if let Some(uid) = uid.take() {
+                        let index = match series.iter().position(|(id, _)| *id == uid) {
+                            Some(index) => index,
+                            None => {
+                                series.push((uid, Series::default()));
+                                series.len() - 1
+                            }
+                        };
+                        let entry = &mut series[index].1;
+                        entry.kind = kind;
+                        entry.recurring |= recurring || recurrence_id.is_some();
+                        entry.added.append(&mut added);
+                        entry.removed.append(&mut removed);
+                        if let (Some(start), Some(recurrence_id)) =
+                            (dtstart.take(), recurrence_id.take())
+                        {
+                            entry.overrides.push(Override {
+                                start,
+                                recurrence_id,
+                                summary: summary.take(),
+                            });
+                        }
+                    }
+                }
+                depth -= 1;
+            }
+            "UID" if depth == 2 => uid = Some(line.text()),
+            "RRULE" if depth == 2 => recurring = true,
+            "RDATE" if depth == 2 => {
+                recurring = true;
+                // Periods are reduced to their start.
+                added.extend(
+                    line.value
+                        .split(',')
+                        .filter_map(|date| local_date_time(date.split('/').next()?)),
+                );
+            }
+            "EXDATE" if depth == 2 => {
+                removed.extend(line.value.split(',').filter_map(|date| {
+                    Some(RemovedDate {
+                        kind,
+                        start: contentline::parse_date_or_datetime(date)?,
+                        tz: line.param("TZID").map(str::to_string),
+                    })
+                }));
+            }
+            "SUMMARY" if depth == 2 => summary = Some(line.text()),
+            "DTSTART" if depth == 2 => dtstart = local_date_time(&line.value),
+            "RECURRENCE-ID" if depth == 2 => recurrence_id = local_date_time(&line.value),
+            _ => {}
+        }
+    }
+    series
+}
+
+// Dates are taken as midnight, the way they are expanded.
+fn local_date_time(value: &str) -> Option<String> {
+    contentline::parse_date_or_datetime(value)
+        .map(|value| value.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+fn utc(date_time: &DateTime<Tz>) -> String {
+    date_time
+        .naive_utc()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
@@ -0,0 +1,219 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{Entry, Parser};
+use serde_json::{json, Map, Value};
+
+const NO_ORGANIZATION: &str = "(No organization)";
+
+// Company → department → people tree built from the cards' organizations,
+// titles and relatedTo properties.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrgUnit {
+    pub name: String,
+    pub people: Vec<OrgPerson>,
+    pub units: Vec<OrgUnit>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrgPerson {
+    pub name: String,
+    pub title: Option<String>,
+    pub uid: Option<String>,
+    pub related: Vec<OrgRelation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrgRelation {
+    pub relation: String,
+    // Name of the related card when it is part of the same paste.
+    pub name: String,
+}
+
+// Collects every card in a paste as JSContact, converting vCards on the way.
+pub fn cards_from_source(source: &str) -> Vec<Value> {
+    let source = source.trim();
+    if source.starts_with("BEGIN:") {
+        let mut parser = Parser::new(source);
+        let mut cards = Vec::new();
+        loop {
+            match parser.entry() {
+                Entry::VCard(vcard) => {
+                    if let Ok(card) =
+                        serde_json::from_str(&vcard.into_jscontact().to_string_pretty())
+                    {
+                        cards.push(card);
+                    }
+                }
+                Entry::ICalendar(_) => {}
+                _ => break,
+            }
+        }
+        cards
+    } else {
+        match serde_json::from_str::<Value>(source) {
+            Ok(Value::Array(items)) => items.into_iter().filter(is_card).collect(),
+            Ok(card) if is_card(&card) => vec![card],
+            _ => vec![],
+        }
+    }
+}
+
+pub fn build(cards: &[Value]) -> Vec<OrgUnit> {
+    let names = cards
+        .iter()
+        .filter_map(|card| Some((card.get("uid")?.as_str()?, card_name(card)?)))
+        .collect::<Vec<_>>();
+
+    let mut roots: Vec<OrgUnit> = Vec::new();
+    for card in cards {
+        let Some(name) = card_name(card) else {
+            continue;
+        };
+        let organization = first_value(card, "organizations");
+        let path = organization
+            .map(|org| {
+                org.get("name")
+                    .and_then(Value::as_str)
+                    .into_iter()
+                    .chain(
+                        org.get("units")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|unit| unit.get("name").and_then(Value::as_str)),
+                    )
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|path| !path.is_empty())
+            .unwrap_or_else(|| vec![NO_ORGANIZATION.to_string()]);
+
+        let person = OrgPerson {
+            name,
+            title: card
+                .get("titles")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|titles| titles.values())
+                .filter_map(|title| title.get("name").and_then(Value::as_str))
+                .next()
+                .map(str::to_string),
+            uid: card.get("uid").and_then(Value::as_str).map(str::to_string),
+            related: card
+                .get("relatedTo")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(uri, related)| OrgRelation {
+                    relation: related
+                        .get("relation")
+                        .and_then(Value::as_object)
+                        .and_then(|relation| relation.keys().next())
+                        .map(String::as_str)
+                        .unwrap_or("related")
+                        .to_string(),
+                    name: names
+                        .iter()
+                        .find(|(uid, _)| uid == uri)
+                        .map(|(_, name)| name.clone())
+                        .unwrap_or_else(|| uri.clone()),
+                })
+                .collect(),
+        };
+
+        let mut units = &mut roots;
+        let mut path = path.into_iter().peekable();
+        while let Some(name) = path.next() {
+            let idx = match units.iter().position(|unit| unit.name == name) {
+                Some(idx) => idx,
+                None => {
+                    units.push(OrgUnit {
+                        name,
+                        ..Default::default()
+                    });
+                    units.len() - 1
+                }
+            };
+            if path.peek().is_none() {
+                units[idx].people.push(person);
+                break;
+            }
+            units = &mut units[idx].units;
+        }
+    }
+    roots
+}
+
+pub fn people_count(units: &[OrgUnit]) -> usize {
+    units
+        .iter()
+        .map(|unit| unit.people.len() + people_count(&unit.units))
+        .sum()
+}
+
+pub fn to_json(units: &[OrgUnit]) -> String {
+    serde_json::to_string_pretty(&Value::Array(units.iter().map(unit_json).collect()))
+        .unwrap_or_default()
+}
+
+fn unit_json(unit: &OrgUnit) -> Value {
+    json!({
+        "name": unit.name,
+        "people": unit.people.iter().map(|person| {
+            let mut object = Map::new();
+            object.insert("name".into(), person.name.clone().into());
+            if let Some(title) = &person.title {
+                object.insert("title".into(), title.clone().into());
+            }
+            if let Some(uid) = &person.uid {
+                object.insert("uid".into(), uid.clone().into());
+            }
+            if !person.related.is_empty() {
+                object.insert(
+                    "related".into(),
+                    person
+                        .related
+                        .iter()
+                        .map(|related| json!({ "relation": related.relation, "name": related.name }))
+                        .collect(),
+                );
+            }
+            Value::Object(object)
+        }).collect::<Vec<_>>(),
+        "units": unit.units.iter().map(unit_json).collect::<Vec<_>>(),
+    })
+}
+
+fn is_card(value: &Value) -> bool {
+    value.get("@type").and_then(Value::as_str) == Some("Card")
+}
+
+pub fn card_name(card: &Value) -> Option<String> {
+    let name = card.get("name")?;
+    name.get("full")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+            let parts = name
+                .get("components")?
+                .as_array()?
+                .iter()
+                .filter(|component| {
+                    matches!(
+                        component.get("kind").and_then(Value::as_str),
+                        Some("given" | "surname")
+                    )
+                })
+                .filter_map(|component| component.get("value").and_then(Value::as_str))
+                .collect::<Vec<_>>();
+            (!parts.is_empty()).then(|| parts.join(" "))
+        })
+}
+
+fn first_value<'x>(card: &'x Value, property: &str) -> Option<&'x Value> {
+    card.get(property)?.as_object()?.values().next()
+}
@@ -0,0 +1,271 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::findings::{Code, Finding};
+
+// RFC 8984, Section 4.3.5: patches to these properties are ignored by servers.
+const OVERRIDE_IMMUTABLE: &[&str] = &[
+    "@type",
+    "excludedRecurrenceRules",
+    "method",
+    "privacy",
+    "prodId",
+    "recurrenceId",
+    "recurrenceIdTimeZone",
+    "recurrenceOverrides",
+    "recurrenceRules",
+    "relatedTo",
+    "replyTo",
+    "sentBy",
+    "timeZones",
+    "uid",
+];
+
+const LOCALIZATION_IMMUTABLE: &[&str] = &["@type", "locale", "localizations", "uid"];
+
+// Validates the PatchObjects found in `recurrenceOverrides` and `localizations`
+// of every object in a JSCalendar document, including group entries.
+pub fn validate_patches(source: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if let Ok(value) = serde_json::from_str::<Value>(source) {
+        validate_object(&value, "", &mut findings);
+    }
+    findings
+}
+
+// The PatchObject (RFC 8620, Section 5.3) that updates the first JSCalendar
+// or JSContact object into the second. A group holding a single entry stands
+// for that entry.
+pub fn diff_objects(from: &str, to: &str) -> Result<String, String> {
+    let parse = |source: &str, which: &str| {
+        let value = serde_json::from_str::<Value>(source)
+            .map_err(|err| format!("The {} version is not valid JSON: {}", which, err))?;
+        match single_entry(value) {
+            Value::Object(object) => Ok(object),
+            _ => Err(format!("The {} version is not a single object.", which)),
+        }
+    };
+    let from = parse(from, "first")?;
+    let to = parse(to, "second")?;
+    if from.get("@type") != to.get("@type") {
+        return Err("The two versions are objects of different types.".to_string());
+    }
+    let mut patch = Map::new();
+    diff_into(&from, &to, "", &mut patch);
+    serde_json::to_string_pretty(&patch).map_err(|err| err.to_string())
+}
+
+fn single_entry(value: Value) -> Value {
+    match value {
+        Value::Object(mut object)
+            if object.get("@type").and_then(Value::as_str) == Some("Group") =>
+        {
+            match object.remove("entries") {
+                Some(Value::Array(mut entries)) if entries.len() == 1 => entries.remove(0),
+                entries => {
+                    if let Some(entries) = entries {
+                        object.insert("entries".into(), entries);
+                    }
+                    Value::Object(object)
+                }
+            }
+        }
+        Value::Array(mut values) if values.len() == 1 => single_entry(values.remove(0)),
+        value => value,
+    }
+}
+
+// Members of objects found in both versions are patched one by one. Arrays
+// cannot be patched inside, so they are replaced whole like other values, and
+// removed members are set to null.
+fn diff_into(
+    from: &Map<String, Value>,
+    to: &Map<String, Value>,
+    prefix: &str,
+    patch: &mut Map<String, Value>,
+) {
+    for (key, value) in from {
+        if !value.is_null() && to.get(key).is_none_or(Value::is_null) {
+            patch.insert(format!("{}{}", prefix, escape(key)), Value::Null);
+        }
+    }
+    for (key, value) in to {
+        let pointer = format!("{}{}", prefix, escape(key));
+        match (from.get(key), value) {
+            (_, Value::Null) => {}
+            (Some(old), value) if old == value => {}
+            (Some(Value::Object(old)), Value::Object(new)) if !key.is_empty() => {
+                diff_into(old, new, &format!("{}/", pointer), patch);
+            }
+            (_, value) => {
+                patch.insert(pointer, value.clone());
+            }
+        }
+    }
+}
+
+fn validate_object(value: &Value, path: &str, findings: &mut Vec<Finding>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+
+    for (property, immutable) in [
+        ("recurrenceOverrides", OVERRIDE_IMMUTABLE),
+        ("localizations", LOCALIZATION_IMMUTABLE),
+    ] {
+        if let Some(patches) = object.get(property).and_then(Value::as_object) {
+            for (key, patch) in patches {
+                let patch_path = format!("{}/{}/{}", path, property, escape(key));
+                match patch.as_object() {
+                    Some(patch) => {
+                        validate_patch(object, patch, immutable, &patch_path, findings);
+                    }
+                    None => findings.push(
+                        Finding::new(Code::InvalidPatchPointer, "Patch is not a JSON object.")
+                            .with_location(patch_path),
+                    ),
+                }
+            }
+        }
+    }
+
+    if let Some(entries) = object.get("entries").and_then(Value::as_array) {
+        for (idx, entry) in entries.iter().enumerate() {
+            validate_object(entry, &format!("{}/entries/{}", path, idx), findings);
+        }
+    }
+}
+
+fn validate_patch(
+    base: &Map<String, Value>,
+    patch: &Map<String, Value>,
+    immutable: &[&str],
+    path: &str,
+    findings: &mut Vec<Finding>,
+) {
+    let mut pointers = Vec::with_capacity(patch.len());
+
+    for key in patch.keys() {
+        let location = format!("{}/{}", path, escape(key));
+        let segments = match parse_pointer(key) {
+            Ok(segments) => segments,
+            Err(reason) => {
+                findings.push(
+                    Finding::new(
+                        Code::InvalidPatchPointer,
+                        format!("Invalid patch pointer {:?}: {}.", key, reason),
+                    )
+                    .with_location(location),
+                );
+                continue;
+            }
+        };
+
+        if immutable.contains(&segments[0].as_str()) {
+            findings.push(
+                Finding::new(
+                    Code::ImmutablePatch,
+                    format!("Property {:?} cannot be patched here.", segments[0]),
+                )
+                .with_location(location),
+            );
+            continue;
+        }
+
+        // Every segment but the last must resolve to an existing object.
+        let mut current = Some(base);
+        for (idx, segment) in segments.iter().enumerate() {
+            let Some(object) = current else {
+                break;
+            };
+            match object.get(segment) {
+                Some(Value::Array(_)) if idx + 1 < segments.len() => {
+                    findings.push(
+                        Finding::new(
+                            Code::ArrayPatch,
+                            format!(
+                                "Pointer {:?} patches inside an array, the array must be replaced as a whole.",
+                                key
+                            ),
+                        )
+                        .with_location(location.clone()),
+                    );
+                    break;
+                }
+                Some(value) => current = value.as_object(),
+                None if idx + 1 < segments.len() => {
+                    findings.push(
+                        Finding::new(
+                            Code::MissingPatchParent,
+                            format!(
+                                "Pointer {:?} refers to {:?}, which does not exist in the base object.",
+                                key, segment
+                            ),
+                        )
+                        .with_location(location.clone()),
+                    );
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        pointers.push((key, segments, location));
+    }
+
+    for (key, segments, location) in &pointers {
+        if let Some((other, _, _)) = pointers.iter().find(|(other, other_segments, _)| {
+            other != key
+                && other_segments.len() < segments.len()
+                && segments.starts_with(other_segments)
+        }) {
+            findings.push(
+                Finding::new(
+                    Code::OverlappingPatch,
+                    format!("Pointer {:?} overlaps with pointer {:?}.", key, other),
+                )
+                .with_location(location.clone()),
+            );
+        }
+    }
+}
+
+// Patch keys are JSON pointers with an implicit leading "/".
+fn parse_pointer(key: &str) -> Result<Vec<String>, &'static str> {
+    if key.is_empty() {
+        return Err("pointer is empty");
+    } else if key.starts_with('/') {
+        return Err("pointer must not start with \"/\"");
+    }
+
+    key.split('/')
+        .map(|segment| {
+            if segment.is_empty() {
+                return Err("pointer contains an empty segment");
+            }
+            let mut result = String::with_capacity(segment.len());
+            let mut chars = segment.chars();
+            while let Some(ch) = chars.next() {
+                if ch == '~' {
+                    match chars.next() {
+                        Some('0') => result.push('~'),
+                        Some('1') => result.push('/'),
+                        _ => return Err("\"~\" must be followed by \"0\" or \"1\""),
+                    }
+                } else {
+                    result.push(ch);
+                }
+            }
+            Ok(result)
+        })
+        .collect()
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
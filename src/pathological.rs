@@ -0,0 +1,185 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    contentline,
+    findings::{Code, Finding},
+};
+
+// Periods a bounded sub-daily rule may step through before it is flagged.
+const MAX_PERIODS: i64 = 1_000_000;
+// Candidates a rule may generate per period before BYSETPOS picks from them.
+const MAX_CANDIDATES: u64 = 10_000;
+const DAYS_IN_MONTH: [u32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+struct Rule {
+    line: usize,
+    value: String,
+    dtstart: Option<NaiveDateTime>,
+}
+
+// Flags rules whose expansion is likely to take very long or never finish:
+// sub-daily frequencies over long spans, BYSETPOS over huge candidate sets
+// and filters no date can match. Runs on the text, before anything expands.
+pub fn audit(source: &str) -> Vec<Finding> {
+    collect_rules(source)
+        .into_iter()
+        .filter_map(|rule| {
+            let reason = check(&rule)?;
+            Some(
+                Finding::new(
+                    Code::PathologicalRecurrence,
+                    format!("RRULE:{} {}.", rule.value, reason),
+                )
+                .with_location(format!("line {}", rule.line)),
+            )
+        })
+        .collect()
+}
+
+fn collect_rules(source: &str) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    let mut pending: Vec<(usize, String)> = Vec::new();
+    let mut dtstart = None;
+    let mut depth = 0;
+    for line in contentline::parse(source) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                if depth == 2 {
+                    pending.clear();
+                    dtstart = None;
+                }
+            }
+            "END" => {
+                if depth == 2 {
+                    rules.extend(pending.drain(..).map(|(line, value)| Rule {
+                        line,
+                        value,
+                        dtstart,
+                    }));
+                }
+                depth -= 1;
+            }
+            "DTSTART" if depth == 2 => dtstart = parse_date_time(&line.value),
+            "RRULE" if depth == 2 => pending.push((line.line, line.value.trim().to_string())),
+            _ => {}
+        }
+    }
+    rules
+}
+
+fn check(rule: &Rule) -> Option<String> {
+    let part = |name: &str| {
+        rule.value.split(';').find_map(|part| {
+            part.split_once('=')
+                .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim().to_string())
+        })
+    };
+    let list_len = |name: &str| part(name).map_or(1, |list| list.split(',').count() as u64);
+    let freq = part("FREQ")?.to_ascii_uppercase();
+    let interval = part("INTERVAL")
+        .and_then(|interval| interval.parse::<i64>().ok())
+        .filter(|interval| *interval > 0)
+        .unwrap_or(1);
+
+    if let (Some(months), Some(days)) = (part("BYMONTH"), part("BYMONTHDAY")) {
+        if never_matches(&months, &days) {
+            return Some(format!(
+                "never matches: no month in BYMONTH={} has day {}, so the search runs until it gives up",
+                months, days
+            ));
+        }
+    }
+
+    let period_seconds = match freq.as_str() {
+        "SECONDLY" => 1,
+        "MINUTELY" => 60,
+        "HOURLY" => 3_600,
+        _ => 0,
+    };
+    if period_seconds > 0 && part("COUNT").is_none() {
+        match (
+            part("UNTIL").and_then(|until| parse_date_time(&until)),
+            rule.dtstart,
+        ) {
+            (Some(until), Some(dtstart)) => {
+                let periods = (until - dtstart).num_seconds() / (period_seconds * interval);
+                if periods > MAX_PERIODS {
+                    return Some(format!(
+                        "steps through about {} {} periods before UNTIL",
+                        periods,
+                        freq.to_ascii_lowercase()
+                    ));
+                }
+            }
+            (None, _) if freq != "HOURLY" => {
+                return Some(format!(
+                    "is {} without COUNT or UNTIL, so it never ends",
+                    freq.to_ascii_lowercase()
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    if part("BYSETPOS").is_some() {
+        // Weekdays repeat about 5 times a month and 53 times a year.
+        let weekdays = match freq.as_str() {
+            "MONTHLY" => 5,
+            "YEARLY" => 53,
+            _ => 1,
+        };
+        let candidates = [
+            "BYMONTH",
+            "BYWEEKNO",
+            "BYYEARDAY",
+            "BYMONTHDAY",
+            "BYHOUR",
+            "BYMINUTE",
+            "BYSECOND",
+        ]
+        .iter()
+        .map(|name| list_len(name))
+        .product::<u64>()
+            * part("BYDAY").map_or(1, |days| days.split(',').count() as u64 * weekdays);
+        if candidates > MAX_CANDIDATES {
+            return Some(format!(
+                "builds about {} candidates per period for BYSETPOS to choose from",
+                candidates
+            ));
+        }
+    }
+    None
+}
+
+fn never_matches(months: &str, days: &str) -> bool {
+    let months = months
+        .split(',')
+        .filter_map(|month| month.trim().parse::<usize>().ok())
+        .filter(|month| (1..=12).contains(month))
+        .collect::<Vec<_>>();
+    let days = days
+        .split(',')
+        .filter_map(|day| day.trim().parse::<i64>().ok())
+        .map(|day| day.unsigned_abs() as u32)
+        .collect::<Vec<_>>();
+    !months.is_empty()
+        && !days.is_empty()
+        && months.iter().all(|month| {
+            days.iter()
+                .all(|day| *day == 0 || *day > DAYS_IN_MONTH[month - 1])
+        })
+}
+
+// Anything after the date of a malformed date-time is ignored.
+fn parse_date_time(value: &str) -> Option<NaiveDateTime> {
+    contentline::parse_datetime(value)
+        .or_else(|| contentline::parse_date(value.trim().get(..8)?)?.and_hms_opt(0, 0, 0))
+}
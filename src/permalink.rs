@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+use crate::bundle::SessionBundle;
+
+const FRAGMENT_PREFIX: &str = "s=";
+const COMPRESSION_LEVEL: u8 = 9;
+// Guards against fragments that inflate to far more than any pasted source.
+const MAX_INFLATED_LEN: usize = 16 * 1024 * 1024;
+
+// URL fragment carrying the name, source and options of a session, without
+// its outputs, which are produced again when the link is opened.
+pub fn encode(bundle: &SessionBundle) -> String {
+    let bundle = SessionBundle {
+        version: bundle.version.clone(),
+        name: bundle.name.clone(),
+        source: bundle.source.clone(),
+        options: bundle.options.clone(),
+        ..Default::default()
+    };
+    format!(
+        "{}{}",
+        FRAGMENT_PREFIX,
+        URL_SAFE_NO_PAD.encode(compress_to_vec(
+            bundle.to_string().as_bytes(),
+            COMPRESSION_LEVEL
+        ))
+    )
+}
+
+// None when the fragment is not a permalink, so other anchors are left alone.
+pub fn decode(fragment: &str) -> Option<Result<SessionBundle, String>> {
+    let encoded = fragment
+        .strip_prefix('#')
+        .unwrap_or(fragment)
+        .strip_prefix(FRAGMENT_PREFIX)?;
+    Some(
+        URL_SAFE_NO_PAD
+            .decode(encoded.trim())
+            .map_err(|err| format!("The link is damaged: {}", err))
+            .and_then(|compressed| {
+                decompress_to_vec_with_limit(&compressed, MAX_INFLATED_LEN)
+                    .map_err(|err| format!("The link is damaged: {}", err))
+            })
+            .and_then(|json| {
+                String::from_utf8(json).map_err(|_| "The link is damaged.".to_string())
+            })
+            .and_then(|json| json.parse::<SessionBundle>()),
+    )
+}
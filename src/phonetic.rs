@@ -0,0 +1,291 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, ContentLine},
+    findings::{Code, Finding},
+};
+
+// Vendor properties carrying furigana and other name readings.
+const VENDOR_PHONETIC: &[&str] = &[
+    "X-PHONETIC-FIRST-NAME",
+    "X-PHONETIC-MIDDLE-NAME",
+    "X-PHONETIC-LAST-NAME",
+    "X-PHONETIC-ORG",
+];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PhoneticData {
+    pub readings: Vec<PhoneticReading>,
+    pub sounds: Vec<NameSound>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PhoneticReading {
+    // vCard property or JSON pointer the reading was found at.
+    pub property: String,
+    pub written: Option<String>,
+    pub reading: String,
+    pub system: Option<String>,
+    pub script: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NameSound {
+    pub property: String,
+    // Set when the browser can play the sound directly.
+    pub playable_uri: Option<String>,
+    pub value: String,
+}
+
+impl PhoneticData {
+    pub fn extract(text: &str) -> Self {
+        let text = text.trim_start();
+        if text.starts_with('{') || text.starts_with('[') {
+            serde_json::from_str::<Value>(text)
+                .map(|value| Self::from_jscontact(&value))
+                .unwrap_or_default()
+        } else {
+            Self::from_vcard(&contentline::parse(text))
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty() && self.sounds.is_empty()
+    }
+
+    fn from_vcard(lines: &[ContentLine]) -> Self {
+        let mut data = PhoneticData::default();
+        for line in lines {
+            if line.name == "SOUND" {
+                let value = line.text();
+                let playable_uri = if value.starts_with("data:")
+                    || value.starts_with("https:")
+                    || value.starts_with("http:")
+                {
+                    Some(value.clone())
+                } else if line.param("ENCODING").is_some_and(|encoding| {
+                    encoding.eq_ignore_ascii_case("b") || encoding.eq_ignore_ascii_case("base64")
+                }) {
+                    let subtype = line
+                        .param_values("TYPE")
+                        .into_iter()
+                        .next()
+                        .unwrap_or_else(|| "basic".to_string());
+                    Some(format!("data:audio/{};base64,{}", subtype, value))
+                } else {
+                    None
+                };
+                data.sounds.push(NameSound {
+                    property: format!("SOUND (line {})", line.line),
+                    playable_uri,
+                    value,
+                });
+            } else if let Some(system) = line.param("PHONETIC") {
+                let written = lines
+                    .iter()
+                    .find(|other| {
+                        other.name == line.name
+                            && other.param("PHONETIC").is_none()
+                            && other.param("ALTID").is_some()
+                            && other.param("ALTID") == line.param("ALTID")
+                    })
+                    .map(|other| other.text());
+                for (idx, reading) in contentline::split_unescaped(&line.value, ';')
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, reading)| !reading.is_empty())
+                {
+                    data.readings.push(PhoneticReading {
+                        property: format!("{} field {} (line {})", line.name, idx + 1, line.line),
+                        written: written.as_ref().and_then(|written| {
+                            contentline::split_unescaped(written, ';')
+                                .get(idx)
+                                .map(|value| contentline::unescape(value))
+                        }),
+                        reading: contentline::unescape(&reading),
+                        system: Some(system.to_string()),
+                        script: line.param("SCRIPT").map(str::to_string),
+                    });
+                }
+            } else if VENDOR_PHONETIC.contains(&line.name.as_str()) {
+                data.readings.push(PhoneticReading {
+                    property: format!("{} (line {})", line.name, line.line),
+                    written: None,
+                    reading: line.text(),
+                    system: None,
+                    script: None,
+                });
+            }
+        }
+        data
+    }
+
+    fn from_jscontact(value: &Value) -> Self {
+        let mut data = PhoneticData::default();
+        match value {
+            Value::Array(cards) => {
+                for (idx, card) in cards.iter().enumerate() {
+                    jscontact_readings(card, &format!("/{}", idx), &mut data);
+                }
+            }
+            card => jscontact_readings(card, "", &mut data),
+        }
+        data
+    }
+}
+
+fn jscontact_readings(card: &Value, path: &str, data: &mut PhoneticData) {
+    let readings_for = |object: &Value, path: &str, data: &mut PhoneticData| {
+        let system = object.get("phoneticSystem").and_then(Value::as_str);
+        let script = object.get("phoneticScript").and_then(Value::as_str);
+        for (idx, component) in object
+            .get("components")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            if let Some(reading) = component.get("phonetic").and_then(Value::as_str) {
+                data.readings.push(PhoneticReading {
+                    property: format!("{}/components/{}/phonetic", path, idx),
+                    written: component
+                        .get("value")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    reading: reading.to_string(),
+                    system: system.map(str::to_string),
+                    script: script.map(str::to_string),
+                });
+            }
+        }
+    };
+
+    if let Some(name) = card.get("name") {
+        readings_for(name, &format!("{}/name", path), data);
+    }
+    for (id, address) in card
+        .get("addresses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        readings_for(address, &format!("{}/addresses/{}", path, id), data);
+    }
+
+    // Localized readings are patches such as "name/components/0/phonetic".
+    for (lang, patch) in card
+        .get("localizations")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let Some(patch) = patch.as_object() else {
+            continue;
+        };
+        let system = patch
+            .get("name/phoneticSystem")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let script = patch
+            .get("name/phoneticScript")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        for (pointer, reading) in patch {
+            let Some(reading) = reading.as_str().filter(|_| pointer.ends_with("/phonetic")) else {
+                continue;
+            };
+            data.readings.push(PhoneticReading {
+                property: format!("{}/localizations/{}/{}", path, lang, pointer),
+                written: card
+                    .pointer(&format!("/{}", pointer.trim_end_matches("/phonetic")))
+                    .and_then(|component| component.get("value"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                reading: reading.to_string(),
+                system: system.clone(),
+                script: script.clone(),
+            });
+        }
+    }
+
+    for (id, media) in card
+        .get("media")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        if media.get("kind").and_then(Value::as_str) == Some("sound") {
+            if let Some(uri) = media.get("uri").and_then(Value::as_str) {
+                data.sounds.push(NameSound {
+                    property: format!("{}/media/{}", path, id),
+                    playable_uri: (uri.starts_with("data:")
+                        || uri.starts_with("https:")
+                        || uri.starts_with("http:"))
+                    .then(|| uri.to_string()),
+                    value: uri.to_string(),
+                });
+            }
+        }
+    }
+
+    // Properties without a JSContact equivalent are preserved in vCardProps.
+    for prop in card
+        .get("vCardProps")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+    {
+        if let (Some(name), Some(reading)) = (
+            prop.first().and_then(Value::as_str),
+            prop.get(3).and_then(Value::as_str),
+        ) {
+            let name = name.to_ascii_uppercase();
+            if VENDOR_PHONETIC.contains(&name.as_str()) {
+                data.readings.push(PhoneticReading {
+                    property: format!("{}/vCardProps ({})", path, name),
+                    written: None,
+                    reading: reading.to_string(),
+                    system: None,
+                    script: None,
+                });
+            }
+        }
+    }
+}
+
+// Warns when a conversion step ends up with fewer readings or sounds than the
+// source had.
+pub fn check_conversion(source: &str, converted: &str, roundtrip: &str) -> Vec<Finding> {
+    let source = PhoneticData::extract(source);
+    if source.is_empty() {
+        return vec![];
+    }
+
+    let mut findings = Vec::new();
+    for (stage, text) in [("conversion", converted), ("round trip", roundtrip)] {
+        let output = PhoneticData::extract(text);
+        for (what, before, after) in [
+            (
+                "phonetic readings",
+                source.readings.len(),
+                output.readings.len(),
+            ),
+            ("name sounds", source.sounds.len(), output.sounds.len()),
+        ] {
+            if after < before {
+                findings.push(Finding::new(
+                    Code::PhoneticDataDropped,
+                    format!("The {} kept {} of {} {}.", stage, after, before, what),
+                ));
+            }
+        }
+    }
+    findings
+}
@@ -0,0 +1,1134 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{Duration, NaiveDateTime, NaiveTime, TimeZone};
+use serde_json::{Map, Value};
+
+use crate::{
+    contentline, location,
+    occurrences::EntryKind,
+    rrule_text::{self, ordinal},
+};
+
+// What a typical phone or webmail address book shows for a contact.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContactPreview {
+    pub name: String,
+    // Parts of the structured name, labeled by kind.
+    pub name_components: Vec<LabeledValue>,
+    pub initials: String,
+    // URI an <img> can load, with inline base64 photos made data: URIs.
+    pub photo: Option<String>,
+    pub job: Option<String>,
+    // Preferred entries first.
+    pub phones: Vec<LabeledValue>,
+    pub emails: Vec<LabeledValue>,
+    pub addresses: Vec<AddressPreview>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LabeledValue {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressPreview {
+    pub label: String,
+    // The address as it is printed on an envelope, one line per entry.
+    pub lines: Vec<String>,
+    // "latitude, longitude" from GEO.
+    pub coordinates: Option<String>,
+    pub maps: MapLinks,
+    pub mapping: Mapping,
+}
+
+// The same place on OpenStreetMap and Google Maps, pinned when its
+// coordinates are known and searched for by address otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MapLinks {
+    pub openstreetmap: String,
+    pub google: String,
+}
+
+impl MapLinks {
+    pub fn at(latitude: &str, longitude: &str) -> Self {
+        MapLinks {
+            openstreetmap: format!(
+                "https://www.openstreetmap.org/?mlat={}&mlon={}#map=17/{}/{}",
+                latitude, longitude, latitude, longitude
+            ),
+            google: format!(
+                "https://www.google.com/maps/search/?api=1&query={},{}",
+                latitude, longitude
+            ),
+        }
+    }
+
+    pub fn search(query: &str) -> Self {
+        MapLinks {
+            openstreetmap: format!(
+                "https://www.openstreetmap.org/search?query={}",
+                url_encode(query)
+            ),
+            google: format!(
+                "https://www.google.com/maps/search/?api=1&query={}",
+                url_encode(query)
+            ),
+        }
+    }
+}
+
+// A converted JSON entry and the path it was found at, such as
+// addresses/k1, to show what an ADR or LOCATION became.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mapping {
+    pub path: String,
+    pub json: String,
+}
+
+impl Mapping {
+    fn new(property: &str, id: &str, entry: &Map<String, Value>) -> Self {
+        Mapping {
+            path: format!("{}/{}", property, id),
+            json: serde_json::to_string_pretty(entry).unwrap_or_default(),
+        }
+    }
+}
+
+impl ContactPreview {
+    pub fn from_jscontact(source: &str) -> Option<Self> {
+        let card = serde_json::from_str::<Value>(source).ok()?;
+        let card = card.as_object()?;
+        if card.get("@type").and_then(Value::as_str) != Some("Card") {
+            return None;
+        }
+
+        let emails = by_pref(card, "emails")
+            .into_iter()
+            .filter_map(|email| {
+                Some(LabeledValue {
+                    label: context_label(email, "Email"),
+                    value: email.get("address")?.as_str()?.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let organization = primary(card, "organizations")
+            .and_then(|org| org.get("name"))
+            .and_then(Value::as_str);
+        let title = primary(card, "titles")
+            .and_then(|title| title.get("name"))
+            .and_then(Value::as_str);
+
+        // Clients fall back to the organization and then the email address
+        // when a card has no name.
+        let card_name = display_name(card);
+        let has_name = card_name.is_some();
+        let name = card_name
+            .or_else(|| organization.map(str::to_string))
+            .or_else(|| emails.first().map(|email| email.value.clone()))
+            .unwrap_or_else(|| "No name".to_string());
+
+        Some(ContactPreview {
+            initials: name_initials(card).unwrap_or_else(|| initials(&name)),
+            photo: card
+                .get("media")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|media| media.values())
+                .filter_map(Value::as_object)
+                .filter(|media| media.get("kind").and_then(Value::as_str) == Some("photo"))
+                .min_by_key(|media| pref(media))
+                .and_then(photo_source),
+            name_components: name_components(card),
+            job: match (title, organization) {
+                (Some(title), Some(org)) if has_name => Some(format!("{} at {}", title, org)),
+                (Some(title), _) => Some(title.to_string()),
+                (None, Some(org)) if has_name => Some(org.to_string()),
+                _ => None,
+            },
+            phones: by_pref(card, "phones")
+                .into_iter()
+                .filter_map(|phone| {
+                    let features = phone.get("features").and_then(Value::as_object);
+                    let label = if features.is_some_and(|features| features.contains_key("mobile"))
+                    {
+                        "Mobile".to_string()
+                    } else if features.is_some_and(|features| features.contains_key("fax")) {
+                        "Fax".to_string()
+                    } else {
+                        context_label(phone, "Phone")
+                    };
+                    Some(LabeledValue {
+                        label,
+                        value: phone.get("number")?.as_str()?.to_string(),
+                    })
+                })
+                .collect(),
+            emails,
+            addresses: addresses(card),
+            name,
+        })
+    }
+}
+
+fn display_name(card: &Map<String, Value>) -> Option<String> {
+    let name = card.get("name")?.as_object()?;
+    if let Some(full) = name.get("full").and_then(Value::as_str) {
+        return Some(full.to_string());
+    }
+
+    let components = name.get("components")?.as_array()?;
+    let part = |kind: &str| {
+        components
+            .iter()
+            .filter(|component| component.get("kind").and_then(Value::as_str) == Some(kind))
+            .filter_map(|component| component.get("value").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+    };
+    let parts = [
+        "title",
+        "given",
+        "given2",
+        "surname",
+        "surname2",
+        "credential",
+    ]
+    .into_iter()
+    .flat_map(part)
+    .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+fn name_components(card: &Map<String, Value>) -> Vec<LabeledValue> {
+    card.get("name")
+        .and_then(|name| name.get("components"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|component| {
+            let label = match component.get("kind")?.as_str()? {
+                "title" => "Prefix",
+                "given" => "Given name",
+                "given2" => "Middle name",
+                "surname" => "Family name",
+                "surname2" => "Second family name",
+                "credential" => "Credential",
+                "generation" => "Generation",
+                // Separators only matter to ordered names.
+                _ => return None,
+            };
+            Some(LabeledValue {
+                label: label.to_string(),
+                value: component.get("value")?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+// vCard 3 photos with ENCODING=b can come through as bare base64 instead of
+// a data: URI.
+fn photo_source(media: &Map<String, Value>) -> Option<String> {
+    let uri = media.get("uri")?.as_str()?.trim();
+    let is_base64 = |text: &str| {
+        !text.is_empty()
+            && text.bytes().all(|byte| {
+                byte.is_ascii_alphanumeric()
+                    || matches!(byte, b'+' | b'/' | b'=' | b'\r' | b'\n' | b' ')
+            })
+    };
+    if uri.contains(':') || !is_base64(uri) {
+        return Some(uri.to_string());
+    }
+    let media_type = media
+        .get("mediaType")
+        .and_then(Value::as_str)
+        .unwrap_or("image/jpeg");
+    Some(format!(
+        "data:{};base64,{}",
+        media_type,
+        uri.split_ascii_whitespace().collect::<String>()
+    ))
+}
+
+fn name_initials(card: &Map<String, Value>) -> Option<String> {
+    let components = card.get("name")?.get("components")?.as_array()?;
+    let initial = |kind: &str| {
+        components
+            .iter()
+            .find(|component| component.get("kind").and_then(Value::as_str) == Some(kind))
+            .and_then(|component| component.get("value")?.as_str()?.chars().next())
+    };
+    let initials = [initial("given"), initial("surname")]
+        .into_iter()
+        .flatten()
+        .flat_map(char::to_uppercase)
+        .collect::<String>();
+    (!initials.is_empty()).then_some(initials)
+}
+
+// Honorifics such as "Dr." or "Esq." are skipped.
+fn initials(name: &str) -> String {
+    let words = name
+        .split_whitespace()
+        .filter(|word| {
+            !word.ends_with('.') && word.chars().next().is_some_and(char::is_alphanumeric)
+        })
+        .collect::<Vec<_>>();
+    match words.as_slice() {
+        [] => "?".to_string(),
+        [word] => word.chars().take(1).flat_map(char::to_uppercase).collect(),
+        [first, .., last] => [first, last]
+            .into_iter()
+            .filter_map(|word| word.chars().next())
+            .flat_map(char::to_uppercase)
+            .collect(),
+    }
+}
+
+// Entries without a "pref" sort after every entry that has one.
+fn pref(entry: &Map<String, Value>) -> u64 {
+    entry
+        .get("pref")
+        .and_then(Value::as_u64)
+        .unwrap_or(u64::MAX)
+}
+
+fn primary<'x>(card: &'x Map<String, Value>, property: &str) -> Option<&'x Map<String, Value>> {
+    card.get(property)?
+        .as_object()?
+        .values()
+        .filter_map(Value::as_object)
+        .min_by_key(|entry| pref(entry))
+}
+
+fn by_pref<'x>(card: &'x Map<String, Value>, property: &str) -> Vec<&'x Map<String, Value>> {
+    let mut entries = card
+        .get(property)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|entries| entries.values())
+        .filter_map(Value::as_object)
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| pref(entry));
+    entries
+}
+
+fn context_label(entry: &Map<String, Value>, default: &str) -> String {
+    let contexts = entry.get("contexts").and_then(Value::as_object);
+    if contexts.is_some_and(|contexts| contexts.contains_key("work")) {
+        "Work".to_string()
+    } else if contexts.is_some_and(|contexts| contexts.contains_key("private")) {
+        "Home".to_string()
+    } else {
+        default.to_string()
+    }
+}
+
+// Addresses with their ids, which by_pref does not keep, for the mapping.
+fn addresses(card: &Map<String, Value>) -> Vec<AddressPreview> {
+    let mut addresses = card
+        .get("addresses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(id, address)| Some((id, address.as_object()?)))
+        .collect::<Vec<_>>();
+    addresses.sort_by_key(|(_, address)| pref(address));
+    addresses
+        .into_iter()
+        .filter_map(|(id, address)| {
+            let text = format_address(address);
+            let coordinates = address
+                .get("coordinates")
+                .and_then(Value::as_str)
+                .and_then(parse_geo);
+            let maps = match (&coordinates, &text) {
+                (Some((latitude, longitude)), _) => MapLinks::at(latitude, longitude),
+                (None, Some(text)) => MapLinks::search(&text.replace('\n', ", ")),
+                (None, None) => return None,
+            };
+            Some(AddressPreview {
+                label: context_label(address, "Address"),
+                lines: text
+                    .map(|text| text.lines().map(str::to_string).collect())
+                    .unwrap_or_default(),
+                coordinates: coordinates
+                    .map(|(latitude, longitude)| format!("{}, {}", latitude, longitude)),
+                maps,
+                mapping: Mapping::new("addresses", id, address),
+            })
+        })
+        .collect()
+}
+
+// Latitude and longitude of a geo: URI, without its uncertainty or other
+// parameters.
+fn parse_geo(uri: &str) -> Option<(String, String)> {
+    let (latitude, longitude) = uri
+        .trim()
+        .strip_prefix("geo:")?
+        .split(';')
+        .next()?
+        .split_once(',')?;
+    let longitude = longitude.split(',').next()?;
+    (latitude.trim().parse::<f64>().is_ok() && longitude.trim().parse::<f64>().is_ok())
+        .then(|| (latitude.trim().to_string(), longitude.trim().to_string()))
+}
+
+fn format_address(address: &Map<String, Value>) -> Option<String> {
+    if let Some(full) = address.get("full").and_then(Value::as_str) {
+        return Some(full.to_string());
+    }
+
+    let components = address.get("components")?.as_array()?;
+    let values = components
+        .iter()
+        .filter_map(|component| {
+            Some((
+                component.get("kind")?.as_str()?,
+                component.get("value")?.as_str()?,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    // Ordered components already carry their own separators.
+    if address.get("isOrdered").and_then(Value::as_bool) == Some(true) {
+        let text = values.iter().map(|(_, value)| *value).collect::<String>();
+        return (!text.trim().is_empty()).then_some(text);
+    }
+
+    let join = |kinds: &[&str], separator: &str| {
+        kinds
+            .iter()
+            .flat_map(|kind| {
+                values
+                    .iter()
+                    .filter(move |(item, _)| item == kind)
+                    .map(|(_, value)| *value)
+            })
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+    let lines = [
+        join(
+            &[
+                "number",
+                "name",
+                "block",
+                "building",
+                "floor",
+                "apartment",
+                "room",
+            ],
+            " ",
+        ),
+        [
+            join(&["district", "locality"], " "),
+            join(&["region", "postcode"], " "),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(", "),
+        join(&["country"], " "),
+    ]
+    .into_iter()
+    .filter(|line| !line.is_empty())
+    .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+// What an email client shows for an invitation to the first event found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventPreview {
+    pub title: String,
+    pub when: String,
+    // The same time in the event's own zone, when it differs from the viewer's.
+    pub event_zone_time: Option<String>,
+    pub recurrence: Option<String>,
+    pub location: Option<EventLocation>,
+    // Alerts triggered by arriving at or leaving a place.
+    pub location_alerts: Vec<String>,
+    pub join_url: Option<String>,
+    pub attendees: Vec<Attendee>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventLocation {
+    pub name: String,
+    // The address when it is given apart from the name, one line per entry.
+    pub address: Vec<String>,
+    // "latitude, longitude" from GEO or a structured location.
+    pub coordinates: Option<String>,
+    pub maps: MapLinks,
+    // Geofence radius in meters.
+    pub radius: Option<u32>,
+    pub mapping: Mapping,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attendee {
+    pub name: String,
+    pub status: AttendeeStatus,
+    pub organizer: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttendeeStatus {
+    Accepted,
+    Declined,
+    Tentative,
+    Pending,
+}
+
+impl EventPreview {
+    pub fn from_jscalendar(source: &str, viewer_tz: Option<chrono_tz::Tz>) -> Option<Self> {
+        let value = serde_json::from_str::<Value>(source).ok()?;
+        let event = first_event(&value)?;
+
+        let start = event
+            .get("start")
+            .and_then(Value::as_str)
+            .and_then(|start| NaiveDateTime::parse_from_str(start, "%Y-%m-%dT%H:%M:%S").ok())?;
+        let duration = event
+            .get("duration")
+            .and_then(Value::as_str)
+            .and_then(parse_duration)
+            .unwrap_or_default();
+        let all_day = event.get("showWithoutTime").and_then(Value::as_bool) == Some(true);
+        let event_tz = event
+            .get("timeZone")
+            .and_then(Value::as_str)
+            .and_then(|tz| tz.parse::<chrono_tz::Tz>().ok());
+
+        // All-day and floating events show the same wall-clock time everywhere.
+        let (when, event_zone_time) = match (event_tz.filter(|_| !all_day), viewer_tz) {
+            (Some(event_tz), Some(viewer_tz)) if event_tz != viewer_tz => {
+                match event_tz.from_local_datetime(&start).earliest() {
+                    Some(instant) => {
+                        let local = instant.with_timezone(&viewer_tz).naive_local();
+                        (
+                            format_range(local, local + duration, false, Some(viewer_tz.name())),
+                            Some(format_range(
+                                start,
+                                start + duration,
+                                false,
+                                Some(event_tz.name()),
+                            )),
+                        )
+                    }
+                    None => (
+                        format_range(start, start + duration, false, Some(event_tz.name())),
+                        None,
+                    ),
+                }
+            }
+            (Some(event_tz), _) => (
+                format_range(start, start + duration, false, Some(event_tz.name())),
+                None,
+            ),
+            (None, _) => (format_range(start, start + duration, all_day, None), None),
+        };
+
+        Some(EventPreview {
+            title: title(event),
+            when,
+            event_zone_time,
+            recurrence: recurrence_text(event),
+            location: main_location(event),
+            location_alerts: location_alerts(event),
+            join_url: event
+                .get("virtualLocations")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|locations| locations.values())
+                .find_map(|location| location.get("uri").and_then(Value::as_str))
+                .map(str::to_string),
+            attendees: attendees(event),
+        })
+    }
+}
+
+// The details a calendar client shows when an event, task or journal entry is
+// opened.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventSummary {
+    pub kind: EntryKind,
+    pub title: String,
+    pub when: Option<String>,
+    // Status and completion of tasks.
+    pub progress: Option<String>,
+    pub recurrence: Option<String>,
+    pub organizer: Option<String>,
+    pub attendees: Vec<Attendee>,
+    pub location: Option<EventLocation>,
+    pub description: Option<String>,
+    pub alarms: Vec<String>,
+}
+
+impl EventSummary {
+    // One summary for every event and task of a JSCalendar object or group.
+    pub fn from_jscalendar(source: &str) -> Vec<Self> {
+        let Ok(value) = serde_json::from_str::<Value>(source) else {
+            return vec![];
+        };
+        let mut entries = Vec::new();
+        collect_entries(&value, &mut entries);
+        entries
+            .into_iter()
+            .map(|(kind, entry)| {
+                let attendees = attendees(entry);
+                EventSummary {
+                    kind,
+                    title: title(entry),
+                    when: entry_when(entry, kind == EntryKind::Task),
+                    progress: (kind == EntryKind::Task)
+                        .then(|| task_progress(entry))
+                        .flatten(),
+                    recurrence: recurrence_text(entry),
+                    organizer: attendees
+                        .iter()
+                        .find(|attendee| attendee.organizer)
+                        .map(|attendee| attendee.name.clone())
+                        .or_else(|| {
+                            entry
+                                .get("organizerCalendarAddress")
+                                .and_then(Value::as_str)
+                                .map(|address| address.trim_start_matches("mailto:").to_string())
+                        }),
+                    attendees,
+                    location: main_location(entry),
+                    description: entry
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::trim)
+                        .filter(|description| !description.is_empty())
+                        .map(str::to_string),
+                    alarms: entry
+                        .get("alerts")
+                        .and_then(Value::as_object)
+                        .into_iter()
+                        .flat_map(|alerts| alerts.values())
+                        .filter(|alert| alert.get(location::PROXIMITY_PROPERTY).is_none())
+                        .filter_map(alert_text)
+                        .chain(location_alerts(entry))
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    // JSCalendar has no journal entries, so they are read from the iCalendar.
+    pub fn journals_from_icalendar(source: &str) -> Vec<Self> {
+        contentline::components(source)
+            .iter()
+            .filter(|component| component.name() == "VJOURNAL")
+            .map(|component| {
+                let property = |name: &str| {
+                    component
+                        .properties
+                        .iter()
+                        .find(|property| property.name == name)
+                };
+                let start = property("DTSTART").and_then(|line| {
+                    let value = line.value.trim();
+                    match contentline::parse_datetime(value) {
+                        Some(start) => Some((start, false, line)),
+                        None => contentline::parse_date(value)
+                            .map(|date| (date.and_time(NaiveTime::MIN), true, line)),
+                    }
+                });
+                EventSummary {
+                    kind: EntryKind::Journal,
+                    title: property("SUMMARY")
+                        .map(|line| line.text())
+                        .filter(|title| !title.is_empty())
+                        .unwrap_or_else(|| "(No title)".to_string()),
+                    // Journal entries have a date or an instant, not a span.
+                    when: start.map(|(start, all_day, line)| {
+                        let tz_name = if line.value.trim().ends_with('Z') {
+                            Some("UTC")
+                        } else {
+                            line.param("TZID")
+                        };
+                        match tz_name {
+                            _ if all_day => start.format("%A %b %-d, %Y").to_string(),
+                            Some(tz_name) => {
+                                format!("{} ({})", start.format("%A %b %-d, %Y %-I:%M%P"), tz_name)
+                            }
+                            None => start.format("%A %b %-d, %Y %-I:%M%P").to_string(),
+                        }
+                    }),
+                    progress: None,
+                    recurrence: property("RRULE").map(|line| {
+                        rrule_text::explain_rrule(&line.value, start.map(|(start, _, _)| start))
+                    }),
+                    organizer: property("ORGANIZER").map(|line| {
+                        line.param("CN").map(str::to_string).unwrap_or_else(|| {
+                            line.value.trim().trim_start_matches("mailto:").to_string()
+                        })
+                    }),
+                    attendees: vec![],
+                    location: None,
+                    description: property("DESCRIPTION")
+                        .map(|line| line.text().trim().to_string())
+                        .filter(|description| !description.is_empty()),
+                    alarms: vec![],
+                }
+            })
+            .collect()
+    }
+}
+
+// "Completed", "In progress, 40%" or "Needs action".
+fn task_progress(entry: &Map<String, Value>) -> Option<String> {
+    let percent = entry
+        .get("percentComplete")
+        .and_then(Value::as_u64)
+        .filter(|percent| *percent > 0);
+    let status = match entry.get("progress").and_then(Value::as_str) {
+        Some("completed") => "Completed",
+        Some("in-process") => "In progress",
+        Some("failed") => "Failed",
+        Some("cancelled") => "Cancelled",
+        Some("needs-action") => "Needs action",
+        _ => return percent.map(|percent| format!("{}% done", percent)),
+    };
+    Some(match percent {
+        Some(percent) if status == "In progress" => format!("{}, {}%", status, percent),
+        _ => status.to_string(),
+    })
+}
+
+fn collect_entries<'x>(value: &'x Value, entries: &mut Vec<(EntryKind, &'x Map<String, Value>)>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    match object.get("@type").and_then(Value::as_str) {
+        Some("Event") => entries.push((EntryKind::Event, object)),
+        Some("Task") => entries.push((EntryKind::Task, object)),
+        Some("Group") => {
+            for entry in object
+                .get("entries")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                collect_entries(entry, entries);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn title(entry: &Map<String, Value>) -> String {
+    entry
+        .get("title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("(No title)")
+        .to_string()
+}
+
+fn attendees(entry: &Map<String, Value>) -> Vec<Attendee> {
+    let organizer_address = entry
+        .get("organizerCalendarAddress")
+        .and_then(Value::as_str);
+    entry
+        .get("participants")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|participants| participants.values())
+        .filter_map(Value::as_object)
+        .filter_map(|participant| {
+            let address = participant.get("calendarAddress").and_then(Value::as_str);
+            let name = participant
+                .get("name")
+                .and_then(Value::as_str)
+                .or_else(|| participant.get("email").and_then(Value::as_str))
+                .or_else(|| address.map(|address| address.trim_start_matches("mailto:")))?
+                .to_string();
+            let roles = participant.get("roles").and_then(Value::as_object);
+            Some(Attendee {
+                name,
+                status: match participant
+                    .get("participationStatus")
+                    .and_then(Value::as_str)
+                {
+                    Some("accepted") => AttendeeStatus::Accepted,
+                    Some("declined") => AttendeeStatus::Declined,
+                    Some("tentative") => AttendeeStatus::Tentative,
+                    _ => AttendeeStatus::Pending,
+                },
+                organizer: roles.is_some_and(|roles| roles.contains_key("owner"))
+                    || (address.is_some() && address == organizer_address),
+            })
+        })
+        .collect()
+}
+
+// Times are shown in the entry's own zone. Tasks may only have a due date.
+fn entry_when(entry: &Map<String, Value>, task: bool) -> Option<String> {
+    let date_time = |key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok())
+    };
+    let all_day = entry.get("showWithoutTime").and_then(Value::as_bool) == Some(true);
+    let tz_name = entry
+        .get("timeZone")
+        .and_then(Value::as_str)
+        .filter(|_| !all_day);
+    let start = date_time("start");
+    if task {
+        let due = date_time("due").map(|due| {
+            let due = if all_day {
+                due.format("%A %b %-d, %Y").to_string()
+            } else {
+                due.format("%A %b %-d, %Y %-I:%M%P").to_string()
+            };
+            match tz_name {
+                Some(tz_name) => format!("Due {} ({})", due, tz_name),
+                None => format!("Due {}", due),
+            }
+        });
+        return match (start, due) {
+            (Some(start), Some(due)) => Some(format!(
+                "Starts {} ⋅ {}",
+                start.format("%a %b %-d, %Y"),
+                due
+            )),
+            (Some(start), None) => Some(format_range(start, start, all_day, tz_name)),
+            (None, due) => due,
+        };
+    }
+    let start = start?;
+    let duration = entry
+        .get("duration")
+        .and_then(Value::as_str)
+        .and_then(parse_duration)
+        .unwrap_or_default();
+    Some(format_range(start, start + duration, all_day, tz_name))
+}
+
+// Describes when an alert goes off and, unless it only shows a notification,
+// how it is delivered.
+fn alert_text(alert: &Value) -> Option<String> {
+    let trigger = alert.get("trigger")?;
+    let when = match trigger.get("@type").and_then(Value::as_str) {
+        Some("AbsoluteTrigger") => format!(
+            "On {}",
+            trigger
+                .get("when")
+                .and_then(Value::as_str)
+                .and_then(|when| chrono::DateTime::parse_from_rfc3339(when).ok())?
+                .format("%b %-d, %Y %-I:%M%P UTC")
+        ),
+        Some("OffsetTrigger") | None => {
+            let offset = trigger.get("offset")?.as_str()?;
+            let anchor = match trigger.get("relativeTo").and_then(Value::as_str) {
+                Some("end") => "end",
+                _ => "start",
+            };
+            let (before, duration) = match offset.strip_prefix('-') {
+                Some(offset) => (true, parse_duration(offset)?),
+                None => (false, parse_duration(offset.trim_start_matches('+'))?),
+            };
+            if duration.is_zero() {
+                format!("At {}", anchor)
+            } else {
+                format!(
+                    "{} {} {}",
+                    duration_text(duration),
+                    if before { "before" } else { "after" },
+                    anchor
+                )
+            }
+        }
+        Some(_) => return None,
+    };
+    Some(match alert.get("action").and_then(Value::as_str) {
+        Some("email") => format!("{}, by email", when),
+        _ => when,
+    })
+}
+
+pub fn duration_text(duration: Duration) -> String {
+    let mut seconds = duration.num_seconds();
+    let mut parts = Vec::new();
+    for (unit, length) in [
+        ("week", 604_800),
+        ("day", 86_400),
+        ("hour", 3_600),
+        ("minute", 60),
+        ("second", 1),
+    ] {
+        let count = seconds / length;
+        seconds %= length;
+        match count {
+            0 => {}
+            1 => parts.push(format!("1 {}", unit)),
+            count => parts.push(format!("{} {}s", count, unit)),
+        }
+    }
+    parts.join(" ")
+}
+
+fn first_event(value: &Value) -> Option<&Map<String, Value>> {
+    let object = value.as_object()?;
+    match object.get("@type").and_then(Value::as_str)? {
+        "Event" => Some(object),
+        "Group" => object
+            .get("entries")?
+            .as_array()?
+            .iter()
+            .find_map(first_event),
+        _ => None,
+    }
+}
+
+fn format_range(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    all_day: bool,
+    tz_name: Option<&str>,
+) -> String {
+    let mut text = if all_day {
+        // All-day durations end at midnight of the following day.
+        let last_day = (end - Duration::days(1)).date().max(start.date());
+        if last_day == start.date() {
+            start.format("%A %b %-d, %Y").to_string()
+        } else {
+            format!(
+                "{} – {}",
+                start.format("%a %b %-d"),
+                last_day.format("%a %b %-d, %Y")
+            )
+        }
+    } else if start.date() == end.date() {
+        format!(
+            "{} ⋅ {} – {}",
+            start.format("%A %b %-d, %Y"),
+            start.format("%-I:%M%P"),
+            end.format("%-I:%M%P")
+        )
+    } else {
+        format!(
+            "{} – {}",
+            start.format("%a %b %-d, %Y %-I:%M%P"),
+            end.format("%a %b %-d, %Y %-I:%M%P")
+        )
+    };
+    if let Some(tz_name) = tz_name {
+        text = format!("{} ({})", text, tz_name);
+    }
+    text
+}
+
+// Parses the subset of ISO 8601 durations used by JSCalendar, which is also
+// the one of iCalendar.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.strip_prefix('P')?;
+    let (date, time) = value.split_once('T').unwrap_or((value, ""));
+    let mut total = Duration::zero();
+    for (part, units) in [
+        (date, &[('W', 604_800), ('D', 86_400)][..]),
+        (time, &[('H', 3_600), ('M', 60), ('S', 1)][..]),
+    ] {
+        let mut number = String::new();
+        for ch in part.chars() {
+            if ch.is_ascii_digit() {
+                number.push(ch);
+            } else {
+                let (_, seconds) = units.iter().find(|(unit, _)| *unit == ch)?;
+                total += Duration::seconds(number.parse::<i64>().ok()? * seconds);
+                number.clear();
+            }
+        }
+        if !number.is_empty() {
+            return None;
+        }
+    }
+    Some(total)
+}
+
+fn main_location(event: &Map<String, Value>) -> Option<EventLocation> {
+    let locations = event.get("locations")?.as_object()?;
+    let (id, location) = event
+        .get("mainLocationId")
+        .and_then(Value::as_str)
+        .and_then(|id| locations.get_key_value(id))
+        .or_else(|| locations.iter().next())?;
+    let location = location.as_object()?;
+    let name = location
+        .get("name")
+        .or_else(|| location.get("description"))
+        .and_then(Value::as_str)?
+        .to_string();
+    // Structured locations keep their address in the description.
+    let address = location
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|address| *address != name)
+        .map(|address| {
+            address
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let coordinates = location
+        .get("coordinates")
+        .and_then(Value::as_str)
+        .and_then(parse_geo);
+    let maps = match &coordinates {
+        Some((latitude, longitude)) => MapLinks::at(latitude, longitude),
+        None if !address.is_empty() => MapLinks::search(&address.join(", ")),
+        None => MapLinks::search(&name),
+    };
+    let radius = location
+        .get(location::RADIUS_PROPERTY)
+        .and_then(Value::as_f64)
+        .map(|radius| radius.round() as u32);
+    Some(EventLocation {
+        name,
+        address,
+        coordinates: coordinates
+            .map(|(latitude, longitude)| format!("{}, {}", latitude, longitude)),
+        maps,
+        radius,
+        mapping: Mapping::new("locations", id, location),
+    })
+}
+
+fn location_alerts(event: &Map<String, Value>) -> Vec<String> {
+    let locations = event.get("locations").and_then(Value::as_object);
+    event
+        .get("alerts")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|alerts| alerts.values())
+        .filter_map(|alert| {
+            let action = match alert.get(location::PROXIMITY_PROPERTY)?.as_str()? {
+                "arrive" => "arriving at",
+                "depart" => "leaving",
+                _ => return None,
+            };
+            let place = alert
+                .get(location::LOCATION_ID_PROPERTY)
+                .and_then(Value::as_str)
+                .and_then(|id| locations?.get(id));
+            let name = place
+                .and_then(|place| place.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or("a location");
+            Some(
+                match place
+                    .and_then(|place| place.get(location::RADIUS_PROPERTY))
+                    .and_then(Value::as_f64)
+                {
+                    Some(radius) => {
+                        format!("Alert when {} {} (within {:.0} m)", action, name, radius)
+                    }
+                    None => format!("Alert when {} {}", action, name),
+                },
+            )
+        })
+        .collect()
+}
+
+fn recurrence_text(event: &Map<String, Value>) -> Option<String> {
+    let rule = match event
+        .get("recurrenceRules")
+        .or_else(|| event.get("recurrenceRule"))?
+    {
+        Value::Array(rules) => rules.first()?.as_object()?,
+        Value::Object(rule) => rule,
+        _ => return None,
+    };
+    let frequency = rule.get("frequency")?.as_str()?;
+    let interval = rule.get("interval").and_then(Value::as_u64).unwrap_or(1);
+
+    let mut text = if interval > 1 {
+        let unit = match frequency {
+            "yearly" => "years",
+            "monthly" => "months",
+            "weekly" => "weeks",
+            "daily" => "days",
+            "hourly" => "hours",
+            "minutely" => "minutes",
+            _ => "seconds",
+        };
+        format!("Every {} {}", interval, unit)
+    } else {
+        match frequency {
+            "yearly" => "Annually",
+            "monthly" => "Monthly",
+            "weekly" => "Weekly",
+            "daily" => "Daily",
+            "hourly" => "Hourly",
+            "minutely" => "Every minute",
+            _ => "Every second",
+        }
+        .to_string()
+    };
+
+    let days = rule
+        .get("byDay")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|day| {
+            let name = match day.get("day")?.as_str()? {
+                "mo" => "Monday",
+                "tu" => "Tuesday",
+                "we" => "Wednesday",
+                "th" => "Thursday",
+                "fr" => "Friday",
+                "sa" => "Saturday",
+                "su" => "Sunday",
+                _ => return None,
+            };
+            Some(match day.get("nthOfPeriod").and_then(Value::as_i64) {
+                Some(-1) => format!("the last {}", name),
+                Some(nth) if nth < 0 => format!("the {} to last {}", ordinal(-nth), name),
+                Some(nth) => format!("the {} {}", ordinal(nth), name),
+                None => name.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    if !days.is_empty() {
+        text = format!("{} on {}", text, days.join(", "));
+    }
+
+    if let Some(count) = rule.get("count").and_then(Value::as_u64) {
+        text = format!("{}, {} times", text, count);
+    } else if let Some(until) = rule
+        .get("until")
+        .and_then(Value::as_str)
+        .and_then(|until| NaiveDateTime::parse_from_str(until, "%Y-%m-%dT%H:%M:%S").ok())
+    {
+        text = format!("{}, until {}", text, until.format("%b %-d, %Y"));
+    }
+
+    Some(text)
+}
+
+pub fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
@@ -0,0 +1,138 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{ICalendar, ICalendarComponent, ICalendarProperty, ICalendarValue},
+};
+use chrono::{DateTime, NaiveDateTime, TimeZone};
+
+// Bounds how far past DTSTART an instant can be and still get a definitive
+// answer, instants beyond it are reported as inconclusive.
+const PROBE_LIMIT: usize = 5000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProbeStatus {
+    // The occurrence covering the instant.
+    Occurring {
+        start: DateTime<chrono_tz::Tz>,
+        end: DateTime<chrono_tz::Tz>,
+    },
+    // Not happening, with the next occurrence if there is one.
+    NotOccurring {
+        next: Option<DateTime<chrono_tz::Tz>>,
+    },
+    // The expansion limit was reached before getting to the instant.
+    Inconclusive,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProbeResult {
+    pub summary: String,
+    pub uid: Option<String>,
+    pub recurring: bool,
+    pub status: ProbeStatus,
+}
+
+struct Series {
+    uid: Option<String>,
+    summary: String,
+    recurring: bool,
+    covering: Option<(NaiveDateTime, NaiveDateTime)>,
+    next: Option<NaiveDateTime>,
+    last_start: Option<NaiveDateTime>,
+}
+
+// Answers, for every event in the calendar, whether one of its occurrences
+// covers `instant`. Floating times are read in the instant's time zone.
+pub fn probe(icalendar: &ICalendar, instant: DateTime<chrono_tz::Tz>) -> Vec<ProbeResult> {
+    let tz = instant.timezone();
+    let at = instant.naive_utc();
+    let expanded = icalendar.expand_dates(Tz::Tz(tz), PROBE_LIMIT);
+    let truncated = expanded.events.len() >= PROBE_LIMIT;
+
+    // Overrides share the UID of their master, so results are grouped by UID.
+    let mut series: Vec<Series> = Vec::new();
+    for event in expanded.events {
+        let Some(component) = icalendar.components.get(event.comp_id as usize) else {
+            continue;
+        };
+        let Some(event) = event.try_into_date_time() else {
+            continue;
+        };
+        let uid = text_property(component, ICalendarProperty::Uid);
+        let index = match series
+            .iter()
+            .position(|item| uid.is_some() && item.uid == uid)
+        {
+            Some(index) => index,
+            None => {
+                series.push(Series {
+                    summary: text_property(component, ICalendarProperty::Summary)
+                        .unwrap_or_else(|| "Untitled event".to_string()),
+                    uid,
+                    recurring: false,
+                    covering: None,
+                    next: None,
+                    last_start: None,
+                });
+                series.len() - 1
+            }
+        };
+        let item = &mut series[index];
+        item.recurring |= component.entries.iter().any(|entry| {
+            matches!(
+                entry.name,
+                ICalendarProperty::Rrule | ICalendarProperty::Rdate
+            )
+        });
+
+        let (start, end) = (event.start.naive_utc(), event.end.naive_utc());
+        item.last_start = item.last_start.max(Some(start));
+        // Zero length occurrences cover the instant they start at.
+        if start <= at && (at < end || start == end && start == at) {
+            item.covering.get_or_insert((start, end));
+        } else if start > at && item.next.is_none_or(|next| start < next) {
+            item.next = Some(start);
+        }
+    }
+
+    series
+        .into_iter()
+        .map(|item| {
+            let local = |time: NaiveDateTime| tz.from_utc_datetime(&time);
+            let status = match (item.covering, item.next) {
+                (Some((start, end)), _) => ProbeStatus::Occurring {
+                    start: local(start),
+                    end: local(end),
+                },
+                (None, None) if truncated && item.last_start.is_some_and(|last| last < at) => {
+                    ProbeStatus::Inconclusive
+                }
+                (None, next) => ProbeStatus::NotOccurring {
+                    next: next.map(local),
+                },
+            };
+            ProbeResult {
+                summary: item.summary,
+                uid: item.uid,
+                recurring: item.recurring,
+                status,
+            }
+        })
+        .collect()
+}
+
+fn text_property(component: &ICalendarComponent, property: ICalendarProperty) -> Option<String> {
+    component
+        .entries
+        .iter()
+        .find(|entry| entry.name == property)
+        .and_then(|entry| match entry.values.first() {
+            Some(ICalendarValue::Text(text)) => Some(text.clone()),
+            _ => None,
+        })
+}
@@ -0,0 +1,145 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::contentline::{self, fold_line, ContentLine};
+
+// Properties holding a single TEXT value, shown and edited unescaped. The
+// rest keep their raw value so list and structured separators survive.
+const TEXT_PROPERTIES: &[&str] = &[
+    "SUMMARY",
+    "DESCRIPTION",
+    "LOCATION",
+    "COMMENT",
+    "CONTACT",
+    "FN",
+    "NOTE",
+    "TITLE",
+    "ROLE",
+];
+
+// One property of an iCalendar or vCard, as shown in the property table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PropertyRow {
+    // First line of the property, identifies it within the source.
+    pub line: usize,
+    // Component path, such as "VCALENDAR/VEVENT[event-1]".
+    pub component: String,
+    pub name: String,
+    // Parameters as written, such as ";TZID=Europe/Berlin".
+    pub params: String,
+    pub value: String,
+    // Whether `value` is unescaped text.
+    pub text: bool,
+}
+
+pub fn rows(source: &str) -> Vec<PropertyRow> {
+    let mut rows = contentline::components(source)
+        .into_iter()
+        .flat_map(|component| {
+            let path = component.path;
+            component.properties.into_iter().map(move |property| {
+                let text = TEXT_PROPERTIES.contains(&property.name.as_str());
+                PropertyRow {
+                    line: property.line,
+                    component: path.clone(),
+                    params: params(&property),
+                    value: if text {
+                        property.text()
+                    } else {
+                        property.value.clone()
+                    },
+                    name: property.name,
+                    text,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by_key(|row| row.line);
+    rows
+}
+
+// Replaces the value of the property starting at `line`, leaving every other
+// line as it was written.
+pub fn set_value(source: &str, line: usize, value: &str) -> String {
+    let Some(mut property) = contentline::unfold(source)
+        .into_iter()
+        .find(|(number, _)| *number == line)
+        .and_then(|(number, raw)| contentline::parse_line(number, &raw))
+    else {
+        return source.to_string();
+    };
+    property.value = if TEXT_PROPERTIES.contains(&property.name.as_str()) {
+        contentline::escape(value)
+    } else {
+        value.replace(['\r', '\n'], "")
+    };
+    let mut folded = String::new();
+    fold_line(&mut folded, &property.to_line());
+
+    let mut text = String::with_capacity(source.len() + value.len());
+    let mut lines = source.split_inclusive('\n').peekable();
+    for raw in lines.by_ref().take(line - 1) {
+        text.push_str(raw);
+    }
+    // The property is folded with the line breaks of its first line.
+    let first = lines.next().unwrap_or_default();
+    let ending = &first[first.trim_end_matches(['\r', '\n']).len()..];
+    let folded = folded.strip_suffix("\r\n").unwrap_or(&folded);
+    if ending == "\n" {
+        text.push_str(&folded.replace("\r\n", "\n"));
+    } else {
+        text.push_str(folded);
+    }
+    text.push_str(ending);
+    while lines.next_if(|raw| raw.starts_with([' ', '\t'])).is_some() {}
+    text.extend(lines);
+    text
+}
+
+// Parameters as `to_line` writes them, without the name and value.
+fn params(property: &ContentLine) -> String {
+    let bare = ContentLine {
+        group: None,
+        value: String::new(),
+        ..property.clone()
+    }
+    .to_line();
+    bare[property.name.len()..bare.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_other_lines_as_written() {
+        let source = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDESCRIPTION:A description folded\n  where its writer chose\nSUMMARY:Old\n title\nx-custom;param=value:kept\nEND:VEVENT\nEND:VCALENDAR";
+        assert_eq!(
+            set_value(source, 5, "New, title"),
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDESCRIPTION:A description folded\n  where its writer chose\nSUMMARY:New\\, title\nx-custom;param=value:kept\nEND:VEVENT\nEND:VCALENDAR"
+        );
+        assert_eq!(
+            set_value(source, 7, "changed"),
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDESCRIPTION:A description folded\n  where its writer chose\nSUMMARY:Old\n title\nX-CUSTOM;PARAM=value:changed\nEND:VEVENT\nEND:VCALENDAR"
+        );
+        assert_eq!(set_value(source, 4, "X"), source);
+        assert_eq!(set_value(source, 42, "X"), source);
+    }
+
+    #[test]
+    fn folds_the_edited_line() {
+        let source = "BEGIN:VCARD\r\nFN:Jane\r\nEND:VCARD\r\n";
+        let value = "J".repeat(80);
+        assert_eq!(
+            set_value(source, 2, &value),
+            format!(
+                "BEGIN:VCARD\r\nFN:{}\r\n {}\r\nEND:VCARD\r\n",
+                &value[..72],
+                &value[72..]
+            )
+        );
+    }
+}
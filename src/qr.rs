@@ -0,0 +1,171 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// QR codes of vCards, either the vCard itself or its MECARD form, which is
+// what most phone cameras read and takes far less room.
+
+use qrcodegen::{QrCode, QrCodeEcc};
+
+use crate::contentline;
+
+// Bytes a version 40 QR code holds at the lowest error correction level.
+pub const MAX_PAYLOAD_BYTES: usize = 2953;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QrMatrix {
+    pub size: usize,
+    // Row by row, true for dark modules.
+    pub modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    pub fn encode(payload: &str) -> Result<Self, String> {
+        let code = QrCode::encode_text(payload, QrCodeEcc::Low).map_err(|_| {
+            format!(
+                "The payload is {} bytes, more than the {} a QR code can hold.",
+                payload.len(),
+                MAX_PAYLOAD_BYTES
+            )
+        })?;
+        let size = code.size();
+        Ok(QrMatrix {
+            size: size as usize,
+            modules: (0..size)
+                .flat_map(|y| (0..size).map(move |x| (x, y)))
+                .map(|(x, y)| code.get_module(x, y))
+                .collect(),
+        })
+    }
+
+    // SVG path data drawing the dark modules as unit squares, offset by the
+    // quiet zone of four modules the standard asks for.
+    pub fn svg_path(&self) -> String {
+        let mut path = String::new();
+        for (index, dark) in self.modules.iter().enumerate() {
+            if *dark {
+                path.push_str(&format!(
+                    "M{},{}h1v1h-1z",
+                    index % self.size + 4,
+                    index / self.size + 4
+                ));
+            }
+        }
+        path
+    }
+
+    // Width and height including the quiet zone.
+    pub fn extent(&self) -> usize {
+        self.size + 8
+    }
+}
+
+// Why a vCard does not fit in a QR code, naming the embedded media that
+// usually makes it too large.
+pub fn size_warning(vcard: &str) -> Option<String> {
+    if vcard.len() <= MAX_PAYLOAD_BYTES {
+        return None;
+    }
+    let media = contentline::parse(vcard)
+        .into_iter()
+        .filter(|line| matches!(line.name.as_str(), "PHOTO" | "LOGO" | "SOUND" | "KEY"))
+        .filter(|line| line.value.len() > 200)
+        .map(|line| line.name)
+        .collect::<Vec<_>>();
+    Some(if media.is_empty() {
+        format!(
+            "The vCard is {} bytes, more than the {} a QR code can hold. Try the MECARD form.",
+            vcard.len(),
+            MAX_PAYLOAD_BYTES
+        )
+    } else {
+        format!(
+            "The vCard is {} bytes, more than the {} a QR code can hold, mostly because of its embedded {}. Try the MECARD form, which leaves them out.",
+            vcard.len(),
+            MAX_PAYLOAD_BYTES,
+            media.join(", ")
+        )
+    })
+}
+
+// The MECARD form of the first card: name, phones, emails, address, URL,
+// birthday, nickname and note.
+pub fn mecard(vcard: &str) -> Option<String> {
+    let lines = contentline::parse(vcard);
+    // Values are escaped as they are added.
+    let mut fields = Vec::new();
+    let first = |name: &str| lines.iter().find(|line| line.name == name);
+
+    let name = first("N")
+        .map(|line| {
+            let parts = contentline::split_unescaped(&line.value, ';')
+                .iter()
+                .map(|part| contentline::unescape(part))
+                .collect::<Vec<_>>();
+            let family = parts.first().cloned().unwrap_or_default();
+            let given = parts.get(1).cloned().unwrap_or_default();
+            if given.is_empty() {
+                escape(&family)
+            } else {
+                format!("{},{}", escape(&family), escape(&given))
+            }
+        })
+        .filter(|name| !name.is_empty())
+        .or_else(|| first("FN").map(|line| escape(&line.text())))?;
+    fields.push(("N", name));
+    if let Some(line) = first("NICKNAME") {
+        fields.push(("NICKNAME", escape(&line.text())));
+    }
+    for line in lines.iter().filter(|line| line.name == "TEL") {
+        fields.push(("TEL", escape(line.text().trim_start_matches("tel:"))));
+    }
+    for line in lines.iter().filter(|line| line.name == "EMAIL") {
+        fields.push(("EMAIL", escape(&line.text())));
+    }
+    if let Some(line) = first("ADR") {
+        // MECARD keeps the seven parts of ADR, separated by commas.
+        let parts = contentline::split_unescaped(&line.value, ';')
+            .iter()
+            .map(|part| escape(&contentline::unescape(part)))
+            .collect::<Vec<_>>();
+        if parts.iter().any(|part| !part.is_empty()) {
+            fields.push(("ADR", parts.join(",")));
+        }
+    }
+    if let Some(line) = first("BDAY") {
+        let date = line.value.trim().replace('-', "");
+        if date.len() == 8 && date.bytes().all(|byte| byte.is_ascii_digit()) {
+            fields.push(("BDAY", date));
+        }
+    }
+    if let Some(line) = first("URL") {
+        fields.push(("URL", escape(&line.text())));
+    }
+    if let Some(line) = first("NOTE") {
+        fields.push(("NOTE", escape(&line.text())));
+    }
+
+    let mut out = String::from("MECARD:");
+    for (name, value) in fields {
+        out.push_str(&format!("{}:{};", name, value));
+    }
+    out.push(';');
+    Some(out)
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' | ';' | ':' | ',' | '"' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\n' => out.push(' '),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
@@ -0,0 +1,118 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono_tz::Tz;
+
+pub const PARAMETERS: &[&str] = &["sample", "tz", "expand", "format"];
+
+// Options a link can preset with query parameters, such as
+// ?sample=ical_002&tz=Europe/Berlin&expand=100&format=jcal. Unknown
+// parameters and values that do not parse are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    pub sample: Option<String>,
+    // Zone occurrences are expanded in.
+    pub timezone: Option<Tz>,
+    // Number of occurrences expanded.
+    pub expand: Option<usize>,
+    pub output: Option<OutputFormat>,
+}
+
+// What the output is shown as. The plain forms name the counterpart format
+// of the source, which the page already picks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    JCal,
+    JCard,
+    XCal,
+    XCard,
+    Jmap,
+}
+
+impl OutputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::JCal => "jcal",
+            OutputFormat::JCard => "jcard",
+            OutputFormat::XCal => "xcal",
+            OutputFormat::XCard => "xcard",
+            OutputFormat::Jmap => "jmap",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" | "icalendar" | "ical" | "vcard" | "jscalendar" | "jscontact" => {
+                Some(OutputFormat::Text)
+            }
+            "jcal" => Some(OutputFormat::JCal),
+            "jcard" => Some(OutputFormat::JCard),
+            "xcal" => Some(OutputFormat::XCal),
+            "xcard" => Some(OutputFormat::XCard),
+            "jmap" => Some(OutputFormat::Jmap),
+            _ => None,
+        }
+    }
+}
+
+impl QueryOptions {
+    // `pairs` are the decoded names and values. Sample names are checked
+    // against `samples`.
+    pub fn parse<'x>(
+        pairs: impl IntoIterator<Item = (&'x str, &'x str)>,
+        samples: &[&str],
+        max_expand: usize,
+    ) -> Self {
+        let mut options = QueryOptions::default();
+        for (name, value) in pairs {
+            let value = value.trim();
+            match name {
+                "sample" => {
+                    if let Some(sample) = samples.iter().find(|sample| **sample == value) {
+                        options.sample = Some(sample.to_string());
+                    }
+                }
+                "tz" => {
+                    if let Ok(timezone) = value.parse::<Tz>() {
+                        options.timezone = Some(timezone);
+                    }
+                }
+                "expand" => {
+                    if let Ok(count) = value.parse::<usize>() {
+                        options.expand = Some(count.clamp(1, max_expand));
+                    }
+                }
+                "format" => {
+                    if let Some(output) = OutputFormat::parse(value) {
+                        options.output = Some(output);
+                    }
+                }
+                _ => {}
+            }
+        }
+        options
+    }
+
+    // Names and values in the order they are written, not yet encoded.
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(sample) = &self.sample {
+            pairs.push(("sample", sample.clone()));
+        }
+        if let Some(timezone) = self.timezone {
+            pairs.push(("tz", timezone.name().to_string()));
+        }
+        if let Some(count) = self.expand {
+            pairs.push(("expand", count.to_string()));
+        }
+        if let Some(output) = self.output {
+            pairs.push(("format", output.as_str().to_string()));
+        }
+        pairs
+    }
+}
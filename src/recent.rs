@@ -0,0 +1,151 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+
+use crate::{bundle::BundleOptions, Format};
+
+const RECENT_LIMIT: usize = 20;
+// Larger inputs are not kept, so the history stays within the storage quota.
+pub const MAX_SOURCE_LEN: usize = 256 * 1024;
+// Bytes that may change between two inputs taken as one being typed.
+const TYPING_LIMIT: usize = 256;
+
+// One input that was converted, with the options it was converted with.
+#[derive(Clone, Debug, Default)]
+pub struct RecentInput {
+    // Name of the document it was edited in.
+    pub document: String,
+    pub source: String,
+    pub format: Option<Format>,
+    pub converted: DateTime<Utc>,
+    pub summary: String,
+    pub options: BundleOptions,
+}
+
+// The last inputs converted, newest first, persisted across reloads.
+#[derive(Clone, Debug, Default)]
+pub struct RecentInputs {
+    inputs: Vec<RecentInput>,
+}
+
+impl RecentInputs {
+    pub fn inputs(&self) -> &[RecentInput] {
+        &self.inputs
+    }
+
+    pub fn get(&self, index: usize) -> Option<&RecentInput> {
+        self.inputs.get(index)
+    }
+
+    // Converting the same input again moves it to the top. While a document
+    // is being typed in, its last input is replaced rather than kept, so that
+    // live conversions do not make an entry per keystroke.
+    pub fn record(&mut self, input: RecentInput) {
+        if input.source.trim().is_empty() || input.source.len() > MAX_SOURCE_LEN {
+            return;
+        }
+        self.inputs.retain(|recent| recent.source != input.source);
+        if self.inputs.first().is_some_and(|last| {
+            last.document == input.document
+                && input.converted - last.converted < Duration::minutes(1)
+                && is_typed_over(&last.source, &input.source)
+        }) {
+            self.inputs.remove(0);
+        }
+        self.inputs.insert(0, input);
+        self.inputs.truncate(RECENT_LIMIT);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.inputs.len() {
+            self.inputs.remove(index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+    }
+}
+
+// Whether one input differs from the other only by a few characters typed or
+// deleted in one place.
+fn is_typed_over(previous: &str, current: &str) -> bool {
+    let (previous, current) = (previous.as_bytes(), current.as_bytes());
+    let prefix = previous
+        .iter()
+        .zip(current)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    previous.len().max(current.len()) - prefix - suffix <= TYPING_LIMIT
+}
+
+// "Team sync", "Team sync and 3 more" or "Empty".
+pub fn summarize(titles: &[String]) -> String {
+    match titles {
+        [] => "Empty".to_string(),
+        [title] => title.clone(),
+        [title, rest @ ..] => format!("{} and {} more", title, rest.len()),
+    }
+}
+
+impl fmt::Display for RecentInputs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = Value::Array(
+            self.inputs
+                .iter()
+                .map(|input| {
+                    json!({
+                        "document": input.document,
+                        "source": input.source,
+                        "format": input.format.map(|format| format.as_str()),
+                        "converted": input.converted.to_rfc3339(),
+                        "summary": input.summary,
+                        "options": input.options.to_json(),
+                    })
+                })
+                .collect(),
+        );
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RecentInputs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = serde_json::from_str::<Value>(s).map_err(|err| err.to_string())?;
+        let inputs = value
+            .as_array()
+            .ok_or_else(|| "Missing inputs".to_string())?
+            .iter()
+            .filter_map(|input| {
+                let text = |key: &str| input.get(key).and_then(Value::as_str);
+                Some(RecentInput {
+                    document: text("document").unwrap_or_default().to_string(),
+                    source: text("source")?.to_string(),
+                    format: text("format").and_then(Format::parse),
+                    converted: DateTime::parse_from_rfc3339(text("converted")?)
+                        .ok()?
+                        .with_timezone(&Utc),
+                    summary: text("summary").unwrap_or_default().to_string(),
+                    options: BundleOptions::from_json(input.get("options").unwrap_or(&Value::Null)),
+                })
+            })
+            .take(RECENT_LIMIT)
+            .collect();
+        Ok(RecentInputs { inputs })
+    }
+}
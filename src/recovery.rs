@@ -0,0 +1,326 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// What the parser does when an iCalendar or vCard does not follow its RFC:
+// lenient parsing skips or coerces the offending line and carries on, strict
+// parsing fails the entry on the first one.
+
+use crate::{
+    contentline::{self, ContentLine},
+    converter::EntryResult,
+    findings::{Code, Finding},
+    validation, Format,
+};
+
+// Properties whose values are text, where a backslash escapes the next
+// character.
+const TEXT_PROPERTIES: &[&str] = &[
+    "ADR",
+    "CATEGORIES",
+    "COMMENT",
+    "CONTACT",
+    "DESCRIPTION",
+    "FN",
+    "LOCATION",
+    "N",
+    "NICKNAME",
+    "NOTE",
+    "ORG",
+    "RESOURCES",
+    "ROLE",
+    "SUMMARY",
+    "TITLE",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recovery {
+    // Counted from the start of its entry.
+    pub line: usize,
+    // "line N", prefixed with the entry in a multi-entry file.
+    pub location: String,
+    pub property: Option<String>,
+    // What does not follow the RFC.
+    pub problem: String,
+    // What lenient parsing does about it.
+    pub action: String,
+}
+
+impl Recovery {
+    fn new(
+        line: usize,
+        property: Option<&str>,
+        problem: impl Into<String>,
+        action: impl Into<String>,
+    ) -> Self {
+        Recovery {
+            line,
+            location: format!("line {}", line),
+            property: property.map(str::to_string),
+            problem: problem.into(),
+            action: action.into(),
+        }
+    }
+
+    // The error strict parsing stops at.
+    pub fn to_finding(&self, more: usize) -> Finding {
+        let mut message = match &self.property {
+            Some(property) => format!(
+                "Strict parsing stopped at {} ({}): {}. Lenient parsing would {}.",
+                self.location,
+                property,
+                self.problem,
+                lowercase_first(&self.action)
+            ),
+            None => format!(
+                "Strict parsing stopped at {}: {}. Lenient parsing would {}.",
+                self.location,
+                self.problem,
+                lowercase_first(&self.action)
+            ),
+        };
+        if more > 0 {
+            message.push_str(&format!(" {} more recoveries would follow.", more));
+        }
+        Finding::new(Code::StrictParsingViolation, message).with_location(self.location.clone())
+    }
+}
+
+// Every recovery lenient parsing makes in an iCalendar or vCard, in line
+// order. Other formats are parsed strictly either way. It runs on every
+// conversion, so whatever a line holds is reported rather than trusted.
+pub fn scan(source: &str) -> Vec<Recovery> {
+    if !source.trim_start().starts_with("BEGIN:") {
+        return vec![];
+    }
+    // Blank lines before the end of the file are skipped.
+    let end = source.trim_end().lines().count();
+    let mut recoveries = source
+        .lines()
+        .take(end)
+        .enumerate()
+        .filter(|(_, raw)| raw.trim().is_empty())
+        .map(|(index, _)| Recovery::new(index + 1, None, "blank line", "Skip it"))
+        .collect::<Vec<_>>();
+
+    let mut vcard = false;
+    let mut depth = 0usize;
+    for (number, raw) in contentline::unfold(source) {
+        // Lines that are not content lines fail the entry in either mode.
+        let Some(line) = contentline::parse_line(number, &raw) else {
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" => {
+                if depth == 0 {
+                    vcard = line.value.trim().eq_ignore_ascii_case("VCARD");
+                }
+                depth += 1;
+            }
+            "END" => depth = depth.saturating_sub(1),
+            _ => check(&line, &raw, vcard, &mut recoveries),
+        }
+    }
+    recoveries.sort_by_key(|recovery| recovery.line);
+    recoveries
+}
+
+// The recoveries of every converted iCalendar and vCard entry.
+pub fn scan_all(results: &[EntryResult]) -> Vec<Recovery> {
+    let count = results.len();
+    results
+        .iter()
+        .enumerate()
+        .flat_map(|(index, entry)| {
+            entry_recoveries(entry)
+                .into_iter()
+                .map(move |mut recovery| {
+                    if count > 1 {
+                        recovery.location = format!("entry {}, {}", index + 1, recovery.location);
+                    }
+                    recovery
+                })
+        })
+        .collect()
+}
+
+// Fails every entry that converted only thanks to a recovery, with the first
+// one as its error.
+pub fn strict(results: Vec<EntryResult>) -> Vec<EntryResult> {
+    let count = results.len();
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut entry)| {
+            let recoveries = entry_recoveries(&entry);
+            if let Some(first) = recoveries.first() {
+                let mut finding = first.to_finding(recoveries.len() - 1);
+                if count > 1 {
+                    finding.location = finding
+                        .location
+                        .map(|location| format!("entry {}, {}", index + 1, location));
+                }
+                entry.result = Err(finding);
+            }
+            entry
+        })
+        .collect()
+}
+
+fn entry_recoveries(entry: &EntryResult) -> Vec<Recovery> {
+    match &entry.result {
+        Ok(result) if matches!(result.format, Format::ICalendar | Format::VCard) => {
+            scan(&entry.source)
+        }
+        _ => vec![],
+    }
+}
+
+fn check(line: &ContentLine, raw: &str, vcard: bool, out: &mut Vec<Recovery>) {
+    let name = line.name.as_str();
+    for (param, value) in &line.params {
+        if param == "TYPE" && is_bare_parameter(raw, value) {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                format!("parameter {} has no name", value),
+                format!("Read it as TYPE={}", value),
+            ));
+        } else if param == "VALUE"
+            && !validation::VALUE_TYPES.contains(&value.to_ascii_uppercase().as_str())
+        {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                format!("unknown value type {}", value),
+                "Read the value as text",
+            ));
+        } else if param == "ENCODING" && value.eq_ignore_ascii_case("QUOTED-PRINTABLE") {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                "quoted-printable value, a vCard 2.1 encoding",
+                "Decode it",
+            ));
+        }
+    }
+
+    let value = line.value.trim();
+    if name == "RRULE" && !vcard {
+        if let Some(problem) = validation::recurrence_rule_problems(value).first() {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                format!("\"{}\" {}", value, problem),
+                "Keep the rule as text",
+            ));
+        }
+    } else if let Some(problem) = validation::value_problem(line, vcard) {
+        // VERSION is checked when the format is detected.
+        if name != "VERSION" {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                format!("\"{}\" {}", value, problem),
+                "Keep the value as text",
+            ));
+        }
+    }
+
+    if TEXT_PROPERTIES.contains(&name) {
+        if let Some(escape) = invalid_escape(&line.value) {
+            out.push(Recovery::new(
+                line.line,
+                Some(name),
+                format!("\\{} is not an escape", escape),
+                format!("Drop the backslash and keep {}", escape),
+            ));
+        }
+    }
+}
+
+// Bare parameters are written without "=", as vCard 2.1 did.
+fn is_bare_parameter(raw: &str, value: &str) -> bool {
+    let head = raw.split(':').next().unwrap_or_default();
+    head.split(';')
+        .skip(1)
+        .any(|param| param.eq_ignore_ascii_case(value))
+}
+
+// The first character after a backslash that RFC 5545 and RFC 6350 do not
+// let be escaped.
+fn invalid_escape(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('\\' | ';' | ',' | 'n' | 'N' | ':') | None => {}
+                Some(ch) => return Some(ch),
+            }
+        }
+    }
+    None
+}
+
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(lines: &[&str]) -> String {
+        let mut source = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\n".to_string();
+        for line in lines {
+            source.push_str(line);
+            source.push_str("\r\n");
+        }
+        source.push_str("END:VEVENT\r\nEND:VCALENDAR\r\n");
+        source
+    }
+
+    #[test]
+    fn reports_malformed_rrules() {
+        for rule in [
+            "RRULE:FREQ=WEEKLY;BYDAY=ÖA",
+            "RRULE:FREQ=MONTHLY;BYDAY=1€",
+            "RRULE:FREQ=MONTHLY;BYDAY=2S€U",
+            "RRULE:FREQ=DAILY;UNTIL=20170216T090😀000",
+            "RRULE:FREQ=YEARLY;BYMONTH=é;BYSETPOS=-",
+            "RRULE:FREQ",
+            "RRULE:;;;=",
+            "RRULE:€",
+        ] {
+            let recoveries = scan(&calendar(&["DTSTART:20240101T090000Z", rule]));
+            assert_eq!(recoveries.len(), 1, "{}", rule);
+            assert_eq!(recoveries[0].line, 5);
+            assert_eq!(recoveries[0].property.as_deref(), Some("RRULE"));
+        }
+    }
+
+    #[test]
+    fn reports_malformed_values() {
+        let recoveries = scan(&calendar(&[
+            "DTSTART:2024€101T090000Z",
+            "EXDATE:😀,20240101",
+            "DURATION:PT€H",
+            "TZOFFSETFROM:-070é0",
+            "GEO:é;ü",
+            "SUMMARY:a\\é",
+        ]));
+        assert_eq!(
+            recoveries
+                .iter()
+                .map(|recovery| recovery.line)
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6, 7, 8, 9]
+        );
+    }
+}
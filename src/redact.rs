@@ -0,0 +1,173 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::{Map, Value};
+
+use crate::contentline::{self, fold_line, ContentLine};
+
+// Properties kept on redacted components, enough to compute free/busy time.
+const ICALENDAR_KEEP: &[&str] = &[
+    "UID",
+    "DTSTAMP",
+    "DTSTART",
+    "DTEND",
+    "DUE",
+    "DURATION",
+    "RRULE",
+    "RDATE",
+    "EXDATE",
+    "RECURRENCE-ID",
+    "CLASS",
+    "TRANSP",
+    "STATUS",
+    "SEQUENCE",
+    "CREATED",
+    "LAST-MODIFIED",
+];
+const JSCALENDAR_KEEP: &[&str] = &[
+    "@type",
+    "uid",
+    "start",
+    "due",
+    "duration",
+    "estimatedDuration",
+    "timeZone",
+    "showWithoutTime",
+    "recurrenceId",
+    "recurrenceIdTimeZone",
+    "recurrenceRules",
+    "excludedRecurrenceRules",
+    "recurrenceOverrides",
+    "excluded",
+    "privacy",
+    "freeBusyStatus",
+    "status",
+    "progress",
+    "sequence",
+    "created",
+    "updated",
+];
+
+// Strips everything but the time blocks from PRIVATE and CONFIDENTIAL
+// events and tasks, the way CalDAV servers present other users' calendars.
+// Returns the redacted iCalendar and the number of components redacted.
+pub fn redact_icalendar(source: &str) -> (String, usize) {
+    let mut text = String::with_capacity(source.len());
+    let mut redacted = 0;
+    let mut depth = 0;
+    // Lines of the current event or task with their nesting depth.
+    let mut component: Option<Vec<(usize, Option<ContentLine>, String)>> = None;
+    for (number, raw) in contentline::unfold(source) {
+        let line = contentline::parse_line(number, &raw);
+        let name = line.as_ref().map(|line| line.name.as_str());
+        let is_end = name == Some("END");
+        if name == Some("BEGIN") {
+            depth += 1;
+            let value = line.as_ref().map(|line| line.value.to_ascii_uppercase());
+            if depth == 2 && matches!(value.as_deref(), Some("VEVENT" | "VTODO")) {
+                component = Some(Vec::new());
+            }
+        }
+
+        match &mut component {
+            Some(lines) => lines.push((depth, line, raw)),
+            None => fold_line(&mut text, &raw),
+        }
+
+        if is_end {
+            if let Some(lines) = component.take_if(|_| depth == 2) {
+                let private = lines.iter().any(|(depth, line, _)| {
+                    *depth == 2
+                        && line.as_ref().is_some_and(|line| {
+                            line.name == "CLASS"
+                                && matches!(
+                                    line.value.trim().to_ascii_uppercase().as_str(),
+                                    "PRIVATE" | "CONFIDENTIAL"
+                                )
+                        })
+                });
+                redacted += usize::from(private);
+                // Nested components such as alarms sit deeper and are dropped.
+                for (line_depth, line, raw) in &lines {
+                    let keep = !private
+                        || *line_depth == 2
+                            && line.as_ref().is_some_and(|line| {
+                                matches!(line.name.as_str(), "BEGIN" | "END")
+                                    || ICALENDAR_KEEP.contains(&line.name.as_str())
+                            });
+                    if keep {
+                        fold_line(&mut text, raw);
+                    }
+                }
+            }
+            depth = depth.saturating_sub(1);
+        }
+    }
+    (text, redacted)
+}
+
+// JSCalendar counterpart of `redact_icalendar`, for "private" and "secret"
+// entries of an object or group.
+pub fn redact_jscalendar(source: &str) -> Result<(String, usize), String> {
+    let mut root: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+    let objects = match root.get("@type").and_then(Value::as_str) {
+        Some("Group") => match root.get_mut("entries").and_then(Value::as_array_mut) {
+            Some(entries) => entries.iter_mut().collect::<Vec<_>>(),
+            None => Vec::new(),
+        },
+        _ => vec![&mut root],
+    };
+
+    let mut redacted = 0;
+    for object in objects {
+        let Value::Object(object) = object else {
+            continue;
+        };
+        if !matches!(
+            object.get("privacy").and_then(Value::as_str),
+            Some("private" | "secret")
+        ) {
+            continue;
+        }
+        retain_keys(object);
+        if let Some(Value::Object(overrides)) = object.get_mut("recurrenceOverrides") {
+            for patch in overrides.values_mut() {
+                if let Value::Object(patch) = patch {
+                    retain_keys(patch);
+                }
+            }
+        }
+        redacted += 1;
+    }
+
+    serde_json::to_string_pretty(&root)
+        .map(|text| (text, redacted))
+        .map_err(|err| err.to_string())
+}
+
+// Patch keys are pointers, so only their first segment is checked.
+fn retain_keys(object: &mut Map<String, Value>) {
+    object.retain(|key, _| {
+        let property = key.split('/').next().unwrap_or_default();
+        JSCALENDAR_KEEP.contains(&property)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_unmatched_end() {
+        let (text, redacted) = redact_icalendar(
+            "END:VEVENT\r\nBEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nCLASS:PRIVATE\r\nSUMMARY:Doctor\r\nDTSTART:20240101T090000Z\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        );
+        assert_eq!(redacted, 1);
+        assert!(text.starts_with("END:VEVENT\r\n"));
+        assert!(!text.contains("SUMMARY"));
+        assert!(text.contains("DTSTART:20240101T090000Z"));
+    }
+}
@@ -0,0 +1,589 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::contentline::{self, fold_line, ContentLine};
+
+// Pre-processing applied to a source exported by a specific vendor, fixing
+// known defects before conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Exchange,
+    Google,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Repair {
+    pub text: String,
+    pub changes: Vec<Change>,
+    // Likely defects left as they were, as fixing them could be wrong.
+    pub notes: Vec<Change>,
+}
+
+// Properties referencing a VTIMEZONE through the TZID parameter.
+const TZID_PROPERTIES: &[&str] = &[
+    "DTSTART",
+    "DTEND",
+    "DUE",
+    "RECURRENCE-ID",
+    "EXDATE",
+    "RDATE",
+];
+
+impl Profile {
+    pub const ALL: &'static [Profile] = &[Profile::Exchange, Profile::Google];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Profile::Exchange => "Exchange / Outlook",
+            Profile::Google => "Google Calendar",
+        }
+    }
+}
+
+impl Repair {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+pub fn apply(source: &str, profile: Profile) -> Repair {
+    let original = contentline::unfold(source);
+    let mut lines = original
+        .iter()
+        .map(|(line, text)| {
+            // Unparseable lines keep an empty name and are written back as is.
+            contentline::parse_line(*line, text).unwrap_or(ContentLine {
+                line: *line,
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut changes = Vec::new();
+    let mut notes = Vec::new();
+    match profile {
+        Profile::Exchange => exchange(&mut lines, &mut changes, &mut notes),
+        Profile::Google => google(&mut lines, &mut changes),
+    }
+
+    // Untouched lines are copied verbatim, only repaired ones are rewritten.
+    let mut text = String::with_capacity(source.len());
+    for line in &lines {
+        let raw = original
+            .binary_search_by_key(&line.line, |(line, _)| *line)
+            .ok()
+            .map(|idx| original[idx].1.as_str())
+            .unwrap_or_default();
+        if line.name.is_empty() || contentline::parse_line(line.line, raw).as_ref() == Some(line) {
+            fold_line(&mut text, raw);
+        } else {
+            fold_line(&mut text, &line.to_line());
+        }
+    }
+    changes.sort_by_key(|change| change.line);
+    Repair {
+        text,
+        changes,
+        notes,
+    }
+}
+
+fn exchange(lines: &mut Vec<ContentLine>, changes: &mut Vec<Change>, notes: &mut Vec<Change>) {
+    // All-day events exported as midnight to midnight, flagged by Outlook.
+    // Without the flag a UTC one may be all-day too, but it is only reported.
+    for (start, end) in components(lines, "VEVENT") {
+        let flagged = lines[start..end].iter().any(|line| {
+            line.name == "X-MICROSOFT-CDO-ALLDAYEVENT" && line.value.eq_ignore_ascii_case("TRUE")
+        });
+        let dtstart = lines[start..end]
+            .iter()
+            .position(|line| line.name == "DTSTART");
+        let dtend = lines[start..end]
+            .iter()
+            .position(|line| line.name == "DTEND");
+        let (Some(dtstart), Some(dtend)) = (dtstart, dtend) else {
+            continue;
+        };
+        let (dtstart, dtend) = (start + dtstart, start + dtend);
+        let (Some(from), Some(to)) = (
+            midnight(&lines[dtstart].value),
+            midnight(&lines[dtend].value),
+        ) else {
+            continue;
+        };
+        let utc = lines[dtstart].value.ends_with('Z') && lines[dtend].value.ends_with('Z');
+        if to <= from {
+            continue;
+        }
+        if !flagged {
+            if utc {
+                notes.push(Change {
+                    line: lines[dtstart].line,
+                    message: "Left the midnight to midnight UTC event as it is, X-MICROSOFT-CDO-ALLDAYEVENT does not flag it as all-day.".to_string(),
+                });
+            }
+            continue;
+        }
+        for (idx, date) in [(dtstart, from), (dtend, to)] {
+            let line = &mut lines[idx];
+            line.remove_param("TZID");
+            line.set_param("VALUE", "DATE");
+            line.value = contentline::format_date(date);
+        }
+        changes.push(Change {
+            line: lines[dtstart].line,
+            message: format!(
+                "Converted midnight to midnight {} event to an all-day event.",
+                if utc { "UTC" } else { "local" }
+            ),
+        });
+    }
+
+    // Windows time zone names, both in TZID parameters and VTIMEZONE ids.
+    let mut defined = Vec::new();
+    let mut renamed: Vec<(String, &str)> = Vec::new();
+    let mut in_timezone = false;
+    for line in lines.iter_mut() {
+        match line.name.as_str() {
+            "BEGIN" | "END" if line.value.eq_ignore_ascii_case("VTIMEZONE") => {
+                in_timezone = line.name == "BEGIN";
+            }
+            "TZID" if in_timezone => {
+                if let Some(iana) = windows_to_iana(&line.value) {
+                    renamed.push((line.value.clone(), iana));
+                    changes.push(Change {
+                        line: line.line,
+                        message: format!("Renamed VTIMEZONE \"{}\" to \"{}\".", line.value, iana),
+                    });
+                    line.value = iana.to_string();
+                }
+                defined.push(line.value.clone());
+            }
+            name if TZID_PROPERTIES.contains(&name) => {
+                let Some(tzid) = line.param("TZID").map(str::to_string) else {
+                    continue;
+                };
+                if let Some(iana) = windows_to_iana(&tzid) {
+                    line.set_param("TZID", iana);
+                    if !renamed.iter().any(|(windows, _)| *windows == tzid) {
+                        renamed.push((tzid.clone(), iana));
+                        changes.push(Change {
+                            line: line.line,
+                            message: format!(
+                                "Mapped Windows time zone \"{}\" to \"{}\".",
+                                tzid, iana
+                            ),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Time zones referenced without a definition.
+    let mut reported = Vec::new();
+    for line in lines.iter() {
+        let Some(tzid) = line.param("TZID") else {
+            continue;
+        };
+        if defined.iter().any(|defined| defined == tzid) || reported.contains(&tzid) {
+            continue;
+        }
+        reported.push(tzid);
+        changes.push(Change {
+            line: line.line,
+            message: if tzid.parse::<chrono_tz::Tz>().is_ok() {
+                format!(
+                    "No VTIMEZONE for \"{}\", resolved from the IANA time zone database.",
+                    tzid
+                )
+            } else {
+                format!(
+                    "No VTIMEZONE for \"{}\" and it is not a known time zone, times will be floating.",
+                    tzid
+                )
+            },
+        });
+    }
+
+    // Outlook bookkeeping properties with no JSCalendar equivalent.
+    remove_prefixed(lines, changes, "X-MICROSOFT-CDO-");
+}
+
+fn google(lines: &mut Vec<ContentLine>, changes: &mut Vec<Change>) {
+    struct Event {
+        range: (usize, usize),
+        uid: Option<String>,
+        recurrence_id: Option<usize>,
+        status: Option<usize>,
+    }
+    let events = components(lines, "VEVENT")
+        .into_iter()
+        .map(|(start, end)| {
+            let find = |name: &str| (start..end).find(|idx| lines[*idx].name == name);
+            Event {
+                range: (start, end),
+                uid: find("UID").map(|idx| lines[idx].value.clone()),
+                recurrence_id: find("RECURRENCE-ID"),
+                status: find("STATUS"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Cancelled instances are exported as overrides rather than exceptions.
+    let mut dropped = Vec::new();
+    let mut exdates: Vec<(usize, ContentLine)> = Vec::new();
+    for event in &events {
+        let Some(recurrence_id) = event.recurrence_id else {
+            continue;
+        };
+        let recurrence_id = &lines[recurrence_id];
+        let master = events.iter().find(|master| {
+            master.recurrence_id.is_none() && master.uid.is_some() && master.uid == event.uid
+        });
+        let cancelled = event
+            .status
+            .is_some_and(|idx| lines[idx].value.eq_ignore_ascii_case("CANCELLED"));
+        match master {
+            Some(master) if cancelled => {
+                let mut exdate = recurrence_id.clone();
+                exdate.name = "EXDATE".to_string();
+                exdate.remove_param("RANGE");
+                exdates.push((master.range.1 - 1, exdate));
+                dropped.push(event.range);
+                changes.push(Change {
+                    line: recurrence_id.line,
+                    message: format!(
+                        "Replaced cancelled instance {} with an EXDATE on its series.",
+                        recurrence_id.value
+                    ),
+                });
+            }
+            Some(_) => {}
+            None => changes.push(Change {
+                line: recurrence_id.line,
+                message: format!(
+                    "Instance {} has no series in this file and converts as a standalone event.",
+                    recurrence_id.value
+                ),
+            }),
+        }
+    }
+
+    // Statuses that are not valid for events, typically copied from the
+    // attendee's participation status.
+    let mut invalid = Vec::new();
+    for event in &events {
+        let Some(idx) = event.status else {
+            continue;
+        };
+        let status = &lines[idx];
+        if !["TENTATIVE", "CONFIRMED", "CANCELLED"]
+            .iter()
+            .any(|valid| status.value.eq_ignore_ascii_case(valid))
+            && !dropped.contains(&event.range)
+        {
+            changes.push(Change {
+                line: status.line,
+                message: format!("Removed invalid event status \"{}\".", status.value),
+            });
+            invalid.push(idx);
+        }
+    }
+
+    // Meet links have a standard equivalent.
+    for event in &events {
+        let (start, end) = event.range;
+        if lines[start..end]
+            .iter()
+            .any(|line| line.name == "CONFERENCE")
+        {
+            continue;
+        }
+        if let Some(line) = lines[start..end]
+            .iter_mut()
+            .find(|line| line.name == "X-GOOGLE-CONFERENCE")
+        {
+            line.name = "CONFERENCE".to_string();
+            line.set_param("VALUE", "URI");
+            line.set_param("FEATURE", "VIDEO");
+            changes.push(Change {
+                line: line.line,
+                message: "Converted X-GOOGLE-CONFERENCE to CONFERENCE.".to_string(),
+            });
+        }
+    }
+
+    let mut repaired = Vec::with_capacity(lines.len());
+    for (idx, line) in std::mem::take(lines).into_iter().enumerate() {
+        if invalid.contains(&idx)
+            || dropped
+                .iter()
+                .any(|(start, end)| (*start..*end).contains(&idx))
+        {
+            continue;
+        }
+        repaired.extend(
+            exdates
+                .iter()
+                .filter(|(at, _)| *at == idx)
+                .map(|(_, exdate)| exdate.clone()),
+        );
+        repaired.push(line);
+    }
+    *lines = repaired;
+
+    remove_prefixed(lines, changes, "X-GOOGLE-");
+}
+
+// Drops vendor properties, reporting each name once.
+fn remove_prefixed(lines: &mut Vec<ContentLine>, changes: &mut Vec<Change>, prefix: &str) {
+    let mut removed: Vec<(String, usize, usize)> = Vec::new();
+    lines.retain(|line| {
+        if !line.name.starts_with(prefix) {
+            return true;
+        }
+        match removed.iter_mut().find(|(name, _, _)| *name == line.name) {
+            Some((_, _, count)) => *count += 1,
+            None => removed.push((line.name.clone(), line.line, 1)),
+        }
+        false
+    });
+    for (name, line, count) in removed {
+        changes.push(Change {
+            line,
+            message: if count == 1 {
+                format!("Removed {}.", name)
+            } else {
+                format!("Removed {} ({} occurrences).", name, count)
+            },
+        });
+    }
+}
+
+// Index ranges, BEGIN to END inclusive, of every component of a given type.
+fn components(lines: &[ContentLine], component: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (idx, line) in lines.iter().enumerate() {
+        if !line.value.eq_ignore_ascii_case(component) {
+            continue;
+        }
+        match line.name.as_str() {
+            "BEGIN" => start = Some(idx),
+            "END" => ranges.extend(start.take().map(|start| (start, idx + 1))),
+            _ => {}
+        }
+    }
+    ranges
+}
+
+fn midnight(value: &str) -> Option<NaiveDate> {
+    contentline::parse_datetime(value)
+        .filter(|date_time| date_time.time() == NaiveTime::MIN)
+        .map(|date_time| date_time.date())
+}
+
+// Primary mappings from the CLDR windowsZones table.
+pub fn windows_to_iana(name: &str) -> Option<&'static str> {
+    let name = name.trim();
+    WINDOWS_ZONES
+        .iter()
+        .find(|(windows, _)| windows.eq_ignore_ascii_case(name))
+        .map(|(_, iana)| *iana)
+}
+
+static WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Aleutian Standard Time", "America/Adak"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Marquesas Standard Time", "Pacific/Marquesas"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("UTC-09", "Etc/GMT+9"),
+    ("Pacific Standard Time (Mexico)", "America/Tijuana"),
+    ("UTC-08", "Etc/GMT+8"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time (Mexico)", "America/Mazatlan"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Yukon Standard Time", "America/Whitehorse"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Easter Island Standard Time", "Pacific/Easter"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time (Mexico)", "America/Cancun"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("Haiti Standard Time", "America/Port-au-Prince"),
+    ("Cuba Standard Time", "America/Havana"),
+    ("US Eastern Standard Time", "America/Indianapolis"),
+    ("Turks And Caicos Standard Time", "America/Grand_Turk"),
+    ("Paraguay Standard Time", "America/Asuncion"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Venezuela Standard Time", "America/Caracas"),
+    ("Central Brazilian Standard Time", "America/Cuiaba"),
+    ("SA Western Standard Time", "America/La_Paz"),
+    ("Pacific SA Standard Time", "America/Santiago"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("Tocantins Standard Time", "America/Araguaina"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("SA Eastern Standard Time", "America/Cayenne"),
+    ("Argentina Standard Time", "America/Buenos_Aires"),
+    ("Greenland Standard Time", "America/Godthab"),
+    ("Montevideo Standard Time", "America/Montevideo"),
+    ("Magallanes Standard Time", "America/Punta_Arenas"),
+    ("Saint Pierre Standard Time", "America/Miquelon"),
+    ("Bahia Standard Time", "America/Bahia"),
+    ("UTC-02", "Etc/GMT+2"),
+    ("Azores Standard Time", "Atlantic/Azores"),
+    ("Cape Verde Standard Time", "Atlantic/Cape_Verde"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("Sao Tome Standard Time", "Africa/Sao_Tome"),
+    ("Morocco Standard Time", "Africa/Casablanca"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("Jordan Standard Time", "Asia/Amman"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("Middle East Standard Time", "Asia/Beirut"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("Syria Standard Time", "Asia/Damascus"),
+    ("West Bank Standard Time", "Asia/Hebron"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("South Sudan Standard Time", "Africa/Juba"),
+    ("Kaliningrad Standard Time", "Europe/Kaliningrad"),
+    ("Sudan Standard Time", "Africa/Khartoum"),
+    ("Libya Standard Time", "Africa/Tripoli"),
+    ("Namibia Standard Time", "Africa/Windhoek"),
+    ("Arabic Standard Time", "Asia/Baghdad"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Belarus Standard Time", "Europe/Minsk"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("E. Africa Standard Time", "Africa/Nairobi"),
+    ("Volgograd Standard Time", "Europe/Volgograd"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Astrakhan Standard Time", "Europe/Astrakhan"),
+    ("Azerbaijan Standard Time", "Asia/Baku"),
+    ("Russia Time Zone 3", "Europe/Samara"),
+    ("Mauritius Standard Time", "Indian/Mauritius"),
+    ("Saratov Standard Time", "Europe/Saratov"),
+    ("Georgian Standard Time", "Asia/Tbilisi"),
+    ("Caucasus Standard Time", "Asia/Yerevan"),
+    ("Afghanistan Standard Time", "Asia/Kabul"),
+    ("West Asia Standard Time", "Asia/Tashkent"),
+    ("Ekaterinburg Standard Time", "Asia/Yekaterinburg"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("Qyzylorda Standard Time", "Asia/Qyzylorda"),
+    ("India Standard Time", "Asia/Calcutta"),
+    ("Sri Lanka Standard Time", "Asia/Colombo"),
+    ("Nepal Standard Time", "Asia/Katmandu"),
+    ("Central Asia Standard Time", "Asia/Almaty"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("Omsk Standard Time", "Asia/Omsk"),
+    ("Myanmar Standard Time", "Asia/Rangoon"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("Altai Standard Time", "Asia/Barnaul"),
+    ("W. Mongolia Standard Time", "Asia/Hovd"),
+    ("North Asia Standard Time", "Asia/Krasnoyarsk"),
+    ("N. Central Asia Standard Time", "Asia/Novosibirsk"),
+    ("Tomsk Standard Time", "Asia/Tomsk"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("North Asia East Standard Time", "Asia/Irkutsk"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("Ulaanbaatar Standard Time", "Asia/Ulaanbaatar"),
+    ("Aus Central W. Standard Time", "Australia/Eucla"),
+    ("Transbaikal Standard Time", "Asia/Chita"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("North Korea Standard Time", "Asia/Pyongyang"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Yakutsk Standard Time", "Asia/Yakutsk"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("West Pacific Standard Time", "Pacific/Port_Moresby"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("Vladivostok Standard Time", "Asia/Vladivostok"),
+    ("Lord Howe Standard Time", "Australia/Lord_Howe"),
+    ("Bougainville Standard Time", "Pacific/Bougainville"),
+    ("Russia Time Zone 10", "Asia/Srednekolymsk"),
+    ("Magadan Standard Time", "Asia/Magadan"),
+    ("Norfolk Standard Time", "Pacific/Norfolk"),
+    ("Sakhalin Standard Time", "Asia/Sakhalin"),
+    ("Central Pacific Standard Time", "Pacific/Guadalcanal"),
+    ("Russia Time Zone 11", "Asia/Kamchatka"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+    ("UTC+12", "Etc/GMT-12"),
+    ("Fiji Standard Time", "Pacific/Fiji"),
+    ("Chatham Islands Standard Time", "Pacific/Chatham"),
+    ("UTC+13", "Etc/GMT-13"),
+    ("Tonga Standard Time", "Pacific/Tongatapu"),
+    ("Samoa Standard Time", "Pacific/Apia"),
+    ("Line Islands Standard Time", "Pacific/Kiritimati"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(lines: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:1\r\n{}END:VEVENT\r\nEND:VCALENDAR\r\n",
+            lines
+        )
+    }
+
+    #[test]
+    fn converts_flagged_all_day_events() {
+        let repaired = apply(
+            &event("DTSTART:20240101T000000Z\r\nDTEND:20240102T000000Z\r\nX-MICROSOFT-CDO-ALLDAYEVENT:TRUE\r\n"),
+            Profile::Exchange,
+        );
+        assert_eq!(
+            repaired.text,
+            event("DTSTART;VALUE=DATE:20240101\r\nDTEND;VALUE=DATE:20240102\r\n")
+        );
+        assert_eq!(repaired.changes.len(), 2);
+        assert!(repaired.notes.is_empty());
+    }
+
+    #[test]
+    fn reports_unflagged_utc_midnight_events() {
+        for flag in ["", "X-MICROSOFT-CDO-ALLDAYEVENT:FALSE\r\n"] {
+            let source = event(&format!(
+                "DTSTART:20240101T000000Z\r\nDTEND:20240102T000000Z\r\n{}",
+                flag
+            ));
+            let repaired = apply(&source, Profile::Exchange);
+            assert!(repaired
+                .text
+                .contains("DTSTART:20240101T000000Z\r\nDTEND:20240102T000000Z\r\n"));
+            assert_eq!(repaired.notes.len(), 1);
+            assert_eq!(repaired.notes[0].line, 4);
+        }
+    }
+}
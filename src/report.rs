@@ -0,0 +1,186 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use crate::{entry_title, findings::Finding, Converter, EntryResult, Format};
+
+const REPORT_TYPE: &str = "jmap-convert-report";
+// Layout of the report, raised when a field changes meaning.
+const REPORT_VERSION: u64 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundtripStatus {
+    // The roundtrip reproduced the source line for line.
+    Identical,
+    // Only the form of some values changed.
+    Lossless,
+    Lossy,
+    Failed,
+}
+
+// Summary of one conversion for tracking quality across a corpus. Its JSON
+// form is the same whether the web page or the command line wrote it.
+#[derive(Clone, Debug, Default)]
+pub struct ConversionReport {
+    pub version: String,
+    // RFC 3339 time the report was made.
+    pub generated: String,
+    pub name: String,
+    pub bytes: usize,
+    pub lines: usize,
+    pub entries: Vec<EntryReport>,
+    // Findings about the input as a whole, such as stripped extensions.
+    pub findings: Vec<Finding>,
+    pub timings: Timings,
+}
+
+#[derive(Clone, Debug)]
+pub struct EntryReport {
+    pub title: Option<String>,
+    pub format: Option<Format>,
+    pub roundtrip: RoundtripStatus,
+    pub losses: usize,
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub conversion_ms: f64,
+    pub expansion_ms: Option<f64>,
+}
+
+impl RoundtripStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundtripStatus::Identical => "identical",
+            RoundtripStatus::Lossless => "lossless",
+            RoundtripStatus::Lossy => "lossy",
+            RoundtripStatus::Failed => "failed",
+        }
+    }
+}
+
+impl ConversionReport {
+    pub fn new(name: impl Into<String>, source: &str, results: &[EntryResult]) -> Self {
+        ConversionReport {
+            name: name.into(),
+            bytes: source.len(),
+            lines: source.lines().count(),
+            entries: results.iter().map(EntryReport::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    // Format of the first entry it could be told for.
+    pub fn format(&self) -> Option<Format> {
+        self.entries.iter().find_map(|entry| entry.format)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let count = |status: RoundtripStatus| {
+            self.entries
+                .iter()
+                .filter(|entry| entry.roundtrip == status)
+                .count()
+        };
+        json!({
+            "@type": REPORT_TYPE,
+            "reportVersion": REPORT_VERSION,
+            "version": self.version,
+            "generated": self.generated,
+            "input": {
+                "name": self.name,
+                "bytes": self.bytes,
+                "lines": self.lines,
+                "entries": self.entries.len(),
+                "format": self.format().map(|format| format.as_str()),
+            },
+            "roundtrip": {
+                "identical": count(RoundtripStatus::Identical),
+                "lossless": count(RoundtripStatus::Lossless),
+                "lossy": count(RoundtripStatus::Lossy),
+                "failed": count(RoundtripStatus::Failed),
+            },
+            "entries": self
+                .entries
+                .iter()
+                .map(|entry| json!({
+                    "title": entry.title,
+                    "format": entry.format.map(|format| format.as_str()),
+                    "roundtrip": entry.roundtrip.as_str(),
+                    "losses": entry.losses,
+                    "findings": entry.findings.iter().map(Finding::to_json).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "findings": self.findings.iter().map(Finding::to_json).collect::<Vec<_>>(),
+            "timings": {
+                "conversionMs": self.timings.conversion_ms,
+                "expansionMs": self.timings.expansion_ms,
+            },
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        let name = self
+            .name
+            .trim()
+            .trim_end_matches(".json")
+            .trim_end_matches(".ics")
+            .trim_end_matches(".vcf");
+        format!(
+            "{}.report.json",
+            if name.is_empty() {
+                "jmap-convert"
+            } else {
+                name
+            }
+        )
+    }
+}
+
+impl EntryReport {
+    pub fn new(entry: &EntryResult) -> Self {
+        match &entry.result {
+            Ok(result) => EntryReport {
+                title: entry_title(&entry.source),
+                format: Some(result.format),
+                roundtrip: if lines(&entry.source).eq(lines(&result.roundtrip)) {
+                    RoundtripStatus::Identical
+                } else if result.fidelity.is_empty() {
+                    RoundtripStatus::Lossless
+                } else {
+                    RoundtripStatus::Lossy
+                },
+                losses: result.fidelity.len(),
+                findings: result.findings.clone(),
+            },
+            Err(finding) => EntryReport {
+                title: entry_title(&entry.source),
+                format: Converter.detect_format(&entry.source).ok(),
+                roundtrip: RoundtripStatus::Failed,
+                losses: 0,
+                findings: vec![finding.clone()],
+            },
+        }
+    }
+}
+
+impl fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string_pretty(&self.to_json()).map_err(|_| fmt::Error)?
+        )
+    }
+}
+
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.trim().lines().map(str::trim_end)
+}
@@ -0,0 +1,702 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use serde_json::{Map, Value};
+
+use crate::contentline;
+
+const WEEKDAYS: [&str; 5] = ["mo", "tu", "we", "th", "fr"];
+const WEEKEND: [&str; 2] = ["sa", "su"];
+
+// A recurring event or task and its recurrence in words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub title: String,
+    pub text: String,
+}
+
+// RRULE parts, or the members of a JSCalendar RecurrenceRule, with day and
+// frequency names in lowercase.
+#[derive(Debug, Default)]
+struct Rule {
+    frequency: String,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    by_day: Vec<(Option<i32>, String)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<String>,
+    by_year_day: Vec<i32>,
+    by_week_no: Vec<i32>,
+    by_set_position: Vec<i32>,
+    by_hour: Vec<i32>,
+    by_minute: Vec<i32>,
+}
+
+#[derive(Debug, Default)]
+struct Recurrence {
+    title: String,
+    start: Option<NaiveDateTime>,
+    rules: Vec<Rule>,
+    excluded_rules: Vec<Rule>,
+    added: Vec<NaiveDateTime>,
+    removed: Vec<NaiveDateTime>,
+}
+
+// Explains the RRULE, EXRULE, RDATE and EXDATE of every recurring event,
+// to-do and journal entry. Overrides with a RECURRENCE-ID are left out.
+pub fn from_icalendar(source: &str) -> Vec<Explanation> {
+    contentline::components(source)
+        .into_iter()
+        .filter(|component| {
+            let name = component.path.rsplit('/').next().unwrap_or_default();
+            ["VEVENT[", "VTODO[", "VJOURNAL["]
+                .iter()
+                .any(|kind| name.starts_with(kind))
+        })
+        .filter(|component| {
+            !component
+                .properties
+                .iter()
+                .any(|property| property.name == "RECURRENCE-ID")
+        })
+        .filter_map(|component| {
+            let mut recurrence = Recurrence::default();
+            for property in &component.properties {
+                match property.name.as_str() {
+                    "SUMMARY" => recurrence.title = contentline::unescape(&property.value),
+                    "DTSTART" => recurrence.start = parse_ical_date(&property.value),
+                    "RRULE" => recurrence.rules.push(parse_rrule(&property.value)),
+                    "EXRULE" => recurrence.excluded_rules.push(parse_rrule(&property.value)),
+                    "RDATE" => recurrence.added.extend(parse_ical_dates(&property.value)),
+                    "EXDATE" => recurrence.removed.extend(parse_ical_dates(&property.value)),
+                    _ => {}
+                }
+            }
+            recurrence.explain()
+        })
+        .collect()
+}
+
+// Explains the recurrenceRules, excludedRecurrenceRules and the added or
+// excluded dates of the recurrenceOverrides of every event and task.
+pub fn from_jscalendar(source: &str) -> Vec<Explanation> {
+    let Ok(value) = serde_json::from_str::<Value>(source) else {
+        return vec![];
+    };
+    let mut entries = Vec::new();
+    collect_entries(&value, &mut entries);
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let rules = |key: &str| match entry.get(key) {
+                Some(Value::Array(rules)) => rules.iter().filter_map(json_rule).collect(),
+                Some(rule) => json_rule(rule).into_iter().collect(),
+                None => vec![],
+            };
+            let mut recurrence = Recurrence {
+                title: entry
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                start: entry
+                    .get("start")
+                    .and_then(Value::as_str)
+                    .and_then(parse_local_date_time),
+                rules: rules("recurrenceRules")
+                    .into_iter()
+                    .chain(rules("recurrenceRule"))
+                    .collect(),
+                excluded_rules: rules("excludedRecurrenceRules"),
+                ..Default::default()
+            };
+            // Empty patches add an occurrence; other patches modify one.
+            for (date, patch) in entry
+                .get("recurrenceOverrides")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                let Some(date) = parse_local_date_time(date) else {
+                    continue;
+                };
+                if patch.get("excluded").and_then(Value::as_bool) == Some(true) {
+                    recurrence.removed.push(date);
+                } else if patch.as_object().is_some_and(Map::is_empty) {
+                    recurrence.added.push(date);
+                }
+            }
+            recurrence.explain()
+        })
+        .collect()
+}
+
+// A single RRULE value in words, such as the recurrence of an AVAILABLE
+// component.
+pub fn explain_rrule(value: &str, start: Option<NaiveDateTime>) -> String {
+    capitalize(&rule_text(&parse_rrule(value), start))
+}
+
+pub fn ordinal(n: i64) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+impl Recurrence {
+    fn explain(mut self) -> Option<Explanation> {
+        if self.rules.is_empty() && self.added.is_empty() {
+            return None;
+        }
+        let year = self.start.map(|start| start.year());
+        let mut text = self
+            .rules
+            .iter()
+            .map(|rule| rule_text(rule, self.start))
+            .collect::<Vec<_>>();
+        if text.is_empty() {
+            text.push(format!("on {}", dates_text(&mut self.added, year)));
+        } else if !self.added.is_empty() {
+            text.push(format!("also on {}", dates_text(&mut self.added, year)));
+        }
+        let mut except = self
+            .excluded_rules
+            .iter()
+            .map(|rule| rule_text(rule, self.start))
+            .collect::<Vec<_>>();
+        if !self.removed.is_empty() {
+            except.push(dates_text(&mut self.removed, year));
+        }
+        let mut text = text.join(", ");
+        if !except.is_empty() {
+            text = format!("{}, except {}", text, join_and(except));
+        }
+        Some(Explanation {
+            title: if self.title.is_empty() {
+                "(No title)".to_string()
+            } else {
+                self.title
+            },
+            text: capitalize(&text),
+        })
+    }
+}
+
+fn rule_text(rule: &Rule, start: Option<NaiveDateTime>) -> String {
+    let unit = match rule.frequency.as_str() {
+        "yearly" => "year",
+        "monthly" => "month",
+        "weekly" => "week",
+        "daily" => "day",
+        "hourly" => "hour",
+        "minutely" => "minute",
+        "secondly" => "second",
+        _ => return "on an unknown schedule".to_string(),
+    };
+    let every = match rule.interval {
+        0 | 1 => format!("every {}", unit),
+        2 => format!("every other {}", unit),
+        interval => format!("every {} {}s", interval, unit),
+    };
+    let plain_days = rule
+        .by_day
+        .iter()
+        .filter(|(nth, _)| nth.is_none())
+        .map(|(_, day)| day.as_str())
+        .collect::<Vec<_>>();
+    let positional = positional_days(rule);
+    let months = join_and(
+        rule.by_month
+            .iter()
+            .map(|month| month_name(month))
+            .collect(),
+    );
+
+    let mut text = match rule.frequency.as_str() {
+        "yearly" if !positional.is_empty() && !rule.by_month.is_empty() => {
+            format!("{} on the {} of {}", every, positional, months)
+        }
+        "yearly" if !positional.is_empty() => {
+            format!("{} on the {} of the year", every, positional)
+        }
+        "yearly" if !rule.by_month_day.is_empty() && rule.by_month.len() == 1 => format!(
+            "{} on {} {}",
+            every,
+            months,
+            join_and(
+                rule.by_month_day
+                    .iter()
+                    .map(|day| day.to_string())
+                    .collect()
+            )
+        ),
+        "yearly" if !rule.by_month_day.is_empty() => format!(
+            "{} on the {} of {}",
+            every,
+            month_days(&rule.by_month_day),
+            if months.is_empty() {
+                "every month".to_string()
+            } else {
+                months.clone()
+            }
+        ),
+        "yearly" if !rule.by_year_day.is_empty() => format!(
+            "{} on the {} of the year",
+            every,
+            join_and(
+                rule.by_year_day
+                    .iter()
+                    .map(|day| nth_text(*day, "day"))
+                    .collect()
+            )
+        ),
+        "yearly" if !rule.by_week_no.is_empty() => format!(
+            "{} in week {}",
+            every,
+            join_and(rule.by_week_no.iter().map(i32::to_string).collect())
+        ),
+        "yearly" if !rule.by_month.is_empty() => format!("{} in {}", every, months),
+        "yearly" if plain_days.is_empty() => match start {
+            Some(start) => format!("{} on {}", every, start.format("%B %-d")),
+            None => every.clone(),
+        },
+        "monthly" if !positional.is_empty() => match rule.interval {
+            0 | 1 => format!("every {} of the month", positional),
+            _ => format!("{} on the {}", every, positional),
+        },
+        "monthly" if !rule.by_month_day.is_empty() => {
+            format!("{} on the {}", every, month_days(&rule.by_month_day))
+        }
+        "monthly" if !plain_days.is_empty() => {
+            format!("{} on {}", every, days_text(&plain_days, true))
+        }
+        "monthly" => match start {
+            Some(start) => format!("{} on the {}", every, ordinal(start.day() as i64)),
+            None => every.clone(),
+        },
+        "weekly" | "daily" if !plain_days.is_empty() => match rule.interval {
+            0 | 1 => format!("every {}", days_text(&plain_days, false)),
+            _ => format!("{} on {}", every, days_text(&plain_days, true)),
+        },
+        "weekly" => match start {
+            Some(start) => format!("{} on {}", every, day_name(weekday_code(start.date()))),
+            None => every.clone(),
+        },
+        _ => every.clone(),
+    };
+
+    // Filters that narrow a frequency finer than the one they name.
+    if !rule.by_month.is_empty() && !matches!(rule.frequency.as_str(), "yearly") {
+        text = format!("{} in {}", text, months);
+    }
+    if !rule.by_month_day.is_empty() && !matches!(rule.frequency.as_str(), "yearly" | "monthly") {
+        text = format!(
+            "{} on the {} of the month",
+            text,
+            month_days(&rule.by_month_day)
+        );
+    }
+    if !plain_days.is_empty()
+        && !matches!(rule.frequency.as_str(), "monthly" | "weekly" | "daily")
+        && positional.is_empty()
+    {
+        text = format!("{} on {}", text, days_text(&plain_days, true));
+    }
+    if let Some(times) = times_text(rule, start) {
+        text = format!("{} {}", text, times);
+    }
+
+    if let Some(count) = rule.count {
+        text = match count {
+            1 => format!("{}, once", text),
+            count => format!("{}, {} times", text, count),
+        };
+    } else if let Some(until) = rule.until {
+        text = format!("{} until {}", text, until.format("%b %-d, %Y"));
+    }
+    text
+}
+
+// Days picked by their position in the month or year, such as "2nd Tuesday"
+// or, with BYSETPOS, "last weekday".
+fn positional_days(rule: &Rule) -> String {
+    if !rule.by_set_position.is_empty() && !rule.by_day.is_empty() {
+        let days = rule
+            .by_day
+            .iter()
+            .map(|(_, day)| day.as_str())
+            .collect::<Vec<_>>();
+        let kind = if is_set(&days, &WEEKDAYS) {
+            "weekday".to_string()
+        } else if is_set(&days, &WEEKEND) {
+            "weekend day".to_string()
+        } else if days.len() == 7 {
+            "day".to_string()
+        } else {
+            days.iter()
+                .map(|day| day_name(day))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        let positions = rule
+            .by_set_position
+            .iter()
+            .map(|position| nth_text(*position, ""))
+            .map(|position| position.trim_end().to_string())
+            .collect::<Vec<_>>();
+        return format!("{} {}", join_and(positions), kind);
+    }
+    join_and(
+        rule.by_day
+            .iter()
+            .filter_map(|(nth, day)| Some(nth_text((*nth)?, day_name(day))))
+            .collect(),
+    )
+}
+
+fn times_text(rule: &Rule, start: Option<NaiveDateTime>) -> Option<String> {
+    if !rule.by_hour.is_empty() {
+        let minutes = if rule.by_minute.is_empty() {
+            vec![start.map_or(0, |start| start.minute() as i32)]
+        } else {
+            rule.by_minute.clone()
+        };
+        let times = rule
+            .by_hour
+            .iter()
+            .flat_map(|hour| {
+                minutes
+                    .iter()
+                    .map(move |minute| format!("{}:{:02}", hour, minute))
+            })
+            .collect::<Vec<_>>();
+        Some(if times.len() > 6 {
+            format!("at {} times a day", times.len())
+        } else {
+            format!("at {}", join_and(times))
+        })
+    } else if !rule.by_minute.is_empty() {
+        Some(format!(
+            "at {} minutes past the hour",
+            join_and(rule.by_minute.iter().map(i32::to_string).collect())
+        ))
+    } else {
+        None
+    }
+}
+
+fn parse_rrule(value: &str) -> Rule {
+    let mut rule = Rule {
+        interval: 1,
+        ..Default::default()
+    };
+    let numbers = |value: &str| {
+        value
+            .split(',')
+            .filter_map(|number| number.trim().trim_start_matches('+').parse().ok())
+            .collect::<Vec<_>>()
+    };
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => rule.frequency = value.trim().to_ascii_lowercase(),
+            "INTERVAL" => rule.interval = value.trim().parse().unwrap_or(1),
+            "COUNT" => rule.count = value.trim().parse().ok(),
+            "UNTIL" => rule.until = parse_ical_date(value),
+            "BYDAY" => {
+                rule.by_day = value
+                    .split(',')
+                    .filter_map(|day| {
+                        let (nth, name) = contentline::parse_byday(day)?;
+                        Some((nth, name.to_ascii_lowercase()))
+                    })
+                    .collect()
+            }
+            "BYMONTHDAY" => rule.by_month_day = numbers(value),
+            "BYMONTH" => {
+                rule.by_month = value
+                    .split(',')
+                    .map(|month| month.trim().to_string())
+                    .collect()
+            }
+            "BYYEARDAY" => rule.by_year_day = numbers(value),
+            "BYWEEKNO" => rule.by_week_no = numbers(value),
+            "BYSETPOS" => rule.by_set_position = numbers(value),
+            "BYHOUR" => rule.by_hour = numbers(value),
+            "BYMINUTE" => rule.by_minute = numbers(value),
+            _ => {}
+        }
+    }
+    rule
+}
+
+// RFC 8984 section 4.3.3.
+fn json_rule(value: &Value) -> Option<Rule> {
+    let rule = value.as_object()?;
+    let numbers = |key: &str| {
+        rule.get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_i64)
+            .filter_map(|number| number.try_into().ok())
+            .collect::<Vec<_>>()
+    };
+    Some(Rule {
+        frequency: rule.get("frequency")?.as_str()?.to_ascii_lowercase(),
+        interval: rule
+            .get("interval")
+            .and_then(Value::as_u64)
+            .and_then(|interval| interval.try_into().ok())
+            .unwrap_or(1),
+        count: rule
+            .get("count")
+            .and_then(Value::as_u64)
+            .and_then(|count| count.try_into().ok()),
+        until: rule
+            .get("until")
+            .and_then(Value::as_str)
+            .and_then(parse_local_date_time),
+        by_day: rule
+            .get("byDay")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|day| {
+                Some((
+                    day.get("nthOfPeriod")
+                        .and_then(Value::as_i64)
+                        .and_then(|nth| nth.try_into().ok()),
+                    day.get("day")?.as_str()?.to_ascii_lowercase(),
+                ))
+            })
+            .collect(),
+        by_month_day: numbers("byMonthDay"),
+        by_month: rule
+            .get("byMonth")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|month| month.as_str().map(str::to_string))
+            .collect(),
+        by_year_day: numbers("byYearDay"),
+        by_week_no: numbers("byWeekNo"),
+        by_set_position: numbers("bySetPosition"),
+        by_hour: numbers("byHour"),
+        by_minute: numbers("byMinute"),
+    })
+}
+
+fn collect_entries<'x>(value: &'x Value, entries: &mut Vec<&'x Map<String, Value>>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    match object.get("@type").and_then(Value::as_str) {
+        Some("Event" | "Task") => entries.push(object),
+        Some("Group") => {
+            for entry in object
+                .get("entries")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                collect_entries(entry, entries);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_ical_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_end_matches('Z');
+    if value.len() >= 15 {
+        contentline::parse_datetime(value.get(..15)?)
+    } else {
+        contentline::parse_date(value.get(..8)?)?.and_hms_opt(0, 0, 0)
+    }
+}
+
+// Periods are reduced to their start.
+fn parse_ical_dates(value: &str) -> Vec<NaiveDateTime> {
+    value
+        .split(',')
+        .filter_map(|date| parse_ical_date(date.split('/').next().unwrap_or_default()))
+        .collect()
+}
+
+fn parse_local_date_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+// Dates in the year the recurrence starts are written without it.
+fn dates_text(dates: &mut Vec<NaiveDateTime>, year: Option<i32>) -> String {
+    dates.sort();
+    dates.dedup_by_key(|date| date.date());
+    join_and(
+        dates
+            .iter()
+            .map(|date| {
+                if Some(date.year()) == year {
+                    date.format("%b %-d").to_string()
+                } else {
+                    date.format("%b %-d, %Y").to_string()
+                }
+            })
+            .collect(),
+    )
+}
+
+fn month_days(days: &[i32]) -> String {
+    join_and(
+        days.iter()
+            .map(|day| match day {
+                -1 => "last day".to_string(),
+                day if *day < 0 => format!("{} to last day", ordinal(-*day as i64)),
+                day => ordinal(*day as i64),
+            })
+            .collect(),
+    )
+}
+
+fn nth_text(nth: i32, what: &str) -> String {
+    match nth {
+        -1 => format!("last {}", what),
+        nth if nth < 0 => format!("{} to last {}", ordinal(-nth as i64), what),
+        nth => format!("{} {}", ordinal(nth as i64), what),
+    }
+}
+
+// All weekdays read as "weekday"; otherwise days are listed, in the plural
+// when they repeat within a longer period.
+fn days_text(days: &[&str], plural: bool) -> String {
+    let (name, suffix) = if is_set(days, &WEEKDAYS) {
+        ("weekday".to_string(), plural)
+    } else if is_set(days, &WEEKEND) {
+        ("weekend day".to_string(), plural)
+    } else {
+        return join_and(
+            days.iter()
+                .map(|day| {
+                    if plural {
+                        format!("{}s", day_name(day))
+                    } else {
+                        day_name(day).to_string()
+                    }
+                })
+                .collect(),
+        );
+    };
+    if suffix {
+        format!("{}s", name)
+    } else {
+        name
+    }
+}
+
+fn is_set(days: &[&str], set: &[&str]) -> bool {
+    days.len() == set.len() && set.iter().all(|day| days.contains(day))
+}
+
+fn day_name(day: &str) -> &'static str {
+    match day {
+        "mo" => "Monday",
+        "tu" => "Tuesday",
+        "we" => "Wednesday",
+        "th" => "Thursday",
+        "fr" => "Friday",
+        "sa" => "Saturday",
+        "su" => "Sunday",
+        _ => "day",
+    }
+}
+
+fn weekday_code(date: NaiveDate) -> &'static str {
+    ["mo", "tu", "we", "th", "fr", "sa", "su"][date.weekday().num_days_from_monday() as usize]
+}
+
+// Leap months, written with an "L" suffix, are named after the month they
+// follow.
+fn month_name(month: &str) -> String {
+    let (number, leap) = match month.strip_suffix(['L', 'l']) {
+        Some(number) => (number, true),
+        None => (month, false),
+    };
+    let name = number
+        .parse::<u32>()
+        .ok()
+        .and_then(|number| NaiveDate::from_ymd_opt(2000, number, 1))
+        .map(|date| date.format("%B").to_string())
+        .unwrap_or_else(|| month.to_string());
+    if leap {
+        format!("leap {}", name)
+    } else {
+        name
+    }
+}
+
+fn join_and(mut items: Vec<String>) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items.remove(0),
+        _ => {
+            let last = items.pop().unwrap_or_default();
+            format!("{} and {}", items.join(", "), last)
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(lines: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Standup\r\n{}END:VEVENT\r\nEND:VCALENDAR\r\n",
+            lines
+        )
+    }
+
+    #[test]
+    fn skips_non_ascii_byday() {
+        let rule = parse_rrule("FREQ=MONTHLY;BYDAY=1€,2S€U,ÖA,-1FR");
+        assert_eq!(rule.by_day, vec![(Some(-1), "fr".to_string())]);
+        let explanations = from_icalendar(&event(
+            "DTSTART:20170216T090000\r\nRRULE:FREQ=WEEKLY;BYDAY=1€\r\n",
+        ));
+        assert_eq!(explanations.len(), 1);
+    }
+
+    #[test]
+    fn ignores_non_ascii_dates() {
+        assert_eq!(parse_ical_date("20170216T090😀000"), None);
+        assert_eq!(parse_ical_date("2017€216T090000"), None);
+        assert_eq!(
+            parse_ical_date("20170216T090000Z"),
+            NaiveDate::from_ymd_opt(2017, 2, 16).and_then(|date| date.and_hms_opt(9, 0, 0))
+        );
+        let explanations = from_icalendar(&event(
+            "DTSTART:20170216T090😀000\r\nRRULE:FREQ=DAILY;UNTIL=20170216T090😀000\r\nEXDATE:2017€216T090000\r\n",
+        ));
+        assert_eq!(explanations.len(), 1);
+    }
+}
@@ -0,0 +1,1356 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Randomized but valid iCalendar, vCard, JSCalendar and JSContact documents,
+// so that every sample exercises different conversion paths: recurrence
+// rules, time zones, attendees, alarms and less common properties. The same
+// seed always builds the same document.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    contentline::{self, escape, fold_line},
+    Format,
+};
+
+pub const FORMATS: [Format; 4] = [
+    Format::ICalendar,
+    Format::VCard,
+    Format::JSCalendar,
+    Format::JSContact,
+];
+
+const PRODID: &str = "-//Stalwart Labs LLC//jmap-convert sample generator//EN";
+const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+// Zones with half-hour, quarter-hour and southern hemisphere offsets.
+const TIMEZONES: &[&str] = &[
+    "Europe/Berlin",
+    "Europe/London",
+    "America/New_York",
+    "America/St_Johns",
+    "America/Sao_Paulo",
+    "Asia/Tokyo",
+    "Asia/Kolkata",
+    "Asia/Kathmandu",
+    "Australia/Lord_Howe",
+    "Pacific/Chatham",
+    "Pacific/Apia",
+    "Africa/Casablanca",
+];
+const TITLES: &[&str] = &[
+    "Team sync",
+    "Quarterly review",
+    "Dentist",
+    "Réunion budgétaire",
+    "Yoga 🧘",
+    "Sprint planning; retro",
+    "Lunch with Åsa, Björn & Zoë",
+    "設計レビュー",
+    "Board meeting: Q3 \"final\" numbers",
+    "Release party 🎉",
+];
+const DESCRIPTIONS: &[&str] = &[
+    "Agenda:\n1. Status\n2. Risks, blockers\n3. Next steps",
+    "Bring the signed forms; parking is on level −2.",
+    "Dial-in details are in the invitation. Пожалуйста, не опаздывайте.",
+    "A long description that runs well past the seventy-five octet line limit so that it has to be folded over several lines, with a few multi-byte characters like é, ß and 漢字 near the fold.",
+];
+const PLACES: &[(&str, Option<(f64, f64)>)] = &[
+    ("Conference room 4.01", None),
+    (
+        "Café Einstein, Unter den Linden 42, Berlin",
+        Some((52.5163, 13.3889)),
+    ),
+    ("Shibuya Crossing, Tokyo", Some((35.6595, 139.7005))),
+    ("Home office", None),
+    ("Sydney Opera House", Some((-33.8568, 151.2153))),
+];
+const PEOPLE: &[(&str, &str)] = &[
+    ("Ada Lovelace", "ada@example.com"),
+    ("José Álvarez", "jose.alvarez@example.org"),
+    ("O'Brien, Seán", "sean@example.net"),
+    ("李小龍", "bruce.lee@example.cn"),
+    ("Nguyễn Văn An", "an.nguyen@example.vn"),
+    ("Олег Петров", "oleg@example.ru"),
+    ("Amélie Poulain", "amelie@example.fr"),
+    ("Conference Room B", "room-b@example.com"),
+    ("Zoë Ångström", "zoe@example.se"),
+];
+const CATEGORIES: &[&str] = &["Work", "Personal", "Travel", "Health", "Ünïcödé"];
+const PARTICIPATION: &[&str] = &["accepted", "declined", "tentative", "needs-action"];
+const GIVEN_NAMES: &[&str] = &["Ada", "José", "Seán", "小龍", "An", "Олег", "Amélie", "Zoë"];
+const SURNAMES: &[&str] = &[
+    "Lovelace",
+    "Álvarez",
+    "O'Brien",
+    "李",
+    "Nguyễn",
+    "Петров",
+    "Poulain",
+    "Ångström",
+];
+const ORGANIZATIONS: &[&str] = &[
+    "Example Corp.",
+    "Stalwart Labs",
+    "Société Générale d'Exemple",
+    "株式会社サンプル",
+];
+const JOB_TITLES: &[&str] = &["Engineer", "Head of Sales", "Directeur, R&D", "CTO"];
+// Street, locality, region, postcode, country, its ISO code and the
+// coordinates of the place.
+type Address = (
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static str,
+    (f64, f64),
+);
+
+const ADDRESSES: &[Address] = &[
+    (
+        "Unter den Linden 42",
+        "Berlin",
+        "",
+        "10117",
+        "Germany",
+        "DE",
+        (52.5170, 13.3889),
+    ),
+    (
+        "1600 Amphitheatre Pkwy",
+        "Mountain View",
+        "CA",
+        "94043",
+        "USA",
+        "US",
+        (37.4220, -122.0841),
+    ),
+    (
+        "2-21-1 Shibuya",
+        "Tokyo",
+        "",
+        "150-0002",
+        "Japan",
+        "JP",
+        (35.6580, 139.7016),
+    ),
+    (
+        "10 Downing Street",
+        "London",
+        "",
+        "SW1A 2AA",
+        "United Kingdom",
+        "GB",
+        (51.5034, -0.1276),
+    ),
+    (
+        "Rua Augusta, 1500; apt. 3",
+        "São Paulo",
+        "SP",
+        "01304-001",
+        "Brazil",
+        "BR",
+        (-23.5558, -46.6623),
+    ),
+];
+const PHONES: &[&str] = &[
+    "+49 30 1234567",
+    "+1-650-253-0000",
+    "+81 3 1234 5678",
+    "+44 20 7946 0958",
+    "+55 11 91234-5678",
+];
+
+pub struct Sample {
+    pub seed: u64,
+    pub format: Format,
+    pub text: String,
+}
+
+// Builds a document in `format`, or in a format picked from the seed.
+pub fn generate(seed: u64, format: Option<Format>) -> Sample {
+    let mut rng = Rng::new(seed);
+    // Drawn either way, so that a seed builds the same content whether the
+    // format was picked from it or asked for.
+    let picked = rng.pick(&FORMATS);
+    let format = format.unwrap_or(picked);
+    let text = match format {
+        Format::ICalendar | Format::JCal | Format::JSCalendar => {
+            let events = (0..rng.range(1, 3))
+                .map(|_| EventSpec::random(&mut rng))
+                .collect::<Vec<_>>();
+            if format == Format::JSCalendar {
+                jscalendar(&events, rng.uuid())
+            } else {
+                icalendar(&events)
+            }
+        }
+        Format::VCard | Format::JCard | Format::JSContact => {
+            let count = rng.range(1, 3);
+            let mut contacts = (0..count)
+                .map(|_| ContactSpec::random(&mut rng))
+                .collect::<Vec<_>>();
+            // A group of the other contacts.
+            if count > 1 && rng.chance(30) {
+                let members = contacts.iter().map(|contact| contact.uid.clone()).collect();
+                contacts.push(ContactSpec::group(&mut rng, members));
+            }
+            if format == Format::JSContact {
+                jscontact(&contacts)
+            } else {
+                vcard(&contacts, rng.chance(25))
+            }
+        }
+    };
+    Sample { seed, format, text }
+}
+
+// Accepts the decimal seeds shown with each sample.
+pub fn parse_seed(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+// SplitMix64, so that a seed builds the same document on every platform and
+// release.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Inclusive of both ends.
+    fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + (self.next() % u64::from(max - min + 1)) as u32
+    }
+
+    fn chance(&mut self, percent: u32) -> bool {
+        self.range(1, 100) <= percent
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+
+    fn uuid(&mut self) -> String {
+        let (high, low) = (self.next(), self.next());
+        format!(
+            "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xFFFF,
+            high & 0xFFF,
+            0x8000 | (low >> 48) & 0x3FFF,
+            low & 0xFFFF_FFFF_FFFF
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TimeMode {
+    Utc,
+    Zoned(&'static str),
+    Floating,
+    AllDay,
+}
+
+struct Rule {
+    frequency: &'static str,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    // Ordinal and index into WEEKDAYS.
+    by_day: Vec<(Option<i32>, usize)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    by_week_no: Vec<i32>,
+    by_set_position: Vec<i32>,
+    week_start: Option<usize>,
+}
+
+struct Person {
+    name: &'static str,
+    email: &'static str,
+    status: &'static str,
+    resource: bool,
+}
+
+enum Alarm {
+    Before(i64),
+    At(NaiveDateTime),
+    Email(i64),
+}
+
+struct EventSpec {
+    uid: String,
+    task: bool,
+    title: &'static str,
+    description: Option<&'static str>,
+    start: NaiveDateTime,
+    mode: TimeMode,
+    minutes: i64,
+    rule: Option<Rule>,
+    excluded: Option<NaiveDateTime>,
+    // Start of the occurrence moved by an override.
+    overridden: Option<NaiveDateTime>,
+    location: Option<(&'static str, Option<(f64, f64)>)>,
+    organizer: Option<(&'static str, &'static str)>,
+    attendees: Vec<Person>,
+    alarms: Vec<Alarm>,
+    categories: Vec<&'static str>,
+    priority: Option<u32>,
+    private: bool,
+    free: bool,
+    color: Option<&'static str>,
+    conference: Option<String>,
+    vendor: bool,
+}
+
+impl EventSpec {
+    fn random(rng: &mut Rng) -> Self {
+        let mode = match rng.range(0, 9) {
+            0..=1 => TimeMode::Utc,
+            2 => TimeMode::Floating,
+            3 => TimeMode::AllDay,
+            _ => TimeMode::Zoned(rng.pick(TIMEZONES)),
+        };
+        let date = NaiveDate::from_ymd_opt(
+            rng.range(2024, 2027) as i32,
+            rng.range(1, 12),
+            rng.range(1, 28),
+        )
+        .unwrap_or_default();
+        let time = match mode {
+            TimeMode::AllDay => NaiveTime::MIN,
+            _ => NaiveTime::from_hms_opt(rng.range(6, 20), rng.pick(&[0, 15, 30, 45]), 0)
+                .unwrap_or_default(),
+        };
+        let start = date.and_time(time);
+        let minutes = match mode {
+            TimeMode::AllDay => 1440 * i64::from(rng.range(1, 3)),
+            _ => rng.pick(&[15, 30, 45, 60, 90, 120, 480]),
+        };
+        let rule = rng.chance(60).then(|| Rule::random(rng, start));
+        // Plain daily rules have an easily known second occurrence to exclude
+        // or move.
+        let second = rule
+            .as_ref()
+            .filter(|rule| rule.frequency == "DAILY" && rule.is_plain())
+            .map(|rule| start + Duration::days(i64::from(rule.interval)));
+        let (excluded, overridden) = match second {
+            Some(second) if rng.chance(50) => (Some(second), None),
+            Some(second) => (None, Some(second)),
+            None => (None, None),
+        };
+        let organizer = rng.chance(50).then(|| rng.pick(PEOPLE));
+        let attendees = match organizer {
+            Some(_) => (0..rng.range(1, 8))
+                .map(|_| {
+                    let (name, email) = rng.pick(PEOPLE);
+                    Person {
+                        name,
+                        email,
+                        status: rng.pick(PARTICIPATION),
+                        resource: name.starts_with("Conference"),
+                    }
+                })
+                .fold(Vec::<Person>::new(), |mut people, person| {
+                    if people.iter().all(|other| other.email != person.email) {
+                        people.push(person);
+                    }
+                    people
+                }),
+            None => vec![],
+        };
+        let alarms = (0..rng.range(0, 2))
+            .map(|_| match rng.range(0, 4) {
+                0 => Alarm::At(start - Duration::hours(i64::from(rng.range(1, 48)))),
+                1 => Alarm::Email(i64::from(rng.range(1, 7)) * 1440),
+                _ => Alarm::Before(rng.pick(&[0, 5, 10, 15, 30, 60])),
+            })
+            .collect();
+        let categories = (0..rng.range(0, 2)).map(|_| rng.pick(CATEGORIES)).fold(
+            Vec::new(),
+            |mut categories, category| {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+                categories
+            },
+        );
+
+        EventSpec {
+            uid: rng.uuid(),
+            task: rng.chance(15),
+            title: rng.pick(TITLES),
+            description: rng.chance(50).then(|| rng.pick(DESCRIPTIONS)),
+            start,
+            mode,
+            minutes,
+            rule,
+            excluded,
+            overridden,
+            location: rng.chance(60).then(|| rng.pick(PLACES)),
+            organizer,
+            attendees,
+            alarms,
+            categories,
+            priority: rng.chance(30).then(|| rng.range(1, 9)),
+            private: rng.chance(20),
+            free: rng.chance(20),
+            color: rng
+                .chance(20)
+                .then(|| rng.pick(&["slateblue", "tomato", "seagreen"])),
+            conference: rng
+                .chance(25)
+                .then(|| format!("https://meet.example.com/{}", &rng.uuid()[..8])),
+            vendor: rng.chance(40),
+        }
+    }
+
+    // Where the overridden occurrence at `start` moves to.
+    fn moved(&self, start: NaiveDateTime) -> NaiveDateTime {
+        match self.mode {
+            TimeMode::AllDay => start + Duration::days(1),
+            _ => start + Duration::hours(1),
+        }
+    }
+}
+
+impl Rule {
+    fn random(rng: &mut Rng, start: NaiveDateTime) -> Self {
+        let frequency = rng.pick(&["DAILY", "WEEKLY", "WEEKLY", "MONTHLY", "YEARLY"]);
+        let weekday = start.weekday().num_days_from_monday() as usize;
+        let mut rule = Rule {
+            frequency,
+            interval: if rng.chance(30) { rng.range(2, 4) } else { 1 },
+            count: None,
+            until: None,
+            by_day: vec![],
+            by_month_day: vec![],
+            by_month: vec![],
+            by_week_no: vec![],
+            by_set_position: vec![],
+            week_start: None,
+        };
+        match frequency {
+            "DAILY" if rng.chance(30) => rule.by_day = (0..5).map(|day| (None, day)).collect(),
+            "WEEKLY" => {
+                rule.by_day = (0..7)
+                    .filter(|day| *day == weekday || rng.chance(25))
+                    .map(|day| (None, day))
+                    .collect();
+                if rule.interval > 1 && rng.chance(50) {
+                    rule.week_start = Some(6);
+                }
+            }
+            "MONTHLY" => match rng.range(0, 3) {
+                0 => rule.by_month_day = vec![-1],
+                1 => rule.by_day = vec![(Some(rng.pick(&[1, 2, 3, 4, -1])), weekday)],
+                2 => {
+                    rule.by_day = (0..5).map(|day| (None, day)).collect();
+                    rule.by_set_position = vec![rng.pick(&[1, -1])];
+                }
+                _ => rule.by_month_day = vec![start.day() as i32],
+            },
+            "YEARLY" => match rng.range(0, 2) {
+                0 => {
+                    rule.by_month = vec![start.month()];
+                    rule.by_day = vec![(Some(-1), 6)];
+                }
+                1 => {
+                    rule.by_week_no = vec![rng.range(1, 52) as i32];
+                    rule.by_day = vec![(None, 0)];
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        match rng.range(0, 2) {
+            0 => rule.count = Some(rng.range(2, 20)),
+            1 => rule.until = Some(start + Duration::days(i64::from(rng.range(30, 400)))),
+            _ => {}
+        }
+        rule
+    }
+
+    fn is_plain(&self) -> bool {
+        self.by_day.is_empty()
+            && self.by_month_day.is_empty()
+            && self.by_month.is_empty()
+            && self.by_week_no.is_empty()
+    }
+
+    fn to_rrule(&self, mode: TimeMode) -> String {
+        let mut parts = vec![format!("FREQ={}", self.frequency)];
+        if self.interval > 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", ical_date_time(until, mode, true)));
+        }
+        let list = |values: Vec<String>| values.join(",");
+        if !self.by_day.is_empty() {
+            parts.push(format!(
+                "BYDAY={}",
+                list(
+                    self.by_day
+                        .iter()
+                        .map(|(nth, day)| match nth {
+                            Some(nth) => format!("{}{}", nth, WEEKDAYS[*day]),
+                            None => WEEKDAYS[*day].to_string(),
+                        })
+                        .collect()
+                )
+            ));
+        }
+        for (name, values) in [
+            ("BYMONTHDAY", &self.by_month_day),
+            ("BYWEEKNO", &self.by_week_no),
+            ("BYSETPOS", &self.by_set_position),
+        ] {
+            if !values.is_empty() {
+                parts.push(format!(
+                    "{}={}",
+                    name,
+                    list(values.iter().map(i32::to_string).collect())
+                ));
+            }
+        }
+        if !self.by_month.is_empty() {
+            parts.push(format!(
+                "BYMONTH={}",
+                list(self.by_month.iter().map(u32::to_string).collect())
+            ));
+        }
+        if let Some(day) = self.week_start {
+            parts.push(format!("WKST={}", WEEKDAYS[day]));
+        }
+        parts.join(";")
+    }
+
+    fn to_json(&self) -> Value {
+        let mut rule = Map::new();
+        rule.insert("@type".into(), json!("RecurrenceRule"));
+        rule.insert(
+            "frequency".into(),
+            json!(self.frequency.to_ascii_lowercase()),
+        );
+        if self.interval > 1 {
+            rule.insert("interval".into(), json!(self.interval));
+        }
+        if let Some(count) = self.count {
+            rule.insert("count".into(), json!(count));
+        }
+        if let Some(until) = self.until {
+            rule.insert("until".into(), json!(local_date_time(until)));
+        }
+        if !self.by_day.is_empty() {
+            let days = self
+                .by_day
+                .iter()
+                .map(|(nth, day)| {
+                    let mut day = json!({
+                        "@type": "NDay",
+                        "day": WEEKDAYS[*day].to_ascii_lowercase(),
+                    });
+                    if let Some(nth) = nth {
+                        day["nthOfPeriod"] = json!(nth);
+                    }
+                    day
+                })
+                .collect::<Vec<_>>();
+            rule.insert("byDay".into(), json!(days));
+        }
+        if !self.by_month_day.is_empty() {
+            rule.insert("byMonthDay".into(), json!(self.by_month_day));
+        }
+        if !self.by_month.is_empty() {
+            let months = self.by_month.iter().map(u32::to_string).collect::<Vec<_>>();
+            rule.insert("byMonth".into(), json!(months));
+        }
+        if !self.by_week_no.is_empty() {
+            rule.insert("byWeekNo".into(), json!(self.by_week_no));
+        }
+        if !self.by_set_position.is_empty() {
+            rule.insert("bySetPosition".into(), json!(self.by_set_position));
+        }
+        if let Some(day) = self.week_start {
+            rule.insert(
+                "firstDayOfWeek".into(),
+                json!(WEEKDAYS[day].to_ascii_lowercase()),
+            );
+        }
+        Value::Object(rule)
+    }
+}
+
+fn icalendar(events: &[EventSpec]) -> String {
+    let mut ical = String::new();
+    fold_line(&mut ical, "BEGIN:VCALENDAR");
+    fold_line(&mut ical, "VERSION:2.0");
+    fold_line(&mut ical, &format!("PRODID:{}", PRODID));
+    if events.iter().any(|event| event.organizer.is_some()) {
+        fold_line(&mut ical, "METHOD:REQUEST");
+    }
+    for event in events {
+        let component = if event.task { "VTODO" } else { "VEVENT" };
+        fold_line(&mut ical, &format!("BEGIN:{}", component));
+        event_lines(&mut ical, event, None);
+        fold_line(&mut ical, &format!("END:{}", component));
+        if let Some(moved) = event.overridden {
+            fold_line(&mut ical, &format!("BEGIN:{}", component));
+            event_lines(&mut ical, event, Some(moved));
+            fold_line(&mut ical, &format!("END:{}", component));
+        }
+    }
+    fold_line(&mut ical, "END:VCALENDAR");
+    ical
+}
+
+// The properties of the event, or of its override for the occurrence at
+// `recurrence_id`.
+fn event_lines(ical: &mut String, event: &EventSpec, recurrence_id: Option<NaiveDateTime>) {
+    let mut line = |text: String| fold_line(ical, &text);
+    let mode = event.mode;
+    line(format!("UID:{}", event.uid));
+    line(format!(
+        "DTSTAMP:{}",
+        ical_date_time(event.start - Duration::days(30), TimeMode::Utc, false)
+    ));
+    let start = match recurrence_id {
+        Some(recurrence_id) => {
+            line(format!(
+                "RECURRENCE-ID{}:{}",
+                tz_param(mode),
+                ical_date_time(recurrence_id, mode, false)
+            ));
+            event.moved(recurrence_id)
+        }
+        None => event.start,
+    };
+    line(format!(
+        "DTSTART{}:{}",
+        tz_param(mode),
+        ical_date_time(start, mode, false)
+    ));
+    if event.task {
+        line(format!(
+            "DUE{}:{}",
+            tz_param(mode),
+            ical_date_time(start + Duration::minutes(event.minutes), mode, false)
+        ));
+    } else {
+        line(format!("DURATION:{}", duration(event.minutes)));
+    }
+    match recurrence_id {
+        Some(_) => line(format!(
+            "SUMMARY:{}",
+            escape(&format!("{} (moved)", event.title))
+        )),
+        None => line(format!("SUMMARY:{}", escape(event.title))),
+    }
+    if recurrence_id.is_none() {
+        if let Some(rule) = &event.rule {
+            line(format!("RRULE:{}", rule.to_rrule(mode)));
+        }
+        if let Some(excluded) = event.excluded {
+            line(format!(
+                "EXDATE{}:{}",
+                tz_param(mode),
+                ical_date_time(excluded, mode, false)
+            ));
+        }
+    }
+    if let Some(description) = event.description {
+        line(format!("DESCRIPTION:{}", escape(description)));
+    }
+    if let Some((name, coordinates)) = event.location {
+        line(format!("LOCATION:{}", escape(name)));
+        if let Some((lat, lon)) = coordinates {
+            line(format!("GEO:{};{}", lat, lon));
+        }
+    }
+    if let Some((name, email)) = event.organizer {
+        line(format!(
+            "ORGANIZER;CN={}:mailto:{}",
+            param_value(name),
+            email
+        ));
+    }
+    for attendee in &event.attendees {
+        line(format!(
+            "ATTENDEE;CN={};CUTYPE={};ROLE=REQ-PARTICIPANT;PARTSTAT={};RSVP=TRUE:mailto:{}",
+            param_value(attendee.name),
+            if attendee.resource {
+                "ROOM"
+            } else {
+                "INDIVIDUAL"
+            },
+            attendee.status.to_ascii_uppercase(),
+            attendee.email
+        ));
+    }
+    if !event.categories.is_empty() {
+        line(format!(
+            "CATEGORIES:{}",
+            event
+                .categories
+                .iter()
+                .map(|category| escape(category))
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+    }
+    if let Some(priority) = event.priority {
+        line(format!("PRIORITY:{}", priority));
+    }
+    if event.private {
+        line("CLASS:PRIVATE".to_string());
+    }
+    if event.free && !event.task {
+        line("TRANSP:TRANSPARENT".to_string());
+    }
+    if let Some(color) = event.color {
+        line(format!("COLOR:{}", color));
+    }
+    if let Some(url) = &event.conference {
+        line(format!(
+            "CONFERENCE;VALUE=URI;FEATURE=AUDIO,VIDEO;LABEL=Video call:{}",
+            url
+        ));
+    }
+    if event.vendor {
+        line("X-MICROSOFT-CDO-BUSYSTATUS:BUSY".to_string());
+        line("X-APPLE-TRAVEL-ADVISORY-BEHAVIOR:AUTOMATIC".to_string());
+    }
+    for alarm in &event.alarms {
+        line("BEGIN:VALARM".to_string());
+        match alarm {
+            Alarm::Before(minutes) => {
+                line("ACTION:DISPLAY".to_string());
+                line(format!("TRIGGER:-{}", duration(*minutes)));
+                line("DESCRIPTION:Reminder".to_string());
+            }
+            Alarm::At(when) => {
+                line("ACTION:DISPLAY".to_string());
+                line(format!(
+                    "TRIGGER;VALUE=DATE-TIME:{}",
+                    ical_date_time(*when, TimeMode::Utc, false)
+                ));
+                line("DESCRIPTION:Reminder".to_string());
+            }
+            Alarm::Email(minutes) => {
+                line("ACTION:EMAIL".to_string());
+                line(format!("TRIGGER;RELATED=START:-{}", duration(*minutes)));
+                line(format!("SUMMARY:{}", escape(event.title)));
+                line("DESCRIPTION:Coming up".to_string());
+                line(format!("ATTENDEE:mailto:{}", PEOPLE[0].1));
+            }
+        }
+        line("END:VALARM".to_string());
+    }
+}
+
+fn jscalendar(events: &[EventSpec], group_uid: String) -> String {
+    let mut entries = events.iter().map(event_json).collect::<Vec<_>>();
+    let root = if entries.len() == 1 {
+        entries.remove(0)
+    } else {
+        json!({
+            "@type": "Group",
+            "uid": group_uid,
+            "entries": entries,
+        })
+    };
+    serde_json::to_string_pretty(&root).unwrap_or_default()
+}
+
+fn event_json(event: &EventSpec) -> Value {
+    let mut object = Map::new();
+    let mut set = |key: &str, value: Value| {
+        object.insert(key.to_string(), value);
+    };
+    set("@type", json!(if event.task { "Task" } else { "Event" }));
+    set("uid", json!(event.uid));
+    set(
+        "updated",
+        json!(format!(
+            "{}Z",
+            (event.start - Duration::days(30)).format("%Y-%m-%dT%H:%M:%S")
+        )),
+    );
+    set("title", json!(event.title));
+    set("start", json!(local_date_time(event.start)));
+    match event.mode {
+        TimeMode::Utc => set("timeZone", json!("Etc/UTC")),
+        TimeMode::Zoned(tz) => set("timeZone", json!(tz)),
+        TimeMode::AllDay => set("showWithoutTime", json!(true)),
+        TimeMode::Floating => {}
+    }
+    if event.task {
+        set(
+            "due",
+            json!(local_date_time(
+                event.start + Duration::minutes(event.minutes)
+            )),
+        );
+    } else {
+        set("duration", json!(duration(event.minutes)));
+    }
+    if let Some(description) = event.description {
+        set("description", json!(description));
+    }
+    if let Some(rule) = &event.rule {
+        set("recurrenceRule", rule.to_json());
+    }
+    let mut overrides = Map::new();
+    if let Some(excluded) = event.excluded {
+        overrides.insert(local_date_time(excluded), json!({ "excluded": true }));
+    }
+    if let Some(moved) = event.overridden {
+        overrides.insert(
+            local_date_time(moved),
+            json!({
+                "title": format!("{} (moved)", event.title),
+                "start": local_date_time(event.moved(moved)),
+            }),
+        );
+    }
+    if !overrides.is_empty() {
+        set("recurrenceOverrides", Value::Object(overrides));
+    }
+    if let Some((name, coordinates)) = event.location {
+        let mut location = json!({ "@type": "Location", "name": name });
+        if let Some((lat, lon)) = coordinates {
+            location["coordinates"] = json!(format!("geo:{},{}", lat, lon));
+        }
+        set("locations", json!({ "loc1": location }));
+    }
+    if let Some(url) = &event.conference {
+        set(
+            "virtualLocations",
+            json!({
+                "video": {
+                    "@type": "VirtualLocation",
+                    "name": "Video call",
+                    "uri": url,
+                    "features": { "audio": true, "video": true },
+                }
+            }),
+        );
+    }
+    if let Some((name, email)) = event.organizer {
+        set(
+            "organizerCalendarAddress",
+            json!(format!("mailto:{}", email)),
+        );
+        let mut participants = Map::new();
+        participants.insert(
+            "organizer".into(),
+            json!({
+                "@type": "Participant",
+                "name": name,
+                "calendarAddress": format!("mailto:{}", email),
+                "roles": { "owner": true },
+            }),
+        );
+        for (index, attendee) in event.attendees.iter().enumerate() {
+            participants.insert(
+                format!("p{}", index + 1),
+                json!({
+                    "@type": "Participant",
+                    "name": attendee.name,
+                    "email": attendee.email,
+                    "calendarAddress": format!("mailto:{}", attendee.email),
+                    "kind": if attendee.resource { "location" } else { "individual" },
+                    "participationStatus": attendee.status,
+                    "expectReply": true,
+                    "roles": { "attendee": true },
+                }),
+            );
+        }
+        set("participants", Value::Object(participants));
+    }
+    if !event.alarms.is_empty() {
+        let alerts = event
+            .alarms
+            .iter()
+            .enumerate()
+            .map(|(index, alarm)| {
+                let alert = match alarm {
+                    Alarm::Before(minutes) => json!({
+                        "@type": "Alert",
+                        "trigger": {
+                            "@type": "OffsetTrigger",
+                            "offset": format!("-{}", duration(*minutes)),
+                        },
+                    }),
+                    Alarm::At(when) => json!({
+                        "@type": "Alert",
+                        "trigger": {
+                            "@type": "AbsoluteTrigger",
+                            "when": format!("{}Z", when.format("%Y-%m-%dT%H:%M:%S")),
+                        },
+                    }),
+                    Alarm::Email(minutes) => json!({
+                        "@type": "Alert",
+                        "action": "email",
+                        "trigger": {
+                            "@type": "OffsetTrigger",
+                            "offset": format!("-{}", duration(*minutes)),
+                        },
+                    }),
+                };
+                (format!("a{}", index + 1), alert)
+            })
+            .collect::<Map<_, _>>();
+        set("alerts", Value::Object(alerts));
+    }
+    if !event.categories.is_empty() {
+        set(
+            "keywords",
+            Value::Object(
+                event
+                    .categories
+                    .iter()
+                    .map(|category| (category.to_string(), json!(true)))
+                    .collect(),
+            ),
+        );
+    }
+    if let Some(priority) = event.priority {
+        set("priority", json!(priority));
+    }
+    if event.private {
+        set("privacy", json!("private"));
+    }
+    if event.free && !event.task {
+        set("freeBusyStatus", json!("free"));
+    }
+    if let Some(color) = event.color {
+        set("color", json!(color));
+    }
+    if event.vendor {
+        set("example.com:busyStatus", json!("busy"));
+    }
+    Value::Object(object)
+}
+
+struct ContactSpec {
+    uid: String,
+    given: &'static str,
+    surname: &'static str,
+    nickname: Option<&'static str>,
+    organization: Option<&'static str>,
+    title: Option<&'static str>,
+    emails: Vec<(&'static str, String)>,
+    phones: Vec<(&'static str, &'static str)>,
+    address: Option<Address>,
+    coordinates: Option<(f64, f64)>,
+    // Year, month and day, the year unknown for some birthdays.
+    birthday: Option<(Option<i32>, u32, u32)>,
+    note: Option<&'static str>,
+    categories: Vec<&'static str>,
+    url: Option<String>,
+    timezone: Option<&'static str>,
+    // UIDs of the members, for a group.
+    members: Vec<String>,
+    vendor: bool,
+}
+
+impl ContactSpec {
+    fn random(rng: &mut Rng) -> Self {
+        // Mostly a matching pair, sometimes a mix of scripts.
+        let index = rng.range(0, GIVEN_NAMES.len() as u32 - 1) as usize;
+        let given = GIVEN_NAMES[index];
+        let surname = if rng.chance(80) {
+            SURNAMES[index]
+        } else {
+            rng.pick(SURNAMES)
+        };
+        let slug = format!("user{}", rng.range(1, 999));
+        let emails = (0..rng.range(0, 2))
+            .map(|index| {
+                let context = if index == 0 { "work" } else { "private" };
+                let domain = if index == 0 {
+                    "example.com"
+                } else {
+                    "example.org"
+                };
+                (context, format!("{}@{}", slug, domain))
+            })
+            .collect();
+        let phones = (0..rng.range(0, 2))
+            .map(|index| (if index == 0 { "cell" } else { "work" }, rng.pick(PHONES)))
+            .collect();
+        let address = rng.chance(60).then(|| rng.pick(ADDRESSES));
+        ContactSpec {
+            uid: format!("urn:uuid:{}", rng.uuid()),
+            given,
+            surname,
+            nickname: rng.chance(20).then_some("Bobby \"Tables\""),
+            organization: rng.chance(50).then(|| rng.pick(ORGANIZATIONS)),
+            title: rng.chance(40).then(|| rng.pick(JOB_TITLES)),
+            emails,
+            phones,
+            address,
+            coordinates: address.filter(|_| rng.chance(50)).map(|address| address.6),
+            birthday: rng.chance(50).then(|| {
+                let year = rng.chance(75).then(|| rng.range(1940, 2010) as i32);
+                (year, rng.range(1, 12), rng.range(1, 28))
+            }),
+            note: rng.chance(30).then(|| rng.pick(DESCRIPTIONS)),
+            categories: if rng.chance(40) {
+                vec![rng.pick(CATEGORIES)]
+            } else {
+                vec![]
+            },
+            url: rng
+                .chance(30)
+                .then(|| format!("https://{}.example.com/", slug)),
+            timezone: rng.chance(20).then(|| rng.pick(TIMEZONES)),
+            members: vec![],
+            vendor: rng.chance(40),
+        }
+    }
+
+    fn group(rng: &mut Rng, members: Vec<String>) -> Self {
+        ContactSpec {
+            uid: format!("urn:uuid:{}", rng.uuid()),
+            given: "",
+            surname: "",
+            nickname: None,
+            organization: None,
+            title: None,
+            emails: vec![],
+            phones: vec![],
+            address: None,
+            coordinates: None,
+            birthday: None,
+            note: None,
+            categories: vec![],
+            url: None,
+            timezone: None,
+            members,
+            vendor: false,
+        }
+    }
+
+    fn full_name(&self) -> String {
+        if !self.members.is_empty() {
+            "Project team".to_string()
+        } else if self.surname.chars().all(|ch| ch >= '\u{2e80}') {
+            // Family name first, without a space, for CJK names.
+            format!("{}{}", self.surname, self.given)
+        } else {
+            format!("{} {}", self.given, self.surname)
+        }
+    }
+}
+
+fn vcard(contacts: &[ContactSpec], version_3: bool) -> String {
+    let mut text = String::new();
+    for contact in contacts {
+        let mut line = |value: String| fold_line(&mut text, &value);
+        line("BEGIN:VCARD".to_string());
+        line(format!("VERSION:{}", if version_3 { "3.0" } else { "4.0" }));
+        line(format!("UID:{}", contact.uid));
+        line(format!("FN:{}", escape(&contact.full_name())));
+        if !contact.members.is_empty() {
+            if version_3 {
+                line(format!("N:{};;;;", escape(&contact.full_name())));
+                line("X-ADDRESSBOOKSERVER-KIND:group".to_string());
+                for member in &contact.members {
+                    line(format!("X-ADDRESSBOOKSERVER-MEMBER:{}", member));
+                }
+            } else {
+                line("KIND:group".to_string());
+                for member in &contact.members {
+                    line(format!("MEMBER:{}", member));
+                }
+            }
+            line("END:VCARD".to_string());
+            continue;
+        }
+        line(format!(
+            "N:{};{};;;",
+            escape(contact.surname),
+            escape(contact.given)
+        ));
+        if let Some(nickname) = contact.nickname {
+            line(format!("NICKNAME:{}", escape(nickname)));
+        }
+        if let Some(organization) = contact.organization {
+            line(format!("ORG:{}", escape(organization)));
+        }
+        if let Some(title) = contact.title {
+            line(format!("TITLE:{}", escape(title)));
+        }
+        for (context, address) in &contact.emails {
+            line(format!("EMAIL;TYPE={}:{}", context, address));
+        }
+        for (kind, number) in &contact.phones {
+            if version_3 {
+                line(format!("TEL;TYPE={}:{}", kind.to_ascii_uppercase(), number));
+            } else {
+                line(format!(
+                    "TEL;VALUE=uri;TYPE={}:tel:{}",
+                    kind,
+                    number.replace(' ', "-")
+                ));
+            }
+        }
+        if let Some((street, locality, region, postcode, country, _, _)) = contact.address {
+            let geo = match (contact.coordinates, version_3) {
+                (Some((lat, lon)), false) => format!(";GEO=\"geo:{},{}\"", lat, lon),
+                _ => String::new(),
+            };
+            line(format!(
+                "ADR;TYPE=home{}:;;{};{};{};{};{}",
+                geo,
+                escape(street),
+                escape(locality),
+                escape(region),
+                escape(postcode),
+                escape(country)
+            ));
+        }
+        if let Some((year, month, day)) = contact.birthday {
+            match year {
+                Some(year) => line(format!("BDAY:{:04}{:02}{:02}", year, month, day)),
+                None if version_3 => line(format!(
+                    "BDAY;X-APPLE-OMIT-YEAR=1604:1604-{:02}-{:02}",
+                    month, day
+                )),
+                None => line(format!("BDAY:--{:02}{:02}", month, day)),
+            }
+        }
+        if let Some(note) = contact.note {
+            line(format!("NOTE:{}", escape(note)));
+        }
+        if !contact.categories.is_empty() {
+            line(format!("CATEGORIES:{}", contact.categories.join(",")));
+        }
+        if let Some(url) = &contact.url {
+            line(format!("URL:{}", url));
+        }
+        if let Some(tz) = contact.timezone {
+            line(format!("TZ:{}", tz));
+        }
+        if contact.vendor {
+            line("X-SOCIALPROFILE;TYPE=twitter:https://x.com/example".to_string());
+            line(format!("X-PHONETIC-LAST-NAME:{}", escape(contact.surname)));
+        }
+        line("END:VCARD".to_string());
+    }
+    text
+}
+
+fn jscontact(contacts: &[ContactSpec]) -> String {
+    let cards = contacts.iter().map(contact_json).collect::<Vec<_>>();
+    let root = if cards.len() == 1 {
+        cards.into_iter().next().unwrap_or_default()
+    } else {
+        Value::Array(cards)
+    };
+    serde_json::to_string_pretty(&root).unwrap_or_default()
+}
+
+fn contact_json(contact: &ContactSpec) -> Value {
+    let mut object = Map::new();
+    let mut set = |key: &str, value: Value| {
+        object.insert(key.to_string(), value);
+    };
+    set("@type", json!("Card"));
+    set("version", json!("1.0"));
+    set("uid", json!(contact.uid));
+    if !contact.members.is_empty() {
+        set("kind", json!("group"));
+        set("name", json!({ "full": contact.full_name() }));
+        set(
+            "members",
+            Value::Object(
+                contact
+                    .members
+                    .iter()
+                    .map(|member| (member.clone(), json!(true)))
+                    .collect(),
+            ),
+        );
+        return Value::Object(object);
+    }
+    set(
+        "name",
+        json!({
+            "full": contact.full_name(),
+            "components": [
+                { "kind": "given", "value": contact.given },
+                { "kind": "surname", "value": contact.surname },
+            ],
+        }),
+    );
+    if let Some(nickname) = contact.nickname {
+        set("nicknames", json!({ "n1": { "name": nickname } }));
+    }
+    if let Some(organization) = contact.organization {
+        set("organizations", json!({ "o1": { "name": organization } }));
+    }
+    if let Some(title) = contact.title {
+        set("titles", json!({ "t1": { "name": title } }));
+    }
+    if !contact.emails.is_empty() {
+        let emails = contact
+            .emails
+            .iter()
+            .enumerate()
+            .map(|(index, (context, address))| {
+                (
+                    format!("e{}", index + 1),
+                    json!({ "address": address, "contexts": { *context: true } }),
+                )
+            })
+            .collect::<Map<_, _>>();
+        set("emails", Value::Object(emails));
+    }
+    if !contact.phones.is_empty() {
+        let phones = contact
+            .phones
+            .iter()
+            .enumerate()
+            .map(|(index, (kind, number))| {
+                let phone = match *kind {
+                    "cell" => json!({ "number": number, "features": { "mobile": true } }),
+                    _ => json!({ "number": number, "contexts": { "work": true } }),
+                };
+                (format!("p{}", index + 1), phone)
+            })
+            .collect::<Map<_, _>>();
+        set("phones", Value::Object(phones));
+    }
+    if let Some((street, locality, region, postcode, country, code, _)) = contact.address {
+        let mut components = vec![json!({ "kind": "name", "value": street })];
+        for (kind, value) in [
+            ("locality", locality),
+            ("region", region),
+            ("postcode", postcode),
+            ("country", country),
+        ] {
+            if !value.is_empty() {
+                components.push(json!({ "kind": kind, "value": value }));
+            }
+        }
+        let mut address = json!({
+            "components": components,
+            "countryCode": code,
+            "contexts": { "private": true },
+        });
+        if let Some((lat, lon)) = contact.coordinates {
+            address["coordinates"] = json!(format!("geo:{},{}", lat, lon));
+        }
+        if let Some(tz) = contact.timezone {
+            address["timeZone"] = json!(tz);
+        }
+        set("addresses", json!({ "a1": address }));
+    }
+    if let Some((year, month, day)) = contact.birthday {
+        let mut date = json!({ "@type": "PartialDate", "month": month, "day": day });
+        if let Some(year) = year {
+            date["year"] = json!(year);
+        }
+        set(
+            "anniversaries",
+            json!({ "k1": { "kind": "birth", "date": date } }),
+        );
+    }
+    if let Some(note) = contact.note {
+        set("notes", json!({ "n1": { "note": note } }));
+    }
+    if !contact.categories.is_empty() {
+        set(
+            "keywords",
+            Value::Object(
+                contact
+                    .categories
+                    .iter()
+                    .map(|category| (category.to_string(), json!(true)))
+                    .collect(),
+            ),
+        );
+    }
+    if let Some(url) = &contact.url {
+        set("links", json!({ "l1": { "uri": url } }));
+    }
+    if contact.vendor {
+        set("example.com:socialProfile", json!("https://x.com/example"));
+    }
+    Value::Object(object)
+}
+
+fn tz_param(mode: TimeMode) -> String {
+    match mode {
+        TimeMode::Zoned(tz) => format!(";TZID={}", tz),
+        TimeMode::AllDay => ";VALUE=DATE".to_string(),
+        TimeMode::Utc | TimeMode::Floating => String::new(),
+    }
+}
+
+// UNTIL is in UTC whenever DTSTART has a time zone.
+fn ical_date_time(value: NaiveDateTime, mode: TimeMode, until: bool) -> String {
+    match mode {
+        TimeMode::AllDay => contentline::format_date(value.date()),
+        TimeMode::Utc => format!("{}Z", contentline::format_datetime(value)),
+        TimeMode::Zoned(_) if until => format!("{}Z", contentline::format_datetime(value)),
+        TimeMode::Zoned(_) | TimeMode::Floating => contentline::format_datetime(value),
+    }
+}
+
+fn local_date_time(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+fn duration(minutes: i64) -> String {
+    if minutes == 0 {
+        return "PT0S".to_string();
+    }
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    let mut text = "P".to_string();
+    if days > 0 {
+        text.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 {
+        text.push('T');
+        if hours > 0 {
+            text.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            text.push_str(&format!("{}M", minutes));
+        }
+    }
+    text
+}
+
+// Quotes parameter values that contain separators.
+fn param_value(value: &str) -> String {
+    if value.contains([',', ';', ':']) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
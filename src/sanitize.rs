@@ -0,0 +1,244 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, fold_line},
+    findings::{Code, Finding},
+};
+
+// Unknown properties kept by the conversion as jCal or jCard arrays.
+const CONVERTED_PROPERTIES: &[&str] = &["iCalProps", "vCardProps"];
+
+// Strips X- properties, components and parameters from iCalendar and vCard,
+// and vendor-prefixed keys such as "example.com:foo" from JSCalendar and
+// JSContact, except for the names in `keep`. Every removed item is reported.
+pub fn strip_vendor(source: &str, keep: &[String]) -> (String, Vec<Finding>) {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with("BEGIN:") {
+        strip_text(source, keep)
+    } else if trimmed.starts_with(['{', '[']) {
+        match serde_json::from_str::<Value>(trimmed) {
+            Ok(mut root) => {
+                let mut findings = Vec::new();
+                strip_value("", &mut root, keep, &mut findings);
+                (
+                    serde_json::to_string_pretty(&root).unwrap_or_else(|_| source.to_string()),
+                    findings,
+                )
+            }
+            Err(_) => (source.to_string(), vec![]),
+        }
+    } else {
+        (source.to_string(), vec![])
+    }
+}
+
+// Parses a comma or whitespace separated list of names to keep.
+pub fn parse_keep_list(value: &str) -> Vec<String> {
+    value
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn strip_text(source: &str, keep: &[String]) -> (String, Vec<Finding>) {
+    let mut text = String::with_capacity(source.len());
+    let mut findings = Vec::new();
+    let mut depth = 0;
+    // Depth of the vendor component being removed.
+    let mut skipping: Option<usize> = None;
+    for (number, raw) in contentline::unfold(source) {
+        let Some(mut line) = contentline::parse_line(number, &raw) else {
+            if skipping.is_none() {
+                fold_line(&mut text, &raw);
+            }
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                let name = line.value.trim().to_ascii_uppercase();
+                if skipping.is_none() && is_vendor_name(&name, keep) {
+                    skipping = Some(depth);
+                    findings.push(stripped(format!("component {}", name), number));
+                }
+            }
+            "END" => {
+                depth = depth.saturating_sub(1);
+                if skipping == Some(depth + 1) {
+                    skipping = None;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        if skipping.is_some() {
+            continue;
+        }
+        if is_vendor_name(&line.name, keep) {
+            findings.push(stripped(format!("property {}", line.name), number));
+            continue;
+        }
+
+        let vendor_params = line
+            .params
+            .iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| is_vendor_name(name, keep))
+            .collect::<Vec<_>>();
+        if vendor_params.is_empty() {
+            fold_line(&mut text, &raw);
+        } else {
+            for param in vendor_params {
+                line.remove_param(&param);
+                findings.push(stripped(
+                    format!("parameter {} of {}", param, line.name),
+                    number,
+                ));
+            }
+            fold_line(&mut text, &line.to_line());
+        }
+    }
+    (text, findings)
+}
+
+fn strip_value(path: &str, value: &mut Value, keep: &[String], findings: &mut Vec<Finding>) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, value| {
+                // Keys of sets such as keywords or members are data, not properties.
+                let vendor =
+                    is_vendor_key(key) && *value != Value::Bool(true) && !is_kept(key, keep);
+                if vendor {
+                    findings.push(
+                        Finding::new(
+                            Code::VendorExtensionStripped,
+                            format!("Removed vendor property {}.", key),
+                        )
+                        .with_location(pointer(path, key)),
+                    );
+                }
+                !vendor
+            });
+            for (key, value) in object.iter_mut() {
+                let path = pointer(path, key);
+                if CONVERTED_PROPERTIES.contains(&key.as_str()) {
+                    strip_converted(&path, value, keep, findings);
+                } else {
+                    strip_value(&path, value, keep, findings);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                strip_value(&format!("{}/{}", path, index), item, keep, findings);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Converted properties are [name, parameters, type, value] arrays.
+fn strip_converted(path: &str, value: &mut Value, keep: &[String], findings: &mut Vec<Finding>) {
+    let Value::Array(properties) = value else {
+        return;
+    };
+    let mut index = 0;
+    properties.retain_mut(|property| {
+        let location = format!("{}/{}", path, index);
+        index += 1;
+        let name = property
+            .get(0)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        if is_vendor_name(&name, keep) {
+            findings.push(
+                Finding::new(
+                    Code::VendorExtensionStripped,
+                    format!("Removed converted property {}.", name),
+                )
+                .with_location(location),
+            );
+            return false;
+        }
+        if let Some(Value::Object(params)) = property.get_mut(1) {
+            params.retain(|param, _| {
+                let vendor = is_vendor_name(param, keep);
+                if vendor {
+                    findings.push(
+                        Finding::new(
+                            Code::VendorExtensionStripped,
+                            format!(
+                                "Removed converted parameter {} of {}.",
+                                param.to_ascii_uppercase(),
+                                name
+                            ),
+                        )
+                        .with_location(format!("{}/1", location)),
+                    );
+                }
+                !vendor
+            });
+        }
+        true
+    });
+}
+
+fn is_vendor_name(name: &str, keep: &[String]) -> bool {
+    name.len() > 2
+        && name
+            .get(..2)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-"))
+        && !is_kept(name, keep)
+}
+
+fn is_vendor_key(key: &str) -> bool {
+    key.split_once(':').is_some_and(|(prefix, name)| {
+        !name.is_empty()
+            && prefix.contains('.')
+            && prefix
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-'))
+    })
+}
+
+fn is_kept(name: &str, keep: &[String]) -> bool {
+    keep.iter().any(|kept| kept.eq_ignore_ascii_case(name))
+}
+
+fn stripped(item: String, line: usize) -> Finding {
+    Finding::new(
+        Code::VendorExtensionStripped,
+        format!("Removed vendor {}.", item),
+    )
+    .with_location(format!("line {}", line))
+}
+
+fn pointer(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_unmatched_end() {
+        let (text, findings) = strip_vendor(
+            "BEGIN:VCALENDAR\r\nEND:VEVENT\r\nEND:VCALENDAR\r\nEND:VCALENDAR\r\nBEGIN:X-VENDOR\r\nX-FOO:bar\r\nEND:X-VENDOR\r\n",
+            &[],
+        );
+        assert_eq!(
+            text,
+            "BEGIN:VCALENDAR\r\nEND:VEVENT\r\nEND:VCALENDAR\r\nEND:VCALENDAR\r\n"
+        );
+        assert_eq!(findings.len(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{jscalendar::JSCalendar, jscontact::JSContact, Entry, Parser};
+
+use crate::converter::{normalize_json, Format};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Convert,
+    Roundtrip,
+    Reparse,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageResult {
+    pub stage: Stage,
+    pub elapsed_ms: f64,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfTestResult {
+    pub name: String,
+    pub format: &'static str,
+    pub stages: Vec<StageResult>,
+}
+
+pub const STAGES: [Stage; 4] = [
+    Stage::Parse,
+    Stage::Convert,
+    Stage::Roundtrip,
+    Stage::Reparse,
+];
+
+struct Timer<'x, F: Fn() -> f64> {
+    now: &'x F,
+    stages: Vec<StageResult>,
+}
+
+// Runs a document through parse → convert → round trip → re-parse of the
+// round-tripped output, stopping at the first failing stage.
+pub fn run(name: &str, source: &str, now: &impl Fn() -> f64) -> SelfTestResult {
+    let source = source.trim();
+    let json = normalize_json(source);
+    let mut timer = Timer {
+        now,
+        stages: Vec::with_capacity(STAGES.len()),
+    };
+
+    let format = if source.starts_with("BEGIN:") {
+        match timer.stage(Stage::Parse, || match Parser::new(source).entry() {
+            Entry::ICalendar(icalendar) => Ok(Entry::ICalendar(icalendar)),
+            Entry::VCard(vcard) => Ok(Entry::VCard(vcard)),
+            Entry::InvalidLine(text) => Err(format!("Invalid line found: {}", text)),
+            other => Err(format!("Unexpected parser result: {:?}", other)),
+        }) {
+            Some(Entry::ICalendar(icalendar)) => {
+                let converted = timer.stage(Stage::Convert, || Ok(icalendar.into_jscalendar()));
+                let roundtrip = converted.and_then(|jscalendar| {
+                    timer.stage(Stage::Roundtrip, || {
+                        jscalendar
+                            .into_icalendar()
+                            .map(|icalendar| icalendar.to_string())
+                            .ok_or_else(|| "Conversion back to iCalendar failed".to_string())
+                    })
+                });
+                if let Some(roundtrip) = roundtrip {
+                    timer.stage(Stage::Reparse, || reparse_text(&roundtrip, "iCalendar"));
+                }
+                "iCalendar"
+            }
+            Some(Entry::VCard(vcard)) => {
+                let converted = timer.stage(Stage::Convert, || Ok(vcard.into_jscontact()));
+                let roundtrip = converted.and_then(|jscontact| {
+                    timer.stage(Stage::Roundtrip, || {
+                        jscontact
+                            .into_vcard()
+                            .map(|vcard| vcard.to_string())
+                            .ok_or_else(|| "Conversion back to vCard failed".to_string())
+                    })
+                });
+                if let Some(roundtrip) = roundtrip {
+                    timer.stage(Stage::Reparse, || reparse_text(&roundtrip, "vCard"));
+                }
+                "vCard"
+            }
+            _ => "iCalendar/vCard",
+        }
+    } else if let Some((Format::JSCalendar, source)) = &json {
+        let converted = timer
+            .stage(Stage::Parse, || {
+                JSCalendar::parse(source).map_err(|err| err.to_string())
+            })
+            .and_then(|jscalendar| {
+                timer.stage(Stage::Convert, || {
+                    jscalendar
+                        .into_icalendar()
+                        .ok_or_else(|| "Conversion to iCalendar failed".to_string())
+                })
+            });
+        if let Some(icalendar) = converted {
+            if let Some(roundtrip) = timer.stage(Stage::Roundtrip, || {
+                Ok(icalendar.into_jscalendar().to_string_pretty())
+            }) {
+                timer.stage(Stage::Reparse, || {
+                    JSCalendar::parse(&roundtrip)
+                        .map(|_| ())
+                        .map_err(|err| err.to_string())
+                });
+            }
+        }
+        "JSCalendar"
+    } else if let Some((Format::JSContact, source)) = &json {
+        let converted = timer
+            .stage(Stage::Parse, || {
+                JSContact::parse(source).map_err(|err| err.to_string())
+            })
+            .and_then(|jscontact| {
+                timer.stage(Stage::Convert, || {
+                    jscontact
+                        .into_vcard()
+                        .ok_or_else(|| "Conversion to vCard failed".to_string())
+                })
+            });
+        if let Some(vcard) = converted {
+            if let Some(roundtrip) = timer.stage(Stage::Roundtrip, || {
+                Ok(vcard.into_jscontact().to_string_pretty())
+            }) {
+                timer.stage(Stage::Reparse, || {
+                    JSContact::parse(&roundtrip)
+                        .map(|_| ())
+                        .map_err(|err| err.to_string())
+                });
+            }
+        }
+        "JSContact"
+    } else {
+        timer.stage::<()>(Stage::Parse, || Err("Unrecognized format".to_string()));
+        "Unknown"
+    };
+
+    SelfTestResult {
+        name: name.to_string(),
+        format,
+        stages: timer.stages,
+    }
+}
+
+fn reparse_text(text: &str, expected: &str) -> Result<(), String> {
+    match (Parser::new(text).entry(), expected) {
+        (Entry::ICalendar(_), "iCalendar") | (Entry::VCard(_), "vCard") => Ok(()),
+        (other, _) => Err(format!("Round-tripped output did not parse: {:?}", other)),
+    }
+}
+
+impl<F: Fn() -> f64> Timer<'_, F> {
+    fn stage<T>(&mut self, stage: Stage, f: impl FnOnce() -> Result<T, String>) -> Option<T> {
+        let start = (self.now)();
+        let result = f();
+        let elapsed_ms = (self.now)() - start;
+        match result {
+            Ok(value) => {
+                self.stages.push(StageResult {
+                    stage,
+                    elapsed_ms,
+                    error: None,
+                });
+                Some(value)
+            }
+            Err(err) => {
+                self.stages.push(StageResult {
+                    stage,
+                    elapsed_ms,
+                    error: Some(err),
+                });
+                None
+            }
+        }
+    }
+}
+
+impl SelfTestResult {
+    pub fn passed(&self) -> bool {
+        self.stages.len() == STAGES.len() && self.stages.iter().all(|stage| stage.error.is_none())
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.stages.iter().map(|stage| stage.elapsed_ms).sum()
+    }
+
+    pub fn stage(&self, stage: Stage) -> Option<&StageResult> {
+        self.stages.iter().find(|result| result.stage == stage)
+    }
+}
+
+impl Stage {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Stage::Parse => "selftest-parse",
+            Stage::Convert => "selftest-convert",
+            Stage::Roundtrip => "selftest-roundtrip",
+            Stage::Reparse => "selftest-reparse",
+        }
+    }
+}
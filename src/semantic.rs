@@ -0,0 +1,263 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// Differences in meaning between two inputs of any supported format, such as
+// the same event exported by two servers. Both are taken through the
+// converter to JSCalendar or JSContact, so that the order, folding, escaping
+// and syntax of the sources do not matter.
+
+use serde_json::Value;
+
+use crate::{
+    expected::{self, DisagreementKind},
+    Converter, Format,
+};
+
+// Written by every server on its own.
+const IGNORED_KEYS: &[&str] = &["updated", "created", "prodId"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference {
+    pub kind: DisagreementKind,
+    // The entry it concerns, when the inputs hold several.
+    pub entry: Option<String>,
+    // JSON pointer within the entry.
+    pub path: String,
+    pub summary: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+pub fn compare(left: &str, right: &str) -> Result<Vec<Difference>, String> {
+    let (left_calendar, left) = model(left, "first")?;
+    let (right_calendar, right) = model(right, "second")?;
+    if left_calendar != right_calendar {
+        return Err("One input is a calendar and the other a contact card.".to_string());
+    }
+
+    let left = entries(left);
+    let right = entries(right);
+    let several = left.len() > 1 || right.len() > 1;
+    let mut differences = Vec::new();
+    for (key, entry) in &left {
+        let label = several.then(|| entry_label(entry, key));
+        match right.iter().find(|(other, _)| other == key) {
+            Some((_, other)) => {
+                for disagreement in expected::compare_json(entry, other) {
+                    let segments = segments(&disagreement.path);
+                    if segments
+                        .first()
+                        .is_some_and(|key| IGNORED_KEYS.contains(&key.as_str()))
+                    {
+                        continue;
+                    }
+                    differences.push(Difference {
+                        summary: describe(&segments, disagreement.kind),
+                        kind: disagreement.kind,
+                        entry: label.clone(),
+                        path: disagreement.path,
+                        left: disagreement.ours,
+                        right: disagreement.expected,
+                    });
+                }
+            }
+            None => differences.push(Difference {
+                kind: DisagreementKind::Unexpected,
+                summary: format!("{} only in the first input", kind_name(entry)),
+                entry: Some(entry_label(entry, key)),
+                path: String::new(),
+                left: Some(key.clone()),
+                right: None,
+            }),
+        }
+    }
+    for (key, entry) in &right {
+        if !left.iter().any(|(other, _)| other == key) {
+            differences.push(Difference {
+                kind: DisagreementKind::Missing,
+                summary: format!("{} only in the second input", kind_name(entry)),
+                entry: Some(entry_label(entry, key)),
+                path: String::new(),
+                left: None,
+                right: Some(key.clone()),
+            });
+        }
+    }
+    Ok(differences)
+}
+
+// The JSCalendar or JSContact of an input, as the converter writes it. JSON
+// inputs are taken to text and back so that both sides share its defaults.
+fn model(source: &str, which: &str) -> Result<(bool, Value), String> {
+    let convert = |source: &str| {
+        Converter
+            .convert(source)
+            .map_err(|finding| format!("The {} input cannot be read: {}", which, finding.message))
+    };
+    let result = convert(source)?;
+    let calendar = result.format.is_calendar();
+    let json = if matches!(result.format, Format::JSCalendar | Format::JSContact) {
+        convert(&result.converted)?.converted
+    } else {
+        result.converted
+    };
+    serde_json::from_str(&json)
+        .map(|value| (calendar, value))
+        .map_err(|err| format!("The {} input cannot be read: {}", which, err))
+}
+
+// Objects keyed by UID and recurrence id, so that entries are matched
+// whatever their order.
+fn entries(value: Value) -> Vec<(String, Value)> {
+    let values = match value {
+        Value::Object(mut object)
+            if object.get("@type").and_then(Value::as_str) == Some("Group") =>
+        {
+            match object.remove("entries") {
+                Some(Value::Array(entries)) => entries,
+                _ => vec![],
+            }
+        }
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let text = |key: &str| value.get(key).and_then(Value::as_str);
+            let key = match (text("uid"), text("recurrenceId")) {
+                (Some(uid), Some(recurrence_id)) => format!("{} {}", uid, recurrence_id),
+                (Some(uid), None) => uid.to_string(),
+                _ => (index + 1).to_string(),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+fn entry_label(entry: &Value, key: &str) -> String {
+    entry
+        .get("title")
+        .and_then(Value::as_str)
+        .or_else(|| entry.pointer("/name/full").and_then(Value::as_str))
+        .filter(|title| !title.is_empty())
+        .map_or_else(|| key.to_string(), str::to_string)
+}
+
+fn kind_name(entry: &Value) -> &'static str {
+    match entry.get("@type").and_then(Value::as_str) {
+        Some("Task") => "Task",
+        Some("Card") => "Card",
+        _ => "Event",
+    }
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+// "Participant added", "Start changed", "Phone features changed".
+fn describe(segments: &[String], kind: DisagreementKind) -> String {
+    let verb = match kind {
+        DisagreementKind::Unexpected => "removed",
+        DisagreementKind::Missing => "added",
+        DisagreementKind::Different => "changed",
+    };
+    let Some((property, rest)) = segments.split_first() else {
+        return format!("Object {}", verb);
+    };
+    let (mut text, keys) = property_name(property);
+    let rest = match (keys, rest.split_first()) {
+        // Entries of maps such as participants are keyed by an id that
+        // carries no meaning of its own.
+        (Keys::Ids, Some((_, rest))) => rest,
+        (Keys::Values, Some((key, rest))) => {
+            text.push_str(&format!(" {:?}", key));
+            rest
+        }
+        _ => rest,
+    };
+    for segment in rest {
+        if segment.bytes().all(|byte| byte.is_ascii_digit()) {
+            continue;
+        }
+        text.push(' ');
+        text.push_str(&words(segment));
+    }
+    format!("{} {}", text, verb)
+}
+
+// What the keys of a JSON map property stand for.
+enum Keys {
+    None,
+    Ids,
+    Values,
+}
+
+fn property_name(property: &str) -> (String, Keys) {
+    let name = match property {
+        "participants" => "Participant",
+        "locations" => "Location",
+        "virtualLocations" => "Virtual location",
+        "alerts" => "Alert",
+        "links" => "Link",
+        "recurrenceOverrides" => "Recurrence override",
+        "recurrenceRules" => "Recurrence rule",
+        "excludedRecurrenceRules" => "Excluded recurrence rule",
+        "relatedTo" => "Relation",
+        "phones" => "Phone",
+        "emails" => "Email",
+        "addresses" => "Address",
+        "onlineServices" => "Online service",
+        "organizations" => "Organization",
+        "titles" => "Title",
+        "nicknames" => "Nickname",
+        "notes" => "Note",
+        "anniversaries" => "Anniversary",
+        "media" => "Media",
+        "members" => "Member",
+        "keywords" | "categories" => "Keyword",
+        "start" => "Start",
+        "duration" => "Duration",
+        "timeZone" => "Time zone",
+        property => {
+            let mut text = words(property);
+            if let Some(first) = text.get(..1) {
+                text = format!("{}{}", first.to_uppercase(), &text[1..]);
+            }
+            return (text, Keys::None);
+        }
+    };
+    // Keywords, relations and overrides are keyed by what they are about.
+    let keys = match property {
+        "start" | "duration" | "timeZone" | "recurrenceRules" | "excludedRecurrenceRules" => {
+            Keys::None
+        }
+        "recurrenceOverrides" | "relatedTo" | "members" | "keywords" | "categories" => Keys::Values,
+        _ => Keys::Ids,
+    };
+    (name.to_string(), keys)
+}
+
+// participationStatus becomes "participation status".
+fn words(key: &str) -> String {
+    let mut text = String::with_capacity(key.len() + 4);
+    for ch in key.chars() {
+        if ch.is_ascii_uppercase() {
+            text.push(' ');
+            text.push(ch.to_ascii_lowercase());
+        } else if ch == '@' {
+            continue;
+        } else {
+            text.push(ch);
+        }
+    }
+    text
+}
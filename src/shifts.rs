@@ -0,0 +1,309 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::collections::BTreeSet;
+
+use calcard::{common::timezone::Tz, Entry, Parser};
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::contentline::{self, fold_line};
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShiftPattern {
+    // `on` consecutive working days followed by `off` days, e.g. 4-on-4-off.
+    OnOff {
+        on: u32,
+        off: u32,
+    },
+    // Two alternating weekly rosters, indexed by `Weekday::num_days_from_monday`.
+    AlternatingWeeks {
+        week_a: [bool; 7],
+        week_b: [bool; 7],
+    },
+    // Given weekday of the month, e.g. 2nd and 4th Saturday. Ordinal -1 means last.
+    MonthlyOrdinal {
+        weekday: Weekday,
+        ordinals: Vec<i8>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShiftSchedule {
+    pub pattern: ShiftPattern,
+    pub summary: String,
+    pub first_day: NaiveDate,
+    pub start_time: NaiveTime,
+    pub duration_hours: u32,
+    // Empty for floating times.
+    pub tz_id: String,
+    // Number of pattern repetitions (cycles, fortnights or months).
+    pub cycles: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ShiftVerification {
+    pub expected: usize,
+    pub expanded: usize,
+    pub missing: Vec<NaiveDateTime>,
+    pub unexpected: Vec<NaiveDateTime>,
+}
+
+struct ShiftEvent {
+    rrule: String,
+    // Instances the RRULE is expected to produce, the first one being DTSTART.
+    rule_starts: Vec<NaiveDateTime>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl ShiftSchedule {
+    pub fn to_icalendar(&self) -> Result<String, String> {
+        let events = self.events()?;
+        let mut ical = String::new();
+        fold_line(&mut ical, "BEGIN:VCALENDAR");
+        fold_line(&mut ical, "VERSION:2.0");
+        fold_line(
+            &mut ical,
+            "PRODID:-//Stalwart Labs LLC//jmap-convert shift generator//EN",
+        );
+        for (num, event) in events.iter().enumerate() {
+            fold_line(&mut ical, "BEGIN:VEVENT");
+            fold_line(
+                &mut ical,
+                &format!(
+                    "UID:shift-{}-{}@jmap-convert",
+                    contentline::format_date(self.first_day),
+                    num + 1
+                ),
+            );
+            fold_line(
+                &mut ical,
+                &format!(
+                    "DTSTAMP:{}T000000Z",
+                    contentline::format_date(self.first_day)
+                ),
+            );
+            fold_line(
+                &mut ical,
+                &format!(
+                    "DTSTART{}:{}",
+                    self.tz_param(),
+                    contentline::format_datetime(event.rule_starts[0])
+                ),
+            );
+            fold_line(&mut ical, &format!("DURATION:PT{}H", self.duration_hours));
+            fold_line(&mut ical, &format!("RRULE:{}", event.rrule));
+            if !event.rdates.is_empty() {
+                let rdates = event
+                    .rdates
+                    .iter()
+                    .map(|start| contentline::format_datetime(*start))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                fold_line(&mut ical, &format!("RDATE{}:{}", self.tz_param(), rdates));
+            }
+            fold_line(
+                &mut ical,
+                &format!("SUMMARY:{}", contentline::escape(&self.summary)),
+            );
+            fold_line(&mut ical, "END:VEVENT");
+        }
+        fold_line(&mut ical, "END:VCALENDAR");
+        Ok(ical)
+    }
+
+    pub fn expected_starts(&self) -> Result<Vec<NaiveDateTime>, String> {
+        let mut starts = self
+            .events()?
+            .into_iter()
+            .flat_map(|event| event.rule_starts.into_iter().chain(event.rdates))
+            .collect::<Vec<_>>();
+        starts.sort_unstable();
+        starts.dedup();
+        Ok(starts)
+    }
+
+    pub fn verify(&self, icalendar: &str) -> Result<ShiftVerification, String> {
+        let expected = self.expected_starts()?;
+        let icalendar = match Parser::new(icalendar).entry() {
+            Entry::ICalendar(icalendar) => icalendar,
+            _ => return Err("The generated calendar could not be parsed.".to_string()),
+        };
+        let expanded = icalendar
+            .expand_dates(Tz::Floating, expected.len() * 2 + 1)
+            .events
+            .into_iter()
+            .filter_map(|event| event.try_into_date_time())
+            .map(|event| event.start.naive_local())
+            .collect::<BTreeSet<_>>();
+        let expected_set = expected.iter().copied().collect::<BTreeSet<_>>();
+
+        Ok(ShiftVerification {
+            expected: expected.len(),
+            expanded: expanded.len(),
+            missing: expected_set.difference(&expanded).copied().collect(),
+            unexpected: expanded.difference(&expected_set).copied().collect(),
+        })
+    }
+
+    fn events(&self) -> Result<Vec<ShiftEvent>, String> {
+        if self.cycles == 0 {
+            return Err("The number of repetitions must be at least one.".to_string());
+        }
+        if self.duration_hours == 0 || self.duration_hours > 24 {
+            return Err("Shifts must last between 1 and 24 hours.".to_string());
+        }
+        let start = |date: NaiveDate| date.and_time(self.start_time);
+
+        match &self.pattern {
+            ShiftPattern::OnOff { on, off } => {
+                if *on == 0 {
+                    return Err("At least one working day per cycle is required.".to_string());
+                }
+                let cycle = (on + off) as u64;
+                // The first day of each cycle is driven by the RRULE, the
+                // remaining working days are listed explicitly as RDATEs.
+                let rdates = (0..self.cycles as u64)
+                    .flat_map(|num| (1..*on as u64).map(move |day| num * cycle + day))
+                    .map(|offset| start(self.first_day + Days::new(offset)))
+                    .collect();
+                let rule_starts = (0..self.cycles as u64)
+                    .map(|num| start(self.first_day + Days::new(num * cycle)))
+                    .collect();
+                Ok(vec![ShiftEvent {
+                    rrule: format!("FREQ=DAILY;INTERVAL={};COUNT={}", cycle, self.cycles),
+                    rule_starts,
+                    rdates,
+                }])
+            }
+            ShiftPattern::AlternatingWeeks { week_a, week_b } => {
+                let monday = self.first_day
+                    - Days::new(self.first_day.weekday().num_days_from_monday() as u64);
+                let events = [(week_a, 0), (week_b, 7)]
+                    .into_iter()
+                    .filter(|(days, _)| days.contains(&true))
+                    .map(|(days, offset)| {
+                        let rule_starts = (0..self.cycles as u64)
+                            .flat_map(|num| {
+                                days.iter()
+                                    .enumerate()
+                                    .filter(|(_, selected)| **selected)
+                                    .map(move |(day, _)| offset + num * 14 + day as u64)
+                            })
+                            .map(|offset| start(monday + Days::new(offset)))
+                            .collect::<Vec<_>>();
+                        ShiftEvent {
+                            rrule: format!(
+                                "FREQ=WEEKLY;INTERVAL=2;WKST=MO;BYDAY={};COUNT={}",
+                                byday_list(days),
+                                rule_starts.len()
+                            ),
+                            rule_starts,
+                            rdates: vec![],
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if events.is_empty() {
+                    Err("Select at least one working day.".to_string())
+                } else {
+                    Ok(events)
+                }
+            }
+            ShiftPattern::MonthlyOrdinal { weekday, ordinals } => {
+                if ordinals.is_empty() {
+                    return Err("Select at least one week of the month.".to_string());
+                }
+                let rule_starts = self
+                    .monthly_dates(*weekday, ordinals)
+                    .take(ordinals.len() * self.cycles as usize)
+                    .map(start)
+                    .collect::<Vec<_>>();
+                if rule_starts.is_empty() {
+                    return Err("No matching day found.".to_string());
+                }
+                let byday = ordinals
+                    .iter()
+                    .map(|ordinal| format!("{}{}", ordinal, weekday_code(*weekday)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                Ok(vec![ShiftEvent {
+                    rrule: format!("FREQ=MONTHLY;BYDAY={};COUNT={}", byday, rule_starts.len()),
+                    rule_starts,
+                    rdates: vec![],
+                }])
+            }
+        }
+    }
+
+    fn monthly_dates<'x>(
+        &'x self,
+        weekday: Weekday,
+        ordinals: &'x [i8],
+    ) -> impl Iterator<Item = NaiveDate> + 'x {
+        let month = self.first_day.with_day(1).unwrap_or(self.first_day);
+        (0..)
+            .map_while(move |num| month.checked_add_months(Months::new(num)))
+            .take(self.cycles as usize + 1)
+            .flat_map(move |month| {
+                let mut dates = ordinals
+                    .iter()
+                    .filter_map(|ordinal| nth_weekday(month, weekday, *ordinal))
+                    .collect::<Vec<_>>();
+                dates.sort_unstable();
+                dates
+            })
+            .filter(move |date| *date >= self.first_day)
+    }
+
+    fn tz_param(&self) -> String {
+        let tz_id = self.tz_id.trim();
+        if tz_id.is_empty() {
+            String::new()
+        } else {
+            format!(";TZID={}", tz_id)
+        }
+    }
+}
+
+fn nth_weekday(month: NaiveDate, weekday: Weekday, ordinal: i8) -> Option<NaiveDate> {
+    if ordinal > 0 {
+        NaiveDate::from_weekday_of_month_opt(month.year(), month.month(), weekday, ordinal as u8)
+    } else {
+        let last = month.checked_add_months(Months::new(1))?.pred_opt()?;
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        last.checked_sub_days(Days::new(back as u64))
+    }
+}
+
+fn byday_list(days: &[bool; 7]) -> String {
+    days.iter()
+        .zip(WEEKDAYS)
+        .filter(|(selected, _)| **selected)
+        .map(|(_, weekday)| weekday_code(weekday))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DurationStats {
+    pub count: usize,
+    pub window_start: NaiveDateTime,
+    pub window_end: NaiveDateTime,
+    pub total: Duration,
+    pub average: Duration,
+    pub shortest: Duration,
+    pub longest: Duration,
+    pub busiest_day: (NaiveDate, Duration, usize),
+    // Indexed by `Weekday::num_days_from_monday`.
+    pub per_weekday: [usize; 7],
+    pub hours_per_weekday: [f64; 7],
+}
+
+impl DurationStats {
+    // Spans are (start, end) pairs in the local time of each occurrence.
+    pub fn compute(spans: &[(NaiveDateTime, NaiveDateTime)]) -> Option<Self> {
+        let (first, _) = spans.first()?;
+        let mut stats = DurationStats {
+            count: spans.len(),
+            window_start: *first,
+            window_end: *first,
+            total: Duration::zero(),
+            average: Duration::zero(),
+            shortest: Duration::MAX,
+            longest: Duration::zero(),
+            busiest_day: (first.date(), Duration::zero(), 0),
+            per_weekday: [0; 7],
+            hours_per_weekday: [0.0; 7],
+        };
+        let mut per_day: BTreeMap<NaiveDate, (Duration, usize)> = BTreeMap::new();
+
+        for (start, end) in spans {
+            let length = (*end - *start).max(Duration::zero());
+            stats.window_start = stats.window_start.min(*start);
+            stats.window_end = stats.window_end.max(*end);
+            stats.total += length;
+            stats.shortest = stats.shortest.min(length);
+            stats.longest = stats.longest.max(length);
+
+            let weekday = start.weekday().num_days_from_monday() as usize;
+            stats.per_weekday[weekday] += 1;
+            stats.hours_per_weekday[weekday] += length.num_minutes() as f64 / 60.0;
+
+            let day = per_day.entry(start.date()).or_default();
+            day.0 += length;
+            day.1 += 1;
+        }
+
+        stats.average = stats.total / spans.len() as i32;
+        if let Some((date, (length, count))) = per_day
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.0.cmp(&b.0).then(a.1.cmp(&b.1)))
+        {
+            stats.busiest_day = (date, length, count);
+        }
+
+        Some(stats)
+    }
+
+    pub fn busiest_weekday(&self) -> usize {
+        (0..7)
+            .max_by(|a, b| {
+                self.hours_per_weekday[*a]
+                    .total_cmp(&self.hours_per_weekday[*b])
+                    .then(b.cmp(a))
+            })
+            .unwrap_or_default()
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    let (days, hours, minutes) = (minutes / 1440, (minutes % 1440) / 60, minutes % 60);
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{}d", days));
+    }
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+    if minutes > 0 || parts.is_empty() {
+        parts.push(format!("{}m", minutes));
+    }
+    parts.join(" ")
+}
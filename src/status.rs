@@ -0,0 +1,387 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, fold_line},
+    findings::{Code, Finding},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Event,
+    Task,
+}
+
+// iCalendar properties with an enumerated value mapped to JSCalendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    Status,
+    Transparency,
+    Classification,
+}
+
+// iCalendar values and their JSCalendar counterparts.
+pub const EVENT_STATUSES: &[(&str, &str)] = &[
+    ("TENTATIVE", "tentative"),
+    ("CONFIRMED", "confirmed"),
+    ("CANCELLED", "cancelled"),
+];
+pub const TASK_STATUSES: &[(&str, &str)] = &[
+    ("NEEDS-ACTION", "needs-action"),
+    ("IN-PROCESS", "in-process"),
+    ("COMPLETED", "completed"),
+    ("CANCELLED", "cancelled"),
+];
+pub const TRANSPARENCIES: &[(&str, &str)] = &[("OPAQUE", "busy"), ("TRANSPARENT", "free")];
+pub const CLASSES: &[(&str, &str)] = &[
+    ("PUBLIC", "public"),
+    ("PRIVATE", "private"),
+    ("CONFIDENTIAL", "secret"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StatusEntry {
+    // Line of the component's BEGIN, identifies it within the iCalendar.
+    pub line: usize,
+    pub kind: Kind,
+    pub uid: Option<String>,
+    pub recurrence_id: Option<String>,
+    pub summary: String,
+    pub status: Option<String>,
+    pub transp: Option<String>,
+    pub class: Option<String>,
+}
+
+impl Kind {
+    pub const ALL: &'static [Kind] = &[Kind::Event, Kind::Task];
+
+    pub fn component(&self) -> &'static str {
+        match self {
+            Kind::Event => "VEVENT",
+            Kind::Task => "VTODO",
+        }
+    }
+}
+
+impl Field {
+    pub const ALL: &'static [Field] = &[Field::Status, Field::Transparency, Field::Classification];
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Field::Status => "status-field-status",
+            Field::Transparency => "status-field-transparency",
+            Field::Classification => "status-field-classification",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Status => "STATUS",
+            Field::Transparency => "TRANSP",
+            Field::Classification => "CLASS",
+        }
+    }
+
+    // JSCalendar property holding the value, if the component has one.
+    pub fn property(&self, kind: Kind) -> Option<&'static str> {
+        match (self, kind) {
+            (Field::Status, Kind::Event) => Some("status"),
+            (Field::Status, Kind::Task) => Some("progress"),
+            (Field::Transparency, Kind::Event) => Some("freeBusyStatus"),
+            (Field::Transparency, Kind::Task) => None,
+            (Field::Classification, _) => Some("privacy"),
+        }
+    }
+
+    pub fn values(&self, kind: Kind) -> &'static [(&'static str, &'static str)] {
+        match (self, kind) {
+            (Field::Status, Kind::Event) => EVENT_STATUSES,
+            (Field::Status, Kind::Task) => TASK_STATUSES,
+            (Field::Transparency, Kind::Event) => TRANSPARENCIES,
+            (Field::Transparency, Kind::Task) => &[],
+            (Field::Classification, _) => CLASSES,
+        }
+    }
+
+    // JSCalendar values with no iCalendar equivalent, dropped on export.
+    pub fn jscalendar_only(&self, kind: Kind) -> &'static [&'static str] {
+        match (self, kind) {
+            (Field::Status, Kind::Task) => &["failed"],
+            _ => &[],
+        }
+    }
+
+    // Value implied when the property is absent.
+    pub fn default(&self, kind: Kind) -> Option<&'static str> {
+        match (self, kind) {
+            (Field::Transparency, Kind::Event) => Some("OPAQUE"),
+            (Field::Classification, _) => Some("PUBLIC"),
+            _ => None,
+        }
+    }
+
+    // Every value that can be set on at least one component type.
+    pub fn choices(&self) -> Vec<&'static str> {
+        let mut choices: Vec<&'static str> = Vec::new();
+        for kind in Kind::ALL {
+            for (value, _) in self.values(*kind) {
+                if !choices.contains(value) {
+                    choices.push(value);
+                }
+            }
+        }
+        choices
+    }
+
+    pub fn accepts(&self, kind: Kind, value: &str) -> bool {
+        self.jscalendar_value(kind, value).is_some()
+    }
+
+    pub fn jscalendar_value(&self, kind: Kind, value: &str) -> Option<&'static str> {
+        self.values(kind)
+            .iter()
+            .find(|(icalendar, _)| icalendar.eq_ignore_ascii_case(value))
+            .map(|(_, jscalendar)| *jscalendar)
+    }
+
+    pub fn value<'x>(&self, entry: &'x StatusEntry) -> Option<&'x str> {
+        match self {
+            Field::Status => entry.status.as_deref(),
+            Field::Transparency => entry.transp.as_deref(),
+            Field::Classification => entry.class.as_deref(),
+        }
+    }
+}
+
+impl StatusEntry {
+    pub fn mapped(&self, field: Field) -> Option<&'static str> {
+        field
+            .value(self)
+            .or(field.default(self.kind))
+            .and_then(|value| field.jscalendar_value(self.kind, value))
+    }
+
+    pub fn is_known(&self, field: Field) -> bool {
+        field.value(self).is_none() || self.mapped(field).is_some()
+    }
+}
+
+// Lists the events and tasks of an iCalendar with their STATUS.
+pub fn entries(icalendar: &str) -> Vec<StatusEntry> {
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut current: Option<StatusEntry> = None;
+    for line in contentline::parse(icalendar) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                depth += 1;
+                let kind = match line.value.to_ascii_uppercase().as_str() {
+                    "VEVENT" => Kind::Event,
+                    "VTODO" => Kind::Task,
+                    _ => continue,
+                };
+                if depth == 2 {
+                    current = Some(StatusEntry {
+                        line: line.line,
+                        kind,
+                        uid: None,
+                        recurrence_id: None,
+                        summary: String::new(),
+                        status: None,
+                        transp: None,
+                        class: None,
+                    });
+                }
+            }
+            "END" => {
+                if depth == 2 {
+                    entries.extend(current.take());
+                }
+                depth -= 1;
+            }
+            _ if depth != 2 => {}
+            "UID" => {
+                if let Some(entry) = &mut current {
+                    entry.uid = Some(line.text());
+                }
+            }
+            "RECURRENCE-ID" => {
+                if let Some(entry) = &mut current {
+                    entry.recurrence_id = Some(line.value);
+                }
+            }
+            "SUMMARY" => {
+                if let Some(entry) = &mut current {
+                    entry.summary = line.text();
+                }
+            }
+            "STATUS" => {
+                if let Some(entry) = &mut current {
+                    entry.status = Some(line.value.trim().to_string());
+                }
+            }
+            "TRANSP" => {
+                if let Some(entry) = &mut current {
+                    entry.transp = Some(line.value.trim().to_string());
+                }
+            }
+            "CLASS" => {
+                if let Some(entry) = &mut current {
+                    entry.class = Some(line.value.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+pub fn audit(entries: &[StatusEntry]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for field in Field::ALL.iter().copied() {
+        for entry in entries.iter().filter(|entry| !entry.is_known(field)) {
+            let message = match field.property(entry.kind) {
+                Some(property) => format!(
+                    "{} \"{}\" has {}:{}, which is not valid for a {} and has no JSCalendar {}.",
+                    entry.kind.component(),
+                    entry.summary,
+                    field.name(),
+                    field.value(entry).unwrap_or_default(),
+                    entry.kind.component(),
+                    property
+                ),
+                None => format!(
+                    "{} \"{}\" has {}, which is not defined for a {} and is not converted.",
+                    entry.kind.component(),
+                    entry.summary,
+                    field.name(),
+                    entry.kind.component()
+                ),
+            };
+            findings.push(
+                Finding::new(Code::UnknownStatus, message)
+                    .with_location(format!("line {}", entry.line)),
+            );
+        }
+    }
+    findings
+}
+
+// Sets the field on the selected components, replacing the existing value
+// or adding one before the component ends. Components that do not accept the
+// value are left untouched.
+pub fn set_icalendar(source: &str, selected: &[StatusEntry], field: Field, value: &str) -> String {
+    let targets = selected
+        .iter()
+        .filter(|entry| field.accepts(entry.kind, value))
+        .map(|entry| entry.line)
+        .collect::<Vec<_>>();
+    let property = format!("{}:{}", field.name(), value.to_ascii_uppercase());
+
+    let mut text = String::with_capacity(source.len());
+    let mut depth = 0;
+    let mut inside = false;
+    let mut written = false;
+    for (number, raw) in contentline::unfold(source) {
+        let line = contentline::parse_line(number, &raw);
+        let name = line.as_ref().map(|line| line.name.as_str());
+        match name {
+            Some("BEGIN") => {
+                depth += 1;
+                if depth == 2 && targets.contains(&number) {
+                    inside = true;
+                    written = false;
+                }
+            }
+            Some("END") => {
+                if depth == 2 && inside {
+                    if !written {
+                        fold_line(&mut text, &property);
+                    }
+                    inside = false;
+                }
+                depth -= 1;
+            }
+            Some(name) if depth == 2 && inside && name == field.name() => {
+                if !written {
+                    fold_line(&mut text, &property);
+                    written = true;
+                }
+                continue;
+            }
+            _ => {}
+        }
+        fold_line(&mut text, &raw);
+    }
+    text
+}
+
+// Sets the field on the selected entries of a JSCalendar object or group.
+// Overrides are only updated when the recurrence id is already a key of the
+// series' recurrenceOverrides. Returns the updated JSCalendar and the number
+// of entries changed.
+pub fn set_jscalendar(
+    source: &str,
+    selected: &[StatusEntry],
+    field: Field,
+    value: &str,
+) -> Result<(String, usize), String> {
+    let mut root: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+    let objects = match root.get("@type").and_then(Value::as_str) {
+        Some("Group") => match root.get_mut("entries").and_then(Value::as_array_mut) {
+            Some(entries) => entries.iter_mut().collect::<Vec<_>>(),
+            None => Vec::new(),
+        },
+        _ => vec![&mut root],
+    };
+
+    let mut changed = 0;
+    for object in objects {
+        let kind = match object.get("@type").and_then(Value::as_str) {
+            Some("Event") => Kind::Event,
+            Some("Task") => Kind::Task,
+            _ => continue,
+        };
+        let (Some(property), Some(mapped)) =
+            (field.property(kind), field.jscalendar_value(kind, value))
+        else {
+            continue;
+        };
+        let uid = object
+            .get("uid")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        for entry in selected
+            .iter()
+            .filter(|entry| entry.kind == kind && uid.is_some() && entry.uid == uid)
+        {
+            let target = match &entry.recurrence_id {
+                None => Some(&mut *object),
+                Some(recurrence_id) => local_date_time(recurrence_id).and_then(|key| {
+                    object
+                        .get_mut("recurrenceOverrides")
+                        .and_then(|overrides| overrides.get_mut(key))
+                }),
+            };
+            if let Some(Value::Object(target)) = target {
+                target.insert(property.to_string(), Value::from(mapped));
+                changed += 1;
+            }
+        }
+    }
+
+    serde_json::to_string_pretty(&root)
+        .map(|text| (text, changed))
+        .map_err(|err| err.to_string())
+}
+
+// Formats an iCalendar DATE or DATE-TIME as a JSCalendar LocalDateTime.
+fn local_date_time(value: &str) -> Option<String> {
+    contentline::parse_date_or_datetime(value)
+        .map(|date_time| date_time.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
@@ -0,0 +1,138 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::borrow::Cow;
+
+use calcard::common::timezone::Tz;
+use chrono::DateTime;
+use leptos::*;
+
+use jmap_convert::{alarms::Firing, i18n::FluentArgs};
+
+use crate::ui::{i18n::I18n, pager::Pager};
+
+const PAGE_SIZE: usize = 20;
+
+// When the reminders of the expanded occurrences would go off, with the
+// JSCalendar alert trigger each one is written as.
+#[component]
+pub fn AlarmTimelinePanel(#[prop(into)] firings: Signal<Vec<Firing>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let page = create_rw_signal(0usize);
+    create_effect(move |_| {
+        firings.track();
+        page.set(0);
+    });
+    let total = Signal::derive(move || firings.with(Vec::len));
+    let shown = move || {
+        firings.with(|firings| {
+            let pages = firings.len().div_ceil(PAGE_SIZE).max(1);
+            firings
+                .iter()
+                .skip(page.get().min(pages - 1) * PAGE_SIZE)
+                .take(PAGE_SIZE)
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+    let now = DateTime::from_timestamp_millis(js_sys::Date::now() as i64)
+        .unwrap_or_default()
+        .naive_utc();
+
+    view! {
+        <Show when=move || { total.get() > 0 }>
+            <div class="mb-6 text-sm">
+                <h3 class="font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                    {move || i18n.t("alarms-title")}
+                </h3>
+                <Pager page=page total=total page_size=PAGE_SIZE noun="pager-alarms"/>
+                <div class="overflow-x-auto">
+                    <table class="min-w-full text-xs">
+                        <thead>
+                            <tr class="text-gray-500 dark:text-neutral-500">
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("alarms-fires")}
+                                </th>
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("alarms-reminds-of")}
+                                </th>
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("alarms-trigger")}
+                                </th>
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("alarms-action")}
+                                </th>
+                                <th class="py-1 font-medium text-start">"JSCalendar"</th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                            {move || {
+                                shown()
+                                    .into_iter()
+                                    .map(|firing| {
+                                        let past = firing.at.naive_utc() < now;
+                                        view! {
+                                            <tr
+                                                class="align-top text-gray-800 dark:text-neutral-200"
+                                                class:opacity-60=past
+                                                title=i18n.t_with("alarms-line", FluentArgs::from_iter([("line", firing.line)]))
+                                            >
+                                                <td class="pe-4 py-1 whitespace-nowrap font-medium">
+                                                    {date_time(&firing.at, i18n)}
+                                                    {past
+                                                        .then(|| {
+                                                            view! {
+                                                                <span class="block font-normal text-gray-500 dark:text-neutral-500">
+                                                                    {i18n.t("alarms-past")}
+                                                                </span>
+                                                            }
+                                                        })}
+                                                </td>
+                                                <td class="pe-4 py-1 whitespace-nowrap">
+                                                    {firing.title}
+                                                    <span class="block text-gray-500 dark:text-neutral-500">
+                                                        {match firing.occurrence {
+                                                            Some(start) => date_time(&start, i18n),
+                                                            None => i18n.t("alarms-fixed"),
+                                                        }}
+                                                    </span>
+                                                </td>
+                                                <td class="pe-4 py-1 whitespace-nowrap">
+                                                    {firing.trigger}
+                                                    {(firing.repeat > 0)
+                                                        .then(|| {
+                                                            view! {
+                                                                <span class="block text-gray-500 dark:text-neutral-500">
+                                                                    {i18n.t_with("alarms-repeat", FluentArgs::from_iter([("count", firing.repeat)]))}
+                                                                </span>
+                                                            }
+                                                        })}
+                                                </td>
+                                                <td class="pe-4 py-1">{firing.action}</td>
+                                                <td class="py-1 font-mono break-all">{firing.jscalendar}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+fn date_time(date_time: &DateTime<Tz>, i18n: I18n) -> String {
+    format!(
+        "{} ({})",
+        date_time.format("%a %b %-d, %Y %-I:%M%P"),
+        date_time
+            .timezone()
+            .name()
+            .unwrap_or_else(|| Cow::Owned(i18n.t("occurrence-floating")))
+    )
+}
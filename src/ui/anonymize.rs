@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{anonymize, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+#[component]
+pub fn AnonymizePanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] on_anonymize: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let status: RwSignal<Option<Result<usize, String>>> = create_rw_signal(None);
+
+    let run = move |_| {
+        let result = anonymize::anonymize(&source.get_untracked()).map(|anonymized| {
+            if anonymized.replaced > 0 {
+                on_anonymize.call(anonymized.text);
+            }
+            anonymized.replaced
+        });
+        status.set(Some(result));
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("anonymize-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("anonymize-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-white bg-blue-600 hover:bg-blue-500 disabled:opacity-50"
+                    disabled=move || source.with(|source| source.trim().is_empty())
+                    on:click=run
+                >
+                    {move || i18n.t("anonymize-run")}
+                </button>
+                {move || {
+                    status
+                        .get()
+                        .map(|status| match status {
+                            Ok(0) => i18n.t("anonymize-nothing"),
+                            Ok(count) => {
+                                i18n.t_with(
+                                    "anonymize-replaced",
+                                    FluentArgs::from_iter([("count", count)]),
+                                )
+                            }
+                            Err(err) => {
+                                i18n.t_with(
+                                    "anonymize-failed",
+                                    FluentArgs::from_iter([("error", err)]),
+                                )
+                            }
+                        })
+                }}
+            </div>
+        </details>
+    }
+}
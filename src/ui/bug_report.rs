@@ -0,0 +1,55 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+
+use jmap_convert::bug_report::BugReport;
+
+use crate::ui::{i18n::I18n, toast::Toasts};
+
+// Opens a GitHub issue filled in with the failing entry, copying the input
+// to the clipboard first when it does not fit in the link.
+#[component]
+pub fn BugReportButton(#[prop(into)] report: Signal<Option<BugReport>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let toasts = expect_context::<Toasts>();
+
+    let open = move |_| {
+        let Some(report) = report.get_untracked() else {
+            return;
+        };
+        let window = window();
+        if let Some(input) = report.clipboard.clone() {
+            let clipboard = window.navigator().clipboard();
+            spawn_local(async move {
+                match JsFuture::from(clipboard.write_text(&input)).await {
+                    Ok(_) => toasts.show(i18n.t("error-report-bug-copied"), false),
+                    Err(err) => {
+                        log::warn!("Failed to copy to the clipboard: {:?}", err);
+                        toasts.show(i18n.t("error-report-bug-copy-failed"), true);
+                    }
+                }
+            });
+        }
+        if let Err(err) = window.open_with_url_and_target(&report.url(), "_blank") {
+            log::warn!("Failed to open the issue: {:?}", err);
+        }
+    };
+
+    view! {
+        <Show when=move || report.with(Option::is_some)>
+            <button
+                type="button"
+                class="mt-1 me-3 text-xs underline hover:no-underline"
+                title=move || i18n.t("error-report-bug-title")
+                on:click=open
+            >
+                {move || i18n.t("error-report-bug")}
+            </button>
+        </Show>
+    }
+}
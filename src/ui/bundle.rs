@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::bundle::SessionBundle;
+
+use crate::{input, ui::i18n::I18n};
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+// Saves the whole session to a JSON file that can be attached to a ticket,
+// and restores one into a new document.
+#[component]
+pub fn SessionBundlePanel(
+    // Builds the bundle of the session as it is now.
+    #[prop(into)] export: Callback<(), SessionBundle>,
+    // Restores a bundle, returning a note on how it went.
+    #[prop(into)] on_import: Callback<SessionBundle, String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let status = create_rw_signal(String::new());
+    let link = create_node_ref::<html::A>();
+
+    let import_file = move |file: web_sys::File| {
+        spawn_local(async move {
+            let bundle = input::read_file(&file)
+                .await
+                .and_then(|text| text.parse::<SessionBundle>());
+            status.set(match bundle {
+                Ok(bundle) => on_import.call(bundle),
+                Err(err) => err,
+            });
+        });
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("bundle-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("bundle-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <a
+                    node_ref=link
+                    class=BUTTON_CLASS
+                    href="#"
+                    on:click=move |_| {
+                        // Built on click, before the link is followed, so it carries the time of the export.
+                        let bundle = export.call(());
+                        if let Some(link) = link.get_untracked() {
+                            link.set_download(&bundle.file_name());
+                            link.set_href(
+                                &format!(
+                                    "data:application/json;charset=utf-8,{}",
+                                    String::from(js_sys::encode_uri_component(&bundle.to_string())),
+                                ),
+                            );
+                        }
+                        status.set(String::new());
+                    }
+                >
+                    {move || i18n.t("bundle-export")}
+                </a>
+                <label class=format!("cursor-pointer {}", BUTTON_CLASS)>
+                    <input
+                        type="file"
+                        class="sr-only"
+                        accept=".json,application/json"
+                        on:change=move |ev| {
+                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                                import_file(file);
+                            }
+                            input.set_value("");
+                        }
+                    />
+                    {move || i18n.t("bundle-import")}
+                </label>
+            </div>
+            <Show when=move || !status.with(String::is_empty)>
+                <p class="mt-2 text-gray-800 dark:text-neutral-200">{move || status.get()}</p>
+            </Show>
+        </details>
+    }
+}
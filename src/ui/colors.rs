@@ -0,0 +1,89 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::colors::{normalize, ColorData};
+
+use super::i18n::I18n;
+
+#[component]
+pub fn ColorPalettePanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] converted: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let swatch = |color: &str| {
+        view! {
+            <span
+                class="inline-block shrink-0 size-5 rounded border border-gray-200 dark:border-neutral-700"
+                style=format!("background-color: {}", normalize(color).unwrap_or_default())
+            ></span>
+        }
+    };
+    let section = move |title: &'static str, data: ColorData| {
+        let colors = data
+            .colors
+            .into_iter()
+            .map(|color| {
+                view! {
+                    <li class="flex items-center gap-2 py-1">
+                        {swatch(&color.value)}
+                        <span class="text-gray-800 dark:text-neutral-200">{color.value.clone()}</span>
+                        <span class="text-gray-500 dark:text-neutral-500">
+                            {color
+                                .normalized
+                                .filter(|normalized| !normalized.eq_ignore_ascii_case(&color.value))
+                                .map(|normalized| format!("→ {}", normalized))}
+                        </span>
+                        <span class="font-mono text-xs text-gray-500 dark:text-neutral-500">
+                            {color.location}
+                        </span>
+                    </li>
+                }
+            })
+            .collect_view();
+        let categories = data
+            .categories
+            .into_iter()
+            .map(|category| {
+                view! {
+                    <span class="inline-flex items-center gap-x-1.5 py-1 px-2.5 rounded-full text-xs bg-gray-100 text-gray-800 dark:bg-neutral-700 dark:text-neutral-200">
+                        {swatch(&category.color)}
+                        {category.category}
+                    </span>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <h4 class="mt-3 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500">
+                {move || i18n.t(title)}
+            </h4>
+            <ul class="text-sm">{colors}</ul>
+            <div class="mt-1 flex flex-wrap gap-2">{categories}</div>
+        }
+    };
+
+    view! {
+        {move || {
+            let source = ColorData::extract(&source.get());
+            let converted = ColorData::extract(&converted.get());
+            (!source.is_empty() || !converted.is_empty())
+                .then(|| {
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                                {move || i18n.t("colors-title")}
+                            </h3>
+                            {section("colors-source", source)}
+                            {section("colors-converted", converted)}
+                        </div>
+                    }
+                })
+        }}
+    }
+}
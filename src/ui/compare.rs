@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+#[cfg(feature = "compare-previous")]
+use jmap_convert::{
+    compare::{compare_versions, VersionComparison, CURRENT_LABEL, PREVIOUS_LABEL},
+    diff::{has_changes, DiffLine},
+    i18n::FluentArgs,
+};
+
+#[cfg(feature = "compare-previous")]
+use crate::ui::{diff::DiffView, i18n::I18n};
+
+#[cfg(feature = "compare-previous")]
+#[component]
+pub fn VersionComparePanel(source: RwSignal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let comparison: RwSignal<Option<VersionComparison>> = create_rw_signal(None);
+
+    let section = move |title: &'static str, diff: Option<Vec<DiffLine>>| {
+        let body = match diff {
+            Some(lines) if has_changes(&lines) => view! {
+                <DiffView
+                    lines=lines
+                    old_label=Signal::derive(|| PREVIOUS_LABEL.to_string())
+                    new_label=Signal::derive(|| CURRENT_LABEL.to_string())
+                />
+            }
+            .into_view(),
+            Some(_) => view! {
+                <p class="text-sm text-green-700 dark:text-green-500">{i18n.t("diff-identical")}</p>
+            }
+            .into_view(),
+            None => ().into_view(),
+        };
+        view! {
+            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mt-4 mb-2">
+                {i18n.t(title)}
+            </h3>
+            {body}
+        }
+    };
+
+    view! {
+        <div class="mt-6">
+            <button
+                type="button"
+                class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                on:click=move |_| comparison.set(Some(compare_versions(&source.get())))
+            >
+                {move || {
+                    let mut args = FluentArgs::new();
+                    args.set("current", CURRENT_LABEL);
+                    args.set("previous", PREVIOUS_LABEL);
+                    i18n.t_with("compare-run", args)
+                }}
+            </button>
+            {move || {
+                comparison
+                    .get()
+                    .map(|comparison| {
+                        let errors = [
+                            (CURRENT_LABEL, comparison.current.as_ref().err().cloned()),
+                            (PREVIOUS_LABEL, comparison.previous.as_ref().err().cloned()),
+                        ]
+                            .into_iter()
+                            .filter_map(|(label, error)| {
+                                error
+                                    .map(|error| {
+                                        view! {
+                                            <p class="text-sm text-red-700 dark:text-red-500">
+                                                {
+                                                    let mut args = FluentArgs::new();
+                                                    args.set("version", label);
+                                                    args.set("error", error);
+                                                    i18n.t_with("compare-failed", args)
+                                                }
+                                            </p>
+                                        }
+                                    })
+                            })
+                            .collect_view();
+                        view! {
+                            <div class="mt-3">
+                                {errors}
+                                {section("compare-converted", comparison.converted_diff())}
+                                {section("compare-roundtrip", comparison.roundtrip_diff())}
+                            </div>
+                        }
+                    })
+            }}
+        </div>
+    }
+}
+
+#[cfg(not(feature = "compare-previous"))]
+#[component]
+pub fn VersionComparePanel(source: RwSignal<String>) -> impl IntoView {
+    let _ = source;
+}
@@ -0,0 +1,93 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{compatibility, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+// Warnings for constructs that Google, Outlook and Apple drop or misread on
+// import, for the iCalendar or vCard side of the conversion.
+#[component]
+pub fn CompatibilityPanel(#[prop(into)] text: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let report = create_memo(move |_| text.with(|text| compatibility::check(text)));
+    let total = move || {
+        report.with(|report| {
+            report
+                .iter()
+                .map(|(_, findings)| findings.len())
+                .sum::<usize>()
+        })
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || match total() {
+                    0 => i18n.t("compatibility-title"),
+                    total => {
+                        i18n.t_with(
+                            "compatibility-title-warnings",
+                            FluentArgs::from_iter([("count", total)]),
+                        )
+                    }
+                }}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("compatibility-description")}
+            </p>
+            {move || {
+                report
+                    .get()
+                    .into_iter()
+                    .map(|(client, findings)| {
+                        view! {
+                            <div class="mt-3">
+                                <p class="font-medium text-gray-800 dark:text-neutral-200">
+                                    {client.label()}
+                                    <span class="ms-2 text-xs font-normal text-gray-500 dark:text-neutral-500">
+                                        {match findings.len() {
+                                            0 => i18n.t("compatibility-no-problems"),
+                                            count => {
+                                                i18n.t_with(
+                                                    "compatibility-warnings",
+                                                    FluentArgs::from_iter([("count", count)]),
+                                                )
+                                            }
+                                        }}
+                                    </span>
+                                </p>
+                                <ul class="mt-1 space-y-1">
+                                    {findings
+                                        .into_iter()
+                                        .map(|finding| {
+                                            view! {
+                                                <li class="flex gap-3">
+                                                    <span
+                                                        class="font-mono text-xs text-yellow-700 dark:text-yellow-500"
+                                                        title=finding.code.title()
+                                                    >
+                                                        {finding.code.as_str()}
+                                                    </span>
+                                                    <span class="grow">{finding.message}</span>
+                                                    <span class="font-mono text-xs text-gray-500 whitespace-nowrap dark:text-neutral-500">
+                                                        {finding.location}
+                                                    </span>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </details>
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{contact_cleanup::COUNTRIES, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// Toggle and default country for rewriting phone numbers as E.164 and
+// checking email addresses before conversion.
+#[component]
+pub fn ContactCleanupPanel(
+    #[prop(into)] enabled: Signal<bool>,
+    // ISO code, empty for international numbers only.
+    #[prop(into)] country: Signal<String>,
+    // Values rewritten and values left as they were.
+    #[prop(into)] counts: Signal<(usize, usize)>,
+    #[prop(into)] on_change: Callback<(bool, String)>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("cleanup-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("cleanup-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-4">
+                <label class="inline-flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                        prop:checked=move || enabled.get()
+                        on:change=move |ev| on_change.call((event_target_checked(&ev), country.get_untracked()))
+                    />
+                    {move || i18n.t("cleanup-enable")}
+                </label>
+                <label class="inline-flex items-center gap-2">
+                    {move || i18n.t("cleanup-country")}
+                    <select
+                        class=INPUT_CLASS
+                        prop:value=move || country.get()
+                        on:change=move |ev| on_change.call((enabled.get_untracked(), event_target_value(&ev)))
+                    >
+                        <option value="">{move || i18n.t("cleanup-country-none")}</option>
+                        {COUNTRIES
+                            .iter()
+                            .map(|item| {
+                                view! {
+                                    <option value=item.code>
+                                        {format!("{} (+{})", item.name, item.calling_code)}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+            </div>
+            <Show when=move || enabled.get()>
+                <p class="mt-2 text-gray-800 dark:text-neutral-200">
+                    {move || match counts.get() {
+                        (0, 0) => i18n.t("cleanup-clean"),
+                        (cleaned, 0) => {
+                            i18n.t_with("cleanup-rewrote", FluentArgs::from_iter([("cleaned", cleaned)]))
+                        }
+                        (cleaned, invalid) => {
+                            i18n.t_with(
+                                "cleanup-rewrote-invalid",
+                                FluentArgs::from_iter([("cleaned", cleaned), ("invalid", invalid)]),
+                            )
+                        }
+                    }}
+                </p>
+            </Show>
+        </details>
+    }
+}
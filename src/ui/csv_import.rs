@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    csv_import::{Field, Layout},
+    i18n::FluentArgs,
+};
+
+use super::i18n::I18n;
+
+const SELECT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// The vCard field each column of a CSV export is read as, shown only when the
+// source is one.
+#[component]
+pub fn CsvMappingPanel(
+    #[prop(into)] columns: Signal<Option<(Layout, Vec<String>)>>,
+    mapping: RwSignal<Vec<Field>>,
+    #[prop(into)] on_change: Callback<()>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let fields = Field::all();
+
+    view! {
+        {move || {
+            let fields = fields.clone();
+            columns
+                .get()
+                .map(|(layout, headers)| {
+                    let count = headers.len();
+                    view! {
+                        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                                {move || {
+                                    let mut args = FluentArgs::new();
+                                    args.set("layout", i18n.t(layout.message_id()));
+                                    args.set(
+                                        "mapped",
+                                        mapping.with(|mapping| {
+                                            mapping.iter().filter(|field| **field != Field::Ignore).count()
+                                        }),
+                                    );
+                                    args.set("count", count);
+                                    i18n.t_with("csv-title", args)
+                                }}
+                            </summary>
+                            <table class="mt-3 text-sm">
+                                <tbody>
+                                    {headers
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, header)| {
+                                            let fields = fields.clone();
+                                            view! {
+                                                <tr>
+                                                    <td class="pe-4 py-1 text-gray-800 dark:text-neutral-200">
+                                                        {header}
+                                                    </td>
+                                                    <td class="py-1">
+                                                        <select
+                                                            class=SELECT_CLASS
+                                                            on:change=move |ev| {
+                                                                if let Some(field) = Field::parse(&event_target_value(&ev)) {
+                                                                    mapping.update(|mapping| {
+                                                                        if let Some(slot) = mapping.get_mut(index) {
+                                                                            *slot = field;
+                                                                        }
+                                                                    });
+                                                                    on_change.call(());
+                                                                }
+                                                            }
+                                                        >
+                                                            {fields
+                                                                .into_iter()
+                                                                .map(|field| {
+                                                                    view! {
+                                                                        <option
+                                                                            value=field.as_str()
+                                                                            selected=move || {
+                                                                                mapping.with(|mapping| mapping.get(index) == Some(&field))
+                                                                            }
+                                                                        >
+                                                                            {move || field_label(field, i18n)}
+                                                                        </option>
+                                                                    }
+                                                                })
+                                                                .collect_view()}
+                                                        </select>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tbody>
+                            </table>
+                        </details>
+                    }
+                })
+        }}
+    }
+}
+
+fn field_label(field: Field, i18n: I18n) -> String {
+    let mut args = FluentArgs::new();
+    match field {
+        Field::Email(kind) | Field::Phone(kind) => args.set("kind", i18n.t(kind.message_id())),
+        Field::Address(kind, part) => {
+            args.set("kind", i18n.t(kind.message_id()));
+            args.set("part", i18n.t(part.message_id()));
+        }
+        _ => (),
+    }
+    i18n.t_with(field.message_id(), args)
+}
@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    dedup::{self, Cluster},
+    i18n::FluentArgs,
+};
+
+use super::i18n::I18n;
+
+// Likely duplicates in a contact list, with a merged card for each group.
+#[component]
+pub fn DedupPanel(
+    #[prop(into)] source: Signal<String>,
+    // The list with each group replaced by its merged card.
+    #[prop(into)] on_dedupe: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let deduplicated = create_memo(move |_| {
+        source
+            .with(|source| dedup::deduplicate(source).ok())
+            .filter(|deduplicated| !deduplicated.clusters.is_empty())
+    });
+
+    view! {
+        <Show when=move || deduplicated.with(Option::is_some)>
+            <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-10">
+                <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                    <div class="mb-4 flex flex-wrap items-center justify-between gap-2">
+                        <div>
+                            <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                                {move || i18n.t("dedup-title")}
+                            </h2>
+                            <p class="text-sm text-gray-600 dark:text-neutral-400">
+                                {move || {
+                                    deduplicated
+                                        .with(|deduplicated| {
+                                            deduplicated
+                                                .as_ref()
+                                                .map(|deduplicated| {
+                                                    i18n.t_with(
+                                                        "dedup-summary",
+                                                        FluentArgs::from_iter([
+                                                            ("groups", deduplicated.clusters.len()),
+                                                            ("cards", deduplicated.cards),
+                                                        ]),
+                                                    )
+                                                })
+                                        })
+                                }}
+                            </p>
+                        </div>
+                        <button
+                            type="button"
+                            class="py-1 px-3 rounded-lg text-sm text-white bg-blue-600 hover:bg-blue-500"
+                            on:click=move |_| {
+                                if let Some(deduplicated) = deduplicated.get_untracked() {
+                                    on_dedupe.call(deduplicated.text);
+                                }
+                            }
+                        >
+                            {move || i18n.t("dedup-merge-all")}
+                        </button>
+                    </div>
+                    <ul class="text-sm">
+                        {move || {
+                            deduplicated
+                                .get()
+                                .map(|deduplicated| {
+                                    deduplicated
+                                        .clusters
+                                        .into_iter()
+                                        .map(|cluster| view! { <ClusterView cluster=cluster/> })
+                                        .collect_view()
+                                })
+                        }}
+                    </ul>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn ClusterView(cluster: Cluster) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let cards = cluster
+        .cards
+        .iter()
+        .zip(&cluster.names)
+        .map(|(index, name)| format!("#{} {}", index + 1, name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    view! {
+        <li class="mt-3">
+            <p class="font-semibold text-gray-800 dark:text-neutral-200">{cards}</p>
+            <p class="text-xs text-gray-500 dark:text-neutral-500">
+                {move || {
+                    i18n.t_with(
+                        "dedup-same",
+                        FluentArgs::from_iter([("reasons", cluster.reasons.join(", "))]),
+                    )
+                }}
+            </p>
+            <details class="mt-1 text-gray-600 dark:text-neutral-400">
+                <summary class="cursor-pointer">{move || i18n.t("dedup-merged")}</summary>
+                <pre class="mt-1 p-2 overflow-x-auto rounded-lg bg-gray-100 text-xs dark:bg-neutral-700">
+                    {cluster.merged}
+                </pre>
+            </details>
+        </li>
+    }
+}
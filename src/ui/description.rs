@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    description::Description,
+    diff::{diff_chars, DiffOp},
+    html,
+    i18n::FluentArgs,
+};
+
+use super::i18n::I18n;
+
+const BLOCK_CLASS: &str = "bg-gray-100 dark:bg-neutral-700 rounded-lg p-3 text-sm text-gray-800 dark:text-neutral-200 whitespace-pre-wrap break-words font-mono";
+
+#[component]
+pub fn DescriptionDiffPanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] converted: Signal<String>,
+    #[prop(into)] roundtrip: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let block = move |title: String, original: &str, description: Option<Description>| {
+        let body = match description {
+            Some(description) => diff_chars(original, &description.text)
+                .into_iter()
+                .map(|span| {
+                    let class = match span.op {
+                        DiffOp::Equal => "",
+                        DiffOp::Delete => "bg-red-100 text-red-800 line-through dark:bg-red-800/30 dark:text-red-400",
+                        DiffOp::Insert => "bg-green-100 text-green-800 dark:bg-green-800/30 dark:text-green-400",
+                    };
+                    view! { <span class=class>{visible_newlines(&span.text)}</span> }
+                })
+                .collect_view(),
+            None => view! {
+                <span class="text-red-700 dark:text-red-500">{i18n.t("description-missing")}</span>
+            }
+                .into_view(),
+        };
+        view! {
+            <h4 class="mt-3 mb-1 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500">
+                {title}
+            </h4>
+            <div class=BLOCK_CLASS>{body}</div>
+        }
+    };
+
+    view! {
+        {move || {
+            Description::extract(&source.get())
+                .map(|original| {
+                    let converted = Description::extract(&converted.get());
+                    let html_preview = original
+                        .html
+                        .clone()
+                        .or_else(|| converted.as_ref().and_then(|description| description.html.clone()))
+                        .map(|html| {
+                            view! {
+                                <h4 class="mt-3 mb-1 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500">
+                                    {i18n.t("description-html-preview")}
+                                </h4>
+                                <div
+                                    class="border border-gray-200 rounded-lg p-3 text-sm text-gray-800 break-words dark:border-neutral-700 dark:text-neutral-200"
+                                    inner_html=html::sanitize(&html)
+                                ></div>
+                            }
+                        });
+                    let converted_title = match converted
+                        .as_ref()
+                        .and_then(|description| description.content_type.as_deref())
+                    {
+                        Some(content_type) => i18n.t_with(
+                            "description-converted-type",
+                            FluentArgs::from_iter([("type", content_type)]),
+                        ),
+                        None => i18n.t("description-converted"),
+                    };
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                                {i18n.t("description-title")}
+                            </h3>
+                            <h4 class="mt-3 mb-1 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500">
+                                {i18n.t("description-original")}
+                            </h4>
+                            <div class=BLOCK_CLASS>{visible_newlines(&original.text)}</div>
+                            {original
+                                .raw
+                                .as_ref()
+                                .map(|raw| {
+                                    view! {
+                                        <p class="mt-1 text-xs font-mono text-gray-500 break-all dark:text-neutral-500">
+                                            {i18n.t_with("description-raw", FluentArgs::from_iter([("value", raw.as_str())]))}
+                                        </p>
+                                    }
+                                })}
+                            {html_preview}
+                            {block(converted_title, &original.text, converted)}
+                            {block(
+                                i18n.t("description-roundtrip"),
+                                &original.text,
+                                Description::extract(&roundtrip.get()),
+                            )}
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+// Newlines are the most commonly mangled characters, so they are made visible.
+fn visible_newlines(text: &str) -> String {
+    text.replace('\n', "↵\n")
+}
@@ -0,0 +1,252 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::diff::{
+    diff_chars, diff_lines, has_changes, side_by_side, with_context, DiffLine, DiffOp, DiffRow,
+    DiffSpan,
+};
+
+use super::i18n::I18n;
+
+// Unchanged lines shown around each change when only changes are shown.
+const CONTEXT_LINES: usize = 3;
+const DELETE_CLASS: &str = "bg-red-100 text-red-800 dark:bg-red-800/30 dark:text-red-400";
+const INSERT_CLASS: &str = "bg-green-100 text-green-800 dark:bg-green-800/30 dark:text-green-400";
+const GUTTER_CLASS: &str = "pe-2 text-end text-gray-400 select-none dark:text-neutral-500";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLayout {
+    Unified,
+    SideBySide,
+}
+
+// Line diff between the pasted source and its roundtrip.
+#[component]
+pub fn RoundtripDiffPanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] roundtrip: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // The diff is only worked out while the panel is open.
+    let open = create_rw_signal(false);
+    let lines = Signal::derive(move || {
+        if open.get() {
+            diff_lines(&source.get(), &roundtrip.get())
+        } else {
+            vec![]
+        }
+    });
+
+    view! {
+        <details
+            class="mb-6 text-sm text-gray-600 dark:text-neutral-400"
+            on:toggle=move |ev| open.set(event_target::<web_sys::HtmlDetailsElement>(&ev).open())
+        >
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("diff-title")}
+            </summary>
+            <div class="mt-3">
+                <DiffView
+                    lines=lines
+                    old_label=Signal::derive(move || i18n.t("diff-source"))
+                    new_label=Signal::derive(move || i18n.t("diff-roundtrip"))
+                />
+            </div>
+        </details>
+    }
+}
+
+#[component]
+pub fn DiffView(
+    #[prop(into)] lines: MaybeSignal<Vec<DiffLine>>,
+    #[prop(into)] old_label: Signal<String>,
+    #[prop(into)] new_label: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let layout = create_rw_signal(DiffLayout::Unified);
+    let only_changes = create_rw_signal(true);
+    let context = move || {
+        if only_changes.get() {
+            CONTEXT_LINES
+        } else {
+            usize::MAX
+        }
+    };
+    let layout_button = move |value: DiffLayout, label: &'static str| {
+        view! {
+            <button
+                type="button"
+                class=move || {
+                    format!(
+                        "py-1 px-3 text-xs {}",
+                        if layout.get() == value {
+                            "bg-gray-200 text-gray-800 dark:bg-neutral-600 dark:text-neutral-200"
+                        } else {
+                            "bg-gray-100 text-gray-600 hover:bg-gray-200 dark:bg-neutral-700 dark:text-neutral-400 dark:hover:bg-neutral-600"
+                        },
+                    )
+                }
+                on:click=move |_| layout.set(value)
+            >
+                {move || i18n.t(label)}
+            </button>
+        }
+    };
+
+    let unified = move || {
+        with_context(lines.get(), |line| line.op != DiffOp::Equal, context())
+            .into_iter()
+            .map(|line| match line {
+                Some(line) => {
+                    let (prefix, class) = match line.op {
+                        DiffOp::Equal => (" ", ""),
+                        DiffOp::Delete => ("-", DELETE_CLASS),
+                        DiffOp::Insert => ("+", INSERT_CLASS),
+                    };
+                    view! {
+                        <tr class=class>
+                            <td class=GUTTER_CLASS>{line.old_line}</td>
+                            <td class=GUTTER_CLASS>{line.new_line}</td>
+                            <td class="whitespace-pre">{format!("{} {}", prefix, line.text)}</td>
+                        </tr>
+                    }
+                }
+                None => view! {
+                    <tr>
+                        <td colspan="3" class="py-1 text-gray-400 dark:text-neutral-500">"⋯"</td>
+                    </tr>
+                },
+            })
+            .collect_view()
+    };
+    let split = move || {
+        let rows = side_by_side(&lines.get());
+        with_context(rows, DiffRow::is_changed, context())
+            .into_iter()
+            .map(|row| match row {
+                Some(DiffRow { old, new }) => {
+                    // Changed pairs also show which characters changed.
+                    let spans = match (&old, &new) {
+                        (Some(old), Some(new)) if old.op != DiffOp::Equal => {
+                            let spans = diff_chars(&old.text, &new.text);
+                            Some((
+                                spans
+                                    .iter()
+                                    .filter(|span| span.op != DiffOp::Insert)
+                                    .cloned()
+                                    .collect::<Vec<_>>(),
+                                spans
+                                    .into_iter()
+                                    .filter(|span| span.op != DiffOp::Delete)
+                                    .collect::<Vec<_>>(),
+                            ))
+                        }
+                        _ => None,
+                    };
+                    let (old_spans, new_spans) = spans.unzip();
+                    view! {
+                        <tr>
+                            {cell(old.map(|line| (line.old_line, line)), old_spans, DELETE_CLASS)}
+                            {cell(new.map(|line| (line.new_line, line)), new_spans, INSERT_CLASS)}
+                        </tr>
+                    }
+                }
+                None => view! {
+                    <tr>
+                        <td colspan="4" class="py-1 text-gray-400 dark:text-neutral-500">"⋯"</td>
+                    </tr>
+                },
+            })
+            .collect_view()
+    };
+
+    view! {
+        <Show
+            when=move || lines.with(|lines| has_changes(lines))
+            fallback=move || {
+                view! {
+                    <Show when=move || lines.with(|lines| !lines.is_empty())>
+                        <p class="text-sm text-green-700 dark:text-green-500">{move || i18n.t("diff-identical")}</p>
+                    </Show>
+                }
+            }
+        >
+            <div class="mb-2 flex flex-wrap items-center gap-3 text-xs">
+                <div class="inline-flex rounded-lg overflow-hidden">
+                    {layout_button(DiffLayout::Unified, "diff-unified")}
+                    {layout_button(DiffLayout::SideBySide, "diff-side-by-side")}
+                </div>
+                <label class="inline-flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                        prop:checked=move || only_changes.get()
+                        on:change=move |ev| only_changes.set(event_target_checked(&ev))
+                    />
+                    {move || i18n.t("diff-only-changes")}
+                </label>
+            </div>
+            <div class="bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                <table class="min-w-full text-xs font-mono text-gray-800 dark:text-neutral-200">
+                    <Show
+                        when=move || layout.get() == DiffLayout::SideBySide
+                        fallback=move || view! { <tbody>{unified}</tbody> }
+                    >
+                        <thead>
+                            <tr class="text-gray-500 dark:text-neutral-500">
+                                <th colspan="2" class="pb-1 font-medium text-start">{move || old_label.get()}</th>
+                                <th colspan="2" class="pb-1 font-medium text-start">{move || new_label.get()}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{split}</tbody>
+                    </Show>
+                </table>
+            </div>
+        </Show>
+    }
+}
+
+// One side of a side-by-side row, with the changed characters marked when
+// the line was replaced.
+fn cell(
+    line: Option<(Option<usize>, DiffLine)>,
+    spans: Option<Vec<DiffSpan>>,
+    changed_class: &'static str,
+) -> impl IntoView {
+    let Some((number, line)) = line else {
+        return view! {
+            <td class=GUTTER_CLASS></td>
+            <td class="w-1/2 bg-gray-50 dark:bg-neutral-800"></td>
+        }
+        .into_view();
+    };
+    let class = if line.op == DiffOp::Equal {
+        ""
+    } else {
+        changed_class
+    };
+    let text = match spans {
+        Some(spans) => spans
+            .into_iter()
+            .map(|span| {
+                let class = if span.op == DiffOp::Equal {
+                    ""
+                } else {
+                    "font-bold underline"
+                };
+                view! { <span class=class>{span.text}</span> }
+            })
+            .collect_view(),
+        None => line.text.into_view(),
+    };
+    view! {
+        <td class=GUTTER_CLASS>{number}</td>
+        <td class=format!("w-1/2 whitespace-pre {}", class)>{text}</td>
+    }
+    .into_view()
+}
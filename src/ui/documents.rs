@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::documents::Session;
+
+use super::i18n::I18n;
+
+#[component]
+pub fn DocumentTabs(
+    #[prop(into)] session: Signal<Session>,
+    #[prop(into)] on_select: Callback<usize>,
+    #[prop(into)] on_open: Callback<()>,
+    #[prop(into)] on_close: Callback<usize>,
+    #[prop(into)] on_rename: Callback<(usize, String)>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let rename = move |index: usize, name: String| {
+        if let Ok(Some(name)) =
+            window().prompt_with_message_and_default(&i18n.t("documents-name"), &name)
+        {
+            on_rename.call((index, name));
+        }
+    };
+
+    view! {
+        <div class="mb-2 flex items-end gap-1 overflow-x-auto border-b border-gray-200 dark:border-neutral-700">
+            {move || {
+                let (active, documents) = session
+                    .with(|session| {
+                        (
+                            session.active_index(),
+                            session
+                                .documents()
+                                .iter()
+                                .map(|document| document.name.clone())
+                                .collect::<Vec<_>>(),
+                        )
+                    });
+                let closable = documents.len() > 1;
+                documents
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, name)| {
+                        let class = if index == active {
+                            "bg-gray-100 text-gray-800 dark:bg-neutral-700 dark:text-neutral-200"
+                        } else {
+                            "text-gray-500 hover:text-gray-800 dark:text-neutral-500 dark:hover:text-neutral-200"
+                        };
+                        view! {
+                            <div class=format!(
+                                "shrink-0 inline-flex items-center gap-1 py-1 px-3 rounded-t-lg text-sm {}",
+                                class,
+                            )>
+                                <button
+                                    type="button"
+                                    title=i18n.t("documents-rename")
+                                    on:click=move |_| on_select.call(index)
+                                    on:dblclick={
+                                        let name = name.clone();
+                                        move |_| rename(index, name.clone())
+                                    }
+                                >
+                                    {name.clone()}
+                                </button>
+                                {closable
+                                    .then(|| {
+                                        view! {
+                                            <button
+                                                type="button"
+                                                class="text-xs text-gray-400 hover:text-red-600 dark:text-neutral-500 dark:hover:text-red-500"
+                                                title=i18n.t("documents-close")
+                                                on:click=move |_| on_close.call(index)
+                                            >
+                                                "✕"
+                                            </button>
+                                        }
+                                    })}
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }}
+            <button
+                type="button"
+                class="shrink-0 py-1 px-2 text-sm text-gray-500 hover:text-gray-800 dark:text-neutral-500 dark:hover:text-neutral-200"
+                title=move || i18n.t("documents-new")
+                on:click=move |_| on_open.call(())
+            >
+                "+"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{email::Part, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+// Selector for the calendar and vCard parts of a pasted email, shown only
+// when the source is one.
+#[component]
+pub fn EmailParts(
+    #[prop(into)] parts: Signal<Option<Vec<Part>>>,
+    #[prop(into)] selected: Signal<usize>,
+    #[prop(into)] on_select: Callback<usize>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let item_class = move |item: usize| {
+        if selected.get() == item {
+            "w-full py-1 px-2 rounded-lg text-start bg-gray-100 dark:bg-neutral-700"
+        } else {
+            "w-full py-1 px-2 rounded-lg text-start hover:bg-gray-100 dark:hover:bg-neutral-700"
+        }
+    };
+
+    view! {
+        {move || {
+            parts
+                .get()
+                .map(|parts| {
+                    view! {
+                        <div class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                            <p class="font-semibold text-gray-800 dark:text-neutral-200">
+                                {i18n.t("email-title")}
+                            </p>
+                            {if parts.is_empty() {
+                                view! {
+                                    <p class="mt-1">
+                                        {i18n.t("email-no-parts")}
+                                    </p>
+                                }
+                                    .into_view()
+                            } else {
+                                view! {
+                                    <ul class="mt-2">
+                                        {parts
+                                            .into_iter()
+                                            .enumerate()
+                                            .map(|(index, part)| {
+                                                view! {
+                                                    <li>
+                                                        <button
+                                                            type="button"
+                                                            class=move || item_class(index)
+                                                            on:click=move |_| on_select.call(index)
+                                                        >
+                                                            <span class="text-gray-800 dark:text-neutral-200">
+                                                                {part_label(&part, i18n)}
+                                                            </span>
+                                                        </button>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_view()
+                            }}
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+fn part_label(part: &Part, i18n: I18n) -> String {
+    let mut args = FluentArgs::new();
+    args.set("path", part.path.clone());
+    args.set("type", part.content_type.clone());
+    let mut label = i18n.t_with("email-part", args);
+    if let Some(method) = &part.method {
+        label.push_str(&format!(" ({})", method));
+    }
+    if let Some(name) = &part.name {
+        label.push_str(&format!(" · {}", name));
+    }
+    label
+}
@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{entry_title, i18n::FluentArgs, EntryResult};
+
+use super::i18n::I18n;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntrySummary {
+    pub title: String,
+    pub format: Option<&'static str>,
+    pub error: Option<String>,
+    pub findings: usize,
+}
+
+impl EntrySummary {
+    pub fn new(entry: &EntryResult, i18n: I18n) -> Self {
+        let title = entry_title(&entry.source).unwrap_or_else(|| i18n.t("entries-untitled"));
+        match &entry.result {
+            Ok(result) => EntrySummary {
+                title,
+                format: Some(result.format.as_str()),
+                error: None,
+                findings: result.findings.len(),
+            },
+            Err(finding) => EntrySummary {
+                title,
+                format: None,
+                error: Some(finding.message.clone()),
+                findings: 1,
+            },
+        }
+    }
+}
+
+// Selector for files holding several entries, where None stands for all.
+#[component]
+pub fn EntryList(
+    #[prop(into)] entries: Signal<Vec<EntrySummary>>,
+    #[prop(into)] selected: Signal<Option<usize>>,
+    #[prop(into)] on_select: Callback<Option<usize>>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let item_class = move |item: Option<usize>| {
+        if selected.get() == item {
+            "w-full py-1 px-2 flex items-center gap-3 rounded-lg text-start bg-gray-100 dark:bg-neutral-700"
+        } else {
+            "w-full py-1 px-2 flex items-center gap-3 rounded-lg text-start hover:bg-gray-100 dark:hover:bg-neutral-700"
+        }
+    };
+
+    view! {
+        <Show when=move || entries.with(|entries| entries.len() > 1)>
+            <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400" open>
+                <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                    {move || {
+                        entries
+                            .with(|entries| {
+                                let failed = entries.iter().filter(|entry| entry.error.is_some()).count();
+                                let mut args = FluentArgs::new();
+                                args.set("count", entries.len());
+                                args.set("failed", failed);
+                                i18n.t_with("entries-title", args)
+                            })
+                    }}
+                </summary>
+                <ul class="mt-3 max-h-72 overflow-y-auto">
+                    <li>
+                        <button type="button" class=move || item_class(None) on:click=move |_| on_select.call(None)>
+                            <span class="grow text-gray-800 dark:text-neutral-200">{move || i18n.t("entries-all")}</span>
+                        </button>
+                    </li>
+                    {move || {
+                        entries
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, entry)| {
+                                view! {
+                                    <li>
+                                        <button
+                                            type="button"
+                                            class=move || item_class(Some(index))
+                                            on:click=move |_| on_select.call(Some(index))
+                                        >
+                                            <span class="w-8 text-xs text-gray-500 dark:text-neutral-500">
+                                                {index + 1}
+                                            </span>
+                                            <span class="grow text-gray-800 dark:text-neutral-200">
+                                                {entry.title}
+                                            </span>
+                                            {match entry.error {
+                                                Some(error) => {
+                                                    view! {
+                                                        <span class="text-xs text-red-700 dark:text-red-500">
+                                                            {error}
+                                                        </span>
+                                                    }
+                                                        .into_view()
+                                                }
+                                                None => {
+                                                    view! {
+                                                        <span class="text-xs">
+                                                            {entry.format.unwrap_or_default()}
+                                                            {(entry.findings > 0)
+                                                                .then(|| {
+                                                                    format!(
+                                                                        ", {}",
+                                                                        i18n.t_with(
+                                                                            "entries-findings",
+                                                                            FluentArgs::from_iter([("count", entry.findings)]),
+                                                                        ),
+                                                                    )
+                                                                })}
+                                                        </span>
+                                                    }
+                                                        .into_view()
+                                                }
+                                            }}
+                                        </button>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </ul>
+            </details>
+        </Show>
+    }
+}
@@ -0,0 +1,173 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::preview::{AttendeeStatus, EventSummary};
+
+use crate::ui::{i18n::I18n, pager::Pager, preview::LocationView};
+
+const PAGE_SIZE: usize = 10;
+
+// A card per event, task and journal entry of the converted calendar, with
+// what a calendar client shows when one is opened.
+#[component]
+pub fn EventSummaryCards(
+    #[prop(into)] jscalendar: Signal<String>,
+    // Journal entries have no JSCalendar form and are read from here.
+    #[prop(into)] icalendar: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let summaries = create_memo(move |_| {
+        let mut summaries = EventSummary::from_jscalendar(&jscalendar.get());
+        summaries.extend(EventSummary::journals_from_icalendar(&icalendar.get()));
+        summaries
+    });
+    let page = create_rw_signal(0usize);
+    create_effect(move |_| {
+        summaries.track();
+        page.set(0);
+    });
+    let total = Signal::derive(move || summaries.with(Vec::len));
+    let shown = move || {
+        summaries.with(|summaries| {
+            let pages = summaries.len().div_ceil(PAGE_SIZE).max(1);
+            summaries
+                .iter()
+                .skip(page.get().min(pages - 1) * PAGE_SIZE)
+                .take(PAGE_SIZE)
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! {
+        <Show when=move || { total.get() > 0 }>
+            <div class="mb-6">
+                <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                    {move || i18n.t("event-summary-title")}
+                </h3>
+                <Show when=move || { total.get() > PAGE_SIZE }>
+                    <Pager page=page total=total page_size=PAGE_SIZE noun="pager-entries"/>
+                </Show>
+                <div class="grid gap-4 sm:grid-cols-2">
+                    {move || shown().into_iter().map(summary_card).collect_view()}
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+fn summary_card(summary: EventSummary) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let label = move |message_id: &'static str| {
+        view! {
+            <p class="mt-3 text-xs font-medium uppercase text-gray-500 dark:text-neutral-500">
+                {move || i18n.t(message_id)}
+            </p>
+        }
+    };
+    let attendees = summary
+        .attendees
+        .into_iter()
+        .filter(|attendee| !attendee.organizer)
+        .map(|attendee| {
+            let (status, class) = match attendee.status {
+                AttendeeStatus::Accepted => (
+                    "event-summary-accepted",
+                    "text-green-600 dark:text-green-500",
+                ),
+                AttendeeStatus::Declined => {
+                    ("event-summary-declined", "text-red-600 dark:text-red-500")
+                }
+                AttendeeStatus::Tentative => (
+                    "event-summary-tentative",
+                    "text-yellow-600 dark:text-yellow-500",
+                ),
+                AttendeeStatus::Pending => (
+                    "event-summary-no-reply",
+                    "text-gray-400 dark:text-neutral-500",
+                ),
+            };
+            view! {
+                <li class="flex justify-between gap-2">
+                    <span class="truncate">{attendee.name}</span>
+                    <span class=format!("shrink-0 text-xs {}", class)>
+                        {move || i18n.t(status)}
+                    </span>
+                </li>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="border border-gray-200 rounded-xl p-4 text-sm text-gray-800 dark:border-neutral-700 dark:text-neutral-200">
+            <div class="flex items-start justify-between gap-2">
+                <p class="font-semibold">{summary.title}</p>
+                <span class="shrink-0 py-0.5 px-2 rounded-full text-xs bg-gray-100 text-gray-600 dark:bg-neutral-700 dark:text-neutral-400">
+                    {move || i18n.t(summary.kind.message_id())}
+                </span>
+            </div>
+            {summary.when.map(|when| view! { <p class="mt-1">{when}</p> })}
+            {summary
+                .progress
+                .map(|progress| {
+                    view! { <p class="text-teal-700 dark:text-teal-500">{progress}</p> }
+                })}
+            {summary
+                .recurrence
+                .map(|recurrence| {
+                    view! { <p class="text-gray-600 dark:text-neutral-400">{recurrence}</p> }
+                })}
+            {summary
+                .location
+                .map(|location| {
+                    view! {
+                        {label("event-summary-location")}
+                        <LocationView location=location/>
+                    }
+                })}
+            {summary
+                .organizer
+                .map(|organizer| {
+                    view! {
+                        {label("event-summary-organizer")}
+                        <p>{organizer}</p>
+                    }
+                })}
+            {(!attendees.is_empty())
+                .then(|| {
+                    view! {
+                        {label("event-summary-attendees")}
+                        <ul class="space-y-0.5">{attendees}</ul>
+                    }
+                })}
+            {summary
+                .description
+                .map(|description| {
+                    view! {
+                        {label("event-summary-description")}
+                        <p class="whitespace-pre-line line-clamp-6 text-gray-600 dark:text-neutral-400">
+                            {description}
+                        </p>
+                    }
+                })}
+            {(!summary.alarms.is_empty())
+                .then(|| {
+                    view! {
+                        {label("event-summary-alarms")}
+                        <ul class="space-y-0.5 text-gray-600 dark:text-neutral-400">
+                            {summary
+                                .alarms
+                                .into_iter()
+                                .map(|alarm| view! { <li>{alarm}</li> })
+                                .collect_view()}
+                        </ul>
+                    }
+                })}
+        </div>
+    }
+}
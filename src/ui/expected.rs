@@ -0,0 +1,150 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    expected::{self, DisagreementKind},
+    i18n::FluentArgs,
+    Format,
+};
+
+use super::i18n::I18n;
+
+const KINDS: [DisagreementKind; 3] = [
+    DisagreementKind::Different,
+    DisagreementKind::Missing,
+    DisagreementKind::Unexpected,
+];
+
+#[component]
+pub fn ExpectedOutputPanel(
+    #[prop(into)] converted: Signal<String>,
+    // Format of `converted`.
+    #[prop(into)] format: Signal<Format>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let expected_output = create_rw_signal(String::new());
+    let comparison = create_memo(move |_| {
+        let expected_output = expected_output.get();
+        if expected_output.trim().is_empty() || converted.with(String::is_empty) {
+            return None;
+        }
+        Some(expected::compare(
+            format.get(),
+            &converted.get(),
+            &expected_output,
+        ))
+    });
+
+    let summary = move || {
+        comparison.with(|comparison| match comparison {
+            Some(Ok(disagreements)) if disagreements.is_empty() => i18n.t("expected-none"),
+            Some(Ok(disagreements)) => {
+                let counts = KINDS
+                    .iter()
+                    .filter_map(|kind| {
+                        let count = disagreements
+                            .iter()
+                            .filter(|disagreement| disagreement.kind == *kind)
+                            .count();
+                        let message_id = match kind {
+                            DisagreementKind::Different => "expected-different-count",
+                            DisagreementKind::Missing => "expected-missing-count",
+                            DisagreementKind::Unexpected => "expected-unexpected-count",
+                        };
+                        (count > 0).then(|| {
+                            i18n.t_with(message_id, FluentArgs::from_iter([("count", count)]))
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let mut args = FluentArgs::new();
+                args.set("count", disagreements.len());
+                args.set("kinds", counts.join(", "));
+                i18n.t_with("expected-summary", args)
+            }
+            Some(Err(err)) => err.clone(),
+            None => String::new(),
+        })
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("expected-title")}
+            </summary>
+            <p class="mt-3">
+                {move || {
+                    i18n.t_with(
+                        "expected-description",
+                        FluentArgs::from_iter([("format", format.get().as_str())]),
+                    )
+                }}
+            </p>
+            <textarea
+                class="mt-2 p-3 block w-full bg-gray-100 border-gray-200 rounded-lg text-sm font-mono dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-400"
+                rows="6"
+                placeholder=move || i18n.t("expected-placeholder")
+                prop:value=move || expected_output.get()
+                on:input=move |ev| expected_output.set(event_target_value(&ev))
+            ></textarea>
+            <p class="mt-3 font-medium text-gray-800 dark:text-neutral-200">{summary}</p>
+            <Show when=move || {
+                comparison.with(|comparison| matches!(comparison, Some(Ok(disagreements)) if !disagreements.is_empty()))
+            }>
+                <div class="mt-2 overflow-x-auto">
+                    <table class="min-w-full text-xs">
+                        <thead>
+                            <tr class="text-gray-500 dark:text-neutral-500">
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("expected-path")}
+                                </th>
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("expected-ours")}
+                                </th>
+                                <th class="py-1 font-medium text-start">
+                                    {move || i18n.t("expected-expected")}
+                                </th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                            {move || {
+                                comparison
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|disagreement| {
+                                        let class = match disagreement.kind {
+                                            DisagreementKind::Different => "text-yellow-700 dark:text-yellow-500",
+                                            DisagreementKind::Missing => "text-red-700 dark:text-red-500",
+                                            DisagreementKind::Unexpected => "text-blue-700 dark:text-blue-400",
+                                        };
+                                        view! {
+                                            <tr class="align-top">
+                                                <td class="pe-4 py-1 font-mono break-all">
+                                                    <span class=class>{i18n.t(disagreement.kind.message_id())}</span>
+                                                    <br/>
+                                                    {disagreement.path}
+                                                </td>
+                                                <td class="pe-4 py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                    {disagreement.ours.unwrap_or_default()}
+                                                </td>
+                                                <td class="py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                    {disagreement.expected.unwrap_or_default()}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </Show>
+        </details>
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use jmap_convert::{
+    export::{self, ExportContext},
+    i18n::FluentArgs,
+    redact, Format,
+};
+
+use crate::ui::{i18n::I18n, settings::ExportSettings, toast::Toasts};
+
+#[component]
+pub fn CopyButton(
+    #[prop(into)] payload: Signal<String>,
+    // Context without a timestamp, which is taken when copying.
+    #[prop(into)] context: Signal<ExportContext>,
+    #[prop(into)] json: Signal<bool>,
+    // Message id of the label.
+    #[prop(default = "export-copy")] label: &'static str,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let settings = expect_context::<ExportSettings>();
+    let toasts = expect_context::<Toasts>();
+    let status: RwSignal<Option<&'static str>> = create_rw_signal(None);
+
+    let copy = move |_| {
+        let mut context = ExportContext {
+            timestamp: String::from(js_sys::Date::new_0().to_iso_string()),
+            ..context.get()
+        };
+        let mut payload = payload.get();
+        if settings.redact_private.get() {
+            if let Some((redacted, count)) = redact_payload(&payload, &context.format) {
+                payload = redacted;
+                context
+                    .options
+                    .push(("redacted".to_string(), count.to_string()));
+            }
+        }
+        let text = export::with_context(&payload, &context, settings.header(json.get()));
+        spawn_local(async move {
+            let result = match web_sys::window() {
+                Some(window) => JsFuture::from(window.navigator().clipboard().write_text(&text))
+                    .await
+                    .map(|_| ()),
+                None => Err("No window".into()),
+            };
+            match result {
+                Ok(()) => {
+                    status.set(Some("export-copied"));
+                    toasts.show(
+                        i18n.t_with(
+                            "export-copied-toast",
+                            FluentArgs::from_iter([("format", context.format)]),
+                        ),
+                        false,
+                    );
+                }
+                Err(err) => {
+                    log::warn!("Failed to copy to the clipboard: {:?}", err);
+                    status.set(Some("export-copy-failed"));
+                    toasts.show(i18n.t("export-copy-failed-toast"), true);
+                }
+            }
+        });
+    };
+
+    view! {
+        <button
+            type="button"
+            class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+            on:click=copy
+            on:mouseleave=move |_| status.set(None)
+        >
+            {move || i18n.t(status.get().unwrap_or(label))}
+        </button>
+    }
+}
+
+// Saves the payload as a file named after its first entry.
+#[component]
+pub fn DownloadButton(
+    #[prop(into)] payload: Signal<String>,
+    #[prop(into)] format: Signal<Format>,
+    // Message id of the label.
+    #[prop(default = "export-download")] label: &'static str,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let settings = expect_context::<ExportSettings>();
+    let status: RwSignal<Option<&'static str>> = create_rw_signal(None);
+
+    let save = move |_| {
+        if let Err(err) = download(payload.get(), format.get(), settings.redact_private.get()) {
+            log::warn!("Failed to download the output: {:?}", err);
+            status.set(Some("export-download-failed"));
+        }
+    };
+
+    view! {
+        <button
+            type="button"
+            class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+            on:click=save
+            on:mouseleave=move |_| status.set(None)
+        >
+            {move || i18n.t(status.get().unwrap_or(label))}
+        </button>
+    }
+}
+
+// Saves the payload as a file named after its first entry, without private
+// events when `redact_private` is set.
+pub fn download(mut payload: String, format: Format, redact_private: bool) -> Result<(), JsValue> {
+    if redact_private {
+        if let Some((redacted, _)) = redact_payload(&payload, format.as_str()) {
+            payload = redacted;
+        }
+    }
+    save_file(
+        &payload,
+        format.mime_type(),
+        &export::file_name(format, &payload),
+    )
+}
+
+fn redact_payload(payload: &str, format: &str) -> Option<(String, usize)> {
+    match format {
+        "iCalendar" => Some(redact::redact_icalendar(payload)),
+        "JSCalendar" => redact::redact_jscalendar(payload).ok(),
+        _ => None,
+    }
+}
+
+// Hands the contents to the browser as a file download.
+pub fn save_file(contents: &str, mime_type: &str, file_name: &str) -> Result<(), JsValue> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(
+        &js_sys::Array::of1(&JsValue::from_str(contents)),
+        &options,
+    )?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let link = document()
+        .create_element("a")?
+        .dyn_into::<web_sys::HtmlAnchorElement>()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    web_sys::Url::revoke_object_url(&url)
+}
@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    fidelity::{Loss, LossKind},
+    i18n::FluentArgs,
+    Format,
+};
+
+use super::i18n::I18n;
+
+#[component]
+pub fn FidelityReportPanel(
+    #[prop(into)] losses: Signal<Vec<Loss>>,
+    // Format of the source.
+    #[prop(into)] format: Signal<Format>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let summary = move || {
+        losses.with(|losses| {
+            if losses.is_empty() {
+                return i18n.t_with(
+                    "fidelity-none",
+                    FluentArgs::from_iter([("format", format.get().counterpart().as_str())]),
+                );
+            }
+            let counts = LossKind::ALL
+                .iter()
+                .filter_map(|kind| {
+                    let count = losses.iter().filter(|loss| loss.kind == *kind).count();
+                    let message_id = match kind {
+                        LossKind::Dropped => "fidelity-dropped-count",
+                        LossKind::Renamed => "fidelity-renamed-count",
+                        LossKind::ParameterLost => "fidelity-parameter-lost-count",
+                    };
+                    (count > 0)
+                        .then(|| i18n.t_with(message_id, FluentArgs::from_iter([("count", count)])))
+                })
+                .collect::<Vec<_>>();
+            let mut args = FluentArgs::new();
+            args.set("count", losses.len());
+            args.set("kinds", counts.join(", "));
+            i18n.t_with("fidelity-summary", args)
+        })
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("fidelity-title")}
+            </summary>
+            <p class="mt-3">
+                {move || {
+                    i18n.t_with(
+                        "fidelity-description",
+                        FluentArgs::from_iter([("format", format.get().counterpart().as_str())]),
+                    )
+                }}
+            </p>
+            <p class="mt-3 font-medium text-gray-800 dark:text-neutral-200">{summary}</p>
+            <Show when=move || !losses.with(Vec::is_empty)>
+                <div class="mt-2 overflow-x-auto">
+                    <table class="min-w-full text-xs">
+                        <thead>
+                            <tr class="text-gray-500 dark:text-neutral-500">
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("fidelity-property")}
+                                </th>
+                                <th class="pe-4 py-1 font-medium text-start">
+                                    {move || i18n.t("fidelity-lost")}
+                                </th>
+                                <th class="py-1 font-medium text-start">
+                                    {move || i18n.t("fidelity-original")}
+                                </th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                            {move || {
+                                losses
+                                    .get()
+                                    .into_iter()
+                                    .map(|loss| {
+                                        let class = match loss.kind {
+                                            LossKind::Dropped => "text-red-700 dark:text-red-500",
+                                            LossKind::Renamed => "text-blue-700 dark:text-blue-400",
+                                            LossKind::ParameterLost => "text-yellow-700 dark:text-yellow-500",
+                                        };
+                                        let lost = match (loss.kind, loss.detail) {
+                                            (LossKind::Renamed, Some(name)) => {
+                                                i18n.t_with("fidelity-now", FluentArgs::from_iter([("name", name)]))
+                                            }
+                                            (_, Some(detail)) => detail,
+                                            (_, None) => String::new(),
+                                        };
+                                        view! {
+                                            <tr class="align-top">
+                                                <td class="pe-4 py-1 font-mono break-all">
+                                                    <span class=class>{i18n.t(loss.kind.message_id())}</span>
+                                                    {loss
+                                                        .location
+                                                        .map(|location| {
+                                                            view! {
+                                                                <span class="ms-2 text-gray-500 dark:text-neutral-500">
+                                                                    {location}
+                                                                </span>
+                                                            }
+                                                        })}
+                                                    <br/>
+                                                    {loss.path}
+                                                </td>
+                                                <td class="pe-4 py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                    {lost}
+                                                </td>
+                                                <td class="py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                    {loss.value}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </Show>
+        </details>
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{filter::ComponentKind, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+// Component types left out of the conversion and the expansion, without
+// touching the source itself.
+#[component]
+pub fn ComponentFilterPanel(
+    #[prop(into)] excluded: Signal<Vec<ComponentKind>>,
+    // Components dropped from the last conversion.
+    #[prop(into)] removed: Signal<usize>,
+    #[prop(into)] on_change: Callback<Vec<ComponentKind>>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let toggle = move |kind: ComponentKind, included: bool| {
+        let mut excluded = excluded.get_untracked();
+        excluded.retain(|item| *item != kind);
+        if !included {
+            excluded.push(kind);
+        }
+        on_change.call(excluded);
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("filter-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("filter-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap gap-4">
+                {ComponentKind::ALL
+                    .iter()
+                    .map(|kind| {
+                        let kind = *kind;
+                        view! {
+                            <label class="inline-flex items-center gap-1">
+                                <input
+                                    type="checkbox"
+                                    class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                                    prop:checked=move || excluded.with(|excluded| !excluded.contains(&kind))
+                                    on:change=move |ev| toggle(kind, event_target_checked(&ev))
+                                />
+                                {move || i18n.t(kind.message_id())}
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <Show when=move || { removed.get() > 0 }>
+                <p class="mt-2 text-gray-800 dark:text-neutral-200">
+                    {move || i18n.t_with("filter-removed", FluentArgs::from_iter([("count", removed.get())]))}
+                </p>
+            </Show>
+        </details>
+    }
+}
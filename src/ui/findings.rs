@@ -0,0 +1,167 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use codee::string::FromToStringCodec;
+use leptos::*;
+use leptos_use::storage::use_local_storage;
+
+use jmap_convert::{
+    findings::{format_code_list, parse_code_list, Code, Finding, FindingFilter, Severity},
+    i18n::FluentArgs,
+};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+#[component]
+pub fn FindingsPanel(
+    #[prop(into)] findings: Signal<Vec<Finding>>,
+    // Drops the card chrome when shown inside a workspace pane.
+    #[prop(optional)] embedded: bool,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let (suppressed, set_suppressed, _) =
+        use_local_storage::<String, FromToStringCodec>("jmap-convert.suppressed-codes");
+    let min_severity: RwSignal<Option<Severity>> = create_rw_signal(None);
+    let code_query = create_rw_signal(String::new());
+
+    let filter = move || FindingFilter {
+        min_severity: min_severity.get(),
+        code_query: code_query.get(),
+        suppressed: parse_code_list(&suppressed.get()),
+    };
+    let visible = move || {
+        let filter = filter();
+        findings
+            .get()
+            .into_iter()
+            .filter(|finding| filter.matches(finding))
+            .collect::<Vec<_>>()
+    };
+    let set_suppression = move |code: Code, suppress: bool| {
+        let mut codes = parse_code_list(&suppressed.get());
+        codes.retain(|item| *item != code);
+        if suppress {
+            codes.push(code);
+            codes.sort_unstable();
+        }
+        set_suppressed.set(format_code_list(&codes));
+    };
+
+    view! {
+        <Show when=move || !findings.get().is_empty()>
+            <div class=if embedded { "" } else { "max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-10" }>
+                <div class=if embedded {
+                    ""
+                } else {
+                    "bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800"
+                }>
+                    <div class="mb-4 flex flex-wrap items-center justify-between gap-2">
+                        <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                            {move || i18n.t("findings-title")}
+                        </h2>
+                        <div class="flex flex-wrap items-center gap-2">
+                            <select
+                                class=INPUT_CLASS
+                                on:change=move |ev| min_severity.set(Severity::parse(&event_target_value(&ev)))
+                            >
+                                <option value="">{move || i18n.t("findings-all")}</option>
+                                <option value="warning">{move || i18n.t("findings-warnings")}</option>
+                                <option value="error">{move || i18n.t("findings-errors")}</option>
+                            </select>
+                            <input
+                                type="text"
+                                class=INPUT_CLASS
+                                placeholder=move || i18n.t("findings-filter")
+                                prop:value=move || code_query.get()
+                                on:input=move |ev| code_query.set(event_target_value(&ev))
+                            />
+                        </div>
+                    </div>
+
+                    <ul class="divide-y divide-gray-200 dark:divide-neutral-700">
+                        <For
+                            each=visible
+                            key=|finding| finding.clone()
+                            children=move |finding| {
+                                let code = finding.code;
+                                let badge = match finding.severity() {
+                                    Severity::Error => "bg-red-100 text-red-800 dark:bg-red-800/30 dark:text-red-500",
+                                    Severity::Warning => "bg-yellow-100 text-yellow-800 dark:bg-yellow-800/30 dark:text-yellow-500",
+                                    Severity::Info => "bg-blue-100 text-blue-800 dark:bg-blue-800/30 dark:text-blue-400",
+                                };
+                                view! {
+                                    <li class="py-2 flex items-start gap-3 text-sm">
+                                        <span
+                                            class=format!("shrink-0 py-0.5 px-2 rounded-full text-xs font-medium font-mono {}", badge)
+                                            title=code.title()
+                                        >
+                                            {code.as_str()}
+                                        </span>
+                                        <div class="grow text-gray-800 dark:text-neutral-200">
+                                            {finding.message.clone()}
+                                            {finding
+                                                .location
+                                                .clone()
+                                                .map(|location| {
+                                                    view! {
+                                                        <span class="ms-2 text-xs text-gray-500 dark:text-neutral-500">
+                                                            {location}
+                                                        </span>
+                                                    }
+                                                })}
+                                        </div>
+                                        <Show when=move || code.severity() != Severity::Error>
+                                            <button
+                                                type="button"
+                                                class="shrink-0 text-xs text-blue-600 hover:underline dark:text-blue-400"
+                                                on:click=move |_| set_suppression(code, true)
+                                            >
+                                                {move || i18n.t("findings-suppress")}
+                                            </button>
+                                        </Show>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+
+                    <div class="mt-3 flex flex-wrap items-center gap-2 text-xs text-gray-500 dark:text-neutral-500">
+                        {move || {
+                            let total = findings.get().len();
+                            let shown = visible().len();
+                            (shown < total)
+                                .then(|| {
+                                    let mut args = FluentArgs::new();
+                                    args.set("hidden", total - shown);
+                                    args.set("count", total);
+                                    i18n.t_with("findings-hidden", args)
+                                })
+                        }}
+                        {move || {
+                            parse_code_list(&suppressed.get())
+                                .into_iter()
+                                .map(|code| {
+                                    view! {
+                                        <button
+                                            type="button"
+                                            class="py-0.5 px-2 rounded-full font-mono bg-gray-100 hover:bg-gray-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                                            title=i18n.t("findings-unsuppress")
+                                            on:click=move |_| set_suppression(code, false)
+                                        >
+                                            {format!("{} ✕", code.as_str())}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    export::ExportContext,
+    freebusy::{self, Block, BlockKind, BusyStatus},
+    i18n::FluentArgs,
+    preview::duration_text,
+};
+
+use crate::ui::{export::CopyButton, i18n::I18n};
+
+const TIME_FORMAT: &str = "%a %b %-d, %Y %H:%M";
+
+// Free/busy and availability components as a timeline, with their JSCalendar
+// form. Shown only when the calendar has any.
+#[component]
+pub fn FreeBusyPanel(#[prop(into)] icalendar: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let blocks = create_memo(move |_| icalendar.with(|icalendar| freebusy::blocks(icalendar)));
+    let output = Signal::derive(move || blocks.with(|blocks| freebusy::to_jscalendar(blocks)));
+
+    view! {
+        <Show when=move || blocks.with(|blocks| !blocks.is_empty())>
+            <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                    {move || {
+                        blocks
+                            .with(|blocks| {
+                                let count = |kind| blocks.iter().filter(|block| block.kind == kind).count();
+                                let mut args = FluentArgs::new();
+                                args.set("freebusy", count(BlockKind::FreeBusy));
+                                args.set("availability", count(BlockKind::Availability));
+                                i18n.t_with("freebusy-title", args)
+                            })
+                    }}
+                </summary>
+                {move || blocks.get().into_iter().map(|block| timeline(block, i18n)).collect_view()}
+                <div class="relative mt-4 bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                    <div class="absolute top-2 end-2 flex gap-1">
+                        <CopyButton
+                            payload=output
+                            context=Signal::derive(move || ExportContext {
+                                format: "JSCalendar availability".to_string(),
+                                source_type: "iCalendar".to_string(),
+                                version: env!("CARGO_PKG_VERSION").to_string(),
+                                options: vec![],
+                                timestamp: String::new(),
+                            })
+                            json=true
+                        />
+                    </div>
+                    <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                        {move || output.get()}
+                    </pre>
+                </div>
+            </details>
+        </Show>
+    }
+}
+
+fn timeline(block: Block, i18n: I18n) -> impl IntoView {
+    let range = block.range();
+    let position = move |start: chrono::NaiveDateTime, end: chrono::NaiveDateTime| {
+        let Some((from, to)) = range else {
+            return String::new();
+        };
+        let total = (to - from).num_seconds().max(1) as f64;
+        let left = (start - from).num_seconds() as f64 / total * 100.0;
+        let width = ((end - start).num_seconds() as f64 / total * 100.0).max(0.5);
+        format!(
+            "left: {:.2}%; width: {:.2}%",
+            left.clamp(0.0, 100.0),
+            width.min(100.0 - left)
+        )
+    };
+    let heading = [
+        Some(i18n.t(block.kind.message_id())),
+        block.title.clone(),
+        block.uid.clone().map(|uid| format!("UID {}", uid)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    let span = match (block.start, block.end) {
+        (Some(start), Some(end)) => format!(
+            "{} – {}{}",
+            start.format(TIME_FORMAT),
+            end.format(TIME_FORMAT),
+            zone_suffix(block.time_zone.as_deref())
+        ),
+        _ => i18n.t("freebusy-no-span"),
+    };
+
+    view! {
+        <div class="mt-3">
+            <p class="font-medium text-gray-800 dark:text-neutral-200">
+                {heading}
+                <span class="ms-2 font-normal text-gray-500 dark:text-neutral-500">
+                    {i18n.t_with("source-line", FluentArgs::from_iter([("line", block.line)]))}
+                </span>
+            </p>
+            <p>{span}</p>
+            {(block.kind == BlockKind::Availability)
+                .then(|| {
+                    view! {
+                        <p>
+                            {i18n
+                                .t_with(
+                                    "freebusy-outside",
+                                    FluentArgs::from_iter([("status", i18n.t(block.status.message_id()))]),
+                                )}
+                        </p>
+                    }
+                })}
+            {if block.intervals.is_empty() {
+                view! { <p class="mt-1">{i18n.t("freebusy-no-periods")}</p> }.into_view()
+            } else {
+                view! {
+                    <div class="mt-2 overflow-x-auto">
+                        <table class="min-w-full text-xs">
+                            <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                {block
+                                    .intervals
+                                    .into_iter()
+                                    .map(|interval| {
+                                        let style = position(interval.start, interval.end);
+                                        view! {
+                                            <tr class="align-top">
+                                                <td class=format!("pe-4 py-1 font-medium {}", status_class(interval.status))>
+                                                    {i18n.t(interval.status.message_id())}
+                                                </td>
+                                                <td class="pe-4 py-1 whitespace-nowrap">
+                                                    {format!(
+                                                        "{} – {}{}",
+                                                        interval.start.format(TIME_FORMAT),
+                                                        interval.end.format(TIME_FORMAT),
+                                                        zone_suffix(interval.time_zone.as_deref()),
+                                                    )}
+                                                    <br/>
+                                                    <span class="text-gray-500 dark:text-neutral-500">
+                                                        {duration_text(interval.end - interval.start)}
+                                                    </span>
+                                                </td>
+                                                <td class="pe-4 py-1 w-48">
+                                                    <div class="relative mt-1 h-2 w-48 rounded-full bg-gray-100 dark:bg-neutral-700">
+                                                        <div
+                                                            class=format!("absolute h-2 rounded-full {}", bar_class(interval.status))
+                                                            style=style
+                                                        ></div>
+                                                    </div>
+                                                </td>
+                                                <td class="py-1">
+                                                    {interval.title}
+                                                    {interval
+                                                        .recurrence
+                                                        .map(|recurrence| {
+                                                            view! {
+                                                                <br/>
+                                                                <span class="text-gray-500 dark:text-neutral-500">{recurrence}</span>
+                                                            }
+                                                        })}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+                    .into_view()
+            }}
+        </div>
+    }
+}
+
+fn zone_suffix(time_zone: Option<&str>) -> String {
+    time_zone
+        .map(|zone| format!(" ({})", zone))
+        .unwrap_or_default()
+}
+
+fn status_class(status: BusyStatus) -> &'static str {
+    match status {
+        BusyStatus::Free => "text-green-600 dark:text-green-500",
+        BusyStatus::Busy => "text-red-600 dark:text-red-500",
+        BusyStatus::Tentative => "text-yellow-700 dark:text-yellow-500",
+        BusyStatus::Unavailable => "text-gray-800 dark:text-neutral-200",
+    }
+}
+
+fn bar_class(status: BusyStatus) -> &'static str {
+    match status {
+        BusyStatus::Free => "bg-green-500",
+        BusyStatus::Busy => "bg-red-500",
+        BusyStatus::Tentative => "bg-yellow-500",
+        BusyStatus::Unavailable => "bg-gray-500",
+    }
+}
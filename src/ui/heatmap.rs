@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{heatmap::WeeklyHeatmap, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+const WEEKDAYS: [&str; 7] = [
+    "weekday-mon",
+    "weekday-tue",
+    "weekday-wed",
+    "weekday-thu",
+    "weekday-fri",
+    "weekday-sat",
+    "weekday-sun",
+];
+
+// Hours of the week the occurrences fall in, darker where more of them
+// overlap. Rules anchored in the wrong time zone show up as a shifted band.
+#[component]
+pub fn HeatmapPanel(#[prop(into)] heatmap: Signal<Option<WeeklyHeatmap>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let hour_args = move |day: usize, hour: usize, count: usize| {
+        let mut args = FluentArgs::new();
+        args.set("day", i18n.t(WEEKDAYS[day]));
+        args.set("hour", format!("{:02}:00", hour));
+        args.set("count", count);
+        args
+    };
+
+    view! {
+        {move || {
+            heatmap
+                .get()
+                .map(|heatmap| {
+                    let (day, hour) = heatmap.busiest();
+                    view! {
+                        <div class="mb-6 text-sm">
+                            <p class="mb-2 text-gray-600 dark:text-neutral-400">
+                                {i18n.t_with("heatmap-busiest", hour_args(day, hour, heatmap.cells[day][hour]))}
+                            </p>
+                            <div class="overflow-x-auto">
+                                <table class="border-separate border-spacing-0.5 text-xs text-gray-500 dark:text-neutral-500">
+                                    <thead>
+                                        <tr>
+                                            <th></th>
+                                            {(0..24)
+                                                .map(|hour| {
+                                                    view! {
+                                                        <th class="w-5 font-normal text-start">
+                                                            {(hour % 3 == 0).then(|| format!("{:02}", hour))}
+                                                        </th>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {(0..7)
+                                            .map(|day| {
+                                                view! {
+                                                    <tr>
+                                                        <th class="pe-2 font-normal text-start">{i18n.t(WEEKDAYS[day])}</th>
+                                                        {(0..24)
+                                                            .map(|hour| {
+                                                                let count = heatmap.cells[day][hour];
+                                                                let (class, opacity) = if count > 0 {
+                                                                    (
+                                                                        "bg-blue-600",
+                                                                        0.2 + 0.8 * count as f64 / heatmap.max as f64,
+                                                                    )
+                                                                } else {
+                                                                    ("bg-gray-100 dark:bg-neutral-700", 1.0)
+                                                                };
+                                                                view! {
+                                                                    <td
+                                                                        class=format!("h-5 w-5 rounded-sm {}", class)
+                                                                        style=format!("opacity: {:.2}", opacity)
+                                                                        title=i18n.t_with("heatmap-cell", hour_args(day, hour, count))
+                                                                    ></td>
+                                                                }
+                                                            })
+                                                            .collect_view()}
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
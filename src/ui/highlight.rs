@@ -0,0 +1,40 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::highlight::{self, TokenKind};
+
+// Property names, parameters and values of iCalendar and vCard, or the keys
+// and values of JSCalendar and JSContact, each in its own colour.
+#[component]
+pub fn HighlightedText(#[prop(into)] text: Signal<String>) -> impl IntoView {
+    move || {
+        text.with(|text| {
+            highlight::highlight(text)
+                .into_iter()
+                .map(|(kind, token)| match token_class(kind) {
+                    Some(class) => {
+                        view! { <span class=class>{token.to_string()}</span> }.into_view()
+                    }
+                    None => token.to_string().into_view(),
+                })
+                .collect_view()
+        })
+    }
+}
+
+fn token_class(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Plain => None,
+        TokenKind::Punctuation => Some("text-gray-400 dark:text-neutral-500"),
+        TokenKind::Property | TokenKind::Key => Some("text-blue-700 dark:text-blue-400"),
+        TokenKind::Component => Some("text-purple-700 dark:text-purple-400"),
+        TokenKind::Parameter => Some("text-teal-700 dark:text-teal-400"),
+        TokenKind::ParameterValue | TokenKind::String => Some("text-green-700 dark:text-green-400"),
+        TokenKind::Number | TokenKind::Literal => Some("text-orange-700 dark:text-orange-400"),
+    }
+}
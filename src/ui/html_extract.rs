@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{html_extract::Extracted, i18n::FluentArgs};
+
+use super::i18n::I18n;
+
+// What was found in a pasted web page, shown only when the source is one.
+#[component]
+pub fn HtmlExtractNote(
+    #[prop(into)] extracted: Signal<Option<Result<Extracted, String>>>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        {move || {
+            extracted
+                .get()
+                .map(|extracted| {
+                    let summary = match extracted {
+                        Ok(extracted) => {
+                            let count = |id: &str, count: usize| {
+                                (count > 0).then(|| i18n.t_with(id, FluentArgs::from_iter([("count", count)])))
+                            };
+                            let found = [
+                                count("html-extract-schema-org", extracted.json_ld_events),
+                                count("html-extract-hcalendar", extracted.hcalendar_events),
+                                count("html-extract-hcards", extracted.hcards),
+                            ]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                            let mut summary = i18n.t_with("html-extract-found", FluentArgs::from_iter([("found", found)]));
+                            if extracted.json_ld_events + extracted.hcalendar_events > 0 && extracted.hcards > 0 {
+                                summary.push(' ');
+                                summary.push_str(&i18n.t("html-extract-events-only"));
+                            }
+                            if let Some(skipped) = count("html-extract-skipped", extracted.skipped) {
+                                summary.push(' ');
+                                summary.push_str(&skipped);
+                            }
+                            summary
+                        }
+                        Err(err) => err,
+                    };
+                    view! {
+                        <div class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                            <p class="font-semibold text-gray-800 dark:text-neutral-200">{i18n.t("html-extract-title")}</p>
+                            <p class="mt-1">{summary}</p>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use codee::string::FromToStringCodec;
+use leptos::*;
+use leptos_use::storage::use_local_storage;
+
+use jmap_convert::i18n::{Catalog, FluentArgs, Locale};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    // The first of the browser languages there is a translation for.
+    #[default]
+    Browser,
+    Locale(Locale),
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::Browser => f.write_str("browser"),
+            Language::Locale(locale) => locale.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "browser" => Ok(Language::Browser),
+            s => s.parse().map(Language::Locale),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct I18n {
+    pub language: Signal<Language>,
+    set_language: WriteSignal<Language>,
+    catalog: StoredValue<Catalog>,
+}
+
+impl I18n {
+    pub fn load() -> Self {
+        let (language, set_language, _) =
+            use_local_storage::<Language, FromToStringCodec>("jmap-convert.language");
+        I18n {
+            language,
+            set_language,
+            catalog: store_value(Catalog::new()),
+        }
+    }
+
+    pub fn locale(&self) -> Locale {
+        match self.language.get() {
+            Language::Browser => {
+                let languages = window()
+                    .navigator()
+                    .languages()
+                    .iter()
+                    .filter_map(|language| language.as_string())
+                    .collect::<Vec<_>>();
+                Locale::negotiate(languages.iter().map(String::as_str))
+            }
+            Language::Locale(locale) => locale,
+        }
+    }
+
+    // The message in the chosen language, tracking changes to it.
+    pub fn t(&self, id: &str) -> String {
+        let locale = self.locale();
+        self.catalog
+            .with_value(|catalog| catalog.message(locale, id, None))
+    }
+
+    pub fn t_with(&self, id: &str, args: FluentArgs) -> String {
+        let locale = self.locale();
+        self.catalog
+            .with_value(|catalog| catalog.message(locale, id, Some(&args)))
+    }
+}
+
+#[component]
+pub fn LanguageSelect() -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <select
+            class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-xs dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+            title=move || i18n.t("language-title")
+            on:change=move |ev| {
+                if let Ok(language) = event_target_value(&ev).parse() {
+                    i18n.set_language.set(language);
+                }
+            }
+        >
+            <option value="browser" selected=move || i18n.language.get() == Language::Browser>
+                {move || i18n.t("language-browser")}
+            </option>
+            {Locale::ALL
+                .iter()
+                .map(|locale| {
+                    let language = Language::Locale(*locale);
+                    view! {
+                        <option value=locale.as_str() selected=move || i18n.language.get() == language>
+                            {locale.label()}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
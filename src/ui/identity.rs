@@ -0,0 +1,94 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::identity::IdentityData;
+
+use super::i18n::I18n;
+
+#[component]
+pub fn IdentityMappingPanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] converted: Signal<String>,
+    #[prop(into)] roundtrip: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        {move || {
+            let columns = [
+                IdentityData::extract(&source.get()),
+                IdentityData::extract(&converted.get()),
+                IdentityData::extract(&roundtrip.get()),
+            ];
+            (!columns.iter().all(IdentityData::is_empty))
+                .then(|| {
+                    let rows: [(&str, fn(&IdentityData) -> String); 4] = [
+                        ("identity-gender", |data| data.gender.clone().unwrap_or_default()),
+                        (
+                            "identity-grammatical-gender",
+                            |data| data.grammatical_gender.clone().unwrap_or_default(),
+                        ),
+                        ("identity-pronouns", |data| data.pronouns.join(", ")),
+                        (
+                            "identity-vendor",
+                            |data| {
+                                data.vendor
+                                    .iter()
+                                    .map(|(property, value)| format!("{}: {}", property, value))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            },
+                        ),
+                    ];
+                    let rows = rows
+                        .into_iter()
+                        .map(|(label, value)| {
+                            let values = columns.iter().map(value).collect::<Vec<_>>();
+                            let lost = !values[0].is_empty()
+                                && values[1..].iter().any(|value| *value != values[0]);
+                            let cells = values
+                                .into_iter()
+                                .map(|value| {
+                                    view! {
+                                        <td class="px-3 py-2 text-gray-800 dark:text-neutral-200">
+                                            {if value.is_empty() { "—".to_string() } else { value }}
+                                        </td>
+                                    }
+                                })
+                                .collect_view();
+                            view! {
+                                <tr class=if lost { "bg-yellow-50 dark:bg-yellow-800/10" } else { "" }>
+                                    <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">{i18n.t(label)}</td>
+                                    {cells}
+                                </tr>
+                            }
+                        })
+                        .collect_view();
+
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                                {i18n.t("identity-title")}
+                            </h3>
+                            <table class="min-w-full text-sm divide-y divide-gray-200 dark:divide-neutral-700">
+                                <thead>
+                                    <tr class="text-xs text-start uppercase text-gray-500 dark:text-neutral-500">
+                                        <th class="px-3 py-2 text-start"></th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("identity-source")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("identity-converted")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("identity-roundtrip")}</th>
+                                    </tr>
+                                </thead>
+                                <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">{rows}</tbody>
+                            </table>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
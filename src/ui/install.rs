@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use leptos_use::use_event_listener;
+use wasm_bindgen::{JsCast, JsValue};
+
+use super::i18n::I18n;
+
+// Offers to install the page as an app, shown only once the browser has
+// said it can be installed.
+#[component]
+pub fn InstallButton() -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // The deferred beforeinstallprompt event, which web-sys has no type for.
+    let prompt: StoredValue<Option<JsValue>> = store_value(None);
+    let available = create_rw_signal(false);
+
+    let _ = use_event_listener(
+        window(),
+        ev::Custom::<web_sys::Event>::new("beforeinstallprompt"),
+        move |event| {
+            event.prevent_default();
+            prompt.set_value(Some(event.into()));
+            available.set(true);
+        },
+    );
+    let _ = use_event_listener(
+        window(),
+        ev::Custom::<web_sys::Event>::new("appinstalled"),
+        move |_| {
+            prompt.set_value(None);
+            available.set(false);
+        },
+    );
+
+    let install = move |_| {
+        // The event can only prompt once.
+        let Some(event) = prompt.get_value() else {
+            return;
+        };
+        prompt.set_value(None);
+        available.set(false);
+        if let Some(show) = js_sys::Reflect::get(&event, &"prompt".into())
+            .ok()
+            .and_then(|show| show.dyn_into::<js_sys::Function>().ok())
+        {
+            let _ = show.call0(&event);
+        }
+    };
+
+    view! {
+        <Show when=move || available.get()>
+            <button
+                type="button"
+                class="py-1 px-3 rounded-lg text-xs text-white bg-blue-600 hover:bg-blue-500"
+                title=move || i18n.t("header-install-title")
+                on:click=install
+            >
+                {move || i18n.t("header-install")}
+            </button>
+        </Show>
+    }
+}
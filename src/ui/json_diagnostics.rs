@@ -0,0 +1,127 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    json_diagnostics::{self, Diagnostic, Node, Problem},
+};
+
+use super::i18n::I18n;
+
+// Problems of a JSCalendar or JSContact source as a tree of the objects they
+// are in, each labelled with its JSON pointer.
+#[component]
+pub fn JsonDiagnosticsTree(#[prop(into)] diagnostics: Signal<Vec<Diagnostic>>) -> impl IntoView {
+    view! {
+        <div class="mt-2 text-xs">
+            {move || {
+                let root = diagnostics.with(|diagnostics| json_diagnostics::tree(diagnostics));
+                view! {
+                    {problem_list(root.problems)}
+                    {root.children.into_iter().map(node_view).collect_view()}
+                }
+            }}
+        </div>
+    }
+}
+
+// Problems found in a successful conversion, which are all ignored properties.
+#[component]
+pub fn IgnoredPropertiesPanel(#[prop(into)] diagnostics: Signal<Vec<Diagnostic>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    view! {
+        <Show when=move || diagnostics.with(|diagnostics| !diagnostics.is_empty())>
+            <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                    {move || {
+                        i18n.t_with(
+                            "json-ignored-count",
+                            FluentArgs::from_iter([("count", diagnostics.with(Vec::len))]),
+                        )
+                    }}
+                </summary>
+                <p class="mt-3">
+                    {move || i18n.t("json-ignored-description")}
+                </p>
+                <JsonDiagnosticsTree diagnostics=diagnostics/>
+            </details>
+        </Show>
+    }
+}
+
+fn node_view(node: Node) -> View {
+    let key = if node.key.parse::<usize>().is_ok() {
+        format!("[{}]", node.key)
+    } else {
+        node.key
+    };
+    // Leaves are shown flat, only objects with problems inside can expand.
+    if node.children.is_empty() {
+        return view! {
+            <div class="ms-4 mt-1">
+                <span class="font-mono" title=node.pointer>
+                    {key}
+                </span>
+                {problem_list(node.problems)}
+            </div>
+        }
+        .into_view();
+    }
+    view! {
+        <details class="ms-4 mt-1" open=true>
+            <summary class="cursor-pointer font-mono" title=node.pointer>
+                {key}
+            </summary>
+            {problem_list(node.problems)}
+            {node.children.into_iter().map(node_view).collect_view()}
+        </details>
+    }
+    .into_view()
+}
+
+fn problem_list(problems: Vec<Problem>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    (!problems.is_empty()).then(|| {
+        view! {
+            <ul class="ms-4">
+                {problems
+                    .into_iter()
+                    .map(|problem| {
+                        let class = if problem.is_error() {
+                            "text-red-700 dark:text-red-500"
+                        } else {
+                            "text-yellow-700 dark:text-yellow-500"
+                        };
+                        view! { <li class=class>{move || problem_message(i18n, &problem)}</li> }
+                    })
+                    .collect_view()}
+            </ul>
+        }
+    })
+}
+
+fn problem_message(i18n: I18n, problem: &Problem) -> String {
+    let mut args = FluentArgs::new();
+    match problem {
+        Problem::Syntax {
+            message,
+            line,
+            column,
+        } => {
+            args.set("line", *line);
+            args.set("column", *column);
+            args.set("message", message.clone());
+        }
+        Problem::WrongType { expected, found } => {
+            args.set("expected", expected.as_str());
+            args.set("found", found.as_str());
+        }
+        Problem::Unknown | Problem::Ignored => {}
+    }
+    i18n.t_with(problem.message_id(), args)
+}
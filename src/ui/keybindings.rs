@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::keybindings::BINDINGS;
+
+use super::i18n::I18n;
+
+// The keyboard shortcuts of the page, which main.rs dispatches.
+#[component]
+pub fn KeyboardShortcutsPanel() -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("shortcuts-title")}
+            </summary>
+            <p class="mt-2">
+                {move || i18n.t("shortcuts-help")}
+            </p>
+            <table class="mt-2">
+                <tbody>
+                    {BINDINGS
+                        .iter()
+                        .map(|binding| {
+                            view! {
+                                <tr>
+                                    <td class="pe-4 py-0.5">
+                                        <kbd class="px-1.5 py-0.5 rounded-sm font-mono text-xs bg-gray-100 border border-gray-200 dark:bg-neutral-700 dark:border-neutral-600">
+                                            {move || {
+                                                binding
+                                                    .label(
+                                                        &i18n.t("shortcut-key-ctrl"),
+                                                        &i18n.t("shortcut-key-shift"),
+                                                        &i18n.t("shortcut-key-enter"),
+                                                    )
+                                            }}
+                                        </kbd>
+                                    </td>
+                                    <td class="py-0.5">
+                                        {move || i18n.t(binding.action.message_id())}
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </details>
+    }
+}
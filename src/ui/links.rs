@@ -0,0 +1,163 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    links::{self, ContactLink, Placement},
+};
+
+use super::i18n::I18n;
+
+// Long data: URIs are cut when displayed.
+const MAX_URI_LENGTH: usize = 60;
+
+#[component]
+pub fn ContactLinksPanel(
+    #[prop(into)] vcard: Signal<String>,
+    #[prop(into)] jscontact: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        {move || {
+            let links = links::links(&jscontact.get());
+            let placements = links::placements(&vcard.get(), &links);
+            (!links.is_empty() || !placements.is_empty())
+                .then(|| {
+                    let rows = links
+                        .into_iter()
+                        .map(|link: ContactLink| {
+                            let shown = if link.uri.chars().count() > MAX_URI_LENGTH {
+                                format!(
+                                    "{}…",
+                                    link.uri.chars().take(MAX_URI_LENGTH).collect::<String>(),
+                                )
+                            } else {
+                                link.uri.clone()
+                            };
+                            let href = (link.problem.is_none() && !link.uri.starts_with("data:"))
+                                .then(|| link.uri.clone());
+                            view! {
+                                <tr class=if link.problem.is_some() { "bg-yellow-50 dark:bg-yellow-800/10" } else { "" }>
+                                    <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">
+                                        {link.collection}
+                                    </td>
+                                    <td class="px-3 py-2 text-gray-800 dark:text-neutral-200">
+                                        {link.kind.unwrap_or_else(|| "—".to_string())}
+                                        {link
+                                            .pref
+                                            .map(|pref| {
+                                                format!(
+                                                    " ({})",
+                                                    i18n.t_with("links-pref", FluentArgs::from_iter([("pref", pref)])),
+                                                )
+                                            })}
+                                    </td>
+                                    <td class="px-3 py-2 break-all text-gray-800 dark:text-neutral-200">
+                                        {match href {
+                                            Some(href) => {
+                                                view! {
+                                                    <a
+                                                        class="text-blue-600 hover:underline dark:text-blue-400"
+                                                        href=href
+                                                        target="_blank"
+                                                        rel="noopener noreferrer"
+                                                    >
+                                                        {shown}
+                                                    </a>
+                                                }
+                                                    .into_view()
+                                            }
+                                            None => shown.into_view(),
+                                        }}
+                                    </td>
+                                    <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">
+                                        {link.media_type.unwrap_or_default()}
+                                    </td>
+                                    <td class="px-3 py-2">
+                                        {match link.problem {
+                                            Some(problem) => {
+                                                view! { <span class="text-yellow-700 dark:text-yellow-500">{problem}</span> }
+                                            }
+                                            None => {
+                                                view! { <span class="text-green-700 dark:text-green-500">{i18n.t("links-ok")}</span> }
+                                            }
+                                        }}
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view();
+                    let placements = placements
+                        .into_iter()
+                        .map(|placement: Placement| {
+                            let (class, text) = match placement.found {
+                                _ if placement.is_correct() => {
+                                    (
+                                        "text-green-700 dark:text-green-500",
+                                        i18n.t_with("links-placed", FluentArgs::from_iter([("expected", placement.expected)])),
+                                    )
+                                }
+                                Some(found) => {
+                                    (
+                                        "text-yellow-700 dark:text-yellow-500",
+                                        {
+                                            let mut args = FluentArgs::new();
+                                            args.set("found", found);
+                                            args.set("expected", placement.expected);
+                                            i18n.t_with("links-misplaced", args)
+                                        },
+                                    )
+                                }
+                                None => {
+                                    (
+                                        "text-red-700 dark:text-red-500",
+                                        i18n.t_with("links-missing", FluentArgs::from_iter([("expected", placement.expected)])),
+                                    )
+                                }
+                            };
+                            view! {
+                                <li class="py-1 flex gap-3">
+                                    <span class="w-32 shrink-0 text-gray-800 dark:text-neutral-200">
+                                        {placement.property}
+                                    </span>
+                                    <span class="w-16 shrink-0 text-gray-500 dark:text-neutral-500">
+                                        {i18n.t_with("source-line", FluentArgs::from_iter([("line", placement.line)]))}
+                                    </span>
+                                    <span class=class>{text}</span>
+                                </li>
+                            }
+                        })
+                        .collect_view();
+
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                                {i18n.t("links-title")}
+                            </h3>
+                            <table class="min-w-full text-sm divide-y divide-gray-200 dark:divide-neutral-700">
+                                <thead>
+                                    <tr class="text-xs text-start uppercase text-gray-500 dark:text-neutral-500">
+                                        <th class="px-3 py-2 text-start">{i18n.t("links-collection")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("links-kind")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("links-uri")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("links-media-type")}</th>
+                                        <th class="px-3 py-2 text-start">{i18n.t("links-check")}</th>
+                                    </tr>
+                                </thead>
+                                <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">{rows}</tbody>
+                            </table>
+                            <ul class="mt-3 text-sm divide-y divide-gray-200 dark:divide-neutral-700">
+                                {placements}
+                            </ul>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    findings::Finding,
+    i18n::FluentArgs,
+    merge::{self, MergeOutput},
+};
+
+use crate::{input, ui::i18n::I18n};
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+#[derive(Clone)]
+struct Summary {
+    files: usize,
+    entries: usize,
+    timezones: usize,
+    findings: Vec<Finding>,
+}
+
+// Combines several .ics or .vcf files into one, opened in a new document.
+#[component]
+pub fn MergePanel(
+    // Name and text of the merged file.
+    #[prop(into)] on_merge: Callback<(String, String)>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let output = create_rw_signal(MergeOutput::default());
+    let summary: RwSignal<Option<Result<Summary, String>>> = create_rw_signal(None);
+
+    let merge_files = move |files: Vec<web_sys::File>| {
+        spawn_local(async move {
+            let mut sources = Vec::with_capacity(files.len());
+            for file in &files {
+                match input::read_file(file).await {
+                    Ok(text) => sources.push((file.name(), text)),
+                    Err(err) => {
+                        summary.set(Some(Err(err)));
+                        return;
+                    }
+                }
+            }
+            let merged = merge::merge(&sources, output.get_untracked()).map(|merged| {
+                on_merge.call((format!("merged.{}", merged.format.extension()), merged.text));
+                Summary {
+                    files: sources.len(),
+                    entries: merged.entries,
+                    timezones: merged.timezones,
+                    findings: merged.findings,
+                }
+            });
+            summary.set(Some(merged));
+        });
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("merge-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("merge-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <select
+                    class=INPUT_CLASS
+                    title=move || i18n.t("merge-output")
+                    on:change=move |ev| {
+                        if let Ok(selected) = event_target_value(&ev).parse() {
+                            output.set(selected);
+                        }
+                    }
+                >
+                    {MergeOutput::ALL
+                        .iter()
+                        .map(|item| {
+                            view! {
+                                <option value=item.as_str() selected=move || output.get() == *item>
+                                    {move || i18n.t(item.message_id())}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <label class=format!("cursor-pointer {}", BUTTON_CLASS)>
+                    <input
+                        type="file"
+                        class="sr-only"
+                        multiple
+                        accept=".ics,.vcf,.vcard,text/calendar,text/vcard"
+                        on:change=move |ev| {
+                            let input = event_target::<web_sys::HtmlInputElement>(&ev);
+                            if let Some(list) = input.files() {
+                                merge_files((0..list.length()).filter_map(|index| list.get(index)).collect());
+                            }
+                            input.set_value("");
+                        }
+                    />
+                    {move || i18n.t("merge-choose")}
+                </label>
+            </div>
+            {move || {
+                summary
+                    .get()
+                    .map(|summary| match summary {
+                        Err(err) => view! { <p class="mt-2 text-red-700 dark:text-red-500">{err}</p> }.into_view(),
+                        Ok(summary) => {
+                            view! {
+                                <p class="mt-2 text-gray-800 dark:text-neutral-200">
+                                    {
+                                        let mut args = FluentArgs::new();
+                                        args.set("files", summary.files);
+                                        args.set("entries", summary.entries);
+                                        args.set("timezones", summary.timezones);
+                                        i18n.t_with("merge-summary", args)
+                                    }
+                                </p>
+                                <ul class="mt-1 list-disc ps-5">
+                                    {summary
+                                        .findings
+                                        .into_iter()
+                                        .map(|finding| {
+                                            view! {
+                                                <li>
+                                                    <span class="font-mono text-xs">{finding.code.as_str()}</span>
+                                                    " "
+                                                    {finding.message}
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_view()
+                        }
+                    })
+            }}
+        </details>
+    }
+}
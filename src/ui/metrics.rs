@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    metrics::{self, Benchmark, PhaseTimings},
+};
+
+use crate::{next_tick, now_ms, ui::i18n::I18n};
+
+// Parse, conversion, roundtrip and expansion times of the last conversion
+// and the sizes involved, with a micro-benchmark for spotting parser and
+// conversion slowdowns between calcard versions.
+#[component]
+pub fn MetricsFooter(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] converted: Signal<String>,
+    #[prop(into)] roundtrip: Signal<String>,
+    #[prop(into)] expansion_ms: Signal<Option<f64>>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // Measured again whenever a conversion finishes.
+    let timings = create_memo(move |_| {
+        converted.track();
+        source.with_untracked(|source| {
+            (!source.trim().is_empty() && source.len() <= metrics::MAX_SOURCE_LEN)
+                .then(|| metrics::measure(source, now_ms))
+        })
+    });
+    let benchmark: RwSignal<Option<Result<Benchmark, String>>> = create_rw_signal(None);
+    let running = create_rw_signal(false);
+    // Bumped when the source changes so a running benchmark stops.
+    let generation = store_value(0u64);
+    create_effect(move |_| {
+        source.track();
+        generation.update_value(|generation| *generation += 1);
+        running.set(false);
+        benchmark.set(None);
+    });
+
+    let run = move |_| {
+        let text = source.get_untracked();
+        let started = generation.get_value();
+        running.set(true);
+        spawn_local(async move {
+            let mut result = Benchmark::default();
+            for _ in 0..metrics::BENCHMARK_ITERATIONS {
+                // Lets the page update the progress between runs.
+                next_tick().await;
+                if generation.get_value() != started {
+                    return;
+                }
+                match metrics::measure(&text, now_ms) {
+                    Ok(timings) => result.add(timings),
+                    Err(err) => {
+                        benchmark.set(Some(Err(err)));
+                        running.set(false);
+                        return;
+                    }
+                }
+                benchmark.set(Some(Ok(result)));
+            }
+            running.set(false);
+        });
+    };
+
+    let phases = move |timings: PhaseTimings| {
+        let mut args = FluentArgs::new();
+        args.set("parse", format_ms(timings.parse_ms));
+        args.set("conversion", format_ms(timings.conversion_ms));
+        args.set("roundtrip", format_ms(timings.roundtrip_ms));
+        i18n.t_with("metrics-phases", args)
+    };
+
+    view! {
+        <div class="mt-4 flex flex-col gap-1 text-xs text-gray-600 dark:text-neutral-400">
+            <div class="flex flex-wrap items-center gap-x-4 gap-y-1">
+                {move || match timings.get() {
+                    Some(Ok(timings)) => phases(timings).into_view(),
+                    Some(Err(err)) => view! { <span class="text-red-600 dark:text-red-500">{err}</span> }.into_view(),
+                    None => i18n.t("metrics-not-measured").into_view(),
+                }}
+                {move || {
+                    expansion_ms
+                        .get()
+                        .map(|elapsed| {
+                            i18n.t_with("metrics-expansion", FluentArgs::from_iter([("elapsed", format_ms(elapsed))]))
+                        })
+                }}
+                <span>
+                    {move || {
+                        let mut args = FluentArgs::new();
+                        args.set("input", metrics::format_bytes(source.with(String::len)));
+                        args.set("output", metrics::format_bytes(converted.with(String::len)));
+                        args.set("roundtrip", metrics::format_bytes(roundtrip.with(String::len)));
+                        i18n.t_with("metrics-sizes", args)
+                    }}
+                </span>
+                <button
+                    type="button"
+                    class="py-0.5 px-2 rounded-lg border border-gray-200 hover:bg-gray-100 disabled:opacity-50 dark:border-neutral-700 dark:hover:bg-neutral-700"
+                    disabled=move || running.get() || source.with(|source| source.trim().is_empty())
+                    on:click=run
+                >
+                    {move || {
+                        i18n.t_with(
+                            "metrics-run",
+                            FluentArgs::from_iter([("count", metrics::BENCHMARK_ITERATIONS)]),
+                        )
+                    }}
+                </button>
+            </div>
+            {move || {
+                benchmark
+                    .get()
+                    .map(|benchmark| match benchmark {
+                        Ok(benchmark) => {
+                            let total = |timings: Option<PhaseTimings>| {
+                                timings.map(|timings| format_ms(timings.total_ms())).unwrap_or_default()
+                            };
+                            view! {
+                                <p>
+                                    {
+                                        let mut args = FluentArgs::new();
+                                        args.set("done", benchmark.iterations);
+                                        args.set("count", metrics::BENCHMARK_ITERATIONS);
+                                        args.set("mean", phases(benchmark.mean()));
+                                        args.set("fastest", total(benchmark.fastest));
+                                        args.set("slowest", total(benchmark.slowest));
+                                        i18n.t_with("metrics-benchmark", args)
+                                    }
+                                </p>
+                            }
+                                .into_view()
+                        }
+                        Err(err) => view! { <p class="text-red-600 dark:text-red-500">{err}</p> }.into_view(),
+                    })
+            }}
+        </div>
+    }
+}
+
+fn format_ms(elapsed: f64) -> String {
+    if elapsed < 10.0 {
+        format!("{:.2} ms", elapsed)
+    } else {
+        format!("{:.0} ms", elapsed)
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+pub mod alarms;
+pub mod anonymize;
+pub mod bug_report;
+pub mod bundle;
+pub mod colors;
+pub mod compare;
+pub mod compatibility;
+pub mod contact_cleanup;
+pub mod csv_import;
+pub mod dedup;
+pub mod description;
+pub mod diff;
+pub mod documents;
+pub mod email;
+pub mod entries;
+pub mod event_preview;
+pub mod expected;
+pub mod export;
+pub mod fidelity;
+pub mod filter;
+pub mod findings;
+pub mod freebusy;
+pub mod heatmap;
+pub mod highlight;
+pub mod html_extract;
+pub mod i18n;
+pub mod identity;
+pub mod install;
+pub mod json_diagnostics;
+pub mod keybindings;
+pub mod links;
+pub mod merge;
+pub mod metrics;
+pub mod normalize;
+pub mod orgchart;
+pub mod pager;
+pub mod patches;
+pub mod permalink;
+pub mod phonetic;
+pub mod preview;
+pub mod probe;
+pub mod properties;
+pub mod qr;
+pub mod query;
+pub mod recent;
+pub mod recovery;
+pub mod remote;
+pub mod repair;
+pub mod report;
+pub mod samples;
+pub mod sanitize;
+pub mod scheduling;
+pub mod selftest;
+pub mod semantic;
+pub mod settings;
+pub mod shifts;
+pub mod stats;
+pub mod status;
+pub mod theme;
+pub mod timezone;
+pub mod toast;
+pub mod tour;
+pub mod usage;
+pub mod validation;
+pub mod vcard_version;
+pub mod vtimezone;
+pub mod working_hours;
+pub mod workspace;
@@ -0,0 +1,65 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::normalize;
+
+use super::i18n::I18n;
+
+// Rewrites the source in canonical form, for diffs and version control.
+#[component]
+pub fn NormalizePanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] on_normalize: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // Whether the last run changed anything, or why it failed.
+    let outcome: RwSignal<Option<Result<bool, String>>> = create_rw_signal(None);
+
+    let run = move |_| {
+        let current = source.get_untracked();
+        outcome.set(Some(normalize::normalize(&current).map(|normalized| {
+            let changed = normalized != current;
+            if changed {
+                on_normalize.call(normalized);
+            }
+            changed
+        })));
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("normalize-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("normalize-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-3">
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-white bg-blue-600 hover:bg-blue-500 disabled:opacity-50"
+                    disabled=move || source.with(|source| source.trim().is_empty())
+                    on:click=run
+                >
+                    {move || i18n.t("normalize-run")}
+                </button>
+                {move || {
+                    outcome
+                        .get()
+                        .map(|outcome| match outcome {
+                            Ok(true) => view! { <span>{move || i18n.t("normalize-changed")}</span> }.into_view(),
+                            Ok(false) => view! { <span>{move || i18n.t("normalize-unchanged")}</span> }.into_view(),
+                            Err(err) => {
+                                view! { <span class="text-red-600 dark:text-red-500">{err}</span> }.into_view()
+                            }
+                        })
+                }}
+            </div>
+        </details>
+    }
+}
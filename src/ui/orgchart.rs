@@ -0,0 +1,115 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    orgchart::{self, OrgUnit},
+};
+
+use super::i18n::I18n;
+
+#[component]
+pub fn OrgChartPanel(#[prop(into)] source: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let chart = create_memo(move |_| {
+        let cards = orgchart::cards_from_source(&source.get());
+        let has_org_data = cards.iter().any(|card| {
+            ["organizations", "titles", "relatedTo"]
+                .iter()
+                .any(|property| card.get(property).is_some())
+        });
+        if cards.len() > 1 && has_org_data {
+            orgchart::build(&cards)
+        } else {
+            vec![]
+        }
+    });
+
+    view! {
+        <Show when=move || !chart.with(Vec::is_empty)>
+            <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-10">
+                <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                    <div class="mb-4 flex flex-wrap items-center justify-between gap-2">
+                        <div>
+                            <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                                {move || i18n.t("orgchart-title")}
+                            </h2>
+                            <p class="text-sm text-gray-600 dark:text-neutral-400">
+                                {move || {
+                                    let count = chart.with(|units| orgchart::people_count(units));
+                                    i18n.t_with("orgchart-description", FluentArgs::from_iter([("count", count)]))
+                                }}
+                            </p>
+                        </div>
+                        <a
+                            class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                            download="org-chart.json"
+                            href=move || {
+                                format!(
+                                    "data:application/json;charset=utf-8,{}",
+                                    String::from(
+                                        js_sys::encode_uri_component(&chart.with(|units| orgchart::to_json(units))),
+                                    ),
+                                )
+                            }
+                        >
+                            {move || i18n.t("orgchart-export")}
+                        </a>
+                    </div>
+                    <ul class="text-sm">
+                        {move || chart.get().into_iter().map(|unit| view! { <OrgUnitView unit=unit/> }).collect_view()}
+                    </ul>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn OrgUnitView(unit: OrgUnit) -> impl IntoView {
+    view! {
+        <li class="mt-2">
+            <p class="font-semibold text-gray-800 dark:text-neutral-200">{unit.name}</p>
+            <ul class="ms-4 border-s border-gray-200 ps-3 dark:border-neutral-700">
+                {unit
+                    .people
+                    .into_iter()
+                    .map(|person| {
+                        let related = person
+                            .related
+                            .iter()
+                            .map(|related| format!("{}: {}", related.relation, related.name))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        view! {
+                            <li class="mt-1 text-gray-800 dark:text-neutral-200">
+                                {person.name}
+                                {person
+                                    .title
+                                    .map(|title| {
+                                        view! {
+                                            <span class="ms-2 text-gray-500 dark:text-neutral-400">{title}</span>
+                                        }
+                                    })}
+                                {(!related.is_empty())
+                                    .then(|| {
+                                        view! {
+                                            <span class="ms-2 text-xs text-gray-500 dark:text-neutral-500">
+                                                {format!("({})", related)}
+                                            </span>
+                                        }
+                                    })}
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+                {unit.units.into_iter().map(|unit| view! { <OrgUnitView unit=unit/> }).collect_view()}
+            </ul>
+        </li>
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::i18n::FluentArgs;
+
+use super::i18n::I18n;
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 disabled:opacity-40 disabled:pointer-events-none dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+// Range of rows shown and buttons to move between pages of `page_size` rows.
+// `page` counts from 0 and is clamped to the last page.
+#[component]
+pub fn Pager(
+    page: RwSignal<usize>,
+    #[prop(into)] total: Signal<usize>,
+    page_size: usize,
+    // Message id of what the rows are, such as "pager-occurrences".
+    #[prop(default = "pager-rows")] noun: &'static str,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let pages = move || total.get().div_ceil(page_size).max(1);
+    let current = move || page.get().min(pages() - 1);
+
+    view! {
+        <div class="my-2 flex flex-wrap items-center justify-between gap-2 text-sm text-gray-600 dark:text-neutral-400">
+            <span>
+                {move || {
+                    let total = total.get();
+                    let noun = i18n.t_with(noun, FluentArgs::from_iter([("count", total)]));
+                    if total <= page_size {
+                        let mut args = FluentArgs::new();
+                        args.set("count", total);
+                        args.set("noun", noun);
+                        i18n.t_with("pager-count", args)
+                    } else {
+                        let start = current() * page_size;
+                        let mut args = FluentArgs::new();
+                        args.set("start", start + 1);
+                        args.set("end", (start + page_size).min(total));
+                        args.set("total", total);
+                        args.set("noun", noun);
+                        i18n.t_with("pager-range", args)
+                    }
+                }}
+            </span>
+            <Show when=move || { pages() > 1 }>
+                <div class="flex items-center gap-2">
+                    <button
+                        type="button"
+                        class=BUTTON_CLASS
+                        disabled=move || current() == 0
+                        on:click=move |_| page.set(current().saturating_sub(1))
+                    >
+                        {move || i18n.t("pager-previous")}
+                    </button>
+                    <span class="text-xs">{move || {
+                            let mut args = FluentArgs::new();
+                            args.set("page", current() + 1);
+                            args.set("pages", pages());
+                            i18n.t_with("pager-page", args)
+                        }}</span>
+                    <button
+                        type="button"
+                        class=BUTTON_CLASS
+                        disabled=move || current() + 1 >= pages()
+                        on:click=move |_| page.set(current() + 1)
+                    >
+                        {move || i18n.t("pager-next")}
+                    </button>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{export::ExportContext, patches, Format};
+
+use crate::ui::{export::CopyButton, i18n::I18n};
+
+const TEXTAREA_CLASS: &str = "p-3 block w-full bg-gray-100 border-gray-200 rounded-lg text-sm font-mono dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-400";
+const BUTTON_CLASS: &str = "mt-1 py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+// The PatchObject of a JMAP /set update between two versions of a JSCalendar
+// or JSContact object.
+#[component]
+pub fn PatchDiffPanel(
+    // JSCalendar or JSContact of the current document, offered as either
+    // version.
+    #[prop(into)] current: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let before = create_rw_signal(String::new());
+    let after = create_rw_signal(String::new());
+    let patch = create_memo(move |_| {
+        let (before, after) = (before.get(), after.get());
+        if before.trim().is_empty() || after.trim().is_empty() {
+            return None;
+        }
+        Some(patches::diff_objects(&before, &after))
+    });
+    let output = Signal::derive(move || patch.get().and_then(Result::ok).unwrap_or_default());
+
+    let pane = move |message_id: &'static str, version: RwSignal<String>| {
+        view! {
+            <div class="grow basis-0 min-w-0">
+                <textarea
+                    class=TEXTAREA_CLASS
+                    rows="8"
+                    placeholder=move || i18n.t(message_id)
+                    prop:value=move || version.get()
+                    on:input=move |ev| version.set(event_target_value(&ev))
+                ></textarea>
+                <Show when=move || !current.with(String::is_empty)>
+                    <button
+                        type="button"
+                        class=BUTTON_CLASS
+                        on:click=move |_| version.set(current.get_untracked())
+                    >
+                        {move || i18n.t("patches-use-current")}
+                    </button>
+                </Show>
+            </div>
+        }
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("patches-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("patches-description")}
+            </p>
+            <div class="mt-2 flex flex-col sm:flex-row gap-3">
+                {pane("patches-before", before)}
+                {pane("patches-after", after)}
+            </div>
+            {move || {
+                patch
+                    .get()
+                    .map(|patch| match patch {
+                        Ok(_) => {
+                            view! {
+                                <div class="relative mt-3 bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                                    <div class="absolute top-2 end-2 flex gap-1">
+                                        <CopyButton
+                                            payload=output
+                                            context=Signal::derive(move || ExportContext {
+                                                format: "PatchObject".to_string(),
+                                                source_type: before
+                                                    .with(|before| jmap_convert::Converter.detect_format(before))
+                                                    .unwrap_or(Format::JSCalendar)
+                                                    .as_str()
+                                                    .to_string(),
+                                                version: env!("CARGO_PKG_VERSION").to_string(),
+                                                options: vec![],
+                                                timestamp: String::new(),
+                                            })
+                                            json=true
+                                        />
+                                    </div>
+                                    <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                                        {move || output.get()}
+                                    </pre>
+                                </div>
+                            }
+                                .into_view()
+                        }
+                        Err(err) => {
+                            view! { <p class="mt-3 text-red-600 dark:text-red-500">{err}</p> }.into_view()
+                        }
+                    })
+            }}
+        </details>
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+use jmap_convert::i18n::FluentArgs;
+
+use crate::ui::{i18n::I18n, toast::Toasts};
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+// Links longer than this are cut short by some issue trackers and chat tools.
+const MAX_SHARED_URL_LEN: usize = 8000;
+
+#[component]
+pub fn PermalinkPanel(
+    // Fragment holding the session as it is now.
+    #[prop(into)] fragment: Callback<(), String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let toasts = expect_context::<Toasts>();
+    let status = create_rw_signal(String::new());
+
+    let share = move |_| {
+        let window = window();
+        let location = window.location();
+        let url = format!(
+            "{}{}#{}",
+            location.origin().unwrap_or_default(),
+            location.pathname().unwrap_or_default(),
+            fragment.call(())
+        );
+        if let Ok(history) = window.history() {
+            _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+        }
+        status.set(if url.len() > MAX_SHARED_URL_LEN {
+            i18n.t_with(
+                "permalink-too-long",
+                FluentArgs::from_iter([("length", url.len())]),
+            )
+        } else {
+            String::new()
+        });
+        spawn_local(async move {
+            match JsFuture::from(window.navigator().clipboard().write_text(&url)).await {
+                Ok(_) => toasts.show(i18n.t("permalink-copied"), false),
+                Err(err) => {
+                    log::warn!("Failed to copy to the clipboard: {:?}", err);
+                    toasts.show(i18n.t("permalink-copy-failed"), true);
+                }
+            }
+        });
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("permalink-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("permalink-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <button type="button" class=BUTTON_CLASS on:click=share>
+                    {move || i18n.t("permalink-copy")}
+                </button>
+            </div>
+            <Show when=move || !status.with(String::is_empty)>
+                <p class="mt-2 text-yellow-700 dark:text-yellow-500">{move || status.get()}</p>
+            </Show>
+        </details>
+    }
+}
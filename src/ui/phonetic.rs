@@ -0,0 +1,96 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::phonetic::PhoneticData;
+
+use super::i18n::I18n;
+
+#[component]
+pub fn PhoneticPanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] converted: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let section = move |title: &'static str, data: PhoneticData| {
+        let readings = data
+            .readings
+            .into_iter()
+            .map(|reading| {
+                let system = [reading.system, reading.script]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                view! {
+                    <tr>
+                        <td class="px-3 py-2 font-mono text-xs text-gray-500 dark:text-neutral-500">
+                            {reading.property}
+                        </td>
+                        <td class="px-3 py-2 text-gray-800 dark:text-neutral-200">
+                            {reading.written.unwrap_or_default()}
+                        </td>
+                        <td class="px-3 py-2 text-gray-800 dark:text-neutral-200">{reading.reading}</td>
+                        <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">{system}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        let sounds = data
+            .sounds
+            .into_iter()
+            .map(|sound| {
+                view! {
+                    <li class="flex flex-wrap items-center gap-3 py-1">
+                        <span class="font-mono text-xs text-gray-500 dark:text-neutral-500">
+                            {sound.property}
+                        </span>
+                        {match sound.playable_uri {
+                            Some(uri) => view! { <audio controls preload="none" src=uri></audio> }.into_view(),
+                            None => view! {
+                                <span class="text-xs text-gray-800 break-all dark:text-neutral-200">
+                                    {sound.value}
+                                    {format!(" ({})", i18n.t("phonetic-not-playable"))}
+                                </span>
+                            }
+                                .into_view(),
+                        }}
+                    </li>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <h4 class="mt-3 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500">
+                {i18n.t(title)}
+            </h4>
+            <table class="min-w-full text-sm">
+                <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">{readings}</tbody>
+            </table>
+            <ul>{sounds}</ul>
+        }
+    };
+
+    view! {
+        {move || {
+            let source = PhoneticData::extract(&source.get());
+            let converted = PhoneticData::extract(&converted.get());
+            (!source.is_empty() || !converted.is_empty())
+                .then(|| {
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                                {i18n.t("phonetic-title")}
+                            </h3>
+                            {section("workspace-source", source)}
+                            {section("workspace-converted", converted)}
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,345 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    preview::{
+        AttendeeStatus, ContactPreview, EventLocation, EventPreview, LabeledValue, MapLinks,
+        Mapping,
+    },
+};
+
+use super::i18n::I18n;
+
+const AVATAR_COLORS: [&str; 6] = [
+    "bg-blue-600",
+    "bg-teal-600",
+    "bg-purple-600",
+    "bg-orange-600",
+    "bg-pink-600",
+    "bg-green-600",
+];
+
+#[component]
+pub fn ContactPreviewCard(#[prop(into)] jscontact: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        {move || {
+            ContactPreview::from_jscontact(&jscontact.get())
+                .map(|contact| {
+                    let color = AVATAR_COLORS[contact
+                        .name
+                        .chars()
+                        .map(|ch| ch as usize)
+                        .sum::<usize>() % AVATAR_COLORS.len()];
+                    let avatar = match contact.photo {
+                        Some(uri) => view! {
+                            <img class="size-16 rounded-full object-cover" src=uri alt=""/>
+                        }
+                            .into_view(),
+                        None => view! {
+                            <span class=format!(
+                                "inline-flex items-center justify-center size-16 rounded-full text-xl font-semibold text-white {}",
+                                color,
+                            )>{contact.initials}</span>
+                        }
+                            .into_view(),
+                    };
+                    let rows = |items: Vec<LabeledValue>, scheme: &'static str| {
+                        items
+                            .into_iter()
+                            .map(|item| {
+                                view! {
+                                    <li class="py-2">
+                                        <p class="text-xs text-gray-500 dark:text-neutral-500">{item.label}</p>
+                                        <a
+                                            class="text-sm text-blue-600 hover:underline dark:text-blue-400"
+                                            href=format!("{}{}", scheme, item.value)
+                                        >
+                                            {item.value}
+                                        </a>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    };
+
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                                {move || i18n.t("preview-contact")}
+                            </h3>
+                            <div class="max-w-sm border border-gray-200 rounded-xl p-5 dark:border-neutral-700">
+                                <div class="flex flex-col items-center text-center">
+                                    {avatar}
+                                    <p class="mt-3 text-lg font-semibold text-gray-800 dark:text-neutral-200">
+                                        {contact.name}
+                                    </p>
+                                    {contact
+                                        .job
+                                        .map(|job| {
+                                            view! {
+                                                <p class="text-sm text-gray-500 dark:text-neutral-400">{job}</p>
+                                            }
+                                        })}
+                                </div>
+                                {(!contact.name_components.is_empty())
+                                    .then(|| {
+                                        view! {
+                                            <dl class="mt-4 grid grid-cols-2 gap-x-3 gap-y-1 text-sm">
+                                                {contact
+                                                    .name_components
+                                                    .into_iter()
+                                                    .map(|component| {
+                                                        view! {
+                                                            <dt class="text-xs text-gray-500 dark:text-neutral-500">
+                                                                {component.label}
+                                                            </dt>
+                                                            <dd class="text-gray-800 dark:text-neutral-200">
+                                                                {component.value}
+                                                            </dd>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </dl>
+                                        }
+                                    })}
+                                <ul class="mt-4 divide-y divide-gray-200 dark:divide-neutral-700">
+                                    {rows(contact.phones, "tel:")}
+                                    {rows(contact.emails, "mailto:")}
+                                    {contact
+                                        .addresses
+                                        .into_iter()
+                                        .map(|address| {
+                                            view! {
+                                                <li class="py-2">
+                                                    <p class="text-xs text-gray-500 dark:text-neutral-500">
+                                                        {address.label}
+                                                    </p>
+                                                    <address class="text-sm not-italic text-gray-800 dark:text-neutral-200">
+                                                        {address
+                                                            .lines
+                                                            .into_iter()
+                                                            .map(|line| view! { <span class="block">{line}</span> })
+                                                            .collect_view()}
+                                                    </address>
+                                                    {address
+                                                        .coordinates
+                                                        .map(|coordinates| {
+                                                            view! {
+                                                                <p class="text-xs text-gray-500 dark:text-neutral-500">
+                                                                    {coordinates}
+                                                                </p>
+                                                            }
+                                                        })}
+                                                    <MapLinksView maps=address.maps/>
+                                                    <MappingView mapping=address.mapping/>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+#[component]
+pub fn InvitationPreview(#[prop(into)] jscalendar: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let viewer_tz = viewer_timezone();
+
+    view! {
+        {move || {
+            EventPreview::from_jscalendar(&jscalendar.get(), viewer_tz)
+                .map(|event| {
+                    let attendees = event
+                        .attendees
+                        .into_iter()
+                        .map(|attendee| {
+                            let (icon, class) = match attendee.status {
+                                AttendeeStatus::Accepted => ("✓", "text-green-600 dark:text-green-500"),
+                                AttendeeStatus::Declined => ("✕", "text-red-600 dark:text-red-500"),
+                                AttendeeStatus::Tentative => ("?", "text-yellow-600 dark:text-yellow-500"),
+                                AttendeeStatus::Pending => ("…", "text-gray-400 dark:text-neutral-500"),
+                            };
+                            view! {
+                                <span class="inline-flex items-center gap-x-1.5 py-1 px-2.5 rounded-full text-xs bg-gray-100 text-gray-800 dark:bg-neutral-700 dark:text-neutral-200">
+                                    <span class=class>{icon}</span>
+                                    {attendee.name}
+                                    {attendee
+                                        .organizer
+                                        .then(|| {
+                                            view! {
+                                                <span class="text-gray-500 dark:text-neutral-400">
+                                                    {move || i18n.t("preview-organizer")}
+                                                </span>
+                                            }
+                                        })}
+                                </span>
+                            }
+                        })
+                        .collect_view();
+
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200 mb-2">
+                                {move || i18n.t("preview-invitation")}
+                            </h3>
+                            <div class="max-w-lg border border-gray-200 rounded-xl p-5 dark:border-neutral-700">
+                                <p class="text-lg font-semibold text-gray-800 dark:text-neutral-200">
+                                    {event.title}
+                                </p>
+                                <p class="mt-1 text-sm text-gray-800 dark:text-neutral-200">{event.when}</p>
+                                {event
+                                    .event_zone_time
+                                    .map(|time| {
+                                        view! {
+                                            <p class="text-xs text-gray-500 dark:text-neutral-500">{time}</p>
+                                        }
+                                    })}
+                                {event
+                                    .recurrence
+                                    .map(|recurrence| {
+                                        view! {
+                                            <p class="text-sm text-gray-600 dark:text-neutral-400">{recurrence}</p>
+                                        }
+                                    })}
+                                {event
+                                    .location
+                                    .map(|location| {
+                                        view! {
+                                            <div class="mt-3">
+                                                <LocationView location=location/>
+                                            </div>
+                                        }
+                                    })}
+                                {event
+                                    .location_alerts
+                                    .into_iter()
+                                    .map(|alert| {
+                                        view! {
+                                            <p class="text-xs text-gray-500 dark:text-neutral-500">{alert}</p>
+                                        }
+                                    })
+                                    .collect_view()}
+                                {event
+                                    .join_url
+                                    .map(|url| {
+                                        view! {
+                                            <a
+                                                class="mt-3 inline-flex py-1.5 px-3 rounded-lg text-sm text-white bg-blue-600 hover:bg-blue-500"
+                                                href=url
+                                                target="_blank"
+                                            >
+                                                {move || i18n.t("preview-join")}
+                                            </a>
+                                        }
+                                    })}
+                                <div class="mt-4 flex flex-wrap gap-2">{attendees}</div>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+// A place with its address block, coordinates and map links, and the
+// JSCalendar location it was converted to.
+#[component]
+pub fn LocationView(location: EventLocation) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <div class="text-sm text-gray-800 dark:text-neutral-200">
+            <p>
+                {location.name}
+                {location
+                    .radius
+                    .map(|radius| {
+                        view! {
+                            <span class="ms-2 text-xs text-gray-500 dark:text-neutral-500">
+                                {move || {
+                                    i18n.t_with("preview-radius", FluentArgs::from_iter([("radius", radius)]))
+                                }}
+                            </span>
+                        }
+                    })}
+            </p>
+            {(!location.address.is_empty())
+                .then(|| {
+                    view! {
+                        <address class="not-italic text-gray-600 dark:text-neutral-400">
+                            {location
+                                .address
+                                .into_iter()
+                                .map(|line| view! { <span class="block">{line}</span> })
+                                .collect_view()}
+                        </address>
+                    }
+                })}
+            {location
+                .coordinates
+                .map(|coordinates| {
+                    view! { <p class="text-xs text-gray-500 dark:text-neutral-500">{coordinates}</p> }
+                })}
+            <MapLinksView maps=location.maps/>
+            <MappingView mapping=location.mapping/>
+        </div>
+    }
+}
+
+#[component]
+pub fn MapLinksView(maps: MapLinks) -> impl IntoView {
+    view! {
+        <p class="flex gap-3 text-xs">
+            <a
+                class="text-blue-600 hover:underline dark:text-blue-400"
+                href=maps.openstreetmap
+                target="_blank"
+            >
+                "OpenStreetMap"
+            </a>
+            <a
+                class="text-blue-600 hover:underline dark:text-blue-400"
+                href=maps.google
+                target="_blank"
+            >
+                "Google Maps"
+            </a>
+        </p>
+    }
+}
+
+// The converted JSON entry, collapsed under its path.
+#[component]
+pub fn MappingView(mapping: Mapping) -> impl IntoView {
+    view! {
+        <details class="mt-1 text-xs text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-mono">{mapping.path}</summary>
+            <pre class="mt-1 p-2 overflow-x-auto rounded-lg bg-gray-100 text-start dark:bg-neutral-700">
+                {mapping.json}
+            </pre>
+        </details>
+    }
+}
+
+pub fn viewer_timezone() -> Option<chrono_tz::Tz> {
+    let options = js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &js_sys::Object::new())
+        .resolved_options();
+    js_sys::Reflect::get(&options, &"timeZone".into())
+        .ok()?
+        .as_string()?
+        .parse()
+        .ok()
+}
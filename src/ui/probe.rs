@@ -0,0 +1,157 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::{Entry, Parser};
+use chrono::{NaiveDateTime, TimeZone};
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    probe::{self, ProbeResult, ProbeStatus},
+};
+
+use crate::ui::{i18n::I18n, preview::viewer_timezone};
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+const TIME_FORMAT: &str = "%a %b %-d, %Y %-I:%M%P %Z";
+
+#[component]
+pub fn TimeProbePanel(#[prop(into)] icalendar: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let at = create_rw_signal(String::new());
+    let timezone = create_rw_signal(
+        viewer_timezone()
+            .map(|tz| tz.name().to_string())
+            .unwrap_or_else(|| "UTC".to_string()),
+    );
+
+    let results = move || -> Result<Vec<ProbeResult>, String> {
+        let at = at.get();
+        let naive = NaiveDateTime::parse_from_str(&at, "%Y-%m-%dT%H:%M")
+            .or_else(|_| NaiveDateTime::parse_from_str(&at, "%Y-%m-%dT%H:%M:%S"))
+            .map_err(|_| i18n.t("probe-enter-date"))?;
+        let tz = timezone
+            .get()
+            .trim()
+            .parse::<chrono_tz::Tz>()
+            .map_err(|_| {
+                i18n.t_with(
+                    "probe-unknown-timezone",
+                    FluentArgs::from_iter([("timezone", timezone.get())]),
+                )
+            })?;
+        let instant = tz.from_local_datetime(&naive).earliest().ok_or_else(|| {
+            let mut args = FluentArgs::new();
+            args.set("at", at.clone());
+            args.set("timezone", tz.name());
+            i18n.t_with("probe-nonexistent", args)
+        })?;
+        match Parser::new(&icalendar.get()).entry() {
+            Entry::ICalendar(icalendar) => Ok(probe::probe(&icalendar, instant)),
+            _ => Err(i18n.t("probe-unparsable")),
+        }
+    };
+
+    view! {
+        <div class="mb-6">
+            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("probe-title")}
+            </h3>
+            <div class="mt-2 flex flex-wrap items-center gap-2 text-sm">
+                <input
+                    type="datetime-local"
+                    class=INPUT_CLASS
+                    prop:value=move || at.get()
+                    on:change=move |ev| at.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    class=INPUT_CLASS
+                    placeholder="Europe/Madrid"
+                    prop:value=move || timezone.get()
+                    on:change=move |ev| timezone.set(event_target_value(&ev))
+                />
+            </div>
+            {move || {
+                if at.get().is_empty() {
+                    return None;
+                }
+                Some(match results() {
+                    Ok(results) if results.is_empty() => {
+                        view! {
+                            <p class="mt-2 text-sm text-gray-500 dark:text-neutral-500">
+                                {i18n.t("probe-no-events")}
+                            </p>
+                        }
+                            .into_view()
+                    }
+                    Ok(results) => {
+                        view! {
+                            <ul class="mt-2 text-sm divide-y divide-gray-200 dark:divide-neutral-700">
+                                {results
+                                    .into_iter()
+                                    .map(|result| {
+                                        let (class, answer) = match result.status {
+                                            ProbeStatus::Occurring { start, end } => {
+                                                (
+                                                    "text-green-700 dark:text-green-500",
+                                                    {
+                                                        let mut args = FluentArgs::new();
+                                                        args.set("start", start.format(TIME_FORMAT).to_string());
+                                                        args.set("end", end.format(TIME_FORMAT).to_string());
+                                                        i18n.t_with("probe-occurring", args)
+                                                    },
+                                                )
+                                            }
+                                            ProbeStatus::NotOccurring { next: Some(next) } => {
+                                                (
+                                                    "text-gray-600 dark:text-neutral-400",
+                                                    i18n
+                                                        .t_with(
+                                                            "probe-next",
+                                                            FluentArgs::from_iter([("next", next.format(TIME_FORMAT).to_string())]),
+                                                        ),
+                                                )
+                                            }
+                                            ProbeStatus::NotOccurring { next: None } => {
+                                                (
+                                                    "text-gray-600 dark:text-neutral-400",
+                                                    i18n.t("probe-not-again"),
+                                                )
+                                            }
+                                            ProbeStatus::Inconclusive => {
+                                                (
+                                                    "text-yellow-700 dark:text-yellow-500",
+                                                    i18n.t("probe-inconclusive"),
+                                                )
+                                            }
+                                        };
+                                        view! {
+                                            <li class="py-2 flex flex-wrap justify-between gap-2">
+                                                <span class="text-gray-800 dark:text-neutral-200">
+                                                    {result.summary}
+                                                    {result.recurring.then(|| format!(" ({})", i18n.t("probe-recurring")))}
+                                                </span>
+                                                <span class=class>{answer}</span>
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </ul>
+                        }
+                            .into_view()
+                    }
+                    Err(err) => {
+                        view! {
+                            <p class="mt-2 text-sm text-red-700 dark:text-red-500">{err}</p>
+                        }
+                            .into_view()
+                    }
+                })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,116 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::properties::{rows, set_value, PropertyRow};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "w-full py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// The properties of an iCalendar or vCard source as a table whose values can
+// be edited in place. Each committed edit rewrites only that property.
+#[component]
+pub fn PropertyTablePanel(
+    #[prop(into)] source: Signal<String>,
+    // Receives the source with the edit applied.
+    #[prop(into)] on_change: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // The table is only built while the panel is open.
+    let open = create_rw_signal(false);
+    let query = create_rw_signal(String::new());
+    let visible = move || {
+        if !open.get() {
+            return vec![];
+        }
+        let query = query.get().to_ascii_uppercase();
+        rows(&source.get())
+            .into_iter()
+            .filter(|row| {
+                query.is_empty()
+                    || row.name.contains(&query)
+                    || row.component.to_ascii_uppercase().contains(&query)
+            })
+            .collect::<Vec<_>>()
+    };
+    let edit = move |line: usize, value: String| {
+        let updated = set_value(&source.get_untracked(), line, &value);
+        if updated != source.get_untracked() {
+            on_change.call(updated);
+        }
+    };
+
+    view! {
+        <details
+            class="mb-6 text-sm text-gray-600 dark:text-neutral-400"
+            on:toggle=move |ev| open.set(event_target::<web_sys::HtmlDetailsElement>(&ev).open())
+        >
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("properties-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("properties-description")}
+            </p>
+            <input
+                type="text"
+                class=format!("mt-2 max-w-xs {}", INPUT_CLASS)
+                placeholder=move || i18n.t("properties-filter")
+                prop:value=move || query.get()
+                on:input=move |ev| query.set(event_target_value(&ev))
+            />
+            <div class="mt-2 overflow-x-auto">
+                <table class="min-w-full text-sm">
+                    <thead>
+                        <tr class="text-xs text-gray-500 dark:text-neutral-500">
+                            <th class="px-3 py-1 font-medium text-start">
+                                {move || i18n.t("properties-component")}
+                            </th>
+                            <th class="px-3 py-1 font-medium text-start">
+                                {move || i18n.t("properties-property")}
+                            </th>
+                            <th class="px-3 py-1 font-medium text-start">
+                                {move || i18n.t("properties-value")}
+                            </th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                        <For
+                            each=visible
+                            key=|row| row.clone()
+                            children=move |row: PropertyRow| {
+                                let line = row.line;
+                                view! {
+                                    <tr class="align-top">
+                                        <td class="px-3 py-2 font-mono text-xs text-gray-500 dark:text-neutral-500">
+                                            {row.component}
+                                        </td>
+                                        <td class="px-3 py-2 font-mono text-xs text-gray-800 dark:text-neutral-200">
+                                            {row.name}
+                                            <span class="text-gray-500 dark:text-neutral-500">{row.params}</span>
+                                        </td>
+                                        <td class="px-3 py-1 w-1/2">
+                                            <input
+                                                type="text"
+                                                class=INPUT_CLASS
+                                                title=move || {
+                                                    i18n.t(if row.text { "properties-text" } else { "properties-raw" })
+                                                }
+                                                prop:value=row.value
+                                                on:change=move |ev| edit(line, event_target_value(&ev))
+                                            />
+                                        </td>
+                                    </tr>
+                                }
+                            }
+                        />
+                    </tbody>
+                </table>
+            </div>
+        </details>
+    }
+}
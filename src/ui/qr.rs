@@ -0,0 +1,108 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    qr::{self, QrMatrix},
+};
+
+use super::i18n::I18n;
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+const SELECT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-xs dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// A scannable QR code of the vCard or of its MECARD form.
+#[component]
+pub fn QrCodePanel(#[prop(into)] vcard: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let shown = create_rw_signal(false);
+    let compact = create_rw_signal(false);
+    let payload = create_memo(move |_| {
+        if compact.get() {
+            vcard.with(|vcard| qr::mecard(vcard)).unwrap_or_default()
+        } else {
+            vcard.get()
+        }
+    });
+    let code = create_memo(move |_| {
+        if !shown.get() {
+            return None;
+        }
+        Some(payload.with(|payload| QrMatrix::encode(payload)))
+    });
+    let warning = move || {
+        if compact.get() {
+            None
+        } else {
+            vcard.with(|vcard| qr::size_warning(vcard))
+        }
+    };
+
+    view! {
+        <Show when=move || !vcard.with(String::is_empty)>
+            <div class="mb-6">
+                <div class="flex flex-wrap items-center gap-2">
+                    <button type="button" class=BUTTON_CLASS on:click=move |_| shown.update(|shown| *shown = !*shown)>
+                        {move || i18n.t(if shown.get() { "qr-hide" } else { "qr-show" })}
+                    </button>
+                    <Show when=move || shown.get()>
+                        <select
+                            class=SELECT_CLASS
+                            aria-label=move || i18n.t("qr-payload")
+                            on:change=move |ev| compact.set(event_target_value(&ev) == "mecard")
+                        >
+                            <option value="vcard" selected=move || !compact.get()>"vCard"</option>
+                            <option value="mecard" selected=move || compact.get()>"MECARD"</option>
+                        </select>
+                        <span class="text-xs text-gray-500 dark:text-neutral-500">
+                            {move || {
+                                let mut args = FluentArgs::new();
+                                args.set("size", payload.with(String::len));
+                                args.set("max", qr::MAX_PAYLOAD_BYTES);
+                                i18n.t_with("qr-size", args)
+                            }}
+                        </span>
+                    </Show>
+                </div>
+                <Show when=move || shown.get()>
+                    {move || {
+                        warning()
+                            .map(|warning| {
+                                view! { <p class="mt-2 text-sm text-yellow-700 dark:text-yellow-500">{warning}</p> }
+                            })
+                    }}
+                    {move || {
+                        code.get()
+                            .map(|code| match code {
+                                Ok(code) => {
+                                    let extent = code.extent();
+                                    view! {
+                                        <svg
+                                            class="mt-3 size-64 bg-white"
+                                            xmlns="http://www.w3.org/2000/svg"
+                                            viewBox=format!("0 0 {0} {0}", extent)
+                                            shape-rendering="crispEdges"
+                                            role="img"
+                                            aria-label=i18n.t("qr-code")
+                                        >
+                                            <path d=code.svg_path() fill="#000"></path>
+                                        </svg>
+                                    }
+                                        .into_view()
+                                }
+                                Err(err) => {
+                                    view! { <p class="mt-2 text-sm text-red-600 dark:text-red-500">{err}</p> }
+                                        .into_view()
+                                }
+                            })
+                    }}
+                </Show>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+use leptos_router::{use_navigate, use_query_map, NavigateOptions};
+
+use jmap_convert::query::{QueryOptions, PARAMETERS};
+
+// The options in the query of the address, and a setter that writes them
+// back. Writing replaces the history entry, so the back button still leaves
+// the page, and keeps the path and any fragment.
+pub fn use_query_options(
+    samples: Vec<&'static str>,
+    max_expand: usize,
+) -> (Memo<QueryOptions>, impl Fn(&QueryOptions) + Copy + 'static) {
+    let query = use_query_map();
+    let options = create_memo(move |_| {
+        query.with(|query| {
+            QueryOptions::parse(
+                PARAMETERS
+                    .iter()
+                    .filter_map(|name| Some((*name, query.get(name)?.as_str()))),
+                &samples,
+                max_expand,
+            )
+        })
+    });
+    let navigate = store_value(use_navigate());
+    let set_options = move |options: &QueryOptions| {
+        let query = options
+            .to_pairs()
+            .into_iter()
+            .map(|(name, value)| {
+                format!(
+                    "{}={}",
+                    name,
+                    String::from(js_sys::encode_uri_component(&value))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        let query = if query.is_empty() {
+            query
+        } else {
+            format!("?{}", query)
+        };
+        let location = window().location();
+        if location.search().unwrap_or_default() == query {
+            return;
+        }
+        navigate.with_value(|navigate| {
+            navigate(
+                &format!(
+                    "{}{}{}",
+                    location.pathname().unwrap_or_default(),
+                    query,
+                    location.hash().unwrap_or_default()
+                ),
+                NavigateOptions {
+                    replace: true,
+                    scroll: false,
+                    ..Default::default()
+                },
+            )
+        });
+    };
+    (options, set_options)
+}
@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{DateTime, Utc};
+use leptos::*;
+
+use jmap_convert::{i18n::FluentArgs, recent::RecentInputs};
+
+use super::i18n::I18n;
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+// The last inputs converted, newest first. Clicking one loads it into the
+// editor with the options it was converted with.
+#[component]
+pub fn RecentInputsSidebar(
+    #[prop(into)] recent: Signal<RecentInputs>,
+    open: RwSignal<bool>,
+    #[prop(into)] on_restore: Callback<usize>,
+    #[prop(into)] on_remove: Callback<usize>,
+    #[prop(into)] on_clear: Callback<()>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <Show when=move || open.get()>
+            <aside class="fixed inset-y-0 end-0 z-50 w-80 max-w-full flex flex-col bg-white shadow-lg text-sm dark:bg-neutral-800">
+                <div class="p-4 flex items-center justify-between gap-2 border-b border-gray-200 dark:border-neutral-700">
+                    <h3 class="font-semibold text-gray-800 dark:text-neutral-200">
+                        {move || i18n.t("header-history")}
+                    </h3>
+                    <div class="flex items-center gap-2">
+                        <Show when=move || !recent.with(|recent| recent.inputs().is_empty())>
+                            <button type="button" class=BUTTON_CLASS on:click=move |_| on_clear.call(())>
+                                {move || i18n.t("recent-clear")}
+                            </button>
+                        </Show>
+                        <button type="button" class=BUTTON_CLASS on:click=move |_| open.set(false)>
+                            {move || i18n.t("recent-close")}
+                        </button>
+                    </div>
+                </div>
+                <div class="grow overflow-y-auto">
+                    {move || {
+                        let now = DateTime::from_timestamp_millis(js_sys::Date::now() as i64)
+                            .unwrap_or_default();
+                        let rows = recent
+                            .with(|recent| {
+                                recent
+                                    .inputs()
+                                    .iter()
+                                    .map(|input| {
+                                        (
+                                            input.summary.clone(),
+                                            input.format.map(|format| format.as_str()),
+                                            age(input.converted, now, i18n),
+                                            input.document.clone(),
+                                        )
+                                    })
+                                    .collect::<Vec<_>>()
+                            });
+                        if rows.is_empty() {
+                            return view! {
+                                <p class="p-4 text-gray-500 dark:text-neutral-500">
+                                    {i18n.t("recent-empty")}
+                                </p>
+                            }
+                                .into_view();
+                        }
+                        rows.into_iter()
+                            .enumerate()
+                            .map(|(index, (summary, format, age, document))| {
+                                view! {
+                                    <div class="group flex items-start gap-2 px-4 py-2 border-b border-gray-100 hover:bg-gray-50 dark:border-neutral-700 dark:hover:bg-neutral-700/50">
+                                        <button
+                                            type="button"
+                                            class="grow min-w-0 text-start"
+                                            title=i18n.t("recent-load")
+                                            on:click=move |_| {
+                                                on_restore.call(index);
+                                                open.set(false);
+                                            }
+                                        >
+                                            <span class="block truncate font-medium text-gray-800 dark:text-neutral-200">
+                                                {summary}
+                                            </span>
+                                            <span class="block text-xs text-gray-500 dark:text-neutral-500">
+                                                {[format.map(str::to_string).unwrap_or_else(|| i18n.t("recent-unknown-format")), age, document]
+                                                    .into_iter()
+                                                    .filter(|part| !part.is_empty())
+                                                    .collect::<Vec<_>>()
+                                                    .join(" ⋅ ")}
+                                            </span>
+                                        </button>
+                                        <button
+                                            type="button"
+                                            class="shrink-0 invisible group-hover:visible text-xs text-gray-400 hover:text-red-600 dark:text-neutral-500"
+                                            title=i18n.t("recent-remove")
+                                            on:click=move |_| on_remove.call(index)
+                                        >
+                                            "✕"
+                                        </button>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </aside>
+        </Show>
+    }
+}
+
+// "Just now", "5 min ago", "3 h ago", then the date.
+fn age(converted: DateTime<Utc>, now: DateTime<Utc>, i18n: I18n) -> String {
+    let minutes = (now - converted).num_minutes();
+    match minutes {
+        ..=0 => i18n.t("recent-just-now"),
+        1..=59 => i18n.t_with(
+            "recent-minutes-ago",
+            FluentArgs::from_iter([("count", minutes)]),
+        ),
+        60..=1439 => i18n.t_with(
+            "recent-hours-ago",
+            FluentArgs::from_iter([("count", minutes / 60)]),
+        ),
+        _ => converted.format("%b %-d, %Y").to_string(),
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{i18n::FluentArgs, recovery::Recovery};
+
+use super::i18n::I18n;
+
+// Switch between lenient and strict parsing, and every line lenient parsing
+// skipped or coerced in the last conversion.
+#[component]
+pub fn ParserRecoveryPanel(
+    #[prop(into)] strict: Signal<bool>,
+    #[prop(into)] recoveries: Signal<Vec<Recovery>>,
+    #[prop(into)] on_change: Callback<bool>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let mode = move |value: bool, label: &'static str| {
+        view! {
+            <label class="inline-flex items-center gap-1">
+                <input
+                    type="radio"
+                    name="parsing-mode"
+                    class="border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                    prop:checked=move || strict.get() == value
+                    on:change=move |_| on_change.call(value)
+                />
+                {move || i18n.t(label)}
+            </label>
+        }
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("recovery-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("recovery-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-4">
+                {mode(false, "recovery-lenient")} {mode(true, "recovery-strict")}
+            </div>
+            {move || {
+                recoveries
+                    .with(|recoveries| {
+                        if recoveries.is_empty() {
+                            return view! {
+                                <p class="mt-3 text-gray-800 dark:text-neutral-200">
+                                    {i18n.t("recovery-none")}
+                                </p>
+                            }
+                                .into_view();
+                        }
+                        view! {
+                            <p class="mt-3 font-medium text-gray-800 dark:text-neutral-200">
+                                {i18n
+                                    .t_with(
+                                        if strict.get() { "recovery-refused" } else { "recovery-made" },
+                                        FluentArgs::from_iter([("count", recoveries.len())]),
+                                    )}
+                            </p>
+                            <div class="mt-2 overflow-x-auto">
+                                <table class="min-w-full text-xs">
+                                    <thead>
+                                        <tr class="text-gray-500 dark:text-neutral-500">
+                                            <th class="pe-4 py-1 font-medium text-start">
+                                                {i18n.t("recovery-where")}
+                                            </th>
+                                            <th class="pe-4 py-1 font-medium text-start">
+                                                {i18n.t("recovery-problem")}
+                                            </th>
+                                            <th class="py-1 font-medium text-start">
+                                                {i18n.t("recovery-action")}
+                                            </th>
+                                        </tr>
+                                    </thead>
+                                    <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                        {recoveries
+                                            .iter()
+                                            .map(|recovery| {
+                                                view! {
+                                                    <tr class="align-top">
+                                                        <td class="pe-4 py-1 whitespace-nowrap">
+                                                            {recovery.location.clone()}
+                                                            {recovery
+                                                                .property
+                                                                .clone()
+                                                                .map(|property| {
+                                                                    view! {
+                                                                        <br/>
+                                                                        <span class="font-mono text-gray-800 dark:text-neutral-200">
+                                                                            {property}
+                                                                        </span>
+                                                                    }
+                                                                })}
+                                                        </td>
+                                                        <td class="pe-4 py-1 break-all">
+                                                            {recovery.problem.clone()}
+                                                        </td>
+                                                        <td class="py-1">{recovery.action.clone()}</td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                        }
+                            .into_view()
+                    })
+            }}
+        </details>
+    }
+}
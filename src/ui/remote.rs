@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::i18n::FluentArgs;
+
+use crate::{
+    fetch,
+    ui::{i18n::I18n, settings::FetchSettings},
+};
+
+const INPUT_CLASS: &str = "py-1 px-2 grow min-w-0 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// Loads a published calendar, address book or JSON document by its URL,
+// webcal:// links included.
+#[component]
+pub fn RemoteSourcePanel(#[prop(into)] on_load: Callback<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let fetch_settings = expect_context::<FetchSettings>();
+    let url = create_rw_signal(String::new());
+    let loading = create_rw_signal(false);
+    let status: RwSignal<Option<Result<String, String>>> = create_rw_signal(None);
+
+    let load = move || {
+        if loading.get_untracked() {
+            return;
+        }
+        let options = fetch_settings.options();
+        loading.set(true);
+        status.set(None);
+        spawn_local(async move {
+            let result = fetch::fetch_text(&url.get_untracked(), &options).await;
+            loading.set(false);
+            match result {
+                Ok(resource) => {
+                    status.set(Some(Ok(i18n.t_with(
+                        if resource.from_cache {
+                            "remote-loaded-cached"
+                        } else {
+                            "remote-loaded"
+                        },
+                        FluentArgs::from_iter([("url", resource.url)]),
+                    ))));
+                    on_load.call(resource.body);
+                }
+                Err(err) => status.set(Some(Err(err))),
+            }
+        });
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("remote-title")}
+            </summary>
+            <form
+                class="mt-3 flex items-center gap-2"
+                on:submit=move |ev| {
+                    ev.prevent_default();
+                    load();
+                }
+            >
+                <input
+                    type="url"
+                    class=INPUT_CLASS
+                    placeholder=move || i18n.t("remote-placeholder")
+                    prop:value=move || url.get()
+                    on:input=move |ev| url.set(event_target_value(&ev))
+                />
+                <button
+                    type="submit"
+                    class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 disabled:opacity-50 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                    disabled=move || loading.get() || url.with(|url| url.trim().is_empty())
+                >
+                    {move || i18n.t(if loading.get() { "remote-fetching" } else { "remote-fetch" })}
+                </button>
+            </form>
+            <p class="mt-2 text-xs">
+                {move || {
+                    i18n.t_with(
+                        "remote-limits",
+                        FluentArgs::from_iter([("size", fetch::MAX_BODY_BYTES / (1024 * 1024))]),
+                    )
+                }}
+            </p>
+            {move || {
+                status
+                    .get()
+                    .map(|status| match status {
+                        Ok(message) => view! { <p class="mt-2 text-green-600 dark:text-green-500">{message}</p> },
+                        Err(err) => view! { <p class="mt-2 text-red-600 dark:text-red-500">{err}</p> },
+                    })
+            }}
+        </details>
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    repair::{self, Change, Profile, Repair},
+};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+#[component]
+pub fn RepairPanel(
+    #[prop(into)] source: Signal<String>,
+    #[prop(into)] on_repair: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let profile = create_rw_signal(Profile::ALL[0]);
+    let report: RwSignal<Option<(Profile, Repair)>> = create_rw_signal(None);
+
+    let run = move |_| {
+        let profile = profile.get_untracked();
+        let repaired = repair::apply(&source.get_untracked(), profile);
+        if !repaired.is_empty() {
+            on_repair.call(repaired.text.clone());
+        }
+        report.set(Some((profile, repaired)));
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("repair-title")}
+            </summary>
+            <div class="mt-3 flex flex-col gap-3">
+                <div class="flex flex-wrap items-center gap-2">
+                    <select
+                        class=INPUT_CLASS
+                        on:change=move |ev| {
+                            if let Some(selected) = Profile::ALL
+                                .get(event_target_value(&ev).parse::<usize>().unwrap_or_default())
+                            {
+                                profile.set(*selected);
+                            }
+                        }
+                    >
+                        {Profile::ALL
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, item)| {
+                                view! {
+                                    <option value=idx.to_string() selected=move || profile.get() == *item>
+                                        {item.label()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-white bg-blue-600 hover:bg-blue-500 disabled:opacity-50"
+                        disabled=move || source.with(|source| source.trim().is_empty())
+                        on:click=run
+                    >
+                        {move || i18n.t("repair-run")}
+                    </button>
+                </div>
+                {move || {
+                    report
+                        .get()
+                        .map(|(profile, repaired)| {
+                            let applied = if repaired.is_empty() {
+                                view! {
+                                    <p>
+                                        {i18n
+                                            .t_with(
+                                                "repair-nothing",
+                                                FluentArgs::from_iter([("profile", profile.label())]),
+                                            )}
+                                    </p>
+                                }
+                                    .into_view()
+                            } else {
+                                view! {
+                                    <div>
+                                        <p>
+                                            {
+                                                let mut args = FluentArgs::new();
+                                                args.set("count", repaired.changes.len());
+                                                args.set("profile", profile.label());
+                                                i18n.t_with("repair-applied", args)
+                                            }
+                                        </p>
+                                        <ChangeList changes=repaired.changes/>
+                                    </div>
+                                }
+                                    .into_view()
+                            };
+                            let notes = (!repaired.notes.is_empty())
+                                .then(|| {
+                                    view! {
+                                        <div>
+                                            <p>{i18n.t("repair-notes")}</p>
+                                            <ChangeList changes=repaired.notes/>
+                                        </div>
+                                    }
+                                });
+                            view! { {applied} {notes} }
+                        })
+                }}
+            </div>
+        </details>
+    }
+}
+
+#[component]
+fn ChangeList(changes: Vec<Change>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    view! {
+        <ul class="mt-2 divide-y divide-gray-200 dark:divide-neutral-700">
+            {changes
+                .into_iter()
+                .map(|change| {
+                    view! {
+                        <li class="py-1 flex gap-3">
+                            <span class="shrink-0 w-16 text-gray-500 dark:text-neutral-500">
+                                {i18n.t_with("source-line", FluentArgs::from_iter([("line", change.line)]))}
+                            </span>
+                            <span class="text-gray-800 dark:text-neutral-200">
+                                {change.message}
+                            </span>
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    }
+}
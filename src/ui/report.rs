@@ -0,0 +1,73 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{i18n::FluentArgs, report::ConversionReport};
+
+use crate::ui::{export::save_file, i18n::I18n};
+
+const CLI_OPTION: &str = "--report";
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+#[component]
+pub fn ConversionReportPanel(
+    // Builds the report of the last conversion, None before there is one.
+    #[prop(into)] report: Callback<(), Option<ConversionReport>>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let status = create_rw_signal(String::new());
+
+    let download = move |_| {
+        let Some(report) = report.call(()) else {
+            status.set(i18n.t("report-nothing"));
+            return;
+        };
+        status.set(
+            match save_file(&report.to_string(), "application/json", &report.file_name()) {
+                Ok(()) => String::new(),
+                Err(err) => {
+                    log::warn!("Failed to download the report: {:?}", err);
+                    i18n.t("report-failed")
+                }
+            },
+        );
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("report-title")}
+            </summary>
+            <p class="mt-3">
+                {move || {
+                    // Sets the option in code type wherever the translation puts it.
+                    let description = i18n.t_with(
+                        "report-description",
+                        FluentArgs::from_iter([("option", CLI_OPTION)]),
+                    );
+                    let (before, after) = description
+                        .split_once(CLI_OPTION)
+                        .map(|(before, after)| (before.to_string(), after.to_string()))
+                        .unwrap_or((description, String::new()));
+                    view! {
+                        {before}
+                        <code class="font-mono">{CLI_OPTION}</code>
+                        {after}
+                    }
+                }}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <button type="button" class=BUTTON_CLASS on:click=download>
+                    {move || i18n.t("report-download")}
+                </button>
+                <Show when=move || !status.with(String::is_empty)>
+                    <span class="text-gray-800 dark:text-neutral-200">{move || status.get()}</span>
+                </Show>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{i18n::FluentArgs, samples, Format};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// Random but valid documents, each shown with the seed that rebuilds it.
+#[component]
+pub fn SampleGenerator(#[prop(into)] on_generate: Callback<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    // Picked from the seed when None.
+    let format: RwSignal<Option<Format>> = create_rw_signal(None);
+    let seed = create_rw_signal(String::new());
+    // The seed and format built, or the id of the error message.
+    let generated: RwSignal<Option<Result<(u64, Format), &'static str>>> = create_rw_signal(None);
+
+    let build = move |value: u64| {
+        let sample = samples::generate(value, format.get_untracked());
+        seed.set(sample.seed.to_string());
+        generated.set(Some(Ok((sample.seed, sample.format))));
+        on_generate.call(sample.text);
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("samples-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("samples-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <select
+                    class=INPUT_CLASS
+                    on:change=move |ev| format.set(Format::parse(&event_target_value(&ev)))
+                >
+                    <option value="">{move || i18n.t("samples-any-format")}</option>
+                    {samples::FORMATS
+                        .iter()
+                        .map(|format| view! { <option value=format.as_str()>{format.as_str()}</option> })
+                        .collect_view()}
+                </select>
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-sm text-white bg-blue-600 hover:bg-blue-500"
+                    on:click=move |_| build(u64::from(rand::random::<u32>()))
+                >
+                    {move || i18n.t("samples-new")}
+                </button>
+                <input
+                    type="text"
+                    inputmode="numeric"
+                    class=INPUT_CLASS
+                    placeholder=move || i18n.t("samples-seed")
+                    prop:value=move || seed.get()
+                    on:change=move |ev| seed.set(event_target_value(&ev))
+                />
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-sm border border-gray-200 hover:bg-gray-100 dark:border-neutral-700 dark:hover:bg-neutral-700"
+                    on:click=move |_| match samples::parse_seed(&seed.get_untracked()) {
+                        Some(value) => build(value),
+                        None => generated.set(Some(Err("samples-invalid-seed"))),
+                    }
+                >
+                    {move || i18n.t("samples-rebuild")}
+                </button>
+            </div>
+            {move || {
+                generated
+                    .get()
+                    .map(|generated| match generated {
+                        Ok((seed, format)) => view! {
+                            <p class="mt-2 text-gray-800 dark:text-neutral-200">
+                                {move || {
+                                    i18n.t_with(
+                                        "samples-generated",
+                                        FluentArgs::from_iter([
+                                            ("format", format.as_str().to_string()),
+                                            ("seed", seed.to_string()),
+                                        ]),
+                                    )
+                                }}
+                            </p>
+                        }
+                            .into_view(),
+                        Err(err) => {
+                            view! { <p class="mt-2 text-red-600 dark:text-red-500">{move || i18n.t(err)}</p> }
+                                .into_view()
+                        }
+                    })
+            }}
+        </details>
+    }
+}
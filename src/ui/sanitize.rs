@@ -0,0 +1,60 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::i18n::FluentArgs;
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// Toggle and whitelist for stripping vendor extensions before conversion.
+// Every removed item is listed in the findings.
+#[component]
+pub fn VendorStripPanel(
+    #[prop(into)] enabled: Signal<bool>,
+    // Comma separated names to keep.
+    #[prop(into)] keep: Signal<String>,
+    #[prop(into)] removed: Signal<usize>,
+    #[prop(into)] on_change: Callback<(bool, String)>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("sanitize-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("sanitize-description")}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-4">
+                <label class="inline-flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                        prop:checked=move || enabled.get()
+                        on:change=move |ev| on_change.call((event_target_checked(&ev), keep.get_untracked()))
+                    />
+                    {move || i18n.t("sanitize-enable")}
+                </label>
+                <input
+                    type="text"
+                    class=format!("grow {}", INPUT_CLASS)
+                    placeholder=move || i18n.t("sanitize-keep")
+                    prop:value=move || keep.get()
+                    on:change=move |ev| on_change.call((enabled.get_untracked(), event_target_value(&ev)))
+                />
+            </div>
+            <Show when=move || enabled.get()>
+                <p class="mt-2 text-gray-800 dark:text-neutral-200">
+                    {move || i18n.t_with("sanitize-removed", FluentArgs::from_iter([("count", removed.get())]))}
+                </p>
+            </Show>
+        </details>
+    }
+}
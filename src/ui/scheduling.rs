@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::DateTime;
+use leptos::*;
+
+use jmap_convert::{
+    export::ExportContext,
+    itip::{self, SchedulingMessage},
+    Format,
+};
+
+use crate::ui::{
+    export::{CopyButton, DownloadButton},
+    i18n::I18n,
+};
+
+const SELECT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+const ANSWERS: [(&str, &str); 3] = [
+    ("ACCEPTED", "scheduling-accept"),
+    ("TENTATIVE", "scheduling-maybe"),
+    ("DECLINED", "scheduling-decline"),
+];
+
+// What an iTIP message with a METHOD means, who it concerns and, for
+// invitations, the REPLY an attendee would send.
+#[component]
+pub fn SchedulingPanel(
+    #[prop(into)] icalendar: Signal<String>,
+    #[prop(into)] on_open: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let message =
+        create_memo(move |_| icalendar.with(|source| SchedulingMessage::interpret(source)));
+    let replier = create_rw_signal(String::new());
+    let answer = create_rw_signal("ACCEPTED".to_string());
+    let reply = create_rw_signal(String::new());
+    create_effect(move |_| {
+        message.track();
+        reply.set(String::new());
+        replier.set(String::new());
+    });
+
+    // Attendees other than the organizer.
+    let repliers = move || {
+        message.with(|message| {
+            message
+                .iter()
+                .flat_map(|message| {
+                    message.attendees.iter().filter(|attendee| {
+                        message
+                            .organizer
+                            .as_ref()
+                            .is_none_or(|organizer| organizer.address != attendee.address)
+                    })
+                })
+                .map(|attendee| attendee.address.clone())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let generate = move |_| {
+        let address = replier.get_untracked();
+        let address = if address.is_empty() {
+            repliers().first().cloned().unwrap_or_default()
+        } else {
+            address
+        };
+        let now = DateTime::from_timestamp_millis(js_sys::Date::now() as i64).unwrap_or_default();
+        reply.set(
+            icalendar
+                .with_untracked(|source| {
+                    itip::reply(source, &address, &answer.get_untracked(), now)
+                })
+                .unwrap_or_default(),
+        );
+    };
+
+    view! {
+        {move || {
+            message
+                .get()
+                .map(|message| {
+                    let attendees = message
+                        .attendees
+                        .iter()
+                        .map(|attendee| {
+                            let class = match attendee.partstat.as_str() {
+                                "ACCEPTED" => "text-green-600 dark:text-green-500",
+                                "DECLINED" => "text-red-600 dark:text-red-500",
+                                "TENTATIVE" => "text-yellow-600 dark:text-yellow-500",
+                                _ => "text-gray-500 dark:text-neutral-500",
+                            };
+                            view! {
+                                <tr>
+                                    <td class="px-3 py-2 text-gray-800 dark:text-neutral-200">
+                                        {attendee.name.clone()}
+                                        {(attendee.name != attendee.address)
+                                            .then(|| {
+                                                view! {
+                                                    <span class="ms-2 text-xs text-gray-500 dark:text-neutral-500">
+                                                        {attendee.address.clone()}
+                                                    </span>
+                                                }
+                                            })}
+                                    </td>
+                                    <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">
+                                        {attendee.role.clone().unwrap_or_else(|| "REQ-PARTICIPANT".to_string())}
+                                    </td>
+                                    <td class=format!("px-3 py-2 {}", class)>{attendee.partstat.clone()}</td>
+                                    <td class="px-3 py-2 text-gray-500 dark:text-neutral-500">
+                                        {attendee.rsvp.then(|| i18n.t("scheduling-reply-requested"))}
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view();
+                    let can_reply = message.can_reply;
+
+                    view! {
+                        <div class="mb-6">
+                            <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                                {i18n.t("scheduling-title")}
+                                <span class="ms-2 py-0.5 px-2 rounded-full text-xs font-medium bg-blue-100 text-blue-800 dark:bg-blue-800/30 dark:text-blue-500">
+                                    {message.method.clone()}
+                                </span>
+                            </h3>
+                            <p class="mt-2 text-sm text-gray-800 dark:text-neutral-200">
+                                {message.explanation.clone()}
+                            </p>
+                            <table class="mt-2 min-w-full text-sm">
+                                <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                    {attendees}
+                                </tbody>
+                            </table>
+                            <Show when=move || can_reply>
+                                <div class="mt-3 flex flex-wrap items-center gap-2 text-sm text-gray-600 dark:text-neutral-400">
+                                    <span>{i18n.t("scheduling-reply-as")}</span>
+                                    <select
+                                        class=SELECT_CLASS
+                                        on:change=move |ev| replier.set(event_target_value(&ev))
+                                    >
+                                        {repliers()
+                                            .into_iter()
+                                            .map(|address| {
+                                                view! { <option value=address.clone()>{address.clone()}</option> }
+                                            })
+                                            .collect_view()}
+                                    </select>
+                                    <select
+                                        class=SELECT_CLASS
+                                        on:change=move |ev| answer.set(event_target_value(&ev))
+                                    >
+                                        {ANSWERS
+                                            .iter()
+                                            .map(|&(partstat, label)| {
+                                                view! { <option value=partstat>{i18n.t(label)}</option> }
+                                            })
+                                            .collect_view()}
+                                    </select>
+                                    <button
+                                        type="button"
+                                        class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                                        on:click=generate
+                                    >
+                                        {i18n.t("scheduling-generate")}
+                                    </button>
+                                </div>
+                            </Show>
+                        </div>
+                    }
+                })
+        }}
+        <Show when=move || !reply.with(String::is_empty)>
+            <div class="relative mb-6 bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                <div class="absolute top-2 end-2 flex gap-1">
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-white hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-800 dark:hover:bg-neutral-600"
+                        on:click=move |_| on_open.call(reply.get_untracked())
+                    >
+                        {move || i18n.t("scheduling-open")}
+                    </button>
+                    <CopyButton
+                        payload=reply
+                        context=Signal::derive(move || ExportContext {
+                            format: Format::ICalendar.as_str().to_string(),
+                            source_type: Format::ICalendar.as_str().to_string(),
+                            version: env!("CARGO_PKG_VERSION").to_string(),
+                            options: vec![("method".to_string(), "REPLY".to_string())],
+                            timestamp: String::new(),
+                        })
+                        json=false
+                    />
+                    <DownloadButton payload=reply format=Format::ICalendar/>
+                </div>
+                <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                    {move || reply.get()}
+                </pre>
+            </div>
+        </Show>
+    }
+}
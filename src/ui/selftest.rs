@@ -0,0 +1,187 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    selftest::{self, SelfTestResult, STAGES},
+};
+
+use crate::{ui::i18n::I18n, SAMPLES};
+
+#[component]
+pub fn SelfTest() -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let snippets: RwSignal<Vec<String>> = create_rw_signal(vec![]);
+    let snippet = create_rw_signal(String::new());
+    let results: RwSignal<Vec<SelfTestResult>> = create_rw_signal(vec![]);
+
+    let run_all = move |_| {
+        let performance = window().performance();
+        let now = move || {
+            performance
+                .as_ref()
+                .map(|performance| performance.now())
+                .unwrap_or_else(js_sys::Date::now)
+        };
+        let mut run = SAMPLES
+            .iter()
+            .map(|(name, sample)| selftest::run(name, sample, &now))
+            .collect::<Vec<_>>();
+        run.extend(snippets.get().iter().enumerate().map(|(num, snippet)| {
+            selftest::run(&format!("snippet_{:03}", num + 1), snippet, &now)
+        }));
+        results.set(run);
+    };
+
+    view! {
+        <div class="max-w-4xl px-4 py-10 sm:px-6 lg:px-8 mx-auto">
+            <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                <div class="mb-8">
+                    <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                        {move || i18n.t("selftest-title")}
+                    </h2>
+                    <p class="text-sm text-gray-600 dark:text-neutral-400">
+                        {move || {
+                            i18n.t_with(
+                                "selftest-description",
+                                FluentArgs::from_iter([("version", env!("CARGO_PKG_VERSION"))]),
+                            )
+                        }}
+                    </p>
+                </div>
+
+                <textarea
+                    class="p-3 block w-full bg-gray-100 border-gray-200 rounded-lg text-sm font-mono dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-400"
+                    rows="6"
+                    placeholder=move || i18n.t("selftest-placeholder")
+                    prop:value=move || snippet.get()
+                    on:change=move |ev| snippet.set(event_target_value(&ev))
+                ></textarea>
+                <div class="flex flex-wrap items-center gap-2 mt-3">
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                        on:click=move |_| {
+                            let text = snippet.get();
+                            if !text.trim().is_empty() {
+                                snippets.update(|snippets| snippets.push(text));
+                                snippet.set(String::new());
+                            }
+                        }
+                    >
+                        {move || {
+                            i18n.t_with(
+                                "selftest-add",
+                                FluentArgs::from_iter([("count", snippets.with(Vec::len))]),
+                            )
+                        }}
+                    </button>
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-sm text-white bg-blue-600 hover:bg-blue-500"
+                        on:click=run_all
+                    >
+                        {move || i18n.t("selftest-run")}
+                    </button>
+                    <span class="text-sm text-gray-600 dark:text-neutral-400">
+                        {move || {
+                            let results = results.get();
+                            if results.is_empty() {
+                                String::new()
+                            } else {
+                                let mut args = FluentArgs::new();
+                                args.set("passed", results.iter().filter(|result| result.passed()).count());
+                                args.set("count", results.len());
+                                args.set(
+                                    "elapsed",
+                                    format!("{:.2}", results.iter().map(|result| result.total_ms()).sum::<f64>()),
+                                );
+                                i18n.t_with("selftest-summary", args)
+                            }
+                        }}
+                    </span>
+                </div>
+
+                <Show when=move || !results.get().is_empty()>
+                    <div class="mt-6 border border-gray-200 overflow-x-auto dark:border-neutral-700">
+                        <table class="min-w-full divide-y divide-gray-200 dark:divide-neutral-700">
+                            <thead>
+                                <tr>
+                                    <th class="px-4 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500">
+                                        {move || i18n.t("selftest-sample")}
+                                    </th>
+                                    <th class="px-4 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500">
+                                        {move || i18n.t("selftest-format")}
+                                    </th>
+                                    {STAGES
+                                        .iter()
+                                        .map(|&stage| {
+                                            view! {
+                                                <th class="px-4 py-3 text-start text-xs font-medium text-gray-500 uppercase dark:text-neutral-500">
+                                                    {move || i18n.t(stage.message_id())}
+                                                </th>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                <For
+                                    each=move || results.get()
+                                    key=|result| (result.name.clone(), result.total_ms().to_bits())
+                                    children=move |result| {
+                                        let cells = STAGES
+                                            .iter()
+                                            .map(|stage| match result.stage(*stage) {
+                                                Some(stage) if stage.error.is_none() => {
+                                                    view! {
+                                                        <td class="px-4 py-3 whitespace-nowrap text-sm text-green-700 dark:text-green-500">
+                                                            {format!("✓ {:.2}ms", stage.elapsed_ms)}
+                                                        </td>
+                                                    }
+                                                }
+                                                Some(stage) => {
+                                                    view! {
+                                                        <td
+                                                            class="px-4 py-3 whitespace-nowrap text-sm text-red-700 dark:text-red-500"
+                                                            title=stage.error.clone().unwrap_or_default()
+                                                        >
+                                                            {format!("✗ {:.2}ms", stage.elapsed_ms)}
+                                                        </td>
+                                                    }
+                                                }
+                                                None => {
+                                                    view! {
+                                                        <td class="px-4 py-3 whitespace-nowrap text-sm text-gray-400 dark:text-neutral-600">
+                                                            "–"
+                                                        </td>
+                                                    }
+                                                }
+                                            })
+                                            .collect_view();
+                                        view! {
+                                            <tr>
+                                                <td class="px-4 py-3 whitespace-nowrap text-sm font-medium text-gray-800 dark:text-neutral-200">
+                                                    {result.name.clone()}
+                                                </td>
+                                                <td class="px-4 py-3 whitespace-nowrap text-sm text-gray-800 dark:text-neutral-200">
+                                                    {result.format}
+                                                </td>
+                                                {cells}
+                                            </tr>
+                                        }
+                                    }
+                                />
+                            </tbody>
+                        </table>
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
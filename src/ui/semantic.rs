@@ -0,0 +1,149 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{expected::DisagreementKind, i18n::FluentArgs, semantic};
+
+use super::i18n::I18n;
+
+const TEXTAREA_CLASS: &str = "p-3 block w-full bg-gray-100 border-gray-200 rounded-lg text-sm font-mono dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-400";
+const BUTTON_CLASS: &str = "mt-1 py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+
+// Two inputs of any format compared by what they mean, such as the same event
+// exported by two servers.
+#[component]
+pub fn SemanticComparePanel(
+    // The source being edited, offered as either input.
+    #[prop(into)] current: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let first = create_rw_signal(String::new());
+    let second = create_rw_signal(String::new());
+    let comparison = create_memo(move |_| {
+        let (first, second) = (first.get(), second.get());
+        if first.trim().is_empty() || second.trim().is_empty() {
+            return None;
+        }
+        Some(semantic::compare(&first, &second))
+    });
+
+    let pane = move |message_id: &'static str, input: RwSignal<String>| {
+        view! {
+            <div class="grow basis-0 min-w-0">
+                <textarea
+                    class=TEXTAREA_CLASS
+                    rows="8"
+                    placeholder=move || i18n.t(message_id)
+                    prop:value=move || input.get()
+                    on:input=move |ev| input.set(event_target_value(&ev))
+                ></textarea>
+                <Show when=move || !current.with(|current| current.trim().is_empty())>
+                    <button
+                        type="button"
+                        class=BUTTON_CLASS
+                        on:click=move |_| input.set(current.get_untracked())
+                    >
+                        {move || i18n.t("semantic-use-current")}
+                    </button>
+                </Show>
+            </div>
+        }
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("semantic-title")}
+            </summary>
+            <p class="mt-3">
+                {move || i18n.t("semantic-description")}
+            </p>
+            <div class="mt-2 flex flex-col sm:flex-row gap-3">
+                {pane("semantic-first-input", first)}
+                {pane("semantic-second-input", second)}
+            </div>
+            {move || {
+                comparison
+                    .get()
+                    .map(|comparison| match comparison {
+                        Err(err) => view! { <p class="mt-3 text-red-600 dark:text-red-500">{err}</p> }.into_view(),
+                        Ok(differences) if differences.is_empty() => {
+                            view! {
+                                <p class="mt-3 font-medium text-green-600 dark:text-green-500">
+                                    {move || i18n.t("semantic-same")}
+                                </p>
+                            }
+                                .into_view()
+                        }
+                        Ok(differences) => {
+                            view! {
+                                <p class="mt-3 font-medium text-gray-800 dark:text-neutral-200">
+                                    {
+                                        let count = differences.len();
+                                        move || i18n.t_with("semantic-differences", FluentArgs::from_iter([("count", count)]))
+                                    }
+                                </p>
+                                <div class="mt-2 overflow-x-auto">
+                                    <table class="min-w-full text-xs">
+                                        <thead>
+                                            <tr class="text-gray-500 dark:text-neutral-500">
+                                                <th class="pe-4 py-1 font-medium text-start">
+                                                    {move || i18n.t("semantic-difference")}
+                                                </th>
+                                                <th class="pe-4 py-1 font-medium text-start">
+                                                    {move || i18n.t("semantic-first")}
+                                                </th>
+                                                <th class="py-1 font-medium text-start">{move || i18n.t("semantic-second")}</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                            {differences
+                                                .into_iter()
+                                                .map(|difference| {
+                                                    let class = match difference.kind {
+                                                        DisagreementKind::Different => "text-yellow-700 dark:text-yellow-500",
+                                                        DisagreementKind::Missing => "text-blue-700 dark:text-blue-400",
+                                                        DisagreementKind::Unexpected => "text-red-700 dark:text-red-500",
+                                                    };
+                                                    view! {
+                                                        <tr class="align-top">
+                                                            <td class="pe-4 py-1">
+                                                                {difference
+                                                                    .entry
+                                                                    .map(|entry| {
+                                                                        view! {
+                                                                            <span class="text-gray-500 dark:text-neutral-500">
+                                                                                {entry}
+                                                                            </span>
+                                                                            <br/>
+                                                                        }
+                                                                    })}
+                                                                <span class=class>{difference.summary}</span>
+                                                                <br/>
+                                                                <span class="font-mono break-all">{difference.path}</span>
+                                                            </td>
+                                                            <td class="pe-4 py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                                {difference.left.unwrap_or_default()}
+                                                            </td>
+                                                            <td class="py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                                {difference.right.unwrap_or_default()}
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }
+                                .into_view()
+                        }
+                    })
+            }}
+        </details>
+    }
+}
@@ -0,0 +1,303 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use codee::string::FromToStringCodec;
+use leptos::*;
+use leptos_use::storage::{use_local_storage, use_local_storage_with_options, UseStorageOptions};
+
+use jmap_convert::{export::HeaderStyle, i18n::FluentArgs};
+
+use super::i18n::I18n;
+use crate::fetch::{self, FetchOptions};
+
+// Larger sources are only converted once committed, not while typed.
+pub const LIVE_CONVERSION_LIMIT: usize = 512 * 1024;
+
+#[derive(Clone, Copy)]
+pub struct FetchSettings {
+    pub cors_proxy: Signal<String>,
+    set_cors_proxy: WriteSignal<String>,
+    pub bypass_cache: Signal<bool>,
+    set_bypass_cache: WriteSignal<bool>,
+}
+
+impl FetchSettings {
+    pub fn load() -> Self {
+        let (cors_proxy, set_cors_proxy, _) =
+            use_local_storage::<String, FromToStringCodec>("jmap-convert.cors-proxy");
+        let (bypass_cache, set_bypass_cache, _) =
+            use_local_storage::<bool, FromToStringCodec>("jmap-convert.bypass-cache");
+        FetchSettings {
+            cors_proxy,
+            set_cors_proxy,
+            bypass_cache,
+            set_bypass_cache,
+        }
+    }
+
+    pub fn options(&self) -> FetchOptions {
+        FetchOptions {
+            cors_proxy: self.cors_proxy.get(),
+            bypass_cache: self.bypass_cache.get(),
+        }
+    }
+}
+
+// Context header styles for copied output, one for text formats and one for
+// JSON, and whether private calendar data is redacted.
+#[derive(Clone, Copy)]
+pub struct ExportSettings {
+    pub text_header: Signal<HeaderStyle>,
+    set_text_header: WriteSignal<HeaderStyle>,
+    pub json_header: Signal<HeaderStyle>,
+    set_json_header: WriteSignal<HeaderStyle>,
+    pub redact_private: Signal<bool>,
+    set_redact_private: WriteSignal<bool>,
+}
+
+impl ExportSettings {
+    pub fn load() -> Self {
+        let (text_header, set_text_header, _) =
+            use_local_storage::<HeaderStyle, FromToStringCodec>("jmap-convert.export-header-text");
+        let (json_header, set_json_header, _) =
+            use_local_storage::<HeaderStyle, FromToStringCodec>("jmap-convert.export-header-json");
+        let (redact_private, set_redact_private, _) =
+            use_local_storage::<bool, FromToStringCodec>("jmap-convert.export-redact-private");
+        ExportSettings {
+            text_header,
+            set_text_header,
+            json_header,
+            set_json_header,
+            redact_private,
+            set_redact_private,
+        }
+    }
+
+    pub fn header(&self, json: bool) -> HeaderStyle {
+        if json {
+            self.json_header.get()
+        } else {
+            self.text_header.get()
+        }
+    }
+}
+
+// Whether the source converts while it is typed, how long typing must pause
+// first and whether conversion times are shown below the results.
+#[derive(Clone, Copy)]
+pub struct EditorSettings {
+    pub live: Signal<bool>,
+    set_live: WriteSignal<bool>,
+    pub delay_ms: Signal<u32>,
+    set_delay_ms: WriteSignal<u32>,
+    pub show_metrics: Signal<bool>,
+    set_show_metrics: WriteSignal<bool>,
+}
+
+impl EditorSettings {
+    pub fn load() -> Self {
+        let (live, set_live, _) = use_local_storage_with_options::<bool, FromToStringCodec>(
+            "jmap-convert.live-conversion",
+            UseStorageOptions::default().initial_value(true),
+        );
+        let (delay_ms, set_delay_ms, _) = use_local_storage_with_options::<u32, FromToStringCodec>(
+            "jmap-convert.live-conversion-delay",
+            UseStorageOptions::default().initial_value(400),
+        );
+        let (show_metrics, set_show_metrics, _) =
+            use_local_storage::<bool, FromToStringCodec>("jmap-convert.show-metrics");
+        EditorSettings {
+            live,
+            set_live,
+            delay_ms,
+            set_delay_ms,
+            show_metrics,
+            set_show_metrics,
+        }
+    }
+}
+
+#[component]
+pub fn FetchSettingsPanel() -> impl IntoView {
+    let settings = expect_context::<FetchSettings>();
+    let export_settings = expect_context::<ExportSettings>();
+    let editor_settings = expect_context::<EditorSettings>();
+    let i18n = expect_context::<I18n>();
+    let header_select = move |label: &'static str,
+                              json: bool,
+                              value: Signal<HeaderStyle>,
+                              set_value: WriteSignal<HeaderStyle>| {
+        view! {
+            <label class="inline-flex items-center gap-1">
+                <span>{label}</span>
+                <select
+                    class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                    on:change=move |ev| {
+                        if let Ok(style) = event_target_value(&ev).parse() {
+                            set_value.set(style);
+                        }
+                    }
+                >
+                    {HeaderStyle::available(json)
+                        .iter()
+                        .map(|style| {
+                            view! {
+                                <option
+                                    value=style.as_str()
+                                    selected=move || value.get() == *style
+                                >
+                                    {move || i18n.t(style.message_id())}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+        }
+    };
+    let cached_entries: RwSignal<Option<u32>> = create_rw_signal(None);
+    let refresh_count = move || {
+        spawn_local(async move {
+            cached_entries.set(fetch::cache_len().await.ok());
+        })
+    };
+    refresh_count();
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("settings-title")}
+            </summary>
+            <div class="mt-3 flex flex-col gap-2">
+                <label class="flex flex-wrap items-center gap-2">
+                    <span>{move || i18n.t("settings-cors-proxy")}</span>
+                    <input
+                        type="text"
+                        class="grow py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                        placeholder="https://proxy.example.com/?url={url}"
+                        prop:value=move || settings.cors_proxy.get()
+                        on:change=move |ev| settings.set_cors_proxy.set(event_target_value(&ev).trim().to_string())
+                    />
+                </label>
+                <p class="text-xs">
+                    {move || {
+                        i18n.t_with(
+                            "settings-cors-proxy-help",
+                            FluentArgs::from_iter([("placeholder", "{url}")]),
+                        )
+                    }}
+                </p>
+                <div class="flex flex-wrap items-center gap-3">
+                    <label class="inline-flex items-center gap-1">
+                        <input
+                            type="checkbox"
+                            class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                            prop:checked=move || settings.bypass_cache.get()
+                            on:change=move |ev| settings.set_bypass_cache.set(event_target_checked(&ev))
+                        />
+                        {move || i18n.t("settings-bypass-cache")}
+                    </label>
+                    <span class="text-xs">
+                        {move || match cached_entries.get() {
+                            Some(count) => {
+                                i18n.t_with("settings-cached", FluentArgs::from_iter([("count", count)]))
+                            }
+                            None => i18n.t("settings-cache-unavailable"),
+                        }}
+                    </span>
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                        on:click=move |_| {
+                            spawn_local(async move {
+                                if let Err(err) = fetch::cache_clear().await {
+                                    log::warn!("Failed to clear the fetch cache: {}", err);
+                                }
+                                cached_entries.set(fetch::cache_len().await.ok());
+                            })
+                        }
+                    >
+                        {move || i18n.t("settings-clear-cache")}
+                    </button>
+                </div>
+                <div class="flex flex-wrap items-center gap-3">
+                    <span>{move || i18n.t("settings-copy-header")}</span>
+                    {header_select(
+                        "iCalendar / vCard",
+                        false,
+                        export_settings.text_header,
+                        export_settings.set_text_header,
+                    )}
+                    {header_select(
+                        "JSON",
+                        true,
+                        export_settings.json_header,
+                        export_settings.set_json_header,
+                    )}
+                </div>
+                <p class="text-xs">
+                    {move || i18n.t("settings-copy-header-help")}
+                </p>
+                <label class="inline-flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                        prop:checked=move || export_settings.redact_private.get()
+                        on:change=move |ev| export_settings.set_redact_private.set(event_target_checked(&ev))
+                    />
+                    {move || i18n.t("settings-redact")}
+                </label>
+                <p class="text-xs">
+                    {move || i18n.t("settings-redact-help")}
+                </p>
+                <div class="flex flex-wrap items-center gap-3">
+                    <label class="inline-flex items-center gap-1">
+                        <input
+                            type="checkbox"
+                            class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                            prop:checked=move || editor_settings.live.get()
+                            on:change=move |ev| editor_settings.set_live.set(event_target_checked(&ev))
+                        />
+                        {move || i18n.t("settings-live")}
+                    </label>
+                    <label class="inline-flex items-center gap-1">
+                        <span>{move || i18n.t("settings-live-delay")}</span>
+                        <input
+                            type="number"
+                            min="0"
+                            step="100"
+                            class="w-20 py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                            prop:value=move || editor_settings.delay_ms.get().to_string()
+                            on:change=move |ev| {
+                                if let Ok(delay) = event_target_value(&ev).trim().parse() {
+                                    editor_settings.set_delay_ms.set(delay);
+                                }
+                            }
+                        />
+                        <span>{move || i18n.t("settings-live-delay-unit")}</span>
+                    </label>
+                </div>
+                <p class="text-xs">
+                    {move || {
+                        i18n.t_with(
+                            "settings-live-limit",
+                            FluentArgs::from_iter([("size", LIVE_CONVERSION_LIMIT / 1024)]),
+                        )
+                    }}
+                </p>
+                <label class="inline-flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                        prop:checked=move || editor_settings.show_metrics.get()
+                        on:change=move |ev| editor_settings.set_show_metrics.set(event_target_checked(&ev))
+                    />
+                    {move || i18n.t("settings-metrics")}
+                </label>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{Local, NaiveDate, NaiveTime, Weekday};
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    shifts::{ShiftPattern, ShiftSchedule, ShiftVerification},
+};
+
+use super::i18n::I18n;
+
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Mon, "weekday-mon"),
+    (Weekday::Tue, "weekday-tue"),
+    (Weekday::Wed, "weekday-wed"),
+    (Weekday::Thu, "weekday-thu"),
+    (Weekday::Fri, "weekday-fri"),
+    (Weekday::Sat, "weekday-sat"),
+    (Weekday::Sun, "weekday-sun"),
+];
+const ORDINALS: [(i8, &str); 5] = [
+    (1, "shifts-first"),
+    (2, "shifts-second"),
+    (3, "shifts-third"),
+    (4, "shifts-fourth"),
+    (-1, "shifts-last"),
+];
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+#[component]
+pub fn ShiftGenerator(#[prop(into)] on_generate: Callback<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let kind = create_rw_signal("onoff".to_string());
+    let on_days = create_rw_signal(4u32);
+    let off_days = create_rw_signal(4u32);
+    let week_a = create_rw_signal([true, true, true, false, false, false, false]);
+    let week_b = create_rw_signal([false, false, false, true, true, false, false]);
+    let weekday = create_rw_signal(Weekday::Sat);
+    let ordinals = create_rw_signal(vec![2i8, 4]);
+    let summary = create_rw_signal(i18n.t("shifts-default-summary"));
+    let first_day = create_rw_signal(Local::now().date_naive());
+    let start_time = create_rw_signal(NaiveTime::from_hms_opt(8, 0, 0).unwrap());
+    let duration_hours = create_rw_signal(8u32);
+    let tz_id = create_rw_signal(String::new());
+    let cycles = create_rw_signal(4u32);
+    let result: RwSignal<Option<Result<ShiftVerification, String>>> = create_rw_signal(None);
+
+    let generate = move |_| {
+        let pattern = match kind.get().as_str() {
+            "alternating" => ShiftPattern::AlternatingWeeks {
+                week_a: week_a.get(),
+                week_b: week_b.get(),
+            },
+            "monthly" => ShiftPattern::MonthlyOrdinal {
+                weekday: weekday.get(),
+                ordinals: ordinals.get(),
+            },
+            _ => ShiftPattern::OnOff {
+                on: on_days.get(),
+                off: off_days.get(),
+            },
+        };
+        let schedule = ShiftSchedule {
+            pattern,
+            summary: summary.get(),
+            first_day: first_day.get(),
+            start_time: start_time.get(),
+            duration_hours: duration_hours.get(),
+            tz_id: tz_id.get(),
+            cycles: cycles.get(),
+        };
+        match schedule.to_icalendar() {
+            Ok(icalendar) => {
+                result.set(Some(schedule.verify(&icalendar)));
+                on_generate.call(icalendar);
+            }
+            Err(err) => result.set(Some(Err(err))),
+        }
+    };
+
+    let day_checkboxes = move |days: RwSignal<[bool; 7]>| {
+        WEEKDAYS
+            .iter()
+            .enumerate()
+            .map(|(day, &(_, label))| {
+                view! {
+                    <label class="inline-flex items-center gap-1">
+                        <input
+                            type="checkbox"
+                            class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                            prop:checked=move || days.get()[day]
+                            on:change=move |ev| {
+                                let checked = event_target_checked(&ev);
+                                days.update(|days| days[day] = checked);
+                            }
+                        />
+                        {move || i18n.t(label)}
+                    </label>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="mt-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("shifts-title")}
+            </summary>
+            <div class="mt-3 flex flex-col gap-3">
+                <div class="flex flex-wrap items-center gap-2">
+                    <select
+                        class=INPUT_CLASS
+                        on:change=move |ev| kind.set(event_target_value(&ev))
+                    >
+                        <option value="onoff">{move || i18n.t("shifts-on-off")}</option>
+                        <option value="alternating">{move || i18n.t("shifts-alternating")}</option>
+                        <option value="monthly">{move || i18n.t("shifts-monthly")}</option>
+                    </select>
+                    <input
+                        type="text"
+                        class=INPUT_CLASS
+                        placeholder=move || i18n.t("shifts-summary")
+                        prop:value=move || summary.get()
+                        on:change=move |ev| summary.set(event_target_value(&ev))
+                    />
+                </div>
+
+                <Show when=move || kind.get() == "onoff">
+                    <div class="flex flex-wrap items-center gap-2">
+                        <input
+                            type="number"
+                            min="1"
+                            class=INPUT_CLASS
+                            prop:value=move || on_days.get().to_string()
+                            on:change=move |ev| {
+                                on_days.set(event_target_value(&ev).parse().unwrap_or(1))
+                            }
+                        />
+                        <span>{move || i18n.t("shifts-days-on")}</span>
+                        <input
+                            type="number"
+                            min="0"
+                            class=INPUT_CLASS
+                            prop:value=move || off_days.get().to_string()
+                            on:change=move |ev| {
+                                off_days.set(event_target_value(&ev).parse().unwrap_or(0))
+                            }
+                        />
+                        <span>{move || i18n.t("shifts-days-off")}</span>
+                    </div>
+                </Show>
+                <Show when=move || kind.get() == "alternating">
+                    <div class="flex flex-wrap items-center gap-3">
+                        <span class="w-16">{move || i18n.t("shifts-week-a")}</span>
+                        {day_checkboxes(week_a)}
+                    </div>
+                    <div class="flex flex-wrap items-center gap-3">
+                        <span class="w-16">{move || i18n.t("shifts-week-b")}</span>
+                        {day_checkboxes(week_b)}
+                    </div>
+                    <p class="text-xs">{move || i18n.t("shifts-week-a-start")}</p>
+                </Show>
+                <Show when=move || kind.get() == "monthly">
+                    <div class="flex flex-wrap items-center gap-3">
+                        {ORDINALS
+                            .iter()
+                            .map(|&(ordinal, label)| {
+                                view! {
+                                    <label class="inline-flex items-center gap-1">
+                                        <input
+                                            type="checkbox"
+                                            class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                                            prop:checked=move || ordinals.get().contains(&ordinal)
+                                            on:change=move |ev| {
+                                                let checked = event_target_checked(&ev);
+                                                ordinals
+                                                    .update(|ordinals| {
+                                                        ordinals.retain(|item| *item != ordinal);
+                                                        if checked {
+                                                            ordinals.push(ordinal);
+                                                            ordinals.sort_unstable_by_key(|item| (*item < 0, *item));
+                                                        }
+                                                    });
+                                            }
+                                        />
+                                        {move || i18n.t(label)}
+                                    </label>
+                                }
+                            })
+                            .collect_view()}
+                        <select
+                            class=INPUT_CLASS
+                            on:change=move |ev| {
+                                if let Ok(day) = event_target_value(&ev).parse::<Weekday>() {
+                                    weekday.set(day);
+                                }
+                            }
+                        >
+                            {WEEKDAYS
+                                .iter()
+                                .map(|&(day, label)| {
+                                    view! {
+                                        <option value=day.to_string() selected=move || weekday.get() == day>
+                                            {move || i18n.t(label)}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </div>
+                </Show>
+
+                <div class="flex flex-wrap items-center gap-2">
+                    <span>{move || i18n.t("shifts-first-day")}</span>
+                    <input
+                        type="date"
+                        class=INPUT_CLASS
+                        prop:value=move || first_day.get().format("%Y-%m-%d").to_string()
+                        on:change=move |ev| {
+                            if let Ok(date) = NaiveDate::parse_from_str(&event_target_value(&ev), "%Y-%m-%d") {
+                                first_day.set(date);
+                            }
+                        }
+                    />
+                    <span>{move || i18n.t("shifts-at")}</span>
+                    <input
+                        type="time"
+                        class=INPUT_CLASS
+                        prop:value=move || start_time.get().format("%H:%M").to_string()
+                        on:change=move |ev| {
+                            if let Ok(time) = NaiveTime::parse_from_str(&event_target_value(&ev), "%H:%M") {
+                                start_time.set(time);
+                            }
+                        }
+                    />
+                    <span>{move || i18n.t("shifts-for")}</span>
+                    <input
+                        type="number"
+                        min="1"
+                        max="24"
+                        class=INPUT_CLASS
+                        prop:value=move || duration_hours.get().to_string()
+                        on:change=move |ev| {
+                            duration_hours.set(event_target_value(&ev).parse().unwrap_or(8))
+                        }
+                    />
+                    <span>{move || i18n.t("shifts-hours")}</span>
+                    <input
+                        type="number"
+                        min="1"
+                        class=INPUT_CLASS
+                        prop:value=move || cycles.get().to_string()
+                        on:change=move |ev| cycles.set(event_target_value(&ev).parse().unwrap_or(1))
+                    />
+                    <span>{move || i18n.t("shifts-repetitions")}</span>
+                    <input
+                        type="text"
+                        class=INPUT_CLASS
+                        placeholder=move || i18n.t("shifts-timezone")
+                        prop:value=move || tz_id.get()
+                        on:change=move |ev| tz_id.set(event_target_value(&ev))
+                    />
+                    <button
+                        type="button"
+                        class="py-1 px-3 rounded-lg text-sm text-white bg-blue-600 hover:bg-blue-500"
+                        on:click=generate
+                    >
+                        {move || i18n.t("shifts-generate")}
+                    </button>
+                </div>
+
+                {move || {
+                    result
+                        .get()
+                        .map(|result| match result {
+                            Ok(verification) if verification.missing.is_empty()
+                                && verification.unexpected.is_empty() => {
+                                view! {
+                                    <p class="text-xs text-green-700 dark:text-green-500">
+                                        {i18n
+                                            .t_with(
+                                                "shifts-verified",
+                                                FluentArgs::from_iter([("count", verification.expected)]),
+                                            )}
+                                    </p>
+                                }
+                                    .into_view()
+                            }
+                            Ok(verification) => {
+                                let format_dates = |dates: &[chrono::NaiveDateTime]| {
+                                    dates
+                                        .iter()
+                                        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                };
+                                view! {
+                                    <p class="text-xs text-red-700 dark:text-red-500">
+                                        {
+                                            let mut args = FluentArgs::new();
+                                            args.set("count", verification.expected);
+                                            args.set("expanded", verification.expanded);
+                                            args.set("missing", format_dates(&verification.missing));
+                                            args.set("unexpected", format_dates(&verification.unexpected));
+                                            i18n.t_with("shifts-mismatch", args)
+                                        }
+                                    </p>
+                                }
+                                    .into_view()
+                            }
+                            Err(err) => {
+                                view! { <p class="text-xs text-red-700 dark:text-red-500">{err}</p> }
+                                    .into_view()
+                            }
+                        })
+                }}
+            </div>
+        </details>
+    }
+}
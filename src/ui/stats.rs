@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    stats::{format_duration, DurationStats},
+};
+
+use super::i18n::I18n;
+
+const WEEKDAYS: [&str; 7] = [
+    "weekday-mon",
+    "weekday-tue",
+    "weekday-wed",
+    "weekday-thu",
+    "weekday-fri",
+    "weekday-sat",
+    "weekday-sun",
+];
+
+#[component]
+pub fn DurationStatsPanel(#[prop(into)] stats: Signal<Option<DurationStats>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        {move || {
+            stats
+                .get()
+                .filter(|stats| stats.count > 1)
+                .map(|stats| {
+                    let max_hours = stats.hours_per_weekday.iter().copied().fold(0.0f64, f64::max);
+                    let busiest_weekday = stats.busiest_weekday();
+                    let summary = [
+                        (
+                            "stats-window",
+                            format!(
+                                "{} – {}",
+                                stats.window_start.format("%b %-d, %Y"),
+                                stats.window_end.format("%b %-d, %Y"),
+                            ),
+                        ),
+                        ("stats-scheduled", format_duration(stats.total)),
+                        ("stats-average", format_duration(stats.average)),
+                        (
+                            "stats-shortest-longest",
+                            format!(
+                                "{} / {}",
+                                format_duration(stats.shortest),
+                                format_duration(stats.longest),
+                            ),
+                        ),
+                        (
+                            "stats-busiest-day",
+                            {
+                                let mut args = FluentArgs::new();
+                                args.set("day", stats.busiest_day.0.format("%a %b %-d, %Y").to_string());
+                                args.set("duration", format_duration(stats.busiest_day.1));
+                                args.set("count", stats.busiest_day.2);
+                                i18n.t_with("stats-busiest-day-value", args)
+                            },
+                        ),
+                    ];
+
+                    view! {
+                        <div class="mb-6 grid gap-4 sm:grid-cols-2">
+                            <dl class="grid grid-cols-2 gap-x-4 gap-y-1 text-sm">
+                                {summary
+                                    .into_iter()
+                                    .map(|(label, value)| {
+                                        view! {
+                                            <dt class="text-gray-500 dark:text-neutral-500">{i18n.t(label)}</dt>
+                                            <dd class="text-gray-800 dark:text-neutral-200">{value}</dd>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </dl>
+                            <div>
+                                <div class="flex items-end gap-2 h-24">
+                                    {(0..7)
+                                        .map(|day| {
+                                            let hours = stats.hours_per_weekday[day];
+                                            let height = if max_hours > 0.0 {
+                                                (hours / max_hours * 100.0).round()
+                                            } else {
+                                                0.0
+                                            };
+                                            let color = if day == busiest_weekday && hours > 0.0 {
+                                                "bg-blue-600"
+                                            } else {
+                                                "bg-blue-300 dark:bg-blue-800"
+                                            };
+                                            view! {
+                                                <div
+                                                    class=format!("flex-1 rounded-t {}", color)
+                                                    style=format!("height: {}%", height)
+                                                    title={
+                                                        let mut args = FluentArgs::new();
+                                                        args.set("day", i18n.t(WEEKDAYS[day]));
+                                                        args.set("count", stats.per_weekday[day]);
+                                                        args.set("hours", format!("{:.1}", hours));
+                                                        i18n.t_with("stats-weekday", args)
+                                                    }
+                                                ></div>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                                <div class="flex gap-2 mt-1">
+                                    {(0..7)
+                                        .map(|day| {
+                                            view! {
+                                                <div class="flex-1 text-center text-xs text-gray-500 dark:text-neutral-500">
+                                                    {i18n.t(WEEKDAYS[day])}
+                                                    <br/>
+                                                    {stats.per_weekday[day]}
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    status::{self, Field, Kind, StatusEntry},
+};
+
+use super::i18n::I18n;
+
+const INPUT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+#[component]
+pub fn StatusPanel(
+    field: Field,
+    #[prop(into)] source: Signal<String>,
+    // iCalendar form of the source, which is the source itself unless `json`.
+    #[prop(into)] icalendar: Signal<String>,
+    #[prop(into)] json: Signal<bool>,
+    #[prop(into)] on_change: Callback<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let entries = create_memo(move |_| {
+        let mut entries = status::entries(&icalendar.get());
+        entries.retain(|entry| field.property(entry.kind).is_some());
+        entries
+    });
+    let selected: RwSignal<Vec<usize>> = create_rw_signal(Vec::new());
+    let target = create_rw_signal(field.choices()[0].to_string());
+    let message = create_rw_signal(String::new());
+
+    let apply = move |_| {
+        let target = target.get_untracked();
+        let chosen = entries.with_untracked(|entries| {
+            entries
+                .iter()
+                .filter(|entry| selected.with_untracked(|selected| selected.contains(&entry.line)))
+                .cloned()
+                .collect::<Vec<StatusEntry>>()
+        });
+        let applicable = chosen
+            .iter()
+            .filter(|entry| field.accepts(entry.kind, &target))
+            .count();
+        let result = if json.get_untracked() {
+            status::set_jscalendar(&source.get_untracked(), &chosen, field, &target)
+        } else {
+            Ok((
+                status::set_icalendar(&source.get_untracked(), &chosen, field, &target),
+                applicable,
+            ))
+        };
+        match result {
+            Ok((updated, changed)) => {
+                let skipped = chosen.len().saturating_sub(changed);
+                let mut args = FluentArgs::new();
+                args.set("count", changed);
+                args.set("value", target);
+                args.set("skipped", skipped);
+                message.set(i18n.t_with(
+                    if skipped == 0 {
+                        "status-set"
+                    } else {
+                        "status-set-skipped"
+                    },
+                    args,
+                ));
+                selected.set(Vec::new());
+                if changed > 0 {
+                    on_change.call(updated);
+                }
+            }
+            Err(err) => message.set(err),
+        }
+    };
+
+    let mapping_rows = move |kind: Kind| {
+        let property = field.property(kind).unwrap_or_default();
+        field
+            .values(kind)
+            .iter()
+            .map(move |(icalendar, jscalendar)| (format!("{}:{}", field.name(), icalendar), *jscalendar))
+            .chain(
+                field
+                    .jscalendar_only(kind)
+                    .iter()
+                    .map(move |jscalendar| (i18n.t("status-dropped"), *jscalendar)),
+            )
+            .map(move |(icalendar, jscalendar)| {
+                view! {
+                    <tr>
+                        <td class="py-1 pe-4">{kind.component()}</td>
+                        <td class="py-1 pe-4 font-mono">{icalendar}</td>
+                        <td class="py-1 font-mono">{format!("{}: \"{}\"", property, jscalendar)}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t(field.message_id())}
+            </summary>
+            <table class="mt-3 text-xs">
+                <thead>
+                    <tr class="text-start text-gray-500 dark:text-neutral-500">
+                        <th class="pe-4 font-medium text-start">{move || i18n.t("status-component")}</th>
+                        <th class="pe-4 font-medium text-start">"iCalendar"</th>
+                        <th class="font-medium text-start">"JSCalendar"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {Kind::ALL.iter().map(|kind| mapping_rows(*kind)).collect_view()}
+                </tbody>
+            </table>
+
+            <ul class="mt-4 divide-y divide-gray-200 dark:divide-neutral-700">
+                <For
+                    each=move || entries.get()
+                    key=|entry| entry.clone()
+                    children=move |entry| {
+                        let line = entry.line;
+                        let property = field.property(entry.kind).unwrap_or_default();
+                        let (class, mapped) = match (field.value(&entry), entry.mapped(field)) {
+                            (_, Some(mapped)) => {
+                                (
+                                    "text-gray-800 dark:text-neutral-200",
+                                    format!("{}: \"{}\"", property, mapped),
+                                )
+                            }
+                            (None, None) => ("text-gray-500 dark:text-neutral-500", i18n.t_with("status-missing", FluentArgs::from_iter([("property", property)]))),
+                            (Some(_), None) => ("text-red-700 dark:text-red-500", i18n.t("status-unknown")),
+                        };
+                        view! {
+                            <li class="py-1 flex items-center gap-3">
+                                <input
+                                    type="checkbox"
+                                    class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                                    prop:checked=move || selected.with(|selected| selected.contains(&line))
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        selected
+                                            .update(|selected| {
+                                                selected.retain(|item| *item != line);
+                                                if checked {
+                                                    selected.push(line);
+                                                }
+                                            });
+                                    }
+                                />
+                                <span class="grow text-gray-800 dark:text-neutral-200">
+                                    {if entry.summary.is_empty() { i18n.t("status-untitled") } else { entry.summary.clone() }}
+                                    {entry.recurrence_id.as_ref().map(|id| format!(" ({})", id))}
+                                </span>
+                                <span class="font-mono text-xs">
+                                    {field
+                                        .value(&entry)
+                                        .map(|value| format!("{}:{}", field.name(), value))
+                                        .unwrap_or_default()}
+                                </span>
+                                <span class=format!("text-xs {}", class)>{mapped}</span>
+                            </li>
+                        }
+                    }
+                />
+            </ul>
+
+            <div class="mt-3 flex flex-wrap items-center gap-2">
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                    on:click=move |_| {
+                        selected.set(entries.with(|entries| entries.iter().map(|entry| entry.line).collect()))
+                    }
+                >
+                    {move || i18n.t("status-select-all")}
+                </button>
+                <select class=INPUT_CLASS on:change=move |ev| target.set(event_target_value(&ev))>
+                    {field
+                        .choices()
+                        .into_iter()
+                        .map(|value| {
+                            view! {
+                                <option value=value selected=move || target.get() == value>
+                                    {value}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <button
+                    type="button"
+                    class="py-1 px-3 rounded-lg text-xs text-white bg-blue-600 hover:bg-blue-500 disabled:opacity-50"
+                    disabled=move || selected.with(Vec::is_empty)
+                    on:click=apply
+                >
+                    {move || i18n.t_with("status-set-field", FluentArgs::from_iter([("field", field.name())]))}
+                </button>
+                <span>{move || message.get()}</span>
+            </div>
+        </details>
+    }
+}
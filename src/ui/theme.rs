@@ -0,0 +1,129 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{fmt, str::FromStr};
+
+use codee::string::FromToStringCodec;
+use leptos::*;
+use leptos_use::{storage::use_local_storage, use_preferred_dark};
+
+use super::i18n::I18n;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Dark,
+    // Follows the operating system preference.
+    #[default]
+    System,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::System => "system",
+        }
+    }
+
+    // The id of its name in the translations.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            Theme::Light => "theme-light",
+            Theme::Dark => "theme-dark",
+            Theme::System => "theme-system",
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            "system" => Ok(Theme::System),
+            _ => Err(format!("Unknown theme: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ThemeSettings {
+    pub theme: Signal<Theme>,
+    set_theme: WriteSignal<Theme>,
+    prefers_dark: Signal<bool>,
+}
+
+impl ThemeSettings {
+    pub fn load() -> Self {
+        let (theme, set_theme, _) =
+            use_local_storage::<Theme, FromToStringCodec>("jmap-convert.theme");
+        ThemeSettings {
+            theme,
+            set_theme,
+            prefers_dark: use_preferred_dark(),
+        }
+    }
+
+    pub fn is_dark(&self) -> bool {
+        match self.theme.get() {
+            Theme::Light => false,
+            Theme::Dark => true,
+            Theme::System => self.prefers_dark.get(),
+        }
+    }
+
+    // Tailwind is configured for class-based dark mode, so the dark: variants
+    // apply below an element with the dark class. The body itself is styled
+    // directly since it cannot be its own ancestor.
+    pub fn body_class(&self) -> &'static str {
+        if self.is_dark() {
+            "dark bg-slate-900"
+        } else {
+            "bg-gray-100"
+        }
+    }
+}
+
+#[component]
+pub fn ThemeSelect() -> impl IntoView {
+    let settings = expect_context::<ThemeSettings>();
+    let i18n = expect_context::<I18n>();
+
+    view! {
+        <select
+            class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-xs dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+            title=move || i18n.t("theme-title")
+            on:change=move |ev| {
+                if let Ok(theme) = event_target_value(&ev).parse() {
+                    settings.set_theme.set(theme);
+                }
+            }
+        >
+            {Theme::ALL
+                .iter()
+                .map(|theme| {
+                    view! {
+                        <option value=theme.as_str() selected=move || settings.theme.get() == *theme>
+                            {move || i18n.t(theme.message_id())}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
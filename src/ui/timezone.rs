@@ -0,0 +1,52 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use calcard::common::timezone::Tz;
+use leptos::*;
+
+use super::i18n::I18n;
+
+// IANA time zone picker, where the empty value stands for floating time.
+#[component]
+pub fn TimezoneSelect(
+    #[prop(into)] selected: Signal<Tz>,
+    #[prop(into)] on_change: Callback<Tz>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let selected_name = create_memo(move |_| match selected.get() {
+        Tz::Tz(tz) => tz.name(),
+        _ => "",
+    });
+
+    view! {
+        <select
+            class="py-1 px-2 max-w-48 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+            title=move || i18n.t("timezone-title")
+            on:change=move |ev| {
+                on_change
+                    .call(
+                        event_target_value(&ev)
+                            .parse::<chrono_tz::Tz>()
+                            .map_or(Tz::Floating, Tz::Tz),
+                    )
+            }
+        >
+            <option value="" selected=move || selected_name.get().is_empty()>
+                {move || i18n.t("timezone-as-written")}
+            </option>
+            {chrono_tz::TZ_VARIANTS
+                .iter()
+                .map(|tz| {
+                    view! {
+                        <option value=tz.name() selected=move || selected_name.get() == tz.name()>
+                            {tz.name()}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
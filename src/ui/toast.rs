@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::time::Duration;
+
+use leptos::*;
+
+const TOAST_DURATION: Duration = Duration::from_millis(2500);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToastMessage {
+    pub text: String,
+    pub failed: bool,
+    // Tells a message from an identical later one, so an earlier timeout
+    // does not hide it.
+    id: u64,
+}
+
+// Short confirmations shown at the bottom of the page, one at a time.
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    pub message: Signal<Option<ToastMessage>>,
+    set_message: WriteSignal<Option<ToastMessage>>,
+    next_id: StoredValue<u64>,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        let (message, set_message) = create_signal(None);
+        Toasts {
+            message: message.into(),
+            set_message,
+            next_id: store_value(0),
+        }
+    }
+
+    pub fn show(&self, text: impl Into<String>, failed: bool) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.set_message.set(Some(ToastMessage {
+            text: text.into(),
+            failed,
+            id,
+        }));
+        let Toasts {
+            message,
+            set_message,
+            ..
+        } = *self;
+        set_timeout(
+            move || {
+                if message.with_untracked(|message| message.as_ref().map(|message| message.id))
+                    == Some(id)
+                {
+                    set_message.set(None);
+                }
+            },
+            TOAST_DURATION,
+        );
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[component]
+pub fn ToastStack() -> impl IntoView {
+    let toasts = expect_context::<Toasts>();
+
+    view! {
+        {move || {
+            toasts
+                .message
+                .get()
+                .map(|message| {
+                    let class = if message.failed {
+                        "bg-red-600 text-white"
+                    } else {
+                        "bg-gray-800 text-white dark:bg-neutral-200 dark:text-neutral-800"
+                    };
+                    view! {
+                        <div
+                            role="status"
+                            class=format!(
+                                "fixed bottom-4 end-4 z-50 py-2 px-4 rounded-lg shadow-lg text-sm {}",
+                                class,
+                            )
+                        >
+                            {message.text}
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::i18n::FluentArgs;
+
+use super::i18n::I18n;
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+// Added to the section the current step explains.
+const HIGHLIGHT_CLASSES: [&str; 3] = ["ring-2", "ring-blue-500", "ring-offset-4"];
+
+// Element ids of the sections the tour walks through.
+pub const SOURCE_TARGET: &str = "tour-source";
+pub const CONVERSION_TARGET: &str = "tour-conversion";
+pub const ROUNDTRIP_TARGET: &str = "tour-roundtrip";
+pub const EXPANSION_TARGET: &str = "tour-expansion";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TourStep {
+    Detection,
+    Conversion,
+    Roundtrip,
+    Expansion,
+}
+
+impl TourStep {
+    pub const ALL: [TourStep; 4] = [
+        TourStep::Detection,
+        TourStep::Conversion,
+        TourStep::Roundtrip,
+        TourStep::Expansion,
+    ];
+
+    pub fn target(&self) -> &'static str {
+        match self {
+            TourStep::Detection => SOURCE_TARGET,
+            TourStep::Conversion => CONVERSION_TARGET,
+            TourStep::Roundtrip => ROUNDTRIP_TARGET,
+            TourStep::Expansion => EXPANSION_TARGET,
+        }
+    }
+
+    pub fn title_id(&self) -> &'static str {
+        match self {
+            TourStep::Detection => "tour-detection",
+            TourStep::Conversion => "tour-conversion",
+            TourStep::Roundtrip => "tour-roundtrip",
+            TourStep::Expansion => "tour-expansion",
+        }
+    }
+
+    pub fn text_id(&self) -> &'static str {
+        match self {
+            TourStep::Detection => "tour-detection-text",
+            TourStep::Conversion => "tour-conversion-text",
+            TourStep::Roundtrip => "tour-roundtrip-text",
+            TourStep::Expansion => "tour-expansion-text",
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|step| step == self)
+            .unwrap_or_default()
+    }
+
+    pub fn next(&self) -> Option<TourStep> {
+        Self::ALL.get(self.position() + 1).copied()
+    }
+
+    pub fn previous(&self) -> Option<TourStep> {
+        Self::ALL.get(self.position().checked_sub(1)?).copied()
+    }
+}
+
+// Card walking through the sections of the page, highlighting the one the
+// current step explains. The tour ends when `step` is set to None.
+#[component]
+pub fn ConversionTour(step: RwSignal<Option<TourStep>>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let highlighted: StoredValue<Option<&'static str>> = store_value(None);
+    create_effect(move |_| {
+        let current = step.get().map(|step| step.target());
+        let document = document();
+        if let Some(element) = highlighted
+            .get_value()
+            .and_then(|target| document.get_element_by_id(target))
+        {
+            for class in HIGHLIGHT_CLASSES {
+                _ = element.class_list().remove_1(class);
+            }
+        }
+        // Sections that are not shown, such as the expansion of a sample
+        // without recurrences, are explained without a highlight.
+        if let Some(element) = current.and_then(|target| document.get_element_by_id(target)) {
+            for class in HIGHLIGHT_CLASSES {
+                _ = element.class_list().add_1(class);
+            }
+            element.scroll_into_view_with_bool(true);
+        }
+        highlighted.set_value(current);
+    });
+
+    view! {
+        {move || {
+            step.get()
+                .map(|current| {
+                    view! {
+                        <div
+                            role="dialog"
+                            class="fixed bottom-4 start-4 z-50 max-w-sm p-4 rounded-xl shadow-lg bg-white text-sm text-gray-600 dark:bg-neutral-800 dark:text-neutral-400"
+                        >
+                            <p class="text-xs text-gray-500 dark:text-neutral-500">
+                                {move || {
+                                    let mut args = FluentArgs::new();
+                                    args.set("step", current.position() + 1);
+                                    args.set("steps", TourStep::ALL.len());
+                                    i18n.t_with("tour-step", args)
+                                }}
+                            </p>
+                            <h3 class="mt-1 font-semibold text-gray-800 dark:text-neutral-200">
+                                {move || i18n.t(current.title_id())}
+                            </h3>
+                            <p class="mt-2">{move || i18n.t(current.text_id())}</p>
+                            <div class="mt-3 flex items-center justify-between gap-2">
+                                <button
+                                    type="button"
+                                    class="text-xs text-blue-600 hover:underline dark:text-blue-400"
+                                    on:click=move |_| step.set(None)
+                                >
+                                    {move || i18n.t("tour-end")}
+                                </button>
+                                <div class="flex gap-2">
+                                    {current
+                                        .previous()
+                                        .map(|previous| {
+                                            view! {
+                                                <button
+                                                    type="button"
+                                                    class=BUTTON_CLASS
+                                                    on:click=move |_| step.set(Some(previous))
+                                                >
+                                                    {move || i18n.t("tour-back")}
+                                                </button>
+                                            }
+                                        })}
+                                    <button
+                                        type="button"
+                                        class=BUTTON_CLASS
+                                        on:click=move |_| step.set(current.next())
+                                    >
+                                        {move || i18n.t(if current.next().is_some() { "tour-next" } else { "tour-finish" })}
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{i18n::FluentArgs, usage::UsageReport};
+
+use crate::ui::{export::save_file, i18n::I18n};
+
+const BUTTON_CLASS: &str = "py-1 px-3 rounded-lg text-sm text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600";
+// Rows shown before the table is expanded.
+const SHOWN_ROWS: usize = 25;
+
+#[component]
+pub fn PropertyUsagePanel(
+    // Converts every open document and tallies what they use.
+    #[prop(into)] analyze: Callback<(), UsageReport>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let report: RwSignal<Option<UsageReport>> = create_rw_signal(None);
+    let show_all = create_rw_signal(false);
+
+    let download = move |_| {
+        if let Some(report) = report.get_untracked() {
+            if let Err(err) = save_file(&report.to_string(), "text/csv", "property-usage.csv") {
+                log::warn!("Failed to download the usage report: {:?}", err);
+            }
+        }
+    };
+
+    view! {
+        <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("usage-title")}
+            </summary>
+            <p class="mt-3">
+                {move || {
+                    i18n.t_with("usage-description", FluentArgs::from_iter([("option", "--usage")]))
+                }}
+            </p>
+            <div class="mt-2 flex flex-wrap items-center gap-2">
+                <button
+                    type="button"
+                    class=BUTTON_CLASS
+                    on:click=move |_| {
+                        show_all.set(false);
+                        report.set(Some(analyze.call(())));
+                    }
+                >
+                    {move || i18n.t("usage-analyze")}
+                </button>
+                <Show when=move || report.with(|report| report.as_ref().is_some_and(|report| !report.is_empty()))>
+                    <button type="button" class=BUTTON_CLASS on:click=download>
+                        {move || i18n.t("usage-download")}
+                    </button>
+                </Show>
+            </div>
+            {move || {
+                report
+                    .get()
+                    .map(|report| {
+                        let usage = report.usage();
+                        let total = usage.len();
+                        let shown = if show_all.get() { total } else { total.min(SHOWN_ROWS) };
+                        view! {
+                            <p class="mt-3 font-medium text-gray-800 dark:text-neutral-200">
+                                {move || {
+                                    i18n.t_with(
+                                        "usage-summary",
+                                        FluentArgs::from_iter([
+                                            ("entries", report.entries),
+                                            ("failed", report.failed),
+                                            ("total", total),
+                                        ]),
+                                    )
+                                }}
+                            </p>
+                            <div class="mt-2 overflow-x-auto">
+                                <table class="min-w-full text-xs">
+                                    <thead>
+                                        <tr class="text-gray-500 dark:text-neutral-500">
+                                            <th class="pe-4 py-1 font-medium text-start">{move || i18n.t("usage-name")}</th>
+                                            <th class="pe-4 py-1 font-medium text-start">{move || i18n.t("usage-format")}</th>
+                                            <th class="pe-4 py-1 font-medium text-end">
+                                                {move || i18n.t("usage-occurrences")}
+                                            </th>
+                                            <th class="pe-4 py-1 font-medium text-end">{move || i18n.t("usage-entries")}</th>
+                                            <th class="py-1 font-medium text-end">{move || i18n.t("usage-findings")}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                        {usage
+                                            .into_iter()
+                                            .take(shown)
+                                            .map(|usage| {
+                                                view! {
+                                                    <tr>
+                                                        <td class="pe-4 py-1 font-mono break-all text-gray-800 dark:text-neutral-200">
+                                                            {usage.name.clone()}
+                                                        </td>
+                                                        <td class="pe-4 py-1 whitespace-nowrap">{usage.format.as_str()}</td>
+                                                        <td class="pe-4 py-1 text-end">{usage.occurrences}</td>
+                                                        <td class="pe-4 py-1 text-end">{usage.entries}</td>
+                                                        <td class=if usage.findings > 0 {
+                                                            "py-1 text-end font-semibold text-yellow-700 dark:text-yellow-500"
+                                                        } else {
+                                                            "py-1 text-end"
+                                                        }>{usage.findings}</td>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                            <Show when=move || { shown < total }>
+                                <button
+                                    type="button"
+                                    class="mt-2 text-xs text-blue-600 hover:underline dark:text-blue-500"
+                                    on:click=move |_| show_all.set(true)
+                                >
+                                    {move || i18n.t_with("usage-show-all", FluentArgs::from_iter([("count", total)]))}
+                                </button>
+                            </Show>
+                        }
+                    })
+            }}
+        </details>
+    }
+}
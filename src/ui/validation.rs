@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    findings::{Finding, Severity},
+    i18n::FluentArgs,
+    validation::validate,
+};
+
+use super::i18n::I18n;
+
+// Switches the results between the conversion and the validation report.
+#[component]
+pub fn ResultsTabs(validating: RwSignal<bool>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let tab = move |value: bool, label: &'static str| {
+        view! {
+            <button
+                type="button"
+                class=move || {
+                    format!(
+                        "py-1 px-3 text-sm {}",
+                        if validating.get() == value {
+                            "bg-gray-200 text-gray-800 dark:bg-neutral-600 dark:text-neutral-200"
+                        } else {
+                            "bg-white text-gray-600 hover:bg-gray-100 dark:bg-neutral-800 dark:text-neutral-400 dark:hover:bg-neutral-700"
+                        },
+                    )
+                }
+                on:click=move |_| validating.set(value)
+            >
+                {move || i18n.t(label)}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-4">
+            <div class="inline-flex rounded-lg overflow-hidden shadow-xs">
+                {tab(false, "validation-convert")}
+                {tab(true, "validation-validate")}
+            </div>
+        </div>
+    }
+}
+
+// Conformance of the source to RFC 5545, 6350, 8984 or 9553, whichever
+// defines its format.
+#[component]
+pub fn ValidationPanel(#[prop(into)] source: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let findings = create_memo(move |_| source.with(|source| validate(source)));
+    let count = move |severity: Severity| {
+        findings.with(|findings| {
+            findings
+                .iter()
+                .filter(|finding| finding.severity() == severity)
+                .count()
+        })
+    };
+
+    view! {
+        <div class="max-w-4xl px-4 sm:px-6 lg:px-8 mx-auto pb-10">
+            <div class="bg-white rounded-xl shadow-xs p-4 sm:p-7 dark:bg-neutral-800">
+                <div class="mb-4 flex flex-wrap items-center justify-between gap-2">
+                    <h2 class="text-xl font-bold text-gray-800 dark:text-neutral-200">
+                        {move || i18n.t("validation-title")}
+                    </h2>
+                    <p class="text-sm text-gray-600 dark:text-neutral-400">
+                        {move || {
+                            let mut args = FluentArgs::new();
+                            args.set("errors", count(Severity::Error));
+                            args.set("warnings", count(Severity::Warning));
+                            i18n.t_with("validation-counts", args)
+                        }}
+                    </p>
+                </div>
+                <Show
+                    when=move || findings.with(|findings| !findings.is_empty())
+                    fallback=move || {
+                        view! {
+                            <p class="text-sm text-green-700 dark:text-green-500">
+                                {move || i18n.t("validation-none")}
+                            </p>
+                        }
+                    }
+                >
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full text-sm text-gray-800 dark:text-neutral-200">
+                            <thead>
+                                <tr class="text-start text-xs text-gray-500 dark:text-neutral-500">
+                                    <th class="pb-2 pe-3 font-medium text-start">
+                                        {move || i18n.t("validation-severity")}
+                                    </th>
+                                    <th class="pb-2 pe-3 font-medium text-start">
+                                        {move || i18n.t("validation-code")}
+                                    </th>
+                                    <th class="pb-2 pe-3 font-medium text-start">
+                                        {move || i18n.t("validation-problem")}
+                                    </th>
+                                    <th class="pb-2 font-medium text-start">
+                                        {move || i18n.t("validation-location")}
+                                    </th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-200 dark:divide-neutral-700">
+                                {move || findings.get().into_iter().map(row).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+fn row(finding: Finding) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let severity = finding.severity();
+    let badge = match severity {
+        Severity::Error => "bg-red-100 text-red-800 dark:bg-red-800/30 dark:text-red-500",
+        Severity::Warning => {
+            "bg-yellow-100 text-yellow-800 dark:bg-yellow-800/30 dark:text-yellow-500"
+        }
+        Severity::Info => "bg-blue-100 text-blue-800 dark:bg-blue-800/30 dark:text-blue-400",
+    };
+    view! {
+        <tr class="align-top">
+            <td class="py-2 pe-3">
+                <span class=format!("py-0.5 px-2 rounded-full text-xs font-medium {}", badge)>
+                    {move || i18n.t(severity.message_id())}
+                </span>
+            </td>
+            <td class="py-2 pe-3 font-mono text-xs" title=finding.code.title()>
+                {finding.code.as_str()}
+            </td>
+            <td class="py-2 pe-3">{finding.message}</td>
+            <td class="py-2 font-mono text-xs text-gray-500 whitespace-nowrap dark:text-neutral-500">
+                {finding.location}
+            </td>
+        </tr>
+    }
+}
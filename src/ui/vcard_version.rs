@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use leptos::*;
+
+use jmap_convert::{
+    export::ExportContext,
+    i18n::FluentArgs,
+    vcard_version::{self, Version},
+    Format,
+};
+
+use crate::ui::{
+    export::{CopyButton, DownloadButton},
+    i18n::I18n,
+};
+
+const SELECT_CLASS: &str = "py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300";
+
+// The vCard rewritten for another vCard version, with what that version
+// cannot hold.
+#[component]
+pub fn VCardVersionPanel(#[prop(into)] vcard: Signal<String>) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let detected = create_memo(move |_| vcard.with(|vcard| vcard_version::detect(vcard)));
+    // Unset until a version is picked, then the other version than the
+    // source's is offered.
+    let picked: RwSignal<Option<Version>> = create_rw_signal(None);
+    let target = Signal::derive(move || {
+        picked.get().unwrap_or(match detected.get() {
+            Some(Version::V40) => Version::V30,
+            _ => Version::V40,
+        })
+    });
+    let migration =
+        create_memo(move |_| vcard.with(|vcard| vcard_version::migrate(vcard, target.get())));
+    let output = Signal::derive(move || migration.with(|migration| migration.output.clone()));
+
+    view! {
+        <Show when=move || !vcard.with(String::is_empty)>
+            <div class="mb-6">
+                <h3 class="text-sm font-semibold text-gray-800 dark:text-neutral-200">
+                    {move || i18n.t("vcard-version-title")}
+                </h3>
+                <div class="mt-2 flex flex-wrap items-center gap-2 text-sm text-gray-600 dark:text-neutral-400">
+                    <span>
+                        {move || match detected.get() {
+                            Some(version) => {
+                                i18n.t_with(
+                                    "vcard-version-detected",
+                                    FluentArgs::from_iter([("version", version.as_str())]),
+                                )
+                            }
+                            None => i18n.t("vcard-version-write"),
+                        }}
+                    </span>
+                    <select
+                        class=SELECT_CLASS
+                        aria-label=move || i18n.t("vcard-version-target")
+                        on:change=move |ev| picked.set(Version::parse(&event_target_value(&ev)))
+                    >
+                        {[Version::V30, Version::V40]
+                            .into_iter()
+                            .map(|version| {
+                                view! {
+                                    <option
+                                        value=version.as_str()
+                                        selected=move || target.get() == version
+                                    >
+                                        {format!("vCard {}", version.as_str())}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </div>
+                {move || {
+                    let unrepresentable = migration.with(|migration| migration.unrepresentable.clone());
+                    (!unrepresentable.is_empty())
+                        .then(|| {
+                            view! {
+                                <p class="mt-3 text-xs font-medium uppercase text-gray-500 dark:text-neutral-500">
+                                    {i18n
+                                        .t_with(
+                                            "vcard-version-unrepresentable",
+                                            FluentArgs::from_iter([("version", target.get().as_str())]),
+                                        )}
+                                </p>
+                                <ul class="text-sm">
+                                    {unrepresentable
+                                        .into_iter()
+                                        .map(|item| {
+                                            view! {
+                                                <li class="py-1 flex gap-3">
+                                                    <span class="w-32 shrink-0 text-gray-800 dark:text-neutral-200">
+                                                        {item.property}
+                                                    </span>
+                                                    <span class="w-16 shrink-0 text-gray-500 dark:text-neutral-500">
+                                                        {i18n.t_with("source-line", FluentArgs::from_iter([("line", item.line)]))}
+                                                    </span>
+                                                    <span class="text-yellow-700 dark:text-yellow-500">
+                                                        {item.reason}
+                                                    </span>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                        })
+                }}
+                <div class="relative mt-3 bg-gray-100 dark:bg-neutral-700 rounded-lg p-4 overflow-x-auto">
+                    <div class="absolute top-2 end-2 flex gap-1">
+                        <CopyButton
+                            payload=output
+                            context=Signal::derive(move || ExportContext {
+                                format: Format::VCard.as_str().to_string(),
+                                source_type: Format::VCard.as_str().to_string(),
+                                version: env!("CARGO_PKG_VERSION").to_string(),
+                                options: vec![
+                                    ("vcard-version".to_string(), target.get().as_str().to_string()),
+                                ],
+                                timestamp: String::new(),
+                            })
+                            json=false
+                        />
+                        <DownloadButton payload=output format=Format::VCard/>
+                    </div>
+                    <pre class="text-sm text-gray-800 dark:text-neutral-200 whitespace-pre">
+                        {move || output.get()}
+                    </pre>
+                </div>
+            </div>
+        </Show>
+    }
+}
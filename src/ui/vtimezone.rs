@@ -0,0 +1,128 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{DateTime, Datelike};
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    vtimezone::{self, format_offset},
+};
+
+use super::i18n::I18n;
+
+// Years of transitions listed, starting with the current one.
+const YEARS: i32 = 4;
+
+// The embedded VTIMEZONE definitions, what they were mapped to and where they
+// disagree with the IANA database. Shown only when the calendar has any.
+#[component]
+pub fn TimeZoneInspectorPanel(
+    #[prop(into)] icalendar: Signal<String>,
+    #[prop(into)] jscalendar: Signal<String>,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let year = DateTime::from_timestamp_millis(js_sys::Date::now() as i64)
+        .unwrap_or_default()
+        .year();
+    let reports = create_memo(move |_| {
+        icalendar.with(|icalendar| {
+            jscalendar.with(|jscalendar| vtimezone::inspect(icalendar, jscalendar, year, YEARS))
+        })
+    });
+
+    view! {
+        <Show when=move || reports.with(|reports| !reports.is_empty())>
+            <details class="mb-6 text-sm text-gray-600 dark:text-neutral-400">
+                <summary class="cursor-pointer font-semibold text-gray-800 dark:text-neutral-200">
+                    {move || {
+                        let (count, warnings) = reports
+                            .with(|reports| {
+                                (
+                                    reports.len(),
+                                    reports.iter().filter(|report| !report.warnings.is_empty()).count(),
+                                )
+                            });
+                        let mut args = FluentArgs::new();
+                        args.set("count", count);
+                        args.set("warnings", warnings);
+                        i18n.t_with("vtimezone-title", args)
+                    }}
+                </summary>
+                {move || {
+                    reports
+                        .get()
+                        .into_iter()
+                        .map(|report| {
+                            view! {
+                                <div class="mt-3">
+                                    <p class="font-medium text-gray-800 dark:text-neutral-200">
+                                        {report.tz_id}
+                                        <span class="ms-2 font-normal text-gray-500 dark:text-neutral-500">
+                                            {i18n.t_with("source-line", FluentArgs::from_iter([("line", report.line)]))}
+                                        </span>
+                                    </p>
+                                    <p>
+                                        {match (report.mapped, report.compared) {
+                                            (Some(mapped), _) => {
+                                                i18n.t_with("vtimezone-mapped", FluentArgs::from_iter([("tz", mapped)]))
+                                            }
+                                            (None, Some(compared)) => {
+                                                i18n.t_with("vtimezone-unused-resolves", FluentArgs::from_iter([("tz", compared)]))
+                                            }
+                                            (None, None) => i18n.t("vtimezone-unused"),
+                                        }}
+                                    </p>
+                                    {report
+                                        .warnings
+                                        .into_iter()
+                                        .map(|warning| {
+                                            view! {
+                                                <p class="mt-1 text-yellow-700 dark:text-yellow-500">{warning}</p>
+                                            }
+                                        })
+                                        .collect_view()}
+                                    <table class="mt-2 text-xs">
+                                        <tbody>
+                                            {report
+                                                .transitions
+                                                .into_iter()
+                                                .map(|transition| {
+                                                    view! {
+                                                        <tr>
+                                                            <td class="pe-4 py-0.5 text-gray-800 dark:text-neutral-200">
+                                                                {i18n.t(transition.kind.message_id())}
+                                                            </td>
+                                                            <td class="pe-4 py-0.5 font-mono">
+                                                                {format!(
+                                                                    "{} → {}",
+                                                                    transition.local_before().format("%Y-%m-%d %H:%M"),
+                                                                    transition.local_after().format("%H:%M"),
+                                                                )}
+                                                            </td>
+                                                            <td class="pe-4 py-0.5">
+                                                                {format!(
+                                                                    "{} → {}",
+                                                                    format_offset(transition.offset_from),
+                                                                    format_offset(transition.offset_to),
+                                                                )}
+                                                            </td>
+                                                            <td class="py-0.5">{transition.name}</td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </details>
+        </Show>
+    }
+}
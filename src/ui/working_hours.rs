@@ -0,0 +1,191 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::NaiveTime;
+use leptos::*;
+
+use jmap_convert::{
+    i18n::FluentArgs,
+    working_hours::{Holidays, OverlayMode, WorkingHours},
+};
+
+use crate::{
+    fetch,
+    ui::{i18n::I18n, settings::FetchSettings},
+};
+
+const WEEKDAYS: [&str; 7] = [
+    "weekday-mon",
+    "weekday-tue",
+    "weekday-wed",
+    "weekday-thu",
+    "weekday-fri",
+    "weekday-sat",
+    "weekday-sun",
+];
+
+#[component]
+pub fn WorkingHoursPanel(
+    working_hours: RwSignal<WorkingHours>,
+    holidays: RwSignal<Holidays>,
+    overlay_mode: RwSignal<OverlayMode>,
+    #[prop(into)] on_change: Callback<()>,
+) -> impl IntoView {
+    let fetch_settings = expect_context::<FetchSettings>();
+    let i18n = expect_context::<I18n>();
+    let holiday_source = create_rw_signal(String::new());
+    let holiday_url = create_rw_signal(String::new());
+    let holiday_error = create_rw_signal(String::new());
+    // Holidays restored from elsewhere, such as a session bundle, show their calendar.
+    create_effect(move |_| {
+        let source = holidays.with(|holidays| holidays.source().to_string());
+        if !source.is_empty() {
+            holiday_source.set(source);
+        }
+    });
+
+    let load_holidays = move |source: String| {
+        match Holidays::parse(&source) {
+            Ok(parsed) => {
+                holiday_error.set(String::new());
+                holidays.set(parsed);
+            }
+            Err(err) => {
+                holiday_error.set(err);
+                holidays.set(Holidays::default());
+            }
+        }
+        holiday_source.set(source);
+        on_change.call(());
+    };
+    let fetch_holidays = move |_| {
+        let options = fetch_settings.options();
+        spawn_local(async move {
+            match fetch::fetch_text(&holiday_url.get_untracked(), &options).await {
+                Ok(resource) => load_holidays(resource.body),
+                Err(err) => holiday_error.set(err),
+            }
+        });
+    };
+
+    let set_time = move |value: String, is_start: bool| {
+        if let Ok(time) = NaiveTime::parse_from_str(&value, "%H:%M") {
+            working_hours.update(|hours| {
+                if is_start {
+                    hours.start = time;
+                } else {
+                    hours.end = time;
+                }
+            });
+            on_change.call(());
+        }
+    };
+
+    view! {
+        <details class="mb-4 text-sm text-gray-600 dark:text-neutral-400">
+            <summary class="cursor-pointer font-medium text-gray-800 dark:text-neutral-200">
+                {move || i18n.t("working-hours-title")}
+            </summary>
+            <div class="mt-3 grid gap-4 sm:grid-cols-2">
+                <div>
+                    <div class="flex items-center gap-2 mb-2">
+                        <input
+                            type="time"
+                            class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                            prop:value=move || working_hours.get().start.format("%H:%M").to_string()
+                            on:change=move |ev| set_time(event_target_value(&ev), true)
+                        />
+                        <span>{move || i18n.t("working-hours-to")}</span>
+                        <input
+                            type="time"
+                            class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                            prop:value=move || working_hours.get().end.format("%H:%M").to_string()
+                            on:change=move |ev| set_time(event_target_value(&ev), false)
+                        />
+                    </div>
+                    <div class="flex flex-wrap gap-3 mb-2">
+                        {WEEKDAYS
+                            .iter()
+                            .enumerate()
+                            .map(|(day, message_id)| {
+                                view! {
+                                    <label class="inline-flex items-center gap-1">
+                                        <input
+                                            type="checkbox"
+                                            class="rounded border-gray-200 dark:bg-neutral-700 dark:border-neutral-700"
+                                            prop:checked=move || working_hours.get().days[day]
+                                            on:change=move |ev| {
+                                                let checked = event_target_checked(&ev);
+                                                working_hours.update(|hours| hours.days[day] = checked);
+                                                on_change.call(());
+                                            }
+                                        />
+                                        {move || i18n.t(message_id)}
+                                    </label>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <select
+                        class="py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-sm dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                        on:change=move |ev| overlay_mode.set(OverlayMode::parse(&event_target_value(&ev)))
+                    >
+                        <option value="annotate" selected=move || overlay_mode.get() == OverlayMode::Annotate>
+                            {move || i18n.t("overlay-annotate")}
+                        </option>
+                        <option value="hide" selected=move || overlay_mode.get() == OverlayMode::HideFlagged>
+                            {move || i18n.t("overlay-hide")}
+                        </option>
+                        <option value="only" selected=move || overlay_mode.get() == OverlayMode::OnlyFlagged>
+                            {move || i18n.t("overlay-only")}
+                        </option>
+                    </select>
+                </div>
+                <div>
+                    <textarea
+                        class="p-2 block w-full bg-gray-100 border-gray-200 rounded-lg text-xs font-mono dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                        rows="4"
+                        placeholder=move || i18n.t("holidays-paste")
+                        prop:value=move || holiday_source.get()
+                        on:change=move |ev| load_holidays(event_target_value(&ev))
+                    >
+                    </textarea>
+                    <div class="mt-2 flex items-center gap-2">
+                        <input
+                            type="url"
+                            class="grow py-1 px-2 bg-gray-100 border-gray-200 rounded-lg text-xs dark:bg-neutral-700 dark:border-neutral-700 dark:text-neutral-300"
+                            placeholder=move || i18n.t("holidays-url")
+                            prop:value=move || holiday_url.get()
+                            on:change=move |ev| holiday_url.set(event_target_value(&ev))
+                        />
+                        <button
+                            type="button"
+                            class="py-1 px-3 rounded-lg text-xs text-gray-800 bg-gray-100 hover:bg-gray-200 dark:text-neutral-200 dark:bg-neutral-700 dark:hover:bg-neutral-600"
+                            on:click=fetch_holidays
+                        >
+                            {move || i18n.t("holidays-load")}
+                        </button>
+                    </div>
+                    <p class="mt-1 text-xs">
+                        {move || {
+                            let error = holiday_error.get();
+                            if !error.is_empty() {
+                                error
+                            } else if holidays.with(|holidays| holidays.is_empty()) {
+                                i18n.t("holidays-none")
+                            } else {
+                                i18n.t_with(
+                                    "holidays-loaded",
+                                    FluentArgs::from_iter([("count", holidays.with(|holidays| holidays.len()))]),
+                                )
+                            }
+                        }}
+                    </p>
+                </div>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use codee::string::FromToStringCodec;
+use leptos::*;
+use leptos_use::storage::{use_local_storage_with_options, UseStorageOptions};
+
+use super::i18n::I18n;
+
+const MIN_PANE: f64 = 15.0;
+const MAX_PANE: f64 = 85.0;
+const KEYBOARD_STEP: f64 = 2.0;
+// Screens at least this wide start in the split layout.
+const WIDE_SCREEN: f64 = 1280.0;
+
+const PANE_CLASS: &str =
+    "min-w-0 min-h-0 flex flex-col bg-white rounded-xl shadow-xs p-3 dark:bg-neutral-800";
+const PANE_TITLE_CLASS: &str =
+    "mb-2 text-xs font-semibold uppercase text-gray-500 dark:text-neutral-500";
+
+// Split screen layout, with pane sizes stored as percentages.
+#[derive(Clone, Copy)]
+pub struct WorkspaceLayout {
+    pub enabled: Signal<bool>,
+    pub set_enabled: WriteSignal<bool>,
+    columns: Signal<f64>,
+    set_columns: WriteSignal<f64>,
+    rows: Signal<f64>,
+    set_rows: WriteSignal<f64>,
+    bottom_columns: Signal<f64>,
+    set_bottom_columns: WriteSignal<f64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Splitter {
+    Columns,
+    Rows,
+    BottomColumns,
+}
+
+impl WorkspaceLayout {
+    pub fn load() -> Self {
+        let wide = window()
+            .inner_width()
+            .ok()
+            .and_then(|width| width.as_f64())
+            .is_some_and(|width| width >= WIDE_SCREEN);
+        let (enabled, set_enabled, _) = use_local_storage_with_options::<bool, FromToStringCodec>(
+            "jmap-convert.workspace",
+            UseStorageOptions::default().initial_value(wide),
+        );
+        let ratio = |key: &str, initial: f64| {
+            let (value, set_value, _) = use_local_storage_with_options::<f64, FromToStringCodec>(
+                key,
+                UseStorageOptions::default().initial_value(initial),
+            );
+            (value, set_value)
+        };
+        let (columns, set_columns) = ratio("jmap-convert.workspace-columns", 50.0);
+        let (rows, set_rows) = ratio("jmap-convert.workspace-rows", 65.0);
+        let (bottom_columns, set_bottom_columns) =
+            ratio("jmap-convert.workspace-bottom-columns", 50.0);
+        WorkspaceLayout {
+            enabled,
+            set_enabled,
+            columns,
+            set_columns,
+            rows,
+            set_rows,
+            bottom_columns,
+            set_bottom_columns,
+        }
+    }
+
+    fn get(&self, splitter: Splitter) -> f64 {
+        match splitter {
+            Splitter::Columns => self.columns.get(),
+            Splitter::Rows => self.rows.get(),
+            Splitter::BottomColumns => self.bottom_columns.get(),
+        }
+        .clamp(MIN_PANE, MAX_PANE)
+    }
+
+    fn set(&self, splitter: Splitter, value: f64) {
+        let value = value.clamp(MIN_PANE, MAX_PANE);
+        match splitter {
+            Splitter::Columns => self.set_columns.set(value),
+            Splitter::Rows => self.set_rows.set(value),
+            Splitter::BottomColumns => self.set_bottom_columns.set(value),
+        }
+    }
+}
+
+#[component]
+pub fn Workspace(
+    layout: WorkspaceLayout,
+    #[prop(into)] source: ViewFn,
+    #[prop(into)] output: ViewFn,
+    #[prop(into)] roundtrip: ViewFn,
+    #[prop(into)] warnings: ViewFn,
+) -> impl IntoView {
+    let i18n = expect_context::<I18n>();
+    let dragging: RwSignal<Option<Splitter>> = create_rw_signal(None);
+    let container = create_node_ref::<html::Div>();
+    let bottom_row = create_node_ref::<html::Div>();
+
+    let on_move = window_event_listener(ev::pointermove, move |ev| {
+        let Some(splitter) = dragging.get_untracked() else {
+            return;
+        };
+        let element = match splitter {
+            Splitter::Columns | Splitter::Rows => container.get_untracked(),
+            Splitter::BottomColumns => bottom_row.get_untracked(),
+        };
+        let Some(element) = element else {
+            return;
+        };
+        let rect = element.get_bounding_client_rect();
+        let value = match splitter {
+            Splitter::Columns | Splitter::BottomColumns if rect.width() > 0.0 => {
+                (ev.client_x() as f64 - rect.left()) / rect.width() * 100.0
+            }
+            Splitter::Rows if rect.height() > 0.0 => {
+                (ev.client_y() as f64 - rect.top()) / rect.height() * 100.0
+            }
+            _ => return,
+        };
+        layout.set(splitter, value);
+    });
+    let on_up = window_event_listener(ev::pointerup, move |_| dragging.set(None));
+    on_cleanup(move || {
+        on_move.remove();
+        on_up.remove();
+    });
+
+    let handle = move |splitter: Splitter| {
+        let vertical = splitter != Splitter::Rows;
+        view! {
+            <div
+                role="separator"
+                tabindex="0"
+                aria-orientation=if vertical { "vertical" } else { "horizontal" }
+                aria-valuenow=move || layout.get(splitter).round()
+                aria-valuemin=MIN_PANE
+                aria-valuemax=MAX_PANE
+                class=move || {
+                    format!(
+                        "shrink-0 rounded-full hover:bg-blue-400 focus:outline-hidden focus:bg-blue-400 {} {}",
+                        if vertical { "w-1.5 mx-1 cursor-col-resize" } else { "h-1.5 my-1 cursor-row-resize" },
+                        if dragging.get() == Some(splitter) { "bg-blue-500" } else { "bg-gray-200 dark:bg-neutral-700" },
+                    )
+                }
+                on:pointerdown=move |ev| {
+                    ev.prevent_default();
+                    dragging.set(Some(splitter));
+                }
+                on:keydown=move |ev| {
+                    let step = match (vertical, ev.key().as_str()) {
+                        (true, "ArrowLeft") | (false, "ArrowUp") => -KEYBOARD_STEP,
+                        (true, "ArrowRight") | (false, "ArrowDown") => KEYBOARD_STEP,
+                        _ => return,
+                    };
+                    ev.prevent_default();
+                    layout.set(splitter, layout.get(splitter) + step);
+                }
+            ></div>
+        }
+    };
+    let pane = move |title: &'static str, content: ViewFn| {
+        view! {
+            <h3 class=PANE_TITLE_CLASS>{move || i18n.t(title)}</h3>
+            <div class="grow min-h-0 overflow-auto">{content.run()}</div>
+        }
+    };
+
+    view! {
+        <div
+            node_ref=container
+            class=move || {
+                format!(
+                    "flex flex-col h-[85vh] px-4 sm:px-6 lg:px-8 pb-6 {}",
+                    if dragging.get().is_some() { "select-none" } else { "" },
+                )
+            }
+        >
+            <div class="flex min-h-0" style=move || format!("height: {}%", layout.get(Splitter::Rows))>
+                <div class=PANE_CLASS style=move || format!("width: {}%", layout.get(Splitter::Columns))>
+                    {pane("workspace-source", source)}
+                </div>
+                {handle(Splitter::Columns)}
+                <div class=format!("{} flex-1", PANE_CLASS)>{pane("workspace-converted", output)}</div>
+            </div>
+            {handle(Splitter::Rows)}
+            <div node_ref=bottom_row class="flex flex-1 min-h-0">
+                <div
+                    class=PANE_CLASS
+                    style=move || format!("width: {}%", layout.get(Splitter::BottomColumns))
+                >
+                    {pane("workspace-roundtrip", roundtrip)}
+                </div>
+                {handle(Splitter::BottomColumns)}
+                <div class=format!("{} flex-1", PANE_CLASS)>{pane("workspace-warnings", warnings)}</div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+
+use crate::{
+    contentline,
+    expected::{escape_pointer, is_id_map},
+    findings::Finding,
+    Converter, EntryResult, Format,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UsageKind {
+    Property,
+    Parameter,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyUsage {
+    pub format: Format,
+    pub kind: UsageKind,
+    // Component and property, such as "VEVENT DTSTART;TZID", or a JSON
+    // pointer with map ids and array indices written as "*".
+    pub name: String,
+    pub occurrences: usize,
+    // Entries using it at least once.
+    pub entries: usize,
+    // Findings reported at one of its occurrences.
+    pub findings: usize,
+}
+
+// Which properties and parameters a set of inputs uses and how often each
+// drew a finding, so the conversion paths a corpus depends on get the most
+// testing attention.
+#[derive(Clone, Debug, Default)]
+pub struct UsageReport {
+    pub entries: usize,
+    pub failed: usize,
+    // Keyed by format name, kind and name.
+    usage: BTreeMap<(&'static str, UsageKind, String), PropertyUsage>,
+}
+
+impl UsageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageKind::Property => "property",
+            UsageKind::Parameter => "parameter",
+        }
+    }
+}
+
+impl fmt::Display for UsageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl UsageReport {
+    pub fn add(&mut self, entry: &EntryResult) {
+        self.entries += 1;
+        let (format, findings) = match &entry.result {
+            Ok(result) => (result.format, result.findings.as_slice()),
+            Err(finding) => {
+                self.failed += 1;
+                let Some(format) = Converter.detect_format(&entry.source).ok() else {
+                    return;
+                };
+                (format, std::slice::from_ref(finding))
+            }
+        };
+
+        // Each name with the line or pointer of every occurrence.
+        let mut seen: BTreeMap<(UsageKind, String), Vec<String>> = BTreeMap::new();
+        if format.is_json() {
+            if let Ok(value) = serde_json::from_str::<Value>(&entry.source) {
+                collect_members("", "", &value, &mut seen);
+            }
+        } else {
+            for component in contentline::components(&entry.source) {
+                let kind = component
+                    .path
+                    .rsplit('/')
+                    .next()
+                    .and_then(|name| name.split('[').next())
+                    .unwrap_or_default()
+                    .to_string();
+                for property in &component.properties {
+                    let name = format!("{} {}", kind, property.name.to_ascii_uppercase());
+                    let line = format!("line {}", property.line);
+                    for (param, _) in &property.params {
+                        seen.entry((
+                            UsageKind::Parameter,
+                            format!("{};{}", name, param.to_ascii_uppercase()),
+                        ))
+                        .or_default()
+                        .push(line.clone());
+                    }
+                    seen.entry((UsageKind::Property, name))
+                        .or_default()
+                        .push(line);
+                }
+            }
+        }
+
+        let mut flagged: BTreeMap<(UsageKind, String), usize> = BTreeMap::new();
+        for finding in findings {
+            if let Some(key) = attribute(finding, &seen) {
+                *flagged.entry(key).or_default() += 1;
+            }
+        }
+        for ((kind, name), locations) in seen {
+            let findings = flagged
+                .get(&(kind, name.clone()))
+                .copied()
+                .unwrap_or_default();
+            let usage = self
+                .usage
+                .entry((format.as_str(), kind, name.clone()))
+                .or_insert_with(|| PropertyUsage {
+                    format,
+                    kind,
+                    name,
+                    occurrences: 0,
+                    entries: 0,
+                    findings: 0,
+                });
+            usage.occurrences += locations.len();
+            usage.entries += 1;
+            usage.findings += findings;
+        }
+    }
+
+    // Most findings first, then the most used.
+    pub fn usage(&self) -> Vec<&PropertyUsage> {
+        let mut usage = self.usage.values().collect::<Vec<_>>();
+        usage.sort_by(|a, b| {
+            b.findings
+                .cmp(&a.findings)
+                .then(b.occurrences.cmp(&a.occurrences))
+                .then(a.name.cmp(&b.name))
+        });
+        usage
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.usage.is_empty()
+    }
+}
+
+// Written as CSV, one row per property or parameter.
+impl fmt::Display for UsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "format,kind,name,occurrences,entries,findings")?;
+        for usage in self.usage() {
+            let name = if usage.name.contains([',', '"']) {
+                format!("\"{}\"", usage.name.replace('"', "\"\""))
+            } else {
+                usage.name.clone()
+            };
+            writeln!(
+                f,
+                "{},{},{},{},{},{}",
+                usage.format.as_str(),
+                usage.kind.as_str(),
+                name,
+                usage.occurrences,
+                usage.entries,
+                usage.findings
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// `path` is the JSON pointer of `value`, `name` the same pointer with map ids
+// and array indices replaced.
+fn collect_members(
+    path: &str,
+    name: &str,
+    value: &Value,
+    seen: &mut BTreeMap<(UsageKind, String), Vec<String>>,
+) {
+    match value {
+        Value::Object(map) => {
+            let generic = is_id_map(map);
+            for (key, value) in map {
+                let path = format!("{}/{}", path, escape_pointer(key));
+                let name = if generic {
+                    format!("{}/*", name)
+                } else {
+                    format!("{}/{}", name, escape_pointer(key))
+                };
+                if !generic {
+                    seen.entry((UsageKind::Property, name.clone()))
+                        .or_default()
+                        .push(path.clone());
+                }
+                collect_members(&path, &name, value, seen);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                collect_members(
+                    &format!("{}/{}", path, index),
+                    &format!("{}/*", name),
+                    value,
+                    seen,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+// The usage a finding refers to: the property on its line, or the deepest
+// member its pointer passes through.
+fn attribute(
+    finding: &Finding,
+    seen: &BTreeMap<(UsageKind, String), Vec<String>>,
+) -> Option<(UsageKind, String)> {
+    let location = finding.location.as_deref()?;
+    // Entries of a multi-entry file are prefixed with their number.
+    let location = location.rsplit(", ").next().unwrap_or(location).trim();
+    let matches = |location: &str| {
+        seen.iter()
+            .find(|((kind, _), locations)| {
+                *kind == UsageKind::Property && locations.iter().any(|other| other == location)
+            })
+            .map(|(key, _)| key.clone())
+    };
+    if location.starts_with('/') {
+        let mut pointer = location;
+        loop {
+            if let Some(key) = matches(pointer) {
+                return Some(key);
+            }
+            pointer = &pointer[..pointer.rfind('/').filter(|index| *index > 0)?];
+        }
+    } else {
+        matches(location)
+    }
+}
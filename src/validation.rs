@@ -0,0 +1,1003 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use chrono::{NaiveDateTime, NaiveTime};
+use serde_json::{Map, Value};
+
+use crate::{
+    contentline::{self, ContentLine},
+    expected::escape_pointer,
+    findings::{Code, Finding},
+};
+
+// Required properties and those allowed at most once, per component.
+struct ComponentRule {
+    name: &'static str,
+    required: &'static [&'static str],
+    once: &'static [&'static str],
+}
+
+// RFC 5545 section 3.6.
+const ICALENDAR_RULES: &[ComponentRule] = &[
+    ComponentRule {
+        name: "VCALENDAR",
+        required: &["PRODID", "VERSION"],
+        once: &["PRODID", "VERSION", "CALSCALE", "METHOD"],
+    },
+    ComponentRule {
+        name: "VEVENT",
+        required: &["UID", "DTSTAMP"],
+        once: &[
+            "DTSTAMP",
+            "UID",
+            "DTSTART",
+            "CLASS",
+            "CREATED",
+            "DESCRIPTION",
+            "GEO",
+            "LAST-MODIFIED",
+            "LOCATION",
+            "ORGANIZER",
+            "PRIORITY",
+            "SEQUENCE",
+            "STATUS",
+            "SUMMARY",
+            "TRANSP",
+            "URL",
+            "RECURRENCE-ID",
+            "DTEND",
+            "DURATION",
+        ],
+    },
+    ComponentRule {
+        name: "VTODO",
+        required: &["UID", "DTSTAMP"],
+        once: &[
+            "DTSTAMP",
+            "UID",
+            "CLASS",
+            "COMPLETED",
+            "CREATED",
+            "DESCRIPTION",
+            "DTSTART",
+            "GEO",
+            "LAST-MODIFIED",
+            "LOCATION",
+            "ORGANIZER",
+            "PERCENT-COMPLETE",
+            "PRIORITY",
+            "RECURRENCE-ID",
+            "SEQUENCE",
+            "STATUS",
+            "SUMMARY",
+            "URL",
+            "DUE",
+            "DURATION",
+        ],
+    },
+    ComponentRule {
+        name: "VJOURNAL",
+        required: &["UID", "DTSTAMP"],
+        once: &[
+            "DTSTAMP",
+            "UID",
+            "CLASS",
+            "CREATED",
+            "DTSTART",
+            "LAST-MODIFIED",
+            "ORGANIZER",
+            "RECURRENCE-ID",
+            "SEQUENCE",
+            "STATUS",
+            "SUMMARY",
+            "URL",
+        ],
+    },
+    ComponentRule {
+        name: "VFREEBUSY",
+        required: &["UID", "DTSTAMP"],
+        once: &[
+            "DTSTAMP",
+            "UID",
+            "CONTACT",
+            "DTSTART",
+            "DTEND",
+            "ORGANIZER",
+            "URL",
+        ],
+    },
+    ComponentRule {
+        name: "VTIMEZONE",
+        required: &["TZID"],
+        once: &["TZID", "LAST-MODIFIED", "TZURL"],
+    },
+    ComponentRule {
+        name: "STANDARD",
+        required: &["DTSTART", "TZOFFSETTO", "TZOFFSETFROM"],
+        once: &["DTSTART", "TZOFFSETTO", "TZOFFSETFROM"],
+    },
+    ComponentRule {
+        name: "DAYLIGHT",
+        required: &["DTSTART", "TZOFFSETTO", "TZOFFSETFROM"],
+        once: &["DTSTART", "TZOFFSETTO", "TZOFFSETFROM"],
+    },
+    ComponentRule {
+        name: "VALARM",
+        required: &["ACTION", "TRIGGER"],
+        once: &["ACTION", "TRIGGER", "DURATION", "REPEAT"],
+    },
+];
+
+// RFC 6350 section 6.
+const VCARD_RULE: ComponentRule = ComponentRule {
+    name: "VCARD",
+    required: &["VERSION", "FN"],
+    once: &[
+        "VERSION",
+        "N",
+        "BDAY",
+        "ANNIVERSARY",
+        "GENDER",
+        "PRODID",
+        "REV",
+        "UID",
+        "KIND",
+    ],
+};
+
+// RFC 5545 and 7986, plus RFC 9253.
+const ICALENDAR_PARAMETERS: &[&str] = &[
+    "ALTREP",
+    "CN",
+    "CUTYPE",
+    "DELEGATED-FROM",
+    "DELEGATED-TO",
+    "DIR",
+    "DISPLAY",
+    "EMAIL",
+    "ENCODING",
+    "FBTYPE",
+    "FEATURE",
+    "FMTTYPE",
+    "LABEL",
+    "LANGUAGE",
+    "LINKREL",
+    "MEMBER",
+    "ORDER",
+    "PARTSTAT",
+    "RANGE",
+    "RELATED",
+    "RELTYPE",
+    "ROLE",
+    "RSVP",
+    "SCHEMA",
+    "DERIVED",
+    "SENT-BY",
+    "TZID",
+    "VALUE",
+];
+
+// RFC 6350, the vCard 3.0 parameters still in use, and RFC 9554.
+const VCARD_PARAMETERS: &[&str] = &[
+    "ALTID",
+    "AUTHOR",
+    "AUTHOR-NAME",
+    "CALSCALE",
+    "CC",
+    "CHARSET",
+    "CREATED",
+    "DERIVED",
+    "ENCODING",
+    "GEO",
+    "INDEX",
+    "JSPTR",
+    "LABEL",
+    "LANGUAGE",
+    "LEVEL",
+    "MEDIATYPE",
+    "PHONETIC",
+    "PID",
+    "PREF",
+    "PROP-ID",
+    "SCRIPT",
+    "SERVICE-TYPE",
+    "SORT-AS",
+    "TYPE",
+    "TZ",
+    "USERNAME",
+    "VALUE",
+];
+
+pub const VALUE_TYPES: &[&str] = &[
+    "BINARY",
+    "BOOLEAN",
+    "CAL-ADDRESS",
+    "DATE",
+    "DATE-AND-OR-TIME",
+    "DATE-TIME",
+    "DURATION",
+    "FLOAT",
+    "INTEGER",
+    "LANGUAGE-TAG",
+    "PERIOD",
+    "RECUR",
+    "TEXT",
+    "TIME",
+    "TIMESTAMP",
+    "URI",
+    "UTC-OFFSET",
+];
+
+const WEEKDAYS: &[&str] = &["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+const FREQUENCIES: &[&str] = &[
+    "SECONDLY", "MINUTELY", "HOURLY", "DAILY", "WEEKLY", "MONTHLY", "YEARLY",
+];
+
+struct Component {
+    name: String,
+    line: usize,
+    properties: Vec<ContentLine>,
+    children: Vec<String>,
+}
+
+// Checks a document against the RFC that defines its format: RFC 5545 for
+// iCalendar, RFC 6350 for vCard, RFC 8984 for JSCalendar and RFC 9553 for
+// JSContact. Unlike the conversion, which accepts what it can read, this
+// reports missing required properties, values of the wrong type, unknown
+// parameters and malformed recurrence rules. Works on the text so every
+// finding points at the line or JSON pointer it was written at.
+pub fn validate(source: &str) -> Vec<Finding> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with("BEGIN:") {
+        validate_text(source)
+    } else {
+        match serde_json::from_str::<Value>(trimmed) {
+            Ok(Value::Array(values)) => values
+                .iter()
+                .enumerate()
+                .flat_map(|(index, value)| validate_json(&format!("/{}", index), value))
+                .collect(),
+            Ok(value) => validate_json("", &value),
+            Err(err) => vec![Finding::new(
+                Code::UnrecognizedFormat,
+                format!("Failed to parse the JSON: {}", err),
+            )],
+        }
+    }
+}
+
+fn validate_text(source: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let lines = contentline::parse(source);
+    let scheduling = lines.iter().any(|line| line.name == "METHOD");
+    let mut stack: Vec<Component> = Vec::new();
+    for line in lines {
+        match line.name.as_str() {
+            "BEGIN" => stack.push(Component {
+                name: line.value.trim().to_ascii_uppercase(),
+                line: line.line,
+                properties: Vec::new(),
+                children: Vec::new(),
+            }),
+            "END" => {
+                if let Some(component) = stack.pop() {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(component.name.clone());
+                    }
+                    check_component(&component, scheduling, &mut findings);
+                }
+            }
+            _ => {
+                if let Some(component) = stack.last_mut() {
+                    component.properties.push(line);
+                }
+            }
+        }
+    }
+    // Components are checked as they end, after their children.
+    findings.sort_by_key(|finding| {
+        finding
+            .location
+            .as_deref()
+            .and_then(|location| location.strip_prefix("line "))
+            .and_then(|line| line.parse::<usize>().ok())
+    });
+    findings
+}
+
+fn check_component(component: &Component, scheduling: bool, out: &mut Vec<Finding>) {
+    let vcard = component.name == "VCARD";
+    let rule = if vcard {
+        Some(&VCARD_RULE)
+    } else {
+        ICALENDAR_RULES
+            .iter()
+            .find(|rule| rule.name == component.name)
+    };
+    let location = format!("line {}", component.line);
+    let count = |name: &str| {
+        component
+            .properties
+            .iter()
+            .filter(|property| property.name == name)
+            .count()
+    };
+    let value = |name: &str| {
+        component
+            .properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| property.value.trim().to_ascii_uppercase())
+    };
+
+    if let Some(rule) = rule {
+        let mut required = rule.required.to_vec();
+        // Only scheduling messages may leave out the start of an event.
+        if component.name == "VEVENT" && !scheduling {
+            required.push("DTSTART");
+        }
+        if vcard && value("VERSION").as_deref() == Some("3.0") {
+            required.push("N");
+        }
+        match (component.name.as_str(), value("ACTION").as_deref()) {
+            ("VALARM", Some("DISPLAY")) => required.push("DESCRIPTION"),
+            ("VALARM", Some("EMAIL")) => required.extend(["DESCRIPTION", "SUMMARY", "ATTENDEE"]),
+            _ => {}
+        }
+        for name in required {
+            if count(name) == 0 {
+                out.push(
+                    Finding::new(
+                        Code::MissingRequiredProperty,
+                        format!("{} has no {} property.", component.name, name),
+                    )
+                    .with_location(location.clone()),
+                );
+            }
+        }
+        for name in rule.once {
+            if count(name) > 1 {
+                out.push(
+                    Finding::new(
+                        Code::PropertyCardinality,
+                        format!(
+                            "{} may have one {} property but has {}.",
+                            component.name,
+                            name,
+                            count(name)
+                        ),
+                    )
+                    .with_location(location.clone()),
+                );
+            }
+        }
+    }
+
+    let exclusive: &[(&str, &str)] = match component.name.as_str() {
+        "VEVENT" => &[("DTEND", "DURATION")],
+        "VTODO" => &[("DUE", "DURATION")],
+        _ => &[],
+    };
+    for (first, second) in exclusive {
+        if count(first) > 0 && count(second) > 0 {
+            out.push(
+                Finding::new(
+                    Code::PropertyCardinality,
+                    format!(
+                        "{} may have {} or {} but not both.",
+                        component.name, first, second
+                    ),
+                )
+                .with_location(location.clone()),
+            );
+        }
+    }
+    if component.name == "VALARM" && (count("DURATION") > 0) != (count("REPEAT") > 0) {
+        out.push(
+            Finding::new(
+                Code::PropertyCardinality,
+                "VALARM must have both DURATION and REPEAT or neither.",
+            )
+            .with_location(location.clone()),
+        );
+    }
+    if component.name == "VTIMEZONE"
+        && !component
+            .children
+            .iter()
+            .any(|child| child == "STANDARD" || child == "DAYLIGHT")
+    {
+        out.push(
+            Finding::new(
+                Code::MissingRequiredProperty,
+                "VTIMEZONE has neither a STANDARD nor a DAYLIGHT component.",
+            )
+            .with_location(location),
+        );
+    }
+
+    for property in &component.properties {
+        check_property(property, vcard, out);
+    }
+}
+
+fn check_property(property: &ContentLine, vcard: bool, out: &mut Vec<Finding>) {
+    let location = format!("line {}", property.line);
+    let known = if vcard {
+        VCARD_PARAMETERS
+    } else {
+        ICALENDAR_PARAMETERS
+    };
+    for (param, value) in &property.params {
+        let param = param.to_ascii_uppercase();
+        if param.starts_with("X-") {
+            continue;
+        }
+        if !known.contains(&param.as_str()) {
+            out.push(
+                Finding::new(
+                    Code::UnknownParameter,
+                    format!("{} has an unknown parameter {}.", property.name, param),
+                )
+                .with_location(location.clone()),
+            );
+        } else if param == "VALUE" && !VALUE_TYPES.contains(&value.to_ascii_uppercase().as_str()) {
+            out.push(
+                Finding::new(
+                    Code::InvalidValue,
+                    format!("{} has an unknown value type {}.", property.name, value),
+                )
+                .with_location(location.clone()),
+            );
+        }
+    }
+
+    let value = property.value.trim();
+    if property.name == "RRULE" && !vcard {
+        for problem in recurrence_rule_problems(value) {
+            out.push(
+                Finding::new(
+                    Code::InvalidRecurrenceRule,
+                    format!("RRULE:{} {}.", value, problem),
+                )
+                .with_location(location.clone()),
+            );
+        }
+    }
+    if let Some(problem) = value_problem(property, vcard) {
+        out.push(
+            Finding::new(
+                Code::InvalidValue,
+                format!("{} value \"{}\" {}.", property.name, value, problem),
+            )
+            .with_location(location),
+        );
+    }
+}
+
+// Why the value of a property does not match its type, if it does not.
+pub fn value_problem(property: &ContentLine, vcard: bool) -> Option<&'static str> {
+    let value = property.value.trim();
+    let value_type = property
+        .param("VALUE")
+        .map(|value| value.to_ascii_uppercase());
+    match (property.name.as_str(), vcard) {
+        ("DTSTART" | "DTEND" | "DUE" | "RECURRENCE-ID", false) => {
+            (!is_date_or_date_time(value, value_type.as_deref()))
+                .then_some("is not a valid date or date-time")
+        }
+        ("EXDATE", false) => value
+            .split(',')
+            .any(|value| !is_date_or_date_time(value, value_type.as_deref()))
+            .then_some("is not a list of dates or date-times"),
+        ("RDATE", false) if value_type.as_deref() != Some("PERIOD") => value
+            .split(',')
+            .any(|value| !is_date_or_date_time(value, value_type.as_deref()))
+            .then_some("is not a list of dates or date-times"),
+        ("DTSTAMP" | "CREATED" | "LAST-MODIFIED" | "COMPLETED", false) => {
+            (!is_utc_date_time(value)).then_some("is not a UTC date-time")
+        }
+        ("REV", true) => (!is_utc_date_time(value) && !is_date_or_date_time(value, None))
+            .then_some("is not a timestamp"),
+        ("SEQUENCE" | "REPEAT", false) => {
+            (value.parse::<u32>().is_err()).then_some("is not a non-negative integer")
+        }
+        ("PRIORITY", false) => (!value.parse::<u8>().is_ok_and(|value| value <= 9))
+            .then_some("is not an integer from 0 to 9"),
+        ("PERCENT-COMPLETE", false) => (!value.parse::<u8>().is_ok_and(|value| value <= 100))
+            .then_some("is not an integer from 0 to 100"),
+        ("DURATION", false) => (!is_duration(value)).then_some("is not a duration"),
+        ("TRIGGER", false) => {
+            if value_type.as_deref() == Some("DATE-TIME") {
+                (!is_utc_date_time(value)).then_some("is not a UTC date-time")
+            } else {
+                (!is_duration(value)).then_some("is not a duration")
+            }
+        }
+        ("TZOFFSETFROM" | "TZOFFSETTO", false) => {
+            (!is_utc_offset(value)).then_some("is not a UTC offset")
+        }
+        ("GEO", false) => (!value
+            .split_once(';')
+            .is_some_and(|(lat, lon)| lat.parse::<f64>().is_ok() && lon.parse::<f64>().is_ok()))
+        .then_some("is not a latitude and longitude"),
+        ("VERSION", false) => (value != "2.0").then_some("is not 2.0"),
+        ("VERSION", true) => (value != "3.0" && value != "4.0").then_some("is not 3.0 or 4.0"),
+        _ => None,
+    }
+}
+
+// RFC 5545 section 3.3.10.
+pub fn recurrence_rule_problems(value: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut parts: Vec<(String, String)> = Vec::new();
+    for part in value.split(';').filter(|part| !part.is_empty()) {
+        match part.split_once('=') {
+            Some((name, value)) => {
+                let name = name.trim().to_ascii_uppercase();
+                if parts.iter().any(|(other, _)| *other == name) {
+                    problems.push(format!("repeats {}", name));
+                }
+                parts.push((name, value.trim().to_ascii_uppercase()));
+            }
+            None => problems.push(format!("has a part \"{}\" without a value", part)),
+        }
+    }
+    let part = |name: &str| {
+        parts
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let frequency = part("FREQ");
+    match frequency {
+        None => problems.push("has no FREQ".to_string()),
+        Some(frequency) if !FREQUENCIES.contains(&frequency) => {
+            problems.push(format!("has an unknown FREQ {}", frequency))
+        }
+        _ => {}
+    }
+    if part("COUNT").is_some() && part("UNTIL").is_some() {
+        problems.push("sets both COUNT and UNTIL".to_string());
+    }
+
+    // Signed parts count from the end when negative and never take zero.
+    let numbers = |value: &str, min: i32, max: i32, signed: bool| {
+        value.split(',').all(|item| {
+            item.parse::<i32>().is_ok_and(|number| {
+                let magnitude = if signed { number.abs() } else { number };
+                (!signed || number != 0) && magnitude >= min && magnitude <= max
+            })
+        })
+    };
+    for (name, value) in &parts {
+        let valid = match name.as_str() {
+            "FREQ" => true,
+            "UNTIL" => is_date_or_date_time(value, None),
+            "COUNT" => value.parse::<u32>().is_ok_and(|count| count > 0),
+            "INTERVAL" => value.parse::<u32>().is_ok_and(|interval| interval > 0),
+            "BYSECOND" => numbers(value, 0, 60, false),
+            "BYMINUTE" => numbers(value, 0, 59, false),
+            "BYHOUR" => numbers(value, 0, 23, false),
+            "BYMONTHDAY" => numbers(value, 1, 31, true),
+            "BYYEARDAY" => numbers(value, 1, 366, true),
+            "BYWEEKNO" => numbers(value, 1, 53, true),
+            "BYMONTH" => numbers(value, 1, 12, false),
+            "BYSETPOS" => numbers(value, 1, 366, true),
+            "WKST" => WEEKDAYS.contains(&value.as_str()),
+            "BYDAY" => value.split(',').all(|day| {
+                contentline::parse_byday(day).is_some_and(|(ordinal, weekday)| {
+                    WEEKDAYS.contains(&weekday)
+                        && ordinal.is_none_or(|ordinal| ordinal != 0 && ordinal.abs() <= 53)
+                })
+            }),
+            // RFC 7529 extensions.
+            "RSCALE" | "SKIP" => true,
+            _ => {
+                problems.push(format!("has an unknown part {}", name));
+                continue;
+            }
+        };
+        if !valid {
+            problems.push(format!("has an invalid {} value {}", name, value));
+        }
+    }
+
+    // Parts that only mean something for some frequencies.
+    if let Some(frequency) = frequency {
+        let ordinals = part("BYDAY").is_some_and(|days| {
+            days.split(',').any(|day| {
+                contentline::parse_byday(day).is_some_and(|(ordinal, _)| ordinal.is_some())
+            })
+        });
+        if ordinals && !matches!(frequency, "MONTHLY" | "YEARLY") {
+            problems.push(format!("numbers BYDAY entries with FREQ={}", frequency));
+        }
+        if part("BYWEEKNO").is_some() && frequency != "YEARLY" {
+            problems.push(format!("uses BYWEEKNO with FREQ={}", frequency));
+        }
+        if part("BYYEARDAY").is_some() && matches!(frequency, "DAILY" | "WEEKLY" | "MONTHLY") {
+            problems.push(format!("uses BYYEARDAY with FREQ={}", frequency));
+        }
+        if part("BYMONTHDAY").is_some() && frequency == "WEEKLY" {
+            problems.push("uses BYMONTHDAY with FREQ=WEEKLY".to_string());
+        }
+    }
+    problems
+}
+
+fn validate_json(pointer: &str, value: &Value) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let Value::Object(object) = value else {
+        findings.push(
+            Finding::new(Code::InvalidValue, "The document is not a JSON object.")
+                .with_location(if pointer.is_empty() { "/" } else { pointer }),
+        );
+        return findings;
+    };
+    match object.get("@type").and_then(Value::as_str) {
+        Some("Group") => {
+            require(pointer, object, &["uid", "entries"], &mut findings);
+            check_json_common(pointer, object, &mut findings);
+            match object.get("entries") {
+                Some(Value::Array(entries)) => {
+                    for (index, entry) in entries.iter().enumerate() {
+                        findings.extend(validate_json(
+                            &format!("{}/entries/{}", pointer, index),
+                            entry,
+                        ));
+                    }
+                }
+                Some(_) => invalid(pointer, "entries", "is not an array", &mut findings),
+                None => {}
+            }
+        }
+        Some("Event") => {
+            require(pointer, object, &["uid", "start"], &mut findings);
+            check_json_common(pointer, object, &mut findings);
+        }
+        Some("Task") => {
+            require(pointer, object, &["uid"], &mut findings);
+            check_json_common(pointer, object, &mut findings);
+        }
+        // RFC 9553 section 2.1.
+        Some("Card") => {
+            require(pointer, object, &["version", "uid"], &mut findings);
+            for key in ["version", "uid", "kind", "language"] {
+                if object.get(key).is_some_and(|value| !value.is_string()) {
+                    invalid(pointer, key, "is not a string", &mut findings);
+                }
+            }
+            for key in ["created", "updated"] {
+                if let Some(value) = object.get(key) {
+                    if !value.as_str().is_some_and(is_utc_json_date_time) {
+                        invalid(pointer, key, "is not a UTCDateTime", &mut findings);
+                    }
+                }
+            }
+        }
+        // Top-level type of the JSContact drafts, converted to a group Card.
+        Some("CardGroup") => require(pointer, object, &["uid", "members"], &mut findings),
+        Some(other) => findings.push(
+            Finding::new(
+                Code::InvalidValue,
+                format!("Unknown object type \"{}\".", other),
+            )
+            .with_location(format!("{}/@type", pointer)),
+        ),
+        None => findings.push(
+            Finding::new(Code::MissingRequiredProperty, "The object has no @type.")
+                .with_location(if pointer.is_empty() { "/" } else { pointer }),
+        ),
+    }
+    findings
+}
+
+// RFC 8984 sections 4 and 5.
+fn check_json_common(pointer: &str, object: &Map<String, Value>, out: &mut Vec<Finding>) {
+    if object.get("uid").is_some_and(|value| !value.is_string()) {
+        invalid(pointer, "uid", "is not a string", out);
+    }
+    for key in ["start", "due"] {
+        if let Some(value) = object.get(key) {
+            if !value.as_str().is_some_and(is_local_json_date_time) {
+                invalid(pointer, key, "is not a LocalDateTime", out);
+            }
+        }
+    }
+    for key in ["created", "updated"] {
+        if let Some(value) = object.get(key) {
+            if !value.as_str().is_some_and(is_utc_json_date_time) {
+                invalid(pointer, key, "is not a UTCDateTime", out);
+            }
+        }
+    }
+    for key in ["duration", "estimatedDuration"] {
+        if let Some(value) = object.get(key) {
+            if !value.as_str().is_some_and(is_duration) {
+                invalid(pointer, key, "is not a Duration", out);
+            }
+        }
+    }
+    if let Some(value) = object.get("sequence") {
+        if !value.is_u64() {
+            invalid(pointer, "sequence", "is not an UnsignedInt", out);
+        }
+    }
+    if let Some(value) = object.get("priority") {
+        if value.as_u64().is_none_or(|value| value > 9) {
+            invalid(pointer, "priority", "is not an integer from 0 to 9", out);
+        }
+    }
+    if let Some(value) = object.get("percentComplete") {
+        if value.as_u64().is_none_or(|value| value > 100) {
+            invalid(
+                pointer,
+                "percentComplete",
+                "is not an integer from 0 to 100",
+                out,
+            );
+        }
+    }
+    for key in ["showWithoutTime", "excluded", "useDefaultAlerts"] {
+        if object.get(key).is_some_and(|value| !value.is_boolean()) {
+            invalid(pointer, key, "is not a boolean", out);
+        }
+    }
+    if object
+        .get("timeZone")
+        .is_some_and(|value| !value.is_string() && !value.is_null())
+    {
+        invalid(pointer, "timeZone", "is not a string or null", out);
+    }
+    for key in ["recurrenceRules", "excludedRecurrenceRules"] {
+        match object.get(key) {
+            Some(Value::Array(rules)) => {
+                for (index, rule) in rules.iter().enumerate() {
+                    let location = format!("{}/{}/{}", pointer, escape_pointer(key), index);
+                    for problem in json_recurrence_rule_problems(rule) {
+                        out.push(
+                            Finding::new(
+                                Code::InvalidRecurrenceRule,
+                                format!("Recurrence rule {}.", problem),
+                            )
+                            .with_location(location.clone()),
+                        );
+                    }
+                }
+            }
+            Some(_) => invalid(pointer, key, "is not an array", out),
+            None => {}
+        }
+    }
+}
+
+// RFC 8984 section 4.3.3.
+fn json_recurrence_rule_problems(rule: &Value) -> Vec<String> {
+    let Value::Object(rule) = rule else {
+        return vec!["is not an object".to_string()];
+    };
+    let mut problems = Vec::new();
+    match rule.get("frequency").and_then(Value::as_str) {
+        None => problems.push("has no frequency".to_string()),
+        Some(frequency)
+            if !FREQUENCIES.contains(&frequency.to_ascii_uppercase().as_str())
+                || frequency.chars().any(|ch| ch.is_ascii_uppercase()) =>
+        {
+            problems.push(format!("has an unknown frequency \"{}\"", frequency))
+        }
+        _ => {}
+    }
+    if rule.contains_key("count") && rule.contains_key("until") {
+        problems.push("sets both count and until".to_string());
+    }
+    for key in ["interval", "count"] {
+        if rule
+            .get(key)
+            .is_some_and(|value| value.as_u64().is_none_or(|value| value == 0))
+        {
+            problems.push(format!("has an invalid {}", key));
+        }
+    }
+    if rule
+        .get("until")
+        .is_some_and(|value| !value.as_str().is_some_and(is_local_json_date_time))
+    {
+        problems.push("has an until that is not a LocalDateTime".to_string());
+    }
+    if let Some(first_day) = rule.get("firstDayOfWeek") {
+        if !first_day
+            .as_str()
+            .is_some_and(|day| WEEKDAYS.contains(&day.to_ascii_uppercase().as_str()))
+        {
+            problems.push("has an invalid firstDayOfWeek".to_string());
+        }
+    }
+    if let Some(days) = rule.get("byDay") {
+        let valid = days.as_array().is_some_and(|days| {
+            days.iter().all(|day| {
+                day.get("day")
+                    .and_then(Value::as_str)
+                    .is_some_and(|day| WEEKDAYS.contains(&day.to_ascii_uppercase().as_str()))
+                    && day
+                        .get("nthOfPeriod")
+                        .is_none_or(|nth| nth.as_i64().is_some_and(|nth| nth != 0))
+            })
+        });
+        if !valid {
+            problems.push("has an invalid byDay".to_string());
+        }
+    }
+    let ranges: [(&str, i64, i64, bool); 7] = [
+        ("byMonthDay", 1, 31, true),
+        ("byYearDay", 1, 366, true),
+        ("byWeekNo", 1, 53, true),
+        ("byHour", 0, 23, false),
+        ("byMinute", 0, 59, false),
+        ("bySecond", 0, 60, false),
+        ("bySetPosition", 1, 366, true),
+    ];
+    for (key, min, max, signed) in ranges {
+        if let Some(values) = rule.get(key) {
+            let valid = values.as_array().is_some_and(|values| {
+                values.iter().all(|value| {
+                    value.as_i64().is_some_and(|value| {
+                        let magnitude = if signed { value.abs() } else { value };
+                        (!signed || value != 0) && magnitude >= min && magnitude <= max
+                    })
+                })
+            });
+            if !valid {
+                problems.push(format!("has an invalid {}", key));
+            }
+        }
+    }
+    if let Some(months) = rule.get("byMonth") {
+        let valid = months.as_array().is_some_and(|months| {
+            months.iter().all(|month| {
+                month.as_str().is_some_and(|month| {
+                    month
+                        .strip_suffix('L')
+                        .unwrap_or(month)
+                        .parse::<u32>()
+                        .is_ok_and(|month| (1..=12).contains(&month))
+                })
+            })
+        });
+        if !valid {
+            problems.push("has an invalid byMonth".to_string());
+        }
+    }
+    problems
+}
+
+fn require(pointer: &str, object: &Map<String, Value>, keys: &[&str], out: &mut Vec<Finding>) {
+    let kind = object
+        .get("@type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    for key in keys {
+        if !object.contains_key(*key) {
+            out.push(
+                Finding::new(
+                    Code::MissingRequiredProperty,
+                    format!("{} has no {} property.", kind, key),
+                )
+                .with_location(if pointer.is_empty() { "/" } else { pointer }),
+            );
+        }
+    }
+}
+
+fn invalid(pointer: &str, key: &str, problem: &str, out: &mut Vec<Finding>) {
+    out.push(
+        Finding::new(Code::InvalidValue, format!("{} {}.", key, problem)).with_location(format!(
+            "{}/{}",
+            pointer,
+            escape_pointer(key)
+        )),
+    );
+}
+
+fn is_date_or_date_time(value: &str, value_type: Option<&str>) -> bool {
+    match value_type {
+        Some("DATE") => contentline::parse_date(value).is_some(),
+        Some("DATE-TIME") => contentline::parse_datetime(value).is_some(),
+        _ => contentline::parse_date_or_datetime(value).is_some(),
+    }
+}
+
+fn is_utc_date_time(value: &str) -> bool {
+    value.ends_with('Z') && contentline::parse_datetime(value).is_some()
+}
+
+fn is_local_json_date_time(value: &str) -> bool {
+    !value.ends_with('Z') && NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+}
+
+fn is_utc_json_date_time(value: &str) -> bool {
+    value
+        .strip_suffix('Z')
+        .is_some_and(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok())
+}
+
+// [+-]P[nW] or P[nD][T[nH][nM][nS]], as RFC 5545 and RFC 8984 write them.
+fn is_duration(value: &str) -> bool {
+    let value = value.trim();
+    let value = value
+        .strip_prefix(['+', '-'])
+        .unwrap_or(value)
+        .to_ascii_uppercase();
+    let Some(value) = value.strip_prefix('P') else {
+        return false;
+    };
+    if let Some(weeks) = value.strip_suffix('W') {
+        return !weeks.is_empty() && weeks.bytes().all(|byte| byte.is_ascii_digit());
+    }
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let units = |text: &str, allowed: &[u8]| {
+        let mut digits = 0;
+        let mut last = None;
+        for byte in text.bytes() {
+            if byte.is_ascii_digit() || byte == b'.' {
+                digits += 1;
+            } else {
+                let position = allowed.iter().position(|unit| *unit == byte)?;
+                if digits == 0 || last.is_some_and(|last| position <= last) {
+                    return None;
+                }
+                last = Some(position);
+                digits = 0;
+            }
+        }
+        (digits == 0).then_some(last.is_some())
+    };
+    match (units(date, b"D"), time.map(|time| units(time, b"HMS"))) {
+        (Some(has_date), None) => has_date,
+        (Some(_), Some(Some(has_time))) => has_time,
+        _ => false,
+    }
+}
+
+fn is_utc_offset(value: &str) -> bool {
+    let Some(offset) = value.strip_prefix(['+', '-']) else {
+        return false;
+    };
+    (offset.len() == 4 || offset.len() == 6)
+        && offset.bytes().all(|byte| byte.is_ascii_digit())
+        && NaiveTime::parse_from_str(&format!("{:0<6}", offset), "%H%M%S").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_non_ascii_byday() {
+        assert_eq!(
+            recurrence_rule_problems("FREQ=MONTHLY;BYDAY=1€"),
+            vec!["has an invalid BYDAY value 1€".to_string()]
+        );
+        let findings = validate(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Test//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:1\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "DTSTART:20240101T090000Z\r\n",
+            "RRULE:FREQ=WEEKLY;BYDAY=ÖA\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+        assert!(findings
+            .iter()
+            .any(|finding| finding.message.contains("BYDAY value ÖA")));
+    }
+}
@@ -0,0 +1,579 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::contentline::{self, ContentLine};
+
+// vCard 2.1 and 3.0 properties that 4.0 dropped.
+const REMOVED_IN_V4: &[&str] = &["NAME", "MAILER", "CLASS", "PROFILE"];
+
+// vCard 4.0 properties, and those of its extensions, that 3.0 has no
+// equivalent for.
+const ADDED_IN_V4: &[&str] = &[
+    "GENDER",
+    "ANNIVERSARY",
+    "LANG",
+    "MEMBER",
+    "XML",
+    "CLIENTPIDMAP",
+    "BIRTHPLACE",
+    "DEATHPLACE",
+    "DEATHDATE",
+    "CONTACT-URI",
+    "CREATED",
+    "SOCIALPROFILE",
+    "LANGUAGE",
+    "GRAMGENDER",
+    "PRONOUNS",
+];
+
+// TYPE values vCard 4.0 still has for each property.
+const V4_TYPES: &[(&str, &[&str])] = &[
+    (
+        "TEL",
+        &[
+            "text",
+            "voice",
+            "fax",
+            "cell",
+            "video",
+            "pager",
+            "textphone",
+            "work",
+            "home",
+        ],
+    ),
+    ("EMAIL", &["work", "home"]),
+    ("ADR", &["work", "home"]),
+];
+
+// Properties whose value is text, and text with `;` separated components.
+const TEXT: &[&str] = &["FN", "NOTE", "TITLE", "ROLE", "LABEL"];
+const STRUCTURED: &[&str] = &["N", "ADR", "ORG"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Version {
+    V21,
+    V30,
+    #[default]
+    V40,
+}
+
+// A property, parameter or value that the target version cannot hold and
+// was dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unrepresentable {
+    pub line: usize,
+    pub property: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Migration {
+    pub output: String,
+    pub unrepresentable: Vec<Unrepresentable>,
+}
+
+impl Version {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Version::V21 => "2.1",
+            Version::V30 => "3.0",
+            Version::V40 => "4.0",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "2.1" => Some(Version::V21),
+            "3.0" => Some(Version::V30),
+            "4.0" => Some(Version::V40),
+            _ => None,
+        }
+    }
+}
+
+// Version of the first vCard in the source.
+pub fn detect(source: &str) -> Option<Version> {
+    logical_lines(source)
+        .into_iter()
+        .filter_map(|(line, text)| contentline::parse_line(line, &text))
+        .find(|line| line.name == "VERSION")
+        .and_then(|line| Version::parse(&line.value))
+}
+
+// Rewrites every vCard in the source as vCard 3.0 or 4.0. Quoted-printable and
+// inline binary values are decoded, TYPE parameters, AGENT and LABEL are moved
+// to their form in the target version, and anything it cannot hold is
+// dropped and reported.
+pub fn migrate(source: &str, target: Version) -> Migration {
+    let mut migration = Migration::default();
+    let mut card: Option<Vec<ContentLine>> = None;
+    // Lines of a vCard embedded in a 2.1 AGENT, which are not kept.
+    let mut nested = 0usize;
+    for (line, text) in logical_lines(source) {
+        let Some(line) = contentline::parse_line(line, &text) else {
+            continue;
+        };
+        let is_vcard = line.value.trim().eq_ignore_ascii_case("VCARD");
+        match (&mut card, line.name.as_str()) {
+            (None, "BEGIN") if is_vcard => card = Some(Vec::new()),
+            (Some(_), "BEGIN") if is_vcard => {
+                if nested == 0 {
+                    migration.unrepresentable.push(Unrepresentable {
+                        line: line.line,
+                        property: "AGENT".to_string(),
+                        reason: "An embedded vCard agent cannot be kept".to_string(),
+                    });
+                }
+                nested += 1;
+            }
+            (Some(_), "END") if is_vcard && nested > 0 => nested -= 1,
+            (Some(_), "END") if is_vcard => {
+                let lines = card.take().unwrap_or_default();
+                write_card(&mut migration, lines, target);
+            }
+            (Some(lines), _) if nested == 0 => lines.push(line),
+            _ => {}
+        }
+    }
+    migration.unrepresentable.sort_by_key(|item| item.line);
+    migration
+}
+
+fn write_card(migration: &mut Migration, lines: Vec<ContentLine>, target: Version) {
+    let version = lines
+        .iter()
+        .find(|line| line.name == "VERSION")
+        .and_then(|line| Version::parse(&line.value))
+        .unwrap_or(Version::V30);
+    let report = &mut migration.unrepresentable;
+    let mut properties = Vec::with_capacity(lines.len());
+    let mut labels = Vec::new();
+    let mut sort_string = None;
+    let mut alternatives: Vec<(String, String)> = Vec::new();
+
+    for mut line in lines {
+        // The line before a 2.1 embedded agent, reported with it.
+        if line.name == "VERSION" || (line.name == "AGENT" && line.value.is_empty()) {
+            continue;
+        }
+        decode(&mut line, version);
+
+        if target == Version::V40 {
+            if REMOVED_IN_V4.contains(&line.name.as_str()) {
+                report.push(dropped(&line, "vCard 4.0"));
+                continue;
+            }
+            match line.name.as_str() {
+                "LABEL" => {
+                    labels.push(line);
+                    continue;
+                }
+                "SORT-STRING" => {
+                    sort_string = Some(line.value);
+                    continue;
+                }
+                "AGENT" if line.value.contains("BEGIN:VCARD") => {
+                    report.push(Unrepresentable {
+                        line: line.line,
+                        property: line.name.clone(),
+                        reason: "An embedded vCard agent cannot be kept".to_string(),
+                    });
+                    continue;
+                }
+                "AGENT" => {
+                    line.name = "RELATED".to_string();
+                    line.set_param("TYPE", "agent");
+                    if !line
+                        .param("VALUE")
+                        .is_some_and(|value| value.eq_ignore_ascii_case("uri"))
+                    {
+                        line.set_param("VALUE", "text");
+                    } else {
+                        line.remove_param("VALUE");
+                    }
+                }
+                "GEO" if !line.value.starts_with("geo:") => {
+                    line.value = format!("geo:{}", line.value.replace(';', ","));
+                }
+                _ => {}
+            }
+            to_v4_types(&mut line, report);
+            to_v4_binary(&mut line);
+            if line.name == "TEL"
+                && line.param("VALUE").is_none()
+                && !line.value.starts_with("tel:")
+            {
+                line.set_param("VALUE", "text");
+            }
+        } else {
+            if ADDED_IN_V4.contains(&line.name.as_str()) {
+                report.push(dropped(&line, "vCard 3.0"));
+                continue;
+            }
+            match line.name.as_str() {
+                "KIND" if line.value.eq_ignore_ascii_case("individual") => continue,
+                "KIND" => {
+                    report.push(dropped(&line, "vCard 3.0"));
+                    continue;
+                }
+                "RELATED" if line.has_type("agent") => {
+                    line.name = "AGENT".to_string();
+                    line.remove_param("TYPE");
+                    if line.param("VALUE").is_none() {
+                        line.set_param("VALUE", "uri");
+                    } else {
+                        line.remove_param("VALUE");
+                    }
+                }
+                "RELATED" => {
+                    report.push(dropped(&line, "vCard 3.0"));
+                    continue;
+                }
+                "GEO" => {
+                    if let Some(coordinates) = line.value.strip_prefix("geo:") {
+                        line.value = coordinates
+                            .split(';')
+                            .next()
+                            .unwrap_or_default()
+                            .replace(',', ";");
+                    }
+                }
+                "TEL" => {
+                    if let Some(number) = line.value.strip_prefix("tel:") {
+                        line.value = number.to_string();
+                    }
+                    line.remove_param("VALUE");
+                }
+                _ => {}
+            }
+            // Alternative representations of one property become a single one.
+            if let Some(altid) = line.param("ALTID").map(str::to_string) {
+                let key = (line.name.clone(), altid);
+                if alternatives.contains(&key) {
+                    report.push(Unrepresentable {
+                        line: line.line,
+                        property: line.name.clone(),
+                        reason: "vCard 3.0 has no alternative representations (ALTID)".to_string(),
+                    });
+                    continue;
+                }
+                alternatives.push(key);
+            }
+            if line.name == "N" {
+                if let Some(sort_as) = line.param("SORT-AS") {
+                    sort_string = Some(sort_as.to_string());
+                }
+            }
+            to_v3_types(&mut line);
+            to_v3_binary(&mut line);
+            for param in ["ALTID", "PID", "SORT-AS", "CALSCALE"] {
+                line.remove_param(param);
+            }
+            // The LABEL parameter of an address is a property of its own.
+            if let Some(label) = line.param("LABEL").map(str::to_string) {
+                line.remove_param("LABEL");
+                let mut separate = ContentLine {
+                    line: line.line,
+                    group: line.group.clone(),
+                    name: "LABEL".to_string(),
+                    value: contentline::escape(&label.replace("^n", "\n")),
+                    ..Default::default()
+                };
+                if let Some(types) = line.param("TYPE") {
+                    separate.set_param("TYPE", types);
+                }
+                properties.push(line);
+                properties.push(separate);
+                continue;
+            }
+        }
+        properties.push(line);
+    }
+
+    // LABEL becomes a parameter of the address with the same types.
+    for label in labels {
+        let types = label.param_values("TYPE");
+        let address = properties
+            .iter()
+            .position(|line| {
+                line.name == "ADR"
+                    && line.param("LABEL").is_none()
+                    && line
+                        .param_values("TYPE")
+                        .iter()
+                        .any(|kind| types.contains(kind))
+            })
+            .or_else(|| {
+                properties
+                    .iter()
+                    .position(|line| line.name == "ADR" && line.param("LABEL").is_none())
+            });
+        match address {
+            Some(address) => properties[address].set_param(
+                "LABEL",
+                &contentline::unescape(&label.value).replace('\n', "^n"),
+            ),
+            None => report.push(Unrepresentable {
+                line: label.line,
+                property: label.name,
+                reason: "vCard 4.0 keeps LABEL on an address and there is none".to_string(),
+            }),
+        }
+    }
+    if let Some(sort_string) = sort_string {
+        if target == Version::V40 {
+            if let Some(name) = properties.iter_mut().find(|line| line.name == "N") {
+                name.set_param("SORT-AS", &sort_string);
+            }
+        } else {
+            properties.push(ContentLine {
+                name: "SORT-STRING".to_string(),
+                value: sort_string,
+                ..Default::default()
+            });
+        }
+    }
+    // FN is required by both and N by 3.0.
+    if !properties.iter().any(|line| line.name == "FN") {
+        let value = properties
+            .iter()
+            .find(|line| line.name == "N")
+            .map(|name| {
+                let parts = contentline::split_unescaped(&name.value, ';');
+                [3, 1, 2, 0, 4]
+                    .iter()
+                    .filter_map(|index| parts.get(*index))
+                    .filter(|part| !part.is_empty())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        properties.insert(
+            0,
+            ContentLine {
+                name: "FN".to_string(),
+                value,
+                ..Default::default()
+            },
+        );
+    }
+    if target == Version::V30 && !properties.iter().any(|line| line.name == "N") {
+        properties.push(ContentLine {
+            name: "N".to_string(),
+            value: ";;;;".to_string(),
+            ..Default::default()
+        });
+    }
+
+    let out = &mut migration.output;
+    contentline::fold_line(out, "BEGIN:VCARD");
+    contentline::fold_line(out, &format!("VERSION:{}", target.as_str()));
+    for line in properties {
+        contentline::fold_line(out, &line.to_line());
+    }
+    contentline::fold_line(out, "END:VCARD");
+}
+
+fn dropped(line: &ContentLine, version: &str) -> Unrepresentable {
+    Unrepresentable {
+        line: line.line,
+        property: line.name.clone(),
+        reason: format!("{} has no equivalent", version),
+    }
+}
+
+// Decodes quoted-printable values and writes 2.1 text with the escaping of
+// the later versions.
+fn decode(line: &mut ContentLine, version: Version) {
+    let encoding = line.param("ENCODING").map(str::to_ascii_uppercase);
+    let quoted_printable = encoding.as_deref() == Some("QUOTED-PRINTABLE");
+    if quoted_printable {
+        let bytes = contentline::decode_quoted_printable(&line.value);
+        line.value = match line
+            .param("CHARSET")
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("iso-8859-1" | "latin1" | "windows-1252") => {
+                bytes.iter().map(|byte| char::from(*byte)).collect()
+            }
+            _ => String::from_utf8_lossy(&bytes).into_owned(),
+        };
+        line.remove_param("ENCODING");
+    }
+    line.remove_param("CHARSET");
+    if version != Version::V21 && !quoted_printable {
+        return;
+    }
+    let name = line.name.as_str();
+    if STRUCTURED.contains(&name) {
+        line.value = contentline::split_unescaped(&line.value, ';')
+            .iter()
+            .map(|part| escape_v21(part))
+            .collect::<Vec<_>>()
+            .join(";");
+    } else if TEXT.contains(&name) || name.starts_with("X-") {
+        line.value = escape_v21(&line.value);
+    }
+}
+
+// vCard 2.1 only escapes semicolons, and quoted-printable values may hold
+// raw line breaks.
+fn escape_v21(value: &str) -> String {
+    let value = value.replace("\\;", ";").replace("\r\n", "\n");
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+fn to_v4_types(line: &mut ContentLine, report: &mut Vec<Unrepresentable>) {
+    let types = line.param_values("TYPE");
+    if types.is_empty() {
+        return;
+    }
+    let allowed = V4_TYPES
+        .iter()
+        .find(|(name, _)| *name == line.name)
+        .map(|(_, allowed)| *allowed);
+    let mut kept = Vec::new();
+    for kind in types {
+        if kind == "pref" {
+            line.set_param("PREF", "1");
+        } else if kind == "internet" && line.name == "EMAIL" {
+            // The only kind of address 4.0 knows.
+        } else if allowed.is_none_or(|allowed| allowed.contains(&kind.as_str())) {
+            kept.push(kind);
+        } else {
+            report.push(Unrepresentable {
+                line: line.line,
+                property: line.name.clone(),
+                reason: format!("TYPE={} has no vCard 4.0 equivalent", kind),
+            });
+        }
+    }
+    line.remove_param("TYPE");
+    if !kept.is_empty() {
+        line.set_param("TYPE", &kept.join(","));
+    }
+}
+
+fn to_v3_types(line: &mut ContentLine) {
+    if line.param("PREF").is_some() {
+        line.remove_param("PREF");
+        let mut types = line.param_values("TYPE");
+        if !types.iter().any(|kind| kind == "pref") {
+            types.push("pref".to_string());
+        }
+        line.set_param("TYPE", &types.join(","));
+    }
+}
+
+// Inline binary values of 2.1 and 3.0 become data: URIs.
+fn to_v4_binary(line: &mut ContentLine) {
+    let encoding = line.param("ENCODING").map(str::to_ascii_lowercase);
+    if !matches!(encoding.as_deref(), Some("b" | "base64")) {
+        if matches!(line.name.as_str(), "PHOTO" | "LOGO" | "SOUND" | "KEY")
+            && line
+                .param("VALUE")
+                .is_some_and(|value| value.eq_ignore_ascii_case("uri"))
+        {
+            line.remove_param("VALUE");
+            if let Some(kind) = line.param_values("TYPE").into_iter().next() {
+                line.remove_param("TYPE");
+                line.set_param("MEDIATYPE", &media_type(&line.name, &kind));
+            }
+        }
+        return;
+    }
+    let media_type = line
+        .param_values("TYPE")
+        .into_iter()
+        .next()
+        .map(|kind| media_type(&line.name, &kind));
+    let data = line.value.split_whitespace().collect::<String>();
+    line.value = match media_type {
+        Some(media_type) => format!("data:{};base64,{}", media_type, data),
+        None => format!("data:;base64,{}", data),
+    };
+    for param in ["ENCODING", "TYPE", "VALUE"] {
+        line.remove_param(param);
+    }
+}
+
+// Media type for the TYPE of an inline or linked 2.1 and 3.0 value, such as
+// JPEG for a PHOTO.
+fn media_type(property: &str, kind: &str) -> String {
+    if kind.contains('/') {
+        return kind.to_string();
+    }
+    let family = match property {
+        "SOUND" => "audio",
+        "KEY" => "application",
+        _ => "image",
+    };
+    format!("{}/{}", family, if kind == "jpg" { "jpeg" } else { kind })
+}
+
+// data: URIs go back inline with their media subtype as the TYPE.
+fn to_v3_binary(line: &mut ContentLine) {
+    let Some(rest) = line.value.strip_prefix("data:") else {
+        if matches!(
+            line.name.as_str(),
+            "PHOTO" | "LOGO" | "SOUND" | "KEY" | "URL"
+        ) {
+            line.remove_param("MEDIATYPE");
+            if line.value.contains(':') && line.name != "URL" {
+                line.set_param("VALUE", "uri");
+            }
+        }
+        return;
+    };
+    let Some((header, data)) = rest.split_once(',') else {
+        return;
+    };
+    let Some(media_type) = header.strip_suffix(";base64") else {
+        return;
+    };
+    let subtype = media_type
+        .split_once('/')
+        .map_or(media_type, |(_, subtype)| subtype)
+        .to_ascii_uppercase();
+    line.value = data.to_string();
+    line.remove_param("MEDIATYPE");
+    line.remove_param("VALUE");
+    line.set_param("ENCODING", "b");
+    if !subtype.is_empty() {
+        line.set_param("TYPE", &subtype);
+    }
+}
+
+// Unfolded lines, with quoted-printable soft line breaks joined as well.
+fn logical_lines(source: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut continued = false;
+    for (line, text) in contentline::unfold(source) {
+        match lines.last_mut() {
+            Some((_, previous)) if continued => previous.push_str(&text),
+            _ => lines.push((line, text)),
+        }
+        continued = match lines.last_mut() {
+            Some((_, current)) if current.ends_with('=') => {
+                let params = current.split(':').next().unwrap_or_default();
+                let soft_break = params.to_ascii_uppercase().contains("QUOTED-PRINTABLE");
+                if soft_break {
+                    current.pop();
+                }
+                soft_break
+            }
+            _ => false,
+        };
+    }
+    lines
+}
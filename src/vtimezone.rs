@@ -0,0 +1,532 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// The VTIMEZONE definitions of an iCalendar, with the IANA zone each one was
+// converted to, the transitions the definition produces and where it departs
+// from the IANA database.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Weekday};
+use serde_json::Value;
+
+use crate::{
+    contentline::{self, Component, ContentLine},
+    repair,
+};
+
+// Mismatching periods listed per zone before the rest are only counted.
+const MAX_MISMATCHES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObservanceKind {
+    Standard,
+    Daylight,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub kind: ObservanceKind,
+    pub name: Option<String>,
+    pub utc: NaiveDateTime,
+    // Offsets from UTC in seconds.
+    pub offset_from: i32,
+    pub offset_to: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeZoneReport {
+    pub tz_id: String,
+    pub line: usize,
+    // The IANA zone the converted JSCalendar uses for this TZID.
+    pub mapped: Option<String>,
+    // The zone the definition was checked against.
+    pub compared: Option<String>,
+    pub transitions: Vec<Transition>,
+    pub warnings: Vec<String>,
+}
+
+impl ObservanceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ObservanceKind::Standard => "Standard",
+            ObservanceKind::Daylight => "Daylight",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ObservanceKind::Standard => "vtimezone-standard",
+            ObservanceKind::Daylight => "vtimezone-daylight",
+        }
+    }
+}
+
+impl Transition {
+    // Wall clock time just before and at the transition.
+    pub fn local_before(&self) -> NaiveDateTime {
+        self.utc + Duration::seconds(self.offset_from as i64)
+    }
+
+    pub fn local_after(&self) -> NaiveDateTime {
+        self.utc + Duration::seconds(self.offset_to as i64)
+    }
+}
+
+// Reports every VTIMEZONE of `icalendar`, listing the transitions of the
+// `years` years starting with `from_year`.
+pub fn inspect(
+    icalendar: &str,
+    jscalendar: &str,
+    from_year: i32,
+    years: i32,
+) -> Vec<TimeZoneReport> {
+    let components = contentline::components(icalendar);
+    let mapping = converted_zones(&components, jscalendar);
+    let until_year = from_year + years - 1;
+
+    let mut reports = Vec::new();
+    for timezone in components
+        .iter()
+        .filter(|component| component.name() == "VTIMEZONE")
+    {
+        let Some(tz_id) = value(timezone, "TZID") else {
+            continue;
+        };
+        let mut warnings = Vec::new();
+        let mut transitions = Vec::new();
+        for observance in components.iter().filter(|component| {
+            component
+                .path
+                .strip_prefix(timezone.path.as_str())
+                .is_some_and(|rest| rest.starts_with('/') && rest[1..].find('/').is_none())
+        }) {
+            let kind = match observance.name() {
+                "STANDARD" => ObservanceKind::Standard,
+                "DAYLIGHT" => ObservanceKind::Daylight,
+                _ => continue,
+            };
+            match observance_transitions(observance, kind, until_year) {
+                Ok(found) => transitions.extend(found),
+                Err(err) => warnings.push(format!(
+                    "{} observance on line {}: {}",
+                    kind.label(),
+                    observance.line,
+                    err
+                )),
+            }
+        }
+        transitions.sort_by_key(|transition| transition.utc);
+        if transitions.is_empty() && warnings.is_empty() {
+            warnings.push("The definition has no observances.".to_string());
+        }
+
+        let mapped = mapping
+            .iter()
+            .find(|(id, _)| *id == tz_id)
+            .map(|(_, zone)| zone.clone());
+        let compared = mapped
+            .as_deref()
+            .and_then(|zone| zone.parse::<chrono_tz::Tz>().ok())
+            .or_else(|| resolve(&tz_id));
+        match compared {
+            Some(zone) => warnings.extend(disagreements(&transitions, zone, from_year, until_year)),
+            None => warnings.push(
+                "Not an IANA or Windows time zone name, the definition cannot be checked."
+                    .to_string(),
+            ),
+        }
+
+        reports.push(TimeZoneReport {
+            line: timezone.line,
+            compared: compared.map(|zone| zone.name().to_string()),
+            transitions: transitions
+                .into_iter()
+                .filter(|transition| (from_year..=until_year).contains(&transition.utc.year()))
+                .collect(),
+            mapped,
+            warnings,
+            tz_id,
+        });
+    }
+    reports
+}
+
+pub fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    format!(
+        "UTC{}{:02}:{:02}",
+        sign,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+// TZIDs paired with the timeZone of the converted entries that use them.
+fn converted_zones(components: &[Component], jscalendar: &str) -> Vec<(String, String)> {
+    let Ok(root) = serde_json::from_str::<Value>(jscalendar) else {
+        return vec![];
+    };
+    let entries = match root.get("entries").and_then(Value::as_array) {
+        Some(entries) => entries.iter().collect::<Vec<_>>(),
+        None => vec![&root],
+    };
+    let mut mapping: Vec<(String, String)> = Vec::new();
+    for component in components {
+        let (Some(uid), Some(tz_id)) = (
+            value(component, "UID"),
+            component
+                .properties
+                .iter()
+                .find(|line| line.name == "DTSTART")
+                .and_then(|line| line.param("TZID")),
+        ) else {
+            continue;
+        };
+        let zone = entries
+            .iter()
+            .find(|entry| entry.get("uid").and_then(Value::as_str) == Some(uid.as_str()))
+            .and_then(|entry| entry.get("timeZone"))
+            .and_then(Value::as_str);
+        if let Some(zone) = zone {
+            if !mapping.iter().any(|(id, _)| id == tz_id) {
+                mapping.push((tz_id.to_string(), zone.to_string()));
+            }
+        }
+    }
+    mapping
+}
+
+fn resolve(tz_id: &str) -> Option<chrono_tz::Tz> {
+    let tz_id = tz_id.trim().trim_start_matches('/');
+    tz_id
+        .parse::<chrono_tz::Tz>()
+        .ok()
+        .or_else(|| repair::windows_to_iana(tz_id)?.parse().ok())
+}
+
+// Periods, sampled hourly, in which the definition and the database give
+// different offsets.
+fn disagreements(
+    transitions: &[Transition],
+    zone: chrono_tz::Tz,
+    from_year: i32,
+    until_year: i32,
+) -> Vec<String> {
+    let (Some(start), Some(end)) = (
+        NaiveDate::from_ymd_opt(from_year, 1, 1),
+        NaiveDate::from_ymd_opt(until_year + 1, 1, 1),
+    ) else {
+        return vec![];
+    };
+    let Some(first) = transitions.first() else {
+        return vec![];
+    };
+    let defined =
+        |at: NaiveDateTime| match transitions.partition_point(|transition| transition.utc <= at) {
+            0 => first.offset_from,
+            index => transitions[index - 1].offset_to,
+        };
+    let database = |at: NaiveDateTime| zone.offset_from_utc_datetime(&at).fix().local_minus_utc();
+
+    // (from, to, defined, database)
+    let mut periods: Vec<(NaiveDateTime, NaiveDateTime, i32, i32)> = Vec::new();
+    let mut at = start.and_time(Default::default());
+    let end = end.and_time(Default::default());
+    while at < end {
+        let (ours, theirs) = (defined(at), database(at));
+        if ours != theirs {
+            match periods.last_mut() {
+                Some(period) if period.1 == at && period.2 == ours && period.3 == theirs => {
+                    period.1 = at + Duration::hours(1);
+                }
+                _ => periods.push((at, at + Duration::hours(1), ours, theirs)),
+            }
+        }
+        at += Duration::hours(1);
+    }
+
+    let mut warnings = periods
+        .iter()
+        .take(MAX_MISMATCHES)
+        .map(|(from, to, ours, theirs)| {
+            format!(
+                "From {} to {} UTC the definition gives {} where the IANA database has {} for {}.",
+                from.format("%Y-%m-%d %H:%M"),
+                to.format("%Y-%m-%d %H:%M"),
+                format_offset(*ours),
+                format_offset(*theirs),
+                zone.name()
+            )
+        })
+        .collect::<Vec<_>>();
+    if periods.len() > MAX_MISMATCHES {
+        warnings.push(format!(
+            "And {} more periods of disagreement.",
+            periods.len() - MAX_MISMATCHES
+        ));
+    }
+    warnings
+}
+
+// Onsets of an observance up to the end of `until_year`: DTSTART, the yearly
+// RRULE and any RDATEs.
+fn observance_transitions(
+    observance: &Component,
+    kind: ObservanceKind,
+    until_year: i32,
+) -> Result<Vec<Transition>, String> {
+    let line = |name: &str| observance.properties.iter().find(|line| line.name == name);
+    let dtstart = line("DTSTART")
+        .and_then(|line| local_date_time(&line.value))
+        .ok_or("DTSTART is missing or not a local date-time.")?;
+    let offset_from = line("TZOFFSETFROM")
+        .and_then(|line| parse_offset(&line.value))
+        .ok_or("TZOFFSETFROM is missing or invalid.")?;
+    let offset_to = line("TZOFFSETTO")
+        .and_then(|line| parse_offset(&line.value))
+        .ok_or("TZOFFSETTO is missing or invalid.")?;
+    let name = line("TZNAME").map(ContentLine::text);
+
+    let mut onsets = vec![dtstart];
+    if let Some(rrule) = line("RRULE") {
+        onsets.extend(yearly_rule(&rrule.value, dtstart, offset_from, until_year)?);
+    }
+    for rdate in observance
+        .properties
+        .iter()
+        .filter(|line| line.name == "RDATE")
+    {
+        onsets.extend(rdate.value.split(',').filter_map(local_date_time));
+    }
+    onsets.sort();
+    onsets.dedup();
+
+    Ok(onsets
+        .into_iter()
+        .filter(|onset| onset.year() <= until_year)
+        .map(|onset| Transition {
+            kind,
+            name: name.clone(),
+            utc: onset - Duration::seconds(offset_from as i64),
+            offset_from,
+            offset_to,
+        })
+        .collect())
+}
+
+// The yearly rules time zones are written with: months, weekdays with an
+// optional ordinal, month days, INTERVAL, UNTIL and COUNT.
+fn yearly_rule(
+    rule: &str,
+    dtstart: NaiveDateTime,
+    offset_from: i32,
+    until_year: i32,
+) -> Result<Vec<NaiveDateTime>, String> {
+    let mut freq = None;
+    let mut interval = 1;
+    let mut months = vec![];
+    let mut weekdays: Vec<(Option<i32>, Weekday)> = vec![];
+    let mut month_days: Vec<i32> = vec![];
+    let mut until = None;
+    let mut count = None;
+    for part in rule.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let list = || value.split(',').map(str::trim);
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(value.trim().to_ascii_uppercase()),
+            "INTERVAL" => interval = value.trim().parse::<i32>().map_err(|_| invalid(part))?,
+            "BYMONTH" => {
+                months = list()
+                    .map(|month| month.parse::<u32>().map_err(|_| invalid(part)))
+                    .collect::<Result<_, _>>()?
+            }
+            "BYDAY" => {
+                weekdays = list()
+                    .map(|day| parse_weekday(day).ok_or_else(|| invalid(part)))
+                    .collect::<Result<_, _>>()?
+            }
+            "BYMONTHDAY" => {
+                month_days = list()
+                    .map(|day| day.parse::<i32>().map_err(|_| invalid(part)))
+                    .collect::<Result<_, _>>()?
+            }
+            "UNTIL" => {
+                let value = value.trim();
+                until = Some(match value.strip_suffix('Z') {
+                    Some(utc) => local_date_time(utc).ok_or_else(|| invalid(part))?,
+                    // Local times are read in the offset in effect before the onset.
+                    None => {
+                        local_date_time(value)
+                            .or_else(|| {
+                                contentline::parse_date(value)
+                                    .and_then(|date| date.and_hms_opt(23, 59, 59))
+                            })
+                            .ok_or_else(|| invalid(part))?
+                            - Duration::seconds(offset_from as i64)
+                    }
+                });
+            }
+            "COUNT" => count = Some(value.trim().parse::<usize>().map_err(|_| invalid(part))?),
+            "WKST" => {}
+            _ => return Err(format!("{} is not supported in time zone rules.", part)),
+        }
+    }
+    if freq.as_deref() != Some("YEARLY") || interval < 1 {
+        return Err("Only yearly rules are supported in time zone definitions.".to_string());
+    }
+    if months.is_empty() {
+        months.push(dtstart.month());
+    }
+
+    let mut onsets = Vec::new();
+    for year in (dtstart.year()..=until_year).step_by(interval as usize) {
+        for month in &months {
+            for day in month_matches(year, *month, &weekdays, &month_days, dtstart.day()) {
+                let onset = day.and_time(dtstart.time());
+                if onset < dtstart {
+                    continue;
+                }
+                if until.is_some_and(|until| onset - Duration::seconds(offset_from as i64) > until)
+                    || count.is_some_and(|count| onsets.len() >= count)
+                {
+                    return Ok(onsets);
+                }
+                onsets.push(onset);
+            }
+        }
+    }
+    Ok(onsets)
+}
+
+fn month_matches(
+    year: i32,
+    month: u32,
+    weekdays: &[(Option<i32>, Weekday)],
+    month_days: &[i32],
+    default_day: u32,
+) -> Vec<NaiveDate> {
+    let days = (1..=31)
+        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .collect::<Vec<_>>();
+    let in_month_days = |date: &NaiveDate| {
+        let last = days.len() as i32;
+        month_days.is_empty()
+            || month_days.iter().any(|day| {
+                let day = if *day < 0 { last + day + 1 } else { *day };
+                date.day() as i32 == day
+            })
+    };
+    let mut matches = if weekdays.is_empty() {
+        if month_days.is_empty() {
+            days.iter()
+                .filter(|date| date.day() == default_day)
+                .copied()
+                .collect()
+        } else {
+            days.iter()
+                .filter(|date| in_month_days(date))
+                .copied()
+                .collect()
+        }
+    } else {
+        let mut matches = Vec::new();
+        for (ordinal, weekday) in weekdays {
+            let same = days
+                .iter()
+                .filter(|date| date.weekday() == *weekday)
+                .copied()
+                .collect::<Vec<_>>();
+            match ordinal {
+                Some(ordinal) if *ordinal > 0 => {
+                    matches.extend(same.get(*ordinal as usize - 1).copied())
+                }
+                Some(ordinal) => matches.extend(
+                    same.len()
+                        .checked_sub(ordinal.unsigned_abs() as usize)
+                        .and_then(|index| same.get(index))
+                        .copied(),
+                ),
+                None => matches.extend(same),
+            }
+        }
+        matches.retain(|date| in_month_days(date));
+        matches
+    };
+    matches.sort();
+    matches.dedup();
+    matches
+}
+
+fn parse_weekday(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let (ordinal, day) = contentline::parse_byday(value)?;
+    let weekday = match day.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    if ordinal == Some(0) {
+        return None;
+    }
+    Some((ordinal, weekday))
+}
+
+// "+0100", "-0530" or "+013045".
+fn parse_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let (sign, digits) = match (value.strip_prefix('+'), value.strip_prefix('-')) {
+        (Some(digits), _) => (1, digits),
+        (_, Some(digits)) => (-1, digits),
+        _ => return None,
+    };
+    if !matches!(digits.len(), 4 | 6) || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let part = |range: std::ops::Range<usize>| digits.get(range)?.parse::<i32>().ok();
+    let seconds = part(0..2)? * 3600 + part(2..4)? * 60 + part(4..6).unwrap_or_default();
+    Some(sign * seconds)
+}
+
+fn local_date_time(value: &str) -> Option<NaiveDateTime> {
+    contentline::parse_datetime(value).filter(|_| !value.trim().ends_with('Z'))
+}
+
+fn invalid(part: &str) -> String {
+    format!("{} is not valid.", part)
+}
+
+fn value(component: &Component, name: &str) -> Option<String> {
+    component
+        .properties
+        .iter()
+        .find(|line| line.name == name)
+        .map(|line| line.value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_non_ascii_byday() {
+        let icalendar = "BEGIN:VCALENDAR\r\nBEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\nBEGIN:DAYLIGHT\r\nDTSTART:19810329T020000\r\nTZOFFSETFROM:+0100\r\nTZOFFSETTO:+0200\r\nRRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2S€U\r\nEND:DAYLIGHT\r\nEND:VTIMEZONE\r\nEND:VCALENDAR\r\n";
+        let reports = inspect(icalendar, "", 2024, 1);
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0]
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("BYDAY=2S€U")));
+        assert_eq!(parse_weekday("2S€U"), None);
+        assert_eq!(parse_weekday("0SU"), None);
+        assert_eq!(parse_weekday("-1su"), Some((Some(-1), Weekday::Sun)));
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::collections::BTreeMap;
+
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{ICalendarProperty, ICalendarValue},
+    Entry, Parser,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+
+const MAX_HOLIDAY_OCCURRENCES: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkingHours {
+    // Indexed by `Weekday::num_days_from_monday`.
+    pub days: [bool; 7],
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Holidays {
+    dates: BTreeMap<NaiveDate, String>,
+    // Calendar the dates were read from.
+    source: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverlayMode {
+    #[default]
+    Annotate,
+    HideFlagged,
+    OnlyFlagged,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        WorkingHours {
+            days: [true, true, true, true, true, false, false],
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        }
+    }
+}
+
+impl WorkingHours {
+    pub fn contains(&self, start: NaiveDateTime, end: NaiveDateTime) -> bool {
+        self.days[start.weekday().num_days_from_monday() as usize]
+            && start.date() == end.date()
+            && start.time() >= self.start
+            && end.time() <= self.end
+    }
+}
+
+impl Holidays {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let source = source.trim();
+        if source.is_empty() {
+            return Ok(Holidays::default());
+        }
+
+        let icalendar = match Parser::new(source).entry() {
+            Entry::ICalendar(icalendar) => icalendar,
+            Entry::VCard(_) => {
+                return Err("The holiday calendar must be an iCalendar file, not a vCard.".into())
+            }
+            Entry::InvalidLine(text) => return Err(format!("Invalid line found: {}", text)),
+            _ => return Err("The holiday calendar could not be parsed.".into()),
+        };
+
+        let mut dates = BTreeMap::new();
+        for event in icalendar
+            .expand_dates(Tz::Floating, MAX_HOLIDAY_OCCURRENCES)
+            .events
+        {
+            let comp_id = event.comp_id as usize;
+            let Some(event) = event.try_into_date_time() else {
+                continue;
+            };
+            let name = icalendar
+                .components
+                .get(comp_id)
+                .and_then(|component| {
+                    component
+                        .entries
+                        .iter()
+                        .find(|entry| entry.name == ICalendarProperty::Summary)
+                })
+                .and_then(|entry| match entry.values.first() {
+                    Some(ICalendarValue::Text(text)) => Some(text.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| "Holiday".to_string());
+
+            // All-day holidays end at midnight of the following day, which is exclusive.
+            let first = event.start.naive_local().date();
+            let mut last = event.end.naive_local();
+            if last.time() == NaiveTime::MIN && last.date() > first {
+                last -= chrono::Duration::days(1);
+            }
+            for date in first.iter_days().take_while(|date| *date <= last.date()) {
+                dates.entry(date).or_insert_with(|| name.clone());
+            }
+        }
+
+        Ok(Holidays {
+            dates,
+            source: source.to_string(),
+        })
+    }
+
+    pub fn find(&self, start: NaiveDateTime, end: NaiveDateTime) -> Option<&str> {
+        let last = if end.time() == NaiveTime::MIN && end.date() > start.date() {
+            end.date().pred_opt()?
+        } else {
+            end.date().max(start.date())
+        };
+        self.dates
+            .range(start.date()..=last)
+            .next()
+            .map(|(_, name)| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.dates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dates.is_empty()
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl OverlayMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            OverlayMode::Annotate => "annotate",
+            OverlayMode::HideFlagged => "hide",
+            OverlayMode::OnlyFlagged => "only",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "hide" => OverlayMode::HideFlagged,
+            "only" => OverlayMode::OnlyFlagged,
+            _ => OverlayMode::Annotate,
+        }
+    }
+
+    pub fn includes(&self, flagged: bool) -> bool {
+        match self {
+            OverlayMode::Annotate => true,
+            OverlayMode::HideFlagged => !flagged,
+            OverlayMode::OnlyFlagged => flagged,
+        }
+    }
+}
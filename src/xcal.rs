@@ -0,0 +1,273 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+// xCal (RFC 6321) and xCard (RFC 6351), the XML forms of iCalendar and vCard.
+// They follow the same property model as jCal and jCard, so they are written
+// from it.
+
+use serde_json::{Map, Value};
+
+use crate::{html::escape, jcal};
+
+const ICALENDAR_NS: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+const VCARD_NS: &str = "urn:ietf:params:xml:ns:vcard-4.0";
+
+// Order of the recurrence rule parts in the xCal schema.
+const RECUR_PARTS: &[&str] = &[
+    "freq",
+    "until",
+    "count",
+    "interval",
+    "bysecond",
+    "byminute",
+    "byhour",
+    "byday",
+    "bymonthday",
+    "byyearday",
+    "byweekno",
+    "bymonth",
+    "bysetpos",
+    "wkst",
+];
+
+// Writes iCalendar text as xCal or vCard text as xCard.
+pub fn from_text(source: &str) -> String {
+    let components = jcal::components(source);
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    if components
+        .first()
+        .and_then(|component| component.as_array()?.first()?.as_str())
+        == Some("vcard")
+    {
+        out.push_str(&format!("<vcards xmlns=\"{}\">\n", VCARD_NS));
+        for component in &components {
+            write_vcard(&mut out, component);
+        }
+        out.push_str("</vcards>\n");
+    } else {
+        out.push_str(&format!("<icalendar xmlns=\"{}\">\n", ICALENDAR_NS));
+        for component in &components {
+            write_component(&mut out, component, 1);
+        }
+        out.push_str("</icalendar>\n");
+    }
+    out
+}
+
+fn write_component(out: &mut String, component: &Value, depth: usize) {
+    let Some([name, properties, rest @ ..]) = component.as_array().map(Vec::as_slice) else {
+        return;
+    };
+    let name = name.as_str().unwrap_or_default();
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!("{}<{}>\n", indent, name));
+    if let Some(properties) = properties.as_array().filter(|items| !items.is_empty()) {
+        out.push_str(&format!("{}  <properties>\n", indent));
+        for property in properties {
+            write_property(out, property, depth + 2, false);
+        }
+        out.push_str(&format!("{}  </properties>\n", indent));
+    }
+    if let Some(components) = rest
+        .first()
+        .and_then(Value::as_array)
+        .filter(|items| !items.is_empty())
+    {
+        out.push_str(&format!("{}  <components>\n", indent));
+        for component in components {
+            write_component(out, component, depth + 2);
+        }
+        out.push_str(&format!("{}  </components>\n", indent));
+    }
+    out.push_str(&format!("{}</{}>\n", indent, name));
+}
+
+// Grouped properties are written together, after the ungrouped ones.
+fn write_vcard(out: &mut String, component: &Value) {
+    let properties = component
+        .get(1)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut groups: Vec<(&str, Vec<&Value>)> = Vec::new();
+    out.push_str("  <vcard>\n");
+    for property in properties {
+        match property
+            .get(1)
+            .and_then(|parameters| parameters.get("group")?.as_str())
+        {
+            Some(group) => match groups.iter_mut().find(|(name, _)| *name == group) {
+                Some((_, members)) => members.push(property),
+                None => groups.push((group, vec![property])),
+            },
+            None => write_property(out, property, 2, true),
+        }
+    }
+    for (group, members) in groups {
+        out.push_str(&format!("    <group name=\"{}\">\n", escape(group)));
+        for property in members {
+            write_property(out, property, 3, true);
+        }
+        out.push_str("    </group>\n");
+    }
+    out.push_str("  </vcard>\n");
+}
+
+fn write_property(out: &mut String, property: &Value, depth: usize, vcard: bool) {
+    let Some([name, parameters, kind, values @ ..]) = property.as_array().map(Vec::as_slice) else {
+        return;
+    };
+    let name = name.as_str().unwrap_or_default();
+    let kind = kind.as_str().unwrap_or("unknown");
+    let mut line = format!("{}<{}>", "  ".repeat(depth), name);
+    if let Some(parameters) = parameters.as_object() {
+        write_parameters(&mut line, parameters, vcard);
+    }
+    for value in values {
+        match value {
+            Value::Object(rule) => write_recur(&mut line, rule),
+            Value::Array(parts) => write_structured(&mut line, name, kind, parts, vcard),
+            value => write_value(&mut line, kind, value, vcard),
+        }
+    }
+    line.push_str(&format!("</{}>\n", name));
+    out.push_str(&line);
+}
+
+fn write_parameters(out: &mut String, parameters: &Map<String, Value>, vcard: bool) {
+    let parameters = parameters
+        .iter()
+        .filter(|(name, _)| !(vcard && name.as_str() == "group"))
+        .collect::<Vec<_>>();
+    if parameters.is_empty() {
+        return;
+    }
+    out.push_str("<parameters>");
+    for (name, value) in parameters {
+        let kind = match name.as_str() {
+            "delegated-to" | "delegated-from" | "member" | "sent-by" => "cal-address",
+            "altrep" | "dir" => "uri",
+            "pref" if vcard => "integer",
+            _ => "text",
+        };
+        out.push_str(&format!("<{}>", name));
+        match value {
+            Value::Array(values) => {
+                for value in values {
+                    write_value(out, kind, value, vcard);
+                }
+            }
+            value => write_value(out, kind, value, vcard),
+        }
+        out.push_str(&format!("</{}>", name));
+    }
+    out.push_str("</parameters>");
+}
+
+fn write_value(out: &mut String, kind: &str, value: &Value, vcard: bool) {
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Bool(value) => value.to_string(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    };
+    if kind == "period" {
+        if let Some((start, end)) = text.split_once('/') {
+            let end_kind = if end.trim_start_matches(['-', '+']).starts_with('P') {
+                "duration"
+            } else {
+                "end"
+            };
+            out.push_str(&format!(
+                "<period><start>{}</start><{2}>{}</{2}></period>",
+                escape(start),
+                escape(end),
+                end_kind
+            ));
+            return;
+        }
+    }
+    // xCard keeps the basic date format of vCard and names the element after
+    // the form the value takes.
+    let (kind, text) = match kind {
+        "date-and-or-time" if text.starts_with('T') => ("time", text),
+        "date-and-or-time" if text.contains('T') => ("date-time", text),
+        "date-and-or-time" => ("date", text),
+        kind => (kind, text),
+    };
+    let text = if vcard
+        && matches!(
+            kind,
+            "date" | "date-time" | "time" | "timestamp" | "utc-offset"
+        ) {
+        jcal::basic_date(&text)
+    } else {
+        text
+    };
+    out.push_str(&format!("<{0}>{1}</{0}>", kind, escape(&text)));
+}
+
+fn write_structured(out: &mut String, name: &str, kind: &str, parts: &[Value], vcard: bool) {
+    let fields: &[&str] = match name {
+        "n" => &["surname", "given", "additional", "prefix", "suffix"],
+        "adr" => &[
+            "pobox", "ext", "street", "locality", "region", "code", "country",
+        ],
+        "gender" => &["sex", "identity"],
+        "request-status" => &["code", "description", "data"],
+        "geo" => &["latitude", "longitude"],
+        "clientpidmap" => &["sourceid", "uri"],
+        _ => &[],
+    };
+    for (index, part) in parts.iter().enumerate() {
+        let Some(field) = fields.get(index) else {
+            // Components without a name of their own, such as the units of
+            // ORG, are repeated values of the property type.
+            write_value(out, kind, part, vcard);
+            continue;
+        };
+        let values = match part {
+            Value::Array(values) => values.iter().collect::<Vec<_>>(),
+            value => vec![value],
+        };
+        for value in values {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            if text.is_empty() {
+                out.push_str(&format!("<{}/>", field));
+            } else {
+                out.push_str(&format!("<{0}>{1}</{0}>", field, escape(&text)));
+            }
+        }
+    }
+}
+
+fn write_recur(out: &mut String, rule: &Map<String, Value>) {
+    let mut parts = rule.iter().collect::<Vec<_>>();
+    parts.sort_by_key(|(key, _)| {
+        RECUR_PARTS
+            .iter()
+            .position(|part| key.eq_ignore_ascii_case(part))
+            .unwrap_or(RECUR_PARTS.len())
+    });
+    out.push_str("<recur>");
+    for (key, value) in parts {
+        let values = match value {
+            Value::Array(values) => values.iter().collect::<Vec<_>>(),
+            value => vec![value],
+        };
+        for value in values {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            out.push_str(&format!("<{0}>{1}</{0}>", key, escape(&text)));
+        }
+    }
+    out.push_str("</recur>");
+}